@@ -2,17 +2,33 @@ use std::collections::HashMap;
 
 use eframe::egui;
 
-use crate::application::{AppSettings, TreeFileService};
+use crate::application::{default_settings_dir, AppSettings, TreeFileService};
 use crate::core::i18n::{self as i18n, Texts};
-use crate::core::layout::LayoutEngine;
-use crate::core::tree::{FamilyTree, PersonId};
-use crate::infrastructure::read_image_dimensions;
-use crate::infrastructure::MultiFormatTreeRepository;
+use crate::core::layout::{GridSettings, LayoutConfig, LayoutDirection, LayoutEngine, RelativePlacement};
+use crate::core::tree::{FamilyTree, PersonId, CURRENT_FORMAT_VERSION};
+use crate::core::undo::UndoHistory;
+use crate::infrastructure::{load_system_cjk_font, read_image_dimensions};
+use crate::infrastructure::{ActivityLogStore, ErrorLogFile, MultiFormatTreeRepository, OverwriteWarning, RecoverySnapshot, RecoverySnapshotStore, TreeBackupStore, TreeFileLock, TreeFileLockError};
+use crate::ui::shortcuts::{
+    shortcut_button_label, CAMERA_BOOKMARK_SHORTCUTS, SHORTCUT_GO_HOME, SHORTCUT_NEXT_SIDE_TAB, SHORTCUT_PREVIOUS_SIDE_TAB, SHORTCUT_SELECTION_BACK,
+    SHORTCUT_SELECTION_FORWARD, SHORTCUT_ZOOM_RESET,
+};
+use crate::ui::theme::{font_definitions, high_contrast_visuals, scaled_text_styles};
 use crate::ui::{
-    CanvasRenderer, CanvasState, EventEditorState, EventsTabRenderer, FamiliesTabRenderer,
-    FamilyEditorState, FileMenuRenderer, FileState, HelpMenuRenderer, LogLevel, LogState,
-    PersonEditorState, PersonsTabRenderer, RelationEditorState, SettingsTabRenderer, SideTab,
-    UiState, ViewMenuRenderer,
+    AnnotationEditorState, AnnotationsTabRenderer, ArrangePreview, CameraAnimation, CanvasRenderer, CanvasState,
+    DateNormalizationDialogRenderer, DateNormalizationState, DetailPanelRenderer, EditMenuRenderer,
+    ErrorDialog, EventEditorState, EventsTabRenderer, ExportDialogRenderer, ExportState,
+    FamiliesTabRenderer, FamilyEditorState, FileMenuRenderer, FileState, FindReplaceDialogRenderer,
+    FindReplaceState, HelpMenuRenderer, LockConflict, LogLevel, LogPanelRenderer, LogState,
+    MacroDialogRenderer, MacroRecorderState, OverwriteConfirm, PanZoomHandler, PendingFileAction,
+    PersonEditorState, PersonsTabRenderer, PhotoCheckDialogRenderer, PhotoCheckState,
+    PhotoEditDialogRenderer, PhotoEditDialogState, PlaceEditorState, PlaceMapRenderer,
+    PlaceMapState, PlacesTabRenderer, RecoveryState, RelationCleanupDialogRenderer,
+    RelationCleanupState, RelationEditorState, SampleDataDialogRenderer, SampleDataState,
+    SessionState, SettingsTabRenderer, SideTab, SlideshowRenderer, SlideshowState,
+    SpouseEditorRenderer, StatsTabRenderer, StatusBarRenderer, ToastRenderer, ToastState,
+    UiFontFamily, UiState, ViewMenuRenderer, MAX_BACKUP_KEEP, MAX_CANVAS_ZOOM, MAX_UI_FONT_SIZE,
+    MIN_CANVAS_ZOOM, MIN_UI_FONT_SIZE,
 };
 
 // 定数
@@ -22,14 +38,31 @@ pub const SPOUSE_LINE_OFFSET: f32 = 2.0;
 
 pub struct App {
     pub tree: FamilyTree,
+    /// 一括操作の共通の履歴（元に戻す・やり直す）
+    pub undo_history: UndoHistory,
     
     // 状態管理（機能ごとに分離）
     pub person_editor: PersonEditorState,
     pub relation_editor: RelationEditorState,
     pub family_editor: FamilyEditorState,
     pub event_editor: EventEditorState,
+    pub place_editor: PlaceEditorState,
+    pub annotation_editor: AnnotationEditorState,
     pub canvas: CanvasState,
     pub file: FileState,
+    pub toasts: ToastState,
+    pub export: ExportState,
+    pub find_replace: FindReplaceState,
+    pub relation_cleanup: RelationCleanupState,
+    pub photo_check: PhotoCheckState,
+    pub date_normalization: DateNormalizationState,
+    pub macro_recorder: MacroRecorderState,
+    pub sample_data: SampleDataState,
+    pub recovery: RecoveryState,
+    pub slideshow: SlideshowState,
+    pub place_map: PlaceMapState,
+    pub photo_edit: PhotoEditDialogState,
+    pub session: SessionState,
     pub ui: UiState,
     pub log: LogState,
 }
@@ -38,12 +71,28 @@ impl Default for App {
     fn default() -> Self {
         let mut app = Self {
             tree: FamilyTree::default(),
+            undo_history: UndoHistory::default(),
             person_editor: PersonEditorState::default(),
             relation_editor: RelationEditorState::new(),
             family_editor: FamilyEditorState::new(),
             event_editor: EventEditorState::default(),
+            place_editor: PlaceEditorState::default(),
+            annotation_editor: AnnotationEditorState::default(),
             canvas: CanvasState::default(),
             file: FileState::new(),
+            toasts: ToastState::default(),
+            export: ExportState::default(),
+            find_replace: FindReplaceState::default(),
+            relation_cleanup: RelationCleanupState::default(),
+            photo_check: PhotoCheckState::default(),
+            date_normalization: DateNormalizationState::default(),
+            macro_recorder: MacroRecorderState::default(),
+            sample_data: SampleDataState::default(),
+            recovery: RecoveryState::default(),
+            slideshow: SlideshowState::default(),
+            place_map: PlaceMapState::default(),
+            photo_edit: PhotoEditDialogState::default(),
+            session: SessionState::default(),
             ui: UiState::default(),
             log: LogState::default(),
         };
@@ -52,8 +101,19 @@ impl Default for App {
         if let Err(e) = app.log.set_log_file("logs") {
             eprintln!("Failed to create log directory: {}", e);
         }
+        app.log.set_error_log(ErrorLogFile::in_dir(&default_settings_dir()));
+        if let Err(e) = app.log.set_activity_store(ActivityLogStore::in_dir(&default_settings_dir())) {
+            eprintln!("Failed to load activity log: {}", e);
+        }
 
         app.load_settings_on_startup();
+        app.mark_saved();
+        app.session.baseline = app.tree.clone();
+
+        // 前回が異常終了ならスナップショットが残っている
+        let recovery_store = RecoverySnapshotStore::in_temp_dir();
+        app.recovery.pending_restore = recovery_store.find_leftover();
+        app.recovery.store = Some(recovery_store);
         
         let t = |key: &str| Texts::get(key, app.ui.language);
         app.log.add(t("log_app_started"), LogLevel::Debug);
@@ -67,6 +127,29 @@ impl App {
         self.canvas.show_grid = settings.show_grid;
         self.canvas.grid_size = settings.grid_size.clamp(10.0, 200.0);
         self.ui.node_color_theme = settings.node_color_theme;
+        self.ui.layout_direction = settings.layout_direction;
+        self.ui.edge_style = settings.edge_style;
+        self.ui.bezier_control_offset = settings.bezier_control_offset.clamp(0.0, 200.0);
+        self.ui.semantic_zoom = settings.semantic_zoom;
+        self.ui.show_step_relations = settings.show_step_relations;
+        self.ui.other_gender_color = settings.other_gender_color;
+        self.ui.presume_deceased = settings.presume_deceased;
+        self.ui.presumed_deceased_years = settings.presumed_deceased_years;
+        self.ui.show_all_notes = settings.show_all_notes;
+        self.ui.show_life_years = settings.show_life_years;
+        self.ui.show_detail_panel = settings.show_detail_panel;
+        self.ui.high_contrast_ui = settings.high_contrast_ui;
+        self.ui.ui_font_family = settings.ui_font_family;
+        self.ui.ui_font_size = settings.ui_font_size.clamp(MIN_UI_FONT_SIZE, MAX_UI_FONT_SIZE);
+        self.canvas.grid = GridSettings {
+            snap_strength: settings.grid.snap_strength.clamp(0.0, 1.0),
+            ..settings.grid
+        };
+        self.canvas.show_rulers = settings.show_rulers;
+        self.ui.backup_dir = settings.backup_dir;
+        self.ui.backup_keep = settings.backup_keep.clamp(1, MAX_BACKUP_KEEP);
+        self.relation_editor.relation_kind = settings.new_person_defaults.relation_kind.clone();
+        self.ui.new_person_defaults = settings.new_person_defaults;
     }
 
     fn collect_settings(&self) -> AppSettings {
@@ -75,6 +158,25 @@ impl App {
             show_grid: self.canvas.show_grid,
             grid_size: self.canvas.grid_size,
             node_color_theme: self.ui.node_color_theme,
+            layout_direction: self.ui.layout_direction,
+            edge_style: self.ui.edge_style,
+            bezier_control_offset: self.ui.bezier_control_offset,
+            semantic_zoom: self.ui.semantic_zoom,
+            show_step_relations: self.ui.show_step_relations,
+            other_gender_color: self.ui.other_gender_color,
+            presume_deceased: self.ui.presume_deceased,
+            presumed_deceased_years: self.ui.presumed_deceased_years,
+            show_all_notes: self.ui.show_all_notes,
+            show_life_years: self.ui.show_life_years,
+            show_detail_panel: self.ui.show_detail_panel,
+            high_contrast_ui: self.ui.high_contrast_ui,
+            ui_font_family: self.ui.ui_font_family,
+            ui_font_size: self.ui.ui_font_size,
+            grid: self.canvas.grid,
+            show_rulers: self.canvas.show_rulers,
+            backup_dir: self.ui.backup_dir.clone(),
+            backup_keep: self.ui.backup_keep,
+            new_person_defaults: self.ui.new_person_defaults.clone(),
         }
     }
 
@@ -114,10 +216,45 @@ impl App {
         }
     }
 
-    fn set_error_status_and_log(&mut self, status_prefix: &str, error: &str) {
+    /// 失敗をステータス・ログに残し、ユーザーが確認するまで閉じないダイアログで知らせる
+    pub(crate) fn set_error_status_and_log(&mut self, status_prefix: &str, error: &str) {
         let message = format!("{status_prefix}: {error}");
-        self.file.status = message.clone();
+        self.toasts.error(message.clone());
         self.log.add(message, LogLevel::Error);
+        self.ui.error_dialog = Some(ErrorDialog {
+            title: status_prefix.to_string(),
+            message: error.to_string(),
+        });
+    }
+
+    fn render_error_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.ui.error_dialog.clone() else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let response = egui::Modal::new(egui::Id::new("error_dialog")).show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.heading(egui::RichText::new(&dialog.title).color(LogLevel::Error.color()));
+            ui.add_space(6.0);
+            ui.label(&dialog.message);
+            if let Some(path) = self.log.error_log.as_ref().map(|log| log.path().display().to_string()) {
+                ui.add_space(6.0);
+                ui.weak(format!("{}: {path}", t("error_log_location")));
+            }
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("copy")).clicked() {
+                    ui.ctx().copy_text(format!("{}: {}", dialog.title, dialog.message));
+                }
+                ui.button(t("close")).clicked()
+            })
+            .inner
+        });
+        if response.inner || response.should_close() {
+            self.ui.error_dialog = None;
+        }
     }
 
     pub(crate) fn visible_canvas_left_top(&self) -> (f32, f32) {
@@ -131,22 +268,353 @@ impl App {
         (world_position.x, world_position.y)
     }
 
-    pub fn save(&mut self) {
+    /// 指定位置（ワールド座標）を中心に、夫婦と子のひな形を挿入して子を選択する
+    pub(crate) fn insert_couple_with_child_template(&mut self, center: (f32, f32)) {
         let lang = self.ui.language;
         let t = |key: &str| Texts::get(key, lang);
-        let service = TreeFileService::new(MultiFormatTreeRepository::new());
+        let (_, _, child) = self.tree.insert_couple_with_child(center, t("template_father"), t("template_mother"), t("template_child"));
 
+        self.person_editor.selected = Some(child);
+        self.person_editor.selected_ids = vec![child];
+        self.load_selected_person_into_form(child);
+        self.toasts.info(t("couple_with_child_inserted"));
+        self.log.add(t("couple_with_child_inserted"), LogLevel::Debug);
+    }
+
+    /// 現在のファイルパスに保存し、成功したかを返す
+    pub fn save(&mut self) -> bool {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let repository = MultiFormatTreeRepository::new();
+
+        // 初めて保存するパスでは、別の形式・別の家系図のファイルを黙って上書きしない
+        if self.file.overwrite_checked_path.as_deref() != Some(self.file.file_path.as_str())
+            && let Some(warning) = repository.check_overwrite(&self.file.file_path, &self.tree)
+        {
+            self.log.add(format!("{}: {}", t("log_overwrite_warning"), self.file.file_path), LogLevel::Warning);
+            self.file.overwrite_confirm = Some(OverwriteConfirm {
+                warning,
+                previous_path: self.file.file_path.clone(),
+                pending_action: None,
+            });
+            return false;
+        }
+        let service = TreeFileService::new(repository);
+
+        if !self.ensure_file_lock() {
+            if let Some(conflict) = &mut self.file.lock_conflict {
+                conflict.retry_save = true;
+            }
+            return false;
+        }
         if let Err(error) = service.save_tree(&self.file.file_path, &self.tree) {
             self.set_error_status_and_log(&t("save_error"), &error.to_string());
-            return;
+            return false;
         }
+        self.mark_saved();
+        self.file.overwrite_checked_path = Some(self.file.file_path.clone());
+        self.file.last_saved_at = Some(std::time::Instant::now());
+        self.file.detected_format = MultiFormatTreeRepository::sniff_format(&self.file.file_path);
 
-        self.file.status = format!("{}: {}", t("saved"), self.file.file_path);
+        self.toasts.info(format!("{}: {}", t("saved"), self.file.file_path));
         self.log
             .add(
                 format!("{}: {}", t("log_file_saved"), self.file.file_path),
                 LogLevel::Debug,
             );
+        self.write_secondary_backup();
+        true
+    }
+
+    /// 予備のバックアップ先が設定されていれば、保存したファイルの日時付きコピーを書き込む
+    ///
+    /// コピーに失敗しても保存自体は成功しているので、警告だけ出す。
+    fn write_secondary_backup(&mut self) {
+        if self.ui.backup_dir.trim().is_empty() {
+            return;
+        }
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let store = TreeBackupStore::new(self.ui.backup_dir.trim(), self.ui.backup_keep);
+        match store.back_up(std::path::Path::new(&self.file.file_path)) {
+            Ok(path) => self
+                .log
+                .add(format!("{}: {}", t("log_backup_written"), path.display()), LogLevel::Debug),
+            Err(error) => {
+                let message = format!("{}: {}", t("backup_failed"), error);
+                self.toasts.warning(message.clone());
+                self.log.add(message, LogLevel::Warning);
+            }
+        }
+    }
+
+    /// 現在のファイルのロックを持っていなければ取得し、他のインスタンスが持っていれば確認ダイアログを出して false を返す
+    ///
+    /// 読み取り専用のドライブなどでロックファイルを作れない場合は、警告だけ残して続行する。
+    fn ensure_file_lock(&mut self) -> bool {
+        if self
+            .file
+            .lock
+            .as_ref()
+            .is_some_and(|lock| lock.tree_path() == self.file.file_path && lock.is_held())
+        {
+            return true;
+        }
+        self.file.lock = None;
+        match TreeFileLock::acquire(&self.file.file_path) {
+            Ok(lock) => {
+                self.file.lock = Some(lock);
+                true
+            }
+            Err(TreeFileLockError::HeldBy(owner)) => {
+                self.log.add(
+                    format!("{}: {} ({})", Texts::get("log_file_locked", self.ui.language), self.file.file_path, owner),
+                    LogLevel::Warning,
+                );
+                self.file.lock_conflict = Some(LockConflict { owner, retry_save: false });
+                false
+            }
+            Err(error) => {
+                self.log.add(
+                    format!("{}: {}", Texts::get("file_lock_error", self.ui.language), error),
+                    LogLevel::Warning,
+                );
+                true
+            }
+        }
+    }
+
+    /// 他のインスタンスがファイルを開いていることを知らせ、ロックを引き継ぐか確認する
+    fn render_file_lock_dialog(&mut self, ctx: &egui::Context) {
+        let Some(conflict) = self.file.lock_conflict.clone() else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut take_over = false;
+        let response = egui::Modal::new(egui::Id::new("file_lock_dialog")).show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.heading(egui::RichText::new(t("file_locked_title")).color(LogLevel::Warning.color()));
+            ui.add_space(6.0);
+            ui.label(if conflict.retry_save { t("file_locked_save_message") } else { t("file_locked_open_message") });
+            ui.add_space(6.0);
+            ui.label(format!("{}: {}", t("file_locked_by"), conflict.owner));
+            ui.weak(t("file_locked_stale_hint"));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let take_over_label = if conflict.retry_save { t("take_over_lock_and_save") } else { t("take_over_lock") };
+                if ui.button(take_over_label).clicked() {
+                    take_over = true;
+                }
+                let dismiss_label = if conflict.retry_save { t("cancel") } else { t("continue_without_lock") };
+                if ui.button(dismiss_label).clicked() {
+                    ui.close();
+                }
+            });
+        });
+
+        if take_over {
+            self.file.lock_conflict = None;
+            match TreeFileLock::take_over(&self.file.file_path) {
+                Ok(lock) => {
+                    self.file.lock = Some(lock);
+                    if conflict.retry_save {
+                        self.save();
+                    }
+                }
+                Err(error) => self.set_error_status_and_log(&t("file_lock_error"), &error.to_string()),
+            }
+        } else if response.should_close() {
+            self.file.lock_conflict = None;
+        }
+    }
+
+    /// 保存先の既存ファイルの形式や中身を示し、上書きするか確認する
+    fn render_overwrite_dialog(&mut self, ctx: &egui::Context) {
+        let Some(confirm) = self.file.overwrite_confirm.clone() else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut overwrite = false;
+        let response = egui::Modal::new(egui::Id::new("overwrite_dialog")).show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.heading(egui::RichText::new(t("overwrite_title")).color(LogLevel::Warning.color()));
+            ui.add_space(6.0);
+            ui.label(&self.file.file_path);
+            ui.add_space(6.0);
+            match &confirm.warning {
+                OverwriteWarning::UnknownContent => {
+                    ui.label(t("overwrite_unknown_content"));
+                }
+                OverwriteWarning::DifferentFormat { existing, target } => {
+                    ui.label(t("overwrite_different_format"));
+                    ui.label(format!("{}: {} → {}", t("overwrite_formats"), existing.label(), target.label()));
+                }
+                OverwriteWarning::Unreadable { error } => {
+                    ui.label(t("overwrite_unreadable"));
+                    ui.label(egui::RichText::new(error).weak());
+                }
+                OverwriteWarning::OtherTree { persons, names } => {
+                    ui.label(t("overwrite_other_tree"));
+                    ui.label(format!("{}: {} ({}…)", t("overwrite_existing_persons"), persons, names.join(", ")));
+                }
+            }
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("overwrite_anyway")).clicked() {
+                    overwrite = true;
+                }
+                if ui.button(t("cancel")).clicked() {
+                    ui.close();
+                }
+            });
+        });
+
+        if overwrite {
+            self.file.overwrite_confirm = None;
+            self.file.overwrite_checked_path = Some(self.file.file_path.clone());
+            // 保存を待っていた操作を続ける
+            if self.save()
+                && let Some(action) = confirm.pending_action
+            {
+                self.run_file_action(action, ctx);
+            }
+        } else if response.should_close() {
+            self.file.overwrite_confirm = None;
+            self.file.file_path = confirm.previous_path;
+        }
+    }
+
+    /// 現在の内容を保存済みとして記録する
+    pub(crate) fn mark_saved(&mut self) {
+        self.file.saved_fingerprint = self.tree.fingerprint();
+        self.file.current_fingerprint = self.file.saved_fingerprint;
+        self.file.dirty = false;
+
+        // 保存済みの内容は復旧用スナップショットが不要
+        self.recovery.last_fingerprint = self.file.saved_fingerprint;
+        self.recovery.mutations_since_snapshot = 0;
+        if self.recovery.pending_restore.is_none() {
+            self.clear_recovery_snapshot();
+        }
+    }
+
+    /// 編集を確定させうる入力があったフレームでのみ、保存済みの内容と比べて未保存フラグを更新する
+    ///
+    /// 指紋の計算は家系図全体をたどるため、ポインタの移動やホイールだけのフレームでは行わない。
+    /// 編集は同じフレームの UI 処理の中で反映されるので、フレームの最後に呼び出す。
+    fn update_dirty_flag(&mut self, ctx: &egui::Context) {
+        if !ctx.input(has_edit_input) {
+            return;
+        }
+        let fingerprint = self.tree.fingerprint();
+        self.file.dirty = fingerprint != self.file.saved_fingerprint;
+        if fingerprint != self.file.current_fingerprint {
+            self.file.current_fingerprint = fingerprint;
+            // 編集の件数などの表示は次のフレームで更新する
+            ctx.request_repaint();
+        }
+
+        // 復元の確認中は前回のスナップショットを上書きしない
+        if self.recovery.pending_restore.is_none() && self.recovery.observe(fingerprint) {
+            self.write_recovery_snapshot();
+        }
+    }
+
+    fn write_recovery_snapshot(&mut self) {
+        let Some(store) = &mut self.recovery.store else {
+            return;
+        };
+        let snapshot = RecoverySnapshot {
+            saved_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            file_path: self.file.file_path.clone(),
+            pid: std::process::id(),
+            tree: self.tree.clone(),
+        };
+        // 開いているファイルが変わっていれば保存先も切り替える
+        if let Err(e) = store.switch_source(&self.file.file_path).and_then(|()| store.save(&snapshot)) {
+            let t = |key: &str| Texts::get(key, self.ui.language);
+            self.log.add(format!("{}: {}", t("recovery_snapshot_error"), e), LogLevel::Warning);
+        }
+    }
+
+    fn clear_recovery_snapshot(&mut self) {
+        if let Some(store) = &mut self.recovery.store
+            && let Err(e) = store.switch_source(&self.file.file_path).and_then(|()| store.clear())
+        {
+            let t = |key: &str| Texts::get(key, self.ui.language);
+            self.log.add(format!("{}: {}", t("recovery_snapshot_error"), e), LogLevel::Warning);
+        }
+    }
+
+    /// 前回異常終了したときのスナップショットを復元するか確認する
+    fn render_recovery_dialog(&mut self, ctx: &egui::Context) {
+        let Some((_, snapshot)) = &self.recovery.pending_restore else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut restore = None;
+        egui::Modal::new(egui::Id::new("recovery_dialog")).show(ctx, |ui| {
+            ui.set_max_width(420.0);
+            ui.heading(t("recovery_title"));
+            ui.add_space(6.0);
+            ui.label(t("recovery_message"));
+            ui.add_space(6.0);
+            ui.label(format!("{}: {}", t("recovery_saved_at"), snapshot.saved_at));
+            let file = if snapshot.file_path.is_empty() { t("untitled") } else { snapshot.file_path.clone() };
+            ui.label(format!("{}: {}", t("current_file"), file));
+            ui.label(format!("{}: {}", t("persons"), snapshot.tree.persons.len()));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("recovery_restore")).clicked() {
+                    restore = Some(true);
+                }
+                if ui.button(t("recovery_discard")).clicked() {
+                    restore = Some(false);
+                }
+            });
+        });
+
+        let Some(restore) = restore else {
+            return;
+        };
+        if let Some((leftover, snapshot)) = self.recovery.pending_restore.take() {
+            if restore {
+                // 復元した内容は未保存の変更として扱う（このセッションの編集には数えない）
+                self.session.switch_tree(&self.tree, &snapshot.tree);
+                self.tree = snapshot.tree;
+                self.undo_history.clear();
+                self.file.file_path = snapshot.file_path;
+                self.file.detected_format = None;
+                self.file.saved_fingerprint = 0;
+                self.file.last_saved_at = None;
+                self.file.dirty = true;
+                self.person_editor.selected = None;
+                self.toasts.info(t("recovery_restored"));
+                self.log.add(t("recovery_restored"), LogLevel::Debug);
+                // 再び異常終了しても失われないよう、このインスタンスの保存先に書き直してから古いものを消す
+                self.write_recovery_snapshot();
+            }
+            if let Err(e) = leftover.clear() {
+                self.log.add(format!("{}: {}", t("recovery_snapshot_error"), e), LogLevel::Warning);
+            }
+        }
+    }
+
+    /// ファイル名や未保存マークが変わったときだけウィンドウタイトルを更新する
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let title = format!("{} - {}", self.file.display_name(&t("untitled")), t("window_title"));
+        if title != self.file.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.file.window_title = title;
+        }
     }
 
     pub fn load(&mut self) {
@@ -161,9 +629,28 @@ impl App {
             }
         };
 
+        // 新しい版で作られたファイルは、知らない項目を壊さないよう閲覧モードで開く
+        if tree.format_version > CURRENT_FORMAT_VERSION {
+            self.ui.read_only = true;
+            let message = format!("{} (v{} > v{})", t("newer_format_read_only"), tree.format_version, CURRENT_FORMAT_VERSION);
+            self.toasts.warning(message.clone());
+            self.log.add(message, LogLevel::Warning);
+        }
+
+        self.session.switch_tree(&self.tree, &tree);
         self.tree = tree;
+        self.undo_history.clear();
         self.person_editor.selected = None;
-        self.file.status = format!("{}: {}", t("loaded"), self.file.file_path);
+        self.mark_saved();
+        // 前に開いていたファイルの保存時刻は表示しない
+        self.file.last_saved_at = None;
+        // 他のインスタンスが開いていても読み込みは続け、確認ダイアログで知らせる（閲覧モードでは保存しないのでロックしない）
+        if !self.ui.read_only {
+            self.ensure_file_lock();
+        }
+        let format = MultiFormatTreeRepository::load_format(&self.file.file_path);
+        self.file.detected_format = Some(format);
+        self.toasts.info(format!("{} ({}): {}", t("loaded"), format.label(), self.file.file_path));
         self.log
             .add(
                 format!("{}: {}", t("log_file_loaded"), self.file.file_path),
@@ -187,30 +674,13 @@ impl App {
             .unwrap_or_else(|| Texts::get("unknown", lang))
     }
 
-    pub fn fit_canvas_to_contents(&mut self) {
-        if self.canvas.canvas_rect == egui::Rect::NOTHING {
-            return;
-        }
-
-        if self.tree.persons.is_empty() && self.tree.events.is_empty() {
-            self.canvas.zoom = 1.0;
-            self.canvas.pan = egui::Vec2::ZERO;
-            return;
-        }
-
-        let base_origin = self.canvas.canvas_rect.left_top() + egui::vec2(24.0, 24.0);
-        let origin = if self.canvas.show_grid {
-            LayoutEngine::snap_to_grid(base_origin, self.canvas.grid_size)
-        } else {
-            base_origin
-        };
-
-        let photo_dimensions: HashMap<PersonId, (u32, u32)> = self
-            .tree
+    /// 写真付き表示の人物について、画像ファイルのサイズを取得する
+    pub(crate) fn collect_photo_dimensions(&self) -> HashMap<PersonId, (u32, u32)> {
+        self.tree
             .persons
             .iter()
             .filter_map(|(person_id, person)| {
-                if person.display_mode != crate::core::tree::PersonDisplayMode::NameAndPhoto {
+                if !person.display_mode.shows_photo() {
                     return None;
                 }
 
@@ -220,9 +690,105 @@ impl App {
                     .and_then(read_image_dimensions)
                     .map(|dimensions| (*person_id, dimensions))
             })
-            .collect();
+            .collect()
+    }
 
-        let nodes = LayoutEngine::compute_layout(&self.tree, origin, &photo_dimensions);
+    /// 自動整列した結果をキャンバスにプレビューする（適用するまで家系図は変更しない）
+    pub fn preview_auto_arrange(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        self.refresh_auto_arrange_preview();
+        if self.canvas.arrange_preview.is_none() {
+            self.toasts.info(t("auto_arrange_nothing_to_move"));
+        }
+    }
+
+    /// プレビュー中に家系図が編集されていたら、今の家系図で整列し直す（動かす人物がいなくなれば取りやめる）
+    pub(crate) fn refresh_auto_arrange_preview(&mut self) {
+        let fingerprint = self.file.current_fingerprint;
+        if self.canvas.arrange_preview.as_ref().is_some_and(|preview| preview.fingerprint == fingerprint) {
+            return;
+        }
+        let moves = self.auto_arrange_moves();
+        self.canvas.arrange_preview = (!moves.is_empty()).then_some(ArrangePreview { moves, fingerprint });
+    }
+
+    /// プレビュー中の自動整列を適用する（元に戻すで整列前の位置に戻せる）
+    pub fn apply_auto_arrange_preview(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        self.refresh_auto_arrange_preview();
+        let Some(preview) = self.canvas.arrange_preview.take() else {
+            return;
+        };
+        let (moved, _) = self.apply_undoable(t("auto_arrange"), |tree| {
+            let mut moved = 0;
+            for (person_id, position) in preview.moves {
+                if let Some(person) = tree.persons.get_mut(&person_id) {
+                    person.position = position;
+                    moved += 1;
+                }
+            }
+            moved
+        });
+        self.toasts.info(format!("{}: {moved}", t("auto_arranged")));
+        self.log.add(format!("{}: {moved}", t("log_auto_arranged")), LogLevel::Debug);
+    }
+
+    /// 固定されていない人物を世代ごとに自動整列したときに動く人物と移動先（固定された人物は動かさない）
+    fn auto_arrange_moves(&self) -> HashMap<PersonId, (f32, f32)> {
+        // 今の配置の左上（右から左の場合は右上）を起点にして、キャンバス上の位置が大きく飛ばないようにする
+        let direction = self.ui.layout_direction;
+        let origin = self
+            .tree
+            .persons
+            .values()
+            .map(|person| person.position)
+            .reduce(|(edge_x, min_y), (x, y)| {
+                let edge_x = match direction {
+                    LayoutDirection::LeftToRight => edge_x.min(x),
+                    LayoutDirection::RightToLeft => edge_x.max(x),
+                };
+                (edge_x, min_y.min(y))
+            })
+            .unwrap_or_default();
+        let config = LayoutConfig::default();
+        LayoutEngine::auto_positions(&self.tree, origin, config.x_spacing, config.y_spacing, direction)
+            .into_iter()
+            .filter(|(person_id, position)| {
+                self.tree
+                    .persons
+                    .get(person_id)
+                    .is_some_and(|person| !person.keeps_position() && person.position != *position)
+            })
+            .collect()
+    }
+
+    pub fn fit_canvas_to_contents(&mut self) {
+        self.fit_canvas(false);
+    }
+
+    /// 家系図の横幅がキャンバスに収まるようにズームし、上端を合わせる
+    pub fn fit_canvas_to_width(&mut self) {
+        self.fit_canvas(true);
+    }
+
+    fn fit_canvas(&mut self, width_only: bool) {
+        if self.canvas.canvas_rect == egui::Rect::NOTHING {
+            return;
+        }
+
+        if self.tree.persons.is_empty() && self.tree.events.is_empty() {
+            self.animate_camera_to(1.0, egui::Vec2::ZERO);
+            return;
+        }
+
+        let origin = self.canvas_layout_origin();
+
+        let photo_dimensions = self.collect_photo_dimensions();
+        let nodes = LayoutEngine::compute_layout(&self.tree, &photo_dimensions, self.ui.show_life_years);
 
         let mut world_bounds: Option<egui::Rect> = None;
         for node in &nodes {
@@ -260,18 +826,218 @@ impl App {
 
         let fit_zoom_x = available_width / content_width;
         let fit_zoom_y = available_height / content_height;
-        self.canvas.zoom = fit_zoom_x.min(fit_zoom_y).clamp(0.3, 3.0);
+        let fit_zoom = if width_only { fit_zoom_x } else { fit_zoom_x.min(fit_zoom_y) };
+        let zoom = fit_zoom.clamp(MIN_CANVAS_ZOOM, MAX_CANVAS_ZOOM);
 
         let world_center = bounds.center();
         let screen_center = self.canvas.canvas_rect.center();
-        self.canvas.pan = screen_center - origin - (world_center - origin) * self.canvas.zoom;
+        let mut pan = screen_center - origin - (world_center - origin) * zoom;
+        if width_only {
+            // 縦方向は収まらないので、中央ではなく上端をそろえる
+            pan.y = self.canvas.canvas_rect.top() + margin - origin.y - (bounds.top() - origin.y) * zoom;
+        }
+        self.animate_camera_to(zoom, pan);
+
+        let t = |key: &str| Texts::get(key, lang);
+        self.toasts.info(t(if width_only { "zoom_fit_width_done" } else { "fit_to_view_done" }));
+    }
+
+    /// キャンバス中央を固定したまま指定の倍率にする
+    pub fn set_canvas_zoom(&mut self, zoom: f32) {
+        let anchor = if self.canvas.canvas_rect == egui::Rect::NOTHING {
+            self.canvas.canvas_origin
+        } else {
+            self.canvas.canvas_rect.center()
+        };
+        let factor = zoom / self.canvas.zoom;
+        self.zoom_at(anchor, self.canvas.canvas_origin, factor);
+    }
+
+    /// 指定した人物がキャンバス中央に来るよう、現在のズームのままカメラを移動
+    pub fn center_on_person(&mut self, person_id: PersonId) {
+        if self.canvas.canvas_rect == egui::Rect::NOTHING {
+            return;
+        }
+
+        let origin = self.canvas_layout_origin();
+        let photo_dimensions = self.collect_photo_dimensions();
+        let nodes = LayoutEngine::compute_layout(&self.tree, &photo_dimensions, self.ui.show_life_years);
+        let Some(node) = nodes.iter().find(|node| node.id == person_id) else {
+            return;
+        };
+
+        let zoom = self.canvas.zoom;
+        let screen_center = self.canvas.canvas_rect.center();
+        let pan = screen_center - origin - (node.rect.center() - origin) * zoom;
+        self.animate_camera_to(zoom, pan);
+    }
+
+    /// グリッド表示中なら、設定した強さでキャンバス上の位置をグリッドに合わせる
+    pub(crate) fn snap_to_canvas_grid(&self, position: (f32, f32), origin: egui::Pos2) -> (f32, f32) {
+        if !self.canvas.show_grid {
+            return position;
+        }
+        let relative_pos = egui::pos2(position.0 - origin.x, position.1 - origin.y);
+        let snapped_rel =
+            LayoutEngine::snap_to_grid_with_strength(relative_pos, self.canvas.grid_size, self.canvas.grid.snap_strength);
+        (origin.x + snapped_rel.x, origin.y + snapped_rel.y)
+    }
+
+    /// まだどの人物ともつながっていない人物を、関係を追加した相手の上・下・横に置くときの位置
+    ///
+    /// 既に関係のある人物や固定された人物は動かさないので None。関係を追加する前に呼び出す。
+    pub(crate) fn linked_person_position(&self, anchor: PersonId, person: PersonId, placement: RelativePlacement) -> Option<(f32, f32)> {
+        let unlinked = self.tree.parents_of(person).is_empty()
+            && self.tree.children_of(person).is_empty()
+            && self.tree.spouses_of(person).is_empty();
+        if !unlinked || self.tree.persons.get(&person).is_none_or(|p| p.keeps_position()) {
+            return None;
+        }
+        let config = LayoutConfig::default();
+        let position = LayoutEngine::position_near(&self.tree, anchor, person, placement, config.x_spacing, config.y_spacing)?;
+        Some(self.snap_to_canvas_grid(position, self.canvas.canvas_origin))
+    }
+
+    /// ホームの人物を選択し、キャンバス中央に移動する
+    pub fn go_to_home_person(&mut self) {
+        let Some(person_id) = self.tree.home_person.filter(|id| self.tree.persons.contains_key(id)) else {
+            return;
+        };
+        self.select_single_person(person_id);
+        self.center_on_person(person_id);
+    }
 
+    /// 選択履歴をひとつ戻る（削除済みの人物は飛ばす）
+    pub fn navigate_selection_back(&mut self) {
+        let persons = &self.tree.persons;
+        if let Some(person_id) = self.person_editor.history.go_back(|id| persons.contains_key(&id)) {
+            self.select_single_person(person_id);
+        }
+    }
+
+    /// 選択履歴をひとつ進む（削除済みの人物は飛ばす）
+    pub fn navigate_selection_forward(&mut self) {
+        let persons = &self.tree.persons;
+        if let Some(person_id) = self.person_editor.history.go_forward(|id| persons.contains_key(&id)) {
+            self.select_single_person(person_id);
+        }
+    }
+
+    /// 指定した人物だけを選択し、人物タブの編集欄に読み込む
+    pub(crate) fn select_single_person(&mut self, person_id: PersonId) {
+        self.person_editor.selected = Some(person_id);
+        self.person_editor.selected_ids = vec![person_id];
+        self.load_selected_person_into_form(person_id);
+        self.ui.side_tab = SideTab::Persons;
+    }
+
+    /// 現在のズーム・パンから目標値までのカメラ移動アニメーションを開始
+    pub fn animate_camera_to(&mut self, zoom: f32, pan: egui::Vec2) {
+        self.canvas.camera_animation = Some(CameraAnimation {
+            from_zoom: self.canvas.zoom,
+            from_pan: self.canvas.pan,
+            to_zoom: zoom,
+            to_pan: pan,
+            started_at: None,
+        });
+    }
+
+    /// 保存した表示位置に移動する（番号は保存順で0始まり）
+    pub fn go_to_camera_bookmark(&mut self, index: usize) {
+        if let Some(bookmark) = self.tree.camera_bookmarks.get(index) {
+            let pan = egui::vec2(bookmark.pan.0, bookmark.pan.1);
+            self.animate_camera_to(bookmark.zoom, pan);
+        }
+    }
+
+    /// 高コントラスト設定が変わったときだけ画面全体の配色を切り替える
+    fn apply_ui_theme(&mut self, ctx: &egui::Context) {
+        let high_contrast = self.ui.high_contrast_ui;
+        if self.ui.applied_high_contrast_ui == Some(high_contrast) {
+            return;
+        }
+        if high_contrast {
+            ctx.set_visuals_of(egui::Theme::Dark, high_contrast_visuals());
+            ctx.set_theme(egui::ThemePreference::Dark);
+        } else if self.ui.applied_high_contrast_ui.is_some() {
+            // 起動時は既定の配色のままにし、高コントラストから戻すときだけ元に戻す
+            ctx.set_visuals_of(egui::Theme::Dark, egui::Visuals::dark());
+            ctx.set_theme(egui::ThemePreference::System);
+        }
+        self.ui.applied_high_contrast_ui = Some(high_contrast);
+    }
+
+    /// フォントの種類・文字サイズが変わったときだけ画面に反映する
+    pub(crate) fn apply_ui_fonts(&mut self, ctx: &egui::Context) {
+        let current = (self.ui.ui_font_family, self.ui.ui_font_size);
+        let previous = self.ui.applied_ui_font;
+        if previous == Some(current) {
+            return;
+        }
+        // フォントの読み込みは重いので、種類が変わったときだけ行う
+        if previous.map(|(family, _)| family) != Some(current.0) {
+            self.load_ui_fonts(ctx);
+        }
+        ctx.all_styles_mut(|style| style.text_styles = scaled_text_styles(current.1));
+        self.ui.applied_ui_font = Some(current);
+    }
+
+    fn load_ui_fonts(&mut self, ctx: &egui::Context) {
+        let lang = self.ui.language;
         let t = |key: &str| Texts::get(key, lang);
-        self.file.status = t("fit_to_view_done");
+        let system_font = match self.ui.ui_font_family {
+            UiFontFamily::Bundled => None,
+            UiFontFamily::System => match load_system_cjk_font() {
+                Some((path, bytes)) => {
+                    self.log.add(format!("{}: {}", t("log_system_font_loaded"), path.display()), LogLevel::Debug);
+                    self.ui.system_font_path = Some(path.display().to_string());
+                    Some(bytes)
+                }
+                None => {
+                    self.log.add(t("log_system_font_not_found"), LogLevel::Warning);
+                    self.ui.system_font_path = None;
+                    None
+                }
+            },
+        };
+        ctx.set_fonts(font_definitions(system_font));
+    }
+
+    /// キャンバス上の人物・イベント・注釈の操作方法（閲覧モードではドラッグで動かせない）
+    pub(crate) fn canvas_item_sense(&self) -> egui::Sense {
+        if self.ui.read_only {
+            egui::Sense::click()
+        } else {
+            egui::Sense::click_and_drag()
+        }
+    }
+
+    /// レイアウト計算の基準となるキャンバス原点（グリッド表示時はグリッドに吸着）
+    pub(crate) fn canvas_layout_origin(&self) -> egui::Pos2 {
+        let base_origin = self.canvas.canvas_rect.left_top() + egui::vec2(24.0, 24.0);
+        if self.canvas.show_grid {
+            LayoutEngine::snap_to_grid(base_origin, self.canvas.grid_size)
+        } else {
+            base_origin
+        }
     }
 }
 
 impl eframe::App for App {
+    /// 正常終了時は復旧用スナップショットを削除する（復元の確認中なら次回に持ち越す）
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if self.recovery.pending_restore.is_none() {
+            self.clear_recovery_snapshot();
+        }
+        self.file.lock = None;
+
+        let counts = self.session.counts(&self.tree);
+        if !counts.is_empty() {
+            let t = |key: &str| Texts::get(key, self.ui.language);
+            self.log.add(format!("{}: {}", t("log_session_summary"), self.session_summary(&counts)), LogLevel::Debug);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let lang = self.ui.language;
         let t = |key: &str| Texts::get(key, lang);
@@ -281,12 +1047,60 @@ impl eframe::App for App {
             self.log.add(warning, LogLevel::Warning);
         }
         
+        // 未保存の変更がある状態でウィンドウを閉じようとしたら確認する
+        if ctx.input(|i| i.viewport().close_requested()) && self.file.dirty && !self.file.close_confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.file.pending_action = Some(PendingFileAction::Exit);
+        }
+
+        self.apply_ui_theme(ctx);
+        self.apply_ui_fonts(ctx);
+
+        // Ctrl+PageUp/PageDown でサイドパネルのタブを切り替える
+        if SHORTCUT_NEXT_SIDE_TAB.pressed(ctx) {
+            self.ui.side_tab = self.ui.side_tab.cycled(1);
+        }
+        if SHORTCUT_PREVIOUS_SIDE_TAB.pressed(ctx) {
+            self.ui.side_tab = self.ui.side_tab.cycled(-1);
+        }
+
+        // 選択履歴の記録と Alt+←/→ による移動
+        self.person_editor.history.observe(self.person_editor.selected);
+        if SHORTCUT_SELECTION_BACK.pressed(ctx) {
+            self.navigate_selection_back();
+        }
+        if SHORTCUT_SELECTION_FORWARD.pressed(ctx) {
+            self.navigate_selection_forward();
+        }
+        // Alt+Home でホームの人物に移動
+        if SHORTCUT_GO_HOME.pressed(ctx) {
+            self.go_to_home_person();
+        }
+        // Ctrl+1〜9 で保存した表示位置に移動
+        if let Some(index) = CAMERA_BOOKMARK_SHORTCUTS.iter().position(|shortcut| shortcut.pressed(ctx)) {
+            self.go_to_camera_bookmark(index);
+        }
+        // Ctrl+0 でズームを 100% に戻す
+        if SHORTCUT_ZOOM_RESET.pressed(ctx) {
+            self.set_canvas_zoom(1.0);
+        }
+
         // メニューバー
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 self.render_file_menu(ui, ctx);
+                self.render_edit_menu(ui, ctx);
                 self.render_view_menu(ui);
                 self.render_help_menu(ui, ctx);
+                ui.separator();
+                let home_button = egui::Button::new(format!("⌂ {}", t("home_person_button")));
+                if ui
+                    .add_enabled(self.tree.home_person.is_some(), home_button)
+                    .on_hover_text(shortcut_button_label(&t("go_to_home_person"), &SHORTCUT_GO_HOME))
+                    .clicked()
+                {
+                    self.go_to_home_person();
+                }
             });
         });
         
@@ -300,7 +1114,17 @@ impl eframe::App for App {
                     ui.selectable_value(&mut self.ui.side_tab, SideTab::Persons, t("persons"));
                     ui.selectable_value(&mut self.ui.side_tab, SideTab::Families, t("families"));
                     ui.selectable_value(&mut self.ui.side_tab, SideTab::Events, t("events"));
-                    ui.selectable_value(&mut self.ui.side_tab, SideTab::Settings, t("settings"));
+                    ui.selectable_value(&mut self.ui.side_tab, SideTab::Places, t("places"));
+                    ui.selectable_value(&mut self.ui.side_tab, SideTab::Annotations, t("annotations"));
+                    ui.selectable_value(&mut self.ui.side_tab, SideTab::Statistics, t("statistics_tab"));
+                    // 翻訳のないキーがあれば件数を設定タブに表示する（デバッグビルドのみ記録される）
+                    let missing_translations = i18n::missing_keys().len();
+                    let settings_label = if missing_translations > 0 {
+                        format!("{} ⚠{missing_translations}", t("settings"))
+                    } else {
+                        t("settings")
+                    };
+                    ui.selectable_value(&mut self.ui.side_tab, SideTab::Settings, settings_label);
                 });
                 ui.separator();
 
@@ -308,61 +1132,66 @@ impl eframe::App for App {
                     SideTab::Persons => self.render_persons_tab(ui, t),
                     SideTab::Families => self.render_families_tab(ui, t),
                     SideTab::Events => self.render_events_tab(ui, t),
+                    SideTab::Places => self.render_places_tab(ui, t),
+                    SideTab::Annotations => self.render_annotations_tab(ui, t),
+                    SideTab::Statistics => self.render_stats_tab(ui, t),
                     SideTab::Settings => self.render_settings_tab(ui, t),
                 }
             });
         });
 
         // ログパネル（下部）
-        egui::TopBottomPanel::bottom("log_panel")
-            .resizable(true)
-            .default_height(120.0)
-            .min_height(60.0)
-            .show(ctx, |ui| {
-                ui.horizontal(|ui| {
-                    ui.heading(t("log_panel_title"));
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button(t("clear")).clicked() {
-                            self.log.clear();
-                        }
-                    });
-                });
-                ui.separator();
-                
-                egui::ScrollArea::vertical()
-                    .auto_shrink([false, false])
-                    .stick_to_bottom(true)
-                    .show(ui, |ui| {
-                        for msg in &self.log.messages {
-                            ui.horizontal(|ui| {
-                                ui.label(
-                                    egui::RichText::new(&msg.timestamp)
-                                        .color(egui::Color32::GRAY)
-                                        .monospace()
-                                );
-                                ui.label(
-                                    egui::RichText::new(format!("[{}]", msg.level.as_str()))
-                                        .color(msg.level.color())
-                                        .monospace()
-                                );
-                                ui.label(&msg.message);
-                            });
-                        }
-                    });
-            });
+        self.render_log_panel(ctx);
 
         // ステータスバー
-        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                if !self.file.status.is_empty() {
-                    ui.label(&self.file.status);
-                } else {
-                    ui.label(""); // 空の場合でもスペースを確保
-                }
-            });
-        });
+        self.render_status_bar(ctx);
         
+        // 詳細パネル（右側）
+        self.render_detail_panel(ctx);
+
         // キャンバス（最後に描画することで他のパネルの後ろに配置）
         self.render_canvas(ctx);
+
+        // 配偶者関係の編集ダイアログ
+        self.render_spouse_editor(ctx);
+
+        // 書き出しダイアログ
+        self.render_export_dialog(ctx);
+        self.render_find_replace_dialog(ctx);
+        self.render_relation_cleanup_dialog(ctx);
+        self.render_photo_check_dialog(ctx);
+        self.render_date_normalization_dialog(ctx);
+        self.render_macro_dialog(ctx);
+        self.render_sample_data_dialog(ctx);
+        self.render_slideshow(ctx);
+        self.render_place_map(ctx);
+        self.render_photo_edit_dialog(ctx);
+        self.render_layout_snapshot_delete_dialog(ctx);
+        self.render_unsaved_changes_dialog(ctx);
+        self.render_recovery_dialog(ctx);
+        self.render_file_lock_dialog(ctx);
+        self.render_overwrite_dialog(ctx);
+        self.render_error_dialog(ctx);
+        self.render_toasts(ctx);
+
+        // このフレームの編集を未保存フラグとウィンドウタイトルに反映する
+        self.update_dirty_flag(ctx);
+        self.update_window_title(ctx);
     }
+}
+
+/// クリックの確定・キー入力・文字入力・貼り付け・ファイルのドロップなど、家系図の編集につながりうる入力があるか
+fn has_edit_input(input: &egui::InputState) -> bool {
+    !input.raw.dropped_files.is_empty()
+        || input.events.iter().any(|event| match event {
+            egui::Event::PointerButton { pressed, .. } => !pressed,
+            egui::Event::Key { pressed, .. } => *pressed,
+            egui::Event::Touch { phase, .. } => *phase == egui::TouchPhase::End,
+            egui::Event::Text(_)
+            | egui::Event::Paste(_)
+            | egui::Event::Cut
+            | egui::Event::Ime(egui::ImeEvent::Commit(_))
+            | egui::Event::AccessKitActionRequest(_) => true,
+            _ => false,
+        })
 }
\ No newline at end of file
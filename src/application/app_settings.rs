@@ -6,7 +6,12 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::core::i18n::Language;
-use crate::ui::NodeColorThemePreset;
+use crate::core::layout::{GridSettings, LayoutDirection, SemanticZoomThresholds};
+use crate::core::tree::DEFAULT_PRESUMED_DECEASED_YEARS;
+use crate::ui::{
+    DEFAULT_BACKUP_KEEP, DEFAULT_BEZIER_CONTROL_OFFSET, DEFAULT_OTHER_GENDER_COLOR, DEFAULT_UI_FONT_SIZE, EdgeStyle, NewPersonDefaults,
+    NodeColorThemePreset, UiFontFamily,
+};
 
 const SETTINGS_DIR_NAME: &str = ".family-tree-creator";
 const SETTINGS_FILE_NAME: &str = "settings.toml";
@@ -40,12 +45,82 @@ impl fmt::Display for AppSettingsError {
 
 impl Error for AppSettingsError {}
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub language: Language,
     pub show_grid: bool,
     pub grid_size: f32,
     pub node_color_theme: NodeColorThemePreset,
+    #[serde(default)]
+    pub layout_direction: LayoutDirection,
+    #[serde(default)]
+    pub edge_style: EdgeStyle,
+    #[serde(default = "default_bezier_control_offset")]
+    pub bezier_control_offset: f32,
+    #[serde(default)]
+    pub semantic_zoom: SemanticZoomThresholds,
+    #[serde(default = "default_show_step_relations")]
+    pub show_step_relations: bool,
+    #[serde(default = "default_other_gender_color")]
+    pub other_gender_color: [u8; 3],
+    #[serde(default = "default_presume_deceased")]
+    pub presume_deceased: bool,
+    #[serde(default = "default_presumed_deceased_years")]
+    pub presumed_deceased_years: u32,
+    #[serde(default)]
+    pub show_all_notes: bool,
+    #[serde(default = "default_show_life_years")]
+    pub show_life_years: bool,
+    #[serde(default)]
+    pub show_detail_panel: bool,
+    #[serde(default)]
+    pub high_contrast_ui: bool,
+    #[serde(default)]
+    pub ui_font_family: UiFontFamily,
+    #[serde(default = "default_ui_font_size")]
+    pub ui_font_size: f32,
+    #[serde(default)]
+    pub grid: GridSettings,
+    #[serde(default)]
+    pub show_rulers: bool,
+    #[serde(default)]
+    pub backup_dir: String,
+    #[serde(default = "default_backup_keep")]
+    pub backup_keep: usize,
+    #[serde(default)]
+    pub new_person_defaults: NewPersonDefaults,
+}
+
+fn default_backup_keep() -> usize {
+    DEFAULT_BACKUP_KEEP
+}
+
+fn default_ui_font_size() -> f32 {
+    DEFAULT_UI_FONT_SIZE
+}
+
+fn default_bezier_control_offset() -> f32 {
+    DEFAULT_BEZIER_CONTROL_OFFSET
+}
+
+fn default_show_life_years() -> bool {
+    true
+}
+
+fn default_show_step_relations() -> bool {
+    true
+}
+
+fn default_other_gender_color() -> [u8; 3] {
+    DEFAULT_OTHER_GENDER_COLOR
+}
+
+fn default_presume_deceased() -> bool {
+    true
+}
+
+fn default_presumed_deceased_years() -> u32 {
+    DEFAULT_PRESUMED_DECEASED_YEARS
 }
 
 impl Default for AppSettings {
@@ -55,6 +130,25 @@ impl Default for AppSettings {
             show_grid: true,
             grid_size: 50.0,
             node_color_theme: NodeColorThemePreset::Default,
+            layout_direction: LayoutDirection::LeftToRight,
+            edge_style: EdgeStyle::Straight,
+            bezier_control_offset: DEFAULT_BEZIER_CONTROL_OFFSET,
+            semantic_zoom: SemanticZoomThresholds::default(),
+            show_step_relations: true,
+            other_gender_color: DEFAULT_OTHER_GENDER_COLOR,
+            presume_deceased: true,
+            presumed_deceased_years: DEFAULT_PRESUMED_DECEASED_YEARS,
+            show_all_notes: false,
+            show_life_years: true,
+            show_detail_panel: false,
+            high_contrast_ui: false,
+            ui_font_family: UiFontFamily::default(),
+            ui_font_size: DEFAULT_UI_FONT_SIZE,
+            grid: GridSettings::default(),
+            show_rulers: false,
+            backup_dir: String::new(),
+            backup_keep: DEFAULT_BACKUP_KEEP,
+            new_person_defaults: NewPersonDefaults::default(),
         }
     }
 }
@@ -88,7 +182,8 @@ impl AppSettings {
     }
 }
 
-fn default_settings_dir() -> PathBuf {
+/// 設定ファイルやエラーログを置くディレクトリ
+pub fn default_settings_dir() -> PathBuf {
     PathBuf::from(SETTINGS_DIR_NAME)
 }
 
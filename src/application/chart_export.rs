@@ -0,0 +1,339 @@
+use std::error::Error;
+use std::fmt;
+
+use eframe::egui;
+
+/// 書き出し対象の人物ノード
+#[derive(Debug, Clone)]
+pub struct ChartNode {
+    pub rect: egui::Rect,
+    pub label: String,
+    pub fill: (u8, u8, u8),
+    /// 右下の隅に描く目印の記号（目印がなければ空）
+    pub badges: String,
+}
+
+/// 書き出し対象の家族の枠
+#[derive(Debug, Clone)]
+pub struct ChartBox {
+    pub rect: egui::Rect,
+    pub label: String,
+    pub color: (u8, u8, u8),
+}
+
+/// 図の右下に添える表題欄
+#[derive(Debug, Clone)]
+pub struct ChartTitleBlock {
+    pub title: String,
+    /// 作成者・日付・世代数など、表題の下に並べる行
+    pub details: Vec<String>,
+}
+
+impl ChartTitleBlock {
+    const WIDTH: f32 = 260.0;
+    const PADDING: f32 = 10.0;
+    const TITLE_HEIGHT: f32 = 24.0;
+    const LINE_HEIGHT: f32 = 18.0;
+    /// 図と表題欄の間隔
+    const GAP: f32 = 20.0;
+
+    /// 図の範囲の下側に右寄せで配置した表題欄の矩形
+    pub fn rect_below(&self, chart_bounds: egui::Rect) -> egui::Rect {
+        let height = Self::PADDING * 2.0 + Self::TITLE_HEIGHT + Self::LINE_HEIGHT * self.details.len() as f32;
+        let width = Self::WIDTH.min(chart_bounds.width().max(Self::WIDTH / 2.0));
+        egui::Rect::from_min_size(
+            egui::pos2(chart_bounds.max.x - width, chart_bounds.max.y + Self::GAP),
+            egui::vec2(width, height),
+        )
+    }
+}
+
+/// 書き出し対象の線の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartLineStyle {
+    Solid,
+    Dotted,
+}
+
+/// 書き出し対象の線（配偶者・親子・その他の関係）
+#[derive(Debug, Clone)]
+pub struct ChartLine {
+    pub from: egui::Pos2,
+    pub to: egui::Pos2,
+    pub style: ChartLineStyle,
+}
+
+/// 書き出し用に整理した家系図の描画内容（ワールド座標）
+#[derive(Debug, Clone, Default)]
+pub struct ChartScene {
+    pub nodes: Vec<ChartNode>,
+    pub lines: Vec<ChartLine>,
+    pub family_boxes: Vec<ChartBox>,
+    pub events: Vec<ChartNode>,
+    /// イベントと人物を結ぶ線
+    pub event_lines: Vec<ChartLine>,
+    /// グリッドの間隔（ワールド座標）
+    pub grid_size: f32,
+    pub title_block: Option<ChartTitleBlock>,
+    /// 各ページの中央に薄く描く透かし文字
+    pub watermark: Option<String>,
+}
+
+impl ChartScene {
+    /// 指定したレイヤーに含まれるすべての要素（表題欄を含む）を囲む矩形を返す
+    pub fn bounds(&self, layers: &ChartLayers) -> Option<egui::Rect> {
+        let chart_bounds = self.chart_bounds(layers)?;
+        match &self.title_block {
+            Some(title_block) => Some(chart_bounds.union(title_block.rect_below(chart_bounds))),
+            None => Some(chart_bounds),
+        }
+    }
+
+    /// 表題欄の配置基準となる、図の要素だけを囲む矩形を返す
+    pub fn chart_bounds(&self, layers: &ChartLayers) -> Option<egui::Rect> {
+        let line_rect = |line: &ChartLine| egui::Rect::from_two_pos(line.from, line.to);
+        let mut rects: Vec<egui::Rect> = self.nodes.iter().map(|node| node.rect).collect();
+        rects.extend(self.lines.iter().map(line_rect));
+        if layers.family_boxes {
+            rects.extend(self.family_boxes.iter().map(|family| family.rect));
+        }
+        if layers.events {
+            rects.extend(self.events.iter().map(|event| event.rect));
+            rects.extend(self.event_lines.iter().map(line_rect));
+        }
+        rects.into_iter().reduce(|a, b| a.union(b))
+    }
+}
+
+/// 書き出しに含めるレイヤー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartLayers {
+    pub grid: bool,
+    pub family_boxes: bool,
+    pub events: bool,
+}
+
+impl Default for ChartLayers {
+    fn default() -> Self {
+        Self {
+            grid: false,
+            family_boxes: true,
+            events: true,
+        }
+    }
+}
+
+/// 1枚の画像として書き出す際の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageExportOptions {
+    /// ワールド座標1単位あたりの出力ピクセル数
+    pub scale: f32,
+    /// 印刷時の解像度（ピクセル / インチ）
+    pub dpi: u32,
+    pub layers: ChartLayers,
+}
+
+impl Default for ImageExportOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            dpi: 96,
+            layers: ChartLayers::default(),
+        }
+    }
+}
+
+impl ImageExportOptions {
+    /// 指定した大きさ（ワールド座標）の図を出力したときのピクセル数
+    pub fn pixel_size(&self, size: egui::Vec2) -> (u32, u32) {
+        let width = (size.x * self.scale).round().max(1.0) as u32;
+        let height = (size.y * self.scale).round().max(1.0) as u32;
+        (width, height)
+    }
+
+    /// 指定した大きさ（ワールド座標）の図を印刷したときの寸法（mm）
+    pub fn print_size_mm(&self, size: egui::Vec2) -> (f32, f32) {
+        let (width, height) = self.pixel_size(size);
+        let mm_per_pixel = 25.4 / self.dpi.max(1) as f32;
+        (width as f32 * mm_per_pixel, height as f32 * mm_per_pixel)
+    }
+}
+
+/// 用紙サイズ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    A4,
+    A3,
+    Letter,
+}
+
+impl PaperSize {
+    pub const ALL: [PaperSize; 3] = [PaperSize::A4, PaperSize::A3, PaperSize::Letter];
+
+    /// 縦向きの幅と高さ（mm）
+    pub fn dimensions_mm(self) -> (f32, f32) {
+        match self {
+            PaperSize::A4 => (210.0, 297.0),
+            PaperSize::A3 => (297.0, 420.0),
+            PaperSize::Letter => (215.9, 279.4),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PaperSize::A4 => "A4",
+            PaperSize::A3 => "A3",
+            PaperSize::Letter => "Letter",
+        }
+    }
+}
+
+/// 分割ポスター書き出しの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PosterOptions {
+    pub paper_size: PaperSize,
+    pub landscape: bool,
+    pub columns: u32,
+    pub rows: u32,
+    /// トンボと全体図を描く余白（mm）
+    pub margin_mm: f32,
+    pub layers: ChartLayers,
+}
+
+impl Default for PosterOptions {
+    fn default() -> Self {
+        Self {
+            paper_size: PaperSize::A4,
+            landscape: true,
+            columns: 2,
+            rows: 2,
+            margin_mm: 10.0,
+            layers: ChartLayers::default(),
+        }
+    }
+}
+
+impl PosterOptions {
+    /// 向きを考慮した用紙の幅と高さ（mm）
+    pub fn page_size_mm(&self) -> (f32, f32) {
+        let (width, height) = self.paper_size.dimensions_mm();
+        if self.landscape {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// 書き出し処理のエラーを表す。
+#[derive(Debug)]
+pub enum ChartExportError {
+    EmptyChart,
+    Write(String),
+}
+
+impl fmt::Display for ChartExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartExportError::EmptyChart => write!(f, "Nothing to export"),
+            ChartExportError::Write(message) => write!(f, "Write error: {message}"),
+        }
+    }
+}
+
+impl Error for ChartExportError {}
+
+/// 家系図の描画内容をファイルへ書き出すエクスポーター。
+pub trait ChartExporter {
+    /// 全体図を複数ページに分割して書き出し、作成したファイルパスを返す。
+    fn export_poster(
+        &self,
+        base_path: &str,
+        scene: &ChartScene,
+        options: &PosterOptions,
+    ) -> Result<Vec<String>, ChartExportError>;
+
+    /// 全体図を1枚の画像として書き出す。
+    fn export_image(
+        &self,
+        path: &str,
+        scene: &ChartScene,
+        options: &ImageExportOptions,
+    ) -> Result<(), ChartExportError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_export_sizes_follow_scale_and_dpi() {
+        let options = ImageExportOptions {
+            scale: 2.0,
+            dpi: 300,
+            layers: ChartLayers::default(),
+        };
+        let size = egui::vec2(300.0, 150.0);
+        assert_eq!(options.pixel_size(size), (600, 300));
+
+        let (width_mm, height_mm) = options.print_size_mm(size);
+        assert!((width_mm - 50.8).abs() < 0.01);
+        assert!((height_mm - 25.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bounds_respect_layers() {
+        let node = ChartNode {
+            rect: egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(100.0, 30.0)),
+            label: String::new(),
+            fill: (0, 0, 0),
+            badges: String::new(),
+        };
+        let scene = ChartScene {
+            nodes: vec![node.clone()],
+            events: vec![ChartNode {
+                rect: egui::Rect::from_min_size(egui::pos2(300.0, 0.0), egui::vec2(120.0, 30.0)),
+                ..node
+            }],
+            ..ChartScene::default()
+        };
+
+        let all_layers = ChartLayers::default();
+        assert!(scene.title_block.is_none());
+        let without_events = ChartLayers {
+            events: false,
+            ..all_layers
+        };
+        assert_eq!(scene.bounds(&all_layers).map(|rect| rect.max.x), Some(420.0));
+        assert_eq!(scene.bounds(&without_events).map(|rect| rect.max.x), Some(100.0));
+    }
+
+    #[test]
+    fn test_title_block_extends_bounds_below_chart() {
+        let scene = ChartScene {
+            nodes: vec![ChartNode {
+                rect: egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(400.0, 100.0)),
+                label: String::new(),
+                fill: (0, 0, 0),
+                badges: String::new(),
+            }],
+            title_block: Some(ChartTitleBlock {
+                title: "Family".to_string(),
+                details: vec!["Author: A".to_string(), "Generations: 3".to_string()],
+            }),
+            ..ChartScene::default()
+        };
+
+        let layers = ChartLayers::default();
+        let chart_bounds = scene.chart_bounds(&layers).expect("chart should have bounds");
+        let title_rect = scene
+            .title_block
+            .as_ref()
+            .map(|title_block| title_block.rect_below(chart_bounds))
+            .expect("title block should be placed");
+        assert_eq!(title_rect.max.x, 400.0);
+        assert_eq!(title_rect.min.y, 120.0);
+        assert_eq!(title_rect.height(), 80.0);
+        assert_eq!(scene.bounds(&layers).map(|rect| rect.max.y), Some(200.0));
+    }
+}
@@ -1,7 +1,12 @@
 pub mod app_settings;
+pub mod chart_export;
 pub mod tree_file_service;
 pub mod tree_repository;
 
-pub use app_settings::AppSettings;
+pub use app_settings::{default_settings_dir, AppSettings};
+pub use chart_export::{
+    ChartBox, ChartExportError, ChartExporter, ChartLayers, ChartLine, ChartLineStyle, ChartNode, ChartScene,
+    ChartTitleBlock, ImageExportOptions, PaperSize, PosterOptions,
+};
 pub use tree_file_service::TreeFileService;
 pub use tree_repository::{TreeRepository, TreeRepositoryError};
\ No newline at end of file
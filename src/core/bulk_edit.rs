@@ -0,0 +1,129 @@
+use uuid::Uuid;
+
+use crate::core::tree::{FamilyTree, PersonDisplayMode, PersonId};
+
+/// 複数の人物にまとめて設定する内容（None の項目は変更しない）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BulkEdit {
+    /// 追加する家族
+    pub family: Option<Uuid>,
+    pub deceased: Option<bool>,
+    pub display_mode: Option<PersonDisplayMode>,
+}
+
+/// 一括編集で変わる1人分の1項目（適用前のプレビューに表示する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkEditChange {
+    AddToFamily { person: PersonId, family: Uuid },
+    Deceased { person: PersonId, before: bool, after: bool },
+    DisplayMode { person: PersonId, before: PersonDisplayMode, after: PersonDisplayMode },
+}
+
+impl BulkEditChange {
+    pub fn person(&self) -> PersonId {
+        match self {
+            Self::AddToFamily { person, .. } | Self::Deceased { person, .. } | Self::DisplayMode { person, .. } => *person,
+        }
+    }
+}
+
+impl BulkEdit {
+    /// 変更する項目がひとつもないか
+    pub fn is_empty(&self) -> bool {
+        self.family.is_none() && self.deceased.is_none() && self.display_mode.is_none()
+    }
+
+    /// 適用したときに変わる項目を、人物ごとに変更前と変更後の値で返す（既に同じ値の項目は含まない）
+    pub fn preview(&self, tree: &FamilyTree, person_ids: &[PersonId]) -> Vec<BulkEditChange> {
+        let mut changes = Vec::new();
+        for person_id in person_ids {
+            let Some(person) = tree.persons.get(person_id) else {
+                continue;
+            };
+            if let Some(family_id) = self.family
+                && tree
+                    .get_family(family_id)
+                    .is_some_and(|family| !family.members.contains(person_id))
+            {
+                changes.push(BulkEditChange::AddToFamily { person: *person_id, family: family_id });
+            }
+            if let Some(deceased) = self.deceased
+                && person.deceased != deceased
+            {
+                changes.push(BulkEditChange::Deceased { person: *person_id, before: person.deceased, after: deceased });
+            }
+            if let Some(display_mode) = self.display_mode
+                && person.display_mode != display_mode
+            {
+                changes.push(BulkEditChange::DisplayMode { person: *person_id, before: person.display_mode, after: display_mode });
+            }
+        }
+        changes
+    }
+
+    /// 指定した人物に一括で設定し、実際に変更された人物の数を返す
+    pub fn apply(&self, tree: &mut FamilyTree, person_ids: &[PersonId]) -> usize {
+        let changes = self.preview(tree, person_ids);
+        for change in &changes {
+            match *change {
+                BulkEditChange::AddToFamily { person, family } => tree.add_member_to_family(family, person),
+                BulkEditChange::Deceased { person, after, .. } => {
+                    if let Some(person) = tree.persons.get_mut(&person) {
+                        person.deceased = after;
+                    }
+                }
+                BulkEditChange::DisplayMode { person, after, .. } => {
+                    if let Some(person) = tree.persons.get_mut(&person) {
+                        person.display_mode = after;
+                    }
+                }
+            }
+        }
+        let mut persons: Vec<PersonId> = changes.iter().map(BulkEditChange::person).collect();
+        persons.dedup();
+        persons.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_bulk_edit_applies_only_specified_fields() {
+        let mut tree = FamilyTree::default();
+        let a = tree.add_person("A".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let b = tree.add_person("B".to_string(), Gender::Female, None, "".to_string(), true, None, (0.0, 0.0));
+        let untouched = tree.add_person("C".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        let family = tree.add_family("Family".to_string(), None);
+
+        let edit = BulkEdit {
+            family: Some(family),
+            deceased: Some(true),
+            display_mode: None,
+        };
+        assert!(!edit.is_empty());
+        // プレビューは実際に変わる項目だけを変更前後の値で示す（B は既に故人）
+        assert_eq!(
+            edit.preview(&tree, &[a, b]),
+            [
+                BulkEditChange::AddToFamily { person: a, family },
+                BulkEditChange::Deceased { person: a, before: false, after: true },
+                BulkEditChange::AddToFamily { person: b, family },
+            ]
+        );
+        assert_eq!(edit.apply(&mut tree, &[a, b]), 2);
+
+        assert!(tree.persons[&a].deceased);
+        assert!(tree.persons[&b].deceased);
+        assert!(!tree.persons[&untouched].deceased);
+        assert_eq!(tree.get_family(family).unwrap().members, vec![a, b]);
+        assert_eq!(tree.persons[&a].display_mode, PersonDisplayMode::NameOnly);
+
+        // 既に同じ内容なら変更なし
+        assert!(edit.preview(&tree, &[a, b]).is_empty());
+        assert_eq!(edit.apply(&mut tree, &[a, b]), 0);
+        assert!(BulkEdit::default().is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+use chrono::NaiveDate;
+
+use crate::core::tree::FamilyTree;
+
+/// 日付が入っている項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DateField {
+    Birth,
+    Death,
+    Marriage,
+    Divorce,
+    Adoption,
+    Event,
+}
+
+/// 日付の正規化によって変わる（または解析できない）1項目のプレビュー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatePreview {
+    pub field: DateField,
+    /// 日付の持ち主（人物名・「親 → 子」・「夫 – 妻」・イベント名）
+    pub owner: String,
+    pub before: String,
+    /// 正規化後の日付。解析できない場合は None
+    pub after: Option<String>,
+}
+
+/// 和暦の元号（記号・漢字表記と、元年の前年の西暦）
+const JAPANESE_ERAS: [(char, &str, i32); 5] = [
+    ('M', "明治", 1867),
+    ('T', "大正", 1911),
+    ('S', "昭和", 1925),
+    ('H', "平成", 1988),
+    ('R', "令和", 2018),
+];
+
+/// 英語の月名（3文字以上の省略形も受け付ける）
+const MONTH_NAMES: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november",
+    "december",
+];
+
+/// 自由入力の日付を "YYYY" / "YYYY-MM" / "YYYY-MM-DD" 形式に直す
+///
+/// 対応する形式: "1990/5/15"・"1990.5.15"・"1990年5月15日"・和暦（"H2.5.15"・"平成2年5月15日"・"元年"）・
+/// 英語（"May 15 1990"・"15 May 1990"・"May 1990"）・月と日が区別できる "15/5/1990"
+pub fn normalize_date(text: &str) -> Option<String> {
+    // 全角数字・記号は半角にそろえる
+    let text: String = text
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            '／' => '/',
+            '．' => '.',
+            '－' => '-',
+            _ => c,
+        })
+        .collect();
+    if text.is_empty() {
+        return None;
+    }
+
+    let (year, month, day) = parse_japanese_era(&text)
+        .or_else(|| parse_numeric(&text))
+        .or_else(|| parse_english(&text))?;
+    format_date(year, month, day)
+}
+
+/// 区切り文字で分割し、空でない部分を返す
+fn split_tokens(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '-' | '/' | '.' | ',' | '年' | '月' | '日'))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+fn parse_number(token: &str) -> Option<u32> {
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// 数字だけの日付（年が先頭、または日・月が先頭で年が末尾）
+fn parse_numeric(text: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+    let tokens = split_tokens(text);
+    let numbers: Vec<u32> = tokens.iter().map(|token| parse_number(token)).collect::<Option<_>>()?;
+    match (tokens.as_slice(), numbers.as_slice()) {
+        ([year, ..], [y, rest @ ..]) if year.len() == 4 && rest.len() <= 2 => {
+            Some((*y as i32, rest.first().copied(), rest.get(1).copied()))
+        }
+        // 日と月の順序は、片方が12を超えて区別できるときだけ受け付ける
+        ([_, _, year], [a, b, y]) if year.len() == 4 => match (*a > 12, *b > 12) {
+            (true, false) => Some((*y as i32, Some(*b), Some(*a))),
+            (false, true) => Some((*y as i32, Some(*a), Some(*b))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 和暦（"H2.5.15"・"平成2年5月15日"・"令和元年"）
+fn parse_japanese_era(text: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+    let (base, rest) = JAPANESE_ERAS.iter().find_map(|(letter, name, base)| {
+        if let Some(rest) = text.strip_prefix(name) {
+            return Some((*base, rest));
+        }
+        let mut chars = text.chars();
+        let first = chars.next()?;
+        let rest = chars.as_str();
+        (first.to_ascii_uppercase() == *letter && rest.starts_with(|c: char| c.is_ascii_digit() || c == '元'))
+            .then_some((*base, rest))
+    })?;
+    let rest = rest.replacen('元', "1", 1);
+    let tokens = split_tokens(&rest);
+    if tokens.is_empty() || tokens.len() > 3 {
+        return None;
+    }
+    let numbers: Vec<u32> = tokens.iter().map(|token| parse_number(token)).collect::<Option<_>>()?;
+    let era_year = numbers[0];
+    if era_year == 0 {
+        return None;
+    }
+    Some((base + era_year as i32, numbers.get(1).copied(), numbers.get(2).copied()))
+}
+
+/// 英語の月名を含む日付（"May 15 1990"・"15th May, 1990"・"May 1990"）
+fn parse_english(text: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+    let mut month = None;
+    let mut year = None;
+    let mut day = None;
+    for token in split_tokens(text) {
+        let lower = token.to_lowercase();
+        let is_word = lower.len() >= 3 && lower.chars().all(|c| c.is_ascii_alphabetic());
+        if is_word && let Some(index) = MONTH_NAMES.iter().position(|name| name.starts_with(&lower)) {
+            if month.replace(index as u32 + 1).is_some() {
+                return None;
+            }
+            continue;
+        }
+        let digits = lower.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let suffix = &lower[digits.len()..];
+        if !suffix.is_empty() && !matches!(suffix, "st" | "nd" | "rd" | "th") {
+            return None;
+        }
+        let number = parse_number(digits)?;
+        if digits.len() == 4 && suffix.is_empty() {
+            if year.replace(number as i32).is_some() {
+                return None;
+            }
+        } else if digits.len() <= 2 {
+            if day.replace(number).is_some() {
+                return None;
+            }
+        } else {
+            return None;
+        }
+    }
+    Some((year?, Some(month?), day))
+}
+
+/// 年・月・日の範囲を確認して正規形にする
+fn format_date(year: i32, month: Option<u32>, day: Option<u32>) -> Option<String> {
+    if !(1..=9999).contains(&year) {
+        return None;
+    }
+    match (month, day) {
+        (None, None) => Some(format!("{year:04}")),
+        (Some(month), None) if (1..=12).contains(&month) => Some(format!("{year:04}-{month:02}")),
+        (Some(month), Some(day)) => {
+            NaiveDate::from_ymd_opt(year, month, day).map(|_| format!("{year:04}-{month:02}-{day:02}"))
+        }
+        _ => None,
+    }
+}
+
+/// 家系図の日付のうち、正規化で変わるものと解析できないものの一覧（空の日付は含めない）
+pub fn preview_date_normalization(tree: &FamilyTree) -> Vec<DatePreview> {
+    let person_name = |id| {
+        tree.persons
+            .get(&id)
+            .map(|person| person.name.clone())
+            .unwrap_or_default()
+    };
+    let mut dates: Vec<(DateField, String, &Option<String>)> = Vec::new();
+    for person in tree.persons.values() {
+        dates.push((DateField::Birth, person.name.clone(), &person.birth));
+        dates.push((DateField::Death, person.name.clone(), &person.death));
+    }
+    for spouse in &tree.spouses {
+        let owner = format!("{} – {}", person_name(spouse.person1), person_name(spouse.person2));
+        dates.push((DateField::Marriage, owner.clone(), &spouse.marriage_date));
+        dates.push((DateField::Divorce, owner, &spouse.divorce_date));
+    }
+    for edge in &tree.edges {
+        let owner = format!("{} → {}", person_name(edge.parent), person_name(edge.child));
+        dates.push((DateField::Adoption, owner, &edge.adoption_date));
+    }
+    for event in tree.events.values() {
+        dates.push((DateField::Event, event.name.clone(), &event.date));
+    }
+
+    let mut previews: Vec<DatePreview> = dates
+        .into_iter()
+        .filter_map(|(field, owner, date)| {
+            let before = date.as_deref().filter(|date| !date.trim().is_empty())?;
+            let after = normalize_date(before);
+            (after.as_deref() != Some(before)).then(|| DatePreview {
+                field,
+                owner,
+                before: before.to_string(),
+                after,
+            })
+        })
+        .collect();
+    previews.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.owner.cmp(&b.owner)));
+    previews
+}
+
+/// 解析できる日付をすべて正規形に書き換え、変更した項目数を返す
+pub fn apply_date_normalization(tree: &mut FamilyTree) -> usize {
+    let mut count = 0;
+    let mut normalize = |date: &mut Option<String>| {
+        if let Some(after) = date.as_deref().and_then(normalize_date)
+            && date.as_deref() != Some(after.as_str())
+        {
+            *date = Some(after);
+            count += 1;
+        }
+    };
+
+    for person in tree.persons.values_mut() {
+        normalize(&mut person.birth);
+        normalize(&mut person.death);
+    }
+    for spouse in &mut tree.spouses {
+        normalize(&mut spouse.marriage_date);
+        normalize(&mut spouse.divorce_date);
+    }
+    for edge in &mut tree.edges {
+        normalize(&mut edge.adoption_date);
+    }
+    for event in tree.events.values_mut() {
+        normalize(&mut event.date);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_normalize_date_formats() {
+        assert_eq!(normalize_date("1990/5/15").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("1990.05.15").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("1990年5月15日").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("１９９０年５月").as_deref(), Some("1990-05"));
+        assert_eq!(normalize_date("H2.5.15").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("平成2年5月15日").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("令和元年5月1日").as_deref(), Some("2019-05-01"));
+        assert_eq!(normalize_date("S20").as_deref(), Some("1945"));
+        assert_eq!(normalize_date("May 15 1990").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("15th September, 1990").as_deref(), Some("1990-09-15"));
+        assert_eq!(normalize_date("Dec 1990").as_deref(), Some("1990-12"));
+        assert_eq!(normalize_date("15/5/1990").as_deref(), Some("1990-05-15"));
+        assert_eq!(normalize_date("1990").as_deref(), Some("1990"));
+        assert_eq!(normalize_date("1990-5").as_deref(), Some("1990-05"));
+
+        // 月と日が区別できない・存在しない日付・解釈できない文字列は解析しない
+        assert_eq!(normalize_date("5/6/1990"), None);
+        assert_eq!(normalize_date("1990-02-30"), None);
+        assert_eq!(normalize_date("abt 1900"), None);
+        assert_eq!(normalize_date("May 1990 June"), None);
+    }
+
+    #[test]
+    fn test_preview_and_apply_date_normalization() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person("Taro".to_string(), Gender::Male, Some("H2.5.15".to_string()), "".to_string(), true, Some("about 2020".to_string()), (0.0, 0.0));
+        let other = tree.add_person("Hanako".to_string(), Gender::Female, Some("1992-01-03".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        tree.add_spouse(person, other, "".to_string());
+        tree.spouses[0].marriage_date = Some("May 15 2015".to_string());
+
+        let preview = preview_date_normalization(&tree);
+        assert_eq!(
+            preview,
+            vec![
+                DatePreview { field: DateField::Birth, owner: "Taro".to_string(), before: "H2.5.15".to_string(), after: Some("1990-05-15".to_string()) },
+                DatePreview { field: DateField::Death, owner: "Taro".to_string(), before: "about 2020".to_string(), after: None },
+                DatePreview { field: DateField::Marriage, owner: "Taro – Hanako".to_string(), before: "May 15 2015".to_string(), after: Some("2015-05-15".to_string()) },
+            ]
+        );
+
+        assert_eq!(apply_date_normalization(&mut tree), 2);
+        assert_eq!(tree.persons[&person].birth.as_deref(), Some("1990-05-15"));
+        assert_eq!(tree.persons[&person].death.as_deref(), Some("about 2020"));
+        assert_eq!(tree.spouses[0].marriage_date.as_deref(), Some("2015-05-15"));
+        assert_eq!(preview_date_normalization(&tree).len(), 1);
+    }
+}
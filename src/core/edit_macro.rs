@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::tree::{ExtraFields, FamilyTree, PersonId};
+
+/// 記録した操作のひとつ（対象の人物を基準にして再生する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum MacroStep {
+    /// 対象の人物を parent の子にする
+    AddParent { parent: PersonId, kind: String },
+    /// child を対象の人物の子にする
+    AddChild { child: PersonId, kind: String },
+    /// 対象の人物と spouse を配偶者にする
+    AddSpouse { spouse: PersonId, memo: String },
+    /// parent と対象の人物の親子関係の種類を変える
+    SetParentKind { parent: PersonId, kind: String },
+    /// 対象の人物を家族に加える
+    AddToFamily { family: Uuid },
+}
+
+impl MacroStep {
+    /// 対象の人物に操作を適用する。相手がいない・自分自身が相手になるなど適用できない場合は false
+    pub fn apply(&self, tree: &mut FamilyTree, target: PersonId) -> bool {
+        let applied = self.apply_unsynced(tree, target);
+        if applied {
+            tree.sync_unions();
+        }
+        applied
+    }
+
+    /// 家族の単位を組み直さずに操作を適用する（まとめて適用したら最後に `sync_unions` を呼ぶ）
+    fn apply_unsynced(&self, tree: &mut FamilyTree, target: PersonId) -> bool {
+        if !tree.persons.contains_key(&target) {
+            return false;
+        }
+        let other_exists = |tree: &FamilyTree, other: &PersonId| *other != target && tree.persons.contains_key(other);
+        match self {
+            Self::AddParent { parent, kind } => {
+                if !other_exists(tree, parent) {
+                    return false;
+                }
+                tree.add_parent_child_unsynced(*parent, target, kind.clone());
+            }
+            Self::AddChild { child, kind } => {
+                if !other_exists(tree, child) {
+                    return false;
+                }
+                tree.add_parent_child_unsynced(target, *child, kind.clone());
+            }
+            Self::AddSpouse { spouse, memo } => {
+                if !other_exists(tree, spouse) {
+                    return false;
+                }
+                tree.add_spouse_unsynced(target, *spouse, memo.clone());
+            }
+            Self::SetParentKind { parent, kind } => match tree.parent_child_relation_mut(*parent, target) {
+                Some(edge) => edge.kind = kind.clone(),
+                None => return false,
+            },
+            Self::AddToFamily { family } => {
+                if tree.get_family(*family).is_none() {
+                    return false;
+                }
+                tree.add_member_to_family(*family, target);
+            }
+        }
+        true
+    }
+}
+
+/// 名前を付けて保存した操作の並び
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl EditMacro {
+    pub fn new(name: String, steps: Vec<MacroStep>) -> Self {
+        Self { name, steps, extra: ExtraFields::new() }
+    }
+
+    /// 各対象の人物に記録した操作を順に適用し、適用できた操作の数を返す
+    pub fn replay(&self, tree: &mut FamilyTree, targets: &[PersonId]) -> usize {
+        let applied = targets
+            .iter()
+            .map(|target| self.steps.iter().filter(|step| step.apply_unsynced(tree, *target)).count())
+            .sum();
+        tree.sync_unions();
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+    use crate::core::undo::UndoHistory;
+
+    #[test]
+    fn test_replay_macro_on_each_target() {
+        let mut tree = FamilyTree::default();
+        let add = |tree: &mut FamilyTree, name: &str| {
+            tree.add_person(name.to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0))
+        };
+        let parent = add(&mut tree, "Parent");
+        let first = add(&mut tree, "First");
+        let second = add(&mut tree, "Second");
+        let family = tree.add_family("Main".to_string(), None);
+
+        let edit_macro = EditMacro::new(
+            "Adopted child".to_string(),
+            vec![
+                MacroStep::AddParent { parent, kind: "biological".to_string() },
+                MacroStep::SetParentKind { parent, kind: "adoptive".to_string() },
+                MacroStep::AddToFamily { family },
+            ],
+        );
+        assert_eq!(edit_macro.replay(&mut tree, &[first, second]), 6);
+        for child in [first, second] {
+            assert_eq!(tree.parent_child_relation(parent, child).map(|e| e.kind.as_str()), Some("adoptive"));
+            assert!(tree.get_family(family).is_some_and(|f| f.members.contains(&child)));
+        }
+
+        // 自分自身を親にする操作は飛ばす
+        assert_eq!(edit_macro.replay(&mut tree, &[parent]), 1);
+        assert!(tree.parents_of(parent).is_empty());
+
+        // 再生は元に戻す履歴の1件として記録でき、戻すと再生した関係だけが消える
+        let other = add(&mut tree, "Other");
+        let mut history = UndoHistory::default();
+        let (count, id) = history.record(&mut tree, edit_macro.name.clone(), |tree| edit_macro.replay(tree, &[other]));
+        assert_eq!(count, 3);
+        assert!(id.is_some());
+        tree.persons.get_mut(&first).unwrap().memo = "edited later".to_string();
+        assert_eq!(history.undo(&mut tree).map(|result| result.skipped), Some(0));
+        assert!(tree.parents_of(other).is_empty());
+        assert!(!tree.get_family(family).unwrap().members.contains(&other));
+        assert_eq!(tree.persons[&first].memo, "edited later");
+
+        let json = serde_json::to_string(&edit_macro).unwrap();
+        assert!(json.contains(r#""action":"add_parent""#), "{json}");
+        assert_eq!(serde_json::from_str::<EditMacro>(&json).unwrap(), edit_macro);
+    }
+}
@@ -0,0 +1,229 @@
+use chrono::{Datelike, Days, NaiveDate};
+
+use crate::core::date_normalize::normalize_date;
+use crate::core::i18n::{Language, Texts};
+use crate::core::tree::{EventId, EventRelationType, FamilyTree, PersonId};
+
+/// テンプレートから作ったイベントを人物の下に並べるときの間隔
+const TEMPLATE_EVENT_X_SPACING: f32 = 140.0;
+const TEMPLATE_EVENT_Y_OFFSET: f32 = 120.0;
+
+/// テンプレートの日付の起点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateBase {
+    Birth,
+    Death,
+}
+
+/// 起点の日付からのずれ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOffset {
+    /// 年・月だけの日付にも使える
+    Years(i32),
+    /// 年月日がそろった日付にだけ使える
+    Days(u64),
+}
+
+/// 人物の生年月日・没年月日から日付付きのイベントをまとめて作るテンプレート
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventTemplate {
+    /// テンプレート名の翻訳キー
+    pub key: &'static str,
+    pub base: TemplateBase,
+    /// 作るイベント（名前の翻訳キーと起点からのずれ）
+    pub occurrences: &'static [(&'static str, DateOffset)],
+    pub color: (u8, u8, u8),
+}
+
+/// 法要（年忌は亡くなった年を1回目と数えるので、三回忌は満2年後）
+pub const MEMORIAL_SERVICES: EventTemplate = EventTemplate {
+    key: "template_memorial_services",
+    base: TemplateBase::Death,
+    occurrences: &[
+        ("memorial_7th_day", DateOffset::Days(6)),
+        ("memorial_49th_day", DateOffset::Days(48)),
+        ("memorial_1st", DateOffset::Years(1)),
+        ("memorial_3rd", DateOffset::Years(2)),
+        ("memorial_7th", DateOffset::Years(6)),
+        ("memorial_13th", DateOffset::Years(12)),
+        ("memorial_17th", DateOffset::Years(16)),
+        ("memorial_23rd", DateOffset::Years(22)),
+        ("memorial_27th", DateOffset::Years(26)),
+        ("memorial_33rd", DateOffset::Years(32)),
+    ],
+    color: (215, 215, 235),
+};
+
+/// 長寿の祝い（満年齢で数える）
+pub const LONGEVITY_CELEBRATIONS: EventTemplate = EventTemplate {
+    key: "template_longevity_celebrations",
+    base: TemplateBase::Birth,
+    occurrences: &[
+        ("longevity_60", DateOffset::Years(60)),
+        ("longevity_70", DateOffset::Years(70)),
+        ("longevity_77", DateOffset::Years(77)),
+        ("longevity_80", DateOffset::Years(80)),
+        ("longevity_88", DateOffset::Years(88)),
+        ("longevity_90", DateOffset::Years(90)),
+        ("longevity_99", DateOffset::Years(99)),
+    ],
+    color: (255, 225, 190),
+};
+
+/// 選べるテンプレートの一覧（表示順）
+pub const EVENT_TEMPLATES: [EventTemplate; 2] = [MEMORIAL_SERVICES, LONGEVITY_CELEBRATIONS];
+
+/// テンプレートを適用できない理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventTemplateError {
+    UnknownPerson,
+    /// 起点の日付がない、または解析できない
+    MissingBaseDate,
+}
+
+impl EventTemplate {
+    /// 人物に対して作るイベント（名前の翻訳キーと "YYYY" / "YYYY-MM" / "YYYY-MM-DD" の日付）
+    ///
+    /// 日単位のずれは年月日がそろっているときだけ作る。誕生日を起点にしたものは没年より後の分を作らない。
+    pub fn planned_dates(&self, tree: &FamilyTree, person: PersonId) -> Result<Vec<(&'static str, String)>, EventTemplateError> {
+        let person = tree.persons.get(&person).ok_or(EventTemplateError::UnknownPerson)?;
+        let base = match self.base {
+            TemplateBase::Birth => &person.birth,
+            TemplateBase::Death => &person.death,
+        };
+        let base = base.as_deref().and_then(normalize_date).ok_or(EventTemplateError::MissingBaseDate)?;
+        let death_year = match self.base {
+            TemplateBase::Birth => person.death.as_deref().and_then(normalize_date).map(|death| year_of(&death)),
+            TemplateBase::Death => None,
+        };
+
+        Ok(self
+            .occurrences
+            .iter()
+            .filter_map(|(key, offset)| Some((*key, offset_date(&base, *offset)?)))
+            .filter(|(_, date)| death_year.is_none_or(|death| year_of(date) <= death))
+            .collect())
+    }
+
+    /// テンプレートのイベントを作って人物とつなぎ、作ったイベントを返す
+    ///
+    /// 作ったイベントにはテンプレートとイベントのキーを記録し、同じキー・日付のイベントが既に人物と
+    /// つながっていれば作らない。表示言語や人物名を変えた後に適用し直しても重複しない。
+    pub fn apply(&self, tree: &mut FamilyTree, person: PersonId, lang: Language) -> Result<Vec<EventId>, EventTemplateError> {
+        let planned = self.planned_dates(tree, person)?;
+        let person_name = tree.persons[&person].name.clone();
+        let (x, y) = tree.persons[&person].position;
+
+        let mut created = Vec::new();
+        for (index, (key, date)) in planned.into_iter().enumerate() {
+            let template = self.occurrence_key(key);
+            let exists = tree.event_relations.iter().any(|relation| {
+                relation.person == person
+                    && tree.events.get(&relation.event).is_some_and(|event| {
+                        event.template.as_deref() == Some(template.as_str()) && event.date.as_deref() == Some(date.as_str())
+                    })
+            });
+            if exists {
+                continue;
+            }
+            let name = format!("{} ({})", Texts::get(key, lang), person_name);
+            let position = (x + index as f32 * TEMPLATE_EVENT_X_SPACING, y + TEMPLATE_EVENT_Y_OFFSET);
+            let event = tree.add_event(name, Some(date), Texts::get(self.key, lang), position, self.color);
+            if let Some(event) = tree.events.get_mut(&event) {
+                event.template = Some(template);
+            }
+            tree.add_event_relation(event, person, EventRelationType::Line, String::new());
+            created.push(event);
+        }
+        Ok(created)
+    }
+
+    /// 作ったイベントに記録する出所のキー（"テンプレートのキー/イベントのキー"）
+    fn occurrence_key(&self, key: &str) -> String {
+        format!("{}/{key}", self.key)
+    }
+}
+
+/// 正規化済みの日付をずらす（日単位のずれは年月日がそろっていなければ None）
+fn offset_date(base: &str, offset: DateOffset) -> Option<String> {
+    let parts: Vec<&str> = base.split('-').collect();
+    match (offset, parts.as_slice()) {
+        (DateOffset::Years(years), [year]) => Some(format!("{}", year.parse::<i32>().ok()? + years)),
+        (DateOffset::Years(years), [year, month]) => Some(format!("{}-{month}", year.parse::<i32>().ok()? + years)),
+        (DateOffset::Years(years), [_, _, _]) => {
+            let date = NaiveDate::parse_from_str(base, "%Y-%m-%d").ok()?;
+            let year = date.year() + years;
+            // 2月29日生まれは平年なら2月28日にする
+            let shifted = date.with_year(year).or_else(|| NaiveDate::from_ymd_opt(year, date.month(), 28))?;
+            Some(shifted.format("%Y-%m-%d").to_string())
+        }
+        (DateOffset::Days(days), [_, _, _]) => {
+            let date = NaiveDate::parse_from_str(base, "%Y-%m-%d").ok()?;
+            Some(date.checked_add_days(Days::new(days))?.format("%Y-%m-%d").to_string())
+        }
+        _ => None,
+    }
+}
+
+fn year_of(date: &str) -> i32 {
+    date.split('-').next().and_then(|year| year.parse().ok()).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_memorial_services_from_death_date() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person(
+            "Taro".to_string(),
+            Gender::Male,
+            Some("1940-02-29".to_string()),
+            String::new(),
+            true,
+            Some("2019/1/10".to_string()),
+            (0.0, 0.0),
+        );
+
+        let dates = MEMORIAL_SERVICES.planned_dates(&tree, person).unwrap();
+        assert_eq!(&dates[..4], &[
+            ("memorial_7th_day", "2019-01-16".to_string()),
+            ("memorial_49th_day", "2019-02-27".to_string()),
+            ("memorial_1st", "2020-01-10".to_string()),
+            ("memorial_3rd", "2021-01-10".to_string()),
+        ]);
+
+        let created = MEMORIAL_SERVICES.apply(&mut tree, person, Language::Japanese).unwrap();
+        assert_eq!(created.len(), MEMORIAL_SERVICES.occurrences.len());
+        let first = &tree.events[&created[0]];
+        assert_eq!(first.name, "初七日 (Taro)");
+        assert_eq!(tree.event_relations.len(), created.len());
+        assert_eq!(first.template.as_deref(), Some("template_memorial_services/memorial_7th_day"));
+        // 2回目の適用では作らない（言語や名前を変えても同じ）
+        assert!(MEMORIAL_SERVICES.apply(&mut tree, person, Language::Japanese).unwrap().is_empty());
+        tree.persons.get_mut(&person).unwrap().name = "Taro Yamada".to_string();
+        assert!(MEMORIAL_SERVICES.apply(&mut tree, person, Language::English).unwrap().is_empty());
+
+        // 長寿の祝いは没年までの分だけ作り、2月29日は平年なら28日にする
+        let dates = LONGEVITY_CELEBRATIONS.planned_dates(&tree, person).unwrap();
+        assert_eq!(dates, vec![
+            ("longevity_60", "2000-02-29".to_string()),
+            ("longevity_70", "2010-02-28".to_string()),
+            ("longevity_77", "2017-02-28".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_template_needs_base_date() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person("Hanako".to_string(), Gender::Female, Some("1950".to_string()), String::new(), false, None, (0.0, 0.0));
+        assert_eq!(MEMORIAL_SERVICES.planned_dates(&tree, person), Err(EventTemplateError::MissingBaseDate));
+        assert_eq!(MEMORIAL_SERVICES.planned_dates(&tree, PersonId::new_v4()), Err(EventTemplateError::UnknownPerson));
+        // 年だけの日付からは年単位の分だけ作る
+        let dates = LONGEVITY_CELEBRATIONS.planned_dates(&tree, person).unwrap();
+        assert_eq!(dates[0], ("longevity_60", "2010".to_string()));
+        assert_eq!(dates.len(), LONGEVITY_CELEBRATIONS.occurrences.len());
+    }
+}
@@ -0,0 +1,275 @@
+use uuid::Uuid;
+
+use crate::core::fuzzy;
+use crate::core::tree::{id_matches_query, DeceasedPresumption, FamilyTree, Gender, Person};
+
+/// 人物一覧・キャンバスの絞り込みプリセット
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersonFilterPreset {
+    #[default]
+    All,
+    AliveOnly,
+    DeceasedOnly,
+    NoBirthDate,
+    /// 指定した家族に属する人物
+    InFamily(Uuid),
+    HasPhoto,
+}
+
+/// プリセットと名前検索を組み合わせた人物の絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersonFilter {
+    pub preset: PersonFilterPreset,
+    /// 名前のあいまい検索（部分一致・順不同・かな/ローマ字を区別しない）。短縮IDを貼り付けるとIDでも検索する
+    pub query: String,
+}
+
+impl PersonFilter {
+    /// 何らかの条件が指定されているか
+    pub fn is_active(&self) -> bool {
+        self.preset != PersonFilterPreset::All || !self.query.trim().is_empty()
+    }
+
+    /// 人物が絞り込み条件に一致するか
+    ///
+    /// 生存・死亡の判定には死亡の推定（出生からの年数）も含める。
+    pub fn matches(&self, tree: &FamilyTree, person: &Person, presumption: DeceasedPresumption) -> bool {
+        self.score(tree, person, presumption).is_some()
+    }
+
+    /// 条件に一致する場合、名前検索の一致度を返す（検索語がなければ 0）
+    pub fn score(&self, tree: &FamilyTree, person: &Person, presumption: DeceasedPresumption) -> Option<i32> {
+        let preset_matches = match self.preset {
+            PersonFilterPreset::All => true,
+            PersonFilterPreset::AliveOnly => !presumption.is_deceased(person),
+            PersonFilterPreset::DeceasedOnly => presumption.is_deceased(person),
+            PersonFilterPreset::NoBirthDate => person
+                .birth
+                .as_deref()
+                .is_none_or(|birth| birth.trim().is_empty()),
+            PersonFilterPreset::InFamily(family_id) => tree
+                .get_family(family_id)
+                .is_some_and(|family| family.members.contains(&person.id)),
+            PersonFilterPreset::HasPhoto => person.has_own_photo(),
+        };
+        if !preset_matches {
+            return None;
+        }
+
+        // IDで一致した人物は名前の一致より先に並べる
+        if id_matches_query(&person.id, &self.query) {
+            return Some(i32::MAX);
+        }
+        fuzzy::fuzzy_score(&person.name, &self.query)
+    }
+}
+
+/// キャンバスの絞り込みバーの1条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CanvasQueryTerm {
+    /// タグ（人物が所属する家族グループの名前。部分一致・大文字小文字を区別しない）
+    Tag(String),
+    Alive(bool),
+    Gender(String),
+    HasPhoto(bool),
+    Pinned(bool),
+    Locked(bool),
+}
+
+/// 絞り込みバーの入力の誤り
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanvasQueryError {
+    UnknownKey(String),
+    InvalidValue { key: String, value: String },
+}
+
+/// キャンバスの絞り込みバーの条件（例: `tag:paternal alive:true 太郎`）
+///
+/// `key:value` 形式の条件と名前のあいまい検索を空白区切りで並べ、すべてに一致する人物を残す。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CanvasQuery {
+    terms: Vec<CanvasQueryTerm>,
+    /// `key:value` 以外の語（名前のあいまい検索）
+    name_query: String,
+}
+
+impl CanvasQuery {
+    pub fn parse(text: &str) -> Result<Self, CanvasQueryError> {
+        let mut query = Self::default();
+        let mut name_words = Vec::new();
+        for word in text.split_whitespace() {
+            let Some((key, value)) = word.split_once(':') else {
+                name_words.push(word);
+                continue;
+            };
+            let key = key.to_lowercase();
+            let invalid_value = || CanvasQueryError::InvalidValue {
+                key: key.clone(),
+                value: value.to_string(),
+            };
+            let parse_bool = || match value.to_lowercase().as_str() {
+                "true" | "yes" => Ok(true),
+                "false" | "no" => Ok(false),
+                _ => Err(invalid_value()),
+            };
+            let term = match key.as_str() {
+                "tag" | "family" if !value.is_empty() => CanvasQueryTerm::Tag(value.to_lowercase()),
+                "tag" | "family" => return Err(invalid_value()),
+                "alive" => CanvasQueryTerm::Alive(parse_bool()?),
+                "deceased" => CanvasQueryTerm::Alive(!parse_bool()?),
+                "photo" => CanvasQueryTerm::HasPhoto(parse_bool()?),
+                "pinned" => CanvasQueryTerm::Pinned(parse_bool()?),
+                "locked" => CanvasQueryTerm::Locked(parse_bool()?),
+                "gender" => match value.to_lowercase().as_str() {
+                    gender @ ("male" | "female" | "unknown" | "other") => CanvasQueryTerm::Gender(gender.to_string()),
+                    _ => return Err(invalid_value()),
+                },
+                _ => return Err(CanvasQueryError::UnknownKey(key)),
+            };
+            query.terms.push(term);
+        }
+        query.name_query = name_words.join(" ");
+        Ok(query)
+    }
+
+    /// 何らかの条件が指定されているか
+    pub fn is_active(&self) -> bool {
+        !self.terms.is_empty() || !self.name_query.is_empty()
+    }
+
+    /// 人物がすべての条件に一致するか（`alive:` は死亡の推定も含めて判定する）
+    pub fn matches(&self, tree: &FamilyTree, person: &Person, presumption: DeceasedPresumption) -> bool {
+        self.terms.iter().all(|term| match term {
+            CanvasQueryTerm::Tag(tag) => tree
+                .get_families_containing(person.id)
+                .iter()
+                .any(|family| family.name.to_lowercase().contains(tag.as_str())),
+            CanvasQueryTerm::Alive(alive) => presumption.is_deceased(person) != *alive,
+            CanvasQueryTerm::Gender(gender) => {
+                let person_gender = match person.gender {
+                    Gender::Male => "male",
+                    Gender::Female => "female",
+                    Gender::Unknown => "unknown",
+                    Gender::Other(_) => "other",
+                };
+                person_gender == gender
+            }
+            CanvasQueryTerm::HasPhoto(has_photo) => person.has_own_photo() == *has_photo,
+            CanvasQueryTerm::Pinned(pinned) => person.pinned == *pinned,
+            CanvasQueryTerm::Locked(locked) => person.locked == *locked,
+        }) && fuzzy::fuzzy_score(&person.name, &self.name_query).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_filter_presets_combine_with_query() {
+        let mut tree = FamilyTree::default();
+        let alive = tree.add_person("Taro Yamada".to_string(), Gender::Male, Some("1990".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let deceased = tree.add_person("Hanako Yamada".to_string(), Gender::Female, None, "".to_string(), true, Some("2000".to_string()), (0.0, 0.0));
+        let other = tree.add_person("Jiro Sato".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let family = tree.add_family("Yamada".to_string(), None);
+        tree.add_member_to_family(family, alive);
+        tree.add_member_to_family(family, deceased);
+        tree.persons.get_mut(&other).unwrap().photo_path = Some("photo.png".to_string());
+
+        let matching = |filter: &PersonFilter| {
+            let mut names: Vec<_> = tree
+                .persons
+                .values()
+                .filter(|person| filter.matches(&tree, person, DeceasedPresumption::default()))
+                .map(|person| person.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let mut filter = PersonFilter::default();
+        assert!(!filter.is_active());
+        assert_eq!(matching(&filter).len(), 3);
+
+        filter.preset = PersonFilterPreset::AliveOnly;
+        assert_eq!(matching(&filter), vec!["Jiro Sato", "Taro Yamada"]);
+
+        filter.preset = PersonFilterPreset::DeceasedOnly;
+        assert_eq!(matching(&filter), vec!["Hanako Yamada"]);
+
+        filter.preset = PersonFilterPreset::NoBirthDate;
+        assert_eq!(matching(&filter), vec!["Hanako Yamada", "Jiro Sato"]);
+
+        filter.preset = PersonFilterPreset::HasPhoto;
+        assert_eq!(matching(&filter), vec!["Jiro Sato"]);
+
+        filter.preset = PersonFilterPreset::InFamily(family);
+        filter.query = "taro".to_string();
+        assert!(filter.is_active());
+        assert_eq!(matching(&filter), vec!["Taro Yamada"]);
+
+        // 短縮IDを貼り付けても検索できる
+        filter.preset = PersonFilterPreset::All;
+        filter.query = crate::core::tree::short_id(&deceased).to_uppercase();
+        assert_eq!(matching(&filter), vec!["Hanako Yamada"]);
+    }
+
+    #[test]
+    fn test_alive_filters_use_presumed_deceased() {
+        let mut tree = FamilyTree::default();
+        tree.add_person("Old".to_string(), Gender::Male, Some("1850".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        tree.add_person("Young".to_string(), Gender::Female, Some("1990".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let presumption = DeceasedPresumption { threshold_years: Some(110), current_year: 2026 };
+
+        let names = |matches: &dyn Fn(&Person) -> bool| {
+            let mut names: Vec<_> = tree.persons.values().filter(|person| matches(person)).map(|person| person.name.clone()).collect();
+            names.sort();
+            names
+        };
+        let filter = PersonFilter { preset: PersonFilterPreset::AliveOnly, ..Default::default() };
+        assert_eq!(names(&|person| filter.matches(&tree, person, presumption)), vec!["Young"]);
+        assert_eq!(names(&|person| filter.matches(&tree, person, DeceasedPresumption::default())), vec!["Old", "Young"]);
+
+        let query = CanvasQuery::parse("alive:false").unwrap();
+        assert_eq!(names(&|person| query.matches(&tree, person, presumption)), vec!["Old"]);
+    }
+
+    #[test]
+    fn test_canvas_query() {
+        let mut tree = FamilyTree::default();
+        let taro = tree.add_person("Taro Yamada".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let hanako = tree.add_person("Hanako Yamada".to_string(), Gender::Female, None, "".to_string(), true, None, (0.0, 0.0));
+        tree.add_person("Jiro Sato".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let paternal = tree.add_family("Paternal Yamada".to_string(), None);
+        tree.add_member_to_family(paternal, taro);
+        tree.add_member_to_family(paternal, hanako);
+
+        let matching = |text: &str| {
+            let query = CanvasQuery::parse(text).unwrap();
+            let mut names: Vec<_> = tree
+                .persons
+                .values()
+                .filter(|person| query.matches(&tree, person, DeceasedPresumption::default()))
+                .map(|person| person.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert!(!CanvasQuery::parse("  ").unwrap().is_active());
+        assert_eq!(matching("tag:paternal"), vec!["Hanako Yamada", "Taro Yamada"]);
+        assert_eq!(matching("tag:paternal alive:true"), vec!["Taro Yamada"]);
+        assert_eq!(matching("deceased:yes"), vec!["Hanako Yamada"]);
+        assert_eq!(matching("gender:male jiro"), vec!["Jiro Sato"]);
+
+        assert_eq!(
+            CanvasQuery::parse("age:30"),
+            Err(CanvasQueryError::UnknownKey("age".to_string()))
+        );
+        assert_eq!(
+            CanvasQuery::parse("alive:maybe"),
+            Err(CanvasQueryError::InvalidValue { key: "alive".to_string(), value: "maybe".to_string() })
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use crate::core::tree::FamilyTree;
+
+/// 置換の対象となる項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReplaceField {
+    PersonName,
+    PersonMemo,
+    RelationKind,
+    EventDescription,
+}
+
+/// 検索・置換を行う項目の選択
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplaceScope {
+    pub person_names: bool,
+    pub person_memos: bool,
+    pub relation_kinds: bool,
+    pub event_descriptions: bool,
+}
+
+impl Default for ReplaceScope {
+    fn default() -> Self {
+        Self {
+            person_names: true,
+            person_memos: true,
+            relation_kinds: true,
+            event_descriptions: true,
+        }
+    }
+}
+
+/// 置換によって変更される1項目のプレビュー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacePreview {
+    pub field: ReplaceField,
+    /// 変更される項目の持ち主（人物名・「親 → 子」・イベント名）
+    pub owner: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// 検索・置換の条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FindReplaceQuery {
+    pub find: String,
+    pub replace: String,
+    pub case_sensitive: bool,
+    pub scope: ReplaceScope,
+}
+
+impl FindReplaceQuery {
+    /// text 中の検索文字列をすべて置換した結果。一致しなければ None
+    pub fn replace_in(&self, text: &str) -> Option<String> {
+        if self.find.is_empty() {
+            return None;
+        }
+        if self.case_sensitive {
+            return text.contains(&self.find).then(|| text.replace(&self.find, &self.replace));
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        let mut replaced = false;
+        while !rest.is_empty() {
+            if let Some(matched_len) = self.match_len_ignore_case(rest) {
+                result.push_str(&self.replace);
+                rest = &rest[matched_len..];
+                replaced = true;
+                continue;
+            }
+            let c = rest.chars().next().unwrap_or_default();
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        replaced.then_some(result)
+    }
+
+    /// 大文字小文字を区別せずに text の先頭が検索文字列と一致する場合、一致したバイト長を返す
+    fn match_len_ignore_case(&self, text: &str) -> Option<usize> {
+        let mut text_chars = text.char_indices();
+        for find_char in self.find.chars() {
+            let (_, text_char) = text_chars.next()?;
+            if !find_char.to_lowercase().eq(text_char.to_lowercase()) {
+                return None;
+            }
+        }
+        Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+    }
+
+    /// 置換によって変更される項目の一覧
+    pub fn preview(&self, tree: &FamilyTree) -> Vec<ReplacePreview> {
+        let mut previews = Vec::new();
+        let person_name = |id| {
+            tree.persons
+                .get(&id)
+                .map(|person| person.name.clone())
+                .unwrap_or_default()
+        };
+
+        for person in tree.persons.values() {
+            if self.scope.person_names && let Some(after) = self.replace_in(&person.name) {
+                previews.push(ReplacePreview {
+                    field: ReplaceField::PersonName,
+                    owner: person.name.clone(),
+                    before: person.name.clone(),
+                    after,
+                });
+            }
+            if self.scope.person_memos && let Some(after) = self.replace_in(&person.memo) {
+                previews.push(ReplacePreview {
+                    field: ReplaceField::PersonMemo,
+                    owner: person.name.clone(),
+                    before: person.memo.clone(),
+                    after,
+                });
+            }
+        }
+
+        if self.scope.relation_kinds {
+            for edge in &tree.edges {
+                if let Some(after) = self.replace_in(&edge.kind) {
+                    previews.push(ReplacePreview {
+                        field: ReplaceField::RelationKind,
+                        owner: format!("{} → {}", person_name(edge.parent), person_name(edge.child)),
+                        before: edge.kind.clone(),
+                        after,
+                    });
+                }
+            }
+        }
+
+        if self.scope.event_descriptions {
+            for event in tree.events.values() {
+                if let Some(after) = self.replace_in(&event.description) {
+                    previews.push(ReplacePreview {
+                        field: ReplaceField::EventDescription,
+                        owner: event.name.clone(),
+                        before: event.description.clone(),
+                        after,
+                    });
+                }
+            }
+        }
+
+        previews.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.owner.cmp(&b.owner)));
+        previews
+    }
+
+    /// 置換を実行し、変更した項目数を返す
+    pub fn apply(&self, tree: &mut FamilyTree) -> usize {
+        let mut count = 0;
+        let mut replace = |text: &mut String| {
+            if let Some(after) = self.replace_in(text) {
+                *text = after;
+                count += 1;
+            }
+        };
+
+        for person in tree.persons.values_mut() {
+            if self.scope.person_names {
+                replace(&mut person.name);
+            }
+            if self.scope.person_memos {
+                replace(&mut person.memo);
+            }
+        }
+        if self.scope.relation_kinds {
+            for edge in &mut tree.edges {
+                replace(&mut edge.kind);
+            }
+            tree.sync_unions();
+        }
+        if self.scope.event_descriptions {
+            for event in tree.events.values_mut() {
+                replace(&mut event.description);
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_replace_in_respects_case_sensitivity() {
+        let mut query = FindReplaceQuery {
+            find: "yamada".to_string(),
+            replace: "山田".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(query.replace_in("Yamada Taro, YAMADA").as_deref(), Some("山田 Taro, 山田"));
+        assert_eq!(query.replace_in("Sato"), None);
+
+        query.case_sensitive = true;
+        assert_eq!(query.replace_in("Yamada yamada").as_deref(), Some("Yamada 山田"));
+        assert_eq!(query.replace_in("Yamada"), None);
+    }
+
+    #[test]
+    fn test_preview_and_apply_over_selected_fields() {
+        let mut tree = FamilyTree::default();
+        let parent = tree.add_person("Jon Smith".to_string(), Gender::Male, None, "Jon's memo".to_string(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Ann Smith".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.add_parent_child(parent, child, "Jon-adopted".to_string());
+        tree.add_event("Wedding".to_string(), None, "Jon and Mary".to_string(), (0.0, 0.0), (255, 255, 200));
+
+        let mut query = FindReplaceQuery {
+            find: "Jon".to_string(),
+            replace: "John".to_string(),
+            case_sensitive: true,
+            ..Default::default()
+        };
+        query.scope.relation_kinds = false;
+
+        let preview = query.preview(&tree);
+        let fields: Vec<_> = preview.iter().map(|p| p.field).collect();
+        assert_eq!(fields, vec![ReplaceField::PersonName, ReplaceField::PersonMemo, ReplaceField::EventDescription]);
+        assert_eq!(preview[0].after, "John Smith");
+
+        assert_eq!(query.apply(&mut tree), 3);
+        assert_eq!(tree.persons[&parent].name, "John Smith");
+        assert_eq!(tree.persons[&parent].memo, "John's memo");
+        assert_eq!(tree.edges[0].kind, "Jon-adopted");
+        assert!(query.preview(&tree).is_empty());
+    }
+}
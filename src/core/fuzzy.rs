@@ -0,0 +1,181 @@
+//! あいまい検索（部分一致・順不同・かな/ローマ字の違いを吸収）
+
+/// ひらがな → ローマ字（ヘボン式）の対応表。拗音など2文字のものを先に照合する
+const ROMAJI_TABLE: &[(&str, &str)] = &[
+    ("きゃ", "kya"), ("きゅ", "kyu"), ("きょ", "kyo"),
+    ("しゃ", "sha"), ("しゅ", "shu"), ("しょ", "sho"),
+    ("ちゃ", "cha"), ("ちゅ", "chu"), ("ちょ", "cho"),
+    ("にゃ", "nya"), ("にゅ", "nyu"), ("にょ", "nyo"),
+    ("ひゃ", "hya"), ("ひゅ", "hyu"), ("ひょ", "hyo"),
+    ("みゃ", "mya"), ("みゅ", "myu"), ("みょ", "myo"),
+    ("りゃ", "rya"), ("りゅ", "ryu"), ("りょ", "ryo"),
+    ("ぎゃ", "gya"), ("ぎゅ", "gyu"), ("ぎょ", "gyo"),
+    ("じゃ", "ja"), ("じゅ", "ju"), ("じょ", "jo"),
+    ("びゃ", "bya"), ("びゅ", "byu"), ("びょ", "byo"),
+    ("ぴゃ", "pya"), ("ぴゅ", "pyu"), ("ぴょ", "pyo"),
+    ("あ", "a"), ("い", "i"), ("う", "u"), ("え", "e"), ("お", "o"),
+    ("か", "ka"), ("き", "ki"), ("く", "ku"), ("け", "ke"), ("こ", "ko"),
+    ("さ", "sa"), ("し", "shi"), ("す", "su"), ("せ", "se"), ("そ", "so"),
+    ("た", "ta"), ("ち", "chi"), ("つ", "tsu"), ("て", "te"), ("と", "to"),
+    ("な", "na"), ("に", "ni"), ("ぬ", "nu"), ("ね", "ne"), ("の", "no"),
+    ("は", "ha"), ("ひ", "hi"), ("ふ", "fu"), ("へ", "he"), ("ほ", "ho"),
+    ("ま", "ma"), ("み", "mi"), ("む", "mu"), ("め", "me"), ("も", "mo"),
+    ("や", "ya"), ("ゆ", "yu"), ("よ", "yo"),
+    ("ら", "ra"), ("り", "ri"), ("る", "ru"), ("れ", "re"), ("ろ", "ro"),
+    ("わ", "wa"), ("を", "o"), ("ん", "n"),
+    ("が", "ga"), ("ぎ", "gi"), ("ぐ", "gu"), ("げ", "ge"), ("ご", "go"),
+    ("ざ", "za"), ("じ", "ji"), ("ず", "zu"), ("ぜ", "ze"), ("ぞ", "zo"),
+    ("だ", "da"), ("ぢ", "ji"), ("づ", "zu"), ("で", "de"), ("ど", "do"),
+    ("ば", "ba"), ("び", "bi"), ("ぶ", "bu"), ("べ", "be"), ("ぼ", "bo"),
+    ("ぱ", "pa"), ("ぴ", "pi"), ("ぷ", "pu"), ("ぺ", "pe"), ("ぽ", "po"),
+    ("ぁ", "a"), ("ぃ", "i"), ("ぅ", "u"), ("ぇ", "e"), ("ぉ", "o"),
+    ("ゃ", "ya"), ("ゅ", "yu"), ("ょ", "yo"),
+];
+
+/// 検索用に文字列を正規化する（小文字化・全角英数→半角・カタカナ→ひらがな）
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            // 全角英数記号
+            '！'..='～' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            '　' => ' ',
+            // カタカナ（ァ〜ヶ）をひらがなへ
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 正規化済みの文字列に含まれるひらがなをローマ字に変換する
+pub fn romanize(normalized: &str) -> String {
+    let mut result = String::with_capacity(normalized.len());
+    let mut rest = normalized;
+    let mut double_next = false;
+
+    while let Some(c) = rest.chars().next() {
+        if c == 'っ' {
+            double_next = true;
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        // 長音符は読みの照合に影響しないので無視
+        if c == 'ー' {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        match ROMAJI_TABLE.iter().find(|(kana, _)| rest.starts_with(kana)) {
+            Some((kana, romaji)) => {
+                if double_next && let Some(first) = romaji.chars().next() {
+                    result.push(first);
+                }
+                result.push_str(romaji);
+                rest = &rest[kana.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        double_next = false;
+    }
+    result
+}
+
+/// 1語の検索語が text に部分列として含まれる場合のスコア（高いほど良い一致）
+fn score_token(text: &[char], token: &[char]) -> Option<i32> {
+    if token.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut token_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (index, c) in text.iter().enumerate() {
+        if token_index == token.len() {
+            break;
+        }
+        if *c != token[token_index] {
+            continue;
+        }
+
+        score += 1;
+        // 連続して一致した文字と語の先頭での一致を優遇
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 4;
+        } else if let Some(previous) = previous_match {
+            score -= ((index - previous - 1) as i32).min(3);
+        }
+        if index == 0 || text[index - 1].is_whitespace() {
+            score += 3;
+        }
+
+        previous_match = Some(index);
+        token_index += 1;
+    }
+
+    (token_index == token.len()).then_some(score)
+}
+
+fn score_normalized(text: &str, query: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().collect();
+    // 空白区切りの各語は順不同で、すべて一致する必要がある
+    query.split_whitespace().try_fold(0, |total, token| {
+        let token: Vec<char> = token.chars().collect();
+        score_token(&text, &token).map(|score| total + score)
+    })
+}
+
+/// text に対する query のあいまい一致スコア。一致しない場合は None
+pub fn fuzzy_score(text: &str, query: &str) -> Option<i32> {
+    let query = normalize(query);
+    if query.trim().is_empty() {
+        return Some(0);
+    }
+
+    let text = normalize(text);
+    let direct = score_normalized(&text, &query);
+    let romanized = score_normalized(&romanize(&text), &romanize(&query));
+    direct.max(romanized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_romanize() {
+        assert_eq!(normalize("ＴＡＲＯ　ヤマダ"), "taro やまだ");
+        assert_eq!(romanize("やまだ たろう"), "yamada tarou");
+        assert_eq!(romanize("きょうこ"), "kyouko");
+        assert_eq!(romanize("はっとり"), "hattori");
+    }
+
+    #[test]
+    fn test_fuzzy_score_matches_partial_and_out_of_order() {
+        assert!(fuzzy_score("Taro Yamada", "tro").is_some());
+        assert!(fuzzy_score("Taro Yamada", "yamada taro").is_some());
+        assert!(fuzzy_score("Taro Yamada", "xyz").is_none());
+        assert_eq!(fuzzy_score("Taro Yamada", "  "), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_ignores_kana_and_romaji_differences() {
+        assert!(fuzzy_score("ヤマダ タロウ", "やまだ").is_some());
+        assert!(fuzzy_score("やまだ たろう", "taro").is_some());
+        assert!(fuzzy_score("Yamada Taro", "たろ").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranks_closer_matches_higher() {
+        let exact = fuzzy_score("Taro", "taro").unwrap();
+        let scattered = fuzzy_score("Tanaka Rokuro", "taro").unwrap();
+        assert!(exact > scattered);
+
+        let word_start = fuzzy_score("Hanako Yamada", "ya").unwrap();
+        let mid_word = fuzzy_score("Hanako Miyata", "ya").unwrap();
+        assert!(word_start > mid_word);
+    }
+}
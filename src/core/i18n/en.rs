@@ -1,7 +1,7 @@
 /// 英語翻訳
 /// English translations
 
-use super::add_warning;
+use super::{report_missing_key, Language};
 
 pub fn translate(key: &str) -> String {
     match key {
@@ -9,8 +9,119 @@ pub fn translate(key: &str) -> String {
         "persons" => "👤 Persons",
         "families" => "👪 Families",
         "settings" => "⚙ Settings",
+        "statistics_tab" => "📊 Statistics",
+        "statistics" => "Statistics",
+        "statistics_hint" => "Hover a bar to see who it contains. Click a bar to list the persons.",
+        "chart_lifespan" => "Lifespan (years)",
+        "chart_births_per_decade" => "Births per decade",
+        "chart_children_per_couple" => "Children per couple",
+        "chart_decade_suffix" => "s",
+        "chart_no_data" => "Not enough data",
         "file_menu" => "File",
         "view_menu" => "View",
+        "edit_menu" => "Edit",
+        "undo" => "Undo",
+        "redo" => "Redo",
+        "undone" => "Undone",
+        "redone" => "Redone",
+        "undo_kept_later_edits" => "Items edited afterwards were kept",
+        "find_replace" => "Find & Replace",
+        "auto_arrange" => "Auto Arrange Persons",
+        "auto_arrange_hint" => "Arrange persons by generation with couples side by side. Pinned persons stay where they are.",
+        "auto_arrange_preview" => "Preview of auto arrange – persons to move",
+        "auto_arrange_nothing_to_move" => "All persons are already arranged",
+        "auto_arranged" => "Persons arranged",
+        "log_auto_arranged" => "Auto arranged persons",
+        "pin_person" => "📌 Pin Position",
+        "unpin_person" => "Unpin Position",
+        "lock_person" => "🔒 Lock Position",
+        "unlock_person" => "Unlock Position",
+        "person_locked_hint" => "This person is locked. Unlock it from the context menu to move it.",
+        "find_text" => "Find:",
+        "replace_text" => "Replace with:",
+        "case_sensitive" => "Case sensitive",
+        "replace_scope" => "Search in:",
+        "replace_person_names" => "Person names",
+        "replace_person_memos" => "Person memos",
+        "replace_relation_kinds" => "Relation kinds",
+        "replace_event_descriptions" => "Event descriptions",
+        "affected_entries" => "Affected entries",
+        "replace_all" => "Replace All",
+        "undo_replace" => "Undo Last Replace",
+        "relation_cleanup" => "Clean Up Relations…",
+        "relation_cleanup_hint" => "Find duplicate spouse pairs, redundant parent relations and contradictory relations, and fix them in one batch.",
+        "photo_check" => "Check Photos…",
+        "photo_check_hint" => "Find photos that are missing or cannot be opened, then relocate them to a new folder or clear the links.",
+        "photo_check_no_issues" => "All photos can be opened.",
+        "photo_check_found" => "Broken photos",
+        "photo_check_missing" => "file not found",
+        "photo_check_undecodable" => "not a readable image",
+        "photo_check_relocate" => "Relocate…",
+        "photo_check_relocate_hint" => "Choose a folder; photos with the same file name (including subfolders) are linked again.",
+        "photo_check_clear" => "Clear Links",
+        "photo_check_relocated" => "Photos relocated",
+        "photo_check_cleared" => "Photo links cleared",
+        "cleanup_no_issues" => "No duplicate or contradictory relations found",
+        "cleanup_found" => "Problems found",
+        "cleanup_select_all" => "Select All",
+        "cleanup_select_none" => "Select None",
+        "cleanup_apply" => "Apply Selected Fixes",
+        "cleanup_rescan" => "Rescan",
+        "cleanup_undo" => "Undo Last Cleanup",
+        "cleanup_applied" => "Relation fixes applied",
+        "cleanup_duplicate_spouse" => "Duplicate spouse relation",
+        "cleanup_redundant_parent" => "Multiple parent relations",
+        "cleanup_mutual_parent" => "Parents of each other",
+        "cleanup_parent_and_spouse" => "Parent and child are also spouses",
+        "cleanup_self_relation" => "Relation with oneself",
+        "cleanup_fix_keep_first" => "Keep the first one and remove the rest",
+        "cleanup_fix_keep_kind" => "Keep only",
+        "cleanup_fix_remove_parent" => "Remove parent relation",
+        "cleanup_fix_remove_spouse" => "Remove the spouse relation",
+        "cleanup_fix_remove_self" => "Remove the relation",
+        "date_normalization" => "Normalize Dates…",
+        "date_normalization_hint" => "Rewrite dates such as 1990/5/15, H2.5.15 or May 15 1990 as YYYY-MM-DD and list dates that cannot be read.",
+        "edit_macros" => "Macros…",
+        "edit_macros_hint" => "Record relation edits once and replay them on the selected persons.",
+        "macro_start_recording" => "Start Recording",
+        "macro_stop_recording" => "Stop",
+        "macro_recording" => "Recording",
+        "macro_recording_hint" => "Add parents, children, spouses, change parent kinds or add members to families. Each action is recorded relative to the person it was done on.",
+        "macro_name" => "Name:",
+        "macro_save" => "Save Macro",
+        "macro_saved" => "Macro saved",
+        "macro_targets" => "Persons to apply to",
+        "macro_none_saved" => "No macros saved in this tree yet",
+        "macro_run" => "Run",
+        "macro_applied" => "Macro actions applied",
+        "macro_undo" => "Undo Last Run",
+        "macro_step_add_parent" => "Add parent",
+        "macro_step_add_child" => "Add child",
+        "macro_step_add_spouse" => "Add spouse",
+        "macro_step_set_parent_kind" => "Set kind of parent relation",
+        "macro_step_add_to_family" => "Add to family",
+        "log_macro_saved" => "Macro saved",
+        "log_macro_applied" => "Macro run",
+        "log_macro_deleted" => "Macro deleted",
+        "date_normalization_formats" => "Recognized: 1990/5/15, 1990年5月15日, H2.5.15, 平成2年5月15日, May 15 1990, 15 May 1990. Result: YYYY, YYYY-MM or YYYY-MM-DD.",
+        "dates_to_normalize" => "Dates to rewrite",
+        "dates_unparseable" => "Unreadable dates",
+        "show_unparseable_only" => "Show unreadable dates only",
+        "date_field" => "Field",
+        "date_owner" => "Owner",
+        "date_before" => "Current",
+        "date_after" => "Normalized",
+        "date_field_birth" => "Birth",
+        "date_field_death" => "Death",
+        "date_field_marriage" => "Marriage",
+        "date_field_divorce" => "Divorce",
+        "date_field_adoption" => "Adoption",
+        "date_field_event" => "Event",
+        "date_unparseable" => "⚠ Cannot read",
+        "apply_date_normalization" => "Rewrite Dates",
+        "undo_date_normalization" => "Undo Last Rewrite",
+        "dates_normalized" => "Dates normalized",
+        "replace_done" => "Replaced entries",
         "new" => "New",
         "open" => "Open",
         "save" => "Save",
@@ -20,20 +131,205 @@ pub fn translate(key: &str) -> String {
         "load_error" => "Load error",
         "file_filter_family_tree" => "Family Tree",
         "file_filter_json" => "JSON",
+        "file_filter_yaml" => "YAML",
         "file_filter_sqlite" => "SQLite",
+        "file_filter_gramps" => "Gramps XML",
+        "file_filter_gedcom" => "GEDCOM",
         "file_filter_images" => "Images",
         "default_file_name" => "tree.json",
         "count_suffix" => "",
         "fit_to_view" => "Fit to View",
+        "background_image" => "Background Image",
+        "layout_snapshots" => "Saved Layouts",
+        "layout_snapshot_name" => "Layout name",
+        "save_layout_snapshot" => "Save Current Layout",
+        "save_layout_snapshot_hint" => "Save the positions of all persons and events under this name. An existing layout with the same name is overwritten.",
+        "apply_layout_snapshot" => "Switch to this layout",
+        "delete_layout_snapshot_title" => "Delete this saved layout?",
+        "no_layout_snapshots" => "No saved layouts",
+        "camera_bookmarks" => "Saved Views",
+        "place_map" => "Place Map…",
+        "place_map_show_deaths" => "Death places",
+        "place_map_show_migrations" => "Migrations",
+        "place_map_show_migrations_hint" => "Draw an arrow from each parent's birth place to their child's birth place.",
+        "place_map_no_pins" => "No birth or death place has coordinates yet. Set latitude and longitude in the Places tab.",
+        "camera_bookmark_name" => "View name",
+        "save_camera_bookmark" => "Save Current View",
+        "save_camera_bookmark_hint" => "Save the current pan and zoom under this name. An existing view with the same name is overwritten.",
+        "no_camera_bookmarks" => "No saved views",
+        "camera_bookmark_saved" => "View saved",
+        "log_camera_bookmark_saved" => "Saved view",
+        "log_camera_bookmark_deleted" => "Deleted view",
+        "layout_snapshot_saved" => "Layout saved",
+        "layout_snapshot_applied" => "Layout switched",
+        "log_layout_snapshot_saved" => "Saved layout",
+        "log_layout_snapshot_applied" => "Switched layout",
+        "log_layout_snapshot_deleted" => "Deleted layout",
+        "choose_background_image" => "Choose Image...",
+        "background_opacity" => "Opacity",
+        "background_scale" => "Scale",
+        "background_position" => "Position:",
+        "remove_background_image" => "Remove Background",
+        "background_image_set" => "Background image set",
+        "background_image_removed" => "Background image removed",
+        "manage_annotations" => "Manage Text Annotations",
+        "add_new_annotation" => "➕ Add New Annotation",
+        "annotation_editor" => "Annotation Editor",
+        "no_annotations" => "No annotations yet",
+        "annotation_text" => "Text:",
+        "font_size" => "Font size:",
+        "annotation_text_required" => "Enter the annotation text",
+        "annotation_added" => "Annotation added",
+        "annotation_updated" => "Annotation updated",
+        "annotation_deleted" => "Annotation deleted",
+        "show_all_notes" => "Show All Sticky Notes",
+        "show_life_years" => "Show Birth/Death Years on Nodes",
+        "life_years_born_prefix" => "b. ",
+        "life_years_born_suffix" => "",
+        "show_detail_panel" => "Show Detail Panel",
+        "detail_panel" => "Details",
+        "detail_panel_empty" => "Select a person, family or event to see its details here.",
+        "open_in_editor" => "Edit",
+        "detail_relation" => "Relation",
+        "detail_back" => "Back",
+        "detail_families" => "Families",
+        "detail_events" => "Events",
+        "detail_related_persons" => "Related Persons",
+        "relation_kind_biological" => "biological",
+        "relation_kind_adoptive" => "adoptive",
+        "relation_kind_foster" => "foster",
+        "read_only_mode" => "Read-only Mode",
+        "read_only_mode_hint" => "Disable editing so the tree can be browsed, searched and exported safely",
+        "toggle_sticky_notes" => "Click to show or hide sticky notes",
+        "sticky_notes" => "Sticky notes:",
+        "sticky_note_resolved" => "Resolved",
+        "add_sticky_note" => "Add Note",
+        "sticky_note_added" => "Sticky note added",
+        "sticky_note_deleted" => "Sticky note deleted",
+        "outstanding_notes" => "Outstanding Notes",
+        "no_outstanding_notes" => "No outstanding notes",
         "fit_to_view_done" => "Fit to view applied",
+        "zoom_fit_width" => "Fit Width",
+        "zoom_fit_width_done" => "Fit width applied",
+        "zoom_reset" => "Reset Zoom to 100%",
+        "copy_as_image" => "Copy as Image",
+        "copy_as_image_hint" => "Copy the selected persons (or the visible canvas) to the clipboard as an image",
+        "copy_as_image_done" => "Copied as image",
+        "copy_as_image_not_visible" => "The selected persons are not visible on the canvas",
+        "copy_as_image_timed_out" => "Could not copy the canvas as an image: the screenshot did not arrive",
+        "show_on_canvas" => "Show on Canvas",
         "new_tree_created" => "New tree created",
+        "unsaved_changes_title" => "Save changes?",
+        "unsaved_changes_message" => "The family tree has unsaved changes. Save them before continuing?",
+        "discard_changes" => "Don't Save",
+        "file_locked_title" => "File is open in another window",
+        "file_locked_open_message" => "Another instance is editing this file. Changes you save may overwrite theirs.",
+        "file_locked_save_message" => "Another instance has taken over this file. Saving now may overwrite their changes.",
+        "file_locked_by" => "Locked by",
+        "file_locked_stale_hint" => "If that instance crashed, the lock is left over and you can take it over.",
+        "take_over_lock" => "Take Over Lock",
+        "take_over_lock_and_save" => "Take Over Lock and Save",
+        "continue_without_lock" => "Continue Without Lock",
+        "file_lock_error" => "Could not lock file",
+        "overwrite_title" => "Overwrite this file?",
+        "overwrite_unknown_content" => "The existing file is not a family tree file this app can recognize.",
+        "overwrite_different_format" => "The existing file is in a different format than the one you are saving.",
+        "overwrite_formats" => "Format",
+        "overwrite_unreadable" => "The existing file could not be read. It may be damaged or saved by another application.",
+        "overwrite_other_tree" => "The existing file contains a different family tree with none of the same persons.",
+        "overwrite_existing_persons" => "Persons in the file",
+        "overwrite_anyway" => "Overwrite",
+        "log_overwrite_warning" => "Asked before overwriting",
+        "log_file_locked" => "File is locked by another instance",
+        "window_title" => "Family Tree",
+        "untitled" => "Untitled",
+        "current_file" => "File",
+        "status_persons" => "Persons",
+        "status_relations" => "Relations",
+        "status_events" => "Events",
+        "status_zoom" => "Zoom",
+        "status_selected" => "Selected",
+        "status_unsaved" => "● Unsaved changes",
+        "status_no_changes" => "No unsaved changes",
+        "status_last_saved" => "Saved",
+        "status_not_saved_yet" => "Not saved in this session",
+        "status_just_now" => "just now",
+        "status_minutes_ago" => " min ago",
+        "status_hours_ago" => " h ago",
+        "recovery_title" => "Recover unsaved work?",
+        "recovery_message" => "The application did not exit normally last time. A recovery snapshot of your work was found.",
+        "recovery_saved_at" => "Snapshot taken",
+        "recovery_restore" => "Restore",
+        "recovery_discard" => "Discard",
+        "recovery_restored" => "Restored the recovery snapshot",
+        "recovery_snapshot_error" => "Failed to update recovery snapshot",
+        "generate_sample" => "Generate Sample Data...",
+        "sample_generations" => "Generations:",
+        "sample_children_per_couple" => "Children per couple:",
+        "sample_name_locale" => "Names:",
+        "sample_seed" => "Seed:",
+        "sample_include_photos" => "Include photos",
+        "sample_include_events" => "Include wedding events",
+        "sample_wedding_event" => "Wedding",
+        "sample_replaces_tree" => "The current tree will be replaced. Unsaved changes are offered for saving first.",
+        "generate" => "Generate",
+        "sample_generated" => "Sample data generated (persons)",
         "add_new_person" => "➕ Add New Person",
+        "insert_couple_with_child" => "Insert Couple with Child",
+        "template_father" => "Father",
+        "template_mother" => "Mother",
+        "template_child" => "Child",
+        "couple_with_child_inserted" => "Inserted a couple with a child",
+        "persons_list" => "Persons List",
+        "list_view" => "List",
+        "table_view" => "Table",
+        "search" => "Search:",
+        "filter" => "Filter:",
+        "filter_all" => "All",
+        "filter_alive_only" => "Alive only",
+        "filter_deceased_only" => "Deceased only",
+        "filter_no_birth_date" => "No birth date",
+        "filter_has_photo" => "Has photo",
+        "filter_in_family" => "In family",
+        "clear_filter" => "Clear filter",
+        "canvas_filter_hint" => "Filter canvas (e.g. tag:paternal alive:true)",
+        "canvas_filter_help" => "Persons not matching are dimmed. Conditions: tag:<family name>, alive:true/false, deceased:true/false, gender:male/female/unknown/other, photo:true/false, pinned:true/false, locked:true/false. Other words search names.",
+        "canvas_filter_unknown_key" => "Unknown condition",
+        "canvas_filter_invalid_value" => "Invalid value",
+        "apply_filter_to_canvas" => "Apply filter to canvas",
+        "bulk_edit" => "Bulk Edit",
+        "bulk_edit_family" => "Add to family:",
+        "bulk_edit_keep" => "(unchanged)",
+        "bulk_edit_preview" => "Changes to apply",
+        "bulk_edit_no_changes" => "The selected persons already have these values.",
+        "yes" => "Yes",
+        "no" => "No",
+        "apply" => "Apply",
+        "undo_bulk_edit" => "Undo Bulk Edit",
+        "bulk_edit_done" => "Persons updated by bulk edit",
+        "column_name" => "Name",
+        "column_birth" => "Birth",
+        "column_death" => "Death",
+        "column_gender" => "Gender",
+        "column_children" => "Children",
+        "column_family" => "Family",
+        "selection_back" => "Back to previously selected person",
+        "selection_forward" => "Forward to next selected person",
         "person_editor" => "Person Editor",
         "name" => "Name:",
         "gender" => "Gender:",
         "male" => "Male",
         "female" => "Female",
         "unknown" => "Unknown",
+        "slideshow" => "Photo Slideshow",
+        "slideshow_no_photos" => "No photos to show",
+        "slideshow_photo_missing" => "Photo could not be loaded",
+        "slideshow_play" => "Play",
+        "slideshow_pause" => "Pause",
+        "slideshow_interval" => "Interval",
+        "seconds_suffix" => " s",
+        "other_gender" => "Other",
+        "gender_label" => "Gender Label:",
         "birth" => "Birth:",
         "deceased" => "Deceased",
         "death" => "Death:",
@@ -41,11 +337,67 @@ pub fn translate(key: &str) -> String {
         "update" => "Update",
         "cancel" => "Cancel",
         "delete" => "Delete",
+        "delete_confirm" => "Person to delete",
+        "delete_parent_child_links" => "Parent-child relations removed",
+        "delete_spouse_links" => "Spouse relations removed",
+        "delete_person_relation_links" => "Other relations removed",
+        "delete_event_links" => "Event links removed",
+        "delete_affected_families" => "Removed from families",
+        "delete_placeholder_relatives" => "Also delete relatives left unconnected",
+        "delete_placeholder_relatives_hint" => "Parents, children and spouses with no dates, memo, photo or places and no other connections.",
+        "event_templates" => "Event templates:",
+        "linked_tree" => "Linked tree:",
+        "session_edits" => "This session",
+        "session_minutes" => " min",
+        "session_persons_added" => "Persons added",
+        "session_persons_edited" => "Persons edited",
+        "session_persons_removed" => "Persons deleted",
+        "session_relations_added" => "Relations added",
+        "session_relations_removed" => "Relations removed",
+        "session_events_added" => "Events added",
+        "session_events_removed" => "Events deleted",
+        "session_no_edits" => "No edits yet",
+        "session_copy_summary" => "Copy summary",
+        "log_session_summary" => "Session summary",
+        "linked_tree_hint" => "File path or URL of another tree",
+        "choose_linked_tree" => "Browse…",
+        "linked_person_id" => "Person ID there:",
+        "open_linked_tree" => "Open linked tree",
+        "linked_tree_not_found" => "Linked tree file not found",
+        "linked_person_not_found" => "Person not found in the linked tree",
+        "apply_event_template" => "Create events",
+        "event_template_hint" => "Creates dated events from the birth or death date and links them to this person. Events that already exist are skipped.",
+        "event_template_applied" => "Events created from template",
+        "event_template_nothing_new" => "All events of this template already exist",
+        "event_template_missing_date" => "This template needs a birth or death date",
+        "template_memorial_services" => "Memorial services",
+        "template_longevity_celebrations" => "Longevity celebrations",
+        "memorial_7th_day" => "Shonanoka (7th day)",
+        "memorial_49th_day" => "Shijūkunichi (49th day)",
+        "memorial_1st" => "1st memorial (Isshūki)",
+        "memorial_3rd" => "3rd memorial (Sankaiki)",
+        "memorial_7th" => "7th memorial (Nanakaiki)",
+        "memorial_13th" => "13th memorial (Jūsankaiki)",
+        "memorial_17th" => "17th memorial (Jūshichikaiki)",
+        "memorial_23rd" => "23rd memorial (Nijūsankaiki)",
+        "memorial_27th" => "27th memorial (Nijūshichikaiki)",
+        "memorial_33rd" => "33rd memorial (Sanjūsankaiki)",
+        "longevity_60" => "Kanreki (60)",
+        "longevity_70" => "Koki (70)",
+        "longevity_77" => "Kiju (77)",
+        "longevity_80" => "Sanju (80)",
+        "longevity_88" => "Beiju (88)",
+        "longevity_90" => "Sotsuju (90)",
+        "longevity_99" => "Hakuju (99)",
+        "log_placeholder_relatives_deleted" => "Placeholder relatives deleted",
         "relations" => "Relations:",
         "father" => "Father:",
         "mother" => "Mother:",
         "parent" => "Parent:",
         "spouses" => "Spouses:",
+        "children" => "Children:",
+        "children_with" => "With",
+        "children_other_parent_unknown" => "Other parent unknown",
         "add_relations" => "Add Relations:",
         "add_parent" => "Add Parent:",
         "add_child" => "Add Child:",
@@ -64,9 +416,32 @@ pub fn translate(key: &str) -> String {
         "no_members" => "(No members)",
         "no_family_selected" => "(No family selected)",
         "add_member" => "Add member:",
+        "family_defaults" => "Defaults for this family",
+        "family_defaults_hint" => "Used when adding a new person from this family.",
+        "family_default_kind" => "Parent-child kind",
+        "family_default_kind_global" => "Same as settings",
+        "family_default_surname" => "Surname for new children",
+        "family_label_color" => "Label color",
+        "new_family_member" => "New person in family:",
+        "new_family_member_parent" => "Child of:",
+        "new_family_member_no_parent" => "(none)",
         "delete_family" => "Delete Family",
         "grid" => "Grid:",
         "show_grid" => "Show Grid",
+        "grid_style_lines" => "Lines",
+        "grid_style_dots" => "Dots",
+        "grid_major_every" => "Major line every (cells):",
+        "grid_major_every_hint" => "0 draws all lines the same",
+        "snap_while_dragging" => "Snap while dragging",
+        "snap_strength" => "Snap strength:",
+        "snap_strength_hint" => "1.0 always snaps to the nearest grid point; lower values only snap when close to one; 0 disables snapping",
+        "show_rulers" => "Show rulers and guides",
+        "show_rulers_hint" => "Drag from a ruler to add a guide; drag a guide back onto a ruler to remove it. Dropped nodes snap to nearby guides.",
+        "canvas_guides" => "Guides",
+        "clear_guides" => "Remove All Guides",
+        "log_guide_moved" => "Guide moved",
+        "log_guide_added" => "Guide added",
+        "log_guide_removed" => "Guide removed",
         "grid_size" => "Grid Size:",
         "layout" => "Layout:",
         "reset_positions" => "Reset All Positions",
@@ -85,6 +460,121 @@ pub fn translate(key: &str) -> String {
         "edit_memo" => "Edit memo",
         "edit_kind" => "Edit kind",
         "relation_kind_updated" => "Relation kind updated",
+        "adoption_date" => "Adoption Date:",
+        "adoption_type" => "Adoption Type:",
+        "adoption_note" => "Adoption Note:",
+        "adoption_before_birth" => "Adoption date is before the child's birth date",
+        "step_parents" => "Step-parents:",
+        "step_children" => "Step-children:",
+        "derived_relation" => "(derived from spouse)",
+        "person_id" => "ID:",
+        "copy_id" => "Copy ID",
+        "person_search_hint" => "Search by name, or paste a short ID and press Enter to select that person",
+        "person_found" => "Person selected",
+        "export_poster" => "Export Poster (SVG)...",
+        "export_vcard" => "Export Selected as vCard...",
+        "export_vcard_no_selection" => "Select one or more persons first",
+        "export_icalendar" => "Export Birthdays & Anniversaries (iCalendar)...",
+        "export_html_viewer" => "Export HTML Viewer",
+        "export_html_viewer_hint" => "A single HTML file that relatives can open in any browser to explore the tree (read-only).",
+        "export_html_viewer_with_photos" => "With Photos (larger file)…",
+        "export_html_viewer_without_photos" => "Without Photos…",
+        "file_filter_html" => "HTML Files",
+        "html_viewer_birth" => "Born",
+        "html_viewer_death" => "Died",
+        "html_viewer_parents" => "Parents",
+        "html_viewer_children" => "Children",
+        "html_viewer_spouses" => "Spouses",
+        "html_viewer_hint" => "Drag to move, wheel to zoom, click a person for details",
+        "html_viewer_search" => "Search names",
+        "html_viewer_exported" => "HTML viewer exported",
+        "log_html_viewer_exported" => "HTML viewer exported",
+        "export_descendant_outline" => "Export Descendant Outline...",
+        "export_descendant_outline_hint" => "Write the descendants of the selected person (or the home person) as an indented list with dates (.txt or .md)",
+        "export_text_chart_no_selection" => "Select a person or set a home person first",
+        "export_pedigree_chart" => "Export Pedigree Chart",
+        "export_pedigree_chart_hint" => "Write the ancestors of the selected person (or the home person) with Ahnentafel numbers (father 2n, mother 2n+1) as .txt or .md",
+        "pedigree_generations" => "generations...",
+        "export_poster_title" => "Export Tiled Poster",
+        "paper_size" => "Paper Size:",
+        "landscape" => "Landscape",
+        "poster_columns" => "Columns:",
+        "poster_rows" => "Rows:",
+        "poster_total_size" => "Total size",
+        "export" => "Export",
+        "poster_exported" => "Poster pages exported",
+        "export_failed" => "Export failed",
+        "file_filter_svg" => "SVG Image",
+        "file_filter_vcard" => "vCard",
+        "default_vcard_file_name" => "contacts",
+        "vcard_exported" => "vCard exported",
+        "file_filter_text" => "Text",
+        "file_filter_markdown" => "Markdown",
+        "default_descendant_outline_file_name" => "descendants",
+        "descendant_outline_exported" => "Descendant outline exported",
+        "default_pedigree_chart_file_name" => "pedigree",
+        "pedigree_chart_exported" => "Pedigree chart exported",
+        "file_filter_icalendar" => "iCalendar",
+        "default_calendar_file_name" => "family_calendar",
+        "calendar_birthday" => "Birthday",
+        "calendar_anniversary" => "Wedding anniversary",
+        "calendar_exported" => "Calendar exported",
+        "default_export_file_name" => "family_tree",
+        "export_image" => "Export Image (SVG)...",
+        "export_image_title" => "Export Image",
+        "export_scale" => "Scale:",
+        "export_dpi" => "DPI:",
+        "export_preset_screen" => "Screen (96 DPI)",
+        "export_preset_print" => "Print (300 DPI)",
+        "export_layers" => "Layers:",
+        "layer_grid" => "Grid",
+        "layer_family_boxes" => "Family boxes",
+        "layer_events" => "Events",
+        "export_output_size" => "Output size",
+        "image_exported" => "Image exported",
+        "include_title_block" => "Include title block",
+        "title_block_title" => "Title:",
+        "title_block_author" => "Author:",
+        "title_block_date" => "Date:",
+        "title_block_generations" => "Generations:",
+        "watermark" => "Watermark:",
+        "watermark_hint" => "e.g. DRAFT",
+        "person_relations" => "Other Relations:",
+        "add_person_relation" => "Add Guardian/Godparent/Mentor:",
+        "person_relation_added" => "Relation added",
+        "relation_guardian" => "Guardian",
+        "relation_ward" => "Ward",
+        "relation_godparent" => "Godparent",
+        "relation_godchild" => "Godchild",
+        "relation_mentor" => "Mentor",
+        "relation_mentee" => "Mentee",
+        "multiple_birth" => "Multiple Birth (Twins etc.):",
+        "person_badges" => "Badges:",
+        "home_person" => "Home person",
+        "home_person_hint" => "The person the Home button returns to",
+        "home_person_button" => "Home",
+        "go_to_home_person" => "Go to home person",
+        "log_home_person_set" => "Home person set",
+        "log_home_person_cleared" => "Home person cleared",
+        "badge_military" => "Military service",
+        "badge_emigrated" => "Emigrated",
+        "badge_direct_ancestor" => "Direct ancestor",
+        "badge_clergy" => "Clergy",
+        "badge_nobility" => "Nobility",
+        "log_badge_toggled" => "Badge toggled",
+        "add_multiple_birth" => "Same birth as:",
+        "remove_multiple_birth" => "Remove from multiple birth",
+        "mark_selected_multiple_birth" => "Mark selected persons as multiple birth",
+        "multiple_birth_updated" => "Multiple birth updated",
+        "multiple_birth_date_mismatch" => "Birth dates of the multiple birth siblings do not match",
+        "spouse_editor" => "Spouse Relation",
+        "spouse_label_visible" => "Show memo on the canvas",
+        "spouse_label_above" => "Above the line",
+        "spouse_label_below" => "Below the line",
+        "marriage_date" => "Marriage Date:",
+        "divorced" => "Divorced",
+        "divorce_date" => "Divorce Date:",
+        "spouse_relation_updated" => "Spouse relation updated",
         "new_family_added" => "New family added",
         "member_removed" => "Member removed",
         "member_added" => "Member added",
@@ -92,11 +582,16 @@ pub fn translate(key: &str) -> String {
         "family_deleted" => "Family deleted",
         "positions_reset" => "All positions reset",
         "saved" => "Saved",
+        "newer_format_read_only" => "This file was saved by a newer version. Opened in read-only mode; unknown data is kept when saving",
         "loaded" => "Loaded",
         "edit" => "Edit:",
         "remove_relation" => "Remove relation",
         "selected_family" => "Selected family:",
-        "new_person" => "New Person",
+        "new_person" => "New Person {n}",
+        "new_person_defaults" => "New person defaults",
+        "new_person_name_template" => "Name",
+        "new_person_name_template_hint" => "Used by \"Add New Person\". {n} becomes the next free number.",
+        "new_person_relation_kind" => "Parent-child kind",
         "new_family" => "New Family",
         "tooltip_name" => "Name",
         "tooltip_birth" => "Birth",
@@ -106,7 +601,31 @@ pub fn translate(key: &str) -> String {
         "tooltip_deceased" => "Deceased",
         "tooltip_yes" => "Yes",
         "tooltip_memo" => "Memo",
+        "tooltip_step_parents" => "Step-parents",
+        "tooltip_step_children" => "Step-children",
+        "tooltip_parents" => "Parents",
+        "tooltip_spouses" => "Spouses",
+        "tooltip_children" => "Children",
+        "tooltip_tags" => "Tags",
+        "tooltip_presumed_deceased" => "Presumed deceased (no death record, born long ago)",
+        "presumed_deceased_marker" => "presumed †",
         "help_menu" => "Help",
+        "shortcuts" => "Keyboard Shortcuts",
+        "error_log_location" => "Details were written to",
+        "shortcut_category_file" => "File",
+        "shortcut_category_edit" => "Edit",
+        "shortcut_category_navigation" => "Navigation",
+        "shortcut_category_canvas" => "Canvas",
+        "shortcut_category_help" => "Help",
+        "shortcut_zoom" => "Zoom around the cursor",
+        "shortcut_pan" => "Pan the canvas",
+        "shortcut_multi_select" => "Add to / remove from selection",
+        "gesture_ctrl_wheel" => "Ctrl+Wheel / Pinch",
+        "gesture_drag_background" => "Drag background / Two-finger swipe",
+        "gesture_double_click_background" => "Double-click background",
+        "gesture_ctrl_click" => "Ctrl+Click",
+        "gesture_ctrl_number" => "Ctrl+1 – 9",
+        "gesture_right_click_background" => "Right-click background",
         "about" => "About",
         "license" => "License",
         "app_name" => "Family Tree Creator",
@@ -114,7 +633,9 @@ pub fn translate(key: &str) -> String {
         "app_description" => "This application is a tool for creating and managing family trees.",
         "license_text" => include_str!("../../../LICENSE"),
         "close" => "Close",
+        "copy" => "Copy",
         "events" => "📅 Events",
+        "annotations" => "📝 Notes",
         "manage_events" => "Manage Events",
         "add_new_event" => "➕ Add New Event",
         "event_editor" => "Event Editor",
@@ -128,6 +649,34 @@ pub fn translate(key: &str) -> String {
         "arrow_to_person" => "Arrow → Person",
         "arrow_to_event" => "Arrow ← Person",
         "new_event_added" => "New event added",
+        "places" => "📍 Places",
+        "manage_places" => "Manage Places",
+        "add_new_place" => "Add New Place",
+        "new_place" => "New Place",
+        "new_place_added" => "New place added",
+        "no_places" => "No places yet",
+        "place_editor" => "Place Editor",
+        "place" => "Place",
+        "no_place" => "(none)",
+        "birth_place" => "Birth place",
+        "death_place" => "Death place",
+        "event_place" => "Event place",
+        "latitude" => "Latitude",
+        "longitude" => "Longitude",
+        "invalid_coordinates" => "Latitude must be between -90 and 90 and longitude between -180 and 180",
+        "place_updated" => "Place updated",
+        "place_deleted" => "Place deleted",
+        "merge_place_into" => "Merge into",
+        "merge_places" => "Merge",
+        "merge_places_preview" => "References to move",
+        "merge_places_hint" => "Replace this place with the chosen one everywhere and delete it",
+        "places_merged" => "Places merged",
+        "duplicate_places" => "Possible spelling variants",
+        "merge_duplicate_places_hint" => "Merge these places into the first one",
+        "log_place_added" => "Added place",
+        "log_place_updated" => "Updated place",
+        "log_place_deleted" => "Deleted place",
+        "log_places_merged" => "Merged places",
         "event_updated" => "Event updated",
         "event_deleted" => "Event deleted",
         "relation_added" => "Relation added",
@@ -135,12 +684,63 @@ pub fn translate(key: &str) -> String {
         "display_mode" => "Display Mode:",
         "name_only" => "Name Only",
         "name_and_photo" => "Name and Photo",
+        "photo_only" => "Photo Only (name on hover)",
         "choose_photo" => "Choose Photo...",
+        "secondary_backup" => "Secondary Backup",
+        "secondary_backup_hint" => "Every save also writes a timestamped copy to this folder (e.g. a synced cloud folder).",
+        "secondary_backup_disabled" => "Not set (disabled)",
+        "choose_backup_dir" => "Choose Folder...",
+        "secondary_backup_keep" => "Copies to keep per file",
+        "backup_failed" => "Failed to write backup copy",
+        "log_backup_written" => "Backup copy written",
         "clear_photo" => "Clear Photo",
+        "edit_photo" => "Crop / Rotate...",
+        "rotate_left" => "Rotate Left",
+        "rotate_right" => "Rotate Right",
+        "reset_crop" => "Reset Crop",
+        "photo_crop_hint" => "Drag on the photo to select the area to keep",
+        "use_original_photo" => "Use Original",
+        "photo_edited" => "Edited photo saved to the photo folder",
+        "photo_edit_load_failed" => "Could not open photo",
+        "photo_edit_save_failed" => "Could not save edited photo",
+        "log_photo_edited" => "Edited photo saved",
         "photo_scale" => "Photo Scale:",
         "node_color_theme" => "Node Color Theme:",
         "node_color_theme_default" => "Default",
         "node_color_theme_high_contrast" => "High Contrast",
+        "high_contrast_ui" => "High contrast UI",
+        "high_contrast_ui_hint" => "Black background, white text and yellow focus outlines for the whole window",
+        "ui_font" => "UI font",
+        "ui_font_bundled" => "Bundled (Noto Sans JP)",
+        "ui_font_system" => "System font (auto-detect)",
+        "ui_font_system_not_found" => "No Japanese system font found; using the bundled font",
+        "ui_font_size" => "Text size",
+        "ui_font_size_reset" => "Default",
+        "next_side_tab" => "Next side panel tab",
+        "previous_side_tab" => "Previous side panel tab",
+        "shortcut_focus_persons" => "Move focus between controls and people on the canvas",
+        "gesture_tab" => "Tab / Shift+Tab",
+        "node_style" => "Photo Node Style (saved with the tree)",
+        "node_style_box" => "Box",
+        "node_style_circular_avatar" => "Circular avatar",
+        "layout_direction" => "Automatic Layout Direction",
+        "layout_direction_ltr" => "Left to right",
+        "layout_direction_rtl" => "Right to left (mirrored)",
+        "edge_style" => "Parent-Child Lines",
+        "edge_style_straight" => "Straight",
+        "edge_style_bezier" => "Curved (Bezier)",
+        "bezier_control_offset" => "Curve strength:",
+        "semantic_zoom" => "Semantic Zoom",
+        "semantic_zoom_hint" => "Below each zoom level the node content is dropped",
+        "semantic_zoom_photo" => "Photo:",
+        "semantic_zoom_dates" => "Dates:",
+        "semantic_zoom_name" => "Name:",
+        "other_gender_color" => "Node color for other gender:",
+        "presumed_deceased" => "Presumed Deceased",
+        "presume_deceased" => "Treat persons born long ago as deceased",
+        "presumed_deceased_years" => "Years since birth:",
+        "relation_display" => "Relation Display",
+        "show_step_relations" => "Show derived step-relations",
         // Log messages
         "log_app_started" => "Application started",
         "log_file_saved" => "File saved",
@@ -169,17 +769,34 @@ pub fn translate(key: &str) -> String {
         "log_family_selected" => "Family selected",
         "log_family_member_added" => "Member added to family",
         "log_family_member_removed" => "Member removed from family",
+        "log_spouse_relation_updated" => "Spouse relation updated",
+        "log_adoption_before_birth" => "Adoption date before birth",
+        "log_multiple_birth_date_mismatch" => "Multiple birth date mismatch",
+        "log_poster_exported" => "Poster exported",
+        "log_image_exported" => "Image exported",
+        "log_vcard_exported" => "vCard exported",
+        "log_descendant_outline_exported" => "Descendant outline exported",
+        "log_pedigree_chart_exported" => "Pedigree chart exported",
+        "log_calendar_exported" => "Calendar exported",
+        "log_background_image_set" => "Background image set",
+        "log_annotation_added" => "Annotation added",
+        "log_annotation_updated" => "Annotation updated",
+        "log_annotation_deleted" => "Annotation deleted",
+        "log_sticky_note_added" => "Sticky note added",
         "log_from" => "from",
         "log_to" => "to",
         "log_settings_loaded" => "Settings file loaded",
+        "log_system_font_loaded" => "Loaded system font",
+        "log_system_font_not_found" => "No Japanese system font found; using the bundled font",
         "log_settings_load_failed" => "Failed to load settings file",
         "log_settings_save_failed" => "Failed to save settings file",
         "log_panel_title" => "📋 Log",
+        "log_filter_hint" => "Filter messages",
+        "log_copy_hint" => "Copy the visible messages to the clipboard",
+        "missing_translations" => "Missing translations",
         _ => {
             if cfg!(debug_assertions) {
-                let warning = format!("[i18n Warning] Unknown translation key (en): '{}'", key);
-                eprintln!("{}", warning);
-                add_warning(warning);
+                report_missing_key(key, Language::English);
             }
             key
         }
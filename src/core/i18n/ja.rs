@@ -1,7 +1,7 @@
 /// 日本語翻訳
 /// Japanese translations
 
-use super::add_warning;
+use super::{report_missing_key, Language};
 
 pub fn translate(key: &str) -> String {
     match key {
@@ -9,8 +9,119 @@ pub fn translate(key: &str) -> String {
         "persons" => "👤 人物",
         "families" => "👪 家族",
         "settings" => "⚙ 設定",
+        "statistics_tab" => "📊 統計",
+        "statistics" => "統計",
+        "statistics_hint" => "棒にマウスを乗せると該当する人物を表示します。クリックすると一覧を表示します。",
+        "chart_lifespan" => "寿命（歳）",
+        "chart_births_per_decade" => "年代別の出生数",
+        "chart_children_per_couple" => "夫婦ごとの子の人数",
+        "chart_decade_suffix" => "年代",
+        "chart_no_data" => "データが足りません",
         "file_menu" => "ファイル",
         "view_menu" => "表示",
+        "edit_menu" => "編集",
+        "undo" => "元に戻す",
+        "redo" => "やり直す",
+        "undone" => "元に戻しました",
+        "redone" => "やり直しました",
+        "undo_kept_later_edits" => "後から編集された項目はそのまま残しました",
+        "find_replace" => "検索と置換",
+        "auto_arrange" => "人物を自動整列",
+        "auto_arrange_hint" => "世代ごとに夫婦を隣り合わせて並べます。固定した人物は動かしません。",
+        "auto_arrange_preview" => "自動整列のプレビュー – 動かす人物",
+        "auto_arrange_nothing_to_move" => "すべての人物が整列済みです",
+        "auto_arranged" => "人物を整列しました",
+        "log_auto_arranged" => "人物を自動整列",
+        "pin_person" => "📌 位置を固定",
+        "unpin_person" => "位置の固定を解除",
+        "lock_person" => "🔒 位置をロック",
+        "unlock_person" => "位置のロックを解除",
+        "person_locked_hint" => "この人物はロックされています。動かすには右クリックメニューからロックを解除してください。",
+        "find_text" => "検索:",
+        "replace_text" => "置換後:",
+        "case_sensitive" => "大文字と小文字を区別",
+        "replace_scope" => "対象:",
+        "replace_person_names" => "人物名",
+        "replace_person_memos" => "人物のメモ",
+        "replace_relation_kinds" => "関係の種類",
+        "replace_event_descriptions" => "イベントの説明",
+        "affected_entries" => "変更される項目",
+        "replace_all" => "すべて置換",
+        "undo_replace" => "直前の置換を元に戻す",
+        "relation_cleanup" => "関係の整理…",
+        "relation_cleanup_hint" => "重複した配偶者関係、種類違いの親子関係、互いに矛盾する関係を探して、まとめて修正します。",
+        "photo_check" => "写真の確認…",
+        "photo_check_hint" => "見つからない・開けない写真を探し、新しいフォルダに付け直すか参照を解除します。",
+        "photo_check_no_issues" => "すべての写真を開けます。",
+        "photo_check_found" => "表示できない写真",
+        "photo_check_missing" => "ファイルがありません",
+        "photo_check_undecodable" => "画像として読めません",
+        "photo_check_relocate" => "付け直す…",
+        "photo_check_relocate_hint" => "フォルダを選ぶと、サブフォルダも含めて同じファイル名の写真に付け直します。",
+        "photo_check_clear" => "参照を解除",
+        "photo_check_relocated" => "写真を付け直しました",
+        "photo_check_cleared" => "写真の参照を解除しました",
+        "cleanup_no_issues" => "重複・矛盾した関係は見つかりませんでした",
+        "cleanup_found" => "見つかった問題",
+        "cleanup_select_all" => "すべて選択",
+        "cleanup_select_none" => "選択解除",
+        "cleanup_apply" => "選択した修正を適用",
+        "cleanup_rescan" => "再検査",
+        "cleanup_undo" => "直前の整理を元に戻す",
+        "cleanup_applied" => "関係を修正しました",
+        "cleanup_duplicate_spouse" => "配偶者関係の重複",
+        "cleanup_redundant_parent" => "親子関係の重複",
+        "cleanup_mutual_parent" => "互いに親になっている",
+        "cleanup_parent_and_spouse" => "親子が配偶者にもなっている",
+        "cleanup_self_relation" => "自分自身との関係",
+        "cleanup_fix_keep_first" => "最初の1件を残して削除",
+        "cleanup_fix_keep_kind" => "残す種類",
+        "cleanup_fix_remove_parent" => "親子関係を削除",
+        "cleanup_fix_remove_spouse" => "配偶者関係を削除",
+        "cleanup_fix_remove_self" => "関係を削除",
+        "date_normalization" => "日付の一括正規化…",
+        "date_normalization_hint" => "1990/5/15・H2.5.15・May 15 1990 などの日付を YYYY-MM-DD 形式に書き換え、読み取れない日付を一覧にします。",
+        "edit_macros" => "マクロ…",
+        "edit_macros_hint" => "関係の編集を一度記録し、選択中の人物に繰り返し適用します。",
+        "macro_start_recording" => "記録開始",
+        "macro_stop_recording" => "停止",
+        "macro_recording" => "記録中",
+        "macro_recording_hint" => "親・子・配偶者の追加、親子の種類の変更、家族への追加を行ってください。操作した人物を基準に記録します。",
+        "macro_name" => "名前:",
+        "macro_save" => "マクロを保存",
+        "macro_saved" => "マクロを保存しました",
+        "macro_targets" => "適用する人物",
+        "macro_none_saved" => "この家系図に保存されたマクロはありません",
+        "macro_run" => "実行",
+        "macro_applied" => "マクロの操作を適用しました",
+        "macro_undo" => "直前の実行を元に戻す",
+        "macro_step_add_parent" => "親を追加",
+        "macro_step_add_child" => "子を追加",
+        "macro_step_add_spouse" => "配偶者を追加",
+        "macro_step_set_parent_kind" => "親子関係の種類を変更",
+        "macro_step_add_to_family" => "家族に追加",
+        "log_macro_saved" => "マクロを保存",
+        "log_macro_applied" => "マクロを実行",
+        "log_macro_deleted" => "マクロを削除",
+        "date_normalization_formats" => "対応する形式: 1990/5/15・1990年5月15日・H2.5.15・平成2年5月15日・May 15 1990・15 May 1990。YYYY・YYYY-MM・YYYY-MM-DD 形式に直します。",
+        "dates_to_normalize" => "書き換える日付",
+        "dates_unparseable" => "読み取れない日付",
+        "show_unparseable_only" => "読み取れない日付だけを表示",
+        "date_field" => "項目",
+        "date_owner" => "対象",
+        "date_before" => "現在",
+        "date_after" => "正規化後",
+        "date_field_birth" => "生年月日",
+        "date_field_death" => "没年月日",
+        "date_field_marriage" => "結婚",
+        "date_field_divorce" => "離婚",
+        "date_field_adoption" => "養子縁組",
+        "date_field_event" => "イベント",
+        "date_unparseable" => "⚠ 読み取れません",
+        "apply_date_normalization" => "日付を書き換える",
+        "undo_date_normalization" => "直前の書き換えを元に戻す",
+        "dates_normalized" => "日付を正規化しました",
+        "replace_done" => "置換した項目数",
         "new" => "新規",
         "open" => "開く",
         "save" => "保存",
@@ -20,20 +131,205 @@ pub fn translate(key: &str) -> String {
         "load_error" => "読み込みエラー",
         "file_filter_family_tree" => "家系図ファイル",
         "file_filter_json" => "JSON",
+        "file_filter_yaml" => "YAML",
         "file_filter_sqlite" => "SQLite",
+        "file_filter_gramps" => "Gramps XML",
+        "file_filter_gedcom" => "GEDCOM",
         "file_filter_images" => "画像",
         "default_file_name" => "tree.json",
         "count_suffix" => "個",
         "fit_to_view" => "全体表示",
+        "background_image" => "背景画像",
+        "layout_snapshots" => "保存した配置",
+        "layout_snapshot_name" => "配置の名前",
+        "save_layout_snapshot" => "現在の配置を保存",
+        "save_layout_snapshot_hint" => "すべての人物とイベントの位置をこの名前で保存します。同じ名前の配置は上書きされます。",
+        "apply_layout_snapshot" => "この配置に切り替える",
+        "delete_layout_snapshot_title" => "この保存した配置を削除しますか？",
+        "no_layout_snapshots" => "保存した配置はありません",
+        "camera_bookmarks" => "保存した表示位置",
+        "place_map" => "出生地・死亡地の地図…",
+        "place_map_show_deaths" => "死亡地",
+        "place_map_show_migrations" => "移住",
+        "place_map_show_migrations_hint" => "親の出生地から子の出生地へ矢印を引きます。",
+        "place_map_no_pins" => "座標のある出生地・死亡地がありません。場所タブで緯度と経度を設定してください。",
+        "camera_bookmark_name" => "表示位置の名前",
+        "save_camera_bookmark" => "現在の表示位置を保存",
+        "save_camera_bookmark_hint" => "現在のスクロール位置とズームをこの名前で保存します。同じ名前の表示位置は上書きされます。",
+        "no_camera_bookmarks" => "保存した表示位置はありません",
+        "camera_bookmark_saved" => "表示位置を保存しました",
+        "log_camera_bookmark_saved" => "表示位置を保存",
+        "log_camera_bookmark_deleted" => "表示位置を削除",
+        "layout_snapshot_saved" => "配置を保存しました",
+        "layout_snapshot_applied" => "配置を切り替えました",
+        "log_layout_snapshot_saved" => "配置を保存",
+        "log_layout_snapshot_applied" => "配置を切り替え",
+        "log_layout_snapshot_deleted" => "配置を削除",
+        "choose_background_image" => "画像を選択...",
+        "background_opacity" => "不透明度",
+        "background_scale" => "倍率",
+        "background_position" => "位置:",
+        "remove_background_image" => "背景を削除",
+        "background_image_set" => "背景画像を設定しました",
+        "background_image_removed" => "背景画像を削除しました",
+        "manage_annotations" => "注釈の管理",
+        "add_new_annotation" => "➕ 新しい注釈を追加",
+        "annotation_editor" => "注釈の編集",
+        "no_annotations" => "注釈はまだありません",
+        "annotation_text" => "文字列:",
+        "font_size" => "文字サイズ:",
+        "annotation_text_required" => "注釈の文字列を入力してください",
+        "annotation_added" => "注釈を追加しました",
+        "annotation_updated" => "注釈を更新しました",
+        "annotation_deleted" => "注釈を削除しました",
+        "show_all_notes" => "すべての付箋を表示",
+        "show_life_years" => "ノードに生没年を表示",
+        "life_years_born_prefix" => "",
+        "life_years_born_suffix" => "年生",
+        "show_detail_panel" => "詳細パネルを表示",
+        "detail_panel" => "詳細",
+        "detail_panel_empty" => "人物・家族・イベントを選択すると、ここに詳細を表示します。",
+        "open_in_editor" => "編集",
+        "detail_relation" => "関係",
+        "detail_back" => "戻る",
+        "detail_families" => "家族",
+        "detail_events" => "イベント",
+        "detail_related_persons" => "関係する人物",
+        "relation_kind_biological" => "実親",
+        "relation_kind_adoptive" => "養親",
+        "relation_kind_foster" => "里親",
+        "read_only_mode" => "閲覧モード",
+        "read_only_mode_hint" => "編集を無効にし、家系図の閲覧・検索・書き出しだけを行えるようにします",
+        "toggle_sticky_notes" => "クリックで付箋を開閉",
+        "sticky_notes" => "付箋:",
+        "sticky_note_resolved" => "対応済み",
+        "add_sticky_note" => "付箋を追加",
+        "sticky_note_added" => "付箋を追加しました",
+        "sticky_note_deleted" => "付箋を削除しました",
+        "outstanding_notes" => "未対応の付箋",
+        "no_outstanding_notes" => "未対応の付箋はありません",
         "fit_to_view_done" => "全体表示を実行しました",
+        "zoom_fit_width" => "幅に合わせる",
+        "zoom_fit_width_done" => "幅に合わせて表示しました",
+        "zoom_reset" => "ズームを100%に戻す",
+        "copy_as_image" => "画像としてコピー",
+        "copy_as_image_hint" => "選択中の人物（選択がなければ表示中のキャンバス）を画像としてクリップボードにコピーします",
+        "copy_as_image_done" => "画像としてコピーしました",
+        "copy_as_image_not_visible" => "選択中の人物がキャンバスに表示されていません",
+        "copy_as_image_timed_out" => "画像としてコピーできませんでした（スクリーンショットを取得できません）",
+        "show_on_canvas" => "キャンバスで表示",
         "new_tree_created" => "新しい家系図を作成しました",
+        "unsaved_changes_title" => "変更を保存しますか？",
+        "unsaved_changes_message" => "家系図に保存されていない変更があります。続行する前に保存しますか？",
+        "discard_changes" => "保存しない",
+        "file_locked_title" => "他のウィンドウで開かれています",
+        "file_locked_open_message" => "このファイルは別のインスタンスで編集中です。保存すると相手の変更を上書きする可能性があります。",
+        "file_locked_save_message" => "このファイルは別のインスタンスに引き継がれています。保存すると相手の変更を上書きする可能性があります。",
+        "file_locked_by" => "ロック中",
+        "file_locked_stale_hint" => "相手が異常終了した場合はロックが残っているだけなので、引き継いでかまいません。",
+        "take_over_lock" => "ロックを引き継ぐ",
+        "take_over_lock_and_save" => "ロックを引き継いで保存",
+        "continue_without_lock" => "ロックせずに続ける",
+        "file_lock_error" => "ファイルをロックできませんでした",
+        "overwrite_title" => "このファイルを上書きしますか？",
+        "overwrite_unknown_content" => "既存のファイルは、このアプリで判別できる家系図ファイルではありません。",
+        "overwrite_different_format" => "既存のファイルは、保存しようとしている形式と違う形式です。",
+        "overwrite_formats" => "形式",
+        "overwrite_unreadable" => "既存のファイルを読み込めませんでした。壊れているか、別のアプリで保存された可能性があります。",
+        "overwrite_other_tree" => "既存のファイルには、同じ人物が一人もいない別の家系図が入っています。",
+        "overwrite_existing_persons" => "ファイル内の人物",
+        "overwrite_anyway" => "上書きする",
+        "log_overwrite_warning" => "上書きの確認",
+        "log_file_locked" => "ファイルは別のインスタンスがロック中",
+        "window_title" => "家系図",
+        "untitled" => "無題",
+        "current_file" => "ファイル",
+        "status_persons" => "人物",
+        "status_relations" => "関係",
+        "status_events" => "イベント",
+        "status_zoom" => "ズーム",
+        "status_selected" => "選択中",
+        "status_unsaved" => "● 未保存の変更あり",
+        "status_no_changes" => "変更なし",
+        "status_last_saved" => "最終保存",
+        "status_not_saved_yet" => "このセッションでは未保存",
+        "status_just_now" => "たった今",
+        "status_minutes_ago" => "分前",
+        "status_hours_ago" => "時間前",
+        "recovery_title" => "未保存の作業を復元しますか？",
+        "recovery_message" => "前回は正常に終了しませんでした。作業内容の復旧用スナップショットが見つかりました。",
+        "recovery_saved_at" => "スナップショットの日時",
+        "recovery_restore" => "復元",
+        "recovery_discard" => "破棄",
+        "recovery_restored" => "復旧用スナップショットを復元しました",
+        "recovery_snapshot_error" => "復旧用スナップショットの更新に失敗しました",
+        "generate_sample" => "サンプルデータを生成...",
+        "sample_generations" => "世代数:",
+        "sample_children_per_couple" => "夫婦あたりの子の数:",
+        "sample_name_locale" => "名前:",
+        "sample_seed" => "シード:",
+        "sample_include_photos" => "写真を含める",
+        "sample_include_events" => "結婚イベントを含める",
+        "sample_wedding_event" => "結婚",
+        "sample_replaces_tree" => "現在の家系図は置き換えられます。未保存の変更があれば先に保存するか確認します。",
+        "generate" => "生成",
+        "sample_generated" => "サンプルデータを生成しました（人数）",
         "add_new_person" => "➕ 新しい人物を追加",
+        "insert_couple_with_child" => "夫婦と子を挿入",
+        "template_father" => "父",
+        "template_mother" => "母",
+        "template_child" => "子",
+        "couple_with_child_inserted" => "夫婦と子を挿入しました",
+        "persons_list" => "人物一覧",
+        "list_view" => "リスト",
+        "table_view" => "表",
+        "search" => "検索:",
+        "filter" => "絞り込み:",
+        "filter_all" => "すべて",
+        "filter_alive_only" => "存命のみ",
+        "filter_deceased_only" => "故人のみ",
+        "filter_no_birth_date" => "生年月日なし",
+        "filter_has_photo" => "写真あり",
+        "filter_in_family" => "家族",
+        "clear_filter" => "絞り込みを解除",
+        "canvas_filter_hint" => "キャンバスを絞り込み（例: tag:父方 alive:true）",
+        "canvas_filter_help" => "一致しない人物は薄く表示されます。条件: tag:<家族名>, alive:true/false, deceased:true/false, gender:male/female/unknown/other, photo:true/false, pinned:true/false, locked:true/false。それ以外の語は名前で検索します。",
+        "canvas_filter_unknown_key" => "不明な条件",
+        "canvas_filter_invalid_value" => "不正な値",
+        "apply_filter_to_canvas" => "キャンバスにも適用",
+        "bulk_edit" => "一括編集",
+        "bulk_edit_family" => "家族に追加:",
+        "bulk_edit_keep" => "（変更しない）",
+        "bulk_edit_preview" => "適用する変更",
+        "bulk_edit_no_changes" => "選択した人物はすべて既にこの内容です。",
+        "yes" => "はい",
+        "no" => "いいえ",
+        "apply" => "適用",
+        "undo_bulk_edit" => "一括編集を元に戻す",
+        "bulk_edit_done" => "一括編集で更新した人数",
+        "column_name" => "名前",
+        "column_birth" => "生年月日",
+        "column_death" => "没年月日",
+        "column_gender" => "性別",
+        "column_children" => "子の数",
+        "column_family" => "家族",
+        "selection_back" => "前に選択した人物に戻る",
+        "selection_forward" => "次に選択した人物に進む",
         "person_editor" => "人物エディタ",
         "name" => "名前:",
         "gender" => "性別:",
         "male" => "男性",
         "female" => "女性",
         "unknown" => "不明",
+        "slideshow" => "写真スライドショー",
+        "slideshow_no_photos" => "表示できる写真がありません",
+        "slideshow_photo_missing" => "写真を読み込めませんでした",
+        "slideshow_play" => "再生",
+        "slideshow_pause" => "一時停止",
+        "slideshow_interval" => "間隔",
+        "seconds_suffix" => " 秒",
+        "other_gender" => "その他",
+        "gender_label" => "性別の表記:",
         "birth" => "生年月日:",
         "deceased" => "故人",
         "death" => "没年月日:",
@@ -41,11 +337,67 @@ pub fn translate(key: &str) -> String {
         "update" => "更新",
         "cancel" => "キャンセル",
         "delete" => "削除",
+        "delete_confirm" => "削除する人物",
+        "delete_parent_child_links" => "削除される親子関係",
+        "delete_spouse_links" => "削除される配偶者関係",
+        "delete_person_relation_links" => "削除されるその他の関係",
+        "delete_event_links" => "削除されるイベントとのつながり",
+        "delete_affected_families" => "メンバーから外れる家族",
+        "delete_placeholder_relatives" => "つながりがなくなる親族も削除する",
+        "delete_placeholder_relatives_hint" => "日付・メモ・写真・場所が何もなく、ほかにつながりのない親・子・配偶者です。",
+        "event_templates" => "イベントのテンプレート:",
+        "linked_tree" => "リンク先の家系図:",
+        "session_edits" => "このセッションの編集",
+        "session_minutes" => "分",
+        "session_persons_added" => "追加した人物",
+        "session_persons_edited" => "編集した人物",
+        "session_persons_removed" => "削除した人物",
+        "session_relations_added" => "追加した関係",
+        "session_relations_removed" => "削除した関係",
+        "session_events_added" => "追加したイベント",
+        "session_events_removed" => "削除したイベント",
+        "session_no_edits" => "まだ編集はありません",
+        "session_copy_summary" => "要約をコピー",
+        "log_session_summary" => "セッションの編集",
+        "linked_tree_hint" => "別の家系図のファイルパスまたは URL",
+        "choose_linked_tree" => "参照…",
+        "linked_person_id" => "リンク先の人物ID:",
+        "open_linked_tree" => "リンク先の家系図を開く",
+        "linked_tree_not_found" => "リンク先の家系図ファイルが見つかりません",
+        "linked_person_not_found" => "リンク先の家系図に人物が見つかりません",
+        "apply_event_template" => "イベントを作成",
+        "event_template_hint" => "生年月日・没年月日から日付付きのイベントを作り、この人物とつなぎます。既にあるイベントは作りません。",
+        "event_template_applied" => "テンプレートからイベントを作成しました",
+        "event_template_nothing_new" => "このテンプレートのイベントはすべて作成済みです",
+        "event_template_missing_date" => "このテンプレートには生年月日または没年月日が必要です",
+        "template_memorial_services" => "法要",
+        "template_longevity_celebrations" => "長寿の祝い",
+        "memorial_7th_day" => "初七日",
+        "memorial_49th_day" => "四十九日",
+        "memorial_1st" => "一周忌",
+        "memorial_3rd" => "三回忌",
+        "memorial_7th" => "七回忌",
+        "memorial_13th" => "十三回忌",
+        "memorial_17th" => "十七回忌",
+        "memorial_23rd" => "二十三回忌",
+        "memorial_27th" => "二十七回忌",
+        "memorial_33rd" => "三十三回忌",
+        "longevity_60" => "還暦",
+        "longevity_70" => "古希",
+        "longevity_77" => "喜寿",
+        "longevity_80" => "傘寿",
+        "longevity_88" => "米寿",
+        "longevity_90" => "卒寿",
+        "longevity_99" => "白寿",
+        "log_placeholder_relatives_deleted" => "仮の親族を削除しました",
         "relations" => "関係:",
         "father" => "父親:",
         "mother" => "母親:",
         "parent" => "親:",
         "spouses" => "配偶者:",
+        "children" => "子:",
+        "children_with" => "相手:",
+        "children_other_parent_unknown" => "もう一方の親は未登録",
         "add_relations" => "関係を追加:",
         "add_parent" => "親を追加:",
         "add_child" => "子を追加:",
@@ -64,9 +416,32 @@ pub fn translate(key: &str) -> String {
         "no_members" => "(メンバーなし)",
         "no_family_selected" => "(家族が選択されていません)",
         "add_member" => "メンバーを追加:",
+        "family_defaults" => "この家族の既定値",
+        "family_defaults_hint" => "この家族から新しい人物を追加するときに使います。",
+        "family_default_kind" => "親子関係の種類",
+        "family_default_kind_global" => "設定と同じ",
+        "family_default_surname" => "新しい子の姓",
+        "family_label_color" => "家族名の色",
+        "new_family_member" => "家族に新しい人物:",
+        "new_family_member_parent" => "親:",
+        "new_family_member_no_parent" => "（なし）",
         "delete_family" => "家族を削除",
         "grid" => "グリッド:",
         "show_grid" => "グリッドを表示",
+        "grid_style_lines" => "線",
+        "grid_style_dots" => "点",
+        "grid_major_every" => "太線の間隔（マス）:",
+        "grid_major_every_hint" => "0 ならすべて同じ線で描きます",
+        "snap_while_dragging" => "ドラッグ中もスナップ",
+        "snap_strength" => "スナップの強さ:",
+        "snap_strength_hint" => "1.0 は常に最寄りの交点へ、小さいほど交点の近くでだけスナップし、0 ではスナップしません",
+        "show_rulers" => "定規とガイド線を表示",
+        "show_rulers_hint" => "定規からドラッグするとガイド線を追加し、ガイド線を定規に戻すと削除します。近くに置いた人物はガイド線にそろいます。",
+        "canvas_guides" => "ガイド線",
+        "clear_guides" => "ガイド線をすべて削除",
+        "log_guide_moved" => "ガイド線を移動",
+        "log_guide_added" => "ガイド線を追加",
+        "log_guide_removed" => "ガイド線を削除",
         "grid_size" => "グリッドサイズ:",
         "layout" => "レイアウト:",
         "reset_positions" => "すべての位置をリセット",
@@ -85,6 +460,121 @@ pub fn translate(key: &str) -> String {
         "edit_memo" => "メモ編集",
         "edit_kind" => "種類編集",
         "relation_kind_updated" => "関係の種類を更新しました",
+        "adoption_date" => "養子縁組日:",
+        "adoption_type" => "縁組の種類:",
+        "adoption_note" => "縁組メモ:",
+        "adoption_before_birth" => "養子縁組日が子の生年月日より前です",
+        "step_parents" => "継親:",
+        "step_children" => "継子:",
+        "derived_relation" => "（配偶者関係から導出）",
+        "person_id" => "ID:",
+        "copy_id" => "IDをコピー",
+        "person_search_hint" => "名前で検索します。短縮IDを貼り付けて Enter を押すとその人物を選択します",
+        "person_found" => "人物を選択しました",
+        "export_poster" => "分割ポスターを書き出し (SVG)...",
+        "export_vcard" => "選択した人物を vCard で書き出し...",
+        "export_vcard_no_selection" => "先に人物を選択するか、ホームの人物を設定してください",
+        "export_icalendar" => "誕生日・結婚記念日を iCalendar で書き出し...",
+        "export_html_viewer" => "HTMLビューアーを書き出し",
+        "export_html_viewer_hint" => "ブラウザで開くだけで家系図を見られる1つのHTMLファイルです（閲覧専用）。",
+        "export_html_viewer_with_photos" => "写真を含める（ファイルが大きくなります）…",
+        "export_html_viewer_without_photos" => "写真を含めない…",
+        "file_filter_html" => "HTMLファイル",
+        "html_viewer_birth" => "生年月日",
+        "html_viewer_death" => "没年月日",
+        "html_viewer_parents" => "親",
+        "html_viewer_children" => "子",
+        "html_viewer_spouses" => "配偶者",
+        "html_viewer_hint" => "ドラッグで移動、ホイールで拡大縮小、人物をクリックで詳細",
+        "html_viewer_search" => "名前で検索",
+        "html_viewer_exported" => "HTMLビューアーを書き出しました",
+        "log_html_viewer_exported" => "HTMLビューアーを書き出し",
+        "export_descendant_outline" => "子孫の一覧をテキストで書き出し...",
+        "export_descendant_outline_hint" => "選択中の人物（未選択ならホームの人物）の子孫を、世代ごとに字下げした生没年付きの一覧として書き出します（.txt または .md）",
+        "export_text_chart_no_selection" => "先に人物を選択するか、ホームの人物を設定してください",
+        "export_pedigree_chart" => "祖先表をテキストで書き出し",
+        "export_pedigree_chart_hint" => "選択中の人物（未選択ならホームの人物）の祖先を、番号（父は 2n、母は 2n+1）付きで書き出します（.txt または .md）",
+        "pedigree_generations" => "世代...",
+        "export_poster_title" => "分割ポスターの書き出し",
+        "paper_size" => "用紙サイズ:",
+        "landscape" => "横向き",
+        "poster_columns" => "列数:",
+        "poster_rows" => "行数:",
+        "poster_total_size" => "全体の大きさ",
+        "export" => "書き出し",
+        "poster_exported" => "ポスターのページを書き出しました",
+        "export_failed" => "書き出しに失敗しました",
+        "file_filter_svg" => "SVG画像",
+        "file_filter_vcard" => "vCard",
+        "default_vcard_file_name" => "連絡先",
+        "vcard_exported" => "vCard を書き出しました",
+        "file_filter_text" => "テキスト",
+        "file_filter_markdown" => "Markdown",
+        "default_descendant_outline_file_name" => "子孫一覧",
+        "descendant_outline_exported" => "子孫の一覧を書き出しました",
+        "default_pedigree_chart_file_name" => "祖先表",
+        "pedigree_chart_exported" => "祖先表を書き出しました",
+        "file_filter_icalendar" => "iCalendar",
+        "default_calendar_file_name" => "家族カレンダー",
+        "calendar_birthday" => "誕生日",
+        "calendar_anniversary" => "結婚記念日",
+        "calendar_exported" => "カレンダーを書き出しました",
+        "default_export_file_name" => "家系図",
+        "export_image" => "画像を書き出し (SVG)...",
+        "export_image_title" => "画像の書き出し",
+        "export_scale" => "倍率:",
+        "export_dpi" => "DPI:",
+        "export_preset_screen" => "画面用 (96 DPI)",
+        "export_preset_print" => "印刷用 (300 DPI)",
+        "export_layers" => "レイヤー:",
+        "layer_grid" => "グリッド",
+        "layer_family_boxes" => "家族の枠",
+        "layer_events" => "イベント",
+        "export_output_size" => "出力サイズ",
+        "image_exported" => "画像を書き出しました",
+        "include_title_block" => "表題欄を入れる",
+        "title_block_title" => "表題:",
+        "title_block_author" => "作成者:",
+        "title_block_date" => "日付:",
+        "title_block_generations" => "世代数:",
+        "watermark" => "透かし:",
+        "watermark_hint" => "例: 下書き",
+        "person_relations" => "その他の関係:",
+        "add_person_relation" => "後見人・名付け親・師を追加:",
+        "person_relation_added" => "関係を追加しました",
+        "relation_guardian" => "後見人",
+        "relation_ward" => "被後見人",
+        "relation_godparent" => "名付け親",
+        "relation_godchild" => "名付け子",
+        "relation_mentor" => "師",
+        "relation_mentee" => "弟子",
+        "multiple_birth" => "多胎（双子など）:",
+        "person_badges" => "目印:",
+        "home_person" => "ホームの人物",
+        "home_person_hint" => "ホームボタンで戻る人物です",
+        "home_person_button" => "ホーム",
+        "go_to_home_person" => "ホームの人物へ移動",
+        "log_home_person_set" => "ホームの人物を設定",
+        "log_home_person_cleared" => "ホームの人物を解除",
+        "badge_military" => "軍歴",
+        "badge_emigrated" => "移住",
+        "badge_direct_ancestor" => "直系の祖先",
+        "badge_clergy" => "聖職者",
+        "badge_nobility" => "貴族・名家",
+        "log_badge_toggled" => "目印を切り替えました",
+        "add_multiple_birth" => "同じ出産のきょうだい:",
+        "remove_multiple_birth" => "多胎から外す",
+        "mark_selected_multiple_birth" => "選択中の人物を多胎として登録",
+        "multiple_birth_updated" => "多胎の情報を更新しました",
+        "multiple_birth_date_mismatch" => "多胎のきょうだいの生年月日が一致しません",
+        "spouse_editor" => "配偶者関係",
+        "spouse_label_visible" => "メモをキャンバスに表示",
+        "spouse_label_above" => "線の上",
+        "spouse_label_below" => "線の下",
+        "marriage_date" => "結婚年月日:",
+        "divorced" => "離婚",
+        "divorce_date" => "離婚年月日:",
+        "spouse_relation_updated" => "配偶者関係を更新しました",
         "new_family_added" => "新しい家族を追加しました",
         "member_removed" => "メンバーを削除しました",
         "member_added" => "メンバーを追加しました",
@@ -92,11 +582,16 @@ pub fn translate(key: &str) -> String {
         "family_deleted" => "家族を削除しました",
         "positions_reset" => "すべての位置をリセットしました",
         "saved" => "保存しました",
+        "newer_format_read_only" => "新しいバージョンで保存されたファイルのため、閲覧モードで開きました。保存しても知らない項目は残ります",
         "loaded" => "読み込みました",
         "edit" => "編集:",
         "remove_relation" => "関係を削除",
         "selected_family" => "選択した家族:",
-        "new_person" => "New Person",
+        "new_person" => "新しい人物 {n}",
+        "new_person_defaults" => "新しい人物の初期値",
+        "new_person_name_template" => "名前",
+        "new_person_name_template_hint" => "「新しい人物を追加」で使う名前。{n} は次の番号になる",
+        "new_person_relation_kind" => "親子関係の種類",
         "new_family" => "New Family",
         "tooltip_name" => "名前",
         "tooltip_birth" => "生年月日",
@@ -106,7 +601,31 @@ pub fn translate(key: &str) -> String {
         "tooltip_deceased" => "死亡",
         "tooltip_yes" => "はい",
         "tooltip_memo" => "メモ",
+        "tooltip_step_parents" => "継親",
+        "tooltip_step_children" => "継子",
+        "tooltip_parents" => "親",
+        "tooltip_spouses" => "配偶者",
+        "tooltip_children" => "子",
+        "tooltip_tags" => "タグ",
+        "tooltip_presumed_deceased" => "死亡と推定（死亡の記録なし・出生から長期間経過）",
+        "presumed_deceased_marker" => "推定 †",
         "help_menu" => "ヘルプ",
+        "shortcuts" => "キーボードショートカット",
+        "error_log_location" => "詳細の記録先",
+        "shortcut_category_file" => "ファイル",
+        "shortcut_category_edit" => "編集",
+        "shortcut_category_navigation" => "移動",
+        "shortcut_category_canvas" => "キャンバス",
+        "shortcut_category_help" => "ヘルプ",
+        "shortcut_zoom" => "カーソル位置を中心にズーム",
+        "shortcut_pan" => "キャンバスを移動",
+        "shortcut_multi_select" => "選択に追加・解除",
+        "gesture_ctrl_wheel" => "Ctrl+ホイール / ピンチ",
+        "gesture_drag_background" => "背景をドラッグ / 2本指スワイプ",
+        "gesture_double_click_background" => "背景をダブルクリック",
+        "gesture_ctrl_click" => "Ctrl+クリック",
+        "gesture_ctrl_number" => "Ctrl+1〜9",
+        "gesture_right_click_background" => "背景を右クリック",
         "about" => "バージョン情報",
         "license" => "ライセンス情報",
         "app_name" => "家系図作成ツール",
@@ -114,7 +633,9 @@ pub fn translate(key: &str) -> String {
         "app_description" => "このアプリケーションは家系図を作成・管理するためのツールです。",
         "license_text" => include_str!("../../../LICENSE"),
         "close" => "閉じる",
+        "copy" => "コピー",
         "events" => "📅 イベント",
+        "annotations" => "📝 注釈",
         "manage_events" => "イベント管理",
         "add_new_event" => "➕ 新しいイベントを追加",
         "event_editor" => "イベントエディタ",
@@ -128,6 +649,34 @@ pub fn translate(key: &str) -> String {
         "arrow_to_person" => "矢印 → 人物",
         "arrow_to_event" => "矢印 ← 人物",
         "new_event_added" => "新しいイベントを追加しました",
+        "places" => "📍 場所",
+        "manage_places" => "場所の管理",
+        "add_new_place" => "新しい場所を追加",
+        "new_place" => "新しい場所",
+        "new_place_added" => "新しい場所を追加しました",
+        "no_places" => "場所はまだありません",
+        "place_editor" => "場所の編集",
+        "place" => "場所",
+        "no_place" => "（なし）",
+        "birth_place" => "出生地",
+        "death_place" => "死亡地",
+        "event_place" => "イベントの場所",
+        "latitude" => "緯度",
+        "longitude" => "経度",
+        "invalid_coordinates" => "緯度は -90〜90、経度は -180〜180 の範囲で入力してください",
+        "place_updated" => "場所を更新しました",
+        "place_deleted" => "場所を削除しました",
+        "merge_place_into" => "統合先",
+        "merge_places" => "統合",
+        "merge_places_preview" => "付け替わる参照",
+        "merge_places_hint" => "この場所への参照をすべて統合先に置き換えて削除します",
+        "places_merged" => "場所を統合しました",
+        "duplicate_places" => "表記ゆれの可能性がある場所",
+        "merge_duplicate_places_hint" => "これらの場所を先頭の場所に統合します",
+        "log_place_added" => "場所を追加",
+        "log_place_updated" => "場所を更新",
+        "log_place_deleted" => "場所を削除",
+        "log_places_merged" => "場所を統合",
         "event_updated" => "イベント情報を更新しました",
         "event_deleted" => "イベントを削除しました",
         "relation_added" => "関係を追加しました",
@@ -135,12 +684,63 @@ pub fn translate(key: &str) -> String {
         "display_mode" => "表示モード:",
         "name_only" => "名前のみ",
         "name_and_photo" => "名前と写真",
+        "photo_only" => "写真のみ（名前はホバー時）",
         "choose_photo" => "写真を選択...",
+        "secondary_backup" => "予備のバックアップ",
+        "secondary_backup_hint" => "保存のたびに、日時付きのコピーをこのフォルダー（同期フォルダーなど）にも書き込みます。",
+        "secondary_backup_disabled" => "未設定（無効）",
+        "choose_backup_dir" => "フォルダーを選択...",
+        "secondary_backup_keep" => "ファイルごとに残すコピーの数",
+        "backup_failed" => "バックアップのコピーを書き込めませんでした",
+        "log_backup_written" => "バックアップのコピーを書き込みました",
         "clear_photo" => "写真をクリア",
+        "edit_photo" => "切り抜き・回転...",
+        "rotate_left" => "左に回転",
+        "rotate_right" => "右に回転",
+        "reset_crop" => "切り抜きを解除",
+        "photo_crop_hint" => "写真の上をドラッグして残す範囲を選択します",
+        "use_original_photo" => "元の写真を使う",
+        "photo_edited" => "編集した写真を photo フォルダに保存しました",
+        "photo_edit_load_failed" => "写真を開けませんでした",
+        "photo_edit_save_failed" => "編集した写真を保存できませんでした",
+        "log_photo_edited" => "編集した写真を保存",
         "photo_scale" => "写真倍率:",
         "node_color_theme" => "ノード配色テーマ:",
         "node_color_theme_default" => "標準",
         "node_color_theme_high_contrast" => "高コントラスト",
+        "high_contrast_ui" => "高コントラスト表示",
+        "high_contrast_ui_hint" => "画面全体を黒背景・白文字・黄色のフォーカス枠で表示します",
+        "ui_font" => "画面のフォント",
+        "ui_font_bundled" => "同梱フォント（Noto Sans JP）",
+        "ui_font_system" => "OS のフォント（自動検出）",
+        "ui_font_system_not_found" => "OS に日本語フォントが見つからないため同梱フォントを使います",
+        "ui_font_size" => "文字サイズ",
+        "ui_font_size_reset" => "既定",
+        "next_side_tab" => "次のサイドパネルタブ",
+        "previous_side_tab" => "前のサイドパネルタブ",
+        "shortcut_focus_persons" => "操作部品とキャンバス上の人物の間でフォーカスを移動",
+        "gesture_tab" => "Tab / Shift+Tab",
+        "node_style" => "写真付きノードの形（家系図ごとに保存）",
+        "node_style_box" => "四角形",
+        "node_style_circular_avatar" => "円形アバター",
+        "layout_direction" => "自動レイアウトの向き",
+        "layout_direction_ltr" => "左から右",
+        "layout_direction_rtl" => "右から左（反転）",
+        "edge_style" => "親子の線",
+        "edge_style_straight" => "直線",
+        "edge_style_bezier" => "曲線（ベジェ）",
+        "bezier_control_offset" => "曲がり具合:",
+        "semantic_zoom" => "セマンティックズーム",
+        "semantic_zoom_hint" => "ズーム倍率がそれぞれの値未満になるとノードの内容を省略します",
+        "semantic_zoom_photo" => "写真:",
+        "semantic_zoom_dates" => "生没年:",
+        "semantic_zoom_name" => "名前:",
+        "other_gender_color" => "その他の性別のノード色:",
+        "presumed_deceased" => "死亡の推定",
+        "presume_deceased" => "出生から長期間経過した人物を死亡と推定する",
+        "presumed_deceased_years" => "出生からの年数:",
+        "relation_display" => "関係の表示",
+        "show_step_relations" => "導出した継親・継子を表示",
         // Log messages
         "log_app_started" => "アプリケーションを起動しました",
         "log_file_saved" => "ファイルを保存しました",
@@ -169,17 +769,34 @@ pub fn translate(key: &str) -> String {
         "log_family_selected" => "家族を選択",
         "log_family_member_added" => "家族にメンバーを追加しました",
         "log_family_member_removed" => "家族からメンバーを削除しました",
+        "log_spouse_relation_updated" => "配偶者関係を更新しました",
+        "log_adoption_before_birth" => "養子縁組日が生年月日より前",
+        "log_multiple_birth_date_mismatch" => "多胎の生年月日不一致",
+        "log_poster_exported" => "ポスターを書き出し",
+        "log_image_exported" => "画像を書き出し",
+        "log_vcard_exported" => "vCard を書き出し",
+        "log_descendant_outline_exported" => "子孫の一覧を書き出し",
+        "log_pedigree_chart_exported" => "祖先表を書き出し",
+        "log_calendar_exported" => "カレンダーを書き出し",
+        "log_background_image_set" => "背景画像を設定",
+        "log_annotation_added" => "注釈を追加",
+        "log_annotation_updated" => "注釈を更新",
+        "log_annotation_deleted" => "注釈を削除",
+        "log_sticky_note_added" => "付箋を追加",
         "log_from" => "から",
         "log_to" => "へ",
         "log_settings_loaded" => "設定ファイルを読み込みました",
+        "log_system_font_loaded" => "OS のフォントを読み込みました",
+        "log_system_font_not_found" => "OS に日本語フォントが見つからないため同梱フォントを使います",
         "log_settings_load_failed" => "設定ファイルの読み込みに失敗しました",
         "log_settings_save_failed" => "設定ファイルの保存に失敗しました",
         "log_panel_title" => "📋 ログ",
+        "log_filter_hint" => "メッセージを絞り込み",
+        "log_copy_hint" => "表示中のメッセージをクリップボードにコピー",
+        "missing_translations" => "翻訳のないキー",
         _ => {
             if cfg!(debug_assertions) {
-                let warning = format!("[i18n Warning] Unknown translation key (ja): '{}'", key);
-                eprintln!("{}", warning);
-                add_warning(warning);
+                report_missing_key(key, Language::Japanese);
             }
             key
         }
@@ -11,14 +11,36 @@ mod ja;
 mod en;
 
 static I18N_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static MISSING_KEYS: Mutex<Vec<(Language, String)>> = Mutex::new(Vec::new());
 
 /// i18n警告をバッファに追加
-pub(crate) fn add_warning(message: String) {
+fn add_warning(message: String) {
     if let Ok(mut warnings) = I18N_WARNINGS.lock() {
         warnings.push(message);
     }
 }
 
+/// 翻訳のないキーを記録する（毎フレーム同じ警告が出ないよう、警告は初めて見つかったときだけ）
+pub(crate) fn report_missing_key(key: &str, lang: Language) {
+    let Ok(mut missing) = MISSING_KEYS.lock() else {
+        return;
+    };
+    if missing.iter().any(|(missing_lang, missing_key)| *missing_lang == lang && missing_key == key) {
+        return;
+    }
+    missing.push((lang, key.to_string()));
+    drop(missing);
+
+    let warning = format!("[i18n Warning] Unknown translation key ({}): '{}'", lang.code(), key);
+    eprintln!("{}", warning);
+    add_warning(warning);
+}
+
+/// これまでに見つかった翻訳のないキー（見つかった順）
+pub fn missing_keys() -> Vec<(Language, String)> {
+    MISSING_KEYS.lock().map(|missing| missing.clone()).unwrap_or_default()
+}
+
 /// 警告を取得してバッファをクリア
 pub fn take_warnings() -> Vec<String> {
     if let Ok(mut warnings) = I18N_WARNINGS.lock() {
@@ -28,12 +50,22 @@ pub fn take_warnings() -> Vec<String> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
     Japanese,
     English,
 }
 
+impl Language {
+    /// 言語コード（"ja" / "en"）
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Japanese => "ja",
+            Language::English => "en",
+        }
+    }
+}
+
 pub struct Texts;
 
 impl Texts {
@@ -71,6 +103,22 @@ mod tests {
         assert_eq!(Texts::get("nonexistent_key", Language::English), "nonexistent_key");
     }
 
+    #[test]
+    fn test_missing_keys_are_recorded_once_per_language() {
+        let key = "missing_key_for_warning_test";
+        take_warnings();
+        for _ in 0..3 {
+            Texts::get(key, Language::English);
+        }
+        Texts::get(key, Language::Japanese);
+
+        let recorded: Vec<_> = missing_keys().into_iter().filter(|(_, missing)| missing == key).collect();
+        assert_eq!(recorded, [(Language::English, key.to_string()), (Language::Japanese, key.to_string())]);
+        // 他のテストが同時に警告を追加しうるので、このキーの警告だけを数える
+        let warnings: Vec<_> = take_warnings().into_iter().filter(|warning| warning.contains(key)).collect();
+        assert_eq!(warnings.len(), 2);
+    }
+
     #[test]
     fn test_language_equality() {
         assert_eq!(Language::Japanese, Language::Japanese);
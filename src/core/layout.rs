@@ -1,9 +1,10 @@
 use std::collections::{HashMap, VecDeque};
 
 use eframe::egui;
+use serde::{Deserialize, Serialize};
 
 use crate::core::i18n::{Language, Texts};
-use crate::core::tree::{Event, EventId, FamilyTree, PersonDisplayMode, PersonId};
+use crate::core::tree::{CanvasGuide, Event, EventId, FamilyTree, GuideAxis, PersonDisplayMode, PersonId};
 
 /// 画面上のノード情報
 #[derive(Debug, Clone)]
@@ -16,13 +17,260 @@ pub struct LayoutNode {
     pub rect: egui::Rect,
 }
 
+/// 自動レイアウトで同じ世代の人物を並べる向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    /// 右から左（RTLロケールや一部の図の様式向け）。手動で配置した位置は変更しない
+    RightToLeft,
+}
+
+/// ズーム倍率に応じて人物ノードに描画する内容の詳しさ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NodeDetailLevel {
+    /// 色付きの矩形のみ
+    Block,
+    NameOnly,
+    NameAndDates,
+    /// 写真・名前・生没年のすべて
+    Full,
+}
+
+/// セマンティックズームの閾値（この倍率未満になると該当する内容を省略する）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SemanticZoomThresholds {
+    pub photo: f32,
+    pub dates: f32,
+    pub name: f32,
+}
+
+impl Default for SemanticZoomThresholds {
+    fn default() -> Self {
+        Self {
+            photo: 0.6,
+            dates: 0.5,
+            name: 0.35,
+        }
+    }
+}
+
+impl SemanticZoomThresholds {
+    /// ズーム倍率から描画内容の詳しさを決める
+    pub fn detail_level(&self, zoom: f32) -> NodeDetailLevel {
+        if zoom >= self.photo {
+            NodeDetailLevel::Full
+        } else if zoom >= self.dates {
+            NodeDetailLevel::NameAndDates
+        } else if zoom >= self.name {
+            NodeDetailLevel::NameOnly
+        } else {
+            NodeDetailLevel::Block
+        }
+    }
+}
+
+/// グリッドの描き方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridStyle {
+    #[default]
+    Lines,
+    Dots,
+}
+
+/// グリッドの見た目とスナップの動作
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridSettings {
+    pub style: GridStyle,
+    /// 何マスごとに太い線（大きい点）にするか（0 なら区別しない）
+    pub major_every: u32,
+    /// ドラッグ中もグリッドに合わせる（false なら離したときだけ）
+    pub snap_while_dragging: bool,
+    /// スナップの強さ（1.0 なら常に最寄りの交点へ、0.0 ならスナップしない）
+    pub snap_strength: f32,
+}
+
+impl Default for GridSettings {
+    fn default() -> Self {
+        Self {
+            style: GridStyle::Lines,
+            major_every: 5,
+            snap_while_dragging: false,
+            snap_strength: 1.0,
+        }
+    }
+}
+
+/// 関係を追加した人物を、相手の人物から見てどこに置くか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativePlacement {
+    /// 相手の上（親）
+    Parent,
+    /// 相手の下（子）
+    Child,
+    /// 相手の横（配偶者）
+    Spouse,
+}
+
+/// 細かいグリッドを描く最小の間隔（画面上のピクセル。これより狭いと太い線だけ描く）
+const MIN_GRID_SCREEN_SPACING: f32 = 8.0;
+
+/// 描画に依存しないレイアウト計算の条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutConfig {
+    /// true なら自動整列した位置、false なら保存されている位置を使う
+    pub arrange: bool,
+    pub x_spacing: f32,
+    pub y_spacing: f32,
+    pub show_life_years: bool,
+    pub direction: LayoutDirection,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            arrange: true,
+            x_spacing: 160.0,
+            y_spacing: 140.0,
+            show_life_years: true,
+            direction: LayoutDirection::LeftToRight,
+        }
+    }
+}
+
+/// レイアウト結果の人物ノード（ID を含まないので、生成のたびに ID が変わる家系図でも比較できる）
+///
+/// キャンバスの`compute_layout`も同じ計算からノードを作るので、固定の JSON との比較で描画の位置と大きさを確かめられる。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutDataNode {
+    pub name: String,
+    pub generation: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// レイアウト結果の線（from・to は nodes の添字）
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LayoutDataEdge {
+    pub from: usize,
+    pub to: usize,
+    /// 親子関係なら種類（"biological" など）、配偶者なら "spouse"
+    pub kind: String,
+}
+
+/// 描画から切り離したレイアウト結果（描画の変更を固定の JSON と比べて確かめるためのもの）
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LayoutData {
+    pub nodes: Vec<LayoutDataNode>,
+    pub edges: Vec<LayoutDataEdge>,
+}
+
+/// 名前の下に生没年を表示するときに足すノードの高さ
+pub const LIFE_YEARS_LINE_HEIGHT: f32 = 12.0;
+
+/// 名前を2行に折り返したときに足すノードの高さ（名前1行分）
+pub const NAME_LINE_HEIGHT: f32 = 17.0;
+
+/// 名前の左右の余白の合計
+pub const NAME_PADDING_H: f32 = 16.0;
+
+/// 名前だけを描く人物ノードの幅の範囲
+const MIN_TEXT_NODE_WIDTH: f32 = 100.0;
+const MAX_TEXT_NODE_WIDTH: f32 = 250.0;
+
+/// 名前だけを描く人物ノードで使う名前の最大行数
+pub const MAX_NAME_LINES: usize = 2;
+
+/// 省略したことを示す記号
+const ELLIPSIS: char = '…';
+
 /// レイアウト計算とラベル生成を担当するモジュール
 pub struct LayoutEngine;
 
 impl LayoutEngine {
-    fn estimate_text_node_width(person_name: &str) -> f32 {
-        let char_count = person_name.chars().count();
-        (char_count as f32 * 14.0).max(100.0).min(250.0)
+    /// 14px の文字の幅の推定（漢字・かな・全角文字は半角の倍近く取る）
+    fn estimate_char_width(c: char) -> f32 {
+        let wide = matches!(
+            c as u32,
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60
+                | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD
+        );
+        if wide { 14.0 } else { 8.0 }
+    }
+
+    /// 文字列を描いたときの幅の推定
+    pub fn estimate_text_width(text: &str) -> f32 {
+        text.chars().map(Self::estimate_char_width).sum()
+    }
+
+    /// 名前を max_width に収まる行に分ける（文字の幅は推定値）
+    ///
+    /// 空白があればそこで折り返し、なければ（漢字・かなの名前など）収まる文字数で区切る。
+    /// max_lines 行に収まらない分は最後の行の末尾を「…」にして省略する。
+    pub fn wrap_name(name: &str, max_width: f32, max_lines: usize) -> Vec<String> {
+        Self::wrap_name_measured(name, max_width, max_lines, Self::estimate_char_width)
+    }
+
+    /// wrap_name と同じ規則で、文字の幅を char_width で測って分ける（描画時は実際のフォントで測る）
+    pub fn wrap_name_measured(
+        name: &str,
+        max_width: f32,
+        max_lines: usize,
+        mut char_width: impl FnMut(char) -> f32,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut rest = name.trim();
+        while !rest.is_empty() && lines.len() < max_lines.max(1) {
+            if rest.chars().map(&mut char_width).sum::<f32>() <= max_width {
+                lines.push(rest.to_string());
+                return lines;
+            }
+            if lines.len() + 1 == max_lines.max(1) {
+                // 最後の行は「…」の分を空けて詰められるだけ詰める
+                let ellipsis_width = char_width(ELLIPSIS);
+                let (head, _) = Self::split_at_width(rest, max_width - ellipsis_width, &mut char_width);
+                lines.push(format!("{}{ELLIPSIS}", head.trim_end()));
+                return lines;
+            }
+            let (head, tail) = Self::split_at_width(rest, max_width, &mut char_width);
+            let (head, tail) = match head.rfind(char::is_whitespace) {
+                Some(space) if space > 0 && !tail.starts_with(char::is_whitespace) => (&rest[..space], &rest[space..]),
+                _ => (head, tail),
+            };
+            lines.push(head.trim_end().to_string());
+            rest = tail.trim_start();
+        }
+        lines
+    }
+
+    /// 先頭から max_width に収まる部分と残りに分ける（1文字も収まらなくても先頭の1文字は入れる）
+    fn split_at_width<'t>(text: &'t str, max_width: f32, char_width: &mut impl FnMut(char) -> f32) -> (&'t str, &'t str) {
+        let mut width = 0.0;
+        for (index, c) in text.char_indices() {
+            width += char_width(c);
+            if width > max_width && index > 0 {
+                return text.split_at(index);
+            }
+        }
+        (text, "")
+    }
+
+    /// 名前だけを描くノードで、レイアウトが名前に割り当てた行数
+    pub fn name_line_count(person_name: &str) -> usize {
+        Self::text_node_layout(person_name).1
+    }
+
+    /// 名前だけを描くノードの幅と、名前が何行になるか
+    fn text_node_layout(person_name: &str) -> (f32, usize) {
+        let lines = Self::wrap_name(person_name, MAX_TEXT_NODE_WIDTH - NAME_PADDING_H, MAX_NAME_LINES);
+        let text_width = lines.iter().map(|line| Self::estimate_text_width(line)).fold(0.0, f32::max);
+        let width = (text_width + NAME_PADDING_H).clamp(MIN_TEXT_NODE_WIDTH, MAX_TEXT_NODE_WIDTH);
+        (width, lines.len().max(1))
     }
 
     fn calculate_person_node_size(
@@ -30,124 +278,278 @@ impl LayoutEngine {
         display_mode: PersonDisplayMode,
         photo_scale: f32,
         photo_dimensions: Option<(u32, u32)>,
+        has_life_years: bool,
     ) -> (f32, f32) {
         let font_size = 14.0;
         let padding_v = 16.0;
         let base_node_h = font_size + padding_v;
+        // 名前だけを描くノードは、折り返した名前の行と名前の下の生没年の行の分だけ高くする
+        let (text_node_w, name_lines) = Self::text_node_layout(person_name);
+        let mut text_node_h = base_node_h + (name_lines - 1) as f32 * NAME_LINE_HEIGHT;
+        if has_life_years {
+            text_node_h += LIFE_YEARS_LINE_HEIGHT;
+        }
 
         match display_mode {
-            PersonDisplayMode::NameOnly => (Self::estimate_text_node_width(person_name), base_node_h),
+            PersonDisplayMode::NameOnly => (text_node_w, text_node_h),
             PersonDisplayMode::NameAndPhoto => {
                 if let Some((image_width, image_height)) = photo_dimensions {
                     let width = image_width as f32 * photo_scale;
                     let height = image_height as f32 * photo_scale;
                     (width, height + base_node_h)
                 } else {
-                    (Self::estimate_text_node_width(person_name), base_node_h * 3.0)
+                    (text_node_w, base_node_h * 3.0)
                 }
             }
+            PersonDisplayMode::PhotoOnly => match photo_dimensions {
+                // 名前の欄を持たないので写真の大きさそのまま
+                Some((image_width, image_height)) => {
+                    (image_width as f32 * photo_scale, image_height as f32 * photo_scale)
+                }
+                None => (text_node_w, text_node_h),
+            },
         }
     }
 
-    /// ノードのレイアウトを計算
-    pub fn compute_layout(
-        tree: &FamilyTree,
-        origin: egui::Pos2,
-        photo_dimensions: &HashMap<PersonId, (u32, u32)>,
-    ) -> Vec<LayoutNode> {
-        // 世代計算：ルートを0として子へ+1
+    /// 世代ごとの人物の並び順を計算（世代の昇順）
+    ///
+    /// 配偶者同士は隣り合わせにし、子は親の並び順に沿って並べる。同じ条件の中では名前順。
+    /// 右から左の配置は座標を反転して作るので（`auto_positions`）、並び順は向きによらない。
+    #[cfg(test)]
+    pub fn generation_rows(tree: &FamilyTree) -> Vec<(usize, Vec<PersonId>)> {
+        Self::generation_blocks(tree)
+            .into_iter()
+            .map(|(generation, blocks)| (generation, blocks.into_iter().flatten().collect()))
+            .collect()
+    }
+
+    /// 各人物の世代を計算（ルートを0として子へ+1）
+    ///
+    /// 親が登録されていない配偶者（婚姻で加わった人物）は、相手と同じ世代にそろえる。
+    fn generation_map(tree: &FamilyTree) -> HashMap<PersonId, usize> {
         let roots = tree.roots();
+        let has_parents = |id: PersonId| !tree.parents_of(id).is_empty();
+        let (married_in, mut blood_roots): (Vec<PersonId>, Vec<PersonId>) = roots
+            .iter()
+            .partition(|id| tree.spouses_of(**id).into_iter().any(has_parents));
+        if blood_roots.is_empty() {
+            blood_roots = roots.clone();
+        }
+
         let mut gen_map: HashMap<PersonId, usize> = HashMap::new();
-        let mut q = VecDeque::new();
+        let descend = |gen_map: &mut HashMap<PersonId, usize>, starts: Vec<PersonId>| {
+            let mut q: VecDeque<PersonId> = starts.into();
+            while let Some(pid) = q.pop_front() {
+                let g = gen_map[&pid];
+                for ch in tree.children_of(pid) {
+                    let new_g = g + 1;
+                    let entry = gen_map.entry(ch).or_insert(new_g);
+                    if new_g < *entry {
+                        *entry = new_g;
+                    }
+                    q.push_back(ch);
+                }
+            }
+        };
 
-        for r in &roots {
+        for r in &blood_roots {
             gen_map.insert(*r, 0);
-            q.push_back(*r);
         }
+        descend(&mut gen_map, blood_roots);
 
-        while let Some(pid) = q.pop_front() {
-            let g = gen_map[&pid];
-            for ch in tree.children_of(pid) {
-                let new_g = g + 1;
-                let entry = gen_map.entry(ch).or_insert(new_g);
-                if new_g < *entry {
-                    *entry = new_g;
-                }
-                q.push_back(ch);
+        let mut late_starts = Vec::new();
+        for id in married_in {
+            if gen_map.contains_key(&id) {
+                continue;
             }
+            let partner_generation = tree
+                .spouses_of(id)
+                .into_iter()
+                .filter_map(|spouse| gen_map.get(&spouse).copied())
+                .max()
+                .unwrap_or(0);
+            gen_map.insert(id, partner_generation);
+            late_starts.push(id);
         }
+        // 婚姻で加わった人物だけが親の子（他方の親が未登録など）も世代を決める
+        let unvisited_children: Vec<PersonId> = late_starts
+            .into_iter()
+            .filter(|id| tree.children_of(*id).iter().any(|child| !gen_map.contains_key(child)))
+            .collect();
+        descend(&mut gen_map, unvisited_children);
 
-        if gen_map.is_empty() {
-            for id in tree.persons.keys() {
-                gen_map.insert(*id, 0);
-            }
-        } else {
-            for id in tree.persons.keys() {
-                gen_map.entry(*id).or_insert(0);
-            }
+        for id in tree.persons.keys() {
+            gen_map.entry(*id).or_insert(0);
         }
+        gen_map
+    }
 
+    /// 世代ごとに、配偶者同士をまとめたブロックを左から順に並べる
+    fn generation_blocks(tree: &FamilyTree) -> Vec<(usize, Vec<Vec<PersonId>>)> {
+        let gen_map = Self::generation_map(tree);
         let mut by_gen: HashMap<usize, Vec<PersonId>> = HashMap::new();
         for (id, g) in &gen_map {
             by_gen.entry(*g).or_default().push(*id);
         }
+        let mut generations: Vec<(usize, Vec<PersonId>)> = by_gen.into_iter().collect();
+        generations.sort_by_key(|(g, _)| *g);
+
+        let mut rows: Vec<(usize, Vec<Vec<PersonId>>)> = Vec::new();
+        let mut previous_order: HashMap<PersonId, usize> = HashMap::new();
+        // 名前が同じ人物は生年月日、さらに ID で並べ、HashMap の順序に左右されないようにする
+        let order_key = |id: &PersonId| {
+            let person = tree.persons.get(id);
+            (
+                person.map(|p| p.name.clone()).unwrap_or_default(),
+                person.and_then(|p| p.birth.clone()),
+                *id,
+            )
+        };
+        for (generation, mut ids) in generations {
+            ids.sort_by_key(order_key);
+
+            // 同じ世代の配偶者を同じブロックにまとめる
+            let mut placed = std::collections::HashSet::new();
+            let mut blocks: Vec<Vec<PersonId>> = Vec::new();
+            for id in &ids {
+                if !placed.insert(*id) {
+                    continue;
+                }
+                let mut block = vec![*id];
+                let mut spouses: Vec<PersonId> = tree
+                    .spouses_of(*id)
+                    .into_iter()
+                    .filter(|spouse| gen_map.get(spouse) == Some(&generation) && !placed.contains(spouse))
+                    .collect();
+                spouses.sort_by_key(order_key);
+                for spouse in spouses {
+                    placed.insert(spouse);
+                    block.push(spouse);
+                }
+                blocks.push(block);
+            }
 
-        for ids in by_gen.values_mut() {
-            ids.sort_by_key(|id| tree.persons.get(id).map(|p| p.name.clone()).unwrap_or_default());
+            // 親が前の世代で左にいるブロックほど左へ（親のいないブロックは後ろ）
+            let parent_order = |block: &Vec<PersonId>| {
+                block
+                    .iter()
+                    .flat_map(|id| tree.parents_of(*id))
+                    .filter_map(|parent| previous_order.get(&parent).copied())
+                    .min()
+                    .unwrap_or(usize::MAX)
+            };
+            blocks.sort_by_key(|block| parent_order(block));
+
+            previous_order = blocks.iter().flatten().enumerate().map(|(index, id)| (*id, index)).collect();
+            rows.push((generation, blocks));
         }
+        rows
+    }
 
-        let x_gap = 50.0;
-        let y_gap = 80.0;
-
-        let mut nodes = Vec::new();
-        let mut gens: Vec<usize> = by_gen.keys().copied().collect();
-        gens.sort();
-
-        for g in gens {
-            if let Some(ids) = by_gen.get(&g) {
-                for (i, id) in ids.iter().enumerate() {
-                    let person = tree.persons.get(id);
-                    let person_name = person.map(|p| p.name.as_str()).unwrap_or("Unknown");
-                    let (node_w, node_h) = if let Some(p) = person {
-                        let dimensions = photo_dimensions.get(id).copied();
-                        Self::calculate_person_node_size(
-                            person_name,
-                            p.display_mode,
-                            p.photo_scale,
-                            dimensions,
-                        )
-                    } else {
-                        Self::calculate_person_node_size(
-                            person_name,
-                            PersonDisplayMode::NameOnly,
-                            1.0,
-                            None,
-                        )
-                    };
-                    
-                    let (x, y) = if let Some(person) = person {
-                        person.position
-                    } else {
-                        let auto_x = origin.x + (i as f32) * (node_w + x_gap);
-                        let auto_y = origin.y + (g as f32) * (node_h + y_gap);
-                        (auto_x, auto_y)
-                    };
-                    
-                    let rect = egui::Rect::from_min_size(
-                        egui::pos2(x, y),
-                        egui::vec2(node_w, node_h),
-                    );
-                    nodes.push(LayoutNode {
-                        id: *id,
-                        generation: g,
-                        pos: egui::pos2(x, y),
-                        rect,
-                    });
+    /// 世代ごとの行に人物を自動配置した座標（左上）を返す
+    ///
+    /// 夫婦は隣り合わせにし、きょうだいのまとまりは親（夫婦の場合はその中央）の真下を中心に置く。
+    /// 固定（pinned）・ロック（locked）された人物は今の位置のまま動かさず、その世代の行の高さもその人物に合わせる。
+    /// 左隣や固定された人物と重なる場合は右へずらす。
+    /// 右から左の場合は、origin を通る縦線で左右を反転した配置になる（並び順の先頭が右端）。
+    pub fn auto_positions(
+        tree: &FamilyTree,
+        origin: (f32, f32),
+        x_spacing: f32,
+        y_spacing: f32,
+        direction: LayoutDirection,
+    ) -> HashMap<PersonId, (f32, f32)> {
+        // 右から左の場合は反転した座標で左から右に並べ、最後に元へ戻す
+        let mirror = |(x, y): (f32, f32)| match direction {
+            LayoutDirection::LeftToRight => (x, y),
+            LayoutDirection::RightToLeft => (2.0 * origin.0 - x, y),
+        };
+        let pinned: HashMap<PersonId, (f32, f32)> = tree
+            .persons
+            .values()
+            .filter(|person| person.keeps_position())
+            .map(|person| (person.id, mirror(person.position)))
+            .collect();
+        let mut positions = pinned.clone();
+
+        let mut row_y = origin.1;
+        for (index, (_, blocks)) in Self::generation_blocks(tree).into_iter().enumerate() {
+            if index > 0 {
+                row_y += y_spacing;
+            }
+            if let Some((_, y)) = blocks.iter().flatten().find_map(|id| pinned.get(id)) {
+                row_y = *y;
+            }
+            let y = row_y;
+            // この行と重なる高さにある固定された人物は避けて並べる
+            let obstacles: Vec<f32> = pinned
+                .values()
+                .filter(|(_, pinned_y)| (pinned_y - y).abs() < y_spacing / 2.0)
+                .map(|(x, _)| *x)
+                .collect();
+
+            // 同じ親を持つ連続したブロックをきょうだいのまとまりにする
+            let mut sibling_groups: Vec<(Vec<PersonId>, Vec<Vec<PersonId>>)> = Vec::new();
+            for block in blocks {
+                let mut parents: Vec<PersonId> = block.iter().flat_map(|id| tree.parents_of(*id)).collect();
+                parents.sort();
+                parents.dedup();
+                match sibling_groups.last_mut() {
+                    Some((group_parents, group)) if !parents.is_empty() && *group_parents == parents => group.push(block),
+                    _ => sibling_groups.push((parents, vec![block])),
                 }
             }
+
+            let mut next_free_x: Option<f32> = None;
+            for (parents, group) in sibling_groups {
+                let count = group.iter().map(Vec::len).sum::<usize>();
+                let width = count.saturating_sub(1) as f32 * x_spacing;
+                let parent_xs: Vec<f32> = parents.iter().filter_map(|id| positions.get(id).map(|(x, _)| *x)).collect();
+                let desired_start = (!parent_xs.is_empty())
+                    .then(|| parent_xs.iter().sum::<f32>() / parent_xs.len() as f32 - width / 2.0);
+                let mut x = match (desired_start, next_free_x) {
+                    (Some(desired), Some(free)) => desired.max(free),
+                    (Some(desired), None) => desired,
+                    (None, Some(free)) => free,
+                    (None, None) => origin.0,
+                };
+                for id in group.iter().flatten() {
+                    // 並び順で固定された人物より後ろの人物はその右側に置く
+                    if let Some((pinned_x, _)) = pinned.get(id) {
+                        x = x.max(pinned_x + x_spacing);
+                        continue;
+                    }
+                    while let Some(obstacle) = obstacles.iter().find(|obstacle| (*obstacle - x).abs() < x_spacing) {
+                        x = obstacle + x_spacing;
+                    }
+                    positions.insert(*id, (x, y));
+                    x += x_spacing;
+                }
+                next_free_x = Some(x);
+            }
         }
+        positions.into_iter().map(|(id, position)| (id, mirror(position))).collect()
+    }
 
-        nodes
+    /// ノードのレイアウトを計算（保存されている位置に、表示に合わせた大きさのノードを置く）
+    pub fn compute_layout(
+        tree: &FamilyTree,
+        photo_dimensions: &HashMap<PersonId, (u32, u32)>,
+        show_life_years: bool,
+    ) -> Vec<LayoutNode> {
+        let config = LayoutConfig { arrange: false, show_life_years, ..LayoutConfig::default() };
+        Self::layout_nodes(tree, &config, photo_dimensions)
+            .into_iter()
+            .map(|(id, node)| {
+                let pos = egui::pos2(node.x, node.y);
+                LayoutNode {
+                    id,
+                    generation: node.generation,
+                    pos,
+                    rect: egui::Rect::from_min_size(pos, egui::vec2(node.width, node.height)),
+                }
+            })
+            .collect()
     }
 
     /// 人物のラベル（表示テキスト）を生成
@@ -159,60 +561,7 @@ impl LayoutEngine {
         }
     }
     
-    /// 人物の詳細情報をツールチップ用に生成
-    pub fn person_tooltip(tree: &FamilyTree, id: PersonId, lang: Language) -> String {
-        if let Some(p) = tree.persons.get(&id) {
-            let mut tooltip = format!("{}: {}", Texts::get("tooltip_name", lang), p.name);
-            
-            let calculate_age = |birth: &str, end_date: Option<&str>| -> Option<i32> {
-                let birth_year = birth.split('-').next()?.parse::<i32>().ok()?;
-                let end_year = if let Some(ed) = end_date {
-                    ed.split('-').next()?.parse::<i32>().ok()?
-                } else {
-                    2026
-                };
-                Some(end_year - birth_year)
-            };
-            
-            if let Some(b) = &p.birth {
-                if !b.is_empty() {
-                    tooltip.push_str(&format!("\n{}: {}", Texts::get("tooltip_birth", lang), b));
-                    
-                    if p.deceased {
-                        if let Some(age) = calculate_age(b, p.death.as_deref()) {
-                            tooltip.push_str(&format!(" ({} {}{}) ", Texts::get("tooltip_died_at", lang), age, Texts::get("tooltip_age", lang)));
-                        }
-                    } else {
-                        if let Some(age) = calculate_age(b, None) {
-                            tooltip.push_str(&format!(" ({}{})", age, Texts::get("tooltip_age", lang)));
-                        }
-                    }
-                }
-            }
-            
-            if p.deceased {
-                if let Some(d) = &p.death {
-                    if !d.is_empty() {
-                        tooltip.push_str(&format!("\n{}: {}", Texts::get("tooltip_death", lang), d));
-                    } else {
-                        tooltip.push_str(&format!("\n{}: {}", Texts::get("tooltip_deceased", lang), Texts::get("tooltip_yes", lang)));
-                    }
-                } else {
-                    tooltip.push_str(&format!("\n{}: {}", Texts::get("tooltip_deceased", lang), Texts::get("tooltip_yes", lang)));
-                }
-            }
-            
-            if !p.memo.is_empty() {
-                tooltip.push_str(&format!("\n{}: {}", Texts::get("tooltip_memo", lang), p.memo));
-            }
-            
-            tooltip
-        } else {
-            "Unknown".into()
-        }
-    }
-
-    /// グリッド線を描画
+    /// グリッドを描画（太い線・大きい点は major_every マスごと）
     pub fn draw_grid(
         painter: &egui::Painter,
         rect: egui::Rect,
@@ -220,29 +569,57 @@ impl LayoutEngine {
         zoom: f32,
         pan: egui::Vec2,
         grid_size: f32,
+        settings: &GridSettings,
     ) {
-        let grid_size = grid_size * zoom;
+        let spacing = grid_size * zoom;
         let grid_origin = origin + pan;
-        
-        let start_x = ((rect.left() - grid_origin.x) / grid_size).floor() * grid_size + grid_origin.x;
-        let start_y = ((rect.top() - grid_origin.y) / grid_size).floor() * grid_size + grid_origin.y;
-        
-        let mut x = start_x;
-        while x <= rect.right() {
-            painter.line_segment(
-                [egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())],
-                egui::Stroke::new(0.5, egui::Color32::from_gray(220)),
-            );
-            x += grid_size;
+        let major_every = settings.major_every as i64;
+        let is_major = |index: i64| major_every > 0 && index.rem_euclid(major_every) == 0;
+        // 縮小して細かいグリッドが詰まりすぎる場合は太い線だけ描く
+        let draw_minor = spacing >= MIN_GRID_SCREEN_SPACING;
+        if !draw_minor && (major_every == 0 || spacing * (major_every as f32) < MIN_GRID_SCREEN_SPACING) {
+            return;
         }
-        
-        let mut y = start_y;
-        while y <= rect.bottom() {
-            painter.line_segment(
-                [egui::pos2(rect.left(), y), egui::pos2(rect.right(), y)],
-                egui::Stroke::new(0.5, egui::Color32::from_gray(220)),
-            );
-            y += grid_size;
+
+        let columns = ((rect.left() - grid_origin.x) / spacing).floor() as i64..=((rect.right() - grid_origin.x) / spacing).ceil() as i64;
+        let rows = ((rect.top() - grid_origin.y) / spacing).floor() as i64..=((rect.bottom() - grid_origin.y) / spacing).ceil() as i64;
+        let minor_color = egui::Color32::from_gray(220);
+        let major_color = egui::Color32::from_gray(195);
+
+        match settings.style {
+            GridStyle::Lines => {
+                for column in columns {
+                    let major = is_major(column);
+                    if !major && !draw_minor {
+                        continue;
+                    }
+                    let x = grid_origin.x + column as f32 * spacing;
+                    let stroke = if major { egui::Stroke::new(1.0, major_color) } else { egui::Stroke::new(0.5, minor_color) };
+                    painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], stroke);
+                }
+                for row in rows {
+                    let major = is_major(row);
+                    if !major && !draw_minor {
+                        continue;
+                    }
+                    let y = grid_origin.y + row as f32 * spacing;
+                    let stroke = if major { egui::Stroke::new(1.0, major_color) } else { egui::Stroke::new(0.5, minor_color) };
+                    painter.line_segment([egui::pos2(rect.left(), y), egui::pos2(rect.right(), y)], stroke);
+                }
+            }
+            GridStyle::Dots => {
+                for column in columns {
+                    for row in rows.clone() {
+                        let major = is_major(column) && is_major(row);
+                        if !major && !draw_minor {
+                            continue;
+                        }
+                        let center = grid_origin + egui::vec2(column as f32, row as f32) * spacing;
+                        let (radius, color) = if major { (1.8, major_color) } else { (1.0, minor_color) };
+                        painter.circle_filled(center, radius, color);
+                    }
+                }
+            }
         }
     }
 
@@ -253,6 +630,132 @@ impl LayoutEngine {
         egui::pos2(x, y)
     }
 
+    /// 強さに応じてグリッドにスナップする（交点から grid_size / 2 × strength 以内の軸だけ合わせる）
+    pub fn snap_to_grid_with_strength(pos: egui::Pos2, grid_size: f32, strength: f32) -> egui::Pos2 {
+        let reach = grid_size / 2.0 * strength.clamp(0.0, 1.0);
+        let snapped = Self::snap_to_grid(pos, grid_size);
+        let snap_axis = |value: f32, target: f32| if (value - target).abs() <= reach { target } else { value };
+        egui::pos2(snap_axis(pos.x, snapped.x), snap_axis(pos.y, snapped.y))
+    }
+
+    /// 相手の人物の上・下・横で、他の人物と重ならない位置（左上）を返す（相手がいなければ None）
+    ///
+    /// 既に人物がいる場所は右へ x_spacing ずつずらす。置く人物自身の今の位置は空いているものとして扱う。
+    pub fn position_near(
+        tree: &FamilyTree,
+        anchor: PersonId,
+        person: PersonId,
+        placement: RelativePlacement,
+        x_spacing: f32,
+        y_spacing: f32,
+    ) -> Option<(f32, f32)> {
+        let (anchor_x, anchor_y) = tree.persons.get(&anchor)?.position;
+        let (mut x, y) = match placement {
+            RelativePlacement::Parent => (anchor_x, anchor_y - y_spacing),
+            RelativePlacement::Child => (anchor_x, anchor_y + y_spacing),
+            RelativePlacement::Spouse => (anchor_x + x_spacing, anchor_y),
+        };
+        let occupied: Vec<(f32, f32)> = tree
+            .persons
+            .values()
+            .filter(|other| other.id != person)
+            .map(|other| other.position)
+            .filter(|(_, other_y)| (other_y - y).abs() < y_spacing / 2.0)
+            .collect();
+        while occupied.iter().any(|(other_x, _)| (other_x - x).abs() < x_spacing / 2.0) {
+            x += x_spacing;
+        }
+        Some((x, y))
+    }
+
+    /// 距離が threshold 以内にある最も近いガイド線に座標をそろえる（pos は原点からの相対座標）
+    pub fn snap_to_guides(pos: egui::Pos2, guides: &[CanvasGuide], threshold: f32) -> egui::Pos2 {
+        let nearest = |axis: GuideAxis, value: f32| {
+            guides
+                .iter()
+                .filter(|guide| guide.axis == axis && (guide.position - value).abs() <= threshold)
+                .min_by(|a, b| (a.position - value).abs().total_cmp(&(b.position - value).abs()))
+                .map_or(value, |guide| guide.position)
+        };
+        egui::pos2(nearest(GuideAxis::Vertical, pos.x), nearest(GuideAxis::Horizontal, pos.y))
+    }
+
+    /// 人物ノードの位置と大きさを、egui の状態に依存しない値として計算する
+    ///
+    /// 同じ家系図と条件からは常に同じ結果になる（ノードは上から、同じ高さなら左から並べる）。
+    fn layout_nodes(
+        tree: &FamilyTree,
+        config: &LayoutConfig,
+        photo_dimensions: &HashMap<PersonId, (u32, u32)>,
+    ) -> Vec<(PersonId, LayoutDataNode)> {
+        let generations = Self::generation_map(tree);
+        let positions = if config.arrange {
+            Self::auto_positions(tree, (0.0, 0.0), config.x_spacing, config.y_spacing, config.direction)
+        } else {
+            tree.persons.values().map(|person| (person.id, person.position)).collect()
+        };
+
+        let mut nodes: Vec<(PersonId, LayoutDataNode)> = tree
+            .persons
+            .values()
+            .map(|person| {
+                let (x, y) = positions.get(&person.id).copied().unwrap_or(person.position);
+                let (width, height) = Self::calculate_person_node_size(
+                    &person.name,
+                    person.display_mode,
+                    person.photo_scale,
+                    photo_dimensions.get(&person.id).copied(),
+                    config.show_life_years && person.life_span_label().is_some(),
+                );
+                let node = LayoutDataNode {
+                    name: person.name.clone(),
+                    generation: generations.get(&person.id).copied().unwrap_or_default(),
+                    x,
+                    y,
+                    width,
+                    height,
+                };
+                (person.id, node)
+            })
+            .collect();
+        nodes.sort_by(|(a_id, a), (b_id, b)| {
+            a.y.total_cmp(&b.y)
+                .then(a.x.total_cmp(&b.x))
+                .then_with(|| a.name.cmp(&b.name))
+                .then(a_id.cmp(b_id))
+        });
+        nodes
+    }
+
+    /// 人物ノードと関係線を、固定の JSON と比べられる値として計算する（写真の大きさは考えない）
+    #[cfg(test)]
+    pub fn layout_data(tree: &FamilyTree, config: &LayoutConfig) -> LayoutData {
+        let nodes = Self::layout_nodes(tree, config, &HashMap::new());
+        let index: HashMap<PersonId, usize> = nodes.iter().enumerate().map(|(i, (id, _))| (*id, i)).collect();
+        let mut edges: Vec<LayoutDataEdge> = tree
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                Some(LayoutDataEdge {
+                    from: *index.get(&edge.parent)?,
+                    to: *index.get(&edge.child)?,
+                    kind: edge.kind.clone(),
+                })
+            })
+            .chain(tree.spouses.iter().filter_map(|spouse| {
+                let (a, b) = (*index.get(&spouse.person1)?, *index.get(&spouse.person2)?);
+                Some(LayoutDataEdge { from: a.min(b), to: a.max(b), kind: "spouse".to_string() })
+            }))
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        LayoutData {
+            nodes: nodes.into_iter().map(|(_, node)| node).collect(),
+            edges,
+        }
+    }
+
     /// イベント名からノードサイズを計算
     pub fn calculate_event_node_size(event_name: &str, lang: Language) -> (f32, f32) {
         // イベントノードの高さ：フォントサイズ13.0 + 上下パディング
@@ -315,8 +818,74 @@ impl LayoutEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::sample_data::{generate_sample_tree, SampleNameLocale, SampleOptions};
     use crate::core::tree::{FamilyTree, Gender};
 
+    /// 固定の JSON と比べる（UPDATE_LAYOUT_FIXTURES を設定して実行すると書き直す）
+    fn assert_matches_fixture(name: &str, data: &LayoutData) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/layout")
+            .join(format!("{name}.json"));
+        if std::env::var_os("UPDATE_LAYOUT_FIXTURES").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, serde_json::to_string_pretty(data).unwrap() + "\n").unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("{} がありません（UPDATE_LAYOUT_FIXTURES=1 で作成）", path.display()));
+        let expected: LayoutData = serde_json::from_str(&expected).unwrap();
+        assert_eq!(*data, expected, "{} と一致しません", path.display());
+    }
+
+    fn example_tree() -> FamilyTree {
+        let json = include_str!("../../examples/sample.json");
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn seeded_sample_tree() -> FamilyTree {
+        generate_sample_tree(&SampleOptions {
+            generations: 3,
+            children_per_couple: 2,
+            locale: SampleNameLocale::English,
+            seed: 7,
+            ..SampleOptions::default()
+        })
+    }
+
+    #[test]
+    fn test_layout_data_matches_fixtures() {
+        let tree = example_tree();
+        assert_matches_fixture("sample_arranged", &LayoutEngine::layout_data(&tree, &LayoutConfig::default()));
+        let stored = LayoutConfig { arrange: false, show_life_years: false, ..LayoutConfig::default() };
+        assert_matches_fixture("sample_stored", &LayoutEngine::layout_data(&tree, &stored));
+        assert_matches_fixture("seeded_sample", &LayoutEngine::layout_data(&seeded_sample_tree(), &LayoutConfig::default()));
+    }
+
+    #[test]
+    fn test_layout_data_is_independent_of_ids_and_map_order() {
+        // 生成のたびに ID が変わっても、同じシードなら同じ結果になる
+        let first = seeded_sample_tree();
+        let mut keys: Vec<(String, Option<String>)> =
+            first.persons.values().map(|p| (p.name.clone(), p.birth.clone())).collect();
+        keys.sort();
+        let count = keys.len();
+        keys.dedup();
+        assert_eq!(keys.len(), count, "名前と生年月日が重なると並び順が ID に左右される");
+        let config = LayoutConfig::default();
+        assert_eq!(
+            LayoutEngine::layout_data(&first, &config),
+            LayoutEngine::layout_data(&seeded_sample_tree(), &config)
+        );
+
+        // HashMap を作り直しても結果は変わらない
+        let tree = example_tree();
+        let mut rebuilt = tree.clone();
+        let mut persons: Vec<_> = tree.persons.clone().into_iter().collect();
+        persons.reverse();
+        rebuilt.persons = persons.into_iter().collect();
+        assert_eq!(LayoutEngine::layout_data(&tree, &config), LayoutEngine::layout_data(&rebuilt, &config));
+    }
+
     #[test]
     fn test_person_label_basic() {
         let mut tree = FamilyTree::default();
@@ -385,78 +954,6 @@ mod tests {
         assert_eq!(label, "Bob");
     }
 
-    #[test]
-    fn test_person_tooltip_basic() {
-        let mut tree = FamilyTree::default();
-        let id = tree.add_person(
-            "Test Person".to_string(),
-            Gender::Unknown,
-            None,
-            "".to_string(),
-            false,
-            None,
-            (0.0, 0.0),
-        );
-        
-        let tooltip_ja = LayoutEngine::person_tooltip(&tree, id, Language::Japanese);
-        assert!(tooltip_ja.contains("名前: Test Person"));
-        
-        let tooltip_en = LayoutEngine::person_tooltip(&tree, id, Language::English);
-        assert!(tooltip_en.contains("Name: Test Person"));
-    }
-
-    #[test]
-    fn test_person_tooltip_with_details() {
-        let mut tree = FamilyTree::default();
-        let id = tree.add_person(
-            "John".to_string(),
-            Gender::Male,
-            Some("1990-05-15".to_string()),
-            "テストメモ".to_string(),
-            false,
-            None,
-            (0.0, 0.0),
-        );
-        
-        let tooltip_ja = LayoutEngine::person_tooltip(&tree, id, Language::Japanese);
-        assert!(tooltip_ja.contains("名前: John"));
-        assert!(tooltip_ja.contains("生年月日: 1990-05-15"));
-        assert!(tooltip_ja.contains("36歳"));
-        assert!(tooltip_ja.contains("メモ: テストメモ"));
-        
-        let tooltip_en = LayoutEngine::person_tooltip(&tree, id, Language::English);
-        assert!(tooltip_en.contains("Name: John"));
-        assert!(tooltip_en.contains("Birth: 1990-05-15"));
-        assert!(tooltip_en.contains("36years old"));
-        assert!(tooltip_en.contains("Memo: テストメモ"));
-    }
-
-    #[test]
-    fn test_person_tooltip_deceased() {
-        let mut tree = FamilyTree::default();
-        let id = tree.add_person(
-            "Jane".to_string(),
-            Gender::Female,
-            Some("1950-01-01".to_string()),
-            "".to_string(),
-            true,
-            Some("2020-12-31".to_string()),
-            (0.0, 0.0),
-        );
-        
-        let tooltip_ja = LayoutEngine::person_tooltip(&tree, id, Language::Japanese);
-        assert!(tooltip_ja.contains("名前: Jane"));
-        assert!(tooltip_ja.contains("生年月日: 1950-01-01"));
-        assert!(tooltip_ja.contains("享年 70歳"));
-        assert!(tooltip_ja.contains("没年月日: 2020-12-31"));
-        
-        let tooltip_en = LayoutEngine::person_tooltip(&tree, id, Language::English);
-        assert!(tooltip_en.contains("Name: Jane"));
-        assert!(tooltip_en.contains("Birth: 1950-01-01"));
-        assert!(tooltip_en.contains("died at 70years old"));
-        assert!(tooltip_en.contains("Death: 2020-12-31"));
-    }
-
     #[test]
     fn test_compute_layout_single_person() {
         let mut tree = FamilyTree::default();
@@ -470,14 +967,31 @@ mod tests {
             (50.0, 75.0),
         );
         
-        let origin = egui::pos2(0.0, 0.0);
         let photo_dimensions = HashMap::new();
-        let nodes = LayoutEngine::compute_layout(&tree, origin, &photo_dimensions);
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
         
         assert_eq!(nodes.len(), 1);
         assert_eq!(nodes[0].generation, 0);
     }
 
+    #[test]
+    fn test_compute_layout_adds_life_years_line() {
+        let mut tree = FamilyTree::default();
+        let dated = tree.add_person("Dated".to_string(), Gender::Male, Some("1940-01-01".to_string()), "".to_string(), true, Some("2020-01-01".to_string()), (0.0, 0.0));
+        let undated = tree.add_person("Undated".to_string(), Gender::Male, None, "".to_string(), false, None, (200.0, 0.0));
+
+        let height = |show_life_years: bool, id: PersonId| {
+            LayoutEngine::compute_layout(&tree, &HashMap::new(), show_life_years)
+                .into_iter()
+                .find(|node| node.id == id)
+                .map(|node| node.rect.height())
+        };
+        assert_eq!(height(false, dated), Some(30.0));
+        assert_eq!(height(true, dated), Some(30.0 + LIFE_YEARS_LINE_HEIGHT));
+        // 生没年が分からない人物は高さを変えない
+        assert_eq!(height(true, undated), Some(30.0));
+    }
+
     #[test]
     fn test_compute_layout_parent_child() {
         let mut tree = FamilyTree::default();
@@ -502,9 +1016,8 @@ mod tests {
         
         tree.add_parent_child(parent, child, "biological".to_string());
         
-        let origin = egui::pos2(0.0, 0.0);
         let photo_dimensions = HashMap::new();
-        let nodes = LayoutEngine::compute_layout(&tree, origin, &photo_dimensions);
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
         
         assert_eq!(nodes.len(), 2);
         
@@ -528,9 +1041,8 @@ mod tests {
             (100.0, 200.0),
         );
         
-        let origin = egui::pos2(0.0, 0.0);
         let photo_dimensions = HashMap::new();
-        let nodes = LayoutEngine::compute_layout(&tree, origin, &photo_dimensions);
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
         
         assert_eq!(nodes.len(), 1);
         assert_eq!(nodes[0].rect.left(), 100.0);
@@ -547,9 +1059,8 @@ mod tests {
         tree.add_parent_child(grandparent, parent, "biological".to_string());
         tree.add_parent_child(parent, child, "biological".to_string());
         
-        let origin = egui::pos2(0.0, 0.0);
         let photo_dimensions = HashMap::new();
-        let nodes = LayoutEngine::compute_layout(&tree, origin, &photo_dimensions);
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
         
         assert_eq!(nodes.len(), 3);
         
@@ -566,6 +1077,136 @@ mod tests {
         assert!(p_node.rect.top() < c_node.rect.top());
     }
 
+    #[test]
+    fn test_semantic_zoom_detail_level() {
+        let thresholds = SemanticZoomThresholds::default();
+        assert_eq!(thresholds.detail_level(1.0), NodeDetailLevel::Full);
+        assert_eq!(thresholds.detail_level(0.6), NodeDetailLevel::Full);
+        assert_eq!(thresholds.detail_level(0.55), NodeDetailLevel::NameAndDates);
+        assert_eq!(thresholds.detail_level(0.4), NodeDetailLevel::NameOnly);
+        assert_eq!(thresholds.detail_level(0.3), NodeDetailLevel::Block);
+
+        // 閾値を0にすると常にすべて表示
+        let always_full = SemanticZoomThresholds {
+            photo: 0.0,
+            dates: 0.0,
+            name: 0.0,
+        };
+        assert_eq!(always_full.detail_level(0.3), NodeDetailLevel::Full);
+    }
+
+    #[test]
+    fn test_auto_layout_keeps_couples_together_with_children_below() {
+        let mut tree = FamilyTree::default();
+        let mut add = |name: &str| tree.add_person(name.to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        let husband = add("A-Husband");
+        let other = add("B-Other");
+        let wife = add("Z-Wife");
+        let son = add("C-Son");
+        let daughter = add("D-Daughter");
+        let son_wife = add("Y-SonWife");
+        let grandchild = add("E-Grandchild");
+        tree.add_spouse(husband, wife, "".to_string());
+        tree.add_spouse(son, son_wife, "".to_string());
+        for (parent, child) in [(husband, son), (wife, son), (husband, daughter), (wife, daughter), (son, grandchild), (son_wife, grandchild)] {
+            tree.add_parent_child(parent, child, "biological".to_string());
+        }
+
+        // 婚姻で加わった配偶者は相手と同じ世代・隣に並ぶ
+        let rows = LayoutEngine::generation_rows(&tree);
+        assert_eq!(
+            rows,
+            vec![(0, vec![husband, wife, other]), (1, vec![son, son_wife, daughter]), (2, vec![grandchild])]
+        );
+
+        let positions = LayoutEngine::auto_positions(&tree, (0.0, 0.0), 100.0, 50.0, LayoutDirection::LeftToRight);
+        assert_eq!(positions[&husband], (0.0, 0.0));
+        assert_eq!(positions[&wife], (100.0, 0.0));
+        // きょうだい（息子夫婦と娘）は両親の中央の下を中心に置く
+        let couple_center = 50.0;
+        assert_eq!(positions[&son].0 + positions[&daughter].0, couple_center * 2.0);
+        assert_eq!(positions[&son_wife].1, 50.0);
+        // 孫は息子夫婦の中央の下
+        assert_eq!(positions[&grandchild].0, (positions[&son].0 + positions[&son_wife].0) / 2.0);
+        assert_eq!(positions[&grandchild].1, 100.0);
+    }
+
+    #[test]
+    fn test_auto_layout_keeps_pinned_persons_fixed() {
+        let mut tree = FamilyTree::default();
+        let mut add = |name: &str| tree.add_person(name.to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        let ancestor = add("A-Ancestor");
+        let sibling = add("B-Sibling");
+        let child = add("C-Child");
+        tree.add_parent_child(ancestor, child, "biological".to_string());
+        let pinned = tree.persons.get_mut(&ancestor).unwrap();
+        pinned.pinned = true;
+        pinned.position = (500.0, 300.0);
+
+        let positions = LayoutEngine::auto_positions(&tree, (0.0, 0.0), 100.0, 50.0, LayoutDirection::LeftToRight);
+
+        assert_eq!(positions[&ancestor], (500.0, 300.0));
+        // 同じ行は固定された人物の高さにそろい、重ならないよう右へずれる
+        assert_eq!(positions[&sibling], (600.0, 300.0));
+        // 子は固定された親の真下
+        assert_eq!(positions[&child], (500.0, 350.0));
+    }
+
+    #[test]
+    fn test_auto_layout_keeps_locked_persons_fixed() {
+        let mut tree = FamilyTree::default();
+        let parent = tree.add_person("A-Parent".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        let child = tree.add_person("B-Child".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.add_parent_child(parent, child, "biological".to_string());
+        let locked = tree.persons.get_mut(&child).unwrap();
+        locked.locked = true;
+        locked.position = (320.0, 240.0);
+
+        let positions = LayoutEngine::auto_positions(&tree, (0.0, 0.0), 100.0, 50.0, LayoutDirection::LeftToRight);
+
+        assert_eq!(positions[&child], (320.0, 240.0));
+        assert_eq!(positions[&parent], (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_generation_rows_do_not_depend_on_direction() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("A-Father".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("B-Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (200.0, 0.0));
+        let child = tree.add_person("C-Child".to_string(), Gender::Unknown, None, "".to_string(), false, None, (100.0, 100.0));
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "biological".to_string());
+
+        let rows = LayoutEngine::generation_rows(&tree);
+        assert_eq!(rows, vec![(0, vec![father, mother]), (1, vec![child])]);
+
+        // 保存した位置は向きに関係なく変わらない
+        let nodes = LayoutEngine::compute_layout(&tree, &HashMap::new(), false);
+        let father_node = nodes.iter().find(|n| n.id == father).expect("father should be laid out");
+        assert_eq!(father_node.rect.left_top(), egui::pos2(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_auto_positions_right_to_left_mirrors_around_origin() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("A-Father".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("B-Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let child = tree.add_person("C-Child".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, mother, "".to_string());
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "biological".to_string());
+
+        let ltr = LayoutEngine::auto_positions(&tree, (400.0, 0.0), 100.0, 50.0, LayoutDirection::LeftToRight);
+        assert!(ltr[&father].0 < ltr[&child].0);
+
+        let rtl = LayoutEngine::auto_positions(&tree, (400.0, 0.0), 100.0, 50.0, LayoutDirection::RightToLeft);
+        assert_eq!(rtl[&father], (400.0, 0.0));
+        assert_eq!(rtl[&mother], (300.0, 0.0));
+        // 父は子の右側に来る
+        assert!(rtl[&father].0 > rtl[&child].0);
+        assert_eq!(rtl[&child], (350.0, 50.0));
+    }
+
     #[test]
     fn test_compute_layout_with_photo_dimensions_hint() {
         let mut tree = FamilyTree::default();
@@ -589,8 +1230,7 @@ mod tests {
         let mut photo_dimensions = HashMap::new();
         photo_dimensions.insert(person_id, (200, 100));
 
-        let origin = egui::pos2(0.0, 0.0);
-        let nodes = LayoutEngine::compute_layout(&tree, origin, &photo_dimensions);
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
 
         assert_eq!(nodes.len(), 1);
         let node = &nodes[0];
@@ -600,6 +1240,21 @@ mod tests {
         assert_eq!(node.rect.height(), 80.0);
     }
 
+    #[test]
+    fn test_photo_only_node_has_no_name_area() {
+        let mut tree = FamilyTree::default();
+        let person_id = tree.add_person("Photo".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.persons.get_mut(&person_id).unwrap().display_mode = PersonDisplayMode::PhotoOnly;
+        let photo_dimensions = HashMap::from([(person_id, (120, 160))]);
+
+        let nodes = LayoutEngine::compute_layout(&tree, &photo_dimensions, false);
+        assert_eq!(nodes[0].rect.size(), egui::vec2(120.0, 160.0));
+
+        // 写真が読めない場合は名前だけのノードと同じ大きさ
+        let nodes = LayoutEngine::compute_layout(&tree, &HashMap::new(), false);
+        assert_eq!(nodes[0].rect.height(), 30.0);
+    }
+
     #[test]
     fn test_person_label_unknown_id() {
         let tree = FamilyTree::default();
@@ -618,6 +1273,73 @@ mod tests {
         assert_eq!(snapped.y, 550.0);
     }
 
+    #[test]
+    fn test_snap_to_grid_with_strength() {
+        let pos = egui::pos2(110.0, 540.0);
+        assert_eq!(LayoutEngine::snap_to_grid_with_strength(pos, 50.0, 1.0), egui::pos2(100.0, 550.0));
+        // 強さ 0.5 では交点から 12.5 以内の軸だけ合わせる
+        assert_eq!(LayoutEngine::snap_to_grid_with_strength(egui::pos2(115.0, 540.0), 50.0, 0.5), egui::pos2(115.0, 550.0));
+        assert_eq!(LayoutEngine::snap_to_grid_with_strength(pos, 50.0, 0.0), pos);
+    }
+
+    #[test]
+    fn test_position_near_avoids_occupied_slots() {
+        let mut tree = FamilyTree::default();
+        let anchor = tree.add_person("Anchor".to_string(), Gender::Male, None, String::new(), false, None, (100.0, 200.0));
+        let wife = tree.add_person("Wife".to_string(), Gender::Female, None, String::new(), false, None, (260.0, 200.0));
+        tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (100.0, 60.0));
+        let new = tree.add_person("New".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+
+        let near = |placement| LayoutEngine::position_near(&tree, anchor, new, placement, 160.0, 140.0);
+        // 親の場所には既に父がいるので右へずらす
+        assert_eq!(near(RelativePlacement::Parent), Some((260.0, 60.0)));
+        assert_eq!(near(RelativePlacement::Child), Some((100.0, 340.0)));
+        assert_eq!(near(RelativePlacement::Spouse), Some((420.0, 200.0)));
+        // 置く人物自身の位置は避けない
+        assert_eq!(LayoutEngine::position_near(&tree, anchor, wife, RelativePlacement::Spouse, 160.0, 140.0), Some((260.0, 200.0)));
+        assert_eq!(LayoutEngine::position_near(&tree, PersonId::new_v4(), new, RelativePlacement::Child, 160.0, 140.0), None);
+    }
+
+    #[test]
+    fn test_snap_to_guides() {
+        let mut tree = FamilyTree::default();
+        tree.add_guide(GuideAxis::Vertical, 100.0);
+        tree.add_guide(GuideAxis::Vertical, 106.0);
+        tree.add_guide(GuideAxis::Horizontal, 300.0);
+
+        // 近いほうの縦線にそろえ、遠い横線には吸着しない
+        let snapped = LayoutEngine::snap_to_guides(egui::pos2(104.0, 280.0), &tree.guides, 8.0);
+        assert_eq!(snapped, egui::pos2(106.0, 280.0));
+        let snapped = LayoutEngine::snap_to_guides(egui::pos2(40.0, 295.5), &tree.guides, 8.0);
+        assert_eq!(snapped, egui::pos2(40.0, 300.0));
+    }
+
+    #[test]
+    fn test_long_names_wrap_to_two_lines_then_ellipsize() {
+        // 空白のある名前は空白で折り返す
+        let lines = LayoutEngine::wrap_name("Alexander Christopher Montgomery-Smith", 234.0, 2);
+        assert_eq!(lines, vec!["Alexander Christopher".to_string(), "Montgomery-Smith".to_string()]);
+        // 漢字の名前は収まる文字数で区切り、2行に収まらない分は省略する
+        let name = "寿限無寿限無五劫の擦り切れ海砂利水魚の水行末雲来末風来末食う寝る処に住む処";
+        let lines = LayoutEngine::wrap_name(name, 234.0, 2);
+        assert_eq!(lines[0].chars().count(), 16);
+        assert!(lines[1].ends_with('…'));
+        assert!(LayoutEngine::estimate_text_width(&lines[1]) <= 234.0);
+        assert_eq!(LayoutEngine::wrap_name(name, 234.0, 1).len(), 1);
+        assert_eq!(LayoutEngine::wrap_name("Taro", 234.0, 2), vec!["Taro".to_string()]);
+        // 描画時は実際のフォントの幅で同じ規則を使う
+        assert_eq!(LayoutEngine::wrap_name_measured("Taro Yamada", 50.0, 2, |_| 10.0), vec!["Taro", "Yama…"]);
+
+        // 2行になる名前はノードを名前1行分だけ高くし、幅は最大幅に収める
+        let (width, height) =
+            LayoutEngine::calculate_person_node_size(name, PersonDisplayMode::NameOnly, 1.0, None, false);
+        assert!(width > 200.0 && width <= 250.0);
+        assert_eq!(height, 30.0 + NAME_LINE_HEIGHT);
+        let (width, height) =
+            LayoutEngine::calculate_person_node_size("山田太郎", PersonDisplayMode::NameOnly, 1.0, None, false);
+        assert_eq!((width, height), (100.0, 30.0));
+    }
+
     #[test]
     fn test_calculate_event_node_size_empty_name() {
         let (width, height) = LayoutEngine::calculate_event_node_size("", Language::Japanese);
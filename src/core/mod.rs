@@ -1,3 +1,18 @@
 pub mod tree;
 pub mod layout;
 pub mod i18n;
+pub mod validation;
+pub mod filter;
+pub mod fuzzy;
+pub mod find_replace;
+pub mod bulk_edit;
+pub mod sample_data;
+pub mod slideshow;
+pub mod person_card;
+pub mod statistics;
+pub mod relation_cleanup;
+pub mod date_normalize;
+pub mod edit_macro;
+pub mod event_template;
+pub mod undo;
+pub mod place_map;
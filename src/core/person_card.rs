@@ -0,0 +1,287 @@
+use crate::core::i18n::{Language, Texts};
+use crate::core::layout::LayoutEngine;
+use crate::core::tree::{FamilyTree, PersonId};
+
+/// ホバーカードに並べる家族へのリンクの1グループ（例: 親）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamilyLinkGroup {
+    /// 見出しの翻訳キー
+    pub label_key: &'static str,
+    /// 人物IDと表示名
+    pub persons: Vec<(PersonId, String)>,
+}
+
+impl FamilyLinkGroup {
+    /// 要約では名前を並べず人数だけを示すグループか（子・継子）
+    pub fn is_counted(&self) -> bool {
+        matches!(self.label_key, "tooltip_children" | "tooltip_step_children")
+    }
+}
+
+/// キャンバスで人物にマウスを乗せたときに表示するカードの内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonCard {
+    pub name: String,
+    /// 人物自身の写真（既定の写真は表示しない）
+    pub photo_path: Option<String>,
+    /// 生年月日・没年月日・メモなどの行
+    pub details: Vec<String>,
+    /// 親・配偶者・子（継親・継子）へのリンク。該当者のいないグループは含まない
+    pub family: Vec<FamilyLinkGroup>,
+    /// タグ（人物が所属する家族グループの名前）
+    pub tags: Vec<String>,
+}
+
+impl PersonCard {
+    /// 人物のカードを組み立てる（存在しない人物なら None）
+    ///
+    /// 存命の人物の年齢は `current_year` 時点で数える。
+    pub fn build(tree: &FamilyTree, id: PersonId, lang: Language, include_step_relations: bool, current_year: i32) -> Option<Self> {
+        let person = tree.persons.get(&id)?;
+
+        let link_group = |label_key: &'static str, ids: Vec<PersonId>| {
+            (!ids.is_empty()).then(|| FamilyLinkGroup {
+                label_key,
+                persons: ids
+                    .into_iter()
+                    .map(|id| (id, LayoutEngine::person_label(tree, id)))
+                    .collect(),
+            })
+        };
+        let mut family: Vec<FamilyLinkGroup> = [
+            link_group("tooltip_parents", tree.parents_of(id)),
+            link_group("tooltip_spouses", tree.spouses_of(id)),
+            link_group("tooltip_children", tree.children_of(id)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if include_step_relations {
+            family.extend(link_group("tooltip_step_parents", tree.step_parents_of(id)));
+            family.extend(link_group("tooltip_step_children", tree.step_children_of(id)));
+        }
+
+        let tags = tree
+            .families
+            .iter()
+            .filter(|group| group.members.contains(&id))
+            .map(|group| group.name.clone())
+            .collect();
+
+        Some(Self {
+            name: person.name.clone(),
+            photo_path: person.has_own_photo().then(|| person.photo_path.clone()).flatten(),
+            details: Self::detail_lines(tree, id, lang, current_year),
+            family,
+            tags,
+        })
+    }
+
+    /// 関係の要約の1行（例: "Parents: A, B · Spouses: C · Children: 3"）。家族がいなければ None
+    ///
+    /// 子・継子は人数だけを示し、親・配偶者は名前を並べる。
+    pub fn relation_summary(&self, lang: Language) -> Option<String> {
+        let parts: Vec<String> = self
+            .family
+            .iter()
+            .map(|group| {
+                let value = if group.is_counted() {
+                    group.persons.len().to_string()
+                } else {
+                    group.persons.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>().join(", ")
+                };
+                format!("{}: {}", Texts::get(group.label_key, lang), value)
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+
+    /// スクリーンリーダーで読み上げる説明（名前・日付・家族の要約）
+    pub fn accessible_label(&self, lang: Language) -> String {
+        let mut parts = vec![self.name.clone()];
+        parts.extend(self.details.iter().cloned());
+        for group in &self.family {
+            let names: Vec<&str> = group.persons.iter().map(|(_, name)| name.as_str()).collect();
+            parts.push(format!("{}: {}", Texts::get(group.label_key, lang), names.join(", ")));
+        }
+        parts.join(". ")
+    }
+
+    /// 生年月日（年齢）・没年月日・メモの行を生成
+    fn detail_lines(tree: &FamilyTree, id: PersonId, lang: Language, current_year: i32) -> Vec<String> {
+        let Some(p) = tree.persons.get(&id) else {
+            return Vec::new();
+        };
+        let mut lines = Vec::new();
+
+        let calculate_age = |birth: &str, end_date: Option<&str>| -> Option<i32> {
+            let birth_year = birth.split('-').next()?.parse::<i32>().ok()?;
+            let end_year = if let Some(ed) = end_date {
+                ed.split('-').next()?.parse::<i32>().ok()?
+            } else {
+                current_year
+            };
+            Some(end_year - birth_year)
+        };
+
+        if let Some(b) = p.birth.as_deref().filter(|b| !b.is_empty()) {
+            let mut line = format!("{}: {}", Texts::get("tooltip_birth", lang), b);
+            if p.deceased {
+                if let Some(age) = calculate_age(b, p.death.as_deref()) {
+                    line.push_str(&format!(" ({} {}{})", Texts::get("tooltip_died_at", lang), age, Texts::get("tooltip_age", lang)));
+                }
+            } else if let Some(age) = calculate_age(b, None) {
+                line.push_str(&format!(" ({}{})", age, Texts::get("tooltip_age", lang)));
+            }
+            lines.push(line);
+        }
+
+        if p.deceased {
+            match p.death.as_deref().filter(|d| !d.is_empty()) {
+                Some(d) => lines.push(format!("{}: {}", Texts::get("tooltip_death", lang), d)),
+                None => lines.push(format!("{}: {}", Texts::get("tooltip_deceased", lang), Texts::get("tooltip_yes", lang))),
+            }
+        }
+
+        if !p.memo.is_empty() {
+            lines.push(format!("{}: {}", Texts::get("tooltip_memo", lang), p.memo));
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_card_basic() {
+        let mut tree = FamilyTree::default();
+        let id = tree.add_person("Test Person".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+
+        let card = PersonCard::build(&tree, id, Language::English, false, 2026).expect("card should be built");
+        assert_eq!(card.name, "Test Person");
+        assert!(card.details.is_empty());
+        assert!(card.family.is_empty());
+        assert!(card.photo_path.is_none());
+        assert!(PersonCard::build(&tree, PersonId::new_v4(), Language::English, false, 2026).is_none());
+    }
+
+    #[test]
+    fn test_card_details() {
+        let mut tree = FamilyTree::default();
+        let id = tree.add_person(
+            "John".to_string(),
+            Gender::Male,
+            Some("1990-05-15".to_string()),
+            "テストメモ".to_string(),
+            false,
+            None,
+            (0.0, 0.0),
+        );
+
+        let card_ja = PersonCard::build(&tree, id, Language::Japanese, false, 2026).unwrap();
+        assert_eq!(card_ja.details, ["生年月日: 1990-05-15 (36歳)", "メモ: テストメモ"]);
+
+        let card_en = PersonCard::build(&tree, id, Language::English, false, 2026).unwrap();
+        assert_eq!(card_en.details, ["Birth: 1990-05-15 (36years old)", "Memo: テストメモ"]);
+
+        // 存命の人物の年齢は渡した年で数える
+        let card_later = PersonCard::build(&tree, id, Language::English, false, 2030).unwrap();
+        assert_eq!(card_later.details[0], "Birth: 1990-05-15 (40years old)");
+    }
+
+    #[test]
+    fn test_card_deceased() {
+        let mut tree = FamilyTree::default();
+        let id = tree.add_person(
+            "Jane".to_string(),
+            Gender::Female,
+            Some("1950-01-01".to_string()),
+            "".to_string(),
+            true,
+            Some("2020-12-31".to_string()),
+            (0.0, 0.0),
+        );
+
+        let card_ja = PersonCard::build(&tree, id, Language::Japanese, false, 2026).unwrap();
+        assert_eq!(card_ja.details, ["生年月日: 1950-01-01 (享年 70歳)", "没年月日: 2020-12-31"]);
+
+        let card_en = PersonCard::build(&tree, id, Language::English, false, 2026).unwrap();
+        assert_eq!(card_en.details, ["Birth: 1950-01-01 (died at 70years old)", "Death: 2020-12-31"]);
+    }
+
+    #[test]
+    fn test_card_family_links_and_tags() {
+        let mut tree = FamilyTree::default();
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let step_father = tree.add_person("Step".to_string(), Gender::Male, None, "".to_string(), false, None, (100.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 100.0));
+        tree.add_parent_child(mother, child, "biological".to_string());
+        tree.add_spouse(mother, step_father, "".to_string());
+        let family_id = tree.add_family("Smith".to_string(), None);
+        tree.add_member_to_family(family_id, child);
+
+        let card = PersonCard::build(&tree, child, Language::English, false, 2026).unwrap();
+        assert_eq!(
+            card.family,
+            [FamilyLinkGroup { label_key: "tooltip_parents", persons: vec![(mother, "Mother".to_string())] }]
+        );
+        assert_eq!(card.tags, ["Smith"]);
+
+        // 継親・継子は設定で有効なときだけ含める
+        let card = PersonCard::build(&tree, child, Language::English, true, 2026).unwrap();
+        assert_eq!(card.family[1].label_key, "tooltip_step_parents");
+        assert_eq!(card.family[1].persons, [(step_father, "Step".to_string())]);
+
+        let card = PersonCard::build(&tree, step_father, Language::English, true, 2026).unwrap();
+        let labels: Vec<&str> = card.family.iter().map(|group| group.label_key).collect();
+        assert_eq!(labels, ["tooltip_spouses", "tooltip_step_children"]);
+
+        let card = PersonCard::build(&tree, mother, Language::English, true, 2026).unwrap();
+        let labels: Vec<&str> = card.family.iter().map(|group| group.label_key).collect();
+        assert_eq!(labels, ["tooltip_spouses", "tooltip_children"]);
+    }
+
+    #[test]
+    fn test_card_relation_summary() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let person = tree.add_person("Person".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 100.0));
+        let wife = tree.add_person("Wife".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 100.0));
+        tree.add_parent_child(father, person, "biological".to_string());
+        tree.add_parent_child(mother, person, "biological".to_string());
+        tree.add_spouse(person, wife, "".to_string());
+        for name in ["A", "B", "C"] {
+            let child = tree.add_person(name.to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 200.0));
+            tree.add_parent_child(person, child, "biological".to_string());
+        }
+
+        let card = PersonCard::build(&tree, person, Language::English, false, 2026).unwrap();
+        assert_eq!(
+            card.relation_summary(Language::English).as_deref(),
+            Some("Parents: Father, Mother · Spouses: Wife · Children: 3")
+        );
+        let loner = tree.add_person("Loner".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        assert!(PersonCard::build(&tree, loner, Language::English, false, 2026).unwrap().relation_summary(Language::English).is_none());
+    }
+
+    #[test]
+    fn test_card_accessible_label() {
+        let mut tree = FamilyTree::default();
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, Some("1950-01-01".to_string()), "".to_string(), true, Some("2020-12-31".to_string()), (0.0, 0.0));
+        let son = tree.add_person("Son".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 100.0));
+        let daughter = tree.add_person("Daughter".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 100.0));
+        tree.add_parent_child(mother, son, "biological".to_string());
+        tree.add_parent_child(mother, daughter, "biological".to_string());
+
+        let card = PersonCard::build(&tree, mother, Language::English, false, 2026).unwrap();
+        assert_eq!(
+            card.accessible_label(Language::English),
+            "Mother. Birth: 1950-01-01 (died at 70years old). Death: 2020-12-31. Children: Son, Daughter"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::OnceLock;
+
+use crate::core::tree::{FamilyTree, Person, PersonId, PlaceId};
+
+/// 地図に置く印の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapPinKind {
+    Birth,
+    Death,
+}
+
+/// 地図上の1人分の印（出生地または死亡地）
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapPin {
+    pub person: PersonId,
+    pub place: PlaceId,
+    pub kind: MapPinKind,
+    /// 緯度・経度（度）
+    pub coordinates: (f64, f64),
+}
+
+/// 親の出生地から子の出生地への移動（世代をまたいだ家族の移住）
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationLine {
+    pub parent: PersonId,
+    pub child: PersonId,
+    pub from: (f64, f64),
+    pub to: (f64, f64),
+}
+
+/// 場所の緯度・経度（どちらかが未設定なら None）
+fn place_coordinates(tree: &FamilyTree, place: Option<PlaceId>) -> Option<(PlaceId, (f64, f64))> {
+    let place = tree.get_place(place?)?;
+    Some((place.id, (place.latitude?, place.longitude?)))
+}
+
+fn birth_coordinates(tree: &FamilyTree, person: &Person) -> Option<(f64, f64)> {
+    place_coordinates(tree, person.birth_place).map(|(_, coordinates)| coordinates)
+}
+
+/// 座標のある出生地（include_deaths なら死亡地も）の印を人物名順に集める
+pub fn collect_pins(tree: &FamilyTree, include_deaths: bool) -> Vec<MapPin> {
+    let mut persons: Vec<&Person> = tree.persons.values().collect();
+    persons.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+
+    let mut pins = Vec::new();
+    for person in persons {
+        let places = [(MapPinKind::Birth, person.birth_place), (MapPinKind::Death, person.death_place)];
+        for (kind, place) in places {
+            if kind == MapPinKind::Death && !include_deaths {
+                continue;
+            }
+            if let Some((place, coordinates)) = place_coordinates(tree, place) {
+                pins.push(MapPin { person: person.id, place, kind, coordinates });
+            }
+        }
+    }
+    pins
+}
+
+/// 親と子の出生地がどちらも分かっていて、別の地点にある親子の移動を集める
+pub fn collect_migrations(tree: &FamilyTree) -> Vec<MigrationLine> {
+    tree.edges
+        .iter()
+        .filter_map(|edge| {
+            let from = birth_coordinates(tree, tree.persons.get(&edge.parent)?)?;
+            let to = birth_coordinates(tree, tree.persons.get(&edge.child)?)?;
+            (from != to).then_some(MigrationLine { parent: edge.parent, child: edge.child, from, to })
+        })
+        .collect()
+}
+
+/// 緯度・経度を正距円筒図法の平面座標にする（x は経度 -180〜180 が 0〜360、y は北緯 90 が 0・南緯 90 が 180）
+pub fn project(coordinates: (f64, f64)) -> (f32, f32) {
+    let (latitude, longitude) = coordinates;
+    ((longitude.clamp(-180.0, 180.0) + 180.0) as f32, (90.0 - latitude.clamp(-90.0, 90.0)) as f32)
+}
+
+/// 地図の下地にする国境線（Natural Earth 1:110m の国の外周を間引いたもの。パブリックドメイン）
+///
+/// 1行が1つの閉じた外周で、「経度,緯度」を空白で区切って並べる。
+const WORLD_OUTLINE: &str = include_str!("../../assets/world_outline.txt");
+
+/// 下地の国境線を`project`した平面座標で返す（初回に読み込んで以降は使い回す）
+pub fn world_outline() -> &'static [Vec<(f32, f32)>] {
+    static OUTLINE: OnceLock<Vec<Vec<(f32, f32)>>> = OnceLock::new();
+    OUTLINE.get_or_init(|| {
+        WORLD_OUTLINE
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .filter_map(|point| {
+                        let (longitude, latitude) = point.split_once(',')?;
+                        Some(project((latitude.parse().ok()?, longitude.parse().ok()?)))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|ring| ring.len() >= 3)
+            .collect()
+    })
+}
+
+/// 画面上で `radius` 以内に重なる点をまとめる（各グループは points の添字）
+///
+/// 先に現れた点をグループの中心にする単純な方法で、点の順序が同じなら結果も変わらない。
+pub fn cluster_points(points: &[(f32, f32)], radius: f32) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<((f32, f32), Vec<usize>)> = Vec::new();
+    for (index, &(x, y)) in points.iter().enumerate() {
+        let nearby = clusters
+            .iter_mut()
+            .find(|((cx, cy), _)| (cx - x).powi(2) + (cy - y).powi(2) <= radius * radius);
+        match nearby {
+            Some((_, members)) => members.push(index),
+            None => clusters.push(((x, y), vec![index])),
+        }
+    }
+    clusters.into_iter().map(|(_, members)| members).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    fn located_place(tree: &mut FamilyTree, name: &str, coordinates: Option<(f64, f64)>) -> PlaceId {
+        let id = tree.add_place(name.to_string());
+        let place = tree.place_mut(id).unwrap();
+        place.latitude = coordinates.map(|(latitude, _)| latitude);
+        place.longitude = coordinates.map(|(_, longitude)| longitude);
+        id
+    }
+
+    #[test]
+    fn collects_pins_and_migrations_from_located_places() {
+        let mut tree = FamilyTree::default();
+        let tokyo = located_place(&mut tree, "Tokyo", Some((35.68, 139.69)));
+        let osaka = located_place(&mut tree, "Osaka", Some((34.69, 135.50)));
+        let unknown = located_place(&mut tree, "Somewhere", None);
+        let parent = tree.add_person("A Parent".to_string(), Gender::Male, None, String::new(), true, None, (0.0, 0.0));
+        let child = tree.add_person("B Child".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let stranger = tree.add_person("C Stranger".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_parent_child(parent, child, "biological".to_string());
+        tree.persons.get_mut(&parent).unwrap().birth_place = Some(tokyo);
+        tree.persons.get_mut(&parent).unwrap().death_place = Some(osaka);
+        tree.persons.get_mut(&child).unwrap().birth_place = Some(osaka);
+        tree.persons.get_mut(&stranger).unwrap().birth_place = Some(unknown);
+
+        let births = collect_pins(&tree, false);
+        assert_eq!(births.iter().map(|pin| (pin.person, pin.place)).collect::<Vec<_>>(), [(parent, tokyo), (child, osaka)]);
+        let all = collect_pins(&tree, true);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[1].kind, MapPinKind::Death);
+
+        let migrations = collect_migrations(&tree);
+        assert_eq!(migrations.len(), 1);
+        assert_eq!((migrations[0].parent, migrations[0].child), (parent, child));
+        assert_eq!(migrations[0].to, (34.69, 135.50));
+    }
+
+    #[test]
+    fn projects_and_clusters_nearby_points() {
+        assert_eq!(project((90.0, -180.0)), (0.0, 0.0));
+        assert_eq!(project((-90.0, 180.0)), (360.0, 180.0));
+        assert_eq!(project((0.0, 0.0)), (180.0, 90.0));
+
+        let clusters = cluster_points(&[(0.0, 0.0), (100.0, 0.0), (3.0, 4.0), (106.0, 0.0), (200.0, 0.0)], 5.0);
+        assert_eq!(clusters, [vec![0, 2], vec![1], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn world_outline_is_bundled_and_in_range() {
+        let outline = world_outline();
+        assert!(outline.len() > 100);
+        assert!(outline.iter().flatten().all(|&(x, y)| (0.0..=360.0).contains(&x) && (0.0..=180.0).contains(&y)));
+        // 東京のあたりに陸地の外周がある
+        let (tokyo_x, tokyo_y) = project((35.68, 139.69));
+        assert!(outline.iter().flatten().any(|&(x, y)| (x - tokyo_x).abs() < 2.0 && (y - tokyo_y).abs() < 2.0));
+    }
+}
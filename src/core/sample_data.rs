@@ -0,0 +1,246 @@
+use crate::core::i18n::{Language, Texts};
+use crate::core::tree::{
+    EventRelationType, FamilyTree, Gender, PersonDisplayMode, PersonId, DEFAULT_PHOTO_PATH,
+};
+
+/// 生成できる世代数の上限（人数が指数的に増えるため）
+pub const MAX_SAMPLE_GENERATIONS: usize = 6;
+/// 夫婦ごとの子の数の上限
+pub const MAX_SAMPLE_CHILDREN: usize = 4;
+
+const GENERATION_SPACING: f32 = 160.0;
+const PERSON_SPACING: f32 = 150.0;
+const FIRST_BIRTH_YEAR: i32 = 1900;
+const YEARS_PER_GENERATION: i32 = 28;
+
+/// サンプルの人名の言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SampleNameLocale {
+    #[default]
+    Japanese,
+    English,
+}
+
+impl SampleNameLocale {
+    fn male_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Japanese => &["太郎", "次郎", "健一", "誠", "浩", "隆", "大輔", "翔太"],
+            Self::English => &["John", "William", "James", "George", "Thomas", "Henry", "Edward", "Arthur"],
+        }
+    }
+
+    fn female_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Japanese => &["花子", "洋子", "恵子", "由美", "明美", "裕子", "美咲", "陽菜"],
+            Self::English => &["Mary", "Elizabeth", "Margaret", "Alice", "Emma", "Grace", "Rose", "Clara"],
+        }
+    }
+
+    fn surnames(self) -> &'static [&'static str] {
+        match self {
+            Self::Japanese => &["山田", "佐藤", "鈴木", "高橋", "田中", "伊藤", "渡辺", "中村"],
+            Self::English => &["Smith", "Brown", "Taylor", "Wilson", "Evans", "Walker", "Wright", "Green"],
+        }
+    }
+
+    /// イベント名などに使う言語（人名と同じ言語にそろえる）
+    fn language(self) -> Language {
+        match self {
+            Self::Japanese => Language::Japanese,
+            Self::English => Language::English,
+        }
+    }
+
+    fn full_name(self, surname: &str, given: &str) -> String {
+        match self {
+            Self::Japanese => format!("{surname} {given}"),
+            Self::English => format!("{given} {surname}"),
+        }
+    }
+}
+
+/// サンプルデータの生成条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleOptions {
+    pub generations: usize,
+    pub children_per_couple: usize,
+    pub locale: SampleNameLocale,
+    pub include_photos: bool,
+    pub include_events: bool,
+    /// 同じ値なら同じ家系図を生成する
+    pub seed: u64,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self {
+            generations: 3,
+            children_per_couple: 2,
+            locale: SampleNameLocale::default(),
+            include_photos: false,
+            include_events: false,
+            seed: 1,
+        }
+    }
+}
+
+/// 名前の選択に使う簡易な疑似乱数（線形合同法）
+struct SampleRng(u64);
+
+impl SampleRng {
+    fn next_index(&mut self, len: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % len as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.next_index(items.len())]
+    }
+}
+
+/// デモや描画の負荷確認用に、条件に沿った家系図を生成する
+pub fn generate_sample_tree(options: &SampleOptions) -> FamilyTree {
+    let generations = options.generations.clamp(1, MAX_SAMPLE_GENERATIONS);
+    let children_per_couple = options.children_per_couple.clamp(1, MAX_SAMPLE_CHILDREN);
+    let locale = options.locale;
+    let mut rng = SampleRng(options.seed);
+    let mut tree = FamilyTree::default();
+
+    let add = |tree: &mut FamilyTree, rng: &mut SampleRng, gender: Gender, surname: &str, generation: usize, index: usize| {
+        let given = match gender {
+            Gender::Female => rng.pick(locale.female_names()),
+            _ => rng.pick(locale.male_names()),
+        };
+        let birth_year = FIRST_BIRTH_YEAR + generation as i32 * YEARS_PER_GENERATION + rng.next_index(5) as i32;
+        let position = (index as f32 * PERSON_SPACING, generation as f32 * GENERATION_SPACING);
+        let id = tree.add_person(
+            locale.full_name(surname, given),
+            gender,
+            Some(birth_year.to_string()),
+            String::new(),
+            false,
+            None,
+            position,
+        );
+        if options.include_photos
+            && let Some(person) = tree.persons.get_mut(&id)
+        {
+            person.photo_path = Some(DEFAULT_PHOTO_PATH.to_string());
+            person.display_mode = PersonDisplayMode::NameAndPhoto;
+        }
+        id
+    };
+
+    // 夫婦（夫・妻・家の姓）の一覧を世代ごとに作る
+    let root_surname = rng.pick(locale.surnames());
+    let husband = add(&mut tree, &mut rng, Gender::Male, root_surname, 0, 0);
+    let wife = add(&mut tree, &mut rng, Gender::Female, root_surname, 0, 1);
+    let mut couples: Vec<(PersonId, PersonId, &str)> = vec![(husband, wife, root_surname)];
+
+    for generation in 0..generations {
+        for (husband, wife, _) in &couples {
+            tree.add_spouse_unsynced(*husband, *wife, String::new());
+            if options.include_events {
+                // 夫婦の間の少し下に結婚イベントを置く
+                let (x, y) = tree.persons.get(husband).map(|p| p.position).unwrap_or_default();
+                let event_position = (x + PERSON_SPACING * 0.5, y + 70.0);
+                let year = FIRST_BIRTH_YEAR + generation as i32 * YEARS_PER_GENERATION + 25;
+                let event = tree.add_event(
+                    Texts::get("sample_wedding_event", locale.language()),
+                    Some(year.to_string()),
+                    String::new(),
+                    event_position,
+                    (255, 255, 200),
+                );
+                tree.add_event_relation(event, *husband, EventRelationType::Line, String::new());
+                tree.add_event_relation(event, *wife, EventRelationType::Line, String::new());
+            }
+        }
+
+        if generation + 1 == generations {
+            break;
+        }
+
+        // 子と、その配偶者を次の世代の夫婦にする
+        let mut next_couples = Vec::new();
+        let mut index = 0;
+        for (husband, wife, surname) in &couples {
+            for _ in 0..children_per_couple {
+                let child_gender = if rng.next_index(2) == 0 { Gender::Male } else { Gender::Female };
+                let child = add(&mut tree, &mut rng, child_gender.clone(), surname, generation + 1, index);
+                tree.add_parent_child_unsynced(*husband, child, "biological".to_string());
+                tree.add_parent_child_unsynced(*wife, child, "biological".to_string());
+
+                let in_law_surname = rng.pick(locale.surnames());
+                let (spouse_gender, family_surname) = match child_gender {
+                    Gender::Male => (Gender::Female, *surname),
+                    _ => (Gender::Male, in_law_surname),
+                };
+                let spouse = add(&mut tree, &mut rng, spouse_gender, in_law_surname, generation + 1, index + 1);
+                next_couples.push(match child_gender {
+                    Gender::Male => (child, spouse, family_surname),
+                    _ => (spouse, child, family_surname),
+                });
+                index += 2;
+            }
+        }
+        couples = next_couples;
+    }
+
+    // 関係はまとめて追加したので、家族の単位は最後に1回だけ組み直す
+    tree.sync_unions();
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_sample_tree_counts() {
+        let options = SampleOptions {
+            generations: 3,
+            children_per_couple: 2,
+            ..Default::default()
+        };
+        let tree = generate_sample_tree(&options);
+
+        // 1組 → 子2人(+配偶者) → 孫4人(+配偶者)
+        assert_eq!(tree.persons.len(), 2 + 4 + 8);
+        assert_eq!(tree.spouses.len(), 1 + 2 + 4);
+        assert_eq!(tree.edges.len(), (2 + 4) * 2);
+        assert!(tree.events.is_empty());
+    }
+
+    #[test]
+    fn test_generate_sample_tree_options() {
+        let options = SampleOptions {
+            generations: 2,
+            children_per_couple: 1,
+            locale: SampleNameLocale::English,
+            include_photos: true,
+            include_events: true,
+            seed: 42,
+        };
+        let tree = generate_sample_tree(&options);
+
+        assert_eq!(tree.events.len(), 2);
+        assert!(tree.persons.values().all(|p| p.display_mode == PersonDisplayMode::NameAndPhoto));
+        // 同じシードなら同じ名前になる
+        let mut names: Vec<_> = tree.persons.values().map(|p| p.name.clone()).collect();
+        let mut again: Vec<_> = generate_sample_tree(&options).persons.values().map(|p| p.name.clone()).collect();
+        names.sort();
+        again.sort();
+        assert_eq!(names, again);
+    }
+
+    #[test]
+    fn test_generate_sample_tree_clamps_options() {
+        let options = SampleOptions {
+            generations: 0,
+            children_per_couple: 0,
+            ..Default::default()
+        };
+        assert_eq!(generate_sample_tree(&options).persons.len(), 2);
+    }
+}
@@ -0,0 +1,170 @@
+use uuid::Uuid;
+
+use crate::core::tree::{Event, FamilyTree, Person, PersonId};
+
+/// スライドショーの対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlideshowSource {
+    Person(PersonId),
+    Family(Uuid),
+}
+
+/// スライドショーの1枚（写真と説明）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slide {
+    pub person_id: PersonId,
+    /// 表示する写真（写真のない人物のイベントでは None）
+    pub photo_path: Option<String>,
+    /// 人物名またはイベント名
+    pub caption: String,
+    /// 生没年月日（例: "1950-04-01 – 2020-01-02"）またはイベントの日付
+    pub dates: Option<String>,
+    /// イベントの説明（人物の写真では空）
+    pub description: String,
+}
+
+impl Slide {
+    fn from_person(person: &Person) -> Option<Self> {
+        if !person.has_own_photo() {
+            return None;
+        }
+        Some(Self {
+            person_id: person.id,
+            photo_path: Some(person.photo_path.clone()?),
+            caption: person.name.clone(),
+            dates: date_range_label(person),
+            description: String::new(),
+        })
+    }
+
+    /// 人物が関わるイベントの1枚（写真はその人物のもの）
+    fn from_event(person: &Person, event: &Event) -> Self {
+        Self {
+            person_id: person.id,
+            photo_path: person.photo_path.clone().filter(|_| person.has_own_photo()),
+            caption: event.name.clone(),
+            dates: event.date.as_deref().map(str::trim).filter(|date| !date.is_empty()).map(str::to_string),
+            description: event.description.trim().to_string(),
+        }
+    }
+}
+
+/// 対象の写真を表示順に集める
+///
+/// 人物の場合は写真に続けて関わるイベントを日付順（日付不明は最後）に並べる。
+/// 家族の場合はメンバーを生年順（生年不明は最後）に並べ、写真のない人物は飛ばす。
+pub fn collect_slides(tree: &FamilyTree, source: SlideshowSource) -> Vec<Slide> {
+    match source {
+        SlideshowSource::Person(person_id) => {
+            let Some(person) = tree.persons.get(&person_id) else {
+                return Vec::new();
+            };
+            let mut events: Vec<&Event> = tree
+                .event_relations
+                .iter()
+                .filter(|relation| relation.person == person_id)
+                .filter_map(|relation| tree.events.get(&relation.event))
+                .collect();
+            events.sort_by(|a, b| (a.date.is_none(), &a.date, &a.name).cmp(&(b.date.is_none(), &b.date, &b.name)));
+            events.dedup_by_key(|event| event.id);
+            Slide::from_person(person)
+                .into_iter()
+                .chain(events.into_iter().map(|event| Slide::from_event(person, event)))
+                .collect()
+        }
+        SlideshowSource::Family(family_id) => {
+            let Some(family) = tree.families.iter().find(|family| family.id == family_id) else {
+                return Vec::new();
+            };
+            let mut members: Vec<&Person> = family
+                .members
+                .iter()
+                .filter_map(|id| tree.persons.get(id))
+                .collect();
+            members.sort_by_key(|person| (person.birth.is_none(), person.birth.clone()));
+            members.into_iter().filter_map(Slide::from_person).collect()
+        }
+    }
+}
+
+fn date_range_label(person: &Person) -> Option<String> {
+    let birth = person.birth.as_deref().map(str::trim).filter(|date| !date.is_empty());
+    let death = person.death.as_deref().map(str::trim).filter(|date| !date.is_empty());
+    match (birth, death) {
+        (Some(birth), Some(death)) => Some(format!("{birth} – {death}")),
+        (Some(birth), None) => Some(birth.to_string()),
+        (None, Some(death)) => Some(format!("? – {death}")),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::{EventRelationType, Gender};
+
+    fn add_with_photo(tree: &mut FamilyTree, name: &str, birth: Option<&str>, photo: Option<&str>) -> PersonId {
+        let id = tree.add_person(
+            name.to_string(),
+            Gender::Unknown,
+            birth.map(str::to_string),
+            String::new(),
+            false,
+            None,
+            (0.0, 0.0),
+        );
+        tree.persons.get_mut(&id).unwrap().photo_path = photo.map(str::to_string);
+        id
+    }
+
+    #[test]
+    fn family_slides_are_ordered_by_birth_and_skip_members_without_photos() {
+        let mut tree = FamilyTree::default();
+        let child = add_with_photo(&mut tree, "Child", Some("1980-01-01"), Some("child.png"));
+        let parent = add_with_photo(&mut tree, "Parent", Some("1950-05-05"), Some("parent.jpg"));
+        let unknown = add_with_photo(&mut tree, "Unknown", None, Some("unknown.jpg"));
+        let no_photo = add_with_photo(&mut tree, "NoPhoto", Some("1955-01-01"), None);
+        let family_id = tree.add_family("F".to_string(), None);
+        tree.families[0].members = vec![unknown, child, no_photo, parent];
+
+        let slides = collect_slides(&tree, SlideshowSource::Family(family_id));
+
+        let captions: Vec<&str> = slides.iter().map(|slide| slide.caption.as_str()).collect();
+        assert_eq!(captions, ["Parent", "Child", "Unknown"]);
+        assert_eq!(slides[0].dates.as_deref(), Some("1950-05-05"));
+    }
+
+    #[test]
+    fn person_slide_includes_life_dates() {
+        let mut tree = FamilyTree::default();
+        let id = add_with_photo(&mut tree, "A", Some("1900-01-01"), Some("a.png"));
+        tree.persons.get_mut(&id).unwrap().death = Some("1980-12-31".to_string());
+
+        let slides = collect_slides(&tree, SlideshowSource::Person(id));
+        assert_eq!(slides.len(), 1);
+        assert_eq!(slides[0].dates.as_deref(), Some("1900-01-01 – 1980-12-31"));
+    }
+
+    #[test]
+    fn person_slides_continue_with_linked_events_in_date_order() {
+        let mut tree = FamilyTree::default();
+        let id = add_with_photo(&mut tree, "A", Some("1900-01-01"), Some("a.png"));
+        let other = add_with_photo(&mut tree, "B", None, None);
+        let undated = tree.add_event("Undated".to_string(), None, String::new(), (0.0, 0.0), (255, 255, 200));
+        let wedding = tree.add_event("Wedding".to_string(), Some("1925-06-01".to_string()), "Church".to_string(), (0.0, 0.0), (255, 255, 200));
+        let graduation = tree.add_event("Graduation".to_string(), Some("1918-03-20".to_string()), String::new(), (0.0, 0.0), (255, 255, 200));
+        let unrelated = tree.add_event("Unrelated".to_string(), Some("1910-01-01".to_string()), String::new(), (0.0, 0.0), (255, 255, 200));
+        for event in [undated, wedding, graduation] {
+            tree.add_event_relation(event, id, EventRelationType::Line, String::new());
+        }
+        tree.add_event_relation(unrelated, other, EventRelationType::Line, String::new());
+
+        let slides = collect_slides(&tree, SlideshowSource::Person(id));
+
+        let captions: Vec<&str> = slides.iter().map(|slide| slide.caption.as_str()).collect();
+        assert_eq!(captions, ["A", "Graduation", "Wedding", "Undated"]);
+        assert!(slides.iter().all(|slide| slide.photo_path.as_deref() == Some("a.png")));
+        assert_eq!(slides[2].dates.as_deref(), Some("1925-06-01"));
+        assert_eq!(slides[2].description, "Church");
+    }
+}
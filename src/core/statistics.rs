@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::core::tree::{FamilyTree, PersonId};
+
+/// 寿命・出生の集計に使う年の区切り（年）
+pub const STATISTICS_BUCKET_YEARS: i32 = 10;
+
+/// 棒グラフの1本分の集計（キーと、その値に該当する人物）
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticsBucket {
+    /// 区間の始まり（年齢・年）または子の人数
+    pub key: i32,
+    pub persons: Vec<PersonId>,
+}
+
+impl StatisticsBucket {
+    pub fn count(&self) -> usize {
+        self.persons.len()
+    }
+}
+
+/// キーごとに人物をまとめ、キーの昇順に並べる（人物は名前順）
+fn group_by_key(tree: &FamilyTree, entries: impl Iterator<Item = (i32, PersonId)>) -> Vec<StatisticsBucket> {
+    let mut groups: BTreeMap<i32, Vec<PersonId>> = BTreeMap::new();
+    for (key, person) in entries {
+        groups.entry(key).or_default().push(person);
+    }
+    groups
+        .into_iter()
+        .map(|(key, mut persons)| {
+            persons.sort_by_key(|id| tree.persons.get(id).map(|person| person.name.clone()));
+            StatisticsBucket { key, persons }
+        })
+        .collect()
+}
+
+/// 寿命の分布（生年・没年がわかる故人を10年刻みで集計）
+pub fn lifespan_histogram(tree: &FamilyTree) -> Vec<StatisticsBucket> {
+    let lifespans = tree.persons.values().filter(|person| person.deceased).filter_map(|person| {
+        let lifespan = person.death_year()? - person.birth_year()?;
+        (lifespan >= 0).then_some((lifespan - lifespan % STATISTICS_BUCKET_YEARS, person.id))
+    });
+    group_by_key(tree, lifespans)
+}
+
+/// 年代ごとの出生数
+pub fn births_per_decade(tree: &FamilyTree) -> Vec<StatisticsBucket> {
+    let births = tree.persons.values().filter_map(|person| {
+        let year = person.birth_year()?;
+        Some((year.div_euclid(STATISTICS_BUCKET_YEARS) * STATISTICS_BUCKET_YEARS, person.id))
+    });
+    group_by_key(tree, births)
+}
+
+/// 夫婦ごとの子の人数の分布（人物には夫婦の両方を含める）
+pub fn children_per_couple(tree: &FamilyTree) -> Vec<StatisticsBucket> {
+    let couples = tree.spouses.iter().flat_map(|spouse| {
+        let children_of_other = tree.children_of(spouse.person2);
+        let count = tree
+            .children_of(spouse.person1)
+            .iter()
+            .filter(|child| children_of_other.contains(child))
+            .count() as i32;
+        [(count, spouse.person1), (count, spouse.person2)]
+    });
+    group_by_key(tree, couples)
+}
+
+/// 2つの時点の家系図の差から数えた編集の件数（作業の進み具合の報告用）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EditCounts {
+    pub persons_added: usize,
+    pub persons_removed: usize,
+    /// 両方にいて、位置以外の項目が変わった人物
+    pub persons_edited: usize,
+    /// 親子・配偶者・人物間・イベントとの関係
+    pub relations_added: usize,
+    pub relations_removed: usize,
+    pub events_added: usize,
+    pub events_removed: usize,
+}
+
+impl EditCounts {
+    /// before から after への変更を数える（ノードの移動は編集に数えない）
+    pub fn between(before: &FamilyTree, after: &FamilyTree) -> Self {
+        let content = |tree: &FamilyTree, id: &PersonId| {
+            let mut value = serde_json::to_value(tree.persons.get(id)?).ok()?;
+            value.as_object_mut()?.remove("position");
+            Some(value)
+        };
+        let persons_edited = after
+            .persons
+            .keys()
+            .filter(|id| before.persons.contains_key(id) && content(before, id) != content(after, id))
+            .count();
+
+        let (before_relations, after_relations) = (relation_keys(before), relation_keys(after));
+        Self {
+            persons_added: after.persons.keys().filter(|id| !before.persons.contains_key(id)).count(),
+            persons_removed: before.persons.keys().filter(|id| !after.persons.contains_key(id)).count(),
+            persons_edited,
+            relations_added: after_relations.difference(&before_relations).count(),
+            relations_removed: before_relations.difference(&after_relations).count(),
+            events_added: after.events.keys().filter(|id| !before.events.contains_key(id)).count(),
+            events_removed: before.events.keys().filter(|id| !after.events.contains_key(id)).count(),
+        }
+    }
+
+    /// 変更が1件もないか
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::ops::Add for EditCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            persons_added: self.persons_added + other.persons_added,
+            persons_removed: self.persons_removed + other.persons_removed,
+            persons_edited: self.persons_edited + other.persons_edited,
+            relations_added: self.relations_added + other.relations_added,
+            relations_removed: self.relations_removed + other.relations_removed,
+            events_added: self.events_added + other.events_added,
+            events_removed: self.events_removed + other.events_removed,
+        }
+    }
+}
+
+/// 関係を比較できる形にする（親子関係は種類も含め、配偶者関係は順序を問わない）
+fn relation_keys(tree: &FamilyTree) -> HashSet<String> {
+    let edges = tree.edges.iter().map(|e| format!("pc:{}:{}:{}", e.parent, e.child, e.kind));
+    let spouses = tree.spouses.iter().map(|s| {
+        let (a, b) = if s.person1 < s.person2 { (s.person1, s.person2) } else { (s.person2, s.person1) };
+        format!("sp:{a}:{b}")
+    });
+    let person_relations = tree.person_relations.iter().map(|r| format!("pr:{}:{}:{:?}", r.from, r.to, r.kind));
+    let event_relations = tree.event_relations.iter().map(|r| format!("ev:{}:{}", r.event, r.person));
+    edges.chain(spouses).chain(person_relations).chain(event_relations).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_statistics_buckets() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, Some("1901-04-01".to_string()), "".to_string(), true, Some("1975".to_string()), (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, Some("1905".to_string()), "".to_string(), true, Some("1982".to_string()), (0.0, 0.0));
+        let son = tree.add_person("Son".to_string(), Gender::Male, Some("1930".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let daughter = tree.add_person("Daughter".to_string(), Gender::Female, Some("1933".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let uncle = tree.add_person("Uncle".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let aunt = tree.add_person("Aunt".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, mother, "".to_string());
+        tree.add_spouse(uncle, aunt, "".to_string());
+        for child in [son, daughter] {
+            tree.add_parent_child(father, child, "biological".to_string());
+            tree.add_parent_child(mother, child, "biological".to_string());
+        }
+
+        // 74歳と77歳はどちらも70代
+        assert_eq!(lifespan_histogram(&tree), vec![StatisticsBucket { key: 70, persons: vec![father, mother] }]);
+
+        let births = births_per_decade(&tree);
+        assert_eq!(births.iter().map(|bucket| (bucket.key, bucket.count())).collect::<Vec<_>>(), vec![(1900, 2), (1930, 2)]);
+        assert_eq!(births[1].persons, vec![daughter, son]);
+
+        let children = children_per_couple(&tree);
+        assert_eq!(children.iter().map(|bucket| (bucket.key, bucket.count())).collect::<Vec<_>>(), vec![(0, 2), (2, 2)]);
+        assert_eq!(children[1].persons, vec![father, mother]);
+    }
+
+    #[test]
+    fn test_edit_counts_between() {
+        let mut before = FamilyTree::default();
+        let father = before.add_person("Father".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let mother = before.add_person("Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let moved = before.add_person("Moved".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let removed = before.add_person("Removed".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        before.add_spouse(father, mother, "".to_string());
+        before.add_parent_child(father, removed, "biological".to_string());
+
+        let mut after = before.clone();
+        after.remove_person(removed);
+        after.persons.get_mut(&father).unwrap().birth = Some("1900".to_string());
+        after.persons.get_mut(&moved).unwrap().position = (50.0, 50.0);
+        let child = after.add_person("Child".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+        after.add_parent_child(father, child, "biological".to_string());
+        after.add_parent_child(mother, child, "biological".to_string());
+        // 配偶者関係を逆向きに付け直しても変更にしない
+        after.spouses.clear();
+        after.add_spouse(mother, father, "".to_string());
+
+        let counts = EditCounts::between(&before, &after);
+        assert_eq!(
+            counts,
+            EditCounts {
+                persons_added: 1,
+                persons_removed: 1,
+                persons_edited: 1,
+                relations_added: 2,
+                relations_removed: 1,
+                events_added: 0,
+                events_removed: 0,
+            }
+        );
+        assert_eq!((counts + counts).persons_added, 2);
+        assert!(EditCounts::between(&after, &after).is_empty());
+    }
+}
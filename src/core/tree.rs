@@ -1,15 +1,56 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::core::edit_macro::EditMacro;
+
 pub type PersonId = Uuid;
 pub type EventId = Uuid;
+pub type AnnotationId = Uuid;
+pub type StickyNoteId = Uuid;
+pub type PlaceId = Uuid;
+pub type UnionId = Uuid;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+/// 短縮IDの桁数（UUIDの先頭の16進数文字）
+pub const SHORT_ID_LENGTH: usize = 8;
+
+/// 新しく追加した人物に設定される既定の画像
+pub const DEFAULT_PHOTO_PATH: &str = "photo/DefaultImage.gif";
+
+/// 切り抜き・回転した写真の保存先フォルダ
+pub const MANAGED_PHOTO_DIR: &str = "photo";
+
+/// 同名の人物を区別するための短縮IDを返す
+pub fn short_id(id: &Uuid) -> String {
+    id.simple().to_string()[..SHORT_ID_LENGTH].to_string()
+}
+
+/// 貼り付けられたIDを比較用の形（ハイフンなし・小文字）にする
+fn normalize_id_query(text: &str) -> String {
+    text.trim().chars().filter(|c| *c != '-').collect::<String>().to_lowercase()
+}
+
+/// 検索語が人物のID（短縮IDより長い前方一致）として一致するか
+///
+/// 名前の検索語と区別するため、短縮ID以上の長さの16進数だけをIDとみなす。
+pub fn id_matches_query(id: &Uuid, text: &str) -> bool {
+    let query = normalize_id_query(text);
+    query.len() >= SHORT_ID_LENGTH
+        && query.chars().all(|c| c.is_ascii_hexdigit())
+        && id.simple().to_string().starts_with(&query)
+}
+
+/// 読み込んだファイルにあった、この版が知らない項目
+///
+/// 新しい版や他のツールが追加した項目を読み込み時に残しておき、保存時にそのまま書き戻す。
+pub type ExtraFields = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Gender {
     Male,
     Female,
     Unknown,
+    Other(String), // 男性・女性以外の性別（任意の表記）
 }
 
 impl Default for Gender {
@@ -22,6 +63,8 @@ impl Default for Gender {
 pub enum PersonDisplayMode {
     NameOnly,
     NameAndPhoto,
+    /// 写真だけを表示し、名前はマウスを乗せたときだけ表示する（写真を並べた壁掛け用の図向け）
+    PhotoOnly,
 }
 
 impl Default for PersonDisplayMode {
@@ -30,6 +73,70 @@ impl Default for PersonDisplayMode {
     }
 }
 
+/// 人物に付けられる小さな目印（ノードの隅に表示する）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonBadge {
+    Military,
+    Emigrated,
+    DirectAncestor,
+    Clergy,
+    Nobility,
+    /// 新しい版で追加された目印や手で書いた識別子（表示はしないが保存時にそのまま戻す）
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl PersonBadge {
+    /// 選べる目印の一覧（表示順）
+    pub const ALL: [PersonBadge; 5] = [
+        PersonBadge::Military,
+        PersonBadge::Emigrated,
+        PersonBadge::DirectAncestor,
+        PersonBadge::Clergy,
+        PersonBadge::Nobility,
+    ];
+
+    /// ノードや SVG に描く記号（知らない目印は描かない）
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            PersonBadge::Military => "⚓",
+            PersonBadge::Emigrated => "✈",
+            PersonBadge::DirectAncestor => "★",
+            PersonBadge::Clergy => "✝",
+            PersonBadge::Nobility => "👑",
+            PersonBadge::Other(_) => "",
+        }
+    }
+
+    /// 保存時の識別子（翻訳キーにも使う）
+    pub fn key(&self) -> &str {
+        match self {
+            PersonBadge::Military => "military",
+            PersonBadge::Emigrated => "emigrated",
+            PersonBadge::DirectAncestor => "direct_ancestor",
+            PersonBadge::Clergy => "clergy",
+            PersonBadge::Nobility => "nobility",
+            PersonBadge::Other(key) => key,
+        }
+    }
+
+    /// 識別子から目印を得る（知らない識別子は `Other` として残す）
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|badge| badge.key() == key)
+            .unwrap_or_else(|| PersonBadge::Other(key.to_string()))
+    }
+}
+
+impl PersonDisplayMode {
+    /// 写真を表示するモードか
+    pub fn shows_photo(self) -> bool {
+        matches!(self, PersonDisplayMode::NameAndPhoto | PersonDisplayMode::PhotoOnly)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Person {
     pub id: PersonId,
@@ -50,17 +157,238 @@ pub struct Person {
     pub display_mode: PersonDisplayMode, // 表示モード
     #[serde(default = "default_photo_scale")]
     pub photo_scale: f32, // 写真の倍率（デフォルト: 1.0）
+    #[serde(default)]
+    pub multiple_birth_group: Option<Uuid>, // 双子・三つ子など同じ出産のきょうだいで共有するID
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gedcom_extras: Vec<String>, // GEDCOM読込時に対応していなかったタグの行（書き出し時にそのまま戻す）
+    #[serde(default)]
+    pub pinned: bool, // 自動整列で動かさない（固定した人物の周りに他の人物を並べる）
+    #[serde(default)]
+    pub locked: bool, // キャンバスでドラッグして動かせない（自動整列でも動かさない）
+    #[serde(default)]
+    pub birth_place: Option<PlaceId>, // 出生地（地名辞典の場所）
+    #[serde(default)]
+    pub death_place: Option<PlaceId>, // 死亡地（地名辞典の場所）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub badges: Vec<PersonBadge>, // ノードの隅に表示する目印（軍歴・移住・直系の祖先など）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_link: Option<ExternalPersonLink>, // 別の家系図ファイルにいる同じ人物への参照
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 別の家系図ファイル（または URL）にいる同じ人物への参照
+///
+/// 大きな親族を複数のファイルに分けても、ファイルをまたいで行き来できるようにする。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalPersonLink {
+    /// 家系図ファイルのパスまたは URL
+    pub tree: String,
+    /// リンク先の家系図での人物ID（短縮IDでもよい）
+    #[serde(default)]
+    pub person_id: String,
+}
+
+impl ExternalPersonLink {
+    /// リンク先が URL か（ファイルではなくブラウザで開く）
+    pub fn is_url(&self) -> bool {
+        let tree = self.tree.trim().to_ascii_lowercase();
+        tree.starts_with("http://") || tree.starts_with("https://")
+    }
 }
 
 fn default_photo_scale() -> f32 {
     1.0
 }
 
+/// 死亡と推定する出生からの年数（既定値）
+pub const DEFAULT_PRESUMED_DECEASED_YEARS: u32 = 110;
+
+/// 名前を姓と名に分けたもの（姓は空白で区切られた最初か最後の語）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameParts<'a> {
+    pub given: String,
+    /// 1語だけの名前なら空
+    pub surname: &'a str,
+    /// 漢字・かな・ハングルの名前は姓を先に書く
+    pub surname_first: bool,
+}
+
+impl<'a> NameParts<'a> {
+    pub fn split(name: &'a str) -> Self {
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let surname_first = name.chars().any(|c| {
+            matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{ac00}'..='\u{d7af}')
+        });
+        let (given, surname) = match words.as_slice() {
+            [] | [_] => (words.join(" "), ""),
+            [surname, given @ ..] if surname_first => (given.join(" "), *surname),
+            [given @ .., surname] => (given.join(" "), *surname),
+        };
+        Self { given, surname, surname_first }
+    }
+}
+
+impl Person {
+    /// 生年月日から出生年を取得
+    pub fn birth_year(&self) -> Option<i32> {
+        self.birth
+            .as_deref()?
+            .trim()
+            .split('-')
+            .next()?
+            .parse::<i32>()
+            .ok()
+    }
+
+    /// 自動整列などで位置を変えない人物か（固定またはロックされている）
+    pub fn keeps_position(&self) -> bool {
+        self.pinned || self.locked
+    }
+
+    /// 既定の画像以外の写真が設定されているか
+    pub fn has_own_photo(&self) -> bool {
+        self.photo_path
+            .as_deref()
+            .is_some_and(|path| !path.trim().is_empty() && path != DEFAULT_PHOTO_PATH)
+    }
+
+    /// 没年月日から死亡年を取得
+    pub fn death_year(&self) -> Option<i32> {
+        self.death
+            .as_deref()?
+            .trim()
+            .split('-')
+            .next()?
+            .parse::<i32>()
+            .ok()
+    }
+
+    /// 生没年の表示（例: "1900–1980"、存命なら "1990–"）。どちらも不明なら None
+    pub fn life_span_label(&self) -> Option<String> {
+        match (self.birth_year(), self.death_year()) {
+            (Some(birth), Some(death)) => Some(format!("{birth}–{death}")),
+            (Some(birth), None) if self.deceased => Some(format!("{birth}–?")),
+            (Some(birth), None) => Some(format!("{birth}–")),
+            (None, Some(death)) => Some(format!("?–{death}")),
+            (None, None) => None,
+        }
+    }
+
+    /// 死亡の記録はないが、出生から指定年数以上経過しているため死亡と推定されるか
+    pub fn is_presumed_deceased(&self, threshold_years: u32, current_year: i32) -> bool {
+        if self.deceased {
+            return false;
+        }
+        self.birth_year()
+            .is_some_and(|year| current_year - year > threshold_years as i32)
+    }
+}
+
+/// 死亡の記録がない人物を、出生からの年数で死亡とみなすかの設定（絞り込み・書き出しで共通に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeceasedPresumption {
+    /// 死亡とみなす年数（None なら推定しない）
+    pub threshold_years: Option<u32>,
+    pub current_year: i32,
+}
+
+impl DeceasedPresumption {
+    /// 死亡の記録があるか、死亡と推定されるか
+    pub fn is_deceased(&self, person: &Person) -> bool {
+        person.deceased
+            || self
+                .threshold_years
+                .is_some_and(|years| person.is_presumed_deceased(years, self.current_year))
+    }
+}
+
+/// 養子縁組を表す親子関係の種類
+pub const ADOPTIVE_KIND: &str = "adoptive";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParentChild {
     pub parent: PersonId,
     pub child: PersonId,
     pub kind: String, // "biological" / "adoptive" 等、今は自由文字列
+    #[serde(default)]
+    pub adoption_date: Option<String>, // 養子縁組の年月日 "YYYY-MM-DD" など
+    #[serde(default)]
+    pub adoption_type: String, // 養子縁組の種類（普通養子・特別養子など）
+    #[serde(default)]
+    pub adoption_note: String, // 養子縁組に関するメモ
+    #[serde(default)]
+    pub union: Option<UnionId>, // この親子関係が属する家族の単位（`FamilyTree::sync_unions` で設定する）
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl ParentChild {
+    /// 養子縁組による親子関係かどうか
+    pub fn is_adoptive(&self) -> bool {
+        self.kind == ADOPTIVE_KIND
+    }
+}
+
+/// 子と親をつなぐ接続点（同じ種類の親子関係にある1人または2人の親）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentJunction {
+    /// 親子関係の種類（"biological" / "adoptive" など）
+    pub kind: String,
+    /// 2人の場合は両親の中点から子へ線を引く
+    pub parents: Vec<PersonId>,
+    /// 2人の親が配偶者関係にあるか
+    pub are_spouses: bool,
+}
+
+/// 人物を削除する前に示す、削除の影響の一覧
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonDeletionPlan {
+    /// 削除する人物
+    pub person: PersonId,
+    /// 一緒に削除する、削除後にどの人物ともつながらなくなる仮の親族
+    pub orphans: Vec<PersonId>,
+    /// 一緒に削除を選べる仮の親族（orphans が空でも候補は示す）
+    pub orphan_candidates: Vec<PersonId>,
+    /// 削除される親子関係の数
+    pub parent_child_links: usize,
+    /// 削除される配偶者関係の数
+    pub spouse_links: usize,
+    /// 削除される人物間の関係（後見人など）の数
+    pub person_relation_links: usize,
+    /// 削除されるイベントとのつながりの数
+    pub event_links: usize,
+    /// メンバーが減る家族グループ
+    pub families: Vec<Uuid>,
+}
+
+impl PersonDeletionPlan {
+    /// 削除する人物すべて（本人と、一緒に削除する仮の親族）
+    pub fn removed_persons(&self) -> impl Iterator<Item = PersonId> + '_ {
+        std::iter::once(self.person).chain(self.orphans.iter().copied())
+    }
+}
+
+/// 親の組（夫婦または片親）とその子をまとめた家族の単位（GEDCOM の FAM レコードに相当）
+///
+/// 親子の線はこの単位の接続点から引くので、再婚した人物の子もそれぞれの相手との組に分かれる。
+/// 家系図に保存し、親子関係からIDで参照する（子はその単位を参照する親子関係から分かる）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Union {
+    pub id: UnionId,
+    /// 親子関係の種類（"biological" / "adoptive" など）
+    pub kind: String,
+    /// 1人または2人の親
+    pub partners: Vec<PersonId>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 家族の単位を引くためのキー（種類と、順序をそろえた親の組）
+fn union_key(kind: &str, partners: &[PersonId]) -> (String, Vec<PersonId>) {
+    let mut partners = partners.to_vec();
+    partners.sort();
+    (kind.to_string(), partners)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +396,126 @@ pub struct Spouse {
     pub person1: PersonId,
     pub person2: PersonId,
     pub memo: String, // 結婚年月日などのメモ
+    #[serde(default)]
+    pub marriage_date: Option<String>, // 結婚年月日 "YYYY-MM-DD" など
+    #[serde(default)]
+    pub divorced: bool, // 離婚フラグ
+    #[serde(default)]
+    pub divorce_date: Option<String>, // 離婚年月日 "YYYY-MM-DD" など
+    /// キャンバス上のメモの表示方法
+    #[serde(default)]
+    pub label: SpouseLabelStyle,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 配偶者の線に添えるメモの位置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpouseLabelPosition {
+    #[default]
+    Above,
+    Below,
+}
+
+/// 配偶者の線に添えるメモ（結婚年など）の表示方法
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpouseLabelStyle {
+    pub visible: bool,
+    pub position: SpouseLabelPosition,
+    pub font_size: f32,
+    pub color: (u8, u8, u8),
+}
+
+/// 配偶者の線のメモの文字サイズの既定値
+pub const DEFAULT_SPOUSE_LABEL_FONT_SIZE: f32 = 11.0;
+
+impl Default for SpouseLabelStyle {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            position: SpouseLabelPosition::Above,
+            font_size: DEFAULT_SPOUSE_LABEL_FONT_SIZE,
+            color: (90, 90, 90),
+        }
+    }
+}
+
+/// 親子関係とは別の人物間の関係の種類
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PersonRelationKind {
+    #[default]
+    Guardian,  // 後見人
+    Godparent, // 名付け親・代父母
+    Mentor,    // 師・恩人
+}
+
+impl PersonRelationKind {
+    pub const ALL: [PersonRelationKind; 3] = [
+        PersonRelationKind::Guardian,
+        PersonRelationKind::Godparent,
+        PersonRelationKind::Mentor,
+    ];
+
+    /// 相手の人物が担う役割の翻訳キー
+    ///
+    /// `holder` が true の場合は後見人・名付け親・師の側、false の場合はその相手側
+    pub fn role_key(self, holder: bool) -> &'static str {
+        match (self, holder) {
+            (PersonRelationKind::Guardian, true) => "relation_guardian",
+            (PersonRelationKind::Guardian, false) => "relation_ward",
+            (PersonRelationKind::Godparent, true) => "relation_godparent",
+            (PersonRelationKind::Godparent, false) => "relation_godchild",
+            (PersonRelationKind::Mentor, true) => "relation_mentor",
+            (PersonRelationKind::Mentor, false) => "relation_mentee",
+        }
+    }
+}
+
+/// 後見人・名付け親などの人物間の関係（from が後見人等、to が被後見人等）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonRelation {
+    pub from: PersonId,
+    pub to: PersonId,
+    pub kind: PersonRelationKind,
+    #[serde(default)]
+    pub memo: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+fn default_background_opacity() -> f32 {
+    0.5
+}
+
+fn default_background_scale() -> f32 {
+    1.0
+}
+
+/// キャンバスの背景に敷く画像（古い家族写真・地図・家紋など）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanvasBackground {
+    pub path: String,
+    #[serde(default = "default_background_opacity")]
+    pub opacity: f32, // 0.0〜1.0
+    #[serde(default)]
+    pub position: (f32, f32), // 画像の左上の座標
+    #[serde(default = "default_background_scale")]
+    pub scale: f32, // 画像1ピクセルあたりの大きさ
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl CanvasBackground {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            opacity: default_background_opacity(),
+            position: (0.0, 0.0),
+            scale: default_background_scale(),
+            extra: ExtraFields::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +524,38 @@ pub struct Family {
     pub name: String,
     pub members: Vec<PersonId>,
     pub color: Option<(u8, u8, u8)>, // RGB色
+    #[serde(default, skip_serializing_if = "FamilyDefaults::is_empty")]
+    pub defaults: FamilyDefaults,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 家族の画面から人物を追加するときの既定値（同じ家系の入力をそろえるためのもの）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FamilyDefaults {
+    /// 親子関係の種類（空なら全体の設定に従う）
+    pub relation_kind: String,
+    /// 新しい子の名前の前に付ける姓
+    pub surname: String,
+    /// キャンバスに表示する家族名の色（None なら枠の色に合わせる）
+    pub label_color: Option<(u8, u8, u8)>,
+}
+
+impl FamilyDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 姓を付けた名前（姓が空か、名前が既に姓で始まっていればそのまま）
+    pub fn with_surname(&self, name: &str) -> String {
+        let surname = self.surname.trim();
+        if surname.is_empty() || name.starts_with(surname) {
+            name.to_string()
+        } else {
+            format!("{surname} {name}")
+        }
+    }
 }
 
 fn default_event_color() -> (u8, u8, u8) {
@@ -92,6 +572,126 @@ pub struct Event {
     pub position: (f32, f32), // 手動配置の座標(左上)
     #[serde(default = "default_event_color")]
     pub color: (u8, u8, u8), // RGB色
+    #[serde(default)]
+    pub place: Option<PlaceId>, // 場所（地名辞典の場所）
+    /// テンプレートから作ったイベントの出所（"テンプレートのキー/イベントのキー"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 地名辞典の場所（出生地・死亡地・イベントの場所から ID で参照する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub id: PlaceId,
+    pub name: String,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    pub note: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Place {
+    /// 表記ゆれを比べるための名前（大文字小文字・空白・記号を無視）
+    fn normalized_name(&self) -> String {
+        self.name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+}
+
+/// 場所を参照している項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceReference {
+    BirthPlace(PersonId),
+    DeathPlace(PersonId),
+    Event(EventId),
+}
+
+pub const DEFAULT_ANNOTATION_FONT_SIZE: f32 = 16.0;
+
+fn default_annotation_font_size() -> f32 {
+    DEFAULT_ANNOTATION_FONT_SIZE
+}
+
+fn default_annotation_color() -> (u8, u8, u8) {
+    (60, 60, 60) // デフォルトの濃い灰色
+}
+
+/// 人物やイベントから独立したキャンバス上の文字ラベル（「父方」などの見出しや出典メモ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextAnnotation {
+    pub id: AnnotationId,
+    pub text: String,
+    pub position: (f32, f32), // 文字列の左上の座標
+    #[serde(default = "default_annotation_font_size")]
+    pub font_size: f32,
+    #[serde(default = "default_annotation_color")]
+    pub color: (u8, u8, u8), // RGB色
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 人物ノードに貼り付ける付箋（「誕生年を叔母に確認」などの確認事項。メモとは別に管理）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StickyNote {
+    pub id: StickyNoteId,
+    pub person: PersonId,
+    pub text: String,
+    #[serde(default)]
+    pub resolved: bool, // 対応済みかどうか
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 名前を付けて保存したキャンバスの表示位置（パンとズーム）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub id: Uuid,
+    pub name: String,
+    pub zoom: f32,
+    pub pan: (f32, f32),
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// ガイド線の向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuideAxis {
+    /// 縦の線（x 座標をそろえる）
+    Vertical,
+    /// 横の線（y 座標をそろえる）
+    Horizontal,
+}
+
+/// 印刷用に位置をそろえるためのキャンバスのガイド線（position はキャンバス原点からの距離）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanvasGuide {
+    pub id: Uuid,
+    pub axis: GuideAxis,
+    pub position: f32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 名前を付けて保存した配置（「印刷用」「コンパクト」など、人物とイベントの座標の組）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutSnapshot {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub person_positions: HashMap<PersonId, (f32, f32)>,
+    #[serde(default)]
+    pub event_positions: HashMap<EventId, (f32, f32)>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -115,10 +715,32 @@ pub struct EventRelation {
     #[serde(default)]
     pub relation_type: EventRelationType,
     pub memo: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// キャンバスでの人物ノードの描き方（家系図ごとの設定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NodeStyle {
+    /// 角丸の四角形
+    #[default]
+    Box,
+    /// 写真を円形に切り抜き、性別の色の輪で囲んで名前を下に表示する（写真付き表示の人物のみ）
+    CircularAvatar,
+}
+
+/// 保存ファイルの形式バージョン（形式を変えたら上げて、JSON の移行処理を追加する）
+///
+/// - 1: バージョンの導入
+/// - 2: レイアウトの保存・カメラのブックマーク・ガイド線・地名辞典・マクロ
+/// - 3: 家族の単位と、親子関係からの単位の参照
+pub const CURRENT_FORMAT_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FamilyTree {
+    /// 保存ファイルの形式バージョン（バージョン導入前のファイルは 0）
+    #[serde(default)]
+    pub format_version: u32,
     pub persons: HashMap<PersonId, Person>,
     pub edges: Vec<ParentChild>,
     #[serde(default)]
@@ -129,9 +751,76 @@ pub struct FamilyTree {
     pub events: HashMap<EventId, Event>,
     #[serde(default)]
     pub event_relations: Vec<EventRelation>,
+    #[serde(default)]
+    pub person_relations: Vec<PersonRelation>,
+    #[serde(default)]
+    pub background: Option<CanvasBackground>,
+    #[serde(default)]
+    pub annotations: Vec<TextAnnotation>,
+    #[serde(default)]
+    pub sticky_notes: Vec<StickyNote>,
+    #[serde(default)]
+    pub node_style: NodeStyle,
+    #[serde(default)]
+    pub layout_snapshots: Vec<LayoutSnapshot>,
+    #[serde(default)]
+    pub camera_bookmarks: Vec<CameraBookmark>,
+    #[serde(default)]
+    pub guides: Vec<CanvasGuide>,
+    #[serde(default)]
+    pub places: Vec<Place>,
+    /// 親の組と子をまとめた家族の単位（親子関係から参照する）
+    #[serde(default)]
+    pub unions: Vec<Union>,
+    /// 選択中の人物に繰り返し適用する、記録した操作
+    #[serde(default)]
+    pub macros: Vec<EditMacro>,
+    /// 家系図の中心となる人物（ホームボタンで移動する先）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_person: Option<PersonId>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Default for FamilyTree {
+    fn default() -> Self {
+        Self {
+            format_version: CURRENT_FORMAT_VERSION,
+            persons: HashMap::new(),
+            edges: Vec::new(),
+            spouses: Vec::new(),
+            families: Vec::new(),
+            events: HashMap::new(),
+            event_relations: Vec::new(),
+            person_relations: Vec::new(),
+            background: None,
+            annotations: Vec::new(),
+            sticky_notes: Vec::new(),
+            node_style: NodeStyle::default(),
+            layout_snapshots: Vec::new(),
+            camera_bookmarks: Vec::new(),
+            guides: Vec::new(),
+            places: Vec::new(),
+            unions: Vec::new(),
+            macros: Vec::new(),
+            home_person: None,
+            extra: ExtraFields::new(),
+        }
+    }
 }
 
 impl FamilyTree {
+    /// 未保存の変更を検出するための内容のハッシュ値
+    ///
+    /// HashMap の走査順は作り直すたびに変わるので、キーを並べ替えた `serde_json::Value` を経由して文字列にする。
+    pub fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        serde_json::to_value(self).map(|value| value.to_string()).unwrap_or_default().hash(&mut hasher);
+        hasher.finish()
+    }
+
     pub fn add_person(&mut self, name: String, gender: Gender, birth: Option<String>, memo: String, deceased: bool, death: Option<String>, position: (f32, f32)) -> PersonId {
         let id = Uuid::new_v4();
         self.persons.insert(
@@ -145,9 +834,18 @@ impl FamilyTree {
                 position,
                 deceased,
                 death,
-                photo_path: Some("photo/DefaultImage.gif".to_string()),
+                photo_path: Some(DEFAULT_PHOTO_PATH.to_string()),
                 display_mode: PersonDisplayMode::NameOnly,
                 photo_scale: 1.0,
+                multiple_birth_group: None,
+                gedcom_extras: Vec::new(),
+                pinned: false,
+                locked: false,
+                birth_place: None,
+                death_place: None,
+                badges: Vec::new(),
+                external_link: None,
+                extra: ExtraFields::new(),
             },
         );
         id
@@ -157,14 +855,95 @@ impl FamilyTree {
         self.persons.remove(&id);
         self.edges.retain(|e| e.parent != id && e.child != id);
         self.spouses.retain(|s| s.person1 != id && s.person2 != id);
-        
+        self.sync_unions();
+        self.person_relations.retain(|r| r.from != id && r.to != id);
+        self.sticky_notes.retain(|n| n.person != id);
+        self.event_relations.retain(|r| r.person != id);
+        if self.home_person == Some(id) {
+            self.home_person = None;
+        }
+        for snapshot in &mut self.layout_snapshots {
+            snapshot.person_positions.remove(&id);
+        }
+
         // 家族グループからも削除
         for family in &mut self.families {
             family.members.retain(|member_id| *member_id != id);
         }
     }
 
+    /// 人物を削除したときの影響を調べる
+    ///
+    /// 親・子・配偶者のうち、日付・メモ・写真・場所が何もなく、削除する人物以外とのつながりもない人物を
+    /// 仮の親族として候補にする。include_orphans が true なら候補も一緒に削除する計画にする。
+    pub fn plan_person_deletion(&self, id: PersonId, include_orphans: bool) -> Option<PersonDeletionPlan> {
+        self.persons.get(&id)?;
+        let mut relatives = self.parents_of(id);
+        relatives.extend(self.children_of(id));
+        relatives.extend(self.spouses_of(id));
+        let mut orphan_candidates: Vec<PersonId> = Vec::new();
+        for relative in relatives {
+            if relative != id && !orphan_candidates.contains(&relative) && self.is_isolated_placeholder(relative, id) {
+                orphan_candidates.push(relative);
+            }
+        }
+
+        let orphans = if include_orphans { orphan_candidates.clone() } else { Vec::new() };
+        let removed = |person: PersonId| person == id || orphans.contains(&person);
+        Some(PersonDeletionPlan {
+            person: id,
+            parent_child_links: self.edges.iter().filter(|e| removed(e.parent) || removed(e.child)).count(),
+            spouse_links: self.spouses.iter().filter(|s| removed(s.person1) || removed(s.person2)).count(),
+            person_relation_links: self.person_relations.iter().filter(|r| removed(r.from) || removed(r.to)).count(),
+            event_links: self.event_relations.iter().filter(|r| removed(r.person)).count(),
+            families: self
+                .families
+                .iter()
+                .filter(|family| family.members.iter().any(|member| removed(*member)))
+                .map(|family| family.id)
+                .collect(),
+            orphans,
+            orphan_candidates,
+        })
+    }
+
+    /// 削除の計画どおりに人物を削除する
+    pub fn apply_deletion_plan(&mut self, plan: &PersonDeletionPlan) {
+        let removed: Vec<PersonId> = plan.removed_persons().collect();
+        for person in removed {
+            self.remove_person(person);
+        }
+    }
+
+    /// 記録が何もなく、except 以外の人物・イベントとつながっていない人物か
+    fn is_isolated_placeholder(&self, id: PersonId, except: PersonId) -> bool {
+        let Some(person) = self.persons.get(&id) else {
+            return false;
+        };
+        let blank = |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+        let placeholder = blank(&person.birth)
+            && blank(&person.death)
+            && person.memo.trim().is_empty()
+            && !person.has_own_photo()
+            && person.birth_place.is_none()
+            && person.death_place.is_none();
+        let other = |a: PersonId, b: PersonId| (a == id && b != except) || (b == id && a != except);
+        placeholder
+            && !self.edges.iter().any(|e| other(e.parent, e.child))
+            && !self.spouses.iter().any(|s| other(s.person1, s.person2))
+            && !self.person_relations.iter().any(|r| r.from == id || r.to == id)
+            && !self.event_relations.iter().any(|r| r.person == id)
+    }
+
     pub fn add_parent_child(&mut self, parent: PersonId, child: PersonId, kind: String) {
+        self.add_parent_child_unsynced(parent, child, kind);
+        self.sync_unions();
+    }
+
+    /// 家族の単位を組み直さずに親子関係を追加する
+    ///
+    /// ファイルの読み込みなどでまとめて追加するときに使い、最後に `sync_unions` を1回呼ぶ。
+    pub fn add_parent_child_unsynced(&mut self, parent: PersonId, child: PersonId, kind: String) {
         // 重複エッジ防止（同じ親子・同じkindなら追加しない）
         if self
             .edges
@@ -173,10 +952,44 @@ impl FamilyTree {
         {
             return;
         }
-        self.edges.push(ParentChild { parent, child, kind });
+        self.edges.push(ParentChild {
+            parent,
+            child,
+            kind,
+            adoption_date: None,
+            adoption_type: String::new(),
+            adoption_note: String::new(),
+            union: None,
+            extra: ExtraFields::new(),
+        });
+    }
+
+    /// 指定位置を中心に、夫婦とその子をつないだ状態で追加する（父・母・子のIDを返す）
+    pub fn insert_couple_with_child(
+        &mut self,
+        center: (f32, f32),
+        father_name: String,
+        mother_name: String,
+        child_name: String,
+    ) -> (PersonId, PersonId, PersonId) {
+        let (x, y) = center;
+        let father = self.add_person(father_name, Gender::Male, None, String::new(), false, None, (x - 150.0, y - 80.0));
+        let mother = self.add_person(mother_name, Gender::Female, None, String::new(), false, None, (x + 30.0, y - 80.0));
+        let child = self.add_person(child_name, Gender::Unknown, None, String::new(), false, None, (x - 60.0, y + 40.0));
+
+        self.add_spouse(father, mother, String::new());
+        self.add_parent_child(father, child, "biological".to_string());
+        self.add_parent_child(mother, child, "biological".to_string());
+        (father, mother, child)
     }
 
     pub fn add_spouse(&mut self, person1: PersonId, person2: PersonId, memo: String) {
+        self.add_spouse_unsynced(person1, person2, memo);
+        self.sync_unions();
+    }
+
+    /// 家族の単位を組み直さずに配偶者関係を追加する（最後に `sync_unions` を1回呼ぶ）
+    pub fn add_spouse_unsynced(&mut self, person1: PersonId, person2: PersonId, memo: String) {
         // 重複防止（順序に関わらず同じペアなら追加しない）
         if self.spouses.iter().any(|s| {
             (s.person1 == person1 && s.person2 == person2)
@@ -188,11 +1001,43 @@ impl FamilyTree {
             person1,
             person2,
             memo,
+            marriage_date: None,
+            divorced: false,
+            divorce_date: None,
+            label: SpouseLabelStyle::default(),
+            extra: ExtraFields::new(),
         });
     }
 
+    /// 2人の配偶者関係を取得（順序は問わない）
+    pub fn spouse_relation(&self, person1: PersonId, person2: PersonId) -> Option<&Spouse> {
+        self.spouses.iter().find(|s| {
+            (s.person1 == person1 && s.person2 == person2)
+                || (s.person1 == person2 && s.person2 == person1)
+        })
+    }
+
+    /// 2人の配偶者関係を可変参照で取得（順序は問わない）
+    pub fn spouse_relation_mut(&mut self, person1: PersonId, person2: PersonId) -> Option<&mut Spouse> {
+        self.spouses.iter_mut().find(|s| {
+            (s.person1 == person1 && s.person2 == person2)
+                || (s.person1 == person2 && s.person2 == person1)
+        })
+    }
+
+    /// 親子関係を取得
+    pub fn parent_child_relation(&self, parent: PersonId, child: PersonId) -> Option<&ParentChild> {
+        self.edges.iter().find(|e| e.parent == parent && e.child == child)
+    }
+
+    /// 親子関係を可変参照で取得
+    pub fn parent_child_relation_mut(&mut self, parent: PersonId, child: PersonId) -> Option<&mut ParentChild> {
+        self.edges.iter_mut().find(|e| e.parent == parent && e.child == child)
+    }
+
     pub fn remove_parent_child(&mut self, parent: PersonId, child: PersonId) {
         self.edges.retain(|e| !(e.parent == parent && e.child == child));
+        self.sync_unions();
     }
 
     pub fn remove_spouse(&mut self, person1: PersonId, person2: PersonId) {
@@ -200,6 +1045,7 @@ impl FamilyTree {
             !((s.person1 == person1 && s.person2 == person2)
                 || (s.person1 == person2 && s.person2 == person1))
         });
+        self.sync_unions();
     }
 
     pub fn parents_of(&self, child: PersonId) -> Vec<PersonId> {
@@ -233,6 +1079,278 @@ impl FamilyTree {
             .collect()
     }
 
+    /// 短縮IDまたは完全なIDから人物を検索する（一意に特定できる場合のみ）
+    ///
+    /// 「Ada」のような名前を ID の前方一致とみなさないよう、`id_matches_query` と同じく短縮ID以上の長さの16進数だけを受け付ける。
+    pub fn find_person_by_id(&self, text: &str) -> Option<PersonId> {
+        let mut matches = self.persons.keys().filter(|id| id_matches_query(id, text));
+        let found = matches.next().copied();
+        if matches.next().is_some() {
+            return None;
+        }
+        found
+    }
+
+    /// 子の親を接続点ごとにまとめる
+    ///
+    /// 親子関係の種類ごとに、配偶者同士の親を組にし、残りは父と母を1組にする。
+    /// それでも余った親は1人だけの接続点にするので、3人以上の親も省略されない。
+    /// 渡すのは1人の子の親子関係で、配偶者かどうかの判定は呼び出し側が渡す。
+    fn junctions_from_edges<'a>(
+        &self,
+        edges: impl Iterator<Item = &'a ParentChild>,
+        are_spouses: impl Fn(PersonId, PersonId) -> bool,
+    ) -> Vec<ParentJunction> {
+        let mut groups: Vec<(String, Vec<PersonId>)> = Vec::new();
+        for edge in edges {
+            match groups.iter_mut().find(|(kind, _)| *kind == edge.kind) {
+                Some((_, parents)) => parents.push(edge.parent),
+                None => groups.push((edge.kind.clone(), vec![edge.parent])),
+            }
+        }
+
+        let mut junctions = Vec::new();
+        for (kind, mut remaining) in groups {
+            let mut couples = Vec::new();
+            let mut i = 0;
+            while i < remaining.len() {
+                let partner = (i + 1..remaining.len()).find(|&j| are_spouses(remaining[i], remaining[j]));
+                if let Some(j) = partner {
+                    let second = remaining.remove(j);
+                    let first = remaining.remove(i);
+                    couples.push((vec![first, second], true));
+                } else {
+                    i += 1;
+                }
+            }
+
+            let gender_of = |id: &PersonId| self.persons.get(id).map(|person| &person.gender);
+            let father = remaining.iter().position(|id| gender_of(id) == Some(&Gender::Male));
+            let mother = remaining.iter().position(|id| gender_of(id) == Some(&Gender::Female));
+            if let (Some(father), Some(mother)) = (father, mother) {
+                let pair = vec![remaining[father], remaining[mother]];
+                remaining.retain(|id| !pair.contains(id));
+                couples.push((pair, false));
+            }
+
+            let singles = remaining.into_iter().map(|parent| (vec![parent], false));
+            junctions.extend(couples.into_iter().chain(singles).map(|(parents, are_spouses)| ParentJunction {
+                kind: kind.clone(),
+                parents,
+                are_spouses,
+            }));
+        }
+        junctions
+    }
+
+    /// 保存している家族の単位（子のいない夫婦は含まない）
+    pub fn unions(&self) -> &[Union] {
+        &self.unions
+    }
+
+    /// 家族の単位ごとの子（親子関係が追加された順）
+    pub fn children_by_union(&self) -> HashMap<UnionId, Vec<PersonId>> {
+        let mut children: HashMap<UnionId, Vec<PersonId>> = HashMap::new();
+        for edge in &self.edges {
+            let Some(union) = edge.union else {
+                continue;
+            };
+            let entry = children.entry(union).or_default();
+            if !entry.contains(&edge.child) {
+                entry.push(edge.child);
+            }
+        }
+        children
+    }
+
+    /// 家族の単位の2人の親が配偶者関係にあるか
+    pub fn union_partners_are_spouses(&self, union: &Union) -> bool {
+        matches!(union.partners.as_slice(), [first, second] if self.spouse_relation(*first, *second).is_some())
+    }
+
+    /// 親子関係から家族の単位を組み直し、各親子関係に単位のIDを設定する
+    ///
+    /// 子ごとの接続点（`parent_junctions`）を、親の組と種類が同じ既存の単位に割り当てるので、
+    /// 関係を編集しても単位のIDは変わらない。どの親子関係からも参照されなくなった単位は削除する。
+    /// 親子関係・配偶者関係・性別を変えたとき、ファイルを読み込んだときに呼び出す。
+    pub fn sync_unions(&mut self) {
+        // 子ごとの親子関係と配偶者の組を先に引けるようにして、関係の数に比例する手間で組み直す
+        let mut children = Vec::new();
+        let mut edges_by_child: HashMap<PersonId, Vec<usize>> = HashMap::new();
+        for (index, edge) in self.edges.iter().enumerate() {
+            edges_by_child
+                .entry(edge.child)
+                .or_insert_with(|| {
+                    children.push(edge.child);
+                    Vec::new()
+                })
+                .push(index);
+        }
+        let spouse_pairs: HashSet<(PersonId, PersonId)> = self
+            .spouses
+            .iter()
+            .flat_map(|spouse| [(spouse.person1, spouse.person2), (spouse.person2, spouse.person1)])
+            .collect();
+
+        let mut unions: Vec<(Union, bool)> = std::mem::take(&mut self.unions).into_iter().map(|union| (union, false)).collect();
+        let mut union_index: HashMap<(String, Vec<PersonId>), usize> = HashMap::new();
+        for (index, (union, _)) in unions.iter().enumerate() {
+            union_index.entry(union_key(&union.kind, &union.partners)).or_insert(index);
+        }
+
+        let mut assignments = Vec::new();
+        for child in children {
+            let edge_indices = &edges_by_child[&child];
+            let edges = edge_indices.iter().map(|&index| &self.edges[index]);
+            for junction in self.junctions_from_edges(edges, |a, b| spouse_pairs.contains(&(a, b))) {
+                let index = *union_index.entry(union_key(&junction.kind, &junction.parents)).or_insert_with(|| {
+                    unions.push((
+                        Union { id: Uuid::new_v4(), kind: junction.kind.clone(), partners: junction.parents.clone(), extra: ExtraFields::new() },
+                        false,
+                    ));
+                    unions.len() - 1
+                });
+                unions[index].1 = true;
+                let id = unions[index].0.id;
+                assignments.extend(
+                    edge_indices
+                        .iter()
+                        .filter(|&&edge_index| {
+                            let edge = &self.edges[edge_index];
+                            edge.kind == junction.kind && junction.parents.contains(&edge.parent)
+                        })
+                        .map(|&edge_index| (edge_index, id)),
+                );
+            }
+        }
+        for (edge_index, id) in assignments {
+            self.edges[edge_index].union = Some(id);
+        }
+        self.unions = unions.into_iter().filter(|(_, used)| *used).map(|(union, _)| union).collect();
+    }
+
+    /// きょうだい（親を1人以上共有する人物）を返す
+    pub fn siblings_of(&self, person: PersonId) -> Vec<PersonId> {
+        let mut siblings = Vec::new();
+        for parent in self.parents_of(person) {
+            for child in self.children_of(parent) {
+                if child != person && !siblings.contains(&child) {
+                    siblings.push(child);
+                }
+            }
+        }
+        siblings
+    }
+
+    // ===== 目印操作メソッド =====
+
+    /// 人物の目印を付け外しする（付けた順ではなく一覧の順に並べる）
+    pub fn toggle_person_badge(&mut self, id: PersonId, badge: PersonBadge) {
+        let Some(person) = self.persons.get_mut(&id) else {
+            return;
+        };
+        if let Some(index) = person.badges.iter().position(|b| *b == badge) {
+            person.badges.remove(index);
+        } else {
+            person.badges.push(badge);
+            // 知らない目印は既知の目印の後ろに置く
+            person.badges.sort_by_key(|b| PersonBadge::ALL.iter().position(|a| a == b).unwrap_or(usize::MAX));
+        }
+    }
+
+    // ===== 多胎（双子・三つ子など）操作メソッド =====
+
+    /// 指定した人物を同じ出産のきょうだいとして登録する
+    /// 既にいずれかがグループに属している場合は、そのグループに統合する
+    pub fn mark_multiple_birth(&mut self, ids: &[PersonId]) {
+        if ids.len() < 2 {
+            return;
+        }
+
+        let existing_groups: Vec<Uuid> = ids
+            .iter()
+            .filter_map(|id| self.persons.get(id).and_then(|p| p.multiple_birth_group))
+            .collect();
+        let group = existing_groups.first().copied().unwrap_or_else(Uuid::new_v4);
+
+        for person in self.persons.values_mut() {
+            let merged = person
+                .multiple_birth_group
+                .is_some_and(|g| existing_groups.contains(&g));
+            if merged || ids.contains(&person.id) {
+                person.multiple_birth_group = Some(group);
+            }
+        }
+    }
+
+    /// 人物を多胎グループから外す（残りが1人になった場合はグループを解消する）
+    pub fn clear_multiple_birth(&mut self, id: PersonId) {
+        let Some(group) = self.persons.get_mut(&id).and_then(|p| p.multiple_birth_group.take()) else {
+            return;
+        };
+
+        let remaining: Vec<PersonId> = self.multiple_birth_groups().remove(&group).unwrap_or_default();
+        if remaining.len() < 2 {
+            for remaining_id in remaining {
+                if let Some(person) = self.persons.get_mut(&remaining_id) {
+                    person.multiple_birth_group = None;
+                }
+            }
+        }
+    }
+
+    /// 同じ出産のきょうだい（本人を除く）を返す
+    pub fn multiple_birth_siblings_of(&self, id: PersonId) -> Vec<PersonId> {
+        let Some(group) = self.persons.get(&id).and_then(|p| p.multiple_birth_group) else {
+            return Vec::new();
+        };
+        self.multiple_birth_groups()
+            .remove(&group)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|member| *member != id)
+            .collect()
+    }
+
+    /// 多胎グループごとのメンバーを返す
+    pub fn multiple_birth_groups(&self) -> HashMap<Uuid, Vec<PersonId>> {
+        let mut groups: HashMap<Uuid, Vec<PersonId>> = HashMap::new();
+        for person in self.persons.values() {
+            if let Some(group) = person.multiple_birth_group {
+                groups.entry(group).or_default().push(person.id);
+            }
+        }
+        groups
+    }
+
+    /// 継親（親の配偶者のうち、本人の親ではない人物）を導出する
+    pub fn step_parents_of(&self, child: PersonId) -> Vec<PersonId> {
+        let parents = self.parents_of(child);
+        let mut step_parents = Vec::new();
+        for parent in &parents {
+            for spouse in self.spouses_of(*parent) {
+                if spouse != child && !parents.contains(&spouse) && !step_parents.contains(&spouse) {
+                    step_parents.push(spouse);
+                }
+            }
+        }
+        step_parents
+    }
+
+    /// 継子（配偶者の子のうち、本人の子ではない人物）を導出する
+    pub fn step_children_of(&self, person: PersonId) -> Vec<PersonId> {
+        let children = self.children_of(person);
+        let mut step_children = Vec::new();
+        for spouse in self.spouses_of(person) {
+            for child in self.children_of(spouse) {
+                if child != person && !children.contains(&child) && !step_children.contains(&child) {
+                    step_children.push(child);
+                }
+            }
+        }
+        step_children
+    }
+
     /// ルート（親がいない人物）を返す
     pub fn roots(&self) -> Vec<PersonId> {
         let mut has_parent = HashMap::<PersonId, bool>::new();
@@ -248,6 +1366,34 @@ impl FamilyTree {
             .collect()
     }
 
+    // ===== 人物間の関係（後見人・名付け親など）操作メソッド =====
+
+    pub fn add_person_relation(&mut self, from: PersonId, to: PersonId, kind: PersonRelationKind, memo: String) {
+        // 自己参照と重複を防止（同じ組・同じ種類なら追加しない）
+        if from == to
+            || self
+                .person_relations
+                .iter()
+                .any(|r| r.from == from && r.to == to && r.kind == kind)
+        {
+            return;
+        }
+        self.person_relations.push(PersonRelation { from, to, kind, memo, extra: ExtraFields::new() });
+    }
+
+    pub fn remove_person_relation(&mut self, from: PersonId, to: PersonId, kind: PersonRelationKind) {
+        self.person_relations
+            .retain(|r| !(r.from == from && r.to == to && r.kind == kind));
+    }
+
+    /// 指定した人物が関わる人物間の関係を返す
+    pub fn person_relations_of(&self, person: PersonId) -> Vec<&PersonRelation> {
+        self.person_relations
+            .iter()
+            .filter(|r| r.from == person || r.to == person)
+            .collect()
+    }
+
     // ===== 家族操作メソッド =====
 
     pub fn add_family(&mut self, name: String, color: Option<(u8, u8, u8)>) -> Uuid {
@@ -256,12 +1402,40 @@ impl FamilyTree {
             name,
             members: Vec::new(),
             color,
+            defaults: FamilyDefaults::default(),
+            extra: ExtraFields::new(),
         };
         let id = family.id;
         self.families.push(family);
         id
     }
 
+    /// 家族の既定値を使って新しい人物を追加し、家族に加える
+    ///
+    /// 親を指定すると、その子として家族の既定の種類（空なら fallback_kind）で親子関係を作り、名前に姓を付ける。
+    pub fn add_family_member_person(
+        &mut self,
+        family_id: Uuid,
+        parent: Option<PersonId>,
+        name: &str,
+        fallback_kind: &str,
+        gender: Gender,
+        position: (f32, f32),
+    ) -> Option<PersonId> {
+        let defaults = self.families.iter().find(|f| f.id == family_id)?.defaults.clone();
+        let name = if parent.is_some() { defaults.with_surname(name) } else { name.to_string() };
+        let person = self.add_person(name, gender, None, String::new(), false, None, position);
+        self.add_member_to_family(family_id, person);
+        if let Some(parent) = parent {
+            let kind = match defaults.relation_kind.trim() {
+                "" => fallback_kind.to_string(),
+                kind => kind.to_string(),
+            };
+            self.add_parent_child(parent, person, kind);
+        }
+        Some(person)
+    }
+
     pub fn remove_family(&mut self, family_id: Uuid) {
         self.families.retain(|f| f.id != family_id);
     }
@@ -287,6 +1461,9 @@ impl FamilyTree {
                 description,
                 position,
                 color,
+                place: None,
+                template: None,
+                extra: ExtraFields::new(),
             },
         );
         id
@@ -295,6 +1472,9 @@ impl FamilyTree {
     pub fn remove_event(&mut self, id: EventId) {
         self.events.remove(&id);
         self.event_relations.retain(|r| r.event != id);
+        for snapshot in &mut self.layout_snapshots {
+            snapshot.event_positions.remove(&id);
+        }
     }
 
     pub fn add_event_relation(&mut self, event: EventId, person: PersonId, relation_type: EventRelationType, memo: String) {
@@ -307,6 +1487,7 @@ impl FamilyTree {
             person,
             relation_type,
             memo,
+            extra: ExtraFields::new(),
         });
     }
 
@@ -321,6 +1502,135 @@ impl FamilyTree {
             .collect()
     }
 
+    pub fn add_annotation(&mut self, text: String, position: (f32, f32), font_size: f32, color: (u8, u8, u8)) -> AnnotationId {
+        let id = Uuid::new_v4();
+        self.annotations.push(TextAnnotation {
+            id,
+            text,
+            position,
+            font_size,
+            color,
+            extra: ExtraFields::new(),
+        });
+        id
+    }
+
+    pub fn remove_annotation(&mut self, id: AnnotationId) {
+        self.annotations.retain(|a| a.id != id);
+    }
+
+    pub fn annotation(&self, id: AnnotationId) -> Option<&TextAnnotation> {
+        self.annotations.iter().find(|a| a.id == id)
+    }
+
+    pub fn annotation_mut(&mut self, id: AnnotationId) -> Option<&mut TextAnnotation> {
+        self.annotations.iter_mut().find(|a| a.id == id)
+    }
+
+    pub fn add_sticky_note(&mut self, person: PersonId, text: String) -> StickyNoteId {
+        let id = Uuid::new_v4();
+        self.sticky_notes.push(StickyNote {
+            id,
+            person,
+            text,
+            resolved: false,
+            extra: ExtraFields::new(),
+        });
+        id
+    }
+
+    pub fn remove_sticky_note(&mut self, id: StickyNoteId) {
+        self.sticky_notes.retain(|n| n.id != id);
+    }
+
+    pub fn sticky_note_mut(&mut self, id: StickyNoteId) -> Option<&mut StickyNote> {
+        self.sticky_notes.iter_mut().find(|n| n.id == id)
+    }
+
+    /// 指定した人物に貼られた付箋を返す
+    pub fn sticky_notes_of(&self, person: PersonId) -> Vec<&StickyNote> {
+        self.sticky_notes.iter().filter(|n| n.person == person).collect()
+    }
+
+    /// 未対応の付箋をすべて返す
+    pub fn outstanding_sticky_notes(&self) -> Vec<&StickyNote> {
+        self.sticky_notes.iter().filter(|n| !n.resolved).collect()
+    }
+
+    /// 現在の人物・イベントの座標を名前を付けて保存する（同じ名前があれば上書き）
+    pub fn save_layout_snapshot(&mut self, name: String) -> Uuid {
+        let person_positions = self.persons.iter().map(|(id, p)| (*id, p.position)).collect();
+        let event_positions = self.events.iter().map(|(id, e)| (*id, e.position)).collect();
+        if let Some(snapshot) = self.layout_snapshots.iter_mut().find(|s| s.name == name) {
+            snapshot.person_positions = person_positions;
+            snapshot.event_positions = event_positions;
+            return snapshot.id;
+        }
+        let id = Uuid::new_v4();
+        self.layout_snapshots.push(LayoutSnapshot {
+            id,
+            name,
+            person_positions,
+            event_positions,
+            extra: ExtraFields::new(),
+        });
+        id
+    }
+
+    /// 保存した配置に切り替える（保存後に追加された人物・イベントはそのまま）
+    pub fn apply_layout_snapshot(&mut self, id: Uuid) -> bool {
+        let Some(snapshot) = self.layout_snapshots.iter().find(|s| s.id == id) else {
+            return false;
+        };
+        for (person_id, position) in &snapshot.person_positions {
+            if let Some(person) = self.persons.get_mut(person_id) {
+                person.position = *position;
+            }
+        }
+        for (event_id, position) in &snapshot.event_positions {
+            if let Some(event) = self.events.get_mut(event_id) {
+                event.position = *position;
+            }
+        }
+        true
+    }
+
+    pub fn remove_layout_snapshot(&mut self, id: Uuid) {
+        self.layout_snapshots.retain(|s| s.id != id);
+    }
+
+    /// 表示位置を名前を付けて保存する（同じ名前があれば上書き）
+    pub fn save_camera_bookmark(&mut self, name: String, zoom: f32, pan: (f32, f32)) -> Uuid {
+        if let Some(bookmark) = self.camera_bookmarks.iter_mut().find(|b| b.name == name) {
+            bookmark.zoom = zoom;
+            bookmark.pan = pan;
+            return bookmark.id;
+        }
+        let id = Uuid::new_v4();
+        self.camera_bookmarks.push(CameraBookmark { id, name, zoom, pan, extra: ExtraFields::new() });
+        id
+    }
+
+    pub fn remove_camera_bookmark(&mut self, id: Uuid) {
+        self.camera_bookmarks.retain(|b| b.id != id);
+    }
+
+    pub fn add_guide(&mut self, axis: GuideAxis, position: f32) -> Uuid {
+        let id = Uuid::new_v4();
+        self.guides.push(CanvasGuide { id, axis, position, extra: ExtraFields::new() });
+        id
+    }
+
+    pub fn move_guide(&mut self, id: Uuid, position: f32) {
+        if let Some(guide) = self.guides.iter_mut().find(|g| g.id == id) {
+            guide.position = position;
+        }
+    }
+
+    pub fn remove_guide(&mut self, id: Uuid) {
+        self.guides.retain(|g| g.id != id);
+    }
+
     pub fn remove_member_from_family(&mut self, family_id: Uuid, person_id: PersonId) {
         if let Some(family) = self.families.iter_mut().find(|f| f.id == family_id) {
             family.members.retain(|&id| id != person_id);
@@ -341,6 +1651,109 @@ impl FamilyTree {
         }
     }
 
+    // ===== 地名辞典の操作メソッド =====
+
+    pub fn add_place(&mut self, name: String) -> PlaceId {
+        let id = Uuid::new_v4();
+        self.places.push(Place {
+            id,
+            name,
+            latitude: None,
+            longitude: None,
+            note: String::new(),
+            extra: ExtraFields::new(),
+        });
+        id
+    }
+
+    pub fn get_place(&self, id: PlaceId) -> Option<&Place> {
+        self.places.iter().find(|p| p.id == id)
+    }
+
+    pub fn place_mut(&mut self, id: PlaceId) -> Option<&mut Place> {
+        self.places.iter_mut().find(|p| p.id == id)
+    }
+
+    /// 場所を参照している出生地・死亡地・イベントの数
+    pub fn place_usage_count(&self, id: PlaceId) -> usize {
+        self.place_references(id).len()
+    }
+
+    /// 場所を参照している出生地・死亡地・イベント（人物、イベントの順にそれぞれ名前順）
+    pub fn place_references(&self, id: PlaceId) -> Vec<PlaceReference> {
+        let mut persons: Vec<&Person> = self.persons.values().collect();
+        persons.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        let mut events: Vec<&Event> = self.events.values().filter(|e| e.place == Some(id)).collect();
+        events.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+
+        let mut references = Vec::new();
+        for person in persons {
+            if person.birth_place == Some(id) {
+                references.push(PlaceReference::BirthPlace(person.id));
+            }
+            if person.death_place == Some(id) {
+                references.push(PlaceReference::DeathPlace(person.id));
+            }
+        }
+        references.extend(events.into_iter().map(|event| PlaceReference::Event(event.id)));
+        references
+    }
+
+    /// 参照を付け替える（None なら参照を外す）
+    fn replace_place_references(&mut self, from: PlaceId, to: Option<PlaceId>) {
+        for person in self.persons.values_mut() {
+            for place in [&mut person.birth_place, &mut person.death_place] {
+                if *place == Some(from) {
+                    *place = to;
+                }
+            }
+        }
+        for event in self.events.values_mut().filter(|e| e.place == Some(from)) {
+            event.place = to;
+        }
+    }
+
+    /// 場所を削除し、参照している人物・イベントからも外す
+    pub fn remove_place(&mut self, id: PlaceId) {
+        self.places.retain(|p| p.id != id);
+        self.replace_place_references(id, None);
+    }
+
+    /// 表記ゆれの重複を keep にまとめる（参照を付け替え、keep にない座標・メモを引き継ぐ）
+    pub fn merge_places(&mut self, keep: PlaceId, duplicate: PlaceId) {
+        if keep == duplicate {
+            return;
+        }
+        let Some(merged) = self.get_place(duplicate).cloned() else {
+            return;
+        };
+        let Some(place) = self.place_mut(keep) else {
+            return;
+        };
+        if place.latitude.is_none() || place.longitude.is_none() {
+            place.latitude = place.latitude.or(merged.latitude);
+            place.longitude = place.longitude.or(merged.longitude);
+        }
+        if place.note.is_empty() {
+            place.note = merged.note;
+        }
+        self.places.retain(|p| p.id != duplicate);
+        self.replace_place_references(duplicate, Some(keep));
+    }
+
+    /// 大文字小文字・空白・記号だけが違う場所のグループ（2件以上のもの）
+    pub fn duplicate_place_groups(&self) -> Vec<Vec<PlaceId>> {
+        let mut groups: Vec<(String, Vec<PlaceId>)> = Vec::new();
+        for place in &self.places {
+            let key = place.normalized_name();
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, ids)) => ids.push(place.id),
+                None => groups.push((key, vec![place.id])),
+            }
+        }
+        groups.into_iter().map(|(_, ids)| ids).filter(|ids| ids.len() > 1).collect()
+    }
+
     #[allow(dead_code)]
     pub fn get_family(&self, family_id: Uuid) -> Option<&Family> {
         self.families.iter().find(|f| f.id == family_id)
@@ -359,6 +1772,159 @@ impl FamilyTree {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parent_junctions_keep_extra_parents() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let adoptive_father = tree.add_person("AF".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let adoptive_mother = tree.add_person("AM".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let step = tree.add_person("Extra".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "biological".to_string());
+        tree.add_parent_child(adoptive_father, child, ADOPTIVE_KIND.to_string());
+        tree.add_parent_child(step, child, ADOPTIVE_KIND.to_string());
+        tree.add_parent_child(adoptive_mother, child, ADOPTIVE_KIND.to_string());
+        tree.add_spouse(adoptive_father, adoptive_mother, String::new());
+
+        let junctions = parent_junctions(&tree, child);
+
+        assert_eq!(
+            junctions,
+            vec![
+                ParentJunction { kind: "biological".to_string(), parents: vec![father, mother], are_spouses: false },
+                ParentJunction {
+                    kind: ADOPTIVE_KIND.to_string(),
+                    parents: vec![adoptive_father, adoptive_mother],
+                    are_spouses: true,
+                },
+                ParentJunction { kind: ADOPTIVE_KIND.to_string(), parents: vec![step], are_spouses: false },
+            ]
+        );
+        assert!(parent_junctions(&tree, father).is_empty());
+    }
+
+    #[test]
+    fn test_unsynced_adds_build_the_same_unions_after_one_sync() {
+        let build = |synced: bool| {
+            let mut tree = FamilyTree::default();
+            let ids: Vec<PersonId> = (0..6)
+                .map(|i| {
+                    let gender = if i % 2 == 0 { Gender::Male } else { Gender::Female };
+                    tree.add_person(format!("P{i}"), gender, None, String::new(), false, None, (0.0, 0.0))
+                })
+                .collect();
+            let relations = [(ids[0], ids[2], "biological"), (ids[1], ids[2], "biological"), (ids[0], ids[3], "biological"), (ids[4], ids[3], ADOPTIVE_KIND)];
+            for (parent, child, kind) in relations {
+                if synced {
+                    tree.add_parent_child(parent, child, kind.to_string());
+                } else {
+                    tree.add_parent_child_unsynced(parent, child, kind.to_string());
+                }
+            }
+            if synced {
+                tree.add_spouse(ids[0], ids[1], String::new());
+            } else {
+                tree.add_spouse_unsynced(ids[0], ids[1], String::new());
+                tree.sync_unions();
+            }
+            let mut unions: Vec<(String, Vec<String>)> = tree
+                .unions()
+                .iter()
+                .map(|union| {
+                    let mut names: Vec<String> = union.partners.iter().map(|id| tree.persons[id].name.clone()).collect();
+                    names.sort();
+                    (union.kind.clone(), names)
+                })
+                .collect();
+            unions.sort();
+            assert!(tree.edges.iter().all(|edge| edge.union.is_some()));
+            unions
+        };
+
+        assert_eq!(build(false), build(true));
+        assert_eq!(build(false).len(), 3);
+    }
+
+    fn parent_junctions(tree: &FamilyTree, child: PersonId) -> Vec<ParentJunction> {
+        tree.junctions_from_edges(tree.edges.iter().filter(|edge| edge.child == child), |a, b| {
+            tree.spouse_relation(a, b).is_some()
+        })
+    }
+
+    #[test]
+    fn test_plan_person_deletion_with_placeholder_relatives() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person("Person".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let placeholder = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let recorded = tree.add_person("Mother".to_string(), Gender::Female, Some("1900".to_string()), String::new(), false, None, (0.0, 0.0));
+        let wife = tree.add_person("Wife".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_parent_child(placeholder, person, "biological".to_string());
+        tree.add_parent_child(recorded, person, "biological".to_string());
+        tree.add_spouse(person, wife, String::new());
+        tree.add_parent_child(person, child, "biological".to_string());
+        // 妻は子ともつながっているので、削除後も孤立しない
+        tree.add_parent_child(wife, child, "biological".to_string());
+        let event = tree.add_event("Wedding".to_string(), None, String::new(), (0.0, 0.0), (0, 0, 0));
+        tree.add_event_relation(event, person, EventRelationType::Line, String::new());
+        let family = tree.add_family("Smith".to_string(), None);
+        tree.add_member_to_family(family, placeholder);
+
+        let plan = tree.plan_person_deletion(person, false).unwrap();
+        assert_eq!(plan.orphan_candidates, vec![placeholder]);
+        assert!(plan.orphans.is_empty());
+        assert_eq!((plan.parent_child_links, plan.spouse_links, plan.event_links), (3, 1, 1));
+        assert!(plan.families.is_empty());
+
+        let plan = tree.plan_person_deletion(person, true).unwrap();
+        assert_eq!(plan.orphans, vec![placeholder]);
+        assert_eq!(plan.families, vec![family]);
+        tree.apply_deletion_plan(&plan);
+        assert!(!tree.persons.contains_key(&person) && !tree.persons.contains_key(&placeholder));
+        assert!(tree.persons.contains_key(&recorded) && tree.persons.contains_key(&wife));
+        assert!(tree.event_relations.is_empty());
+        assert_eq!(tree.edges.len(), 1);
+        assert!(tree.plan_person_deletion(person, true).is_none());
+    }
+
+    #[test]
+    fn test_unions_separate_children_of_remarriage() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let first_wife = tree.add_person("First".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let second_wife = tree.add_person("Second".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let child1 = tree.add_person("C1".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        let child2 = tree.add_person("C2".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        let child3 = tree.add_person("C3".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        let child4 = tree.add_person("C4".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, first_wife, String::new());
+        tree.add_spouse(father, second_wife, String::new());
+        tree.add_parent_child(father, child1, "biological".to_string());
+        tree.add_parent_child(first_wife, child1, "biological".to_string());
+        tree.add_parent_child(second_wife, child2, "biological".to_string());
+        tree.add_parent_child(father, child2, "biological".to_string());
+        tree.add_parent_child(first_wife, child3, "biological".to_string());
+        tree.add_parent_child(father, child3, "biological".to_string());
+        tree.add_parent_child(father, child4, "biological".to_string());
+
+        let unions = tree.unions().to_vec();
+        let partners: Vec<&[PersonId]> = unions.iter().map(|union| union.partners.as_slice()).collect();
+        assert_eq!(partners, [&[father, first_wife][..], &[second_wife, father], &[father]]);
+        assert!(unions.iter().all(|union| union.kind == "biological"));
+        assert!(tree.union_partners_are_spouses(&unions[0]) && !tree.union_partners_are_spouses(&unions[2]));
+        let children = tree.children_by_union();
+        assert_eq!(children[&unions[0].id], [child1, child3]);
+        assert_eq!(children[&unions[1].id], [child2]);
+        assert_eq!(children[&unions[2].id], [child4]);
+        assert!(tree.edges.iter().all(|edge| edge.union.is_some()));
+
+        // 関係を編集しても残った単位のIDは変わらず、参照されなくなった単位は消える
+        tree.remove_parent_child(father, child4);
+        assert_eq!(tree.unions(), &unions[..2]);
+    }
+
     #[test]
     fn test_add_person() {
         let mut tree = FamilyTree::default();
@@ -382,6 +1948,131 @@ mod tests {
         assert_eq!(person.death, None);
     }
 
+    #[test]
+    fn test_gender_serde_back_compat() {
+        let male: Gender = serde_json::from_str("\"Male\"").unwrap();
+        assert_eq!(male, Gender::Male);
+
+        let other = Gender::Other("non-binary".to_string());
+        let json = serde_json::to_string(&other).unwrap();
+        assert_eq!(serde_json::from_str::<Gender>(&json).unwrap(), other);
+    }
+
+    #[test]
+    fn test_presumed_deceased() {
+        let mut tree = FamilyTree::default();
+        let old = tree.add_person("Old".to_string(), Gender::Male, Some("1900-01-01".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let young = tree.add_person("Young".to_string(), Gender::Female, Some("1990".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let dead = tree.add_person("Dead".to_string(), Gender::Male, Some("1850".to_string()), "".to_string(), true, None, (0.0, 0.0));
+        let no_birth = tree.add_person("NoBirth".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+
+        assert!(tree.persons[&old].is_presumed_deceased(DEFAULT_PRESUMED_DECEASED_YEARS, 2026));
+        assert!(!tree.persons[&old].is_presumed_deceased(DEFAULT_PRESUMED_DECEASED_YEARS, 2010));
+        assert!(!tree.persons[&young].is_presumed_deceased(DEFAULT_PRESUMED_DECEASED_YEARS, 2026));
+        assert!(!tree.persons[&dead].is_presumed_deceased(DEFAULT_PRESUMED_DECEASED_YEARS, 2026));
+        assert!(!tree.persons[&no_birth].is_presumed_deceased(DEFAULT_PRESUMED_DECEASED_YEARS, 2026));
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_content() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person("A".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let saved = tree.fingerprint();
+        assert_eq!(tree.fingerprint(), saved);
+
+        tree.persons.get_mut(&person).unwrap().name = "B".to_string();
+        assert_ne!(tree.fingerprint(), saved);
+
+        tree.persons.get_mut(&person).unwrap().name = "A".to_string();
+        assert_eq!(tree.fingerprint(), saved);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_insertion_order() {
+        let mut tree = FamilyTree::default();
+        for name in ["A", "B", "C", "D", "E", "F", "G", "H"] {
+            tree.add_person(name.to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        }
+        tree.add_event("Wedding".to_string(), None, "".to_string(), (0.0, 0.0), (255, 255, 200));
+        tree.add_event("Funeral".to_string(), None, "".to_string(), (0.0, 0.0), (255, 255, 200));
+
+        // 同じ内容を逆の順で別の HashMap に入れ直す
+        let mut rebuilt = tree.clone();
+        let mut persons: Vec<_> = tree.persons.iter().map(|(id, person)| (*id, person.clone())).collect();
+        persons.reverse();
+        rebuilt.persons = persons.into_iter().collect();
+        let mut events: Vec<_> = tree.events.iter().map(|(id, event)| (*id, event.clone())).collect();
+        events.reverse();
+        rebuilt.events = events.into_iter().collect();
+
+        assert_eq!(rebuilt.fingerprint(), tree.fingerprint());
+    }
+
+    #[test]
+    fn test_insert_couple_with_child() {
+        let mut tree = FamilyTree::default();
+        let (father, mother, child) = tree.insert_couple_with_child(
+            (400.0, 300.0),
+            "Father".to_string(),
+            "Mother".to_string(),
+            "Child".to_string(),
+        );
+
+        assert_eq!(tree.persons.len(), 3);
+        assert_eq!(tree.spouses.len(), 1);
+        assert_eq!(tree.parents_of(child).len(), 2);
+        assert!(tree.parents_of(child).contains(&father));
+        assert!(tree.parents_of(child).contains(&mother));
+        // 親は子より上に配置される
+        assert!(tree.persons[&father].position.1 < tree.persons[&child].position.1);
+        assert!(tree.persons[&father].position.0 < tree.persons[&mother].position.0);
+    }
+
+    #[test]
+    fn test_life_span_label() {
+        let mut tree = FamilyTree::default();
+        let full = tree.add_person("Full".to_string(), Gender::Male, Some("1900-01-01".to_string()), "".to_string(), true, Some("1980-12".to_string()), (0.0, 0.0));
+        let living = tree.add_person("Living".to_string(), Gender::Female, Some("1990".to_string()), "".to_string(), false, None, (0.0, 0.0));
+        let unknown_death = tree.add_person("UnknownDeath".to_string(), Gender::Male, Some("1850".to_string()), "".to_string(), true, None, (0.0, 0.0));
+        let death_only = tree.add_person("DeathOnly".to_string(), Gender::Unknown, None, "".to_string(), true, Some("1944".to_string()), (0.0, 0.0));
+        let none = tree.add_person("None".to_string(), Gender::Unknown, None, "".to_string(), false, None, (0.0, 0.0));
+
+        assert_eq!(tree.persons[&full].life_span_label().as_deref(), Some("1900–1980"));
+        assert_eq!(tree.persons[&living].life_span_label().as_deref(), Some("1990–"));
+        assert_eq!(tree.persons[&unknown_death].life_span_label().as_deref(), Some("1850–?"));
+        assert_eq!(tree.persons[&death_only].life_span_label().as_deref(), Some("?–1944"));
+        assert_eq!(tree.persons[&none].life_span_label(), None);
+    }
+
+    #[test]
+    fn test_find_person_by_id() {
+        let mut tree = FamilyTree::default();
+        let id = tree.add_person("Taro".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.add_person("Taro".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+
+        let short = short_id(&id);
+        assert_eq!(short.len(), SHORT_ID_LENGTH);
+        assert_eq!(tree.find_person_by_id(&short), Some(id));
+        assert_eq!(tree.find_person_by_id(&format!(" {} ", short.to_uppercase())), Some(id));
+        assert_eq!(tree.find_person_by_id(&id.to_string()), Some(id));
+        assert_eq!(tree.find_person_by_id(""), None);
+        assert_eq!(tree.find_person_by_id("zzzz"), None);
+    }
+
+    #[test]
+    fn test_find_person_by_id_ignores_short_hex_like_names() {
+        let mut tree = FamilyTree::default();
+        let taro = tree.add_person("Taro".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let id = Uuid::parse_str("ada0bea0-0000-4000-8000-000000000000").unwrap();
+        let mut ada = tree.persons.remove(&taro).unwrap();
+        ada.id = id;
+        tree.persons.insert(id, ada);
+
+        assert_eq!(tree.find_person_by_id("Ada"), None);
+        assert_eq!(tree.find_person_by_id("a"), None);
+        assert_eq!(tree.find_person_by_id("ada0bea0"), Some(id));
+    }
+
     #[test]
     fn test_remove_person() {
         let mut tree = FamilyTree::default();
@@ -391,6 +2082,7 @@ mod tests {
 
         tree.add_parent_child(parent, child, "biological".to_string());
         tree.add_spouse(parent, spouse, "".to_string());
+        tree.home_person = Some(parent);
 
         tree.remove_person(parent);
 
@@ -398,6 +2090,7 @@ mod tests {
         assert!(tree.persons.get(&parent).is_none());
         assert_eq!(tree.edges.len(), 0);
         assert_eq!(tree.spouses.len(), 0);
+        assert_eq!(tree.home_person, None);
     }
 
     #[test]
@@ -467,6 +2160,124 @@ mod tests {
         assert_eq!(tree.spouses.len(), 0);
     }
 
+    #[test]
+    fn test_spouse_relation_lookup() {
+        let mut tree = FamilyTree::default();
+        let person1 = tree.add_person("Person1".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let person2 = tree.add_person("Person2".to_string(), Gender::Female, None, "".to_string(), false, None, (200.0, 0.0));
+        let person3 = tree.add_person("Person3".to_string(), Gender::Female, None, "".to_string(), false, None, (400.0, 0.0));
+
+        tree.add_spouse(person1, person2, "".to_string());
+        assert!(tree.spouse_relation(person2, person1).is_some());
+        assert!(tree.spouse_relation(person1, person3).is_none());
+
+        // 順序を入れ替えても同じ関係を更新できる
+        if let Some(spouse) = tree.spouse_relation_mut(person2, person1) {
+            spouse.marriage_date = Some("1990-06-01".to_string());
+            spouse.divorced = true;
+            spouse.divorce_date = Some("2000-01-01".to_string());
+        }
+
+        let spouse = tree.spouse_relation(person1, person2).unwrap();
+        assert_eq!(spouse.marriage_date, Some("1990-06-01".to_string()));
+        assert!(spouse.divorced);
+        assert_eq!(spouse.divorce_date, Some("2000-01-01".to_string()));
+    }
+
+    #[test]
+    fn test_step_relations_are_derived_from_spouses() {
+        let mut tree = FamilyTree::default();
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, "".to_string(), false, None, (100.0, 0.0));
+        let step_father = tree.add_person("StepFather".to_string(), Gender::Male, None, "".to_string(), false, None, (200.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 100.0));
+
+        tree.add_parent_child(mother, child, "biological".to_string());
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_spouse(mother, father, "".to_string());
+        tree.add_spouse(mother, step_father, "".to_string());
+
+        assert_eq!(tree.step_parents_of(child), vec![step_father]);
+        assert_eq!(tree.step_children_of(step_father), vec![child]);
+        assert!(tree.step_children_of(father).is_empty());
+        assert!(tree.step_parents_of(mother).is_empty());
+    }
+
+    #[test]
+    fn test_person_relations() {
+        let mut tree = FamilyTree::default();
+        let guardian = tree.add_person("Guardian".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let ward = tree.add_person("Ward".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 100.0));
+
+        tree.add_person_relation(guardian, ward, PersonRelationKind::Guardian, "".to_string());
+        tree.add_person_relation(guardian, ward, PersonRelationKind::Guardian, "dup".to_string());
+        tree.add_person_relation(guardian, ward, PersonRelationKind::Mentor, "".to_string());
+        tree.add_person_relation(ward, ward, PersonRelationKind::Godparent, "".to_string());
+        assert_eq!(tree.person_relations.len(), 2);
+        assert_eq!(tree.person_relations_of(ward).len(), 2);
+        assert!(tree.parents_of(ward).is_empty());
+
+        tree.remove_person_relation(guardian, ward, PersonRelationKind::Mentor);
+        assert_eq!(tree.person_relations.len(), 1);
+
+        tree.remove_person(guardian);
+        assert!(tree.person_relations.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_person_badge() {
+        let mut tree = FamilyTree::default();
+        let a = tree.add_person("A".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        tree.toggle_person_badge(a, PersonBadge::Clergy);
+        tree.toggle_person_badge(a, PersonBadge::Military);
+        assert_eq!(tree.persons[&a].badges, vec![PersonBadge::Military, PersonBadge::Clergy]);
+        tree.toggle_person_badge(a, PersonBadge::Military);
+        assert_eq!(tree.persons[&a].badges, vec![PersonBadge::Clergy]);
+
+        let json = serde_json::to_string(&tree.persons[&a]).unwrap();
+        assert!(json.contains(r#""badges":["clergy"]"#), "{json}");
+        assert_eq!(PersonBadge::from_key("direct_ancestor"), PersonBadge::DirectAncestor);
+    }
+
+    #[test]
+    fn test_unknown_person_badges_are_kept() {
+        let mut tree = FamilyTree::default();
+        let a = tree.add_person("A".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let mut json = serde_json::to_value(&tree.persons[&a]).unwrap();
+        json["badges"] = serde_json::json!(["clergy", "astronaut"]);
+
+        let person: Person = serde_json::from_value(json).unwrap();
+        assert_eq!(person.badges, vec![PersonBadge::Clergy, PersonBadge::Other("astronaut".to_string())]);
+        let saved = serde_json::to_string(&person).unwrap();
+        assert!(saved.contains(r#""badges":["clergy","astronaut"]"#), "{saved}");
+    }
+
+    #[test]
+    fn test_multiple_birth_groups() {
+        let mut tree = FamilyTree::default();
+        let a = tree.add_person("A".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let b = tree.add_person("B".to_string(), Gender::Male, None, "".to_string(), false, None, (100.0, 0.0));
+        let c = tree.add_person("C".to_string(), Gender::Female, None, "".to_string(), false, None, (200.0, 0.0));
+
+        tree.mark_multiple_birth(&[a]);
+        assert!(tree.multiple_birth_siblings_of(a).is_empty());
+
+        tree.mark_multiple_birth(&[a, b]);
+        assert_eq!(tree.multiple_birth_siblings_of(a), vec![b]);
+
+        // 既存グループに三人目を追加すると同じグループに統合される
+        tree.mark_multiple_birth(&[c, b]);
+        assert_eq!(tree.multiple_birth_groups().len(), 1);
+        assert_eq!(tree.multiple_birth_siblings_of(c).len(), 2);
+
+        tree.clear_multiple_birth(c);
+        assert_eq!(tree.multiple_birth_siblings_of(a), vec![b]);
+
+        // 残りが1人になるとグループは解消される
+        tree.clear_multiple_birth(a);
+        assert!(tree.multiple_birth_groups().is_empty());
+    }
+
     #[test]
     fn test_parents_of() {
         let mut tree = FamilyTree::default();
@@ -762,6 +2573,160 @@ mod tests {
         assert_eq!(relations.len(), 1);
     }
 
+    #[test]
+    fn test_add_and_remove_annotation() {
+        let mut tree = FamilyTree::default();
+        let paternal = tree.add_annotation("Paternal side".to_string(), (10.0, 20.0), 24.0, (0, 0, 128));
+        let source = tree.add_annotation("Source: parish register".to_string(), (0.0, 300.0), 12.0, (60, 60, 60));
+        assert_eq!(tree.annotations.len(), 2);
+
+        if let Some(annotation) = tree.annotation_mut(paternal) {
+            annotation.position = (50.0, 60.0);
+        }
+        assert_eq!(tree.annotation(paternal).map(|a| a.position), Some((50.0, 60.0)));
+
+        tree.remove_annotation(paternal);
+        assert!(tree.annotation(paternal).is_none());
+        assert_eq!(tree.annotations.first().map(|a| a.id), Some(source));
+    }
+
+    #[test]
+    fn test_sticky_notes() {
+        let mut tree = FamilyTree::default();
+        let aunt = tree.add_person("Aunt".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let uncle = tree.add_person("Uncle".to_string(), Gender::Male, None, "".to_string(), false, None, (100.0, 0.0));
+        let verify = tree.add_sticky_note(aunt, "Verify birth year".to_string());
+        let photo = tree.add_sticky_note(aunt, "Find wedding photo".to_string());
+        tree.add_sticky_note(uncle, "Ask about military service".to_string());
+
+        assert_eq!(tree.sticky_notes_of(aunt).len(), 2);
+        assert_eq!(tree.outstanding_sticky_notes().len(), 3);
+
+        if let Some(note) = tree.sticky_note_mut(verify) {
+            note.resolved = true;
+        }
+        assert_eq!(tree.outstanding_sticky_notes().len(), 2);
+
+        tree.remove_sticky_note(photo);
+        assert_eq!(tree.sticky_notes_of(aunt).len(), 1);
+
+        tree.remove_person(uncle);
+        assert!(tree.outstanding_sticky_notes().is_empty());
+    }
+
+    #[test]
+    fn test_layout_snapshots() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let event = tree.add_event("Wedding".to_string(), None, "".to_string(), (50.0, 50.0), (255, 255, 200));
+        let print = tree.save_layout_snapshot("print layout".to_string());
+
+        tree.persons.get_mut(&father).unwrap().position = (300.0, 300.0);
+        tree.events.get_mut(&event).unwrap().position = (400.0, 400.0);
+        let compact = tree.save_layout_snapshot("compact".to_string());
+        let son = tree.add_person("Son".to_string(), Gender::Male, None, "".to_string(), false, None, (10.0, 10.0));
+
+        assert!(tree.apply_layout_snapshot(print));
+        assert_eq!(tree.persons[&father].position, (0.0, 0.0));
+        assert_eq!(tree.events[&event].position, (50.0, 50.0));
+        // 保存後に追加した人物は動かさない
+        assert_eq!(tree.persons[&son].position, (10.0, 10.0));
+
+        // 同じ名前で保存すると上書きされる
+        assert_eq!(tree.save_layout_snapshot("compact".to_string()), compact);
+        assert_eq!(tree.layout_snapshots.len(), 2);
+        assert_eq!(tree.layout_snapshots[1].person_positions[&father], (0.0, 0.0));
+
+        // 削除した人物・イベントの位置は保存した配置からも消す
+        tree.remove_person(father);
+        tree.remove_event(event);
+        assert!(tree.layout_snapshots.iter().all(|s| !s.person_positions.contains_key(&father) && !s.event_positions.contains_key(&event)));
+        assert!(tree.layout_snapshots[1].person_positions.contains_key(&son));
+
+        tree.remove_layout_snapshot(print);
+        assert!(!tree.apply_layout_snapshot(print));
+        assert_eq!(tree.layout_snapshots.len(), 1);
+    }
+
+    #[test]
+    fn test_places_merge_and_remove() {
+        let mut tree = FamilyTree::default();
+        let person = tree.add_person("Taro".to_string(), Gender::Male, None, "".to_string(), false, None, (0.0, 0.0));
+        let event = tree.add_event("Wedding".to_string(), None, "".to_string(), (0.0, 0.0), (255, 255, 200));
+        let st_louis = tree.add_place("St. Louis".to_string());
+        let variant = tree.add_place("st louis".to_string());
+        let tokyo = tree.add_place("Tokyo".to_string());
+        if let Some(place) = tree.place_mut(variant) {
+            place.latitude = Some(38.627);
+            place.longitude = Some(-90.199);
+        }
+        tree.persons.get_mut(&person).unwrap().birth_place = Some(variant);
+        tree.persons.get_mut(&person).unwrap().death_place = Some(tokyo);
+        tree.events.get_mut(&event).unwrap().place = Some(variant);
+
+        assert_eq!(tree.duplicate_place_groups(), vec![vec![st_louis, variant]]);
+        assert_eq!(tree.place_usage_count(variant), 2);
+        assert_eq!(
+            tree.place_references(variant),
+            vec![PlaceReference::BirthPlace(person), PlaceReference::Event(event)]
+        );
+
+        tree.merge_places(st_louis, variant);
+        assert!(tree.get_place(variant).is_none());
+        assert_eq!(tree.get_place(st_louis).unwrap().latitude, Some(38.627));
+        assert_eq!(tree.persons[&person].birth_place, Some(st_louis));
+        assert_eq!(tree.events[&event].place, Some(st_louis));
+        assert!(tree.duplicate_place_groups().is_empty());
+
+        tree.remove_place(tokyo);
+        assert_eq!(tree.persons[&person].death_place, None);
+        assert_eq!(tree.places.len(), 1);
+    }
+
+    #[test]
+    fn test_camera_bookmarks() {
+        let mut tree = FamilyTree::default();
+        let paternal = tree.save_camera_bookmark("paternal branch".to_string(), 1.5, (10.0, 20.0));
+        let maternal = tree.save_camera_bookmark("maternal branch".to_string(), 0.5, (-30.0, 0.0));
+
+        // 同じ名前で保存すると位置だけ更新される
+        assert_eq!(tree.save_camera_bookmark("paternal branch".to_string(), 2.0, (0.0, 0.0)), paternal);
+        assert_eq!(tree.camera_bookmarks.len(), 2);
+        assert_eq!(tree.camera_bookmarks[0].zoom, 2.0);
+        assert_eq!(tree.camera_bookmarks[0].pan, (0.0, 0.0));
+
+        tree.remove_camera_bookmark(paternal);
+        assert_eq!(tree.camera_bookmarks.len(), 1);
+        assert_eq!(tree.camera_bookmarks[0].id, maternal);
+    }
+
+    #[test]
+    fn test_add_family_member_person_uses_family_defaults() {
+        let mut tree = FamilyTree::default();
+        let family = tree.add_family("Sato".to_string(), None);
+        let parent = tree.add_person("佐藤 太郎".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_member_to_family(family, parent);
+        tree.families[0].defaults = FamilyDefaults {
+            relation_kind: "adoptive".to_string(),
+            surname: "佐藤".to_string(),
+            label_color: Some((200, 0, 0)),
+        };
+
+        let child = tree.add_family_member_person(family, Some(parent), "花子", "biological", Gender::Female, (0.0, 100.0)).unwrap();
+        assert_eq!(tree.persons[&child].name, "佐藤 花子");
+        assert_eq!(tree.parent_child_relation(parent, child).unwrap().kind, "adoptive");
+        assert!(tree.families[0].members.contains(&child));
+        // 既に姓で始まる名前と、親のいない人物には姓を付けない
+        assert_eq!(FamilyDefaults { surname: "佐藤".to_string(), ..Default::default() }.with_surname("佐藤 次郎"), "佐藤 次郎");
+        let member = tree.add_family_member_person(family, None, "鈴木 一郎", "biological", Gender::Male, (0.0, 0.0)).unwrap();
+        assert_eq!(tree.persons[&member].name, "鈴木 一郎");
+
+        tree.families[0].defaults.relation_kind.clear();
+        let second = tree.add_family_member_person(family, Some(parent), "次郎", "biological", Gender::Male, (0.0, 0.0)).unwrap();
+        assert_eq!(tree.parent_child_relation(parent, second).unwrap().kind, "biological");
+        assert!(tree.add_family_member_person(Uuid::new_v4(), None, "x", "biological", Gender::Unknown, (0.0, 0.0)).is_none());
+    }
+
     #[test]
     fn test_event_relation_types() {
         let mut tree = FamilyTree::default();
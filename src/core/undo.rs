@@ -0,0 +1,648 @@
+//! 家系図への一括操作を、変更した項目だけ元に戻せる形で記録する。
+//!
+//! 操作の前後の家系図を比べて、人物・イベントなどの要素ごと・項目ごとの変更（変更前と変更後の値）を記録する。
+//! 元に戻すときは、今の値が変更後の値のままの項目だけを変更前の値に戻すので、
+//! 操作の後に行った別の編集（人物の追加・ノードの移動・同じ人物の別の項目の変更など）は消えない。
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::io::Write;
+
+use serde_json::{Map, Value};
+
+use crate::core::tree::FamilyTree;
+
+/// 共通の履歴に残す操作の最大数
+pub const MAX_UNDO_ENTRIES: usize = 50;
+
+/// キーから要素を引く表として保存される項目（それ以外の配列は要素の "id" で要素を特定する）
+const KEYED_MAPS: &[&str] = &["persons", "events"];
+
+/// 変更した場所
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    /// 家系図の直下の項目（背景・ノードの表示設定など）
+    Root { field: String },
+    /// ID で特定できる要素の1項目
+    Field { collection: String, key: String, field: String },
+    /// ID で特定できる要素そのもの（追加・削除）
+    Item { collection: String, key: String },
+    /// ID を持たない要素（親子関係など）。要素全体の追加・削除として扱う
+    Element { collection: String },
+}
+
+/// 1か所の変更（値がない場合は None）
+#[derive(Debug, Clone, PartialEq)]
+struct ItemChange {
+    target: Target,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl ItemChange {
+    fn inverse(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            before: self.after.clone(),
+            after: self.before.clone(),
+        }
+    }
+}
+
+/// 1回の操作による家系図の変更
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TreeChange {
+    changes: Vec<ItemChange>,
+}
+
+/// 変更を元に戻した結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertOutcome {
+    /// 実際に戻した変更をもう一度行うための変更（やり直し用）
+    pub redo: TreeChange,
+    /// 操作の後に編集されていたため戻さなかった項目の数
+    pub skipped: usize,
+}
+
+impl TreeChange {
+    /// 家系図を編集し、その編集による変更を記録する
+    #[cfg(test)]
+    pub fn record<R>(tree: &mut FamilyTree, edit: impl FnOnce(&mut FamilyTree) -> R) -> (R, TreeChange) {
+        let before = to_object(tree);
+        let (result, change, _) = Self::record_from(before, tree, edit);
+        (result, change)
+    }
+
+    /// 編集前の家系図を JSON にしたもの（before）から変更を記録し、編集後の JSON も返す
+    fn record_from<R>(
+        before: Option<Map<String, Value>>,
+        tree: &mut FamilyTree,
+        edit: impl FnOnce(&mut FamilyTree) -> R,
+    ) -> (R, TreeChange, Option<Map<String, Value>>) {
+        let result = edit(tree);
+        let after = to_object(tree);
+        let change = match (&before, &after) {
+            (Some(before), Some(after)) => Self::diff(before, after),
+            _ => Self::default(),
+        };
+        (result, change, after)
+    }
+
+    /// 何も変更していないか
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// 変更を元に戻す
+    ///
+    /// 今の値が変更後の値のままの箇所だけを戻し、後から編集された箇所はそのまま残して数を返す。
+    pub fn revert(&self, tree: &mut FamilyTree) -> RevertOutcome {
+        let unchanged = RevertOutcome {
+            redo: TreeChange::default(),
+            skipped: self.changes.len(),
+        };
+        let Some(mut root) = to_object(tree) else {
+            return unchanged;
+        };
+        let mut applied = Vec::new();
+        for change in self.changes.iter().rev() {
+            if revert_change(&mut root, change) {
+                applied.push(change.inverse());
+            }
+        }
+        let skipped = self.changes.len() - applied.len();
+        match serde_json::from_value::<FamilyTree>(Value::Object(root)) {
+            Ok(reverted) => {
+                *tree = reverted;
+                // 一部だけ戻した親子関係でも家族の単位と食い違わないようにする
+                tree.sync_unions();
+                applied.reverse();
+                RevertOutcome {
+                    redo: TreeChange { changes: applied },
+                    skipped,
+                }
+            }
+            Err(_) => unchanged,
+        }
+    }
+
+    fn diff(before: &Map<String, Value>, after: &Map<String, Value>) -> TreeChange {
+        let mut changes = Vec::new();
+        for name in union_keys(before, after) {
+            let (old, new) = (before.get(name), after.get(name));
+            if old == new {
+                continue;
+            }
+            let any_array = old.is_some_and(Value::is_array) || new.is_some_and(Value::is_array);
+            let array_or_missing = |value: Option<&Value>| value.is_none_or(Value::is_array);
+            if KEYED_MAPS.contains(&name.as_str()) {
+                diff_keyed(name, &map_entries(old), &map_entries(new), &mut changes);
+            } else if any_array && array_or_missing(old) && array_or_missing(new) {
+                let (old, new) = (array_items(old), array_items(new));
+                if old.iter().chain(&new).all(|item| item.get("id").is_some()) {
+                    diff_keyed(name, &id_entries(&old), &id_entries(&new), &mut changes);
+                } else {
+                    diff_elements(name, &old, &new, &mut changes);
+                }
+            } else {
+                changes.push(ItemChange {
+                    target: Target::Root { field: name.clone() },
+                    before: old.cloned(),
+                    after: new.cloned(),
+                });
+            }
+        }
+        TreeChange { changes }
+    }
+}
+
+/// 書き込まれたバイト列をそのままハッシュ値に足す（JSON の文字列を作らずに内容のハッシュ値を求めるため）
+struct HashWriter(DefaultHasher);
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 家系図の内容のハッシュ値（前回の操作の後から編集されていないかを、JSON の値を作り直さずに確かめる）
+fn content_hash(tree: &FamilyTree) -> Option<u64> {
+    let mut writer = HashWriter(DefaultHasher::new());
+    serde_json::to_writer(&mut writer, tree).ok()?;
+    Some(writer.0.finish())
+}
+
+fn to_object(tree: &FamilyTree) -> Option<Map<String, Value>> {
+    match serde_json::to_value(tree) {
+        Ok(Value::Object(map)) => Some(map),
+        _ => None,
+    }
+}
+
+/// 両方のキーを、前の順・後にだけあるものの順で並べる
+fn union_keys<'a>(before: &'a Map<String, Value>, after: &'a Map<String, Value>) -> Vec<&'a String> {
+    before.keys().chain(after.keys().filter(|key| !before.contains_key(*key))).collect()
+}
+
+fn map_entries(value: Option<&Value>) -> Vec<(String, &Value)> {
+    match value {
+        Some(Value::Object(map)) => map.iter().map(|(key, value)| (key.clone(), value)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn array_items(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn id_entries<'a>(items: &[&'a Value]) -> Vec<(String, &'a Value)> {
+    items.iter().map(|item| (id_key(item), *item)).collect()
+}
+
+fn id_key(item: &Value) -> String {
+    match item.get("id") {
+        Some(Value::String(id)) => id.clone(),
+        Some(id) => id.to_string(),
+        None => String::new(),
+    }
+}
+
+fn diff_keyed(collection: &str, before: &[(String, &Value)], after: &[(String, &Value)], changes: &mut Vec<ItemChange>) {
+    let after_items: HashMap<&str, &Value> = after.iter().map(|(key, value)| (key.as_str(), *value)).collect();
+    let before_items: HashMap<&str, &Value> = before.iter().map(|(key, value)| (key.as_str(), *value)).collect();
+    let item_change = |key: &str, before: Option<&Value>, after: Option<&Value>| ItemChange {
+        target: Target::Item { collection: collection.to_string(), key: key.to_string() },
+        before: before.cloned(),
+        after: after.cloned(),
+    };
+
+    for (key, old) in before {
+        match after_items.get(key.as_str()) {
+            None => changes.push(item_change(key, Some(old), None)),
+            Some(new) if old == new => {}
+            Some(new) => match (old, new) {
+                (Value::Object(old_fields), Value::Object(new_fields)) => {
+                    for field in union_keys(old_fields, new_fields) {
+                        let (old_value, new_value) = (old_fields.get(field), new_fields.get(field));
+                        if old_value != new_value {
+                            changes.push(ItemChange {
+                                target: Target::Field {
+                                    collection: collection.to_string(),
+                                    key: key.clone(),
+                                    field: field.clone(),
+                                },
+                                before: old_value.cloned(),
+                                after: new_value.cloned(),
+                            });
+                        }
+                    }
+                }
+                _ => changes.push(item_change(key, Some(old), Some(new))),
+            },
+        }
+    }
+    for (key, new) in after {
+        if !before_items.contains_key(key.as_str()) {
+            changes.push(item_change(key, None, Some(new)));
+        }
+    }
+}
+
+/// ID を持たない要素は、同じ内容の要素の数の増減を追加・削除として記録する
+fn diff_elements(collection: &str, before: &[&Value], after: &[&Value], changes: &mut Vec<ItemChange>) {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for item in after {
+        *remaining.entry(item.to_string()).or_default() += 1;
+    }
+    let mut removed = Vec::new();
+    for item in before {
+        match remaining.get_mut(&item.to_string()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => removed.push(*item),
+        }
+    }
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    for item in before {
+        *unmatched.entry(item.to_string()).or_default() += 1;
+    }
+    let element_change = |before: Option<&Value>, after: Option<&Value>| ItemChange {
+        target: Target::Element { collection: collection.to_string() },
+        before: before.cloned(),
+        after: after.cloned(),
+    };
+    changes.extend(removed.into_iter().map(|item| element_change(Some(item), None)));
+    for item in after {
+        match unmatched.get_mut(&item.to_string()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => changes.push(element_change(None, Some(item))),
+        }
+    }
+}
+
+/// 1か所の変更を戻す。今の値が変更後の値でなければ戻さずに false を返す
+fn revert_change(root: &mut Map<String, Value>, change: &ItemChange) -> bool {
+    match &change.target {
+        Target::Root { field } => {
+            if root.get(field) != change.after.as_ref() {
+                return false;
+            }
+            set_or_remove(root, field, change.before.clone());
+            true
+        }
+        Target::Field { collection, key, field } => {
+            let Some(Value::Object(item)) = keyed_item_mut(root, collection, key) else {
+                return false;
+            };
+            if item.get(field) != change.after.as_ref() {
+                return false;
+            }
+            set_or_remove(item, field, change.before.clone());
+            true
+        }
+        Target::Item { collection, key } => {
+            let current = keyed_item_mut(root, collection, key);
+            if current.as_deref() != change.after.as_ref() {
+                return false;
+            }
+            match (current, change.before.clone()) {
+                (Some(item), Some(before)) => *item = before,
+                (None, Some(before)) => insert_keyed_item(root, collection, key, before),
+                (Some(_), None) => remove_keyed_item(root, collection, key),
+                (None, None) => {}
+            }
+            true
+        }
+        Target::Element { collection } => {
+            let Value::Array(items) = root.entry(collection.clone()).or_insert_with(|| Value::Array(Vec::new())) else {
+                return false;
+            };
+            if let Some(after) = &change.after {
+                // 追加した要素を取り除く（すでに消されていれば戻せない）
+                let Some(index) = items.iter().position(|item| item == after) else {
+                    return false;
+                };
+                items.remove(index);
+            }
+            if let Some(before) = &change.before {
+                // 削除した要素を戻す（同じ要素が後から追加されていれば重ねない）
+                if !items.contains(before) {
+                    items.push(before.clone());
+                }
+            }
+            true
+        }
+    }
+}
+
+fn set_or_remove(map: &mut Map<String, Value>, field: &str, value: Option<Value>) {
+    match value {
+        Some(value) => {
+            map.insert(field.to_string(), value);
+        }
+        None => {
+            map.remove(field);
+        }
+    }
+}
+
+fn keyed_item_mut<'a>(root: &'a mut Map<String, Value>, collection: &str, key: &str) -> Option<&'a mut Value> {
+    match root.get_mut(collection)? {
+        Value::Object(map) => map.get_mut(key),
+        Value::Array(items) => items.iter_mut().find(|item| id_key(item) == key),
+        _ => None,
+    }
+}
+
+fn insert_keyed_item(root: &mut Map<String, Value>, collection: &str, key: &str, item: Value) {
+    let empty = if KEYED_MAPS.contains(&collection) { Value::Object(Map::new()) } else { Value::Array(Vec::new()) };
+    match root.entry(collection.to_string()).or_insert(empty) {
+        Value::Object(map) => {
+            map.insert(key.to_string(), item);
+        }
+        Value::Array(items) => items.push(item),
+        _ => {}
+    }
+}
+
+fn remove_keyed_item(root: &mut Map<String, Value>, collection: &str, key: &str) {
+    match root.get_mut(collection) {
+        Some(Value::Object(map)) => {
+            map.remove(key);
+        }
+        Some(Value::Array(items)) => items.retain(|item| id_key(item) != key),
+        _ => {}
+    }
+}
+
+/// 履歴の操作を特定するID
+pub type UndoId = u64;
+
+/// 履歴に残した1回の操作
+#[derive(Debug, Clone)]
+struct UndoEntry {
+    id: UndoId,
+    label: String,
+    change: TreeChange,
+}
+
+/// 元に戻した・やり直した操作の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoResult {
+    /// 操作の名前
+    pub label: String,
+    /// 後から編集されていたため戻さなかった項目の数
+    pub skipped: usize,
+}
+
+/// 一括操作の共通の履歴（元に戻す・やり直す）
+///
+/// どのダイアログの操作も同じ履歴に積む。履歴は家系図ごとなので、別の家系図を開いたら消す。
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    next_id: UndoId,
+    /// 直前の操作の後の家系図の JSON とそのハッシュ値（続けて操作するときに編集前の JSON として使い回す）
+    snapshot: Option<(u64, Map<String, Value>)>,
+}
+
+impl UndoHistory {
+    /// 操作を履歴に積み、そのIDを返す（何も変更していなければ積まずに None）
+    pub fn push(&mut self, label: String, change: TreeChange) -> Option<UndoId> {
+        if change.is_empty() {
+            return None;
+        }
+        self.next_id += 1;
+        let id = self.next_id;
+        self.undo.push(UndoEntry { id, label, change });
+        if self.undo.len() > MAX_UNDO_ENTRIES {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        Some(id)
+    }
+
+    /// 家系図を編集し、その変更を履歴に積む
+    ///
+    /// 前回の操作の後に家系図が編集されていなければ、そのとき作った JSON を編集前の内容として使い、
+    /// 家系図全体を JSON にするのを1回で済ませる。
+    pub fn record<R>(&mut self, tree: &mut FamilyTree, label: String, edit: impl FnOnce(&mut FamilyTree) -> R) -> (R, Option<UndoId>) {
+        let before = match (self.snapshot.take(), content_hash(tree)) {
+            (Some((hash, snapshot)), Some(current)) if hash == current => Some(snapshot),
+            _ => to_object(tree),
+        };
+        let (result, change, after) = TreeChange::record_from(before, tree, edit);
+        self.snapshot = after.zip(content_hash(tree)).map(|(after, hash)| (hash, after));
+        (result, self.push(label, change))
+    }
+
+    /// 次に元に戻す操作の名前
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|entry| entry.label.as_str())
+    }
+
+    /// 次にやり直す操作の名前
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|entry| entry.label.as_str())
+    }
+
+    /// 操作がまだ元に戻せるか
+    pub fn contains(&self, id: UndoId) -> bool {
+        self.undo.iter().any(|entry| entry.id == id)
+    }
+
+    /// 直前の操作を元に戻す
+    pub fn undo(&mut self, tree: &mut FamilyTree) -> Option<UndoResult> {
+        let id = self.undo.last()?.id;
+        self.undo_entry(id, tree)
+    }
+
+    /// 指定した操作を元に戻す（後の操作と重ならない項目だけを戻すので、直前の操作でなくてもよい）
+    pub fn undo_entry(&mut self, id: UndoId, tree: &mut FamilyTree) -> Option<UndoResult> {
+        let index = self.undo.iter().position(|entry| entry.id == id)?;
+        let entry = self.undo.remove(index);
+        let outcome = entry.change.revert(tree);
+        if !outcome.redo.is_empty() {
+            self.redo.push(UndoEntry {
+                id: entry.id,
+                label: entry.label.clone(),
+                change: outcome.redo,
+            });
+        }
+        Some(UndoResult { label: entry.label, skipped: outcome.skipped })
+    }
+
+    /// 元に戻した操作をやり直す
+    pub fn redo(&mut self, tree: &mut FamilyTree) -> Option<UndoResult> {
+        let entry = self.redo.pop()?;
+        let outcome = entry.change.revert(tree);
+        if !outcome.redo.is_empty() {
+            self.undo.push(UndoEntry {
+                id: entry.id,
+                label: entry.label.clone(),
+                change: outcome.redo,
+            });
+        }
+        Some(UndoResult { label: entry.label, skipped: outcome.skipped })
+    }
+
+    /// 履歴をすべて消す（別の家系図を開いたとき）
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.snapshot = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    fn add(tree: &mut FamilyTree, name: &str) -> crate::core::tree::PersonId {
+        tree.add_person(name.to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0))
+    }
+
+    #[test]
+    fn test_revert_keeps_later_unrelated_edits() {
+        let mut tree = FamilyTree::default();
+        let taro = add(&mut tree, "Taro");
+        let hanako = add(&mut tree, "Hanako");
+
+        let (_, change) = TreeChange::record(&mut tree, |tree| {
+            tree.persons.get_mut(&taro).unwrap().name = "Taro Yamada".to_string();
+            tree.persons.get_mut(&hanako).unwrap().memo = "replaced".to_string();
+        });
+        assert_eq!(change.changes.len(), 2);
+
+        // 操作の後の編集：人物の追加・移動・同じ人物の別の項目
+        let jiro = add(&mut tree, "Jiro");
+        tree.persons.get_mut(&taro).unwrap().position = (300.0, 40.0);
+        tree.persons.get_mut(&hanako).unwrap().birth = Some("1950".to_string());
+
+        let outcome = change.revert(&mut tree);
+        assert_eq!(outcome.skipped, 0);
+        assert_eq!(tree.persons[&taro].name, "Taro");
+        assert_eq!(tree.persons[&taro].position, (300.0, 40.0));
+        assert_eq!(tree.persons[&hanako].memo, "");
+        assert_eq!(tree.persons[&hanako].birth.as_deref(), Some("1950"));
+        assert!(tree.persons.contains_key(&jiro));
+
+        // やり直すと操作の結果に戻る
+        outcome.redo.revert(&mut tree);
+        assert_eq!(tree.persons[&taro].name, "Taro Yamada");
+        assert_eq!(tree.persons[&hanako].memo, "replaced");
+    }
+
+    #[test]
+    fn test_revert_skips_fields_edited_afterwards() {
+        let mut tree = FamilyTree::default();
+        let taro = add(&mut tree, "Taro");
+        let hanako = add(&mut tree, "Hanako");
+        let (_, change) = TreeChange::record(&mut tree, |tree| {
+            for person in tree.persons.values_mut() {
+                person.deceased = true;
+            }
+        });
+        tree.persons.get_mut(&taro).unwrap().deceased = false;
+        tree.persons.get_mut(&hanako).unwrap().name = "Hanako Sato".to_string();
+
+        let outcome = change.revert(&mut tree);
+        // Taro は後から手で戻されているので、そのまま（戻す必要もない）
+        assert_eq!(outcome.skipped, 1);
+        assert!(!tree.persons[&hanako].deceased);
+        assert_eq!(tree.persons[&hanako].name, "Hanako Sato");
+    }
+
+    #[test]
+    fn test_revert_relations_and_added_items() {
+        let mut tree = FamilyTree::default();
+        let parent = add(&mut tree, "Parent");
+        let child = add(&mut tree, "Child");
+        let other = add(&mut tree, "Other");
+        tree.add_parent_child(parent, child, "biological".to_string());
+        let family = tree.add_family("Main".to_string(), None);
+
+        let (added, change) = TreeChange::record(&mut tree, |tree| {
+            tree.edges.clear();
+            tree.add_spouse(parent, other, String::new());
+            tree.add_member_to_family(family, child);
+            add(tree, "Added")
+        });
+        // 後から別の親子関係を追加しても残る
+        tree.add_parent_child(other, child, "adoptive".to_string());
+
+        change.revert(&mut tree);
+        assert!(tree.parent_child_relation(parent, child).is_some());
+        assert!(tree.parent_child_relation(other, child).is_some());
+        assert!(tree.spouses.is_empty());
+        assert!(!tree.persons.contains_key(&added));
+        assert!(tree.get_family(family).is_some_and(|family| family.members.is_empty()));
+    }
+
+    #[test]
+    fn test_history_reuses_snapshot_only_while_tree_is_unchanged() {
+        let mut tree = FamilyTree::default();
+        let taro = add(&mut tree, "Taro");
+        let mut history = UndoHistory::default();
+
+        history.record(&mut tree, "first".to_string(), |tree| tree.persons.get_mut(&taro).unwrap().memo = "a".to_string());
+        // 記録の外での編集は、次の操作の変更に含めない
+        tree.persons.get_mut(&taro).unwrap().name = "Taro Yamada".to_string();
+        history.record(&mut tree, "second".to_string(), |tree| tree.persons.get_mut(&taro).unwrap().memo = "b".to_string());
+        history.record(&mut tree, "third".to_string(), |tree| tree.persons.get_mut(&taro).unwrap().memo = "c".to_string());
+
+        history.undo(&mut tree);
+        assert_eq!(tree.persons[&taro].memo, "b");
+        history.undo(&mut tree);
+        assert_eq!(tree.persons[&taro].memo, "a");
+        assert_eq!(tree.persons[&taro].name, "Taro Yamada");
+        history.undo(&mut tree);
+        assert_eq!(tree.persons[&taro].memo, "");
+        assert_eq!(tree.persons[&taro].name, "Taro Yamada");
+    }
+
+    #[test]
+    fn test_history_undo_entries_by_id() {
+        let mut tree = FamilyTree::default();
+        let taro = add(&mut tree, "Taro");
+        let mut history = UndoHistory::default();
+
+        let (_, first) = history.record(&mut tree, "rename".to_string(), |tree| {
+            tree.persons.get_mut(&taro).unwrap().name = "Taro Yamada".to_string();
+        });
+        let (_, second) = history.record(&mut tree, "memo".to_string(), |tree| {
+            tree.persons.get_mut(&taro).unwrap().memo = "memo".to_string();
+        });
+        let (_, nothing) = history.record(&mut tree, "nothing".to_string(), |_| {});
+        assert_eq!(nothing, None);
+        assert_eq!(history.undo_label(), Some("memo"));
+
+        // 前の操作だけを戻しても、後の操作は残る
+        let result = history.undo_entry(first.unwrap(), &mut tree).unwrap();
+        assert_eq!(result, UndoResult { label: "rename".to_string(), skipped: 0 });
+        assert_eq!(tree.persons[&taro].name, "Taro");
+        assert_eq!(tree.persons[&taro].memo, "memo");
+        assert!(!history.contains(first.unwrap()));
+        assert!(history.contains(second.unwrap()));
+
+        assert_eq!(history.redo_label(), Some("rename"));
+        history.redo(&mut tree);
+        assert_eq!(tree.persons[&taro].name, "Taro Yamada");
+        history.undo(&mut tree);
+        assert_eq!(tree.persons[&taro].name, "Taro");
+
+        history.clear();
+        assert_eq!(history.undo_label(), None);
+        assert_eq!(history.redo_label(), None);
+    }
+}
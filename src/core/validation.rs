@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+
+use uuid::Uuid;
+
+use crate::core::tree::{FamilyTree, PersonId};
+
+/// 家系図データの整合性チェックで検出された問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// 養子縁組の年月日が子の生年月日より前になっている
+    AdoptionBeforeBirth { parent: PersonId, child: PersonId },
+    /// 多胎（双子など）のきょうだいの生年月日が一致しない
+    MultipleBirthDateMismatch { group: Uuid },
+}
+
+/// 家系図全体の整合性をチェックし、検出された問題を返す
+pub fn validate_tree(tree: &FamilyTree) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for edge in tree.edges.iter().filter(|e| e.is_adoptive()) {
+        let Some(adoption_date) = edge.adoption_date.as_deref() else {
+            continue;
+        };
+        let Some(birth) = tree
+            .persons
+            .get(&edge.child)
+            .and_then(|child| child.birth.as_deref())
+        else {
+            continue;
+        };
+        if compare_dates(adoption_date, birth) == Some(Ordering::Less) {
+            issues.push(ValidationIssue::AdoptionBeforeBirth {
+                parent: edge.parent,
+                child: edge.child,
+            });
+        }
+    }
+
+    for (group, members) in tree.multiple_birth_groups() {
+        let births: Vec<&str> = members
+            .iter()
+            .filter_map(|id| tree.persons.get(id).and_then(|p| p.birth.as_deref()))
+            .filter(|birth| !birth.trim().is_empty())
+            .collect();
+        // 精度の低い日付を挟むと隣どうしは一致してしまうため、すべての組み合わせを比べる
+        let mismatch = births.iter().enumerate().any(|(index, a)| {
+            births[index + 1..]
+                .iter()
+                .any(|b| compare_dates(a, b).is_some_and(|o| o != Ordering::Equal))
+        });
+        if mismatch {
+            issues.push(ValidationIssue::MultipleBirthDateMismatch { group });
+        }
+    }
+
+    issues
+}
+
+/// "YYYY", "YYYY-MM", "YYYY-MM-DD" 形式の日付を年・月・日に分解する
+pub fn parse_date_parts(date: &str) -> Option<(i32, Option<u32>, Option<u32>)> {
+    let mut parts = date.trim().split('-');
+    let year = parts.next()?.trim().parse::<i32>().ok()?;
+    let month = parts.next().and_then(|m| m.trim().parse::<u32>().ok());
+    let day = month.and(parts.next()).and_then(|d| d.trim().parse::<u32>().ok());
+    Some((year, month, day))
+}
+
+/// 2つの日付を、両方に含まれる精度（年・月・日）までで比較する
+/// 解析できない場合は None を返す
+pub fn compare_dates(a: &str, b: &str) -> Option<Ordering> {
+    let (year_a, month_a, day_a) = parse_date_parts(a)?;
+    let (year_b, month_b, day_b) = parse_date_parts(b)?;
+
+    let ordering = year_a.cmp(&year_b);
+    if ordering != Ordering::Equal {
+        return Some(ordering);
+    }
+    let (Some(month_a), Some(month_b)) = (month_a, month_b) else {
+        return Some(Ordering::Equal);
+    };
+    let ordering = month_a.cmp(&month_b);
+    if ordering != Ordering::Equal {
+        return Some(ordering);
+    }
+    match (day_a, day_b) {
+        (Some(day_a), Some(day_b)) => Some(day_a.cmp(&day_b)),
+        _ => Some(Ordering::Equal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_compare_dates_with_partial_precision() {
+        assert_eq!(compare_dates("2000-01-02", "2000-01-03"), Some(Ordering::Less));
+        assert_eq!(compare_dates("2001", "2000-12-31"), Some(Ordering::Greater));
+        assert_eq!(compare_dates("2000", "2000-05-01"), Some(Ordering::Equal));
+        assert_eq!(compare_dates("2000-06", "2000-05-31"), Some(Ordering::Greater));
+        assert_eq!(compare_dates("unknown", "2000"), None);
+    }
+
+    #[test]
+    fn test_validate_adoption_before_birth() {
+        let mut tree = FamilyTree::default();
+        let parent = tree.add_person("Parent".to_string(), Gender::Female, None, "".to_string(), false, None, (0.0, 0.0));
+        let child = tree.add_person(
+            "Child".to_string(),
+            Gender::Male,
+            Some("2010-04-01".to_string()),
+            "".to_string(),
+            false,
+            None,
+            (0.0, 100.0),
+        );
+        tree.add_parent_child(parent, child, "adoptive".to_string());
+        assert!(validate_tree(&tree).is_empty());
+
+        if let Some(edge) = tree.parent_child_relation_mut(parent, child) {
+            edge.adoption_date = Some("2009-12-24".to_string());
+        }
+        assert_eq!(
+            validate_tree(&tree),
+            vec![ValidationIssue::AdoptionBeforeBirth { parent, child }]
+        );
+
+        if let Some(edge) = tree.parent_child_relation_mut(parent, child) {
+            edge.adoption_date = Some("2012-08-15".to_string());
+        }
+        assert!(validate_tree(&tree).is_empty());
+    }
+
+    #[test]
+    fn test_validate_multiple_birth_dates() {
+        let mut tree = FamilyTree::default();
+        let first = tree.add_person(
+            "First".to_string(),
+            Gender::Female,
+            Some("2015-03-10".to_string()),
+            "".to_string(),
+            false,
+            None,
+            (0.0, 0.0),
+        );
+        let second = tree.add_person(
+            "Second".to_string(),
+            Gender::Female,
+            Some("2015-03-10".to_string()),
+            "".to_string(),
+            false,
+            None,
+            (100.0, 0.0),
+        );
+        tree.mark_multiple_birth(&[first, second]);
+        assert!(validate_tree(&tree).is_empty());
+
+        if let Some(person) = tree.persons.get_mut(&second) {
+            person.birth = Some("2015-03-11".to_string());
+        }
+        let group = tree.persons[&first].multiple_birth_group.expect("group should be set");
+        assert_eq!(
+            validate_tree(&tree),
+            vec![ValidationIssue::MultipleBirthDateMismatch { group }]
+        );
+    }
+
+    #[test]
+    fn test_validate_multiple_birth_dates_with_partial_date_between() {
+        let mut tree = FamilyTree::default();
+        let ids: Vec<PersonId> = ["2015-03-10", "2015", "2015-03-11"]
+            .iter()
+            .enumerate()
+            .map(|(index, birth)| {
+                tree.add_person(
+                    format!("Child {index}"),
+                    Gender::Male,
+                    Some(birth.to_string()),
+                    "".to_string(),
+                    false,
+                    None,
+                    (index as f32 * 100.0, 0.0),
+                )
+            })
+            .collect();
+        tree.mark_multiple_birth(&ids);
+
+        // 年だけの日付はどちらとも一致するが、両端の日付は一致しない
+        let group = tree.persons[&ids[0]].multiple_birth_group.expect("group should be set");
+        assert_eq!(
+            validate_tree(&tree),
+            vec![ValidationIssue::MultipleBirthDateMismatch { group }]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// 操作履歴ファイル名（1行に1件の JSON）
+const ACTIVITY_LOG_FILE_NAME: &str = "activity_log.jsonl";
+
+/// セッションをまたいで保持する操作履歴の1件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityLogRecord {
+    /// "YYYY-MM-DD HH:MM:SS"
+    pub timestamp: String,
+    pub level: String,
+    pub message: String,
+}
+
+/// 読み込む前に書き込んだ場合に保持する操作履歴の件数
+const DEFAULT_KEEP_RECORDS: usize = 100;
+
+/// 操作履歴を JSON Lines 形式で保存・読み込みする
+///
+/// ファイルは保持する件数の2倍に達するたびに新しいものだけに切り詰め、長いセッションでも大きくならないようにする。
+#[derive(Debug, Clone)]
+pub struct ActivityLogStore {
+    path: PathBuf,
+    /// 保持する件数
+    keep: usize,
+    /// ファイルにある件数（読み込むまでは書き込んだ件数）
+    records: usize,
+}
+
+impl ActivityLogStore {
+    pub fn in_dir(dir: &Path) -> Self {
+        Self::new(dir.join(ACTIVITY_LOG_FILE_NAME))
+    }
+
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, keep: DEFAULT_KEEP_RECORDS, records: 0 }
+    }
+
+    pub fn append(&mut self, record: &ActivityLogRecord) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{line}")?;
+        self.records += 1;
+        if self.records >= self.keep.saturating_mul(2) {
+            self.load_recent(self.keep)?;
+        }
+        Ok(())
+    }
+
+    /// 新しいものから最大 limit 件を読み込み、ファイルもその件数に切り詰める（以後は limit 件を保持する）
+    pub fn load_recent(&mut self, limit: usize) -> std::io::Result<Vec<ActivityLogRecord>> {
+        self.keep = limit.max(1);
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                self.records = 0;
+                return Ok(Vec::new());
+            }
+            Err(error) => return Err(error),
+        };
+
+        // 壊れた行は読み飛ばす
+        let records: Vec<ActivityLogRecord> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let skip = records.len().saturating_sub(limit);
+        let recent = records[skip..].to_vec();
+
+        if skip > 0 {
+            let mut trimmed = String::new();
+            for record in &recent {
+                trimmed.push_str(&serde_json::to_string(record).map_err(std::io::Error::other)?);
+                trimmed.push('\n');
+            }
+            fs::write(&self.path, trimmed)?;
+        }
+        self.records = recent.len();
+        Ok(recent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use uuid::Uuid;
+
+    fn record(message: &str) -> ActivityLogRecord {
+        ActivityLogRecord {
+            timestamp: "2024-01-02 03:04:05".to_string(),
+            level: "INFO".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_load_recent_keeps_newest_records_and_trims_file() {
+        let path = env::temp_dir().join(format!("family_tree_activity_{}.jsonl", Uuid::new_v4()));
+        let mut store = ActivityLogStore::new(path.clone());
+        assert!(store.load_recent(10).unwrap().is_empty());
+
+        for message in ["one", "two", "three"] {
+            store.append(&record(message)).unwrap();
+        }
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "not json\n").unwrap();
+
+        let recent = store.load_recent(2).unwrap();
+        assert_eq!(recent, vec![record("two"), record("three")]);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_append_keeps_the_file_capped() {
+        let path = env::temp_dir().join(format!("family_tree_activity_{}.jsonl", Uuid::new_v4()));
+        let mut store = ActivityLogStore::new(path.clone());
+        store.load_recent(3).unwrap();
+
+        for index in 0..20 {
+            store.append(&record(&index.to_string())).unwrap();
+            assert!(fs::read_to_string(&path).unwrap().lines().count() < 6);
+        }
+        assert_eq!(store.load_recent(3).unwrap(), vec![record("17"), record("18"), record("19")]);
+
+        let _ = fs::remove_file(path);
+    }
+}
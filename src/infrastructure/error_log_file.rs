@@ -0,0 +1,104 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// エラーログファイル名
+const ERROR_LOG_FILE_NAME: &str = "errors.log";
+/// ローテーションするファイルサイズの上限
+const DEFAULT_MAX_BYTES: u64 = 512 * 1024;
+/// 残しておく過去のログファイル数（errors.1.log 〜）
+const DEFAULT_MAX_BACKUPS: usize = 3;
+
+/// エラー・警告を追記するローテーション付きのログファイル
+#[derive(Debug, Clone)]
+pub struct ErrorLogFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_backups: usize,
+}
+
+impl ErrorLogFile {
+    /// 設定ディレクトリ内の errors.log を使う
+    pub fn in_dir(dir: &Path) -> Self {
+        Self::with_limits(dir.join(ERROR_LOG_FILE_NAME), DEFAULT_MAX_BYTES, DEFAULT_MAX_BACKUPS)
+    }
+
+    pub fn with_limits(path: PathBuf, max_bytes: u64, max_backups: usize) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_backups,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 1行追記する（上限サイズを超えていれば先にローテーションする）
+    pub fn append(&self, line: &str) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(&self.path).is_ok_and(|metadata| metadata.len() >= self.max_bytes) {
+            self.rotate()?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+
+    fn backup_path(&self, index: usize) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.path.with_file_name(format!("{stem}.{index}.log"))
+    }
+
+    /// errors.log → errors.1.log → errors.2.log … と古いものを順に送る
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_backups == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        let oldest = self.backup_path(self.max_backups);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (1..self.max_backups).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.backup_path(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_append_rotates_when_file_exceeds_limit() {
+        let dir = env::temp_dir().join(format!("family_tree_error_log_{}", Uuid::new_v4()));
+        let log = ErrorLogFile::with_limits(dir.join("errors.log"), 10, 2);
+
+        log.append("first line").unwrap();
+        log.append("second line").unwrap();
+        log.append("third line").unwrap();
+        log.append("fourth line").unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join("errors.log")).unwrap(), "fourth line\n");
+        assert_eq!(fs::read_to_string(dir.join("errors.1.log")).unwrap(), "third line\n");
+        assert_eq!(fs::read_to_string(dir.join("errors.2.log")).unwrap(), "second line\n");
+        // 上限を超えた最も古いログは削除される
+        assert!(!dir.join("errors.3.log").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,606 @@
+//! GEDCOM の読み込みで、出力元アプリごとの癖（方言）を吸収する下位層。
+//!
+//! 文字コードの判別（BOM・`1 CHAR`・ANSEL/Windows-1252）、`CONC`/`CONT` による行の継続、
+//! インデントや空行などの書式の揺れ、Ancestry / FamilySearch / MyHeritage 独自のタグを扱い、
+//! 対応していないタグは `Person::gedcom_extras` に保存できる行として取り出す。
+
+use std::borrow::Cow;
+
+/// 1行の値の最大文字数（これを超える値は `CONC` で分割する）
+const MAX_VALUE_CHARS: usize = 200;
+
+/// GEDCOM ファイルの文字コード
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GedcomEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Ansel,
+    Windows1252,
+}
+
+/// GEDCOM を出力したアプリ（`HEAD` の `SOUR`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GedcomDialect {
+    Standard,
+    Ancestry,
+    FamilySearch,
+    MyHeritage,
+}
+
+/// GEDCOM の1レコード（行とその下位の行）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GedcomRecord {
+    pub level: usize,
+    pub xref: Option<String>,
+    pub tag: String,
+    /// `CONC`/`CONT` を結合した値（`CONT` は改行になる）
+    pub value: String,
+    pub children: Vec<GedcomRecord>,
+}
+
+impl GedcomRecord {
+    pub fn child(&self, tag: &str) -> Option<&GedcomRecord> {
+        self.children.iter().find(|child| child.tag == tag)
+    }
+
+    pub fn children_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a GedcomRecord> {
+        self.children.iter().filter(move |child| child.tag == tag)
+    }
+
+    /// 子レコードの値（空なら None）
+    pub fn child_value(&self, tag: &str) -> Option<&str> {
+        self.child(tag).map(|child| child.value.as_str()).filter(|value| !value.is_empty())
+    }
+
+    /// `@I1@` のような他のレコードへの参照か
+    pub fn is_pointer(&self) -> bool {
+        self.value.len() > 2 && self.value.starts_with('@') && self.value.ends_with('@')
+    }
+
+    /// 指定した階層の GEDCOM の行に戻す（改行は `CONT`、長い値は `CONC` で分割する）
+    pub fn to_lines(&self, level: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        let xref = self.xref.as_ref().map(|xref| format!(" {xref}")).unwrap_or_default();
+        for (index, segment) in self.value.split('\n').enumerate() {
+            for (chunk_index, chunk) in split_value(segment).into_iter().enumerate() {
+                let head = match (index, chunk_index) {
+                    (0, 0) => format!("{level}{xref} {}", self.tag),
+                    (_, 0) => format!("{} CONT", level + 1),
+                    _ => format!("{} CONC", level + 1),
+                };
+                lines.push(if chunk.is_empty() { head } else { format!("{head} {chunk}") });
+            }
+        }
+        for child in &self.children {
+            lines.extend(child.to_lines(level + 1));
+        }
+        lines
+    }
+}
+
+/// 値を `CONC` で分割する（分割位置の前後が空白にならないようにする）
+fn split_value(value: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = value;
+    while rest.chars().count() > MAX_VALUE_CHARS {
+        let mut boundaries: Vec<usize> = rest.char_indices().map(|(index, _)| index).take(MAX_VALUE_CHARS + 1).collect();
+        let mut split = boundaries.pop().unwrap_or(rest.len());
+        // 空白の直前・直後で切ると、末尾の空白を削る読み込み側で失われる
+        while split > 0 && (rest[..split].ends_with(' ') || rest[split..].starts_with(' ')) {
+            split = boundaries.pop().unwrap_or(0);
+        }
+        if split == 0 {
+            break;
+        }
+        chunks.push(&rest[..split]);
+        rest = &rest[split..];
+    }
+    chunks.push(rest);
+    chunks
+}
+
+// ---- 文字コード ----
+
+/// バイト列の文字コードを判別して文字列にする
+///
+/// BOM を最優先し、UTF-8 として正しければ（`1 CHAR ANSEL` と宣言されていても）UTF-8 とみなす。
+/// それ以外は宣言に従い ANSEL、または Windows-1252 として読む。
+pub fn decode_gedcom(bytes: &[u8]) -> (String, GedcomEncoding) {
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return (String::from_utf8_lossy(rest).into_owned(), GedcomEncoding::Utf8);
+    }
+    let utf16_le = bytes.strip_prefix(b"\xFF\xFE").or_else(|| (bytes.get(1) == Some(&0)).then_some(bytes));
+    if let Some(rest) = utf16_le {
+        return (decode_utf16(rest, u16::from_le_bytes), GedcomEncoding::Utf16Le);
+    }
+    let utf16_be = bytes.strip_prefix(b"\xFE\xFF").or_else(|| (bytes.first() == Some(&0)).then_some(bytes));
+    if let Some(rest) = utf16_be {
+        return (decode_utf16(rest, u16::from_be_bytes), GedcomEncoding::Utf16Be);
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return (text.to_string(), GedcomEncoding::Utf8);
+    }
+
+    if declared_charset(bytes).is_some_and(|charset| charset.eq_ignore_ascii_case("ANSEL")) {
+        (decode_ansel(bytes), GedcomEncoding::Ansel)
+    } else {
+        (decode_windows_1252(bytes), GedcomEncoding::Windows1252)
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| to_unit([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// `1 CHAR` で宣言された文字コード名
+fn declared_charset(bytes: &[u8]) -> Option<String> {
+    bytes
+        .split(|byte| *byte == b'\n' || *byte == b'\r')
+        .map(|line| String::from_utf8_lossy(line).trim().to_string())
+        .find_map(|line| line.strip_prefix("1 CHAR ").map(|charset| charset.trim().to_string()))
+}
+
+/// Windows-1252 の 0x80〜0x9F（それ以外は Latin-1 と同じ）
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{FFFD}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{FFFD}', 'Ž', '\u{FFFD}',
+    '\u{FFFD}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{FFFD}', 'ž', 'Ÿ',
+];
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+            _ => byte as char,
+        })
+        .collect()
+}
+
+/// ANSEL の文字（結合文字以外）
+fn ansel_spacing_char(byte: u8) -> Option<char> {
+    Some(match byte {
+        0xA1 => 'Ł',
+        0xA2 => 'Ø',
+        0xA3 => 'Đ',
+        0xA4 => 'Þ',
+        0xA5 => 'Æ',
+        0xA6 => 'Œ',
+        0xA7 => 'ʹ',
+        0xA8 => '·',
+        0xA9 => '♭',
+        0xAA => '®',
+        0xAB => '±',
+        0xAC => 'Ơ',
+        0xAD => 'Ư',
+        0xAE => 'ʼ',
+        0xB0 => 'ʻ',
+        0xB1 => 'ł',
+        0xB2 => 'ø',
+        0xB3 => 'đ',
+        0xB4 => 'þ',
+        0xB5 => 'æ',
+        0xB6 => 'œ',
+        0xB7 => 'ʺ',
+        0xB8 => 'ı',
+        0xB9 => '£',
+        0xBA => 'ð',
+        0xBC => 'ơ',
+        0xBD => 'ư',
+        0xC0 => '°',
+        0xC1 => 'ℓ',
+        0xC2 => '℗',
+        0xC3 => '©',
+        0xC4 => '♯',
+        0xC5 => '¿',
+        0xC6 => '¡',
+        0xCF => 'ß',
+        _ => return None,
+    })
+}
+
+/// ANSEL の結合文字（基底文字の前に置かれる）に対応する Unicode の結合文字
+fn ansel_combining_mark(byte: u8) -> Option<char> {
+    Some(match byte {
+        0xE0 => '\u{0309}',
+        0xE1 => '\u{0300}',
+        0xE2 => '\u{0301}',
+        0xE3 => '\u{0302}',
+        0xE4 => '\u{0303}',
+        0xE5 => '\u{0304}',
+        0xE6 => '\u{0306}',
+        0xE7 => '\u{0307}',
+        0xE8 => '\u{0308}',
+        0xE9 => '\u{030C}',
+        0xEA => '\u{030A}',
+        0xEB => '\u{FE20}',
+        0xEC => '\u{FE21}',
+        0xED => '\u{0315}',
+        0xEE => '\u{030B}',
+        0xEF => '\u{0310}',
+        0xF0 => '\u{0327}',
+        0xF1 => '\u{0328}',
+        0xF2 => '\u{0323}',
+        0xF3 => '\u{0324}',
+        0xF4 => '\u{0325}',
+        0xF5 => '\u{0333}',
+        0xF6 => '\u{0332}',
+        0xF7 => '\u{0326}',
+        0xF8 => '\u{031C}',
+        0xF9 => '\u{032E}',
+        0xFA => '\u{FE22}',
+        0xFB => '\u{FE23}',
+        0xFE => '\u{0313}',
+        _ => return None,
+    })
+}
+
+/// よく使う「基底文字 + 結合文字」の合成済み文字
+const PRECOMPOSED: &[(char, char, char)] = &[
+    ('A', '\u{0300}', 'À'), ('A', '\u{0301}', 'Á'), ('A', '\u{0302}', 'Â'), ('A', '\u{0303}', 'Ã'),
+    ('A', '\u{0308}', 'Ä'), ('A', '\u{030A}', 'Å'), ('A', '\u{030C}', 'Ǎ'), ('a', '\u{0300}', 'à'),
+    ('a', '\u{0301}', 'á'), ('a', '\u{0302}', 'â'), ('a', '\u{0303}', 'ã'), ('a', '\u{0308}', 'ä'),
+    ('a', '\u{030A}', 'å'), ('a', '\u{030C}', 'ǎ'), ('E', '\u{0300}', 'È'), ('E', '\u{0301}', 'É'),
+    ('E', '\u{0302}', 'Ê'), ('E', '\u{0303}', 'Ẽ'), ('E', '\u{0308}', 'Ë'), ('E', '\u{0327}', 'Ȩ'),
+    ('E', '\u{030C}', 'Ě'), ('e', '\u{0300}', 'è'), ('e', '\u{0301}', 'é'), ('e', '\u{0302}', 'ê'),
+    ('e', '\u{0303}', 'ẽ'), ('e', '\u{0308}', 'ë'), ('e', '\u{0327}', 'ȩ'), ('e', '\u{030C}', 'ě'),
+    ('I', '\u{0300}', 'Ì'), ('I', '\u{0301}', 'Í'), ('I', '\u{0302}', 'Î'), ('I', '\u{0303}', 'Ĩ'),
+    ('I', '\u{0308}', 'Ï'), ('I', '\u{030C}', 'Ǐ'), ('i', '\u{0300}', 'ì'), ('i', '\u{0301}', 'í'),
+    ('i', '\u{0302}', 'î'), ('i', '\u{0303}', 'ĩ'), ('i', '\u{0308}', 'ï'), ('i', '\u{030C}', 'ǐ'),
+    ('O', '\u{0300}', 'Ò'), ('O', '\u{0301}', 'Ó'), ('O', '\u{0302}', 'Ô'), ('O', '\u{0303}', 'Õ'),
+    ('O', '\u{0308}', 'Ö'), ('O', '\u{030C}', 'Ǒ'), ('o', '\u{0300}', 'ò'), ('o', '\u{0301}', 'ó'),
+    ('o', '\u{0302}', 'ô'), ('o', '\u{0303}', 'õ'), ('o', '\u{0308}', 'ö'), ('o', '\u{030C}', 'ǒ'),
+    ('U', '\u{0300}', 'Ù'), ('U', '\u{0301}', 'Ú'), ('U', '\u{0302}', 'Û'), ('U', '\u{0303}', 'Ũ'),
+    ('U', '\u{0308}', 'Ü'), ('U', '\u{030A}', 'Ů'), ('U', '\u{030C}', 'Ǔ'), ('u', '\u{0300}', 'ù'),
+    ('u', '\u{0301}', 'ú'), ('u', '\u{0302}', 'û'), ('u', '\u{0303}', 'ũ'), ('u', '\u{0308}', 'ü'),
+    ('u', '\u{030A}', 'ů'), ('u', '\u{030C}', 'ǔ'), ('Y', '\u{0300}', 'Ỳ'), ('Y', '\u{0301}', 'Ý'),
+    ('Y', '\u{0302}', 'Ŷ'), ('Y', '\u{0303}', 'Ỹ'), ('Y', '\u{0308}', 'Ÿ'), ('y', '\u{0300}', 'ỳ'),
+    ('y', '\u{0301}', 'ý'), ('y', '\u{0302}', 'ŷ'), ('y', '\u{0303}', 'ỹ'), ('y', '\u{0308}', 'ÿ'),
+    ('y', '\u{030A}', 'ẙ'), ('C', '\u{0301}', 'Ć'), ('C', '\u{0302}', 'Ĉ'), ('C', '\u{0327}', 'Ç'),
+    ('C', '\u{030C}', 'Č'), ('c', '\u{0301}', 'ć'), ('c', '\u{0302}', 'ĉ'), ('c', '\u{0327}', 'ç'),
+    ('c', '\u{030C}', 'č'), ('N', '\u{0300}', 'Ǹ'), ('N', '\u{0301}', 'Ń'), ('N', '\u{0303}', 'Ñ'),
+    ('N', '\u{0327}', 'Ņ'), ('N', '\u{030C}', 'Ň'), ('n', '\u{0300}', 'ǹ'), ('n', '\u{0301}', 'ń'),
+    ('n', '\u{0303}', 'ñ'), ('n', '\u{0327}', 'ņ'), ('n', '\u{030C}', 'ň'), ('S', '\u{0301}', 'Ś'),
+    ('S', '\u{0302}', 'Ŝ'), ('S', '\u{0327}', 'Ş'), ('S', '\u{030C}', 'Š'), ('s', '\u{0301}', 'ś'),
+    ('s', '\u{0302}', 'ŝ'), ('s', '\u{0327}', 'ş'), ('s', '\u{030C}', 'š'), ('Z', '\u{0301}', 'Ź'),
+    ('Z', '\u{0302}', 'Ẑ'), ('Z', '\u{030C}', 'Ž'), ('z', '\u{0301}', 'ź'), ('z', '\u{0302}', 'ẑ'),
+    ('z', '\u{030C}', 'ž'),
+];
+
+fn decode_ansel(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let mut marks: Vec<char> = Vec::new();
+    for &byte in bytes {
+        if let Some(mark) = ansel_combining_mark(byte) {
+            marks.push(mark);
+            continue;
+        }
+        let base = match byte {
+            0x00..=0x7F => byte as char,
+            _ => ansel_spacing_char(byte).unwrap_or('\u{FFFD}'),
+        };
+        // 合成済み文字があれば使い、なければ Unicode の順（基底文字 → 結合文字）に並べ替える
+        let mut base = base;
+        let mut remaining = Vec::new();
+        for mark in marks.drain(..) {
+            match PRECOMPOSED.iter().find(|(b, m, _)| *b == base && *m == mark) {
+                Some((_, _, composed)) if remaining.is_empty() => base = *composed,
+                _ => remaining.push(mark),
+            }
+        }
+        text.push(base);
+        text.extend(remaining);
+    }
+    text.extend(marks);
+    text
+}
+
+// ---- 行の解析 ----
+
+/// `レベル [@xref@] タグ [値]` の1行を分解する
+fn parse_line(line: &str) -> Option<(usize, Option<String>, String, String)> {
+    let (level, rest) = line.split_once(' ').unwrap_or((line, ""));
+    if level.is_empty() || level.len() > 2 || !level.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let level = level.parse().ok()?;
+    let rest = rest.trim_start_matches(' ');
+
+    let (xref, rest) = match rest.split_once(' ') {
+        Some((xref, rest)) if xref.len() > 2 && xref.starts_with('@') && xref.ends_with('@') => {
+            (Some(xref.to_string()), rest.trim_start_matches(' '))
+        }
+        _ => (None, rest),
+    };
+    let (tag, value) = rest.split_once(' ').unwrap_or((rest, ""));
+    if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let tag = tag.to_ascii_uppercase();
+    // CONC の先頭の空白は値の一部（単語の途中で分割されている）
+    let value = if tag == "CONC" || tag == "CONT" { value } else { value.trim() };
+    Some((level, xref, tag, value.to_string()))
+}
+
+/// 行頭の空白・空行・CRのみの改行・レベルの飛び・改行を含む値などの揺れを許容して解析する
+pub fn parse_gedcom(text: &str) -> Vec<GedcomRecord> {
+    fn close_to(stack: &mut Vec<GedcomRecord>, roots: &mut Vec<GedcomRecord>, level: usize) {
+        while stack.last().is_some_and(|top| top.level >= level) {
+            let record = stack.pop().expect("stack is not empty");
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(record),
+                None => roots.push(record),
+            }
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<GedcomRecord> = Vec::new();
+    for raw in text.split(['\n', '\r']) {
+        let line = raw.trim_start_matches([' ', '\t', '\u{FEFF}']);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((level, xref, tag, value)) = parse_line(line) else {
+            // 改行をそのまま書き出すアプリ向け：直前の値の続きとみなす
+            if let Some(last) = stack.last_mut() {
+                last.value.push('\n');
+                last.value.push_str(raw.trim_end());
+            }
+            continue;
+        };
+
+        if tag == "CONC" || tag == "CONT" {
+            close_to(&mut stack, &mut roots, level);
+            if let Some(parent) = stack.last_mut() {
+                if tag == "CONT" {
+                    parent.value.push('\n');
+                }
+                parent.value.push_str(&value);
+            }
+            continue;
+        }
+
+        close_to(&mut stack, &mut roots, level);
+        stack.push(GedcomRecord {
+            level,
+            xref,
+            tag,
+            value,
+            children: Vec::new(),
+        });
+    }
+    close_to(&mut stack, &mut roots, 0);
+    roots
+}
+
+// ---- 方言の正規化 ----
+
+/// `HEAD` の `SOUR` から出力元アプリを判別する
+pub fn detect_dialect(records: &[GedcomRecord]) -> GedcomDialect {
+    let source = records
+        .iter()
+        .find(|record| record.tag == "HEAD")
+        .and_then(|head| head.child("SOUR"))
+        .map(|source| {
+            let name = source.child_value("NAME").unwrap_or_default();
+            format!("{} {}", source.value, name).to_ascii_uppercase()
+        })
+        .unwrap_or_default();
+
+    if source.contains("ANCESTRY") || source.contains("FTM") || source.contains("FAMILY TREE MAKER") {
+        GedcomDialect::Ancestry
+    } else if source.contains("FAMILYSEARCH") || source.contains("FAMILY SEARCH") {
+        GedcomDialect::FamilySearch
+    } else if source.contains("MYHERITAGE") {
+        GedcomDialect::MyHeritage
+    } else {
+        GedcomDialect::Standard
+    }
+}
+
+/// 方言ごとの書き方を標準的な形にそろえる
+///
+/// - 日付の修飾語（`Abt.`・`circa` など）と月名を標準の大文字の表記にする
+/// - `NAME` が空で `GIVN`/`SURN` だけある名前を `名 /姓/` の形にする
+/// - `PEDI` と Ancestry の `_FREL`/`_MREL` の値を `birth`/`adopted`/`foster` にそろえる
+/// - MyHeritage の `_PRIM Y`（代表写真）が付いた `OBJE` を先頭に並べる
+pub fn normalize_dialect(records: &mut [GedcomRecord], dialect: GedcomDialect) {
+    for record in records {
+        normalize_record(record, dialect);
+    }
+}
+
+fn normalize_record(record: &mut GedcomRecord, dialect: GedcomDialect) {
+    match record.tag.as_str() {
+        "DATE" => record.value = normalize_date(&record.value),
+        "NAME" if record.value.is_empty() => {
+            let given = record.child_value("GIVN").unwrap_or_default();
+            let surname = record.child_value("SURN").unwrap_or_default();
+            record.value = match (given, surname) {
+                ("", "") => String::new(),
+                (given, "") => given.to_string(),
+                (given, surname) => format!("{given} /{surname}/").trim().to_string(),
+            };
+        }
+        "PEDI" | "_FREL" | "_MREL" => record.value = normalize_pedigree(&record.value).into_owned(),
+        _ => {}
+    }
+
+    if dialect == GedcomDialect::MyHeritage {
+        let is_primary = |child: &GedcomRecord| child.child_value("_PRIM").is_some_and(|value| value.eq_ignore_ascii_case("Y"));
+        let slots: Vec<usize> = (0..record.children.len()).filter(|&index| record.children[index].tag == "OBJE").collect();
+        let mut objects: Vec<GedcomRecord> = slots.iter().map(|&index| record.children[index].clone()).collect();
+        objects.sort_by_key(|object| !is_primary(object));
+        for (slot, object) in slots.into_iter().zip(objects) {
+            record.children[slot] = object;
+        }
+    }
+
+    for child in &mut record.children {
+        normalize_record(child, dialect);
+    }
+}
+
+pub(crate) const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+fn normalize_date(date: &str) -> String {
+    date.split_whitespace()
+        .map(|token| {
+            let word = token.trim_end_matches('.').to_ascii_uppercase();
+            match word.as_str() {
+                "ABT" | "ABOUT" | "CIRCA" | "CA" | "C" | "CIR" => "ABT".to_string(),
+                "BEF" | "BEFORE" => "BEF".to_string(),
+                "AFT" | "AFTER" => "AFT".to_string(),
+                "EST" | "ESTIMATED" => "EST".to_string(),
+                "CAL" | "CALCULATED" => "CAL".to_string(),
+                "BET" | "BETWEEN" => "BET".to_string(),
+                _ => MONTHS
+                    .iter()
+                    .find(|month| word.len() >= 3 && month.starts_with(&word[..3]) && month_name_matches(&word))
+                    .map(|month| month.to_string())
+                    .unwrap_or_else(|| token.to_string()),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "JAN" / "JANUARY" のような月名か
+fn month_name_matches(word: &str) -> bool {
+    const FULL: [&str; 12] = [
+        "JANUARY", "FEBRUARY", "MARCH", "APRIL", "MAY", "JUNE", "JULY", "AUGUST", "SEPTEMBER", "OCTOBER", "NOVEMBER", "DECEMBER",
+    ];
+    FULL.iter().any(|full| full.starts_with(word)) || word == "SEPT"
+}
+
+fn normalize_pedigree(value: &str) -> Cow<'static, str> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "natural" | "biological" | "birth" => Cow::Borrowed("birth"),
+        "adopted" => Cow::Borrowed("adopted"),
+        "foster" => Cow::Borrowed("foster"),
+        "sealing" => Cow::Borrowed("sealing"),
+        other => Cow::Owned(other.to_string()),
+    }
+}
+
+// ---- 対応していないタグの保存 ----
+
+/// 取り込めなかったタグを、後で書き出せるように GEDCOM の行として取り出す
+///
+/// 他のレコードへの参照（`@S1@` など）は書き出し時に ID が変わるため保存しない。
+pub fn extras_lines(children: &[GedcomRecord], known_tags: &[&str], level: usize) -> Vec<String> {
+    children
+        .iter()
+        .filter(|child| !known_tags.contains(&child.tag.as_str()))
+        .filter_map(without_pointers)
+        .flat_map(|child| child.to_lines(level))
+        .collect()
+}
+
+fn without_pointers(record: &GedcomRecord) -> Option<GedcomRecord> {
+    if record.is_pointer() {
+        return None;
+    }
+    Some(GedcomRecord {
+        children: record.children.iter().filter_map(without_pointers).collect(),
+        ..record.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_by_bom_declaration_and_content() {
+        let ansel = b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME J\xE8urgen /M\xB2ller/\n";
+        let (text, encoding) = decode_gedcom(ansel);
+        assert_eq!(encoding, GedcomEncoding::Ansel);
+        assert!(text.contains("Jürgen /Møller/"));
+
+        let ansi = b"0 HEAD\n1 CHAR ANSI\n1 NOTE \x93quoted\x94 caf\xE9\n";
+        let (text, encoding) = decode_gedcom(ansi);
+        assert_eq!(encoding, GedcomEncoding::Windows1252);
+        assert!(text.contains("“quoted” café"));
+
+        // ANSEL と宣言していても実際は UTF-8 のファイル
+        let (text, encoding) = decode_gedcom("0 HEAD\n1 CHAR ANSEL\n1 NOTE 山田\n".as_bytes());
+        assert_eq!(encoding, GedcomEncoding::Utf8);
+        assert!(text.contains("山田"));
+
+        let utf16: Vec<u8> = b"\xFF\xFE".iter().copied().chain("0 HEAD".encode_utf16().flat_map(u16::to_le_bytes)).collect();
+        assert_eq!(decode_gedcom(&utf16), ("0 HEAD".to_string(), GedcomEncoding::Utf16Le));
+    }
+
+    #[test]
+    fn parses_continuations_and_loose_formatting() {
+        let text = "0 HEAD\r\n\r\n0 @I1@ indi\r\n   1 NAME Taro /Yamada/\r\n1 NOTE First li\r\n2 CONC ne\r\n2 CONT Second line\r\nraw third line\r\n1 SEX M\r0 TRLR";
+        let records = parse_gedcom(text);
+        assert_eq!(records.len(), 3);
+
+        let person = &records[1];
+        assert_eq!(person.tag, "INDI");
+        assert_eq!(person.xref.as_deref(), Some("@I1@"));
+        assert_eq!(person.child_value("NAME"), Some("Taro /Yamada/"));
+        assert_eq!(person.child_value("NOTE"), Some("First line\nSecond line\nraw third line"));
+        assert_eq!(person.child_value("SEX"), Some("M"));
+    }
+
+    #[test]
+    fn normalizes_vendor_quirks() {
+        let text = "\
+0 HEAD
+1 SOUR MYHERITAGE
+0 @I1@ INDI
+1 NAME
+2 GIVN Taro
+2 SURN Yamada
+1 BIRT
+2 DATE Abt. Jan 1900
+1 OBJE
+2 FILE other.jpg
+1 OBJE
+2 FILE primary.jpg
+2 _PRIM Y
+0 @F1@ FAM
+1 CHIL @I1@
+2 _FREL Natural
+2 _MREL Adopted
+";
+        let mut records = parse_gedcom(text);
+        let dialect = detect_dialect(&records);
+        assert_eq!(dialect, GedcomDialect::MyHeritage);
+        normalize_dialect(&mut records, dialect);
+
+        let person = &records[1];
+        assert_eq!(person.child_value("NAME"), Some("Taro /Yamada/"));
+        assert_eq!(person.child("BIRT").and_then(|birth| birth.child_value("DATE")), Some("ABT JAN 1900"));
+        assert_eq!(person.child("OBJE").and_then(|object| object.child_value("FILE")), Some("primary.jpg"));
+
+        let child = records[2].child("CHIL").unwrap();
+        assert_eq!(child.child_value("_FREL"), Some("birth"));
+        assert_eq!(child.child_value("_MREL"), Some("adopted"));
+    }
+
+    #[test]
+    fn preserves_unknown_tags_as_lines_that_parse_back() {
+        let long_note = format!("{} end", "word ".repeat(80));
+        let text = format!(
+            "0 @I1@ INDI\n1 NAME Taro\n1 _UID 1234\n1 OCCU Farmer\n2 PLAC Kyoto\n2 SOUR @S1@\n1 SOUR @S2@\n1 _STORY {}\n2 CONT next\n",
+            long_note
+        );
+        let records = parse_gedcom(&text);
+        let lines = extras_lines(&records[0].children, &["NAME"], 1);
+
+        assert!(lines.iter().all(|line| line.len() < 255));
+        assert!(!lines.iter().any(|line| line.contains("@S")));
+
+        let reparsed = parse_gedcom(&format!("0 @I1@ INDI\n{}", lines.join("\n")));
+        let tags: Vec<&str> = reparsed[0].children.iter().map(|child| child.tag.as_str()).collect();
+        assert_eq!(tags, vec!["_UID", "OCCU", "_STORY"]);
+        assert_eq!(reparsed[0].child("OCCU").unwrap().child_value("PLAC"), Some("Kyoto"));
+        assert_eq!(reparsed[0].child_value("_STORY"), Some(format!("{long_note}\nnext").as_str()));
+    }
+}
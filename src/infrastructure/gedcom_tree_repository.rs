@@ -0,0 +1,869 @@
+use std::collections::HashMap;
+use std::fs;
+
+use uuid::Uuid;
+
+use crate::application::{TreeRepository, TreeRepositoryError};
+use crate::core::layout::{LayoutDirection, LayoutEngine};
+use crate::core::tree::{
+    EventId, EventRelationType, FamilyTree, Gender, NameParts, ParentChild, PersonId, PlaceId, ADOPTIVE_KIND, CURRENT_FORMAT_VERSION,
+};
+
+use super::gedcom_dialect::{decode_gedcom, detect_dialect, extras_lines, normalize_dialect, parse_gedcom, GedcomRecord, MONTHS};
+
+/// 書き出す GEDCOM のバージョン
+const GEDCOM_VERSION: &str = "5.5.1";
+/// 読み込んだ人物を世代ごとに並べるときの間隔
+const IMPORT_X_SPACING: f32 = 160.0;
+const IMPORT_Y_SPACING: f32 = 140.0;
+/// 読み込んだイベントを並べるときの縦の間隔
+const IMPORT_EVENT_SPACING: f32 = 60.0;
+/// 読み込んだイベントの色
+const IMPORT_EVENT_COLOR: (u8, u8, u8) = (255, 255, 200);
+/// 里親の親子関係の種類
+const FOSTER_KIND: &str = "foster";
+
+/// 人物のイベントとして読み書きするタグとイベント名（それ以外のイベントは `EVEN` と `TYPE` で書く）
+const INDIVIDUAL_EVENTS: &[(&str, &str)] = &[
+    ("BAPM", "Baptism"),
+    ("CHR", "Christening"),
+    ("CONF", "Confirmation"),
+    ("GRAD", "Graduation"),
+    ("EMIG", "Emigration"),
+    ("IMMI", "Immigration"),
+    ("NATU", "Naturalization"),
+    ("RESI", "Residence"),
+    ("CENS", "Census"),
+    ("RETI", "Retirement"),
+    ("BURI", "Burial"),
+    ("CREM", "Cremation"),
+];
+
+/// 夫婦のイベントとして読み込むタグとイベント名（結婚・離婚は配偶者関係の日付にする）
+const FAMILY_EVENTS: &[(&str, &str)] = &[("ENGA", "Engagement"), ("MARB", "Marriage Banns"), ("DIVF", "Divorce Filed")];
+/// 相手の分からない家族では配偶者関係を作れないので、結婚・離婚もイベントとして読み込む
+const SINGLE_PARTNER_FAMILY_EVENTS: &[(&str, &str)] = &[
+    ("ENGA", "Engagement"),
+    ("MARB", "Marriage Banns"),
+    ("DIVF", "Divorce Filed"),
+    ("MARR", "Marriage"),
+    ("DIV", "Divorce"),
+];
+
+/// 人物のレコードで読み込む（`gedcom_extras` に残さない）タグ
+const KNOWN_INDIVIDUAL_TAGS: &[&str] = &[
+    "NAME", "SEX", "_GENDER", "BIRT", "DEAT", "NOTE", "OBJE", "FAMC", "FAMS", "EVEN", "CHAN", "BAPM", "CHR", "CONF",
+    "GRAD", "EMIG", "IMMI", "NATU", "RESI", "CENS", "RETI", "BURI", "CREM",
+];
+
+/// GEDCOM 5.5.1（.ged）で家系図を保存・読込するリポジトリ実装。
+///
+/// 人物（INDI）・家族（FAM）・イベント・ノート・写真（OBJE）を対応付ける。
+/// 家族はこのアプリの配偶者関係と親子関係に、出生・死亡・結婚・離婚はそれぞれの日付欄に変換し、
+/// それ以外のイベントはキャンバス上のイベントにする。出力元アプリごとの癖は `gedcom_dialect` で吸収し、
+/// 対応していないタグは人物の `gedcom_extras` に残して書き出し時に戻す。
+pub struct GedcomTreeRepository;
+
+impl TreeRepository for GedcomTreeRepository {
+    fn load(&self, file_path: &str) -> Result<FamilyTree, TreeRepositoryError> {
+        let bytes = fs::read(file_path).map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+        let (text, _) = decode_gedcom(&bytes);
+        tree_from_gedcom(&text)
+    }
+
+    fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
+        fs::write(file_path, tree_to_gedcom(tree)).map_err(|error| TreeRepositoryError::Write(error.to_string()))
+    }
+}
+
+// ---- 日付 ----
+
+fn is_year(text: &str) -> bool {
+    (3..=4).contains(&text.len()) && text.chars().all(|c| c.is_ascii_digit())
+}
+
+fn month_number(month: &str) -> Option<usize> {
+    MONTHS.iter().position(|name| *name == month).map(|index| index + 1)
+}
+
+/// GEDCOM の日付を "YYYY" / "YYYY-MM" / "YYYY-MM-DD" にする（"ABT 1900" などはそのまま、日付の語句は括弧を外す）
+fn date_from_gedcom(date: &str) -> String {
+    if let Some(phrase) = date.strip_prefix('(').and_then(|date| date.strip_suffix(')')) {
+        return phrase.to_string();
+    }
+    let tokens: Vec<&str> = date.split_whitespace().collect();
+    match tokens.as_slice() {
+        [year] if is_year(year) => year.to_string(),
+        [month, year] if is_year(year) => match month_number(month) {
+            Some(month) => format!("{year}-{month:02}"),
+            None => date.to_string(),
+        },
+        [day, month, year] if is_year(year) => match (day.parse::<u32>(), month_number(month)) {
+            (Ok(day), Some(month)) if (1..=31).contains(&day) => format!("{year}-{month:02}-{day:02}"),
+            _ => date.to_string(),
+        },
+        _ => date.to_string(),
+    }
+}
+
+/// "YYYY" / "YYYY-MM" / "YYYY-MM-DD" を GEDCOM の日付にする
+///
+/// "ABT 1900" のように GEDCOM の修飾語で始まるものはそのまま、それ以外の書き方は日付の語句として括弧で囲む。
+fn date_to_gedcom(date: &str) -> String {
+    let date = date.trim();
+    let parts: Vec<&str> = date.split('-').collect();
+    let numeric = parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    let month = |month: &str| month.parse::<usize>().ok().filter(|month| (1..=12).contains(month)).map(|month| MONTHS[month - 1]);
+    match (numeric, parts.as_slice()) {
+        (true, [year]) if year.len() == 4 => return year.to_string(),
+        (true, [year, month_text]) if year.len() == 4 => {
+            if let Some(month) = month(month_text) {
+                return format!("{month} {year}");
+            }
+        }
+        (true, [year, month_text, day]) if year.len() == 4 => {
+            if let (Some(month), Ok(day)) = (month(month_text), day.parse::<u32>()) {
+                return format!("{day} {month} {year}");
+            }
+        }
+        _ => {}
+    }
+    let qualifier = date.split_whitespace().next().unwrap_or_default();
+    if ["ABT", "BEF", "AFT", "BET", "FROM", "TO", "EST", "CAL", "INT"].contains(&qualifier) {
+        date.to_string()
+    } else {
+        format!("({date})")
+    }
+}
+
+// ---- GEDCOM → FamilyTree ----
+
+/// 読み込んだイベントの出所（同じ出所のものは1つのイベントにまとめる）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ImportedEventKey {
+    /// 書き出したときに付けたイベントの ID（`_UID`）。複数の人物につながったイベントをまとめ直す
+    Uid(String),
+    /// イベントを書いたレコードの xref とその中での位置（家族のイベントは夫婦で共有する）
+    Record(String, usize),
+}
+
+/// 読み込んだイベント
+#[derive(Debug, Clone)]
+struct ImportedEvent {
+    key: ImportedEventKey,
+    name: String,
+    date: Option<String>,
+    description: String,
+    place: Option<String>,
+}
+
+/// `名 /姓/` の区切りを外した名前
+fn person_name(record: &GedcomRecord) -> String {
+    let name = record.child_value("NAME").unwrap_or_default().replace('/', " ");
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn gender_from_gedcom(record: &GedcomRecord) -> Gender {
+    match record.child_value("SEX").map(str::to_ascii_uppercase).as_deref() {
+        Some("M") => Gender::Male,
+        Some("F") => Gender::Female,
+        Some("X") => Gender::Other(record.child_value("_GENDER").unwrap_or_default().to_string()),
+        _ => match record.child_value("_GENDER") {
+            Some(label) => Gender::Other(label.to_string()),
+            None => Gender::Unknown,
+        },
+    }
+}
+
+/// イベントのタグならイベント名を返す（`EVEN` は `TYPE` の値）
+fn event_name(record: &GedcomRecord, tags: &[(&str, &'static str)]) -> Option<String> {
+    if record.tag == "EVEN" {
+        return Some(record.child_value("TYPE").unwrap_or("Event").to_string());
+    }
+    tags.iter().find(|(tag, _)| *tag == record.tag).map(|(_, name)| name.to_string())
+}
+
+fn kind_from_pedigree(pedigree: Option<&str>) -> &'static str {
+    match pedigree {
+        Some("adopted") => ADOPTIVE_KIND,
+        Some("foster") => FOSTER_KIND,
+        _ => "biological",
+    }
+}
+
+/// 同じ名前の場所があればそれを、なければ地名辞典に追加して返す
+fn place_named(tree: &mut FamilyTree, name: &str) -> PlaceId {
+    match tree.places.iter().find(|place| place.name == name) {
+        Some(place) => place.id,
+        None => tree.add_place(name.to_string()),
+    }
+}
+
+/// GEDCOM の文字列から家系図を作る。
+pub fn tree_from_gedcom(text: &str) -> Result<FamilyTree, TreeRepositoryError> {
+    let mut records = parse_gedcom(text);
+    if !records.iter().any(|record| record.tag == "HEAD") {
+        return Err(TreeRepositoryError::Deserialize("missing GEDCOM HEAD record".to_string()));
+    }
+    let dialect = detect_dialect(&records);
+    normalize_dialect(&mut records, dialect);
+
+    let top_level = |tag: &'static str| records.iter().filter(move |record| record.tag == tag);
+    let notes: HashMap<&str, &str> = top_level("NOTE")
+        .filter_map(|note| Some((note.xref.as_deref()?, note.value.as_str())))
+        .collect();
+    let objects: HashMap<&str, &str> = top_level("OBJE")
+        .filter_map(|object| Some((object.xref.as_deref()?, object.child_value("FILE")?)))
+        .collect();
+    // ノートはレコード内に書かれたものと、NOTE レコードへの参照の両方がある
+    let note_text = |record: &GedcomRecord| {
+        record
+            .children_with_tag("NOTE")
+            .filter_map(|note| if note.is_pointer() { notes.get(note.value.as_str()).copied() } else { Some(note.value.as_str()) })
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+    let imported_event = |owner: &str, index: usize, record: &GedcomRecord, name: String| ImportedEvent {
+        key: match record.child_value("_UID") {
+            Some(uid) => ImportedEventKey::Uid(uid.to_string()),
+            None => ImportedEventKey::Record(owner.to_string(), index),
+        },
+        name,
+        date: record.child_value("DATE").map(date_from_gedcom),
+        description: note_text(record),
+        place: record.child_value("PLAC").map(str::to_string),
+    };
+
+    let mut tree = FamilyTree::default();
+    let mut person_ids: HashMap<&str, PersonId> = HashMap::new();
+    let mut event_links: Vec<(ImportedEvent, PersonId)> = Vec::new();
+    // 子と家族の組ごとの親子関係の種類（INDI の FAMC の下の PEDI）
+    let mut pedigrees: HashMap<(&str, &str), &str> = HashMap::new();
+
+    for record in top_level("INDI") {
+        let Some(xref) = record.xref.as_deref() else { continue };
+        let birth = record.child("BIRT");
+        let death = record.child("DEAT");
+        let deceased = death.is_some() || record.child("BURI").is_some() || record.child("CREM").is_some();
+        let mut id = tree.add_person(
+            person_name(record),
+            gender_from_gedcom(record),
+            birth.and_then(|birth| birth.child_value("DATE")).map(date_from_gedcom),
+            note_text(record),
+            deceased,
+            death.and_then(|death| death.child_value("DATE")).map(date_from_gedcom),
+            (0.0, 0.0),
+        );
+        // 書き出したときの人物 ID（`_UID`）があれば使い、他の家系図からのリンクや上書き確認で同じ人物と分かるようにする
+        let uid = record
+            .child_value("_UID")
+            .and_then(|uid| Uuid::parse_str(uid).ok())
+            .filter(|uid| !tree.persons.contains_key(uid));
+        if let Some(uid) = uid
+            && let Some(mut person) = tree.persons.remove(&id)
+        {
+            person.id = uid;
+            tree.persons.insert(uid, person);
+            id = uid;
+        }
+
+        let birth_place = birth.and_then(|birth| birth.child_value("PLAC")).map(|name| place_named(&mut tree, name));
+        let death_place = death.and_then(|death| death.child_value("PLAC")).map(|name| place_named(&mut tree, name));
+        let photo = record.children_with_tag("OBJE").find_map(|object| {
+            if object.is_pointer() { objects.get(object.value.as_str()).copied() } else { object.child_value("FILE") }
+        });
+        if let Some(person) = tree.persons.get_mut(&id) {
+            person.birth_place = birth_place;
+            person.death_place = death_place;
+            if let Some(photo) = photo {
+                person.photo_path = Some(photo.to_string());
+            }
+            // 他のアプリの UUID 以外の `_UID` はそのまま書き戻せるよう残す
+            let known_tags: Vec<&str> = KNOWN_INDIVIDUAL_TAGS.iter().copied().chain(uid.map(|_| "_UID")).collect();
+            person.gedcom_extras = extras_lines(&record.children, &known_tags, 1);
+        }
+
+        for (index, child) in record.children.iter().enumerate() {
+            if let Some(name) = event_name(child, INDIVIDUAL_EVENTS) {
+                event_links.push((imported_event(xref, index, child, name), id));
+            }
+        }
+        for famc in record.children_with_tag("FAMC") {
+            if let Some(pedigree) = famc.child_value("PEDI") {
+                pedigrees.insert((xref, famc.value.as_str()), pedigree);
+            }
+        }
+        person_ids.insert(xref, id);
+    }
+
+    for record in top_level("FAM") {
+        let member = |tag: &str| record.child(tag).and_then(|member| person_ids.get(member.value.as_str())).copied();
+        let husband = member("HUSB");
+        let wife = member("WIFE");
+
+        if let (Some(husband), Some(wife)) = (husband, wife) {
+            tree.add_spouse_unsynced(husband, wife, note_text(record));
+            if let Some(spouse) = tree.spouse_relation_mut(husband, wife) {
+                if let Some(marriage) = record.child("MARR") {
+                    spouse.marriage_date = marriage.child_value("DATE").map(date_from_gedcom);
+                }
+                if let Some(divorce) = record.child("DIV") {
+                    spouse.divorced = true;
+                    spouse.divorce_date = divorce.child_value("DATE").map(date_from_gedcom);
+                }
+            }
+        }
+        let family_xref = record.xref.as_deref().unwrap_or_default();
+        let event_tags = if husband.is_some() && wife.is_some() { FAMILY_EVENTS } else { SINGLE_PARTNER_FAMILY_EVENTS };
+        for (index, child) in record.children.iter().enumerate() {
+            if let Some(name) = event_name(child, event_tags) {
+                let event = imported_event(family_xref, index, child, name);
+                event_links.extend([husband, wife].into_iter().flatten().map(|partner| (event.clone(), partner)));
+            }
+        }
+
+        for chil in record.children_with_tag("CHIL") {
+            let Some(child) = person_ids.get(chil.value.as_str()).copied() else { continue };
+            let pedigree = pedigrees.get(&(chil.value.as_str(), family_xref)).copied();
+            let relations = [(husband, chil.child_value("_FREL")), (wife, chil.child_value("_MREL"))];
+            for (parent, relation) in relations {
+                let Some(parent) = parent else { continue };
+                tree.add_parent_child_unsynced(parent, child, kind_from_pedigree(relation.or(pedigree)).to_string());
+            }
+        }
+    }
+
+    // 世代ごとに夫婦を隣り合わせて並べる（GEDCOM には座標がない）
+    let mut max_x: f32 = 0.0;
+    for (id, position) in LayoutEngine::auto_positions(&tree, (0.0, 0.0), IMPORT_X_SPACING, IMPORT_Y_SPACING, LayoutDirection::LeftToRight) {
+        max_x = max_x.max(position.0);
+        if let Some(person) = tree.persons.get_mut(&id) {
+            person.position = position;
+        }
+    }
+
+    let mut event_ids: HashMap<ImportedEventKey, EventId> = HashMap::new();
+    for (event, person) in event_links {
+        let event_id = match event_ids.get(&event.key) {
+            Some(id) => *id,
+            None => {
+                let position = (max_x + IMPORT_X_SPACING, event_ids.len() as f32 * IMPORT_EVENT_SPACING);
+                let id = tree.add_event(event.name.clone(), event.date.clone(), event.description.clone(), position, IMPORT_EVENT_COLOR);
+                let place = event.place.as_deref().map(|name| place_named(&mut tree, name));
+                if let Some(added) = tree.events.get_mut(&id) {
+                    added.place = place;
+                }
+                event_ids.insert(event.key, id);
+                id
+            }
+        };
+        tree.add_event_relation(event_id, person, EventRelationType::Line, String::new());
+    }
+
+    tree.sync_unions();
+    tree.format_version = CURRENT_FORMAT_VERSION;
+    Ok(tree)
+}
+
+// ---- FamilyTree → GEDCOM ----
+
+fn record(tag: &str, value: impl Into<String>) -> GedcomRecord {
+    GedcomRecord {
+        tag: tag.to_string(),
+        value: value.into(),
+        ..Default::default()
+    }
+}
+
+/// 日付と場所を子に持つイベントのレコード（どちらもなければ値を "Y" にする）
+fn event_record(tag: &str, date: Option<&str>, place: Option<&str>) -> GedcomRecord {
+    let mut event = record(tag, "");
+    if let Some(date) = date.map(str::trim).filter(|date| !date.is_empty()) {
+        event.children.push(record("DATE", date_to_gedcom(date)));
+    }
+    if let Some(place) = place.filter(|place| !place.is_empty()) {
+        event.children.push(record("PLAC", place));
+    }
+    if event.children.is_empty() {
+        event.value = "Y".to_string();
+    }
+    event
+}
+
+fn pedigree_of(edge: &ParentChild) -> Option<&'static str> {
+    match edge.kind.as_str() {
+        ADOPTIVE_KIND => Some("adopted"),
+        FOSTER_KIND => Some("foster"),
+        _ => None,
+    }
+}
+
+/// 書き出す GEDCOM の家族（FAM）。親の組ごとに、家族の単位の子と配偶者関係をまとめる
+#[derive(Default)]
+struct ExportFamily {
+    husband: Option<PersonId>,
+    wife: Option<PersonId>,
+    children: Vec<PersonId>,
+    marriage_date: Option<String>,
+    divorce_date: Option<Option<String>>,
+    note: String,
+}
+
+/// 姓を `/` で囲んだ GEDCOM の名前
+///
+/// 漢字・かなの名前は姓を先に書く（家族の既定の姓と同じ順）ので最初の語を、それ以外は最後の語を姓とみなす。
+/// 1語だけの名前は姓の分からない名として書く。
+fn gedcom_name(name: &str) -> String {
+    let parts = NameParts::split(name);
+    match parts {
+        NameParts { given, surname: "", .. } => given,
+        NameParts { given, surname, surname_first: true } => format!("/{surname}/ {given}"),
+        NameParts { given, surname, surname_first: false } => format!("{given} /{surname}/"),
+    }
+}
+
+/// 同じ親の組の家族（なければ追加して返す）
+fn family_of(families: &mut Vec<ExportFamily>, (husband, wife): (Option<PersonId>, Option<PersonId>)) -> &mut ExportFamily {
+    let index = families
+        .iter()
+        .position(|family| (family.husband == husband && family.wife == wife) || (family.husband == wife && family.wife == husband))
+        .unwrap_or_else(|| {
+            families.push(ExportFamily { husband, wife, ..Default::default() });
+            families.len() - 1
+        });
+    &mut families[index]
+}
+
+/// 家系図を GEDCOM 5.5.1 の文字列（UTF-8）にする。
+///
+/// どの人物ともつながっていないイベントは書き出さない。
+pub fn tree_to_gedcom(tree: &FamilyTree) -> String {
+    // 出力を安定させるため名前順・ID順に並べる
+    let mut persons: Vec<_> = tree.persons.values().collect();
+    persons.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+    let person_xrefs: HashMap<PersonId, String> = persons
+        .iter()
+        .enumerate()
+        .map(|(index, person)| (person.id, format!("@I{}@", index + 1)))
+        .collect();
+    let place_name = |place: Option<PlaceId>| place.and_then(|place| tree.get_place(place)).map(|place| place.name.as_str());
+
+    // 家族の単位を GEDCOM の家族にする。親子関係の種類は子ごとの PEDI・_FREL・_MREL に書くので、
+    // 同じ親の組で種類だけが違う単位は1つの家族にまとめる。3人目以降の親は別の単位なので別の家族になる
+    let is_female = |id: PersonId| tree.persons.get(&id).is_some_and(|person| person.gender == Gender::Female);
+    let couple = |partners: &[PersonId]| match *partners {
+        [a, b] if is_female(a) && !is_female(b) => (Some(b), Some(a)),
+        [a, b] => (Some(a), Some(b)),
+        [a] if is_female(a) => (None, Some(a)),
+        [a] => (Some(a), None),
+        _ => (None, None),
+    };
+    let union_children = tree.children_by_union();
+    let mut families: Vec<ExportFamily> = Vec::new();
+    for union in tree.unions() {
+        let family = family_of(&mut families, couple(&union.partners));
+        for child in union_children.get(&union.id).into_iter().flatten() {
+            if !family.children.contains(child) {
+                family.children.push(*child);
+            }
+        }
+    }
+    // 子のいない夫婦も家族として書き、結婚・離婚の日付とメモを付ける
+    for spouse in &tree.spouses {
+        let family = family_of(&mut families, couple(&[spouse.person1, spouse.person2]));
+        family.note = spouse.memo.clone();
+        family.marriage_date = spouse.marriage_date.clone();
+        if spouse.divorced {
+            family.divorce_date = Some(spouse.divorce_date.clone());
+        }
+    }
+    let family_xrefs: Vec<String> = (0..families.len()).map(|index| format!("@F{}@", index + 1)).collect();
+    let edge = |parent: Option<PersonId>, child: PersonId| {
+        parent.and_then(|parent| tree.edges.iter().find(|edge| edge.parent == parent && edge.child == child))
+    };
+
+    let mut lines = Vec::new();
+    let mut head = record("HEAD", "");
+    let mut source = record("SOUR", "FAMILY_TREE_CREATOR");
+    source.children.push(record("NAME", "Family Tree Creator"));
+    source.children.push(record("VERS", env!("CARGO_PKG_VERSION")));
+    head.children.push(source);
+    head.children.push(record("DATE", chrono::Local::now().format("%-d %b %Y").to_string().to_ascii_uppercase()));
+    let mut gedc = record("GEDC", "");
+    gedc.children.push(record("VERS", GEDCOM_VERSION));
+    gedc.children.push(record("FORM", "LINEAGE-LINKED"));
+    head.children.push(gedc);
+    head.children.push(record("CHAR", "UTF-8"));
+    lines.extend(head.to_lines(0));
+
+    for person in &persons {
+        let mut individual = GedcomRecord {
+            xref: Some(person_xrefs[&person.id].clone()),
+            ..record("INDI", "")
+        };
+        individual.children.push(record("NAME", gedcom_name(&person.name)));
+        individual.children.push(record("_UID", person.id.to_string()));
+        let sex = match &person.gender {
+            Gender::Male => "M",
+            Gender::Female => "F",
+            Gender::Unknown | Gender::Other(_) => "U",
+        };
+        individual.children.push(record("SEX", sex));
+        if let Gender::Other(label) = &person.gender
+            && !label.is_empty()
+        {
+            individual.children.push(record("_GENDER", label.as_str()));
+        }
+        if person.birth.is_some() || person.birth_place.is_some() {
+            individual.children.push(event_record("BIRT", person.birth.as_deref(), place_name(person.birth_place)));
+        }
+        if person.deceased || person.death.is_some() || person.death_place.is_some() {
+            individual.children.push(event_record("DEAT", person.death.as_deref(), place_name(person.death_place)));
+        }
+        for relation in tree.event_relations.iter().filter(|relation| relation.person == person.id) {
+            let Some(event) = tree.events.get(&relation.event) else { continue };
+            let tag = INDIVIDUAL_EVENTS.iter().find(|(_, name)| *name == event.name).map_or("EVEN", |(tag, _)| *tag);
+            let mut written = event_record(tag, event.date.as_deref(), place_name(event.place));
+            if tag == "EVEN" {
+                written.value.clear();
+                written.children.insert(0, record("TYPE", event.name.as_str()));
+            }
+            if !event.description.is_empty() {
+                written.children.push(record("NOTE", event.description.as_str()));
+            }
+            // 複数の人物につながったイベントを読み込み時に1つにまとめ直すための ID
+            written.children.push(record("_UID", event.id.to_string()));
+            individual.children.push(written);
+        }
+        if person.has_own_photo()
+            && let Some(path) = &person.photo_path
+        {
+            let mut object = record("OBJE", "");
+            object.children.push(record("FILE", path.as_str()));
+            individual.children.push(object);
+        }
+        if !person.memo.is_empty() {
+            individual.children.push(record("NOTE", person.memo.as_str()));
+        }
+        let child_families: Vec<_> =
+            families.iter().zip(&family_xrefs).filter(|(family, _)| family.children.contains(&person.id)).collect();
+        for (family, xref) in &child_families {
+            let mut famc = record("FAMC", xref.as_str());
+            // 両親との関係が同じ種類のときだけ家族全体の関係として書く。親の家族が複数ある子は
+            // 実の親の家族にも "birth" を書き、どの家族が実の親か分かるようにする
+            let pedigrees: Vec<_> = [family.husband, family.wife]
+                .into_iter()
+                .flatten()
+                .map(|parent| edge(Some(parent), person.id).and_then(pedigree_of).unwrap_or("birth"))
+                .collect();
+            if let Some(pedigree) = pedigrees.first()
+                && pedigrees.iter().all(|other| other == pedigree)
+                && (*pedigree != "birth" || child_families.len() > 1)
+            {
+                famc.children.push(record("PEDI", *pedigree));
+            }
+            individual.children.push(famc);
+        }
+        for (family, xref) in families.iter().zip(&family_xrefs) {
+            if family.husband == Some(person.id) || family.wife == Some(person.id) {
+                individual.children.push(record("FAMS", xref.as_str()));
+            }
+        }
+        lines.extend(individual.to_lines(0));
+        lines.extend(person.gedcom_extras.iter().cloned());
+    }
+
+    for (family, xref) in families.iter().zip(&family_xrefs) {
+        let mut written = GedcomRecord {
+            xref: Some(xref.clone()),
+            ..record("FAM", "")
+        };
+        for (tag, member) in [("HUSB", family.husband), ("WIFE", family.wife)] {
+            if let Some(member) = member {
+                written.children.push(record(tag, person_xrefs[&member].as_str()));
+            }
+        }
+        if family.marriage_date.is_some() {
+            written.children.push(event_record("MARR", family.marriage_date.as_deref(), None));
+        }
+        if let Some(divorce_date) = &family.divorce_date {
+            written.children.push(event_record("DIV", divorce_date.as_deref(), None));
+        }
+        for child in &family.children {
+            let mut chil = record("CHIL", person_xrefs[child].as_str());
+            // 片方の親とだけ養子などの場合に備え、親ごとの関係も書く（Ancestry の形式）
+            for (tag, parent) in [("_FREL", family.husband), ("_MREL", family.wife)] {
+                if let Some(pedigree) = edge(parent, *child).and_then(pedigree_of) {
+                    let mut label = pedigree.to_string();
+                    label[..1].make_ascii_uppercase();
+                    chil.children.push(record(tag, label));
+                }
+            }
+            written.children.push(chil);
+        }
+        if !family.note.is_empty() {
+            written.children.push(record("NOTE", family.note.as_str()));
+        }
+        lines.extend(written.to_lines(0));
+    }
+
+    lines.push("0 TRLR".to_string());
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::{date_from_gedcom, date_to_gedcom, gedcom_name, tree_from_gedcom, tree_to_gedcom, GedcomTreeRepository};
+    use crate::application::TreeRepository;
+    use crate::core::tree::{EventRelationType, FamilyTree, Gender, Person};
+
+    fn person_named<'a>(tree: &'a FamilyTree, name: &str) -> &'a Person {
+        tree.persons.values().find(|person| person.name == name).expect("person should exist")
+    }
+
+    #[test]
+    fn converts_dates_both_ways() {
+        assert_eq!(date_from_gedcom("2 JAN 1950"), "1950-01-02");
+        assert_eq!(date_from_gedcom("MAR 1901"), "1901-03");
+        assert_eq!(date_from_gedcom("1899"), "1899");
+        assert_eq!(date_from_gedcom("ABT 1900"), "ABT 1900");
+        assert_eq!(date_from_gedcom("(spring 1972)"), "spring 1972");
+
+        assert_eq!(date_to_gedcom("1950-01-02"), "2 JAN 1950");
+        assert_eq!(date_to_gedcom("1901-03"), "MAR 1901");
+        assert_eq!(date_to_gedcom("1899"), "1899");
+        assert_eq!(date_to_gedcom("BEF 1900"), "BEF 1900");
+        assert_eq!(date_to_gedcom("about 1970"), "(about 1970)");
+    }
+
+    #[test]
+    fn imports_gedcom_from_other_apps() {
+        let text = "\
+0 HEAD
+1 SOUR Ancestry.com Family Trees
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME Taro /Yamada/
+1 SEX M
+1 BIRT
+2 DATE 2 Jan 1950
+2 PLAC Kyoto
+1 DEAT
+2 DATE 2010
+1 GRAD
+2 DATE (spring 1972)
+2 NOTE Tokyo & Kyoto
+1 OCCU Farmer
+1 RESI
+2 PLAC Osaka
+1 RESI
+2 PLAC Osaka
+1 OBJE @O1@
+1 NOTE @N1@
+1 FAMS @F1@
+0 @I2@ INDI
+1 NAME Hanako
+1 SEX F
+1 FAMS @F1@
+0 @I3@ INDI
+1 NAME Ichiro /Yamada/
+1 FAMC @F1@
+0 @F1@ FAM
+1 HUSB @I1@
+1 WIFE @I2@
+1 MARR
+2 DATE 1 JUN 1975
+1 CHIL @I3@
+2 _FREL Natural
+2 _MREL Adopted
+0 @O1@ OBJE
+1 FILE photos/taro.jpg
+0 @N1@ NOTE Eldest son
+0 TRLR
+";
+        let tree = tree_from_gedcom(text).expect("gedcom should import");
+        let taro = person_named(&tree, "Taro Yamada");
+        assert_eq!(taro.gender, Gender::Male);
+        assert_eq!(taro.birth.as_deref(), Some("1950-01-02"));
+        assert_eq!(tree.get_place(taro.birth_place.unwrap()).unwrap().name, "Kyoto");
+        assert!(taro.deceased);
+        assert_eq!(taro.death.as_deref(), Some("2010"));
+        assert_eq!(taro.memo, "Eldest son");
+        assert_eq!(taro.photo_path.as_deref(), Some("photos/taro.jpg"));
+        assert_eq!(taro.gedcom_extras, vec!["1 OCCU Farmer".to_string()]);
+
+        let hanako = person_named(&tree, "Hanako");
+        let ichiro = person_named(&tree, "Ichiro Yamada");
+        let spouse = tree.spouse_relation(taro.id, hanako.id).expect("couple should be linked");
+        assert_eq!(spouse.marriage_date.as_deref(), Some("1975-06-01"));
+        assert!(!tree.parent_child_relation(taro.id, ichiro.id).unwrap().is_adoptive());
+        assert!(tree.parent_child_relation(hanako.id, ichiro.id).unwrap().is_adoptive());
+        assert!(ichiro.position.1 > taro.position.1);
+
+        // 同じ内容でも別々に書かれたイベントは別のイベントにする
+        assert_eq!(tree.events.len(), 3);
+        assert_eq!(tree.events.values().filter(|event| event.name == "Residence").count(), 2);
+        let event = tree.events.values().find(|event| event.name == "Graduation").unwrap();
+        assert_eq!(event.date.as_deref(), Some("spring 1972"));
+        assert_eq!(event.description, "Tokyo & Kyoto");
+
+        assert!(tree_from_gedcom("0 @I1@ INDI\n1 NAME Solo\n").is_err());
+    }
+
+    #[test]
+    fn export_then_import_round_trip() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, Some("1940".to_string()), "line 1\nline 2".to_string(), true, Some("2001-02-03".to_string()), (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Other("nonbinary".to_string()), None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("子ども".to_string(), Gender::Female, Some("about 1970".to_string()), String::new(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, mother, "met in Osaka".to_string());
+        let spouse = tree.spouse_relation_mut(father, mother).unwrap();
+        spouse.divorced = true;
+        spouse.divorce_date = Some("1980-05".to_string());
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "adoptive".to_string());
+        tree.persons.get_mut(&child).unwrap().photo_path = Some("child.png".to_string());
+        tree.persons.get_mut(&father).unwrap().gedcom_extras = vec!["1 OCCU Farmer".to_string(), "2 PLAC Kyoto".to_string()];
+        let event = tree.add_event("Reunion".to_string(), Some("1999-08-15".to_string()), String::new(), (0.0, 0.0), (255, 255, 200));
+        tree.add_event_relation(event, child, EventRelationType::Line, String::new());
+        tree.add_event_relation(event, mother, EventRelationType::Line, String::new());
+
+        let text = tree_to_gedcom(&tree);
+        assert!(text.starts_with("0 HEAD\n"));
+        assert!(text.contains("2 CONT line 2"));
+        assert!(text.ends_with("0 TRLR\n"));
+        let imported = tree_from_gedcom(&text).expect("exported gedcom should import");
+
+        assert_eq!(imported.persons.len(), 3);
+        let father = person_named(&imported, "Father");
+        let mother = person_named(&imported, "Mother");
+        let child = person_named(&imported, "子ども");
+        assert_eq!(father.memo, "line 1\nline 2");
+        assert_eq!(father.death.as_deref(), Some("2001-02-03"));
+        assert_eq!(father.gedcom_extras, vec!["1 OCCU Farmer".to_string(), "2 PLAC Kyoto".to_string()]);
+        assert_eq!(mother.gender, Gender::Other("nonbinary".to_string()));
+        assert_eq!(child.birth.as_deref(), Some("about 1970"));
+        assert_eq!(child.photo_path.as_deref(), Some("child.png"));
+
+        let spouse = imported.spouse_relation(father.id, mother.id).expect("couple should survive");
+        assert!(spouse.divorced);
+        assert_eq!(spouse.divorce_date.as_deref(), Some("1980-05"));
+        assert_eq!(spouse.memo, "met in Osaka");
+        assert!(imported.parent_child_relation(mother.id, child.id).unwrap().is_adoptive());
+        assert!(!imported.parent_child_relation(father.id, child.id).unwrap().is_adoptive());
+
+        // 2人につながったイベントは1つにまとまる
+        assert_eq!(imported.events.len(), 1);
+        assert_eq!(imported.event_relations.len(), 2);
+    }
+
+    #[test]
+    fn keeps_person_ids_on_round_trip() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_parent_child(father, child, "biological".to_string());
+
+        let imported = tree_from_gedcom(&tree_to_gedcom(&tree)).expect("exported gedcom should import");
+        assert_eq!(person_named(&imported, "Father").id, father);
+        assert_eq!(person_named(&imported, "Child").id, child);
+        assert!(imported.parent_child_relation(father, child).is_some());
+        assert!(imported.persons.values().all(|person| person.gedcom_extras.is_empty()));
+
+        // UUID でない他のアプリの `_UID` は新しい ID を振り、行は残す
+        let foreign = tree_from_gedcom("0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME Taro\n1 _UID 1234ABCD\n0 @I2@ INDI\n1 NAME Hanako\n0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n0 TRLR\n")
+            .expect("file should import");
+        assert_eq!(person_named(&foreign, "Taro").gedcom_extras, vec!["1 _UID 1234ABCD".to_string()]);
+    }
+
+    #[test]
+    fn writes_surnames_between_slashes() {
+        assert_eq!(gedcom_name("Taro Yamada"), "Taro /Yamada/");
+        assert_eq!(gedcom_name("Mary Ann  Smith"), "Mary Ann /Smith/");
+        assert_eq!(gedcom_name("山田 太郎"), "/山田/ 太郎");
+        assert_eq!(gedcom_name("Hanako"), "Hanako");
+
+        let mut tree = FamilyTree::default();
+        tree.add_person("Taro Yamada".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_person("山田 花子".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let text = tree_to_gedcom(&tree);
+        assert!(text.contains("1 NAME Taro /Yamada/\n"));
+        assert!(text.contains("1 NAME /山田/ 花子\n"));
+
+        let imported = tree_from_gedcom(&text).expect("exported gedcom should import");
+        person_named(&imported, "Taro Yamada");
+        person_named(&imported, "山田 花子");
+    }
+
+    #[test]
+    fn imports_marriage_of_family_with_one_partner_as_events() {
+        let text = "\
+0 HEAD
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME Hanako /Sato/
+1 SEX F
+1 FAMS @F1@
+0 @F1@ FAM
+1 WIFE @I1@
+1 MARR
+2 DATE 1970
+1 DIV
+2 DATE 1980
+0 TRLR
+";
+        let tree = tree_from_gedcom(text).expect("gedcom should import");
+        let hanako = person_named(&tree, "Hanako Sato");
+        let mut events: Vec<_> = tree.events.values().map(|event| (event.name.as_str(), event.date.as_deref())).collect();
+        events.sort();
+        assert_eq!(events, vec![("Divorce", Some("1980")), ("Marriage", Some("1970"))]);
+        assert!(tree.event_relations.iter().all(|relation| relation.person == hanako.id));
+    }
+
+    #[test]
+    fn exports_extra_parents_as_their_own_family() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let step = tree.add_person("Stepfather".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, mother, String::new());
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "biological".to_string());
+        tree.add_parent_child(step, child, "adoptive".to_string());
+
+        let text = tree_to_gedcom(&tree);
+        assert_eq!(text.matches(" FAM\n").count(), 2);
+        assert!(text.contains("2 PEDI birth"));
+        assert!(text.contains("2 PEDI adopted"));
+
+        let imported = tree_from_gedcom(&text).expect("exported gedcom should import");
+        let [father, mother, step, child] = ["Father", "Mother", "Stepfather", "Child"].map(|name| person_named(&imported, name).id);
+        assert!(!imported.parent_child_relation(father, child).unwrap().is_adoptive());
+        assert!(!imported.parent_child_relation(mother, child).unwrap().is_adoptive());
+        assert!(imported.parent_child_relation(step, child).unwrap().is_adoptive());
+        assert!(imported.spouse_relation(father, mother).is_some());
+        assert!(imported.spouse_relation(step, mother).is_none());
+    }
+
+    #[test]
+    fn repository_reads_back_what_it_writes() {
+        let repository = GedcomTreeRepository;
+        let path = env::temp_dir().join(format!("family_tree_test_{}.ged", Uuid::new_v4()));
+        let path = path.to_string_lossy().to_string();
+        let mut tree = FamilyTree::default();
+        tree.add_person("Solo".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+
+        repository.save(&path, &tree).expect("gedcom file should save");
+        let loaded = repository.load(&path).expect("gedcom file should load");
+        assert_eq!(loaded.persons.len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+}
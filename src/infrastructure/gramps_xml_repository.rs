@@ -0,0 +1,800 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use quick_xml::escape::{escape, resolve_predefined_entity};
+use quick_xml::events::Event as XmlEvent;
+use quick_xml::Reader;
+
+use crate::application::{TreeRepository, TreeRepositoryError};
+use crate::core::layout::{LayoutDirection, LayoutEngine};
+use crate::core::tree::{
+    EventId, EventRelationType, FamilyTree, Gender, PersonId, ADOPTIVE_KIND, CURRENT_FORMAT_VERSION,
+};
+
+/// gzip圧縮ファイルの先頭2バイト（.gramps は通常gzip圧縮されている）
+pub const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// 書き出すGramps XMLのバージョン
+const GRAMPS_XML_VERSION: &str = "1.7.1";
+/// 読み込んだ人物を世代ごとに並べるときの間隔
+const IMPORT_X_SPACING: f32 = 160.0;
+const IMPORT_Y_SPACING: f32 = 140.0;
+/// 読み込んだイベントを並べるときの縦の間隔
+const IMPORT_EVENT_SPACING: f32 = 60.0;
+
+/// GrampsのXML形式（.gramps）で家系図を保存・読込するリポジトリ実装。
+///
+/// 人物・家族（夫婦と親子関係）・イベント・メディア（人物の写真）・ノート（メモ）を対応付ける。
+/// Grampsの家族はこのアプリの配偶者関係と親子関係に、出生・死亡・結婚・離婚のイベントは
+/// それぞれの日付欄に変換し、それ以外のイベントはキャンバス上のイベントにする。
+pub struct GrampsXmlRepository;
+
+impl TreeRepository for GrampsXmlRepository {
+    fn load(&self, file_path: &str) -> Result<FamilyTree, TreeRepositoryError> {
+        let bytes = fs::read(file_path).map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let content = if bytes.starts_with(GZIP_MAGIC) {
+            let mut content = String::new();
+            GzDecoder::new(bytes.as_slice())
+                .read_to_string(&mut content)
+                .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            content
+        } else {
+            String::from_utf8(bytes).map_err(|error| TreeRepositoryError::Read(error.to_string()))?
+        };
+        tree_from_gramps_xml(&content)
+    }
+
+    fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
+        let xml = tree_to_gramps_xml(tree);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(xml.as_bytes())
+            .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+        let compressed = encoder
+            .finish()
+            .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+
+        fs::write(file_path, compressed).map_err(|error| TreeRepositoryError::Write(error.to_string()))
+    }
+}
+
+// ---- XMLの読み込み ----
+
+/// 名前空間を除いた要素名で扱う簡易的なXML要素
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+impl XmlElement {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// 子要素の文字列（前後の空白を除き、空なら None）
+    fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name)
+            .map(|child| child.text.trim())
+            .filter(|text| !text.is_empty())
+    }
+}
+
+fn parse_xml(content: &str) -> Result<XmlElement, String> {
+    fn element_from(start: &quick_xml::events::BytesStart<'_>) -> Result<XmlElement, String> {
+        let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|error| error.to_string())?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute.unescape_value().map_err(|error| error.to_string())?.into_owned();
+            attributes.push((key, value));
+        }
+        Ok(XmlElement {
+            name,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    let mut reader = Reader::from_str(content);
+    let mut stack: Vec<XmlElement> = Vec::new();
+    loop {
+        let event = reader.read_event().map_err(|error| error.to_string())?;
+        match event {
+            XmlEvent::Start(start) => stack.push(element_from(&start)?),
+            XmlEvent::Empty(start) => {
+                let element = element_from(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::End(_) => {
+                let element = stack.pop().ok_or("unexpected end tag")?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::Text(text) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&text.xml_content().map_err(|error| error.to_string())?);
+                }
+            }
+            XmlEvent::CData(data) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&data.decode().map_err(|error| error.to_string())?);
+                }
+            }
+            XmlEvent::GeneralRef(reference) => {
+                let Some(current) = stack.last_mut() else { continue };
+                if let Some(c) = reference.resolve_char_ref().map_err(|error| error.to_string())? {
+                    current.text.push(c);
+                } else {
+                    let name = reference.decode().map_err(|error| error.to_string())?;
+                    let resolved = resolve_predefined_entity(&name).ok_or_else(|| format!("unknown entity &{name};"))?;
+                    current.text.push_str(resolved);
+                }
+            }
+            XmlEvent::Eof => return Err("missing root element".to_string()),
+            _ => {}
+        }
+    }
+}
+
+// ---- Gramps → FamilyTree ----
+
+struct GrampsEvent {
+    kind: String,
+    date: Option<String>,
+    description: String,
+}
+
+/// Grampsの日付（dateval / daterange / datespan / datestr）を文字列にする
+fn gramps_date(event: &XmlElement) -> Option<String> {
+    if let Some(value) = event.child("dateval").and_then(|date| date.attr("val")) {
+        return Some(value.to_string());
+    }
+    if let Some(range) = event.child("daterange").or_else(|| event.child("datespan")) {
+        return match (range.attr("start"), range.attr("stop")) {
+            (Some(start), Some(stop)) => Some(format!("{start}/{stop}")),
+            (start, stop) => start.or(stop).map(str::to_string),
+        };
+    }
+    event.child("datestr").and_then(|date| date.attr("val")).map(str::to_string)
+}
+
+fn person_name(person: &XmlElement) -> String {
+    let Some(name) = person.child("name") else {
+        return String::new();
+    };
+    let first = name.child_text("first").unwrap_or_default();
+    let surnames: Vec<&str> = name
+        .children_named("surname")
+        .map(|surname| surname.text.trim())
+        .filter(|surname| !surname.is_empty())
+        .collect();
+    [first.to_string(), surnames.join(" ")]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn gender_from_gramps(code: Option<&str>) -> Gender {
+    match code {
+        Some("M") => Gender::Male,
+        Some("F") => Gender::Female,
+        Some("X") => Gender::Other(String::new()),
+        _ => Gender::Unknown,
+    }
+}
+
+/// Gramps XMLの文字列から家系図を作る。
+pub fn tree_from_gramps_xml(content: &str) -> Result<FamilyTree, TreeRepositoryError> {
+    let root = parse_xml(content).map_err(TreeRepositoryError::Deserialize)?;
+    if root.name != "database" {
+        return Err(TreeRepositoryError::Deserialize(format!(
+            "expected Gramps <database> root, found <{}>",
+            root.name
+        )));
+    }
+
+    let section = |name: &str| root.child(name).into_iter().flat_map(|section| section.children.iter());
+    let events: HashMap<&str, GrampsEvent> = section("events")
+        .filter_map(|event| {
+            Some((
+                event.attr("handle")?,
+                GrampsEvent {
+                    kind: event.child_text("type").unwrap_or_default().to_string(),
+                    date: gramps_date(event),
+                    description: event.child_text("description").unwrap_or_default().to_string(),
+                },
+            ))
+        })
+        .collect();
+    let media: HashMap<&str, &str> = section("objects")
+        .filter_map(|object| Some((object.attr("handle")?, object.child("file")?.attr("src")?)))
+        .collect();
+    let notes: HashMap<&str, &str> = section("notes")
+        .filter_map(|note| Some((note.attr("handle")?, note.child_text("text")?)))
+        .collect();
+    let note_text = |element: &XmlElement| {
+        element
+            .children_named("noteref")
+            .filter_map(|noteref| notes.get(noteref.attr("hlink")?).copied())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let mut tree = FamilyTree::default();
+    let mut person_ids: HashMap<&str, PersonId> = HashMap::new();
+    // 出生・死亡以外のイベントは、複数の人物から参照されても1つのイベントにする
+    let mut event_ids: HashMap<&str, EventId> = HashMap::new();
+    let mut event_links: Vec<(&str, PersonId)> = Vec::new();
+
+    for person in section("people") {
+        let Some(handle) = person.attr("handle") else { continue };
+        let mut birth = None;
+        let mut death = None;
+        let mut deceased = false;
+        let mut other_events = Vec::new();
+        for eventref in person.children_named("eventref") {
+            let Some(event_handle) = eventref.attr("hlink") else { continue };
+            let Some(event) = events.get(event_handle) else { continue };
+            match event.kind.as_str() {
+                "Birth" if birth.is_none() => birth = event.date.clone(),
+                "Death" => {
+                    deceased = true;
+                    death = death.or_else(|| event.date.clone());
+                }
+                "Burial" | "Cremation" => deceased = true,
+                _ => other_events.push(event_handle),
+            }
+        }
+
+        let id = tree.add_person(
+            person_name(person),
+            gender_from_gramps(person.child_text("gender")),
+            birth,
+            note_text(person),
+            deceased,
+            death,
+            (0.0, 0.0),
+        );
+        if let Some(photo) = person
+            .children_named("objref")
+            .find_map(|objref| media.get(objref.attr("hlink")?))
+            && let Some(person) = tree.persons.get_mut(&id)
+        {
+            person.photo_path = Some(photo.to_string());
+        }
+        person_ids.insert(handle, id);
+        event_links.extend(other_events.into_iter().map(|event| (event, id)));
+    }
+
+    for family in section("families") {
+        let parent = |role: &str| family.child(role).and_then(|parent| person_ids.get(parent.attr("hlink")?)).copied();
+        let father = parent("father");
+        let mother = parent("mother");
+
+        if let (Some(father), Some(mother)) = (father, mother) {
+            tree.add_spouse_unsynced(father, mother, note_text(family));
+            for eventref in family.children_named("eventref") {
+                let Some(event_handle) = eventref.attr("hlink") else { continue };
+                let Some(event) = events.get(event_handle) else { continue };
+                let Some(spouse) = tree.spouse_relation_mut(father, mother) else { continue };
+                match event.kind.as_str() {
+                    "Marriage" if spouse.marriage_date.is_none() => spouse.marriage_date = event.date.clone(),
+                    "Divorce" => {
+                        spouse.divorced = true;
+                        spouse.divorce_date = event.date.clone();
+                    }
+                    _ => {
+                        event_links.push((event_handle, father));
+                        event_links.push((event_handle, mother));
+                    }
+                }
+            }
+        }
+
+        for childref in family.children_named("childref") {
+            let Some(child) = childref.attr("hlink").and_then(|handle| person_ids.get(handle)).copied() else {
+                continue;
+            };
+            for (parent, relation) in [(father, childref.attr("frel")), (mother, childref.attr("mrel"))] {
+                let Some(parent) = parent else { continue };
+                let kind = if relation == Some("Adopted") { ADOPTIVE_KIND } else { "biological" };
+                tree.add_parent_child_unsynced(parent, child, kind.to_string());
+            }
+        }
+    }
+
+    // 世代ごとに夫婦を隣り合わせて並べる（Gramps には座標がない）
+    let mut max_x: f32 = 0.0;
+    for (id, position) in LayoutEngine::auto_positions(&tree, (0.0, 0.0), IMPORT_X_SPACING, IMPORT_Y_SPACING, LayoutDirection::LeftToRight) {
+        max_x = max_x.max(position.0);
+        if let Some(person) = tree.persons.get_mut(&id) {
+            person.position = position;
+        }
+    }
+
+    for (event_handle, person) in event_links {
+        let event_id = match event_ids.get(event_handle) {
+            Some(id) => *id,
+            None => {
+                let event = &events[event_handle];
+                let position = (max_x + IMPORT_X_SPACING, event_ids.len() as f32 * IMPORT_EVENT_SPACING);
+                let id = tree.add_event(
+                    event.kind.clone(),
+                    event.date.clone(),
+                    event.description.clone(),
+                    position,
+                    (255, 255, 200),
+                );
+                event_ids.insert(event_handle, id);
+                id
+            }
+        };
+        tree.add_event_relation(event_id, person, EventRelationType::Line, String::new());
+    }
+
+    tree.sync_unions();
+    tree.format_version = CURRENT_FORMAT_VERSION;
+    Ok(tree)
+}
+
+// ---- FamilyTree → Gramps ----
+
+struct ExportEvent {
+    handle: String,
+    kind: String,
+    date: Option<String>,
+    description: String,
+}
+
+#[derive(Default)]
+struct ExportFamily {
+    father: Option<PersonId>,
+    mother: Option<PersonId>,
+    /// (子, 父との関係が養子か, 母との関係が養子か)
+    children: Vec<(PersonId, bool, bool)>,
+    event_handles: Vec<String>,
+    note: String,
+}
+
+/// "YYYY", "YYYY-MM", "YYYY-MM-DD" の形なら Gramps が日付として解釈できる
+fn is_gramps_dateval(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    !parts.is_empty()
+        && parts.len() <= 3
+        && parts[0].len() == 4
+        && parts[1..].iter().all(|part| part.len() == 2)
+        && parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn write_date(out: &mut String, date: &Option<String>) {
+    if let Some(date) = date.as_deref().map(str::trim).filter(|date| !date.is_empty()) {
+        let element = if is_gramps_dateval(date) { "dateval" } else { "datestr" };
+        out.push_str(&format!("      <{element} val=\"{}\"/>\n", escape(date)));
+    }
+}
+
+fn mime_type(path: &str) -> &'static str {
+    let extension = path.rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+    match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 家系図を Gramps XML の文字列にする。
+pub fn tree_to_gramps_xml(tree: &FamilyTree) -> String {
+    // 出力を安定させるため名前順・ID順に並べる
+    let mut persons: Vec<_> = tree.persons.values().collect();
+    persons.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+    let person_handles: HashMap<PersonId, String> = persons
+        .iter()
+        .enumerate()
+        .map(|(index, person)| (person.id, format!("_P{index:04}")))
+        .collect();
+
+    let mut events: Vec<ExportEvent> = Vec::new();
+    let mut add_event = |kind: &str, date: Option<String>, description: String| {
+        let handle = format!("_E{:04}", events.len());
+        events.push(ExportEvent {
+            handle: handle.clone(),
+            kind: kind.to_string(),
+            date,
+            description,
+        });
+        handle
+    };
+
+    let mut app_events: Vec<_> = tree.events.values().collect();
+    app_events.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+    let app_event_handles: HashMap<EventId, String> = app_events
+        .iter()
+        .map(|event| (event.id, add_event(&event.name, event.date.clone(), event.description.clone())))
+        .collect();
+
+    // 人物ごとの出生・死亡イベントと、関連付けられたイベント
+    let mut person_event_handles: HashMap<PersonId, Vec<String>> = HashMap::new();
+    for person in &persons {
+        let handles = person_event_handles.entry(person.id).or_default();
+        if person.birth.is_some() {
+            handles.push(add_event("Birth", person.birth.clone(), String::new()));
+        }
+        if person.deceased || person.death.is_some() {
+            handles.push(add_event("Death", person.death.clone(), String::new()));
+        }
+    }
+    for relation in &tree.event_relations {
+        if let (Some(handle), Some(handles)) = (
+            app_event_handles.get(&relation.event),
+            person_event_handles.get_mut(&relation.person),
+        ) && !handles.contains(handle)
+        {
+            handles.push(handle.clone());
+        }
+    }
+
+    // 夫婦と、その両方（または片方）を親に持つ子を Gramps の家族にまとめる
+    let is_female = |id: PersonId| tree.persons.get(&id).is_some_and(|person| person.gender == Gender::Female);
+    let couple = |a: PersonId, b: Option<PersonId>| match b {
+        Some(b) if is_female(a) && !is_female(b) => (Some(b), Some(a)),
+        Some(b) => (Some(a), Some(b)),
+        None if is_female(a) => (None, Some(a)),
+        None => (Some(a), None),
+    };
+    let mut families: Vec<ExportFamily> = Vec::new();
+    let find_family = |families: &mut Vec<ExportFamily>, (father, mother): (Option<PersonId>, Option<PersonId>)| {
+        families
+            .iter()
+            .position(|family| {
+                (family.father == father && family.mother == mother) || (family.father == mother && family.mother == father)
+            })
+            .unwrap_or_else(|| {
+                families.push(ExportFamily {
+                    father,
+                    mother,
+                    ..Default::default()
+                });
+                families.len() - 1
+            })
+    };
+    for spouse in &tree.spouses {
+        let index = find_family(&mut families, couple(spouse.person1, Some(spouse.person2)));
+        let family = &mut families[index];
+        family.note = spouse.memo.clone();
+        if spouse.marriage_date.is_some() {
+            family.event_handles.push(add_event("Marriage", spouse.marriage_date.clone(), String::new()));
+        }
+        if spouse.divorced {
+            family.event_handles.push(add_event("Divorce", spouse.divorce_date.clone(), String::new()));
+        }
+    }
+    for person in &persons {
+        let parents: Vec<_> = tree.edges.iter().filter(|edge| edge.child == person.id).take(2).collect();
+        let Some(first) = parents.first() else { continue };
+        let index = find_family(&mut families, couple(first.parent, parents.get(1).map(|edge| edge.parent)));
+        let family = &mut families[index];
+        let adopted_by = |parent: Option<PersonId>| {
+            parents
+                .iter()
+                .any(|edge| Some(edge.parent) == parent && edge.is_adoptive())
+        };
+        let entry = (person.id, adopted_by(family.father), adopted_by(family.mother));
+        family.children.push(entry);
+    }
+
+    let family_handles: Vec<String> = (0..families.len()).map(|index| format!("_F{index:04}")).collect();
+    let mut child_of: HashMap<PersonId, Vec<&str>> = HashMap::new();
+    let mut parent_in: HashMap<PersonId, Vec<&str>> = HashMap::new();
+    for (family, handle) in families.iter().zip(&family_handles) {
+        for parent in [family.father, family.mother].into_iter().flatten() {
+            parent_in.entry(parent).or_default().push(handle);
+        }
+        for (child, _, _) in &family.children {
+            child_of.entry(*child).or_default().push(handle);
+        }
+    }
+
+    let mut notes: Vec<(String, &str, String)> = Vec::new();
+    let mut objects: Vec<(String, String)> = Vec::new();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<!DOCTYPE database PUBLIC \"-//Gramps//DTD Gramps XML {GRAMPS_XML_VERSION}//EN\"\n\"http://gramps-project.org/xml/{GRAMPS_XML_VERSION}/grampsxml.dtd\">\n"
+    ));
+    out.push_str(&format!("<database xmlns=\"http://gramps-project.org/xml/{GRAMPS_XML_VERSION}/\">\n"));
+    out.push_str(&format!(
+        "  <header>\n    <created date=\"{}\" version=\"{}\"/>\n  </header>\n",
+        chrono::Local::now().format("%Y-%m-%d"),
+        env!("CARGO_PKG_VERSION")
+    ));
+
+    if !events.is_empty() {
+        out.push_str("  <events>\n");
+        for (index, event) in events.iter().enumerate() {
+            out.push_str(&format!("    <event handle=\"{}\" id=\"E{index:04}\">\n", event.handle));
+            out.push_str(&format!("      <type>{}</type>\n", escape(&event.kind)));
+            write_date(&mut out, &event.date);
+            if !event.description.is_empty() {
+                out.push_str(&format!("      <description>{}</description>\n", escape(&event.description)));
+            }
+            out.push_str("    </event>\n");
+        }
+        out.push_str("  </events>\n");
+    }
+
+    if !persons.is_empty() {
+        out.push_str("  <people>\n");
+        for (index, person) in persons.iter().enumerate() {
+            let handle = &person_handles[&person.id];
+            out.push_str(&format!("    <person handle=\"{handle}\" id=\"I{index:04}\">\n"));
+            let gender = match person.gender {
+                Gender::Male => "M",
+                Gender::Female => "F",
+                Gender::Other(_) => "X",
+                Gender::Unknown => "U",
+            };
+            out.push_str(&format!("      <gender>{gender}</gender>\n"));
+            out.push_str(&format!(
+                "      <name type=\"Birth Name\">\n        <first>{}</first>\n      </name>\n",
+                escape(&person.name)
+            ));
+            for event_handle in &person_event_handles[&person.id] {
+                out.push_str(&format!("      <eventref hlink=\"{event_handle}\" role=\"Primary\"/>\n"));
+            }
+            if person.has_own_photo()
+                && let Some(path) = &person.photo_path
+            {
+                let object_handle = format!("_O{:04}", objects.len());
+                out.push_str(&format!("      <objref hlink=\"{object_handle}\"/>\n"));
+                objects.push((object_handle, path.clone()));
+            }
+            for family_handle in child_of.get(&person.id).into_iter().flatten() {
+                out.push_str(&format!("      <childof hlink=\"{family_handle}\"/>\n"));
+            }
+            for family_handle in parent_in.get(&person.id).into_iter().flatten() {
+                out.push_str(&format!("      <parentin hlink=\"{family_handle}\"/>\n"));
+            }
+            if !person.memo.is_empty() {
+                let note_handle = format!("_N{:04}", notes.len());
+                out.push_str(&format!("      <noteref hlink=\"{note_handle}\"/>\n"));
+                notes.push((note_handle, "Person Note", person.memo.clone()));
+            }
+            out.push_str("    </person>\n");
+        }
+        out.push_str("  </people>\n");
+    }
+
+    if !families.is_empty() {
+        out.push_str("  <families>\n");
+        for (index, (family, handle)) in families.iter().zip(&family_handles).enumerate() {
+            out.push_str(&format!("    <family handle=\"{handle}\" id=\"F{index:04}\">\n"));
+            if family.father.is_some() && family.mother.is_some() {
+                out.push_str("      <rel type=\"Married\"/>\n");
+            }
+            for (role, parent) in [("father", family.father), ("mother", family.mother)] {
+                if let Some(parent) = parent {
+                    out.push_str(&format!("      <{role} hlink=\"{}\"/>\n", person_handles[&parent]));
+                }
+            }
+            for event_handle in &family.event_handles {
+                out.push_str(&format!("      <eventref hlink=\"{event_handle}\" role=\"Family\"/>\n"));
+            }
+            for (child, father_adopted, mother_adopted) in &family.children {
+                out.push_str(&format!("      <childref hlink=\"{}\"", person_handles[child]));
+                if *father_adopted {
+                    out.push_str(" frel=\"Adopted\"");
+                }
+                if *mother_adopted {
+                    out.push_str(" mrel=\"Adopted\"");
+                }
+                out.push_str("/>\n");
+            }
+            if !family.note.is_empty() {
+                let note_handle = format!("_N{:04}", notes.len());
+                out.push_str(&format!("      <noteref hlink=\"{note_handle}\"/>\n"));
+                notes.push((note_handle, "Family Note", family.note.clone()));
+            }
+            out.push_str("    </family>\n");
+        }
+        out.push_str("  </families>\n");
+    }
+
+    if !objects.is_empty() {
+        out.push_str("  <objects>\n");
+        for (index, (handle, path)) in objects.iter().enumerate() {
+            out.push_str(&format!("    <object handle=\"{handle}\" id=\"O{index:04}\">\n"));
+            out.push_str(&format!(
+                "      <file src=\"{}\" mime=\"{}\" description=\"\"/>\n",
+                escape(path.as_str()),
+                mime_type(path)
+            ));
+            out.push_str("    </object>\n");
+        }
+        out.push_str("  </objects>\n");
+    }
+
+    if !notes.is_empty() {
+        out.push_str("  <notes>\n");
+        for (index, (handle, kind, text)) in notes.iter().enumerate() {
+            out.push_str(&format!("    <note handle=\"{handle}\" id=\"N{index:04}\" type=\"{kind}\">\n"));
+            out.push_str(&format!("      <text>{}</text>\n", escape(text.as_str())));
+            out.push_str("    </note>\n");
+        }
+        out.push_str("  </notes>\n");
+    }
+
+    out.push_str("</database>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::{tree_from_gramps_xml, tree_to_gramps_xml, GrampsXmlRepository, GZIP_MAGIC};
+    use crate::application::TreeRepository;
+    use crate::core::tree::{EventRelationType, FamilyTree, Gender};
+
+    fn person_named<'a>(tree: &'a FamilyTree, name: &str) -> &'a crate::core::tree::Person {
+        tree.persons.values().find(|person| person.name == name).expect("person should exist")
+    }
+
+    #[test]
+    fn imports_gramps_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<database xmlns="http://gramps-project.org/xml/1.7.1/">
+  <events>
+    <event handle="_e1" id="E0000"><type>Birth</type><dateval val="1950-01-02"/></event>
+    <event handle="_e2" id="E0001"><type>Death</type><dateval val="2010"/></event>
+    <event handle="_e3" id="E0002"><type>Marriage</type><dateval val="1975-06-01"/></event>
+    <event handle="_e4" id="E0003"><type>Graduation</type><datestr val="spring 1972"/><description>Tokyo &amp; Kyoto</description></event>
+  </events>
+  <people>
+    <person handle="_p1" id="I0000">
+      <gender>M</gender>
+      <name type="Birth Name"><first>Taro</first><surname>Yamada</surname></name>
+      <eventref hlink="_e1" role="Primary"/>
+      <eventref hlink="_e2" role="Primary"/>
+      <eventref hlink="_e4" role="Primary"/>
+      <objref hlink="_o1"/>
+      <parentin hlink="_f1"/>
+      <noteref hlink="_n1"/>
+    </person>
+    <person handle="_p2" id="I0001">
+      <gender>F</gender>
+      <name type="Birth Name"><first>Hanako</first></name>
+      <parentin hlink="_f1"/>
+    </person>
+    <person handle="_p3" id="I0002">
+      <gender>U</gender>
+      <name type="Birth Name"><first>Ichiro</first></name>
+      <childof hlink="_f1"/>
+    </person>
+  </people>
+  <families>
+    <family handle="_f1" id="F0000">
+      <rel type="Married"/>
+      <father hlink="_p1"/>
+      <mother hlink="_p2"/>
+      <eventref hlink="_e3" role="Family"/>
+      <childref hlink="_p3" mrel="Adopted"/>
+    </family>
+  </families>
+  <objects>
+    <object handle="_o1" id="O0000"><file src="photos/taro.jpg" mime="image/jpeg" description=""/></object>
+  </objects>
+  <notes>
+    <note handle="_n1" id="N0000" type="Person Note"><text>Eldest son</text></note>
+  </notes>
+</database>"#;
+
+        let tree = tree_from_gramps_xml(xml).expect("gramps xml should import");
+        let taro = person_named(&tree, "Taro Yamada");
+        assert_eq!(taro.gender, Gender::Male);
+        assert_eq!(taro.birth.as_deref(), Some("1950-01-02"));
+        assert!(taro.deceased);
+        assert_eq!(taro.death.as_deref(), Some("2010"));
+        assert_eq!(taro.memo, "Eldest son");
+        assert_eq!(taro.photo_path.as_deref(), Some("photos/taro.jpg"));
+
+        let hanako = person_named(&tree, "Hanako");
+        let ichiro = person_named(&tree, "Ichiro");
+        let spouse = tree.spouse_relation(taro.id, hanako.id).expect("couple should be linked");
+        assert_eq!(spouse.marriage_date.as_deref(), Some("1975-06-01"));
+        assert!(!tree.parent_child_relation(taro.id, ichiro.id).unwrap().is_adoptive());
+        assert!(tree.parent_child_relation(hanako.id, ichiro.id).unwrap().is_adoptive());
+        assert!(ichiro.position.1 > taro.position.1);
+
+        assert_eq!(tree.events.len(), 1);
+        let event = tree.events.values().next().unwrap();
+        assert_eq!(event.name, "Graduation");
+        assert_eq!(event.date.as_deref(), Some("spring 1972"));
+        assert_eq!(event.description, "Tokyo & Kyoto");
+        assert_eq!(tree.event_relations.len(), 1);
+    }
+
+    #[test]
+    fn export_then_import_round_trip() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Father <Sr.>".to_string(), Gender::Male, Some("1940".to_string()), "memo & notes".to_string(), true, Some("2001-02-03".to_string()), (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Child".to_string(), Gender::Female, Some("about 1970".to_string()), String::new(), false, None, (0.0, 0.0));
+        tree.add_spouse(father, mother, "met in Osaka".to_string());
+        tree.spouse_relation_mut(father, mother).unwrap().divorced = true;
+        tree.add_parent_child(father, child, "biological".to_string());
+        tree.add_parent_child(mother, child, "adoptive".to_string());
+        tree.persons.get_mut(&child).unwrap().photo_path = Some("child.png".to_string());
+        let event = tree.add_event("Reunion".to_string(), Some("1999-08-15".to_string()), String::new(), (0.0, 0.0), (255, 255, 200));
+        tree.add_event_relation(event, child, EventRelationType::Line, String::new());
+        tree.add_event_relation(event, mother, EventRelationType::Line, String::new());
+
+        let imported = tree_from_gramps_xml(&tree_to_gramps_xml(&tree)).expect("exported xml should import");
+
+        assert_eq!(imported.persons.len(), 3);
+        let father = person_named(&imported, "Father <Sr.>");
+        let mother = person_named(&imported, "Mother");
+        let child = person_named(&imported, "Child");
+        assert_eq!(father.memo, "memo & notes");
+        assert_eq!(father.death.as_deref(), Some("2001-02-03"));
+        assert_eq!(child.birth.as_deref(), Some("about 1970"));
+        assert_eq!(child.photo_path.as_deref(), Some("child.png"));
+
+        let spouse = imported.spouse_relation(father.id, mother.id).expect("couple should survive");
+        assert!(spouse.divorced);
+        assert_eq!(spouse.memo, "met in Osaka");
+        assert!(imported.parent_child_relation(mother.id, child.id).unwrap().is_adoptive());
+        assert!(!imported.parent_child_relation(father.id, child.id).unwrap().is_adoptive());
+
+        assert_eq!(imported.events.len(), 1);
+        assert_eq!(imported.event_relations.len(), 2);
+    }
+
+    #[test]
+    fn repository_writes_gzip_and_reads_it_back() {
+        let repository = GrampsXmlRepository;
+        let path = env::temp_dir().join(format!("family_tree_test_{}.gramps", Uuid::new_v4()));
+        let path = path.to_string_lossy().to_string();
+        let mut tree = FamilyTree::default();
+        tree.add_person("Solo".to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+
+        repository.save(&path, &tree).expect("gramps file should save");
+        assert!(fs::read(&path).unwrap().starts_with(GZIP_MAGIC));
+        let loaded = repository.load(&path).expect("gramps file should load");
+        assert_eq!(loaded.persons.len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+}
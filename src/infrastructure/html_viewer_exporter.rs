@@ -0,0 +1,316 @@
+use std::fs;
+
+use serde_json::{json, Value};
+
+use crate::core::tree::{DeceasedPresumption, FamilyTree, Person};
+use crate::infrastructure::vcard_exporter::photo_data_uri;
+
+/// HTML ビューアー書き出しの設定
+#[derive(Debug, Clone)]
+pub struct HtmlViewerOptions {
+    /// ページのタイトル
+    pub title: String,
+    /// 写真を base64 でファイルに埋め込む（埋め込まなければ写真は表示しない）
+    pub inline_photos: bool,
+    /// 画面に出す文言（birth / death / parents / children / spouses / hint / search）
+    pub labels: Vec<(&'static str, String)>,
+    /// 死亡の記録がない人物を死亡とみなす設定（生没年の表示に使う）
+    pub presumption: DeceasedPresumption,
+}
+
+/// 家系図をブラウザだけで見られる1つの HTML ファイルとして書き出す
+///
+/// 家系図のデータは JSON としてページに埋め込み、移動・拡大縮小・人物の詳細表示は小さなスクリプトで行う。
+/// 閲覧専用なので、書き出すのは表示に使う項目だけ。
+pub struct HtmlViewerExporter;
+
+impl HtmlViewerExporter {
+    /// .html ファイルに書き出し、人物の件数を返す
+    pub fn export(file_path: &str, tree: &FamilyTree, options: &HtmlViewerOptions) -> std::io::Result<usize> {
+        fs::write(file_path, Self::render(tree, options))?;
+        Ok(tree.persons.len())
+    }
+
+    /// HTML 文書全体の文字列
+    pub fn render(tree: &FamilyTree, options: &HtmlViewerOptions) -> String {
+        // "</script>" で埋め込みが途切れないよう、"</" をエスケープする
+        let data = Self::tree_data(tree, options).to_string().replace("</", "<\\/");
+        let labels: serde_json::Map<String, Value> =
+            options.labels.iter().map(|(key, text)| (key.to_string(), Value::String(text.clone()))).collect();
+        let labels = Value::Object(labels).to_string().replace("</", "<\\/");
+
+        VIEWER_TEMPLATE
+            // タイトルの "{{" が後の置き換えの目印にならないようにする
+            .replace("{{title}}", &escape_html(&options.title).replace('{', "&#123;"))
+            .replace("{{labels}}", &labels)
+            .replace("{{data}}", &data)
+    }
+
+    /// ビューアーに渡す人物・親子・配偶者のデータ（人物は名前順）
+    fn tree_data(tree: &FamilyTree, options: &HtmlViewerOptions) -> Value {
+        let mut persons: Vec<&Person> = tree.persons.values().collect();
+        persons.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        let persons: Vec<Value> = persons
+            .into_iter()
+            .map(|person| {
+                let photo = (options.inline_photos && person.has_own_photo())
+                    .then(|| person.photo_path.as_deref().and_then(photo_data_uri))
+                    .flatten();
+                json!({
+                    "id": person.id.to_string(),
+                    "name": person.name,
+                    "birth": person.birth.as_deref().unwrap_or(""),
+                    "death": person.death.as_deref().unwrap_or(""),
+                    "deceased": options.presumption.is_deceased(person),
+                    "memo": person.memo,
+                    "x": person.position.0,
+                    "y": person.position.1,
+                    "photo": photo,
+                })
+            })
+            .collect();
+        let edges: Vec<Value> = tree
+            .edges
+            .iter()
+            .map(|edge| json!([edge.parent.to_string(), edge.child.to_string()]))
+            .collect();
+        let spouses: Vec<Value> = tree
+            .spouses
+            .iter()
+            .map(|spouse| json!([spouse.person1.to_string(), spouse.person2.to_string(), spouse.divorced]))
+            .collect();
+        json!({ "persons": persons, "edges": edges, "spouses": spouses })
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// ビューアーのひな形（{{title}} / {{labels}} / {{data}} を置き換える）
+const VIEWER_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{title}}</title>
+<style>
+html, body { margin: 0; height: 100%; overflow: hidden; font-family: sans-serif; }
+canvas { display: block; width: 100%; height: 100%; cursor: grab; background: #fff; }
+#bar { position: absolute; top: 8px; left: 8px; display: flex; gap: 8px; align-items: center; }
+#bar input { padding: 4px 6px; }
+#hint { color: #777; font-size: 12px; }
+#detail { position: absolute; top: 8px; right: 8px; width: 260px; max-height: 90%; overflow: auto; display: none;
+  background: #fafafa; border: 1px solid #ccc; border-radius: 6px; padding: 10px; font-size: 14px; }
+#detail img { max-width: 100%; }
+#detail a { color: #06c; cursor: pointer; }
+</style>
+</head>
+<body>
+<canvas id="chart"></canvas>
+<div id="bar"><input id="search" type="search"><span id="hint"></span></div>
+<div id="detail"></div>
+<script type="application/json" id="labels">{{labels}}</script>
+<script type="application/json" id="tree">{{data}}</script>
+<script>
+(function () {
+  const labels = JSON.parse(document.getElementById('labels').textContent);
+  const tree = JSON.parse(document.getElementById('tree').textContent);
+  const canvas = document.getElementById('chart');
+  const ctx = canvas.getContext('2d');
+  const detail = document.getElementById('detail');
+  const byId = new Map(tree.persons.map(p => [p.id, p]));
+  const view = { x: 0, y: 0, zoom: 1 };
+  let selected = null;
+  let match = '';
+
+  document.getElementById('hint').textContent = labels.hint || '';
+  document.getElementById('search').placeholder = labels.search || '';
+
+  for (const p of tree.persons) {
+    if (p.photo) { p.image = new Image(); p.image.onload = draw; p.image.src = p.photo; }
+  }
+
+  function years(p) {
+    const year = d => (d || '').slice(0, 4);
+    if (!p.birth && !p.death) return '';
+    return year(p.birth) + (p.deceased || p.death ? '–' + year(p.death) : '');
+  }
+
+  function layout() {
+    ctx.font = '14px sans-serif';
+    for (const p of tree.persons) {
+      p.w = Math.min(250, Math.max(100, ctx.measureText(p.name).width + 24));
+      p.h = p.image ? 90 : (years(p) ? 42 : 30);
+    }
+  }
+
+  function center(p) { return { x: p.x + p.w / 2, y: p.y + p.h / 2 }; }
+
+  function fit() {
+    if (!tree.persons.length) return;
+    const minX = Math.min(...tree.persons.map(p => p.x)), minY = Math.min(...tree.persons.map(p => p.y));
+    const maxX = Math.max(...tree.persons.map(p => p.x + p.w)), maxY = Math.max(...tree.persons.map(p => p.y + p.h));
+    view.zoom = Math.min(2, Math.min(canvas.width / (maxX - minX + 80), canvas.height / (maxY - minY + 80)));
+    view.x = (canvas.width - (maxX + minX) * view.zoom) / 2;
+    view.y = (canvas.height - (maxY + minY) * view.zoom) / 2;
+  }
+
+  function draw() {
+    ctx.setTransform(1, 0, 0, 1, 0, 0);
+    ctx.clearRect(0, 0, canvas.width, canvas.height);
+    ctx.setTransform(view.zoom, 0, 0, view.zoom, view.x, view.y);
+    ctx.lineWidth = 1.5 / view.zoom;
+    for (const [a, b, divorced] of tree.spouses) {
+      const p = byId.get(a), q = byId.get(b);
+      if (!p || !q) continue;
+      const s = center(p), t = center(q);
+      ctx.strokeStyle = '#c36'; ctx.setLineDash(divorced ? [6, 4] : []);
+      ctx.beginPath(); ctx.moveTo(s.x, s.y); ctx.lineTo(t.x, t.y); ctx.stroke();
+    }
+    ctx.setLineDash([]); ctx.strokeStyle = '#666';
+    for (const [a, b] of tree.edges) {
+      const p = byId.get(a), q = byId.get(b);
+      if (!p || !q) continue;
+      const s = center(p), t = center(q);
+      ctx.beginPath(); ctx.moveTo(s.x, p.y + p.h); ctx.lineTo(t.x, q.y); ctx.stroke();
+    }
+    for (const p of tree.persons) {
+      const hit = match && p.name.toLowerCase().includes(match);
+      ctx.fillStyle = p === selected ? '#ffe9a8' : hit ? '#d8f0d0' : '#f2f4fa';
+      ctx.strokeStyle = p === selected ? '#c90' : '#889';
+      ctx.fillRect(p.x, p.y, p.w, p.h); ctx.strokeRect(p.x, p.y, p.w, p.h);
+      let textY = p.y + 20;
+      if (p.image && p.image.complete) {
+        const size = 52, ratio = p.image.width / p.image.height || 1;
+        const w = ratio > 1 ? size : size * ratio, h = ratio > 1 ? size / ratio : size;
+        ctx.drawImage(p.image, p.x + (p.w - w) / 2, p.y + 4, w, h);
+        textY = p.y + 72;
+      }
+      ctx.fillStyle = '#111'; ctx.textAlign = 'center'; ctx.font = '14px sans-serif';
+      ctx.fillText(p.name, p.x + p.w / 2, textY, p.w - 8);
+      if (!p.image && years(p)) {
+        ctx.fillStyle = '#666'; ctx.font = '11px sans-serif';
+        ctx.fillText(years(p), p.x + p.w / 2, textY + 14);
+      }
+    }
+  }
+
+  function personAt(x, y) {
+    const wx = (x - view.x) / view.zoom, wy = (y - view.y) / view.zoom;
+    return tree.persons.find(p => wx >= p.x && wx <= p.x + p.w && wy >= p.y && wy <= p.y + p.h) || null;
+  }
+
+  function names(ids) {
+    return ids.map(id => byId.get(id)).filter(Boolean)
+      .map(p => '<a data-id="' + p.id + '">' + escape(p.name) + '</a>').join(', ');
+  }
+
+  function escape(text) {
+    return String(text).replace(/[&<>"]/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;' }[c]));
+  }
+
+  function showDetail(p) {
+    selected = p;
+    if (!p) { detail.style.display = 'none'; draw(); return; }
+    const parents = tree.edges.filter(e => e[1] === p.id).map(e => e[0]);
+    const children = tree.edges.filter(e => e[0] === p.id).map(e => e[1]);
+    const spouses = tree.spouses.filter(s => s[0] === p.id || s[1] === p.id).map(s => s[0] === p.id ? s[1] : s[0]);
+    let html = '<h3>' + escape(p.name) + '</h3>';
+    if (p.photo) html += '<img src="' + p.photo + '">';
+    if (p.birth) html += '<div>' + escape(labels.birth) + ': ' + escape(p.birth) + '</div>';
+    if (p.death) html += '<div>' + escape(labels.death) + ': ' + escape(p.death) + '</div>';
+    if (parents.length) html += '<div>' + escape(labels.parents) + ': ' + names(parents) + '</div>';
+    if (spouses.length) html += '<div>' + escape(labels.spouses) + ': ' + names(spouses) + '</div>';
+    if (children.length) html += '<div>' + escape(labels.children) + ': ' + names(children) + '</div>';
+    if (p.memo) html += '<p>' + escape(p.memo).replace(/\n/g, '<br>') + '</p>';
+    detail.innerHTML = html;
+    detail.style.display = 'block';
+    draw();
+  }
+
+  detail.addEventListener('click', e => {
+    const p = byId.get(e.target.dataset && e.target.dataset.id);
+    if (!p) return;
+    const c = center(p);
+    view.x = canvas.width / 2 - c.x * view.zoom; view.y = canvas.height / 2 - c.y * view.zoom;
+    showDetail(p);
+  });
+
+  let drag = null;
+  canvas.addEventListener('mousedown', e => { drag = { x: e.clientX, y: e.clientY, moved: false }; });
+  window.addEventListener('mousemove', e => {
+    if (!drag) return;
+    const dx = e.clientX - drag.x, dy = e.clientY - drag.y;
+    if (Math.abs(dx) + Math.abs(dy) > 2) drag.moved = true;
+    view.x += dx; view.y += dy; drag.x = e.clientX; drag.y = e.clientY;
+    draw();
+  });
+  window.addEventListener('mouseup', e => {
+    if (drag && !drag.moved) showDetail(personAt(e.clientX, e.clientY));
+    drag = null;
+  });
+  canvas.addEventListener('wheel', e => {
+    e.preventDefault();
+    const factor = Math.exp(-e.deltaY * 0.0015);
+    const zoom = Math.min(5, Math.max(0.05, view.zoom * factor));
+    view.x = e.clientX - (e.clientX - view.x) * zoom / view.zoom;
+    view.y = e.clientY - (e.clientY - view.y) * zoom / view.zoom;
+    view.zoom = zoom;
+    draw();
+  }, { passive: false });
+  document.getElementById('search').addEventListener('input', e => {
+    match = e.target.value.trim().toLowerCase();
+    draw();
+  });
+
+  function resize() {
+    canvas.width = window.innerWidth; canvas.height = window.innerHeight;
+    draw();
+  }
+  window.addEventListener('resize', resize);
+  canvas.width = window.innerWidth; canvas.height = window.innerHeight;
+  layout(); fit(); draw();
+})();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    #[test]
+    fn test_render_embeds_tree_data_safely() {
+        let mut tree = FamilyTree::default();
+        let father = tree.add_person("Taro</script>".to_string(), Gender::Male, Some("1950".to_string()), String::new(), false, None, (0.0, 0.0));
+        let child = tree.add_person("Jiro".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 100.0));
+        tree.add_parent_child(father, child, "biological".to_string());
+        let options = HtmlViewerOptions {
+            title: "Sato & family".to_string(),
+            inline_photos: true,
+            labels: vec![("birth", "Born".to_string())],
+            presumption: DeceasedPresumption::default(),
+        };
+
+        let html = HtmlViewerExporter::render(&tree, &options);
+        assert!(html.contains("<title>Sato &amp; family</title>"));
+        assert!(html.contains(r#"{"birth":"Born"}"#));
+        // 名前の "</script>" で埋め込みのスクリプトが終わらない
+        assert_eq!(html.matches("</script>").count(), 3);
+        assert!(html.contains(r#"Taro<\/script>"#));
+        assert!(html.contains(&format!(r#"[["{father}","{child}"]]"#)));
+        // 既定の画像は埋め込まない
+        assert!(html.contains(r#""photo":null"#));
+        assert!(!html.contains(r#""deceased":true"#));
+
+        // 死亡の推定は書き出す人物の生没にも反映する
+        let presumed = HtmlViewerOptions {
+            presumption: DeceasedPresumption { threshold_years: Some(70), current_year: 2026 },
+            ..options
+        };
+        assert_eq!(HtmlViewerExporter::render(&tree, &presumed).matches(r#""deceased":true"#).count(), 1);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fs;
+
+use crate::core::tree::{DeceasedPresumption, FamilyTree, Person};
+use crate::infrastructure::vcard_exporter::{escape_text, fold_line};
+
+/// 予定の UID に付けるドメイン
+const UID_DOMAIN: &str = "family-tree-creator";
+
+/// iCalendar 書き出しの設定
+#[derive(Debug, Clone)]
+pub struct CalendarExportOptions {
+    /// 誕生日の予定名の接頭辞（例: "Birthday"）
+    pub birthday_label: String,
+    /// 結婚記念日の予定名の接頭辞（例: "Wedding anniversary"）
+    pub anniversary_label: String,
+    /// 死亡の記録がない人物を死亡とみなす設定（存命の人物だけを書き出すのに使う）
+    pub presumption: DeceasedPresumption,
+    /// DTSTAMP に使う UTC 日時 "YYYYMMDDTHHMMSSZ"
+    pub dtstamp: String,
+}
+
+/// 存命の人物の誕生日と結婚記念日を、毎年繰り返す終日予定として iCalendar (RFC 5545) に書き出す
+pub struct IcalendarExporter;
+
+impl IcalendarExporter {
+    /// .ics ファイルに書き出し、予定の件数を返す
+    pub fn export(file_path: &str, tree: &FamilyTree, options: &CalendarExportOptions) -> std::io::Result<usize> {
+        let (text, count) = Self::render(tree, options);
+        fs::write(file_path, text)?;
+        Ok(count)
+    }
+
+    /// VCALENDAR 全体の文字列と予定の件数を返す
+    pub fn render(tree: &FamilyTree, options: &CalendarExportOptions) -> (String, usize) {
+        let is_living = |person: &Person| !options.presumption.is_deceased(person);
+
+        let mut events = Vec::new();
+        for person in tree.persons.values().filter(|p| is_living(p)) {
+            if let Some(date) = person.birth.as_deref().and_then(month_day_date) {
+                events.push(CalendarEvent {
+                    uid: format!("birthday-{}@{UID_DOMAIN}", person.id),
+                    summary: format!("{}: {}", options.birthday_label, person.name),
+                    date,
+                });
+            }
+        }
+        for spouse in tree.spouses.iter().filter(|s| !s.divorced) {
+            let (Some(p1), Some(p2)) = (tree.persons.get(&spouse.person1), tree.persons.get(&spouse.person2)) else {
+                continue;
+            };
+            if !is_living(p1) || !is_living(p2) {
+                continue;
+            }
+            if let Some(date) = spouse.marriage_date.as_deref().and_then(month_day_date) {
+                events.push(CalendarEvent {
+                    uid: format!("anniversary-{}-{}@{UID_DOMAIN}", spouse.person1, spouse.person2),
+                    summary: format!("{}: {} & {}", options.anniversary_label, p1.name, p2.name),
+                    date,
+                });
+            }
+        }
+        // 書き出すたびに順序が変わらないよう UID で並べる
+        events.sort_by(|a, b| a.uid.cmp(&b.uid));
+
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:-//{UID_DOMAIN}//Family Tree Creator//EN"),
+            "CALSCALE:GREGORIAN".to_string(),
+        ];
+        for event in &events {
+            event.write_lines(&mut lines, &options.dtstamp);
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        (lines.iter().map(|line| fold_line(line)).collect(), events.len())
+    }
+}
+
+struct CalendarEvent {
+    uid: String,
+    summary: String,
+    date: (i32, u32, u32),
+}
+
+impl CalendarEvent {
+    fn write_lines(&self, lines: &mut Vec<String>, dtstamp: &str) {
+        let (year, month, day) = self.date;
+        // 2月29日生まれは平年には2月末日に表示する
+        let rrule = if (month, day) == (2, 29) {
+            "RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1"
+        } else {
+            "RRULE:FREQ=YEARLY"
+        };
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", self.uid),
+            format!("DTSTAMP:{dtstamp}"),
+            format!("DTSTART;VALUE=DATE:{year:04}{month:02}{day:02}"),
+            rrule.to_string(),
+            format!("SUMMARY:{}", escape_text(&self.summary)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+}
+
+/// "YYYY-MM-DD"（区切りは - / . のいずれか）から年月日を取り出す。月日が無い日付は対象外
+fn month_day_date(date: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = date.trim().split(['-', '/', '.']);
+    let year = parts.next()?.parse::<i32>().ok()?;
+    let month = parts.next()?.parse::<u32>().ok()?;
+    let day = parts.next()?.parse::<u32>().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    chrono::NaiveDate::from_ymd_opt(year, month, day).map(|_| (year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    fn options() -> CalendarExportOptions {
+        CalendarExportOptions {
+            birthday_label: "Birthday".to_string(),
+            anniversary_label: "Wedding anniversary".to_string(),
+            presumption: DeceasedPresumption { threshold_years: Some(110), current_year: 2026 },
+            dtstamp: "20260101T000000Z".to_string(),
+        }
+    }
+
+    fn add(tree: &mut FamilyTree, name: &str, birth: Option<&str>, deceased: bool) -> uuid::Uuid {
+        tree.add_person(
+            name.to_string(),
+            Gender::Unknown,
+            birth.map(str::to_string),
+            String::new(),
+            deceased,
+            None,
+            (0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn render_includes_only_living_persons_with_full_birth_dates() {
+        let mut tree = FamilyTree::default();
+        add(&mut tree, "Alice", Some("1980-05-17"), false);
+        add(&mut tree, "Bob", Some("1950-01-01"), true);
+        add(&mut tree, "Carol", Some("1890-03-03"), false);
+        add(&mut tree, "Dave", Some("1985"), false);
+
+        let (ics, count) = IcalendarExporter::render(&tree, &options());
+
+        assert_eq!(count, 1);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:19800517\r\nRRULE:FREQ=YEARLY\r\n"));
+        assert!(ics.contains("SUMMARY:Birthday: Alice\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn render_includes_anniversaries_of_living_married_couples() {
+        let mut tree = FamilyTree::default();
+        let a = add(&mut tree, "Alice", None, false);
+        let b = add(&mut tree, "Bob", None, false);
+        let c = add(&mut tree, "Carol", None, true);
+        tree.add_spouse(a, b, String::new());
+        tree.add_spouse(b, c, String::new());
+        for spouse in &mut tree.spouses {
+            spouse.marriage_date = Some("2000-02-29".to_string());
+        }
+
+        let (ics, count) = IcalendarExporter::render(&tree, &options());
+
+        assert_eq!(count, 1);
+        assert!(ics.contains("SUMMARY:Wedding anniversary: Alice & Bob\r\n"));
+        assert!(ics.contains("RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1\r\n"));
+    }
+
+    #[test]
+    fn month_day_date_rejects_partial_and_invalid_dates() {
+        assert_eq!(month_day_date("1980/5/17"), Some((1980, 5, 17)));
+        assert_eq!(month_day_date("1980-05"), None);
+        assert_eq!(month_day_date("1980-02-30"), None);
+    }
+}
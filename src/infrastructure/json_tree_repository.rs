@@ -1,23 +1,147 @@
 use std::fs;
 
+use serde_json::{json, Map, Value};
+
 use crate::application::{TreeRepository, TreeRepositoryError};
-use crate::core::tree::FamilyTree;
+use crate::core::tree::{FamilyTree, CURRENT_FORMAT_VERSION};
 
 /// `FamilyTree`をJSONファイルとして保存・読込するリポジトリ実装。
 pub struct JsonTreeRepository;
 
+/// `format_version` が i のファイルを i + 1 に上げる移行処理（添字がそのまま移行元のバージョン）
+const MIGRATIONS: [fn(&mut Map<String, Value>); CURRENT_FORMAT_VERSION as usize] = [migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// JSONの内容を現在の形式に移行してから`FamilyTree`に変換する。
+pub fn tree_from_json_str(content: &str) -> Result<FamilyTree, TreeRepositoryError> {
+    let value = serde_json::from_str::<Value>(content)
+        .map_err(|error| TreeRepositoryError::Deserialize(error.to_string()))?;
+    tree_from_json_value(value)
+}
+
+/// JSONの値を現在の形式に移行してから`FamilyTree`に変換する（JSON以外のテキスト形式と共通）。
+pub fn tree_from_json_value(mut value: Value) -> Result<FamilyTree, TreeRepositoryError> {
+    migrate(&mut value)?;
+    let mut tree = serde_json::from_value::<FamilyTree>(value)
+        .map_err(|error| TreeRepositoryError::Deserialize(error.to_string()))?;
+    tree.sync_unions();
+    Ok(tree)
+}
+
+/// `FamilyTree`を差分が最小になる正規化済みJSONに変換する。
+///
+/// `Value`を経由することで、`HashMap`の人物・イベントはUUID順に、各フィールドはキー名順に並ぶ。
+/// 保存のたびに順序が入れ替わらないので、ファイルをgitで管理しても変更箇所だけが差分になる。
+pub fn tree_to_canonical_json(tree: &FamilyTree) -> Result<String, TreeRepositoryError> {
+    let mut tree = tree.clone();
+    tree.format_version = saved_format_version(&tree);
+    let value = serde_json::to_value(&tree)
+        .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+    let mut serialized = serde_json::to_string_pretty(&value)
+        .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+    serialized.push('\n');
+    Ok(serialized)
+}
+
+/// 保存するときの形式バージョン（新しい版で作られたファイルは、知らない項目を残したまま版を下げない）
+pub fn saved_format_version(tree: &FamilyTree) -> u32 {
+    tree.format_version.max(CURRENT_FORMAT_VERSION)
+}
+
+/// 古い形式のJSONを1バージョンずつ現在の形式まで移行する。
+///
+/// 新しい版で作られたファイルは移行せずに読み、知らない項目は`extra`に残す。
+fn migrate(value: &mut Value) -> Result<(), TreeRepositoryError> {
+    let root = value
+        .as_object_mut()
+        .ok_or_else(|| TreeRepositoryError::Deserialize("root must be an object".to_string()))?;
+
+    let version = match root.get("format_version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| TreeRepositoryError::Deserialize("invalid format_version".to_string()))?,
+    };
+    if version > CURRENT_FORMAT_VERSION as u64 {
+        u32::try_from(version)
+            .map_err(|_| TreeRepositoryError::Deserialize("invalid format_version".to_string()))?;
+        return Ok(());
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(root);
+    }
+    root.insert("format_version".to_string(), json!(CURRENT_FORMAT_VERSION));
+    Ok(())
+}
+
+/// バージョン導入前（v0）のファイルを v1 に移行する。
+///
+/// - `position` が `Option<(f32, f32)>` だった頃の `null` / 欠落を原点に置き換える
+/// - 必須になった `memo` / `kind` を補う
+/// - 後から追加された配偶者・家族・イベント等のコレクションを空で補う
+fn migrate_v0_to_v1(root: &mut Map<String, Value>) {
+    if let Some(persons) = root.get_mut("persons").and_then(Value::as_object_mut) {
+        for person in persons.values_mut().filter_map(Value::as_object_mut) {
+            if person.get("position").is_none_or(Value::is_null) {
+                person.insert("position".to_string(), json!([0.0, 0.0]));
+            }
+            person.entry("memo").or_insert_with(|| json!(""));
+        }
+    }
+    for edge in root
+        .get_mut("edges")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+    {
+        edge.entry("kind").or_insert_with(|| json!("biological"));
+    }
+    for spouse in root
+        .get_mut("spouses")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+    {
+        spouse.entry("memo").or_insert_with(|| json!(""));
+    }
+
+    root.entry("persons").or_insert_with(|| json!({}));
+    for key in ["edges", "spouses", "families", "event_relations", "person_relations", "annotations", "sticky_notes"] {
+        root.entry(key).or_insert_with(|| json!([]));
+    }
+    root.entry("events").or_insert_with(|| json!({}));
+}
+
+/// v1 のファイルを v2 に移行する。
+///
+/// v2 で追加したレイアウトの保存・カメラのブックマーク・ガイド線・地名辞典・マクロを空で補う。
+/// 人物やイベントに追加した項目（出生地・バッジ・固定など）は省略時の既定値で読める。
+fn migrate_v1_to_v2(root: &mut Map<String, Value>) {
+    for key in ["layout_snapshots", "camera_bookmarks", "guides", "places", "macros"] {
+        root.entry(key).or_insert_with(|| json!([]));
+    }
+}
+
+/// v2 のファイルを v3 に移行する。
+///
+/// v3 で保存するようにした家族の単位を空で補う。単位と親子関係からの参照は、読み込んだ後の
+/// `FamilyTree::sync_unions` で親子関係から組み立てる。
+fn migrate_v2_to_v3(root: &mut Map<String, Value>) {
+    root.entry("unions").or_insert_with(|| json!([]));
+}
+
 impl TreeRepository for JsonTreeRepository {
     fn load(&self, file_path: &str) -> Result<FamilyTree, TreeRepositoryError> {
         let content = fs::read_to_string(file_path)
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        serde_json::from_str::<FamilyTree>(&content)
-            .map_err(|error| TreeRepositoryError::Deserialize(error.to_string()))
+        tree_from_json_str(&content)
     }
 
     fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
-        let serialized = serde_json::to_string_pretty(tree)
-            .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+        let serialized = tree_to_canonical_json(tree)?;
 
         fs::write(file_path, serialized)
             .map_err(|error| TreeRepositoryError::Write(error.to_string()))
@@ -31,9 +155,12 @@ mod tests {
 
     use uuid::Uuid;
 
-    use super::JsonTreeRepository;
+    use super::{tree_from_json_str, tree_to_canonical_json, JsonTreeRepository};
     use crate::application::TreeRepository;
-    use crate::core::tree::FamilyTree;
+    use crate::core::tree::{FamilyTree, CURRENT_FORMAT_VERSION};
+
+    const PERSON_ID: &str = "6f9619ff-8b86-d011-b42d-00cf4fc964ff";
+    const CHILD_ID: &str = "7f9619ff-8b86-d011-b42d-00cf4fc964ff";
 
     #[test]
     fn save_and_load_round_trip() {
@@ -50,8 +177,169 @@ mod tests {
         assert!(loaded_tree_result.is_ok());
         let loaded_tree = loaded_tree_result.expect("json file should load");
         assert_eq!(loaded_tree.persons.len(), 0);
+        assert_eq!(loaded_tree.format_version, CURRENT_FORMAT_VERSION);
 
         let remove_result = fs::remove_file(file_path);
         assert!(remove_result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn migrates_optional_position_era() {
+        let content = format!(
+            r#"{{
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "memo": "", "position": null }},
+                    "{CHILD_ID}": {{ "id": "{CHILD_ID}", "name": "B", "birth": null, "memo": "" }}
+                }},
+                "edges": []
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("legacy file should load");
+        assert_eq!(tree.format_version, CURRENT_FORMAT_VERSION);
+        assert!(tree.persons.values().all(|person| person.position == (0.0, 0.0)));
+    }
+
+    #[test]
+    fn migrates_files_without_events_and_relations() {
+        let content = format!(
+            r#"{{
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "position": [10.0, 20.0] }},
+                    "{CHILD_ID}": {{ "id": "{CHILD_ID}", "name": "B", "birth": null, "position": [10.0, 120.0] }}
+                }},
+                "edges": [{{ "parent": "{PERSON_ID}", "child": "{CHILD_ID}" }}],
+                "spouses": [{{ "person1": "{PERSON_ID}", "person2": "{CHILD_ID}" }}]
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("legacy file should load");
+        assert!(tree.events.is_empty());
+        assert!(tree.families.is_empty());
+        assert_eq!(tree.edges[0].kind, "biological");
+        assert_eq!(tree.spouses[0].memo, "");
+        assert!(tree.persons.values().all(|person| person.memo.is_empty()));
+    }
+
+    #[test]
+    fn migrates_v1_files_to_v2() {
+        let content = format!(
+            r#"{{
+                "format_version": 1,
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "memo": "", "position": [10.0, 20.0] }}
+                }},
+                "edges": []
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("v1 file should load");
+        assert_eq!(tree.format_version, CURRENT_FORMAT_VERSION);
+        let person = &tree.persons[&Uuid::parse_str(PERSON_ID).unwrap()];
+        assert_eq!(person.birth_place, None);
+        assert!(!person.pinned);
+        assert!(tree.places.is_empty() && tree.macros.is_empty() && tree.guides.is_empty());
+
+        let saved: serde_json::Value = serde_json::from_str(&tree_to_canonical_json(&tree).unwrap()).unwrap();
+        assert_eq!(saved["format_version"], serde_json::json!(CURRENT_FORMAT_VERSION));
+        assert_eq!(saved["places"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn migrates_v2_files_to_v3() {
+        let content = format!(
+            r#"{{
+                "format_version": 2,
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "memo": "", "position": [0.0, 0.0] }},
+                    "{CHILD_ID}": {{ "id": "{CHILD_ID}", "name": "B", "birth": null, "memo": "", "position": [0.0, 100.0] }}
+                }},
+                "edges": [{{ "parent": "{PERSON_ID}", "child": "{CHILD_ID}", "kind": "biological" }}]
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("v2 file should load");
+        assert_eq!(tree.format_version, CURRENT_FORMAT_VERSION);
+        assert_eq!(tree.unions().len(), 1);
+        assert_eq!(tree.unions()[0].partners, [Uuid::parse_str(PERSON_ID).unwrap()]);
+        assert_eq!(tree.edges[0].union, Some(tree.unions()[0].id));
+
+        // 保存し直して読み込んでも単位のIDは変わらない
+        let reloaded = tree_from_json_str(&tree_to_canonical_json(&tree).unwrap()).unwrap();
+        assert_eq!(reloaded.unions(), tree.unions());
+        assert_eq!(reloaded.edges[0].union, tree.edges[0].union);
+    }
+
+    #[test]
+    fn loads_newer_format_version_keeping_unknown_fields() {
+        let newer = CURRENT_FORMAT_VERSION + 1;
+        let content = format!(
+            r#"{{
+                "format_version": {newer},
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "memo": "", "pronouns": "they" }}
+                }},
+                "edges": [],
+                "timeline_layers": [{{ "name": "Migration" }}]
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("newer file should load");
+        assert_eq!(tree.format_version, newer);
+        let person_id = Uuid::parse_str(PERSON_ID).unwrap();
+        assert_eq!(tree.persons[&person_id].extra["pronouns"], "they");
+
+        // 保存し直しても版を下げず、知らない項目も残す
+        let saved: serde_json::Value = serde_json::from_str(&tree_to_canonical_json(&tree).unwrap()).unwrap();
+        assert_eq!(saved["format_version"], serde_json::json!(newer));
+        assert_eq!(saved["persons"][PERSON_ID]["pronouns"], "they");
+        assert_eq!(saved["timeline_layers"][0]["name"], "Migration");
+    }
+
+    #[test]
+    fn canonical_json_sorts_persons_by_id_regardless_of_insertion_order() {
+        let content = format!(
+            r#"{{ "persons": {{
+                "{CHILD_ID}": {{ "id": "{CHILD_ID}", "name": "B", "birth": null, "position": [0.0, 0.0] }},
+                "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "position": [0.0, 0.0] }}
+            }} }}"#
+        );
+        let tree = tree_from_json_str(&content).expect("file should load");
+        // HashMap を作り直すとハッシュの種が変わり、走査順も変わりうる
+        let mut reordered = tree.clone();
+        reordered.persons = tree.persons.iter().map(|(id, person)| (*id, person.clone())).collect();
+
+        let first = tree_to_canonical_json(&tree).expect("should serialize");
+        let second = tree_to_canonical_json(&reordered).expect("should serialize");
+        assert_eq!(first, second);
+        assert!(first.find(PERSON_ID).unwrap() < first.find(CHILD_ID).unwrap());
+        assert!(first.ends_with("}\n"));
+    }
+
+    #[test]
+    fn keeps_unknown_fields_on_round_trip() {
+        let content = format!(
+            r#"{{
+                "format_version": {CURRENT_FORMAT_VERSION},
+                "persons": {{
+                    "{PERSON_ID}": {{ "id": "{PERSON_ID}", "name": "A", "birth": null, "memo": "", "nickname": "Al" }},
+                    "{CHILD_ID}": {{ "id": "{CHILD_ID}", "name": "B", "birth": null, "memo": "" }}
+                }},
+                "edges": [{{ "parent": "{PERSON_ID}", "child": "{CHILD_ID}", "kind": "biological", "x-source": {{ "page": 12 }} }}],
+                "plugin_settings": {{ "theme": "sepia" }}
+            }}"#
+        );
+
+        let tree = tree_from_json_str(&content).expect("file should load");
+        let person_id = Uuid::parse_str(PERSON_ID).unwrap();
+        assert_eq!(tree.persons[&person_id].extra["nickname"], "Al");
+        assert!(tree.persons[&Uuid::parse_str(CHILD_ID).unwrap()].extra.is_empty());
+
+        let saved = tree_to_canonical_json(&tree).expect("should serialize");
+        let value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        assert_eq!(value["persons"][PERSON_ID]["nickname"], "Al");
+        assert_eq!(value["edges"][0]["x-source"]["page"], 12);
+        assert_eq!(value["plugin_settings"]["theme"], "sepia");
+        assert_eq!(tree_from_json_str(&saved).expect("saved file should load").extra, tree.extra);
+    }
+}
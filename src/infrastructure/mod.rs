@@ -1,9 +1,42 @@
+pub mod activity_log_store;
+pub mod error_log_file;
+pub mod gedcom_dialect;
+pub mod gedcom_tree_repository;
+pub mod gramps_xml_repository;
+pub mod html_viewer_exporter;
+pub mod icalendar_exporter;
 pub mod image_metadata;
 pub mod json_tree_repository;
 pub mod multi_format_tree_repository;
+pub mod photo_editor;
+pub mod photo_integrity;
 pub mod photo_texture_cache;
+pub mod recovery_snapshot;
+pub mod source_path_key;
 pub mod sqlite_tree_repository;
+pub mod svg_chart_exporter;
+pub mod system_fonts;
+pub mod text_chart_exporter;
+pub mod thumbnail_cache;
+pub mod tree_backup_store;
+pub mod tree_file_lock;
+pub mod vcard_exporter;
+pub mod yaml_tree_repository;
 
+pub use activity_log_store::{ActivityLogRecord, ActivityLogStore};
+pub use error_log_file::ErrorLogFile;
+pub use html_viewer_exporter::{HtmlViewerExporter, HtmlViewerOptions};
+pub use icalendar_exporter::{CalendarExportOptions, IcalendarExporter};
 pub use image_metadata::read_image_dimensions;
-pub use multi_format_tree_repository::MultiFormatTreeRepository;
+pub use multi_format_tree_repository::{MultiFormatTreeRepository, OverwriteWarning, StorageFormat};
+pub use photo_editor::PhotoEdit;
+pub use photo_integrity::{clear_photos, find_broken_photos, relocate_photos, BrokenPhoto, PhotoProblem};
 pub use photo_texture_cache::PhotoTextureCache;
+pub use recovery_snapshot::{RecoverySnapshot, RecoverySnapshotStore};
+pub use svg_chart_exporter::SvgChartExporter;
+pub use system_fonts::load_system_cjk_font;
+pub use text_chart_exporter::{TextChartExporter, TextChartFormat, PEDIGREE_GENERATION_CHOICES};
+pub use thumbnail_cache::ThumbnailCache;
+pub use tree_backup_store::TreeBackupStore;
+pub use tree_file_lock::{LockOwner, TreeFileLock, TreeFileLockError};
+pub use vcard_exporter::VcardExporter;
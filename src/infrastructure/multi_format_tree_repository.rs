@@ -1,15 +1,31 @@
+use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 
 use crate::application::{TreeRepository, TreeRepositoryError};
 use crate::core::tree::FamilyTree;
 
+use super::gedcom_dialect::decode_gedcom;
+use super::gedcom_tree_repository::GedcomTreeRepository;
+use super::gramps_xml_repository::{GrampsXmlRepository, GZIP_MAGIC};
 use super::json_tree_repository::JsonTreeRepository;
 use super::sqlite_tree_repository::SqliteTreeRepository;
+use super::yaml_tree_repository::{YamlTreeRepository, YAML_DOCUMENT_START};
 
-/// ファイル拡張子に応じてJSON/SQLiteを切り替えるリポジトリ。
+/// SQLiteデータベースファイルの先頭16バイト
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+/// 形式の判別に読むファイル先頭のバイト数
+const SNIFF_LENGTH: usize = 64;
+/// 別の家系図だと警告するときに示す人物の数
+const OVERWRITE_SAMPLE_NAMES: usize = 3;
+
+/// 読込時はファイル内容から、保存時はファイル拡張子からJSON/YAML/SQLite/Gramps XML/GEDCOMを切り替えるリポジトリ。
 pub struct MultiFormatTreeRepository {
     json_repository: JsonTreeRepository,
+    yaml_repository: YamlTreeRepository,
     sqlite_repository: SqliteTreeRepository,
+    gramps_repository: GrampsXmlRepository,
+    gedcom_repository: GedcomTreeRepository,
 }
 
 impl MultiFormatTreeRepository {
@@ -17,7 +33,10 @@ impl MultiFormatTreeRepository {
     pub fn new() -> Self {
         Self {
             json_repository: JsonTreeRepository,
+            yaml_repository: YamlTreeRepository,
             sqlite_repository: SqliteTreeRepository,
+            gramps_repository: GrampsXmlRepository,
+            gedcom_repository: GedcomTreeRepository,
         }
     }
 
@@ -29,9 +48,101 @@ impl MultiFormatTreeRepository {
 
         match extension.as_deref() {
             Some("db") | Some("sqlite") => StorageFormat::Sqlite,
+            Some("yaml") | Some("yml") => StorageFormat::Yaml,
+            Some("gramps") => StorageFormat::Gramps,
+            Some("ged") => StorageFormat::Gedcom,
             _ => StorageFormat::Json,
         }
     }
+
+    /// ファイル先頭の内容から形式を判別する（判別できなければ`None`）。
+    pub fn sniff_format(file_path: &str) -> Option<StorageFormat> {
+        let mut head = Vec::with_capacity(SNIFF_LENGTH);
+        File::open(file_path)
+            .ok()?
+            .take(SNIFF_LENGTH as u64)
+            .read_to_end(&mut head)
+            .ok()?;
+        Self::sniff_bytes(&head)
+    }
+
+    fn sniff_bytes(head: &[u8]) -> Option<StorageFormat> {
+        if head.starts_with(SQLITE_MAGIC) {
+            return Some(StorageFormat::Sqlite);
+        }
+        // gzip圧縮された家系図ファイルは Gramps XML だけ
+        if head.starts_with(GZIP_MAGIC) {
+            return Some(StorageFormat::Gramps);
+        }
+
+        // UTF-8 BOM と先頭の空白は読み飛ばす
+        let text = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+        let start = text.iter().position(|byte| !byte.is_ascii_whitespace())?;
+        let text = &text[start..];
+        if text.starts_with(b"{") {
+            Some(StorageFormat::Json)
+        } else if text.starts_with(b"<?xml") || text.starts_with(b"<database") {
+            Some(StorageFormat::Gramps)
+        } else if text.starts_with(YAML_DOCUMENT_START.as_bytes()) {
+            Some(StorageFormat::Yaml)
+        } else {
+            // GEDCOM は読込と同じ文字コードの判別（UTF-16・ANSEL など）を通してから見る
+            let (text, _) = decode_gedcom(head);
+            text.trim_start_matches('\u{feff}')
+                .trim_start()
+                .starts_with("0 HEAD")
+                .then_some(StorageFormat::Gedcom)
+        }
+    }
+
+    /// 読込時の形式（内容から判別できなければ拡張子で決める）。
+    pub fn load_format(file_path: &str) -> StorageFormat {
+        Self::sniff_format(file_path).unwrap_or_else(|| Self::detect_format(file_path))
+    }
+
+    /// 保存先に既にあるファイルを上書きしてよいか調べ、確認が必要なら理由を返す。
+    ///
+    /// 保存する形式（拡張子）と中身の形式が違う場合、同じ形式でも読み込めない場合と、
+    /// 保存する家系図と共通の人物がいない場合に警告する。
+    pub fn check_overwrite(&self, file_path: &str, tree: &FamilyTree) -> Option<OverwriteWarning> {
+        let size = std::fs::metadata(file_path).ok()?.len();
+        if size == 0 {
+            return None;
+        }
+        let target = Self::detect_format(file_path);
+        let Some(existing) = Self::sniff_format(file_path) else {
+            return Some(OverwriteWarning::UnknownContent);
+        };
+        if existing != target {
+            return Some(OverwriteWarning::DifferentFormat { existing, target });
+        }
+
+        let existing_tree = match self.load(file_path) {
+            Ok(tree) => tree,
+            Err(error) => return Some(OverwriteWarning::Unreadable { error: error.to_string() }),
+        };
+        let shares_person = existing_tree.persons.keys().any(|id| tree.persons.contains_key(id));
+        if existing_tree.persons.is_empty() || tree.persons.is_empty() || shares_person {
+            return None;
+        }
+        let mut names: Vec<String> = existing_tree.persons.values().map(|person| person.name.clone()).collect();
+        names.sort();
+        names.truncate(OVERWRITE_SAMPLE_NAMES);
+        Some(OverwriteWarning::OtherTree { persons: existing_tree.persons.len(), names })
+    }
+}
+
+/// 既存のファイルを上書きする前に確認が必要な理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverwriteWarning {
+    /// 中身が家系図ファイルとして判別できない
+    UnknownContent,
+    /// 中身の形式が保存する形式と違う
+    DifferentFormat { existing: StorageFormat, target: StorageFormat },
+    /// 形式は同じだが、壊れているなどで読み込めない（読み込み時のエラー）
+    Unreadable { error: String },
+    /// 保存する家系図と共通の人物がいない別の家系図（人数と名前の例）
+    OtherTree { persons: usize, names: Vec<String> },
 }
 
 impl Default for MultiFormatTreeRepository {
@@ -42,21 +153,161 @@ impl Default for MultiFormatTreeRepository {
 
 impl TreeRepository for MultiFormatTreeRepository {
     fn load(&self, file_path: &str) -> Result<FamilyTree, TreeRepositoryError> {
-        match Self::detect_format(file_path) {
+        match Self::load_format(file_path) {
             StorageFormat::Json => self.json_repository.load(file_path),
+            StorageFormat::Yaml => self.yaml_repository.load(file_path),
             StorageFormat::Sqlite => self.sqlite_repository.load(file_path),
+            StorageFormat::Gramps => self.gramps_repository.load(file_path),
+            StorageFormat::Gedcom => self.gedcom_repository.load(file_path),
         }
     }
 
     fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
         match Self::detect_format(file_path) {
             StorageFormat::Json => self.json_repository.save(file_path, tree),
+            StorageFormat::Yaml => self.yaml_repository.save(file_path, tree),
             StorageFormat::Sqlite => self.sqlite_repository.save(file_path, tree),
+            StorageFormat::Gramps => self.gramps_repository.save(file_path, tree),
+            StorageFormat::Gedcom => self.gedcom_repository.save(file_path, tree),
         }
     }
 }
 
-enum StorageFormat {
+/// 家系図ファイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageFormat {
     Json,
+    Yaml,
     Sqlite,
+    Gramps,
+    Gedcom,
+}
+
+impl StorageFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageFormat::Json => "JSON",
+            StorageFormat::Yaml => "YAML",
+            StorageFormat::Sqlite => "SQLite",
+            StorageFormat::Gramps => "Gramps XML",
+            StorageFormat::Gedcom => "GEDCOM",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use super::{MultiFormatTreeRepository, OverwriteWarning, StorageFormat};
+    use crate::application::TreeRepository;
+    use crate::core::tree::FamilyTree;
+
+    #[test]
+    fn sniffs_format_from_leading_bytes() {
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"SQLite format 3\0rest"),
+            Some(StorageFormat::Sqlite)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"\xEF\xBB\xBF\n  {\"persons\": {}}"),
+            Some(StorageFormat::Json)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"0 HEAD\n1 SOUR X"),
+            Some(StorageFormat::Gedcom)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"---\nformat_version: 1"),
+            Some(StorageFormat::Yaml)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"<?xml version=\"1.0\"?>\n<database>"),
+            Some(StorageFormat::Gramps)
+        );
+        assert_eq!(MultiFormatTreeRepository::sniff_bytes(&[0x1f, 0x8b, 0x08]), Some(StorageFormat::Gramps));
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"\xFF\xFE0\0 \0H\0E\0A\0D\0"),
+            Some(StorageFormat::Gedcom)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"\x000\x00 \x00H\x00E\x00A\x00D\x00\n"),
+            Some(StorageFormat::Gedcom)
+        );
+        assert_eq!(
+            MultiFormatTreeRepository::sniff_bytes(b"0 HEAD\n1 CHAR ANSEL\n1 NOTE M\xE2uller"),
+            Some(StorageFormat::Gedcom)
+        );
+        assert_eq!(MultiFormatTreeRepository::sniff_bytes(b"plain text"), None);
+        assert_eq!(MultiFormatTreeRepository::sniff_bytes(b""), None);
+    }
+
+    #[test]
+    fn loads_misnamed_sqlite_file() {
+        let repository = MultiFormatTreeRepository::new();
+        let sqlite_path = env::temp_dir().join(format!("family_tree_sniff_{}.sqlite", Uuid::new_v4()));
+        let misnamed_path = sqlite_path.with_extension("json");
+        let mut tree = FamilyTree::default();
+        tree.add_person(
+            "Taro".to_string(),
+            crate::core::tree::Gender::Male,
+            None,
+            String::new(),
+            false,
+            None,
+            (0.0, 0.0),
+        );
+
+        repository
+            .save(&sqlite_path.to_string_lossy(), &tree)
+            .expect("sqlite file should save");
+        fs::rename(&sqlite_path, &misnamed_path).expect("rename should succeed");
+
+        let misnamed = misnamed_path.to_string_lossy().to_string();
+        assert_eq!(MultiFormatTreeRepository::load_format(&misnamed), StorageFormat::Sqlite);
+        let loaded = repository.load(&misnamed).expect("misnamed sqlite file should load");
+        assert_eq!(loaded.persons.len(), 1);
+
+        let _ = fs::remove_file(misnamed_path);
+    }
+
+    #[test]
+    fn warns_before_overwriting_other_files() {
+        let repository = MultiFormatTreeRepository::new();
+        let base = env::temp_dir().join(format!("family_tree_overwrite_{}", Uuid::new_v4()));
+        let json_path = base.with_extension("json").to_string_lossy().to_string();
+        let sqlite_path = base.with_extension("sqlite").to_string_lossy().to_string();
+        let mut tree = FamilyTree::default();
+        tree.add_person("Taro".to_string(), crate::core::tree::Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+
+        // 存在しないファイルと自分の家系図のファイルは警告しない
+        assert_eq!(repository.check_overwrite(&json_path, &tree), None);
+        repository.save(&json_path, &tree).unwrap();
+        assert_eq!(repository.check_overwrite(&json_path, &tree), None);
+
+        let mut other = FamilyTree::default();
+        other.add_person("Hanako".to_string(), crate::core::tree::Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        assert_eq!(
+            repository.check_overwrite(&json_path, &other),
+            Some(OverwriteWarning::OtherTree { persons: 1, names: vec!["Taro".to_string()] })
+        );
+
+        // JSON の中身を .sqlite として保存しようとした場合
+        fs::copy(&json_path, &sqlite_path).unwrap();
+        assert_eq!(
+            repository.check_overwrite(&sqlite_path, &tree),
+            Some(OverwriteWarning::DifferentFormat { existing: StorageFormat::Json, target: StorageFormat::Sqlite })
+        );
+        // 形式は合っていても読み込めないファイル
+        fs::write(&json_path, "{ \"persons\": [] }").unwrap();
+        assert!(matches!(repository.check_overwrite(&json_path, &tree), Some(OverwriteWarning::Unreadable { .. })));
+        fs::write(&sqlite_path, "notes").unwrap();
+        assert_eq!(repository.check_overwrite(&sqlite_path, &tree), Some(OverwriteWarning::UnknownContent));
+
+        let _ = fs::remove_file(json_path);
+        let _ = fs::remove_file(sqlite_path);
+    }
 }
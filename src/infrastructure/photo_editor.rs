@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::DynamicImage;
+
+/// 写真に加える編集（回転してから切り抜く）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhotoEdit {
+    /// 時計回りの90度回転の回数（0〜3）
+    pub quarter_turns: u8,
+    /// 回転後の画像に対する切り抜き範囲（0.0〜1.0 の比率で [左, 上, 右, 下]）
+    pub crop: [f32; 4],
+}
+
+impl Default for PhotoEdit {
+    fn default() -> Self {
+        Self {
+            quarter_turns: 0,
+            crop: [0.0, 0.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl PhotoEdit {
+    pub fn rotate_clockwise(&mut self) {
+        self.quarter_turns = (self.quarter_turns + 1) % 4;
+        self.reset_crop();
+    }
+
+    pub fn rotate_counterclockwise(&mut self) {
+        self.quarter_turns = (self.quarter_turns + 3) % 4;
+        self.reset_crop();
+    }
+
+    pub fn reset_crop(&mut self) {
+        self.crop = Self::default().crop;
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 画像を回転・切り抜きする
+pub fn apply_photo_edit(image: &DynamicImage, edit: &PhotoEdit) -> DynamicImage {
+    let rotated = rotate_quarter_turns(image, edit.quarter_turns);
+    let (width, height) = (rotated.width() as f32, rotated.height() as f32);
+    let [left, top, right, bottom] = edit.crop.map(|value| value.clamp(0.0, 1.0));
+    let x = ((left.min(right) * width).round() as u32).min(rotated.width() - 1);
+    let y = ((top.min(bottom) * height).round() as u32).min(rotated.height() - 1);
+    let crop_width = (((right - left).abs() * width).round() as u32).clamp(1, rotated.width() - x);
+    let crop_height = (((bottom - top).abs() * height).round() as u32).clamp(1, rotated.height() - y);
+    rotated.crop_imm(x, y, crop_width, crop_height)
+}
+
+/// 時計回りに90度単位で回転する
+pub fn rotate_quarter_turns(image: &DynamicImage, quarter_turns: u8) -> DynamicImage {
+    match quarter_turns % 4 {
+        1 => image.rotate90(),
+        2 => image.rotate180(),
+        3 => image.rotate270(),
+        _ => image.clone(),
+    }
+}
+
+/// 写真を編集して管理フォルダに PNG で保存し、保存先のパスを返す
+///
+/// 元のファイルは変更しない。ファイル名は元の名前に短いIDを付けて重複を避ける。
+pub fn save_edited_photo(source_path: &str, edit: &PhotoEdit, output_dir: &Path) -> Result<PathBuf, String> {
+    let image = image::open(source_path).map_err(|error| error.to_string())?;
+    let edited = apply_photo_edit(&image, edit);
+
+    fs::create_dir_all(output_dir).map_err(|error| error.to_string())?;
+    let stem = Path::new(source_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "photo".to_string());
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let output_path = output_dir.join(format!("{stem}_{}.png", &suffix[..8]));
+    edited.save(&output_path).map_err(|error| error.to_string())?;
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn sample_image() -> DynamicImage {
+        // 幅4・高さ2、左上だけ赤
+        let mut image = RgbaImage::from_pixel(4, 2, Rgba([0, 0, 0, 255]));
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        DynamicImage::ImageRgba8(image)
+    }
+
+    #[test]
+    fn rotation_swaps_dimensions_and_moves_pixels() {
+        let edit = PhotoEdit { quarter_turns: 1, ..PhotoEdit::default() };
+        let rotated = apply_photo_edit(&sample_image(), &edit);
+        assert_eq!((rotated.width(), rotated.height()), (2, 4));
+        // 時計回りに回すと左上は右上に移る
+        assert_eq!(rotated.to_rgba8().get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn crop_uses_fractions_of_rotated_image() {
+        let edit = PhotoEdit { quarter_turns: 0, crop: [0.0, 0.0, 0.5, 0.5] };
+        let cropped = apply_photo_edit(&sample_image(), &edit);
+        assert_eq!((cropped.width(), cropped.height()), (2, 1));
+        assert_eq!(cropped.to_rgba8().get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn rotating_resets_crop() {
+        let mut edit = PhotoEdit { quarter_turns: 0, crop: [0.1, 0.1, 0.5, 0.5] };
+        edit.rotate_counterclockwise();
+        assert_eq!(edit.quarter_turns, 3);
+        assert_eq!(edit.crop, [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn save_edited_photo_writes_png_into_output_dir() {
+        let dir = std::env::temp_dir().join(format!("family_tree_photo_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("portrait.png");
+        sample_image().save(&source).unwrap();
+
+        let edit = PhotoEdit { quarter_turns: 2, ..PhotoEdit::default() };
+        let saved = save_edited_photo(&source.to_string_lossy(), &edit, &dir.join("managed")).unwrap();
+
+        assert!(saved.starts_with(dir.join("managed")));
+        let saved_image = image::open(&saved).unwrap();
+        assert_eq!((saved_image.width(), saved_image.height()), (4, 2));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
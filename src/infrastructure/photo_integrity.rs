@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::tree::{FamilyTree, PersonDisplayMode, PersonId, DEFAULT_PHOTO_PATH};
+
+/// 付け直しのときにたどるフォルダの深さ
+const RELOCATE_MAX_DEPTH: usize = 8;
+
+/// 写真を表示できない理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoProblem {
+    /// ファイルがない
+    Missing,
+    /// ファイルはあるが画像として読めない
+    Undecodable,
+}
+
+/// 表示できない写真を参照している人物
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenPhoto {
+    pub person: PersonId,
+    pub path: String,
+    pub problem: PhotoProblem,
+}
+
+/// 人物が参照している写真をすべて調べ、見つからない・読めないものを名前順で返す
+///
+/// 既定の画像は調べない。画像として読めるかはファイルの先頭（大きさの情報）だけで判断する。
+pub fn find_broken_photos(tree: &FamilyTree) -> Vec<BrokenPhoto> {
+    let mut persons: Vec<_> = tree.persons.values().collect();
+    persons.sort_by(|a, b| a.name.cmp(&b.name));
+    persons
+        .into_iter()
+        .filter_map(|person| {
+            let path = person.photo_path.as_deref().filter(|path| !path.is_empty() && *path != DEFAULT_PHOTO_PATH)?;
+            let problem = photo_problem(Path::new(path))?;
+            Some(BrokenPhoto { person: person.id, path: path.to_string(), problem })
+        })
+        .collect()
+}
+
+fn photo_problem(path: &Path) -> Option<PhotoProblem> {
+    if !path.is_file() {
+        return Some(PhotoProblem::Missing);
+    }
+    let decodable = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .ok()
+        .is_some_and(|reader| reader.into_dimensions().is_ok());
+    (!decodable).then_some(PhotoProblem::Undecodable)
+}
+
+/// 指定したフォルダ（サブフォルダを含む）から同じファイル名の画像を探して写真のパスを付け直し、付け直した人物を返す
+///
+/// ファイル名の大文字・小文字は区別しない。同じ名前が複数ある場合は先に見つかったものを使う。
+pub fn relocate_photos(tree: &mut FamilyTree, broken: &[BrokenPhoto], folder: &Path) -> Vec<PersonId> {
+    let mut files = HashMap::new();
+    index_files(folder, 0, &mut files);
+
+    let mut relocated = Vec::new();
+    for photo in broken {
+        let Some(file_name) = Path::new(&photo.path).file_name() else {
+            continue;
+        };
+        let Some(found) = files.get(&file_name.to_string_lossy().to_lowercase()) else {
+            continue;
+        };
+        if photo_problem(found).is_some() {
+            continue;
+        }
+        if let Some(person) = tree.persons.get_mut(&photo.person) {
+            person.photo_path = Some(found.display().to_string());
+            relocated.push(photo.person);
+        }
+    }
+    relocated
+}
+
+/// 写真の参照を外し、写真を表示する設定の人物は名前だけの表示に戻す
+pub fn clear_photos(tree: &mut FamilyTree, persons: &[PersonId]) {
+    for id in persons {
+        if let Some(person) = tree.persons.get_mut(id) {
+            person.photo_path = None;
+            person.display_mode = PersonDisplayMode::NameOnly;
+        }
+    }
+}
+
+/// 小文字にしたファイル名からパスを引く表を作る（先に登録したファイルは同名のファイルで上書きしない）
+fn index_files(dir: &Path, depth: usize, files: &mut HashMap<String, PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else {
+            files.entry(entry.file_name().to_string_lossy().to_lowercase()).or_insert(path);
+        }
+    }
+    if depth < RELOCATE_MAX_DEPTH {
+        for subdir in subdirs {
+            index_files(&subdir, depth + 1, files);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+    use image::{Rgba, RgbaImage};
+    use std::env;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_find_and_relocate_broken_photos() {
+        let root = env::temp_dir().join(format!("family_tree_photo_check_{}", Uuid::new_v4()));
+        let moved = root.join("moved").join("nested");
+        fs::create_dir_all(&moved).unwrap();
+        RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255])).save(moved.join("Taro.png")).unwrap();
+        let broken_file = root.join("broken.png");
+        fs::write(&broken_file, "not an image").unwrap();
+
+        let mut tree = FamilyTree::default();
+        let mut add = |name: &str, photo: Option<String>| {
+            let id = tree.add_person(name.to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+            tree.persons.get_mut(&id).unwrap().photo_path = photo;
+            id
+        };
+        let taro = add("Taro", Some(root.join("old").join("taro.PNG").display().to_string()));
+        let hanako = add("Hanako", Some(broken_file.display().to_string()));
+        add("Default", Some(DEFAULT_PHOTO_PATH.to_string()));
+        add("None", None);
+
+        let broken = find_broken_photos(&tree);
+        assert_eq!(
+            broken.iter().map(|photo| (photo.person, photo.problem)).collect::<Vec<_>>(),
+            vec![(hanako, PhotoProblem::Undecodable), (taro, PhotoProblem::Missing)]
+        );
+
+        assert_eq!(relocate_photos(&mut tree, &broken, &root.join("moved")), vec![taro]);
+        assert_eq!(tree.persons[&taro].photo_path, Some(moved.join("Taro.png").display().to_string()));
+
+        tree.persons.get_mut(&hanako).unwrap().display_mode = PersonDisplayMode::PhotoOnly;
+        clear_photos(&mut tree, &[hanako]);
+        assert_eq!(tree.persons[&hanako].photo_path, None);
+        assert_eq!(tree.persons[&hanako].display_mode, PersonDisplayMode::NameOnly);
+        assert!(find_broken_photos(&tree).is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
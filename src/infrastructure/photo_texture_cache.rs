@@ -4,10 +4,18 @@ use std::time::SystemTime;
 
 use eframe::egui;
 
+use crate::application::default_settings_dir;
+use crate::infrastructure::ThumbnailCache;
+
+/// デコードした写真テクスチャの長辺の最大ピクセル数
+pub const MAX_TEXTURE_DIMENSION: u32 = 2048;
+
 #[derive(Clone)]
 enum PhotoCacheEntry {
     Loaded {
         texture: egui::TextureHandle,
+        /// 縮小する前の画像のピクセル数（サムネイルはサムネイル自体の大きさ）
+        source_size: egui::Vec2,
         modified_at: Option<SystemTime>,
     },
     Failed {
@@ -16,9 +24,23 @@ enum PhotoCacheEntry {
 }
 
 /// 人物写真テクスチャの読み込みとキャッシュを管理する。
-#[derive(Default)]
+///
+/// キャンバスのノードにはディスクに保存したサムネイルを使い、
+/// 背景画像やスライドショーなど大きく表示するものだけ元画像をデコードする。
 pub struct PhotoTextureCache {
     entries: HashMap<String, PhotoCacheEntry>,
+    thumbnail_entries: HashMap<String, PhotoCacheEntry>,
+    thumbnails: ThumbnailCache,
+}
+
+impl Default for PhotoTextureCache {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            thumbnail_entries: HashMap::new(),
+            thumbnails: ThumbnailCache::in_dir(&default_settings_dir()),
+        }
+    }
 }
 
 impl PhotoTextureCache {
@@ -28,15 +50,53 @@ impl PhotoTextureCache {
         ctx: &egui::Context,
         photo_path: &str,
     ) -> Option<egui::TextureHandle> {
+        self.get_or_load_with_source_size(ctx, photo_path).map(|(texture, _)| texture)
+    }
+
+    /// テクスチャと、縮小する前の元画像のピクセル数を取得する（背景画像などワールド座標で大きさを決めるもの用）
+    pub fn get_or_load_with_source_size(
+        &mut self,
+        ctx: &egui::Context,
+        photo_path: &str,
+    ) -> Option<(egui::TextureHandle, egui::Vec2)> {
+        Self::get_or_load_entry(&mut self.entries, ctx, photo_path, "person_photo", |_| {
+            Self::load_color_image(photo_path)
+        })
+    }
+
+    /// 指定パスのサムネイルのテクスチャを取得する（ディスク上のサムネイルキャッシュを使う）
+    pub fn get_or_load_thumbnail(
+        &mut self,
+        ctx: &egui::Context,
+        photo_path: &str,
+    ) -> Option<egui::TextureHandle> {
+        let thumbnails = &self.thumbnails;
+        Self::get_or_load_entry(&mut self.thumbnail_entries, ctx, photo_path, "person_thumbnail", |modified_at| {
+            let rgba = thumbnails.load_or_create(photo_path, modified_at)?;
+            let size = [rgba.width() as usize, rgba.height() as usize];
+            let image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice());
+            Some((image, egui::vec2(size[0] as f32, size[1] as f32)))
+        })
+        .map(|(texture, _)| texture)
+    }
+
+    fn get_or_load_entry(
+        entries: &mut HashMap<String, PhotoCacheEntry>,
+        ctx: &egui::Context,
+        photo_path: &str,
+        texture_prefix: &str,
+        load: impl FnOnce(Option<SystemTime>) -> Option<(egui::ColorImage, egui::Vec2)>,
+    ) -> Option<(egui::TextureHandle, egui::Vec2)> {
         let modified_at = Self::read_modified_at(photo_path);
 
-        if let Some(entry) = self.entries.get(photo_path) {
+        if let Some(entry) = entries.get(photo_path) {
             match entry {
                 PhotoCacheEntry::Loaded {
                     texture,
+                    source_size,
                     modified_at: cached_modified_at,
                 } if *cached_modified_at == modified_at => {
-                    return Some(texture.clone());
+                    return Some((texture.clone(), *source_size));
                 }
                 PhotoCacheEntry::Failed {
                     modified_at: cached_modified_at,
@@ -47,10 +107,10 @@ impl PhotoTextureCache {
             }
         }
 
-        let color_image = match Self::load_color_image(photo_path) {
-            Some(color_image) => color_image,
+        let (color_image, source_size) = match load(modified_at) {
+            Some(loaded) => loaded,
             None => {
-                self.entries.insert(
+                entries.insert(
                     photo_path.to_string(),
                     PhotoCacheEntry::Failed { modified_at },
                 );
@@ -59,40 +119,53 @@ impl PhotoTextureCache {
         };
 
         let texture = ctx.load_texture(
-            format!("person_photo::{photo_path}"),
+            format!("{texture_prefix}::{photo_path}"),
             color_image,
             Default::default(),
         );
-        self.entries.insert(
+        entries.insert(
             photo_path.to_string(),
             PhotoCacheEntry::Loaded {
                 texture: texture.clone(),
+                source_size,
                 modified_at,
             },
         );
 
-        Some(texture)
+        Some((texture, source_size))
+    }
+
+    /// 長辺が上限を超える画像を縮小する（高解像度のスキャン画像が巨大なテクスチャになるのを防ぐ）
+    ///
+    /// ノードの大きさは元画像のサイズと `photo_scale` から計算するので、ここで縮小しても配置は変わらない。
+    fn clamp_resolution(image: image::DynamicImage) -> image::DynamicImage {
+        if image.width() <= MAX_TEXTURE_DIMENSION && image.height() <= MAX_TEXTURE_DIMENSION {
+            return image;
+        }
+        image.resize(MAX_TEXTURE_DIMENSION, MAX_TEXTURE_DIMENSION, image::imageops::FilterType::Triangle)
     }
 
     fn read_modified_at(photo_path: &str) -> Option<SystemTime> {
         fs::metadata(photo_path).ok()?.modified().ok()
     }
 
-    fn load_color_image(photo_path: &str) -> Option<egui::ColorImage> {
+    fn load_color_image(photo_path: &str) -> Option<(egui::ColorImage, egui::Vec2)> {
         let image = image::open(photo_path).ok()?;
+        let source_size = egui::vec2(image.width() as f32, image.height() as f32);
+        let image = Self::clamp_resolution(image);
         let size = [image.width() as usize, image.height() as usize];
         let rgba = image.to_rgba8();
         let pixels = rgba.as_flat_samples();
-        Some(egui::ColorImage::from_rgba_unmultiplied(
-            size,
-            pixels.as_slice(),
+        Some((
+            egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice()),
+            source_size,
         ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::PhotoTextureCache;
+    use super::{PhotoTextureCache, MAX_TEXTURE_DIMENSION};
 
     #[test]
     fn returns_none_for_invalid_file_path() {
@@ -100,5 +173,17 @@ mod tests {
         let ctx = eframe::egui::Context::default();
         let texture = cache.get_or_load(&ctx, "__missing_photo__.png");
         assert!(texture.is_none());
+        assert!(cache.get_or_load_thumbnail(&ctx, "__missing_photo__.png").is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn clamp_resolution_caps_long_side_and_keeps_aspect_ratio() {
+        let large = image::DynamicImage::new_rgba8(MAX_TEXTURE_DIMENSION * 2, MAX_TEXTURE_DIMENSION / 2);
+        let clamped = PhotoTextureCache::clamp_resolution(large);
+        assert_eq!((clamped.width(), clamped.height()), (MAX_TEXTURE_DIMENSION, MAX_TEXTURE_DIMENSION / 4));
+
+        let small = image::DynamicImage::new_rgba8(640, 480);
+        let unchanged = PhotoTextureCache::clamp_resolution(small);
+        assert_eq!((unchanged.width(), unchanged.height()), (640, 480));
+    }
+}
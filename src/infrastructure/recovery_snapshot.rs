@@ -0,0 +1,232 @@
+use std::env;
+use std::fs::{self, File, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::tree::FamilyTree;
+use crate::infrastructure::source_path_key::source_path_key;
+
+/// 復旧用スナップショットを置くディレクトリ名
+const RECOVERY_DIR_NAME: &str = "family_tree_recovery";
+
+/// 異常終了時に復元するための家系図のスナップショット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverySnapshot {
+    /// "YYYY-MM-DD HH:MM:SS"
+    pub saved_at: String,
+    /// スナップショット時点で開いていたファイル（未保存なら空）
+    pub file_path: String,
+    /// 書き込んだプロセスの ID（まだ動いているインスタンスのスナップショットを復元しないため）
+    pub pid: u32,
+    pub tree: FamilyTree,
+}
+
+/// 一時ディレクトリに復旧用スナップショットを書き込み・読み込みする
+///
+/// 複数のウィンドウが互いのスナップショットを上書きしないよう、保存先は開いているファイルのパスごと
+/// （未保存の家系図はプロセスごと）に分ける。
+/// 正常終了や保存時には削除されるため、起動時に持ち主のいないものが残っていれば異常終了したとみなす。
+#[derive(Debug, Clone)]
+pub struct RecoverySnapshotStore {
+    dir: PathBuf,
+    /// スナップショットの元になっているファイル（未保存なら空）
+    file_path: String,
+    path: PathBuf,
+    /// このプロセスが動いている間ロックし続けるファイル（異常終了すると OS が解放する）
+    #[allow(dead_code)]
+    instance_lock: Option<Arc<File>>,
+}
+
+impl RecoverySnapshotStore {
+    /// OS の一時ディレクトリを使う
+    pub fn in_temp_dir() -> Self {
+        Self::in_dir(env::temp_dir().join(RECOVERY_DIR_NAME))
+    }
+
+    /// 未保存の家系図の保存先で作り、このプロセスが動いていることをロックファイルで示す
+    pub fn in_dir(dir: PathBuf) -> Self {
+        let instance_lock = fs::create_dir_all(&dir)
+            .and_then(|()| File::create(Self::instance_lock_path(&dir, std::process::id())))
+            .ok()
+            .filter(|file| file.try_lock().is_ok())
+            .map(Arc::new);
+        let path = Self::snapshot_path(&dir, "");
+        Self { dir, file_path: String::new(), path, instance_lock }
+    }
+
+    /// 保存先を開いているファイルに合わせる（ファイルが変わったら前の保存先のスナップショットは削除する）
+    pub fn switch_source(&mut self, file_path: &str) -> std::io::Result<()> {
+        if self.file_path == file_path {
+            return Ok(());
+        }
+        self.clear()?;
+        self.file_path = file_path.to_string();
+        self.path = Self::snapshot_path(&self.dir, file_path);
+        Ok(())
+    }
+
+    /// 書き込み途中で落ちても壊れないよう、一時ファイルに書いてから置き換える
+    pub fn save(&self, snapshot: &RecoverySnapshot) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string(snapshot).map_err(std::io::Error::other)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &self.path)
+    }
+
+    /// 異常終了したインスタンスが残したスナップショットのうち最も新しいものを探す
+    ///
+    /// 返す保存先の `clear` で、復元または破棄したスナップショットを削除できる。
+    pub fn find_leftover(&self) -> Option<(Self, RecoverySnapshot)> {
+        fs::read_dir(&self.dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .filter_map(|path| Some((Self::read(&path)?, path)))
+            .filter(|(snapshot, _)| !self.is_running(snapshot.pid))
+            .max_by(|(a, _), (b, _)| a.saved_at.cmp(&b.saved_at))
+            .map(|(snapshot, path)| {
+                let store = Self {
+                    dir: self.dir.clone(),
+                    file_path: snapshot.file_path.clone(),
+                    path,
+                    instance_lock: None,
+                };
+                (store, snapshot)
+            })
+    }
+
+    pub fn clear(&self) -> std::io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    /// 他のインスタンスが動いていてロックを持っているか
+    ///
+    /// 自分のプロセス ID のスナップショットは、同じ ID を使っていた以前のプロセスが残したものとみなす。
+    fn is_running(&self, pid: u32) -> bool {
+        if pid == std::process::id() {
+            return false;
+        }
+        match File::open(Self::instance_lock_path(&self.dir, pid)) {
+            Ok(file) => matches!(file.try_lock(), Err(TryLockError::WouldBlock)),
+            Err(_) => false,
+        }
+    }
+
+    /// スナップショットがあれば読み込む（壊れている場合は無視する）
+    fn read(path: &Path) -> Option<RecoverySnapshot> {
+        let json = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// ファイルのパスごと、未保存ならプロセスごとのスナップショットのパス
+    fn snapshot_path(dir: &Path, file_path: &str) -> PathBuf {
+        if file_path.is_empty() {
+            dir.join(format!("untitled-{}.json", std::process::id()))
+        } else {
+            dir.join(format!("file-{}.json", source_path_key(Path::new(file_path))))
+        }
+    }
+
+    fn instance_lock_path(dir: &Path, pid: u32) -> PathBuf {
+        dir.join(format!("{pid}.lock"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_save_load_and_clear_snapshot() {
+        let dir = env::temp_dir().join(format!("family_tree_recovery_{}", Uuid::new_v4()));
+        let mut store = RecoverySnapshotStore::in_dir(dir.clone());
+        store.switch_source("tree.json").unwrap();
+        assert!(RecoverySnapshotStore::read(&store.path).is_none());
+
+        let mut tree = FamilyTree::default();
+        tree.add_person("Taro".to_string(), crate::core::tree::Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let snapshot = RecoverySnapshot {
+            saved_at: "2024-01-02 03:04:05".to_string(),
+            file_path: "tree.json".to_string(),
+            pid: std::process::id(),
+            tree,
+        };
+        store.save(&snapshot).unwrap();
+
+        let loaded = RecoverySnapshotStore::read(&store.path).unwrap();
+        assert_eq!(loaded.file_path, "tree.json");
+        assert_eq!(loaded.tree.persons.len(), 1);
+
+        store.clear().unwrap();
+        assert!(RecoverySnapshotStore::read(&store.path).is_none());
+        store.clear().unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_snapshots_are_kept_per_source_file() {
+        let dir = env::temp_dir().join(format!("family_tree_recovery_{}", Uuid::new_v4()));
+        let mut first = RecoverySnapshotStore::in_dir(dir.clone());
+        first.switch_source("a/tree.json").unwrap();
+        let mut second = first.clone();
+        second.switch_source("b/tree.json").unwrap();
+
+        let snapshot = |file_path: &str| RecoverySnapshot {
+            saved_at: "2024-01-02 03:04:05".to_string(),
+            file_path: file_path.to_string(),
+            pid: std::process::id(),
+            tree: FamilyTree::default(),
+        };
+        first.save(&snapshot("a/tree.json")).unwrap();
+        second.save(&snapshot("b/tree.json")).unwrap();
+        assert_eq!(RecoverySnapshotStore::read(&first.path).unwrap().file_path, "a/tree.json");
+        assert_eq!(RecoverySnapshotStore::read(&second.path).unwrap().file_path, "b/tree.json");
+
+        // 別のファイルに切り替えると前のスナップショットは消える
+        first.switch_source("").unwrap();
+        assert!(RecoverySnapshotStore::read(&first.path).is_none());
+        assert_eq!(fs::read_dir(&dir).unwrap().filter(|entry| {
+            entry.as_ref().unwrap().path().extension().is_some_and(|extension| extension == "json")
+        }).count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_leftover_skips_running_instances() {
+        let dir = env::temp_dir().join(format!("family_tree_recovery_{}", Uuid::new_v4()));
+        let store = RecoverySnapshotStore::in_dir(dir.clone());
+        assert!(store.find_leftover().is_none());
+
+        // 終了済みのプロセス（ロックファイルなし）のスナップショットは復元の候補になる
+        let leftover = RecoverySnapshot {
+            saved_at: "2024-01-02 03:04:05".to_string(),
+            file_path: String::new(),
+            pid: u32::MAX,
+            tree: FamilyTree::default(),
+        };
+        fs::write(dir.join("untitled-4294967295.json"), serde_json::to_string(&leftover).unwrap()).unwrap();
+        // ロックを持って動いているプロセスのスナップショットは候補にしない
+        let running = File::create(dir.join("4294967294.lock")).unwrap();
+        running.lock().unwrap();
+        let live = RecoverySnapshot { pid: u32::MAX - 1, saved_at: "2024-12-31 00:00:00".to_string(), ..leftover.clone() };
+        fs::write(dir.join("untitled-4294967294.json"), serde_json::to_string(&live).unwrap()).unwrap();
+
+        let (found_store, found) = store.find_leftover().unwrap();
+        assert_eq!(found.pid, u32::MAX);
+        found_store.clear().unwrap();
+        assert!(store.find_leftover().is_none());
+
+        drop(running);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use std::path::Path;
+
+/// 家系図ファイルのパスから、ファイル名に使える識別子を作る
+///
+/// 別のディレクトリにある同名のファイルを区別するため、正規化した絶対パスのハッシュ値（16桁の16進数）を使う。
+/// ツールチェーンが変わっても同じ値になるよう、標準ライブラリのハッシュではなく FNV-1a を使う。
+pub fn source_path_key(path: &Path) -> String {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in canonical.to_string_lossy().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_same_file_name_in_different_directories_gets_different_keys() {
+        let root = env::temp_dir().join(format!("family_tree_path_key_{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("b")).unwrap();
+        fs::write(root.join("a").join("tree.json"), "{}").unwrap();
+        fs::write(root.join("b").join("tree.json"), "{}").unwrap();
+
+        let a = source_path_key(&root.join("a").join("tree.json"));
+        let b = source_path_key(&root.join("b").join("tree.json"));
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 16);
+        // 相対的な書き方の違いは正規化される
+        assert_eq!(a, source_path_key(&root.join("b").join("..").join("a").join("tree.json")));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
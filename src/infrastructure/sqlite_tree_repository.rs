@@ -5,9 +5,11 @@ use rusqlite::{Connection, OptionalExtension, Transaction, params};
 use uuid::Uuid;
 
 use crate::application::{TreeRepository, TreeRepositoryError};
+use crate::core::edit_macro::EditMacro;
 use crate::core::tree::{
-    Event, EventId, EventRelation, EventRelationType, Family, FamilyTree, Gender, ParentChild,
-    Person, PersonDisplayMode, PersonId, Spouse,
+    CameraBookmark, CanvasBackground, CanvasGuide, Event, EventId, EventRelation, EventRelationType, ExternalPersonLink, ExtraFields, Family, FamilyDefaults, FamilyTree, Gender, GuideAxis, LayoutSnapshot,
+    NodeStyle, ParentChild, Person, PersonBadge, PersonDisplayMode, PersonId, PersonRelation, PersonRelationKind, Place, Spouse, SpouseLabelPosition, SpouseLabelStyle, StickyNote, TextAnnotation, Union,
+    CURRENT_FORMAT_VERSION,
 };
 
 /// `FamilyTree`をSQLiteファイルとして保存・読込するリポジトリ実装。
@@ -15,7 +17,7 @@ use crate::core::tree::{
 /// 人物・関係・家族・イベントを正規化したスキーマで保存する。
 pub struct SqliteTreeRepository;
 
-const SCHEMA_VERSION: i64 = 1;
+const SCHEMA_VERSION: i64 = 8;
 
 impl SqliteTreeRepository {
     fn open_connection(file_path: &str) -> Result<Connection, TreeRepositoryError> {
@@ -31,7 +33,14 @@ impl SqliteTreeRepository {
                 CREATE TABLE IF NOT EXISTS tree_metadata (
                     id INTEGER PRIMARY KEY CHECK (id = 1),
                     schema_version INTEGER NOT NULL,
-                    updated_at TEXT NOT NULL
+                    updated_at TEXT NOT NULL,
+                    background_path TEXT,
+                    background_opacity REAL NOT NULL DEFAULT 0.5,
+                    background_x REAL NOT NULL DEFAULT 0,
+                    background_y REAL NOT NULL DEFAULT 0,
+                    background_scale REAL NOT NULL DEFAULT 1,
+                    node_style INTEGER NOT NULL DEFAULT 0,
+                    home_person TEXT
                 );
 
                 CREATE TABLE IF NOT EXISTS persons (
@@ -46,13 +55,27 @@ impl SqliteTreeRepository {
                     death TEXT,
                     photo_path TEXT,
                     display_mode INTEGER NOT NULL,
-                    photo_scale REAL NOT NULL
+                    photo_scale REAL NOT NULL,
+                    multiple_birth_group TEXT,
+                    gender_label TEXT,
+                    gedcom_extras TEXT NOT NULL DEFAULT '',
+                    pinned INTEGER NOT NULL DEFAULT 0,
+                    birth_place_id TEXT,
+                    death_place_id TEXT,
+                    badges TEXT NOT NULL DEFAULT '',
+                    external_tree TEXT,
+                    external_person_id TEXT NOT NULL DEFAULT '',
+                    locked INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE TABLE IF NOT EXISTS parent_child_edges (
                     parent_id TEXT NOT NULL,
                     child_id TEXT NOT NULL,
                     kind TEXT NOT NULL,
+                    adoption_date TEXT,
+                    adoption_type TEXT NOT NULL DEFAULT '',
+                    adoption_note TEXT NOT NULL DEFAULT '',
+                    union_id TEXT,
                     FOREIGN KEY(parent_id) REFERENCES persons(id) ON DELETE CASCADE,
                     FOREIGN KEY(child_id) REFERENCES persons(id) ON DELETE CASCADE
                 );
@@ -61,6 +84,15 @@ impl SqliteTreeRepository {
                     person1_id TEXT NOT NULL,
                     person2_id TEXT NOT NULL,
                     memo TEXT NOT NULL,
+                    marriage_date TEXT,
+                    divorced INTEGER NOT NULL DEFAULT 0,
+                    divorce_date TEXT,
+                    label_visible INTEGER NOT NULL DEFAULT 1,
+                    label_position INTEGER NOT NULL DEFAULT 0,
+                    label_font_size REAL NOT NULL DEFAULT 11,
+                    label_color_r INTEGER NOT NULL DEFAULT 90,
+                    label_color_g INTEGER NOT NULL DEFAULT 90,
+                    label_color_b INTEGER NOT NULL DEFAULT 90,
                     FOREIGN KEY(person1_id) REFERENCES persons(id) ON DELETE CASCADE,
                     FOREIGN KEY(person2_id) REFERENCES persons(id) ON DELETE CASCADE
                 );
@@ -70,7 +102,12 @@ impl SqliteTreeRepository {
                     name TEXT NOT NULL,
                     color_r INTEGER,
                     color_g INTEGER,
-                    color_b INTEGER
+                    color_b INTEGER,
+                    default_relation_kind TEXT NOT NULL DEFAULT '',
+                    default_surname TEXT NOT NULL DEFAULT '',
+                    label_color_r INTEGER,
+                    label_color_g INTEGER,
+                    label_color_b INTEGER
                 );
 
                 CREATE TABLE IF NOT EXISTS family_members (
@@ -90,7 +127,26 @@ impl SqliteTreeRepository {
                     position_y REAL NOT NULL,
                     color_r INTEGER NOT NULL,
                     color_g INTEGER NOT NULL,
-                    color_b INTEGER NOT NULL
+                    color_b INTEGER NOT NULL,
+                    place_id TEXT,
+                    template_key TEXT
+                );
+
+                CREATE TABLE IF NOT EXISTS places (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    latitude REAL,
+                    longitude REAL,
+                    note TEXT NOT NULL,
+                    sort_order INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS unions (
+                    id TEXT PRIMARY KEY,
+                    kind TEXT NOT NULL,
+                    partner1_id TEXT NOT NULL,
+                    partner2_id TEXT,
+                    sort_order INTEGER NOT NULL
                 );
 
                 CREATE TABLE IF NOT EXISTS event_relations (
@@ -102,6 +158,79 @@ impl SqliteTreeRepository {
                     FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE
                 );
 
+                CREATE TABLE IF NOT EXISTS person_relations (
+                    from_id TEXT NOT NULL,
+                    to_id TEXT NOT NULL,
+                    kind INTEGER NOT NULL,
+                    memo TEXT NOT NULL,
+                    FOREIGN KEY(from_id) REFERENCES persons(id) ON DELETE CASCADE,
+                    FOREIGN KEY(to_id) REFERENCES persons(id) ON DELETE CASCADE
+                );
+
+                CREATE TABLE IF NOT EXISTS annotations (
+                    id TEXT PRIMARY KEY,
+                    text TEXT NOT NULL,
+                    position_x REAL NOT NULL,
+                    position_y REAL NOT NULL,
+                    font_size REAL NOT NULL,
+                    color_r INTEGER NOT NULL,
+                    color_g INTEGER NOT NULL,
+                    color_b INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS sticky_notes (
+                    id TEXT PRIMARY KEY,
+                    person_id TEXT NOT NULL,
+                    text TEXT NOT NULL,
+                    resolved INTEGER NOT NULL,
+                    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE
+                );
+
+                CREATE TABLE IF NOT EXISTS camera_bookmarks (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    zoom REAL NOT NULL,
+                    pan_x REAL NOT NULL,
+                    pan_y REAL NOT NULL,
+                    sort_order INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS canvas_guides (
+                    id TEXT PRIMARY KEY,
+                    axis INTEGER NOT NULL,
+                    position REAL NOT NULL,
+                    sort_order INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS edit_macros (
+                    name TEXT NOT NULL,
+                    steps TEXT NOT NULL,
+                    sort_order INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS layout_snapshots (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    sort_order INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS layout_snapshot_positions (
+                    snapshot_id TEXT NOT NULL,
+                    item_kind INTEGER NOT NULL,
+                    item_id TEXT NOT NULL,
+                    position_x REAL NOT NULL,
+                    position_y REAL NOT NULL,
+                    PRIMARY KEY(snapshot_id, item_kind, item_id),
+                    FOREIGN KEY(snapshot_id) REFERENCES layout_snapshots(id) ON DELETE CASCADE
+                );
+
+                CREATE TABLE IF NOT EXISTS extra_fields (
+                    entity TEXT NOT NULL,
+                    item_key TEXT NOT NULL,
+                    fields TEXT NOT NULL,
+                    PRIMARY KEY(entity, item_key)
+                );
+
                 CREATE INDEX IF NOT EXISTS idx_parent_child_parent ON parent_child_edges(parent_id);
                 CREATE INDEX IF NOT EXISTS idx_parent_child_child ON parent_child_edges(child_id);
                 CREATE INDEX IF NOT EXISTS idx_family_members_person ON family_members(person_id);
@@ -109,6 +238,78 @@ impl SqliteTreeRepository {
                 CREATE INDEX IF NOT EXISTS idx_event_relations_person ON event_relations(person_id);
                 ",
             )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        Self::migrate_schema(connection)
+    }
+
+    /// 旧バージョンで作成されたファイルに不足している列を追加する。
+    fn migrate_schema(connection: &Connection) -> Result<(), TreeRepositoryError> {
+        Self::ensure_column(connection, "spouses", "marriage_date", "TEXT")?;
+        Self::ensure_column(connection, "spouses", "divorced", "INTEGER NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "spouses", "divorce_date", "TEXT")?;
+        Self::ensure_column(connection, "spouses", "label_visible", "INTEGER NOT NULL DEFAULT 1")?;
+        Self::ensure_column(connection, "spouses", "label_position", "INTEGER NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "spouses", "label_font_size", "REAL NOT NULL DEFAULT 11")?;
+        Self::ensure_column(connection, "spouses", "label_color_r", "INTEGER NOT NULL DEFAULT 90")?;
+        Self::ensure_column(connection, "spouses", "label_color_g", "INTEGER NOT NULL DEFAULT 90")?;
+        Self::ensure_column(connection, "spouses", "label_color_b", "INTEGER NOT NULL DEFAULT 90")?;
+        Self::ensure_column(connection, "persons", "multiple_birth_group", "TEXT")?;
+        Self::ensure_column(connection, "persons", "gender_label", "TEXT")?;
+        Self::ensure_column(connection, "persons", "gedcom_extras", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "persons", "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "persons", "birth_place_id", "TEXT")?;
+        Self::ensure_column(connection, "persons", "death_place_id", "TEXT")?;
+        Self::ensure_column(connection, "persons", "badges", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "persons", "external_tree", "TEXT")?;
+        Self::ensure_column(connection, "persons", "locked", "INTEGER NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "persons", "external_person_id", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "families", "default_relation_kind", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "families", "default_surname", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "families", "label_color_r", "INTEGER")?;
+        Self::ensure_column(connection, "families", "label_color_g", "INTEGER")?;
+        Self::ensure_column(connection, "families", "label_color_b", "INTEGER")?;
+        Self::ensure_column(connection, "events", "place_id", "TEXT")?;
+        Self::ensure_column(connection, "events", "template_key", "TEXT")?;
+        Self::ensure_column(connection, "parent_child_edges", "adoption_date", "TEXT")?;
+        Self::ensure_column(connection, "parent_child_edges", "adoption_type", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "parent_child_edges", "adoption_note", "TEXT NOT NULL DEFAULT ''")?;
+        Self::ensure_column(connection, "parent_child_edges", "union_id", "TEXT")?;
+        Self::ensure_column(connection, "tree_metadata", "background_path", "TEXT")?;
+        Self::ensure_column(connection, "tree_metadata", "background_opacity", "REAL NOT NULL DEFAULT 0.5")?;
+        Self::ensure_column(connection, "tree_metadata", "background_x", "REAL NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "tree_metadata", "background_y", "REAL NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "tree_metadata", "background_scale", "REAL NOT NULL DEFAULT 1")?;
+        Self::ensure_column(connection, "tree_metadata", "node_style", "INTEGER NOT NULL DEFAULT 0")?;
+        Self::ensure_column(connection, "tree_metadata", "home_person", "TEXT")?;
+        Self::ensure_column(connection, "tree_metadata", "format_version", "INTEGER NOT NULL DEFAULT 0")?;
+        Ok(())
+    }
+
+    fn ensure_column(
+        connection: &Connection,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = connection
+            .prepare(&format!("PRAGMA table_info({table})"))
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let column_names = statement
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        for column_name in column_names {
+            let column_name =
+                column_name.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            if column_name == column {
+                return Ok(());
+            }
+        }
+
+        connection
+            .execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"))
             .map_err(|error| TreeRepositoryError::Write(error.to_string()))
     }
 
@@ -129,6 +330,14 @@ impl SqliteTreeRepository {
             .map_err(|error| TreeRepositoryError::Deserialize(format!("invalid {field_name}: {error}")))
     }
 
+    fn parse_badges(value: &str) -> Vec<PersonBadge> {
+        value
+            .split(',')
+            .filter(|key| !key.is_empty())
+            .map(PersonBadge::from_key)
+            .collect()
+    }
+
     fn to_bool(value: i64, field_name: &str) -> Result<bool, TreeRepositoryError> {
         match value {
             0 => Ok(false),
@@ -139,11 +348,12 @@ impl SqliteTreeRepository {
         }
     }
 
-    fn to_gender(value: i64) -> Result<Gender, TreeRepositoryError> {
+    fn to_gender(value: i64, label: Option<String>) -> Result<Gender, TreeRepositoryError> {
         match value {
             0 => Ok(Gender::Male),
             1 => Ok(Gender::Female),
             2 => Ok(Gender::Unknown),
+            3 => Ok(Gender::Other(label.unwrap_or_default())),
             _ => Err(TreeRepositoryError::Deserialize(format!(
                 "invalid gender value: {value}"
             ))),
@@ -154,6 +364,7 @@ impl SqliteTreeRepository {
         match value {
             0 => Ok(PersonDisplayMode::NameOnly),
             1 => Ok(PersonDisplayMode::NameAndPhoto),
+            2 => Ok(PersonDisplayMode::PhotoOnly),
             _ => Err(TreeRepositoryError::Deserialize(format!(
                 "invalid display_mode value: {value}"
             ))),
@@ -171,11 +382,77 @@ impl SqliteTreeRepository {
         }
     }
 
-    fn from_gender(value: Gender) -> i64 {
+    fn to_person_relation_kind(value: i64) -> Result<PersonRelationKind, TreeRepositoryError> {
+        match value {
+            0 => Ok(PersonRelationKind::Guardian),
+            1 => Ok(PersonRelationKind::Godparent),
+            2 => Ok(PersonRelationKind::Mentor),
+            _ => Err(TreeRepositoryError::Deserialize(format!(
+                "invalid person relation kind value: {value}"
+            ))),
+        }
+    }
+
+    fn from_gender(value: &Gender) -> i64 {
         match value {
             Gender::Male => 0,
             Gender::Female => 1,
             Gender::Unknown => 2,
+            Gender::Other(_) => 3,
+        }
+    }
+
+    fn gender_label(value: &Gender) -> Option<&str> {
+        match value {
+            Gender::Other(label) => Some(label),
+            _ => None,
+        }
+    }
+
+    fn to_node_style(value: i64) -> Result<NodeStyle, TreeRepositoryError> {
+        match value {
+            0 => Ok(NodeStyle::Box),
+            1 => Ok(NodeStyle::CircularAvatar),
+            _ => Err(TreeRepositoryError::Deserialize(format!(
+                "invalid node_style value: {value}"
+            ))),
+        }
+    }
+
+    fn to_spouse_label_position(value: i64) -> Result<SpouseLabelPosition, TreeRepositoryError> {
+        match value {
+            0 => Ok(SpouseLabelPosition::Above),
+            1 => Ok(SpouseLabelPosition::Below),
+            _ => Err(TreeRepositoryError::Deserialize(format!("invalid spouse label_position: {value}"))),
+        }
+    }
+
+    fn from_spouse_label_position(value: SpouseLabelPosition) -> i64 {
+        match value {
+            SpouseLabelPosition::Above => 0,
+            SpouseLabelPosition::Below => 1,
+        }
+    }
+
+    fn to_guide_axis(value: i64) -> Result<GuideAxis, TreeRepositoryError> {
+        match value {
+            0 => Ok(GuideAxis::Vertical),
+            1 => Ok(GuideAxis::Horizontal),
+            _ => Err(TreeRepositoryError::Deserialize(format!("invalid canvas guide axis: {value}"))),
+        }
+    }
+
+    fn from_guide_axis(value: GuideAxis) -> i64 {
+        match value {
+            GuideAxis::Vertical => 0,
+            GuideAxis::Horizontal => 1,
+        }
+    }
+
+    fn from_node_style(value: NodeStyle) -> i64 {
+        match value {
+            NodeStyle::Box => 0,
+            NodeStyle::CircularAvatar => 1,
         }
     }
 
@@ -183,6 +460,7 @@ impl SqliteTreeRepository {
         match value {
             PersonDisplayMode::NameOnly => 0,
             PersonDisplayMode::NameAndPhoto => 1,
+            PersonDisplayMode::PhotoOnly => 2,
         }
     }
 
@@ -194,10 +472,29 @@ impl SqliteTreeRepository {
         }
     }
 
+    fn from_person_relation_kind(value: PersonRelationKind) -> i64 {
+        match value {
+            PersonRelationKind::Guardian => 0,
+            PersonRelationKind::Godparent => 1,
+            PersonRelationKind::Mentor => 2,
+        }
+    }
+
     fn clear_all_tables(transaction: &Transaction<'_>) -> Result<(), TreeRepositoryError> {
         transaction
             .execute_batch(
                 "
+                DELETE FROM extra_fields;
+                DELETE FROM camera_bookmarks;
+                DELETE FROM canvas_guides;
+                DELETE FROM edit_macros;
+                DELETE FROM places;
+                DELETE FROM unions;
+                DELETE FROM layout_snapshot_positions;
+                DELETE FROM layout_snapshots;
+                DELETE FROM sticky_notes;
+                DELETE FROM annotations;
+                DELETE FROM person_relations;
                 DELETE FROM event_relations;
                 DELETE FROM events;
                 DELETE FROM family_members;
@@ -217,7 +514,9 @@ impl SqliteTreeRepository {
                 SELECT
                     id, name, gender, birth, memo,
                     position_x, position_y, deceased, death,
-                    photo_path, display_mode, photo_scale
+                    photo_path, display_mode, photo_scale,
+                    multiple_birth_group, gender_label, gedcom_extras, pinned,
+                    birth_place_id, death_place_id, badges, external_tree, external_person_id, locked
                 FROM persons
                 ",
             )
@@ -238,6 +537,16 @@ impl SqliteTreeRepository {
                     row.get::<_, Option<String>>(9)?,
                     row.get::<_, i64>(10)?,
                     row.get::<_, f32>(11)?,
+                    row.get::<_, Option<String>>(12)?,
+                    row.get::<_, Option<String>>(13)?,
+                    row.get::<_, String>(14)?,
+                    row.get::<_, i64>(15)?,
+                    row.get::<_, Option<String>>(16)?,
+                    row.get::<_, Option<String>>(17)?,
+                    row.get::<_, String>(18)?,
+                    row.get::<_, Option<String>>(19)?,
+                    row.get::<_, String>(20)?,
+                    row.get::<_, i64>(21)?,
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
@@ -257,12 +566,31 @@ impl SqliteTreeRepository {
                 photo_path,
                 display_mode_value,
                 photo_scale,
+                multiple_birth_group_text,
+                gender_label,
+                gedcom_extras_text,
+                pinned_value,
+                birth_place_text,
+                death_place_text,
+                badges_text,
+                external_tree,
+                external_person_id,
+                locked_value,
             ) = person_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
             let id = Self::parse_uuid(&id_text, "person id")?;
-            let gender = Self::to_gender(gender_value)?;
+            let gender = Self::to_gender(gender_value, gender_label)?;
             let deceased = Self::to_bool(deceased_value, "deceased")?;
             let display_mode = Self::to_display_mode(display_mode_value)?;
+            let multiple_birth_group = multiple_birth_group_text
+                .map(|text| Self::parse_uuid(&text, "person multiple_birth_group"))
+                .transpose()?;
+            let birth_place = birth_place_text
+                .map(|text| Self::parse_uuid(&text, "person birth_place_id"))
+                .transpose()?;
+            let death_place = death_place_text
+                .map(|text| Self::parse_uuid(&text, "person death_place_id"))
+                .transpose()?;
 
             persons.insert(
                 id,
@@ -278,6 +606,15 @@ impl SqliteTreeRepository {
                     photo_path,
                     display_mode,
                     photo_scale,
+                    multiple_birth_group,
+                    gedcom_extras: gedcom_extras_text.lines().map(str::to_string).collect(),
+                    pinned: Self::to_bool(pinned_value, "pinned")?,
+                    locked: Self::to_bool(locked_value, "locked")?,
+                    birth_place,
+                    death_place,
+                    badges: Self::parse_badges(&badges_text),
+                    external_link: external_tree.map(|tree| ExternalPersonLink { tree, person_id: external_person_id }),
+                    extra: ExtraFields::new(),
                 },
             );
         }
@@ -287,7 +624,12 @@ impl SqliteTreeRepository {
 
     fn load_parent_child_edges(connection: &Connection) -> Result<Vec<ParentChild>, TreeRepositoryError> {
         let mut statement = connection
-            .prepare("SELECT parent_id, child_id, kind FROM parent_child_edges")
+            .prepare(
+                "
+                SELECT parent_id, child_id, kind, adoption_date, adoption_type, adoption_note, union_id
+                FROM parent_child_edges
+                ",
+            )
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let edge_rows = statement
@@ -296,18 +638,27 @@ impl SqliteTreeRepository {
                     row.get::<_, String>(0)?,
                     row.get::<_, String>(1)?,
                     row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let mut edges = Vec::new();
         for edge_row in edge_rows {
-            let (parent_text, child_text, kind) =
+            let (parent_text, child_text, kind, adoption_date, adoption_type, adoption_note, union_text) =
                 edge_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
             edges.push(ParentChild {
                 parent: Self::parse_uuid(&parent_text, "edge parent_id")?,
                 child: Self::parse_uuid(&child_text, "edge child_id")?,
                 kind,
+                adoption_date,
+                adoption_type,
+                adoption_note,
+                union: union_text.map(|text| Self::parse_uuid(&text, "edge union_id")).transpose()?,
+                extra: ExtraFields::new(),
             });
         }
 
@@ -316,7 +667,13 @@ impl SqliteTreeRepository {
 
     fn load_spouses(connection: &Connection) -> Result<Vec<Spouse>, TreeRepositoryError> {
         let mut statement = connection
-            .prepare("SELECT person1_id, person2_id, memo FROM spouses")
+            .prepare(
+                "
+                SELECT person1_id, person2_id, memo, marriage_date, divorced, divorce_date,
+                    label_visible, label_position, label_font_size, label_color_r, label_color_g, label_color_b
+                FROM spouses
+                ",
+            )
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let spouse_rows = statement
@@ -325,18 +682,38 @@ impl SqliteTreeRepository {
                     row.get::<_, String>(0)?,
                     row.get::<_, String>(1)?,
                     row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    (
+                        row.get::<_, i64>(6)?,
+                        row.get::<_, i64>(7)?,
+                        row.get::<_, f64>(8)?,
+                        (row.get::<_, u8>(9)?, row.get::<_, u8>(10)?, row.get::<_, u8>(11)?),
+                    ),
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let mut spouses = Vec::new();
         for spouse_row in spouse_rows {
-            let (person1_text, person2_text, memo) =
+            let (person1_text, person2_text, memo, marriage_date, divorced_value, divorce_date, label_values) =
                 spouse_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            let (label_visible, label_position, label_font_size, label_color) = label_values;
             spouses.push(Spouse {
                 person1: Self::parse_uuid(&person1_text, "spouse person1_id")?,
                 person2: Self::parse_uuid(&person2_text, "spouse person2_id")?,
                 memo,
+                marriage_date,
+                divorced: Self::to_bool(divorced_value, "divorced")?,
+                divorce_date,
+                label: SpouseLabelStyle {
+                    visible: Self::to_bool(label_visible, "label_visible")?,
+                    position: Self::to_spouse_label_position(label_position)?,
+                    font_size: label_font_size as f32,
+                    color: label_color,
+                },
+                extra: ExtraFields::new(),
             });
         }
 
@@ -345,7 +722,10 @@ impl SqliteTreeRepository {
 
     fn load_families(connection: &Connection) -> Result<Vec<Family>, TreeRepositoryError> {
         let mut statement = connection
-            .prepare("SELECT id, name, color_r, color_g, color_b FROM families")
+            .prepare(
+                "SELECT id, name, color_r, color_g, color_b, default_relation_kind, default_surname, \
+                 label_color_r, label_color_g, label_color_b FROM families",
+            )
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let family_rows = statement
@@ -356,6 +736,9 @@ impl SqliteTreeRepository {
                     row.get::<_, Option<i64>>(2)?,
                     row.get::<_, Option<i64>>(3)?,
                     row.get::<_, Option<i64>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                    (row.get::<_, Option<i64>>(7)?, row.get::<_, Option<i64>>(8)?, row.get::<_, Option<i64>>(9)?),
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
@@ -364,7 +747,7 @@ impl SqliteTreeRepository {
         let mut family_index = HashMap::new();
 
         for family_row in family_rows {
-            let (id_text, name, color_r, color_g, color_b) =
+            let (id_text, name, color_r, color_g, color_b, relation_kind, surname, label_color) =
                 family_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
             let id = Self::parse_uuid(&id_text, "family id")?;
@@ -378,12 +761,19 @@ impl SqliteTreeRepository {
                 }
             };
 
+            let label_color = match label_color {
+                (Some(red), Some(green), Some(blue)) => Some((red as u8, green as u8, blue as u8)),
+                _ => None,
+            };
+
             family_index.insert(id, families.len());
             families.push(Family {
                 id,
                 name,
                 members: Vec::new(),
                 color,
+                defaults: FamilyDefaults { relation_kind, surname, label_color },
+                extra: ExtraFields::new(),
             });
         }
 
@@ -419,7 +809,7 @@ impl SqliteTreeRepository {
                 "
                 SELECT
                     id, name, date, description,
-                    position_x, position_y, color_r, color_g, color_b
+                    position_x, position_y, color_r, color_g, color_b, place_id, template_key
                 FROM events
                 ",
             )
@@ -437,16 +827,21 @@ impl SqliteTreeRepository {
                     row.get::<_, u8>(6)?,
                     row.get::<_, u8>(7)?,
                     row.get::<_, u8>(8)?,
+                    row.get::<_, Option<String>>(9)?,
+                    row.get::<_, Option<String>>(10)?,
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
         let mut events = HashMap::new();
         for event_row in event_rows {
-            let (id_text, name, date, description, position_x, position_y, red, green, blue) =
+            let (id_text, name, date, description, position_x, position_y, red, green, blue, place_text, template) =
                 event_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
             let id = Self::parse_uuid(&id_text, "event id")?;
+            let place = place_text
+                .map(|text| Self::parse_uuid(&text, "event place_id"))
+                .transpose()?;
             events.insert(
                 id,
                 Event {
@@ -456,6 +851,9 @@ impl SqliteTreeRepository {
                     description,
                     position: (position_x, position_y),
                     color: (red, green, blue),
+                    place,
+                    template,
+                    extra: ExtraFields::new(),
                 },
             );
         }
@@ -463,149 +861,533 @@ impl SqliteTreeRepository {
         Ok(events)
     }
 
-    fn load_event_relations(connection: &Connection) -> Result<Vec<EventRelation>, TreeRepositoryError> {
+    fn load_annotations(connection: &Connection) -> Result<Vec<TextAnnotation>, TreeRepositoryError> {
         let mut statement = connection
-            .prepare("SELECT event_id, person_id, relation_type, memo FROM event_relations")
+            .prepare(
+                "
+                SELECT id, text, position_x, position_y, font_size, color_r, color_g, color_b
+                FROM annotations
+                ",
+            )
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        let relation_rows = statement
+        let annotation_rows = statement
             .query_map([], |row| {
                 Ok((
                     row.get::<_, String>(0)?,
                     row.get::<_, String>(1)?,
-                    row.get::<_, i64>(2)?,
-                    row.get::<_, String>(3)?,
+                    row.get::<_, f32>(2)?,
+                    row.get::<_, f32>(3)?,
+                    row.get::<_, f32>(4)?,
+                    row.get::<_, u8>(5)?,
+                    row.get::<_, u8>(6)?,
+                    row.get::<_, u8>(7)?,
                 ))
             })
             .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        let mut relations = Vec::new();
-        for relation_row in relation_rows {
-            let (event_id_text, person_id_text, relation_type_value, memo) =
-                relation_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
-            relations.push(EventRelation {
-                event: Self::parse_uuid(&event_id_text, "event_relation event_id")?,
-                person: Self::parse_uuid(&person_id_text, "event_relation person_id")?,
-                relation_type: Self::to_event_relation_type(relation_type_value)?,
-                memo,
+        let mut annotations = Vec::new();
+        for annotation_row in annotation_rows {
+            let (id_text, text, position_x, position_y, font_size, red, green, blue) =
+                annotation_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+            annotations.push(TextAnnotation {
+                id: Self::parse_uuid(&id_text, "annotation id")?,
+                text,
+                position: (position_x, position_y),
+                font_size,
+                color: (red, green, blue),
+                extra: ExtraFields::new(),
             });
         }
 
-        Ok(relations)
+        Ok(annotations)
     }
 
-    fn insert_persons(
-        transaction: &Transaction<'_>,
-        persons: &HashMap<PersonId, Person>,
-    ) -> Result<(), TreeRepositoryError> {
-        let mut statement = transaction
-            .prepare(
-                "
-                INSERT INTO persons (
-                    id, name, gender, birth, memo,
-                    position_x, position_y, deceased, death,
-                    photo_path, display_mode, photo_scale
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
-                ",
-            )
-            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+    fn load_sticky_notes(connection: &Connection) -> Result<Vec<StickyNote>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, person_id, text, resolved FROM sticky_notes")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        for person in persons.values() {
-            statement
-                .execute(params![
-                    person.id.to_string(),
-                    &person.name,
-                    Self::from_gender(person.gender),
-                    &person.birth,
-                    &person.memo,
-                    person.position.0,
-                    person.position.1,
-                    if person.deceased { 1_i64 } else { 0_i64 },
-                    &person.death,
-                    &person.photo_path,
-                    Self::from_display_mode(person.display_mode),
-                    person.photo_scale
-                ])
-                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        let note_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut sticky_notes = Vec::new();
+        for note_row in note_rows {
+            let (id_text, person_text, text, resolved) =
+                note_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+            sticky_notes.push(StickyNote {
+                id: Self::parse_uuid(&id_text, "sticky note id")?,
+                person: Self::parse_uuid(&person_text, "sticky note person id")?,
+                text,
+                resolved: Self::to_bool(resolved, "sticky note resolved")?,
+                extra: ExtraFields::new(),
+            });
         }
 
-        Ok(())
+        Ok(sticky_notes)
     }
 
-    fn insert_parent_child_edges(
-        transaction: &Transaction<'_>,
-        edges: &[ParentChild],
-    ) -> Result<(), TreeRepositoryError> {
-        let mut statement = transaction
-            .prepare("INSERT INTO parent_child_edges (parent_id, child_id, kind) VALUES (?1, ?2, ?3)")
-            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
-
-        for edge in edges {
-            statement
-                .execute(params![edge.parent.to_string(), edge.child.to_string(), &edge.kind])
-                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
-        }
+    fn load_places(connection: &Connection) -> Result<Vec<Place>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, name, latitude, longitude, note FROM places ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        Ok(())
-    }
+        let place_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<f64>>(2)?,
+                    row.get::<_, Option<f64>>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-    fn insert_spouses(transaction: &Transaction<'_>, spouses: &[Spouse]) -> Result<(), TreeRepositoryError> {
-        let mut statement = transaction
-            .prepare("INSERT INTO spouses (person1_id, person2_id, memo) VALUES (?1, ?2, ?3)")
-            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        let mut places = Vec::new();
+        for place_row in place_rows {
+            let (id_text, name, latitude, longitude, note) =
+                place_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        for spouse in spouses {
-            statement
-                .execute(params![
-                    spouse.person1.to_string(),
-                    spouse.person2.to_string(),
-                    &spouse.memo
-                ])
-                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+            places.push(Place {
+                id: Self::parse_uuid(&id_text, "place id")?,
+                name,
+                latitude,
+                longitude,
+                note,
+                extra: ExtraFields::new(),
+            });
         }
 
-        Ok(())
+        Ok(places)
     }
 
-    fn insert_families(transaction: &Transaction<'_>, families: &[Family]) -> Result<(), TreeRepositoryError> {
-        let mut family_statement = transaction
-            .prepare("INSERT INTO families (id, name, color_r, color_g, color_b) VALUES (?1, ?2, ?3, ?4, ?5)")
-            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
-
-        let mut member_statement = transaction
-            .prepare("INSERT INTO family_members (family_id, person_id) VALUES (?1, ?2)")
-            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+    fn load_unions(connection: &Connection) -> Result<Vec<Union>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, kind, partner1_id, partner2_id FROM unions ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-        for family in families {
-            let (color_r, color_g, color_b) = match family.color {
-                Some((red, green, blue)) => (Some(red as i64), Some(green as i64), Some(blue as i64)),
-                None => (None, None, None),
-            };
+        let union_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-            family_statement
-                .execute(params![family.id.to_string(), &family.name, color_r, color_g, color_b])
-                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        let mut unions = Vec::new();
+        for union_row in union_rows {
+            let (id_text, kind, partner1_text, partner2_text) =
+                union_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
 
-            for member_id in &family.members {
-                member_statement
-                    .execute(params![family.id.to_string(), member_id.to_string()])
-                    .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+            let mut partners = vec![Self::parse_uuid(&partner1_text, "union partner1_id")?];
+            if let Some(partner2_text) = partner2_text {
+                partners.push(Self::parse_uuid(&partner2_text, "union partner2_id")?);
             }
+            unions.push(Union {
+                id: Self::parse_uuid(&id_text, "union id")?,
+                kind,
+                partners,
+                extra: ExtraFields::new(),
+            });
         }
 
-        Ok(())
+        Ok(unions)
     }
 
-    fn insert_events(
-        transaction: &Transaction<'_>,
+    fn load_edit_macros(connection: &Connection) -> Result<Vec<EditMacro>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT name, steps FROM edit_macros ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let macro_rows = statement
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut macros = Vec::new();
+        for macro_row in macro_rows {
+            let (name, steps_json) = macro_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            // 操作の並びは形が操作ごとに違うので JSON のまま保存する
+            let steps = serde_json::from_str(&steps_json)
+                .map_err(|error| TreeRepositoryError::Deserialize(format!("invalid macro steps: {error}")))?;
+            macros.push(EditMacro::new(name, steps));
+        }
+
+        Ok(macros)
+    }
+
+    fn load_canvas_guides(connection: &Connection) -> Result<Vec<CanvasGuide>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, axis, position FROM canvas_guides ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let guide_rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, f64>(2)?))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut guides = Vec::new();
+        for guide_row in guide_rows {
+            let (id_text, axis, position) = guide_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            guides.push(CanvasGuide {
+                id: Self::parse_uuid(&id_text, "canvas guide id")?,
+                axis: Self::to_guide_axis(axis)?,
+                position: position as f32,
+                extra: ExtraFields::new(),
+            });
+        }
+
+        Ok(guides)
+    }
+
+    fn load_camera_bookmarks(connection: &Connection) -> Result<Vec<CameraBookmark>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, name, zoom, pan_x, pan_y FROM camera_bookmarks ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let bookmark_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, f64>(4)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut bookmarks = Vec::new();
+        for bookmark_row in bookmark_rows {
+            let (id_text, name, zoom, pan_x, pan_y) =
+                bookmark_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+            bookmarks.push(CameraBookmark {
+                id: Self::parse_uuid(&id_text, "camera bookmark id")?,
+                name,
+                zoom: zoom as f32,
+                pan: (pan_x as f32, pan_y as f32),
+                extra: ExtraFields::new(),
+            });
+        }
+
+        Ok(bookmarks)
+    }
+
+    fn load_layout_snapshots(connection: &Connection) -> Result<Vec<LayoutSnapshot>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT id, name FROM layout_snapshots ORDER BY sort_order")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let snapshot_rows = statement
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut snapshots = Vec::new();
+        for snapshot_row in snapshot_rows {
+            let (id_text, name) = snapshot_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            snapshots.push(LayoutSnapshot {
+                id: Self::parse_uuid(&id_text, "layout snapshot id")?,
+                name,
+                person_positions: HashMap::new(),
+                event_positions: HashMap::new(),
+                extra: ExtraFields::new(),
+            });
+        }
+
+        let mut position_statement = connection
+            .prepare("SELECT snapshot_id, item_kind, item_id, position_x, position_y FROM layout_snapshot_positions")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let position_rows = position_statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, f64>(4)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        for position_row in position_rows {
+            let (snapshot_text, item_kind, item_text, position_x, position_y) =
+                position_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            let snapshot_id = Self::parse_uuid(&snapshot_text, "layout snapshot id")?;
+            let item_id = Self::parse_uuid(&item_text, "layout snapshot item id")?;
+            let position = (position_x as f32, position_y as f32);
+            let Some(snapshot) = snapshots.iter_mut().find(|snapshot| snapshot.id == snapshot_id) else {
+                continue;
+            };
+            match item_kind {
+                0 => {
+                    snapshot.person_positions.insert(item_id, position);
+                }
+                1 => {
+                    snapshot.event_positions.insert(item_id, position);
+                }
+                _ => {
+                    return Err(TreeRepositoryError::Read(format!(
+                        "invalid layout snapshot item kind: {item_kind}"
+                    )));
+                }
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    fn load_event_relations(connection: &Connection) -> Result<Vec<EventRelation>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT event_id, person_id, relation_type, memo FROM event_relations")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let relation_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut relations = Vec::new();
+        for relation_row in relation_rows {
+            let (event_id_text, person_id_text, relation_type_value, memo) =
+                relation_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            relations.push(EventRelation {
+                event: Self::parse_uuid(&event_id_text, "event_relation event_id")?,
+                person: Self::parse_uuid(&person_id_text, "event_relation person_id")?,
+                relation_type: Self::to_event_relation_type(relation_type_value)?,
+                memo,
+                extra: ExtraFields::new(),
+            });
+        }
+
+        Ok(relations)
+    }
+
+    fn load_person_relations(connection: &Connection) -> Result<Vec<PersonRelation>, TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT from_id, to_id, kind, memo FROM person_relations")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let relation_rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut relations = Vec::new();
+        for relation_row in relation_rows {
+            let (from_text, to_text, kind_value, memo) =
+                relation_row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            relations.push(PersonRelation {
+                from: Self::parse_uuid(&from_text, "person_relation from_id")?,
+                to: Self::parse_uuid(&to_text, "person_relation to_id")?,
+                kind: Self::to_person_relation_kind(kind_value)?,
+                memo,
+                extra: ExtraFields::new(),
+            });
+        }
+
+        Ok(relations)
+    }
+
+    fn insert_persons(
+        transaction: &Transaction<'_>,
+        persons: &HashMap<PersonId, Person>,
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare(
+                "
+                INSERT INTO persons (
+                    id, name, gender, birth, memo,
+                    position_x, position_y, deceased, death,
+                    photo_path, display_mode, photo_scale, multiple_birth_group, gender_label,
+                    gedcom_extras, pinned, birth_place_id, death_place_id, badges,
+                    external_tree, external_person_id, locked
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)
+                ",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for person in persons.values() {
+            statement
+                .execute(params![
+                    person.id.to_string(),
+                    &person.name,
+                    Self::from_gender(&person.gender),
+                    &person.birth,
+                    &person.memo,
+                    person.position.0,
+                    person.position.1,
+                    if person.deceased { 1_i64 } else { 0_i64 },
+                    &person.death,
+                    &person.photo_path,
+                    Self::from_display_mode(person.display_mode),
+                    person.photo_scale,
+                    person.multiple_birth_group.map(|group| group.to_string()),
+                    Self::gender_label(&person.gender),
+                    person.gedcom_extras.join("\n"),
+                    if person.pinned { 1_i64 } else { 0_i64 },
+                    person.birth_place.map(|place| place.to_string()),
+                    person.death_place.map(|place| place.to_string()),
+                    person.badges.iter().map(|badge| badge.key()).collect::<Vec<_>>().join(","),
+                    person.external_link.as_ref().map(|link| link.tree.as_str()),
+                    person.external_link.as_ref().map_or("", |link| link.person_id.as_str()),
+                    if person.locked { 1_i64 } else { 0_i64 }
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_parent_child_edges(
+        transaction: &Transaction<'_>,
+        edges: &[ParentChild],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare(
+                "
+                INSERT INTO parent_child_edges (
+                    parent_id, child_id, kind, adoption_date, adoption_type, adoption_note, union_id
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for edge in edges {
+            statement
+                .execute(params![
+                    edge.parent.to_string(),
+                    edge.child.to_string(),
+                    &edge.kind,
+                    &edge.adoption_date,
+                    &edge.adoption_type,
+                    &edge.adoption_note,
+                    edge.union.map(|union| union.to_string()),
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_spouses(transaction: &Transaction<'_>, spouses: &[Spouse]) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare(
+                "
+                INSERT INTO spouses (
+                    person1_id, person2_id, memo, marriage_date, divorced, divorce_date,
+                    label_visible, label_position, label_font_size, label_color_r, label_color_g, label_color_b
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                ",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for spouse in spouses {
+            statement
+                .execute(params![
+                    spouse.person1.to_string(),
+                    spouse.person2.to_string(),
+                    &spouse.memo,
+                    &spouse.marriage_date,
+                    if spouse.divorced { 1_i64 } else { 0_i64 },
+                    &spouse.divorce_date,
+                    if spouse.label.visible { 1_i64 } else { 0_i64 },
+                    Self::from_spouse_label_position(spouse.label.position),
+                    spouse.label.font_size as f64,
+                    spouse.label.color.0 as i64,
+                    spouse.label.color.1 as i64,
+                    spouse.label.color.2 as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_families(transaction: &Transaction<'_>, families: &[Family]) -> Result<(), TreeRepositoryError> {
+        let mut family_statement = transaction
+            .prepare(
+                "INSERT INTO families (id, name, color_r, color_g, color_b, default_relation_kind, default_surname, \
+                 label_color_r, label_color_g, label_color_b) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        let mut member_statement = transaction
+            .prepare("INSERT INTO family_members (family_id, person_id) VALUES (?1, ?2)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for family in families {
+            let (color_r, color_g, color_b) = match family.color {
+                Some((red, green, blue)) => (Some(red as i64), Some(green as i64), Some(blue as i64)),
+                None => (None, None, None),
+            };
+            let (label_r, label_g, label_b) = match family.defaults.label_color {
+                Some((red, green, blue)) => (Some(red as i64), Some(green as i64), Some(blue as i64)),
+                None => (None, None, None),
+            };
+
+            family_statement
+                .execute(params![
+                    family.id.to_string(),
+                    &family.name,
+                    color_r,
+                    color_g,
+                    color_b,
+                    &family.defaults.relation_kind,
+                    &family.defaults.surname,
+                    label_r,
+                    label_g,
+                    label_b
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+            for member_id in &family.members {
+                member_statement
+                    .execute(params![family.id.to_string(), member_id.to_string()])
+                    .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insert_events(
+        transaction: &Transaction<'_>,
         events: &HashMap<EventId, Event>,
     ) -> Result<(), TreeRepositoryError> {
         let mut statement = transaction
             .prepare(
                 "
                 INSERT INTO events (
-                    id, name, date, description, position_x, position_y, color_r, color_g, color_b
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                    id, name, date, description, position_x, position_y, color_r, color_g, color_b, place_id, template_key
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
                 ",
             )
             .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
@@ -621,7 +1403,166 @@ impl SqliteTreeRepository {
                     event.position.1,
                     event.color.0 as i64,
                     event.color.1 as i64,
-                    event.color.2 as i64
+                    event.color.2 as i64,
+                    event.place.map(|place| place.to_string()),
+                    &event.template
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_annotations(
+        transaction: &Transaction<'_>,
+        annotations: &[TextAnnotation],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare(
+                "
+                INSERT INTO annotations (
+                    id, text, position_x, position_y, font_size, color_r, color_g, color_b
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for annotation in annotations {
+            statement
+                .execute(params![
+                    annotation.id.to_string(),
+                    &annotation.text,
+                    annotation.position.0,
+                    annotation.position.1,
+                    annotation.font_size,
+                    annotation.color.0 as i64,
+                    annotation.color.1 as i64,
+                    annotation.color.2 as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_sticky_notes(
+        transaction: &Transaction<'_>,
+        sticky_notes: &[StickyNote],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO sticky_notes (id, person_id, text, resolved) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for note in sticky_notes {
+            statement
+                .execute(params![
+                    note.id.to_string(),
+                    note.person.to_string(),
+                    &note.text,
+                    note.resolved as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_places(transaction: &Transaction<'_>, places: &[Place]) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO places (id, name, latitude, longitude, note, sort_order) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, place) in places.iter().enumerate() {
+            statement
+                .execute(params![
+                    place.id.to_string(),
+                    &place.name,
+                    place.latitude,
+                    place.longitude,
+                    &place.note,
+                    sort_order as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_unions(transaction: &Transaction<'_>, unions: &[Union]) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO unions (id, kind, partner1_id, partner2_id, sort_order) VALUES (?1, ?2, ?3, ?4, ?5)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, union) in unions.iter().enumerate() {
+            let Some(partner1) = union.partners.first() else {
+                continue;
+            };
+            statement
+                .execute(params![
+                    union.id.to_string(),
+                    &union.kind,
+                    partner1.to_string(),
+                    union.partners.get(1).map(|partner| partner.to_string()),
+                    sort_order as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_edit_macros(transaction: &Transaction<'_>, macros: &[EditMacro]) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO edit_macros (name, steps, sort_order) VALUES (?1, ?2, ?3)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, edit_macro) in macros.iter().enumerate() {
+            let steps = serde_json::to_string(&edit_macro.steps)
+                .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+            statement
+                .execute(params![&edit_macro.name, steps, sort_order as i64])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_canvas_guides(transaction: &Transaction<'_>, guides: &[CanvasGuide]) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO canvas_guides (id, axis, position, sort_order) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, guide) in guides.iter().enumerate() {
+            statement
+                .execute(params![
+                    guide.id.to_string(),
+                    Self::from_guide_axis(guide.axis),
+                    guide.position as f64,
+                    sort_order as i64
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_camera_bookmarks(
+        transaction: &Transaction<'_>,
+        bookmarks: &[CameraBookmark],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO camera_bookmarks (id, name, zoom, pan_x, pan_y, sort_order) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, bookmark) in bookmarks.iter().enumerate() {
+            statement
+                .execute(params![
+                    bookmark.id.to_string(),
+                    &bookmark.name,
+                    bookmark.zoom as f64,
+                    bookmark.pan.0 as f64,
+                    bookmark.pan.1 as f64,
+                    sort_order as i64
                 ])
                 .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
         }
@@ -629,6 +1570,43 @@ impl SqliteTreeRepository {
         Ok(())
     }
 
+    fn insert_layout_snapshots(
+        transaction: &Transaction<'_>,
+        snapshots: &[LayoutSnapshot],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut snapshot_statement = transaction
+            .prepare("INSERT INTO layout_snapshots (id, name, sort_order) VALUES (?1, ?2, ?3)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        let mut position_statement = transaction
+            .prepare(
+                "INSERT INTO layout_snapshot_positions (snapshot_id, item_kind, item_id, position_x, position_y)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (sort_order, snapshot) in snapshots.iter().enumerate() {
+            snapshot_statement
+                .execute(params![snapshot.id.to_string(), &snapshot.name, sort_order as i64])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+            let person_positions = snapshot.person_positions.iter().map(|(id, position)| (0_i64, id, position));
+            let event_positions = snapshot.event_positions.iter().map(|(id, position)| (1_i64, id, position));
+            for (item_kind, item_id, (position_x, position_y)) in person_positions.chain(event_positions) {
+                position_statement
+                    .execute(params![
+                        snapshot.id.to_string(),
+                        item_kind,
+                        item_id.to_string(),
+                        *position_x as f64,
+                        *position_y as f64
+                    ])
+                    .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn insert_event_relations(
         transaction: &Transaction<'_>,
         relations: &[EventRelation],
@@ -656,20 +1634,230 @@ impl SqliteTreeRepository {
         Ok(())
     }
 
-    fn upsert_metadata(transaction: &Transaction<'_>) -> Result<(), TreeRepositoryError> {
+    fn insert_person_relations(
+        transaction: &Transaction<'_>,
+        relations: &[PersonRelation],
+    ) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT INTO person_relations (from_id, to_id, kind, memo) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for relation in relations {
+            statement
+                .execute(params![
+                    relation.from.to_string(),
+                    relation.to.to_string(),
+                    Self::from_person_relation_kind(relation.kind),
+                    &relation.memo
+                ])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn load_background(connection: &Connection) -> Result<Option<CanvasBackground>, TreeRepositoryError> {
+        let row = connection
+            .query_row(
+                "
+                SELECT background_path, background_opacity, background_x, background_y, background_scale
+                FROM tree_metadata
+                WHERE id = 1
+                ",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, f64>(1)?,
+                        row.get::<_, f64>(2)?,
+                        row.get::<_, f64>(3)?,
+                        row.get::<_, f64>(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        Ok(row.and_then(|(path, opacity, x, y, scale)| {
+            path.map(|path| CanvasBackground {
+                path,
+                opacity: opacity as f32,
+                position: (x as f32, y as f32),
+                scale: scale as f32,
+                extra: ExtraFields::new(),
+            })
+        }))
+    }
+
+    fn load_node_style(connection: &Connection) -> Result<NodeStyle, TreeRepositoryError> {
+        let value = connection
+            .query_row("SELECT node_style FROM tree_metadata WHERE id = 1", [], |row| row.get::<_, i64>(0))
+            .optional()
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+        value.map_or(Ok(NodeStyle::default()), Self::to_node_style)
+    }
+
+    fn load_home_person(connection: &Connection) -> Result<Option<PersonId>, TreeRepositoryError> {
+        let value = connection
+            .query_row("SELECT home_person FROM tree_metadata WHERE id = 1", [], |row| {
+                row.get::<_, Option<String>>(0)
+            })
+            .optional()
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+        value
+            .flatten()
+            .map(|id| Self::parse_uuid(&id, "tree_metadata.home_person"))
+            .transpose()
+    }
+
+    /// 保存したときの形式バージョン（列を追加する前のファイルは現在の版として扱う）
+    fn load_format_version(connection: &Connection) -> Result<u32, TreeRepositoryError> {
+        let value = connection
+            .query_row("SELECT format_version FROM tree_metadata WHERE id = 1", [], |row| row.get::<_, i64>(0))
+            .optional()
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+        let version = u32::try_from(value.unwrap_or_default())
+            .map_err(|_| TreeRepositoryError::Read("invalid format_version".to_string()))?;
+        Ok(version.max(CURRENT_FORMAT_VERSION))
+    }
+
+    /// 列のない項目（新しい版で追加された項目など）を、種類とキーごとにJSONで保存する
+    fn insert_extra_fields(transaction: &Transaction<'_>, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
+        let mut statement = transaction
+            .prepare("INSERT OR REPLACE INTO extra_fields (entity, item_key, fields) VALUES (?1, ?2, ?3)")
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+
+        for (entity, key, fields) in Self::extra_field_entries(tree) {
+            if fields.is_empty() {
+                continue;
+            }
+            let text = serde_json::to_string(fields)
+                .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+            statement
+                .execute(params![entity, key, text])
+                .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn load_extra_fields(connection: &Connection, tree: &mut FamilyTree) -> Result<(), TreeRepositoryError> {
+        let mut statement = connection
+            .prepare("SELECT entity, item_key, fields FROM extra_fields")
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+            })
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let mut stored = HashMap::new();
+        for row in rows {
+            let (entity, key, text) = row.map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+            let fields: ExtraFields = serde_json::from_str(&text)
+                .map_err(|error| TreeRepositoryError::Deserialize(error.to_string()))?;
+            stored.insert((entity, key), fields);
+        }
+        if stored.is_empty() {
+            return Ok(());
+        }
+
+        for (entity, key, fields) in Self::extra_field_slots(tree) {
+            if let Some(values) = stored.remove(&(entity.to_string(), key)) {
+                *fields = values;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 保存する`extra`と、その持ち主を表す種類とキー（IDのない関係は両端のIDで表す）
+    fn extra_field_entries(tree: &FamilyTree) -> Vec<(&'static str, String, &ExtraFields)> {
+        let mut entries = vec![("tree", String::new(), &tree.extra)];
+        entries.extend(tree.background.iter().map(|background| ("background", String::new(), &background.extra)));
+        entries.extend(tree.persons.values().map(|person| ("person", person.id.to_string(), &person.extra)));
+        entries.extend(tree.edges.iter().map(|edge| ("edge", format!("{}:{}", edge.parent, edge.child), &edge.extra)));
+        entries.extend(tree.spouses.iter().map(|spouse| ("spouse", format!("{}:{}", spouse.person1, spouse.person2), &spouse.extra)));
+        entries.extend(tree.families.iter().map(|family| ("family", family.id.to_string(), &family.extra)));
+        entries.extend(tree.events.values().map(|event| ("event", event.id.to_string(), &event.extra)));
+        entries.extend(tree.event_relations.iter().map(|relation| ("event_relation", format!("{}:{}", relation.event, relation.person), &relation.extra)));
+        entries.extend(tree.person_relations.iter().map(|relation| ("person_relation", format!("{}:{}", relation.from, relation.to), &relation.extra)));
+        entries.extend(tree.annotations.iter().map(|annotation| ("annotation", annotation.id.to_string(), &annotation.extra)));
+        entries.extend(tree.sticky_notes.iter().map(|note| ("sticky_note", note.id.to_string(), &note.extra)));
+        entries.extend(tree.layout_snapshots.iter().map(|snapshot| ("layout_snapshot", snapshot.id.to_string(), &snapshot.extra)));
+        entries.extend(tree.camera_bookmarks.iter().map(|bookmark| ("camera_bookmark", bookmark.id.to_string(), &bookmark.extra)));
+        entries.extend(tree.guides.iter().map(|guide| ("guide", guide.id.to_string(), &guide.extra)));
+        entries.extend(tree.places.iter().map(|place| ("place", place.id.to_string(), &place.extra)));
+        entries.extend(tree.unions.iter().map(|union| ("union", union.id.to_string(), &union.extra)));
+        entries
+    }
+
+    /// `extra_field_entries`と同じ種類とキーで、読み込んだ`extra`を戻す先
+    fn extra_field_slots(tree: &mut FamilyTree) -> Vec<(&'static str, String, &mut ExtraFields)> {
+        let mut slots = vec![("tree", String::new(), &mut tree.extra)];
+        slots.extend(tree.background.iter_mut().map(|background| ("background", String::new(), &mut background.extra)));
+        slots.extend(tree.persons.values_mut().map(|person| ("person", person.id.to_string(), &mut person.extra)));
+        slots.extend(tree.edges.iter_mut().map(|edge| ("edge", format!("{}:{}", edge.parent, edge.child), &mut edge.extra)));
+        slots.extend(tree.spouses.iter_mut().map(|spouse| ("spouse", format!("{}:{}", spouse.person1, spouse.person2), &mut spouse.extra)));
+        slots.extend(tree.families.iter_mut().map(|family| ("family", family.id.to_string(), &mut family.extra)));
+        slots.extend(tree.events.values_mut().map(|event| ("event", event.id.to_string(), &mut event.extra)));
+        slots.extend(tree.event_relations.iter_mut().map(|relation| ("event_relation", format!("{}:{}", relation.event, relation.person), &mut relation.extra)));
+        slots.extend(tree.person_relations.iter_mut().map(|relation| ("person_relation", format!("{}:{}", relation.from, relation.to), &mut relation.extra)));
+        slots.extend(tree.annotations.iter_mut().map(|annotation| ("annotation", annotation.id.to_string(), &mut annotation.extra)));
+        slots.extend(tree.sticky_notes.iter_mut().map(|note| ("sticky_note", note.id.to_string(), &mut note.extra)));
+        slots.extend(tree.layout_snapshots.iter_mut().map(|snapshot| ("layout_snapshot", snapshot.id.to_string(), &mut snapshot.extra)));
+        slots.extend(tree.camera_bookmarks.iter_mut().map(|bookmark| ("camera_bookmark", bookmark.id.to_string(), &mut bookmark.extra)));
+        slots.extend(tree.guides.iter_mut().map(|guide| ("guide", guide.id.to_string(), &mut guide.extra)));
+        slots.extend(tree.places.iter_mut().map(|place| ("place", place.id.to_string(), &mut place.extra)));
+        slots.extend(tree.unions.iter_mut().map(|union| ("union", union.id.to_string(), &mut union.extra)));
+        slots
+    }
+
+    fn upsert_metadata(
+        transaction: &Transaction<'_>,
+        background: Option<&CanvasBackground>,
+        node_style: NodeStyle,
+        home_person: Option<PersonId>,
+        format_version: u32,
+    ) -> Result<(), TreeRepositoryError> {
         let updated_at = Utc::now().to_rfc3339();
+        let default_background = CanvasBackground::new(String::new());
+        let background_values = background.unwrap_or(&default_background);
 
         transaction
             .execute(
                 "
-                INSERT INTO tree_metadata (id, schema_version, updated_at)
-                VALUES (1, ?1, ?2)
+                INSERT INTO tree_metadata (
+                    id, schema_version, updated_at,
+                    background_path, background_opacity, background_x, background_y, background_scale,
+                    node_style, home_person, format_version
+                )
+                VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
                 ON CONFLICT(id) DO UPDATE SET
                     schema_version = excluded.schema_version,
-                    updated_at = excluded.updated_at
-                
+                    updated_at = excluded.updated_at,
+                    background_path = excluded.background_path,
+                    background_opacity = excluded.background_opacity,
+                    background_x = excluded.background_x,
+                    background_y = excluded.background_y,
+                    background_scale = excluded.background_scale,
+                    node_style = excluded.node_style,
+                    home_person = excluded.home_person,
+                    format_version = excluded.format_version
                 ",
-                params![SCHEMA_VERSION, updated_at],
+                params![
+                    SCHEMA_VERSION,
+                    updated_at,
+                    background.map(|background| background.path.as_str()),
+                    background_values.opacity as f64,
+                    background_values.position.0 as f64,
+                    background_values.position.1 as f64,
+                    background_values.scale as f64,
+                    Self::from_node_style(node_style),
+                    home_person.map(|id| id.to_string()),
+                    format_version,
+                ],
             )
             .map_err(|error| TreeRepositoryError::Write(error.to_string()))?;
 
@@ -694,15 +1882,46 @@ impl TreeRepository for SqliteTreeRepository {
         let families = Self::load_families(&connection)?;
         let events = Self::load_events(&connection)?;
         let event_relations = Self::load_event_relations(&connection)?;
-
-        Ok(FamilyTree {
+        let person_relations = Self::load_person_relations(&connection)?;
+        let background = Self::load_background(&connection)?;
+        let annotations = Self::load_annotations(&connection)?;
+        let sticky_notes = Self::load_sticky_notes(&connection)?;
+        let node_style = Self::load_node_style(&connection)?;
+        let layout_snapshots = Self::load_layout_snapshots(&connection)?;
+        let camera_bookmarks = Self::load_camera_bookmarks(&connection)?;
+        let guides = Self::load_canvas_guides(&connection)?;
+        let places = Self::load_places(&connection)?;
+        let unions = Self::load_unions(&connection)?;
+        let macros = Self::load_edit_macros(&connection)?;
+        let home_person = Self::load_home_person(&connection)?;
+        let format_version = Self::load_format_version(&connection)?;
+
+        let mut tree = FamilyTree {
+            format_version,
             persons,
             edges,
             spouses,
             families,
             events,
             event_relations,
-        })
+            person_relations,
+            background,
+            annotations,
+            sticky_notes,
+            node_style,
+            layout_snapshots,
+            camera_bookmarks,
+            guides,
+            places,
+            unions,
+            macros,
+            home_person,
+            extra: ExtraFields::new(),
+        };
+        Self::load_extra_fields(&connection, &mut tree)?;
+        // 家族の単位の列がない古いファイルでも、親子関係から単位を組み直す
+        tree.sync_unions();
+        Ok(tree)
     }
 
     fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
@@ -720,7 +1939,23 @@ impl TreeRepository for SqliteTreeRepository {
         Self::insert_families(&transaction, &tree.families)?;
         Self::insert_events(&transaction, &tree.events)?;
         Self::insert_event_relations(&transaction, &tree.event_relations)?;
-        Self::upsert_metadata(&transaction)?;
+        Self::insert_person_relations(&transaction, &tree.person_relations)?;
+        Self::insert_annotations(&transaction, &tree.annotations)?;
+        Self::insert_sticky_notes(&transaction, &tree.sticky_notes)?;
+        Self::insert_layout_snapshots(&transaction, &tree.layout_snapshots)?;
+        Self::insert_camera_bookmarks(&transaction, &tree.camera_bookmarks)?;
+        Self::insert_canvas_guides(&transaction, &tree.guides)?;
+        Self::insert_places(&transaction, &tree.places)?;
+        Self::insert_unions(&transaction, &tree.unions)?;
+        Self::insert_edit_macros(&transaction, &tree.macros)?;
+        Self::insert_extra_fields(&transaction, tree)?;
+        Self::upsert_metadata(
+            &transaction,
+            tree.background.as_ref(),
+            tree.node_style,
+            tree.home_person,
+            tree.format_version.max(CURRENT_FORMAT_VERSION),
+        )?;
 
         transaction
             .commit()
@@ -738,8 +1973,12 @@ mod tests {
     use uuid::Uuid;
 
     use super::SqliteTreeRepository;
+    use crate::core::edit_macro::{EditMacro, MacroStep};
     use crate::application::TreeRepository;
-    use crate::core::tree::{EventRelationType, FamilyTree, Gender, PersonDisplayMode};
+    use crate::core::tree::{
+        CanvasBackground, EventRelationType, ExternalPersonLink, ExtraFields, FamilyDefaults, FamilyTree, Gender, GuideAxis, NodeStyle, PersonBadge, PersonDisplayMode,
+        PersonRelationKind, SpouseLabelPosition, SpouseLabelStyle,
+    };
 
     #[test]
     fn save_and_load_round_trip() {
@@ -780,23 +2019,67 @@ mod tests {
         );
         let child_id = tree.add_person(
             "Child".to_string(),
-            Gender::Female,
+            Gender::Other("non-binary".to_string()),
             Some("2000-02-02".to_string()),
             "memo child".to_string(),
             false,
             None,
             (220.0, 240.0),
         );
-        tree.add_parent_child(parent_id, child_id, "biological".to_string());
+        tree.add_parent_child(parent_id, child_id, "adoptive".to_string());
+        if let Some(edge) = tree.parent_child_relation_mut(parent_id, child_id) {
+            edge.adoption_date = Some("2005-06-07".to_string());
+            edge.adoption_type = "special".to_string();
+            edge.adoption_note = "adoption memo".to_string();
+        }
         tree.add_spouse(parent_id, child_id, "test spouse".to_string());
+        tree.spouses[0].label = SpouseLabelStyle {
+            visible: false,
+            position: SpouseLabelPosition::Below,
+            font_size: 14.5,
+            color: (10, 20, 30),
+        };
+        tree.mark_multiple_birth(&[parent_id, child_id]);
+        tree.add_person_relation(
+            parent_id,
+            child_id,
+            PersonRelationKind::Godparent,
+            "godparent memo".to_string(),
+        );
+        if let Some(spouse) = tree.spouse_relation_mut(parent_id, child_id) {
+            spouse.marriage_date = Some("1995-05-05".to_string());
+            spouse.divorced = true;
+        }
 
         if let Some(parent) = tree.persons.get_mut(&parent_id) {
             parent.display_mode = PersonDisplayMode::NameAndPhoto;
         }
+        if let Some(child) = tree.persons.get_mut(&child_id) {
+            child.display_mode = PersonDisplayMode::PhotoOnly;
+            child.pinned = true;
+            child.locked = true;
+            child.badges = vec![PersonBadge::DirectAncestor, PersonBadge::Military];
+            child.external_link = Some(ExternalPersonLink {
+                tree: "relatives/maternal.json".to_string(),
+                person_id: "1a2b3c4d".to_string(),
+            });
+        }
 
         let family_id = tree.add_family("Main Family".to_string(), Some((1, 2, 3)));
         tree.add_member_to_family(family_id, parent_id);
         tree.add_member_to_family(family_id, child_id);
+        tree.families[0].defaults = FamilyDefaults {
+            relation_kind: "adoptive".to_string(),
+            surname: "Main".to_string(),
+            label_color: Some((4, 5, 6)),
+        };
+        tree.macros.push(EditMacro::new(
+            "Join family".to_string(),
+            vec![
+                MacroStep::AddParent { parent: parent_id, kind: "adoptive".to_string() },
+                MacroStep::AddToFamily { family: family_id },
+            ],
+        ));
 
         let event_id = tree.add_event(
             "Test Event".to_string(),
@@ -812,6 +2095,39 @@ mod tests {
             "event relation memo".to_string(),
         );
 
+        tree.background = Some(CanvasBackground {
+            path: "crest.png".to_string(),
+            opacity: 0.25,
+            position: (-100.0, 40.0),
+            scale: 2.0,
+            extra: ExtraFields::new(),
+        });
+        tree.node_style = NodeStyle::CircularAvatar;
+        tree.home_person = Some(child_id);
+        let annotation_id = tree.add_annotation("Paternal side".to_string(), (5.0, -40.0), 20.0, (10, 20, 30));
+        let note_id = tree.add_sticky_note(parent_id, "Verify birth year with aunt".to_string());
+        if let Some(note) = tree.sticky_note_mut(note_id) {
+            note.resolved = true;
+        }
+        tree.save_layout_snapshot("print layout".to_string());
+        tree.save_layout_snapshot("compact".to_string());
+        tree.save_camera_bookmark("paternal branch".to_string(), 1.25, (-40.0, 15.5));
+        tree.add_guide(GuideAxis::Vertical, 120.0);
+        tree.add_guide(GuideAxis::Horizontal, -35.5);
+        let place_id = tree.add_place("Kyoto".to_string());
+        if let Some(place) = tree.place_mut(place_id) {
+            place.latitude = Some(35.0116);
+            place.longitude = Some(135.7681);
+            place.note = "old capital".to_string();
+        }
+        tree.add_place("Unknown village".to_string());
+        tree.persons.get_mut(&child_id).unwrap().birth_place = Some(place_id);
+        tree.persons.get_mut(&parent_id).unwrap().death_place = Some(place_id);
+        if let Some(event) = tree.events.values_mut().next() {
+            event.place = Some(place_id);
+            event.template = Some("template_memorial_services/memorial_1st".to_string());
+        }
+
         let save_result = repository.save(&file_path_str, &tree);
         assert!(save_result.is_ok(), "{save_result:?}");
 
@@ -822,15 +2138,56 @@ mod tests {
         assert_eq!(loaded_tree.persons.len(), 2);
         assert_eq!(loaded_tree.edges.len(), 1);
         assert_eq!(loaded_tree.spouses.len(), 1);
+        assert_eq!(loaded_tree.spouses[0].label, tree.spouses[0].label);
         assert_eq!(loaded_tree.families.len(), 1);
+        assert_eq!(loaded_tree.families[0].defaults, tree.families[0].defaults);
         assert_eq!(loaded_tree.events.len(), 1);
         assert_eq!(loaded_tree.event_relations.len(), 1);
+        assert_eq!(loaded_tree.person_relations.len(), 1);
+        assert_eq!(loaded_tree.person_relations[0].kind, PersonRelationKind::Godparent);
+        assert_eq!(loaded_tree.person_relations[0].memo, "godparent memo");
 
         let loaded_parent = loaded_tree
             .persons
             .get(&parent_id)
             .expect("parent should exist after load");
         assert_eq!(loaded_parent.display_mode, PersonDisplayMode::NameAndPhoto);
+        assert_eq!(loaded_tree.persons[&child_id].display_mode, PersonDisplayMode::PhotoOnly);
+        assert!(loaded_tree.persons[&child_id].pinned);
+        assert!(!loaded_parent.pinned);
+        assert!(loaded_tree.persons[&child_id].locked);
+        assert!(!loaded_parent.locked);
+        assert_eq!(
+            loaded_tree.persons[&child_id].badges,
+            vec![PersonBadge::DirectAncestor, PersonBadge::Military]
+        );
+        assert!(loaded_parent.badges.is_empty());
+        assert_eq!(
+            loaded_tree.persons[&child_id].external_link,
+            Some(ExternalPersonLink { tree: "relatives/maternal.json".to_string(), person_id: "1a2b3c4d".to_string() })
+        );
+        assert!(loaded_parent.external_link.is_none());
+        assert!(loaded_parent.multiple_birth_group.is_some());
+        assert_eq!(
+            loaded_tree.persons[&child_id].gender,
+            Gender::Other("non-binary".to_string())
+        );
+        assert_eq!(loaded_tree.multiple_birth_siblings_of(parent_id), vec![child_id]);
+
+        let loaded_edge = loaded_tree
+            .parent_child_relation(parent_id, child_id)
+            .expect("parent-child edge should exist after load");
+        assert!(loaded_edge.is_adoptive());
+        assert_eq!(loaded_edge.adoption_date, Some("2005-06-07".to_string()));
+        assert_eq!(loaded_edge.adoption_type, "special");
+        assert_eq!(loaded_edge.adoption_note, "adoption memo");
+
+        let loaded_spouse = loaded_tree
+            .spouse_relation(parent_id, child_id)
+            .expect("spouse relation should exist after load");
+        assert_eq!(loaded_spouse.marriage_date, Some("1995-05-05".to_string()));
+        assert!(loaded_spouse.divorced);
+        assert_eq!(loaded_spouse.divorce_date, None);
 
         let loaded_family = loaded_tree
             .families
@@ -845,6 +2202,98 @@ mod tests {
             .first()
             .expect("event relation should exist after load");
         assert_eq!(loaded_relation.relation_type, EventRelationType::ArrowToPerson);
+        assert_eq!(loaded_tree.background, tree.background);
+        assert_eq!(loaded_tree.node_style, NodeStyle::CircularAvatar);
+        assert_eq!(loaded_tree.home_person, Some(child_id));
+        assert_eq!(loaded_tree.annotation(annotation_id), tree.annotation(annotation_id));
+        assert_eq!(loaded_tree.sticky_notes, tree.sticky_notes);
+        assert_eq!(loaded_tree.layout_snapshots, tree.layout_snapshots);
+        assert_eq!(loaded_tree.macros, tree.macros);
+        assert_eq!(loaded_tree.camera_bookmarks, tree.camera_bookmarks);
+        assert_eq!(loaded_tree.guides, tree.guides);
+        assert_eq!(loaded_tree.places, tree.places);
+        assert_eq!(loaded_tree.unions(), tree.unions());
+        assert_eq!(loaded_tree.edges[0].union, tree.edges[0].union);
+        assert_eq!(loaded_tree.persons[&child_id].birth_place, Some(place_id));
+        assert_eq!(loaded_tree.persons[&parent_id].death_place, Some(place_id));
+        assert!(loaded_tree.events.values().all(|event| event.place == Some(place_id)));
+        assert!(loaded_tree.events.values().all(|event| event.template.as_deref() == Some("template_memorial_services/memorial_1st")));
+
+        let remove_result = fs::remove_file(file_path);
+        assert!(remove_result.is_ok());
+    }
+
+    #[test]
+    fn load_migrates_version_1_spouses_table() {
+        let repository = SqliteTreeRepository;
+        let file_name = format!("family_tree_test_v1_{}.sqlite", Uuid::new_v4());
+        let file_path = env::temp_dir().join(file_name);
+        let file_path_str = file_path.to_string_lossy().to_string();
+
+        {
+            let connection = rusqlite::Connection::open(&file_path_str).expect("open sqlite");
+            connection
+                .execute_batch(
+                    "
+                    CREATE TABLE tree_metadata (
+                        id INTEGER PRIMARY KEY CHECK (id = 1),
+                        schema_version INTEGER NOT NULL,
+                        updated_at TEXT NOT NULL
+                    );
+                    CREATE TABLE spouses (
+                        person1_id TEXT NOT NULL,
+                        person2_id TEXT NOT NULL,
+                        memo TEXT NOT NULL
+                    );
+                    INSERT INTO tree_metadata (id, schema_version, updated_at)
+                    VALUES (1, 1, '2025-01-01T00:00:00Z');
+                    INSERT INTO spouses (person1_id, person2_id, memo) VALUES (
+                        '00000000-0000-0000-0000-000000000001',
+                        '00000000-0000-0000-0000-000000000002',
+                        'old memo'
+                    );
+                    ",
+                )
+                .expect("create version 1 schema");
+        }
+
+        let loaded_tree_result = repository.load(&file_path_str);
+        assert!(loaded_tree_result.is_ok(), "{loaded_tree_result:?}");
+        let loaded_tree = loaded_tree_result.expect("version 1 file should load");
+        assert_eq!(loaded_tree.spouses.len(), 1);
+        assert_eq!(loaded_tree.spouses[0].memo, "old memo");
+        assert_eq!(loaded_tree.spouses[0].marriage_date, None);
+        assert!(!loaded_tree.spouses[0].divorced);
+        assert_eq!(loaded_tree.spouses[0].label, SpouseLabelStyle::default());
+
+        let remove_result = fs::remove_file(file_path);
+        assert!(remove_result.is_ok());
+    }
+
+    #[test]
+    fn keeps_unknown_fields_and_newer_format_version() {
+        let repository = SqliteTreeRepository;
+        let file_name = format!("family_tree_test_extra_{}.sqlite", Uuid::new_v4());
+        let file_path = env::temp_dir().join(file_name);
+        let file_path_str = file_path.to_string_lossy().to_string();
+
+        let mut tree = FamilyTree::default();
+        tree.format_version = crate::core::tree::CURRENT_FORMAT_VERSION + 1;
+        let parent_id = tree.add_person("Parent".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let child_id = tree.add_person("Child".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 120.0));
+        tree.add_parent_child(parent_id, child_id, "biological".to_string());
+        tree.persons.get_mut(&parent_id).unwrap().extra.insert("pronouns".to_string(), serde_json::json!("she"));
+        tree.edges[0].extra.insert("x-source".to_string(), serde_json::json!({ "page": 12 }));
+        tree.extra.insert("plugin_settings".to_string(), serde_json::json!({ "theme": "sepia" }));
+
+        repository.save(&file_path_str, &tree).expect("tree should save");
+        let loaded_tree = repository.load(&file_path_str).expect("tree should load");
+
+        assert_eq!(loaded_tree.format_version, tree.format_version);
+        assert_eq!(loaded_tree.persons[&parent_id].extra, tree.persons[&parent_id].extra);
+        assert!(loaded_tree.persons[&child_id].extra.is_empty());
+        assert_eq!(loaded_tree.edges[0].extra, tree.edges[0].extra);
+        assert_eq!(loaded_tree.extra, tree.extra);
 
         let remove_result = fs::remove_file(file_path);
         assert!(remove_result.is_ok());
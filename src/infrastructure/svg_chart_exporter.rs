@@ -0,0 +1,556 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use eframe::egui;
+
+use crate::application::{
+    ChartExportError, ChartExporter, ChartLayers, ChartLine, ChartLineStyle, ChartScene, ChartTitleBlock,
+    ImageExportOptions, PosterOptions,
+};
+
+/// 全体図の周囲に確保する余白（ワールド座標）
+const CHART_PADDING: f32 = 20.0;
+/// 全体図キーの1マスの大きさ（mm）
+const OVERVIEW_CELL_MM: f32 = 3.0;
+
+/// `ChartScene`をSVGファイルとして書き出すエクスポーター実装。
+///
+/// 分割ポスターでは1ページごとに1つのSVGファイルを作成する。
+pub struct SvgChartExporter;
+
+impl SvgChartExporter {
+    /// 全体図を1枚にまとめたSVG文書を生成する
+    pub fn render_image(scene: &ChartScene, options: &ImageExportOptions) -> Result<String, ChartExportError> {
+        let bounds = scene
+            .bounds(&options.layers)
+            .ok_or(ChartExportError::EmptyChart)?
+            .expand(CHART_PADDING);
+        let (width_mm, height_mm) = options.print_size_mm(bounds.size());
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width_mm}mm" height="{height_mm}mm" viewBox="{} {} {} {}">"#,
+            bounds.min.x,
+            bounds.min.y,
+            bounds.width(),
+            bounds.height()
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white"/>"#,
+            bounds.min.x,
+            bounds.min.y,
+            bounds.width(),
+            bounds.height()
+        );
+        Self::write_chart_elements(&mut svg, scene, &options.layers, bounds);
+        if let Some(watermark) = &scene.watermark {
+            Self::write_watermark(&mut svg, watermark, bounds);
+        }
+        let _ = writeln!(svg, "</svg>");
+
+        Ok(svg)
+    }
+
+    /// 指定したページ（0始まりの行・列）のSVG文書を生成する
+    pub fn render_poster_page(
+        scene: &ChartScene,
+        options: &PosterOptions,
+        row: u32,
+        column: u32,
+    ) -> Result<String, ChartExportError> {
+        let bounds = scene
+            .bounds(&options.layers)
+            .ok_or(ChartExportError::EmptyChart)?
+            .expand(CHART_PADDING);
+
+        let (page_width, page_height) = options.page_size_mm();
+        let margin = options.margin_mm;
+        let content_width = page_width - margin * 2.0;
+        let content_height = page_height - margin * 2.0;
+        let total_width = content_width * options.columns as f32;
+        let total_height = content_height * options.rows as f32;
+
+        // 全ページを合わせた領域に収まる倍率（mm / ワールド座標）
+        let scale = (total_width / bounds.width()).min(total_height / bounds.height());
+        let offset_x = (total_width - bounds.width() * scale) / 2.0;
+        let offset_y = (total_height - bounds.height() * scale) / 2.0;
+        let translate_x = margin + offset_x - content_width * column as f32;
+        let translate_y = margin + offset_y - content_height * row as f32;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{page_width}mm" height="{page_height}mm" viewBox="0 0 {page_width} {page_height}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<defs><clipPath id="content"><rect x="{margin}" y="{margin}" width="{content_width}" height="{content_height}"/></clipPath></defs>"#
+        );
+        let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+        let _ = writeln!(
+            svg,
+            r#"<g clip-path="url(#content)"><g transform="translate({translate_x} {translate_y}) scale({scale}) translate({} {})">"#,
+            -bounds.min.x,
+            -bounds.min.y
+        );
+        Self::write_chart_elements(&mut svg, scene, &options.layers, bounds);
+        let _ = writeln!(svg, "</g></g>");
+        if let Some(watermark) = &scene.watermark {
+            let content_rect =
+                egui::Rect::from_min_size(egui::pos2(margin, margin), egui::vec2(content_width, content_height));
+            Self::write_watermark(&mut svg, watermark, content_rect);
+        }
+
+        Self::write_crop_marks(&mut svg, margin, content_width, content_height);
+        Self::write_overview_key(&mut svg, options, row, column);
+        let _ = writeln!(svg, "</svg>");
+
+        Ok(svg)
+    }
+
+    fn write_chart_elements(svg: &mut String, scene: &ChartScene, layers: &ChartLayers, bounds: egui::Rect) {
+        if layers.grid {
+            Self::write_grid(svg, scene.grid_size, bounds);
+        }
+
+        if layers.family_boxes {
+            for family in &scene.family_boxes {
+                let (r, g, b) = family.color;
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" rx="8" fill="rgb({r},{g},{b})" fill-opacity="0.12" stroke="rgb({r},{g},{b})" stroke-width="2"/>"#,
+                    family.rect.min.x,
+                    family.rect.min.y,
+                    family.rect.width(),
+                    family.rect.height()
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="13" font-family="sans-serif" dominant-baseline="hanging">{}</text>"#,
+                    family.rect.min.x + 20.0,
+                    family.rect.min.y + 6.0,
+                    escape_xml(&family.label)
+                );
+            }
+        }
+
+        for line in &scene.lines {
+            Self::write_line(svg, line);
+        }
+
+        if layers.events {
+            for line in &scene.event_lines {
+                Self::write_line(svg, line);
+            }
+            for event in &scene.events {
+                let (r, g, b) = event.fill;
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="rgb({r},{g},{b})" stroke="dimgray" stroke-width="2"/>"#,
+                    event.rect.min.x,
+                    event.rect.min.y,
+                    event.rect.width(),
+                    event.rect.height()
+                );
+                Self::write_centered_text(svg, event.rect.center(), 13.0, &event.label);
+            }
+        }
+
+        for node in &scene.nodes {
+            let (r, g, b) = node.fill;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="6" fill="rgb({r},{g},{b})" stroke="gray" stroke-width="1"/>"#,
+                node.rect.min.x,
+                node.rect.min.y,
+                node.rect.width(),
+                node.rect.height()
+            );
+            Self::write_centered_text(svg, node.rect.center(), 14.0, &node.label);
+            if !node.badges.is_empty() {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="10" font-family="sans-serif" fill="dimgray" text-anchor="end">{}</text>"#,
+                    node.rect.max.x - 3.0,
+                    node.rect.max.y - 3.0,
+                    escape_xml(&node.badges)
+                );
+            }
+        }
+
+        if let Some(title_block) = &scene.title_block
+            && let Some(chart_bounds) = scene.chart_bounds(layers)
+        {
+            Self::write_title_block(svg, title_block, title_block.rect_below(chart_bounds));
+        }
+    }
+
+    fn write_title_block(svg: &mut String, title_block: &ChartTitleBlock, rect: egui::Rect) {
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white" stroke="black" stroke-width="1"/>"#,
+            rect.min.x,
+            rect.min.y,
+            rect.width(),
+            rect.height()
+        );
+        let x = rect.min.x + 10.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" font-size="16" font-weight="bold" font-family="sans-serif">{}</text>"#,
+            rect.min.y + 28.0,
+            escape_xml(&title_block.title)
+        );
+        for (index, detail) in title_block.details.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{x}" y="{}" font-size="12" font-family="sans-serif">{}</text>"#,
+                rect.min.y + 48.0 + 18.0 * index as f32,
+                escape_xml(detail)
+            );
+        }
+    }
+
+    /// 指定した領域の中央に斜めの透かし文字を描画
+    fn write_watermark(svg: &mut String, text: &str, area: egui::Rect) {
+        let char_count = text.chars().count().max(1) as f32;
+        let font_size = (area.width() * 1.2 / char_count).min(area.height() * 0.3);
+        let center = area.center();
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="{font_size}" font-family="sans-serif" font-weight="bold" fill="gray" fill-opacity="0.18" text-anchor="middle" dominant-baseline="central" transform="rotate(-30 {} {})">{}</text>"#,
+            center.x,
+            center.y,
+            center.x,
+            center.y,
+            escape_xml(text)
+        );
+    }
+
+    fn write_line(svg: &mut String, line: &ChartLine) {
+        let dash = match line.style {
+            ChartLineStyle::Solid => "",
+            ChartLineStyle::Dotted => r#" stroke-dasharray="1 5" stroke-linecap="round""#,
+        };
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#a0a0a0" stroke-width="1.5"{dash}/>"##,
+            line.from.x, line.from.y, line.to.x, line.to.y
+        );
+    }
+
+    fn write_centered_text(svg: &mut String, center: egui::Pos2, font_size: f32, text: &str) {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="{font_size}" font-family="sans-serif" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            center.x,
+            center.y,
+            escape_xml(text)
+        );
+    }
+
+    /// キャンバスと同じ間隔のグリッドを1つのパスとして描画
+    fn write_grid(svg: &mut String, grid_size: f32, bounds: egui::Rect) {
+        if grid_size <= 0.0 {
+            return;
+        }
+
+        let mut path = String::new();
+        let mut x = (bounds.min.x / grid_size).ceil() * grid_size;
+        while x <= bounds.max.x {
+            let _ = write!(path, "M{x} {}V{}", bounds.min.y, bounds.max.y);
+            x += grid_size;
+        }
+        let mut y = (bounds.min.y / grid_size).ceil() * grid_size;
+        while y <= bounds.max.y {
+            let _ = write!(path, "M{} {y}H{}", bounds.min.x, bounds.max.x);
+            y += grid_size;
+        }
+        let _ = writeln!(svg, r##"<path d="{path}" stroke="#dcdcdc" stroke-width="0.5" fill="none"/>"##);
+    }
+
+    /// 裁断位置を示すトンボを内容領域の四隅に描画
+    fn write_crop_marks(svg: &mut String, margin: f32, content_width: f32, content_height: f32) {
+        let gap = 1.0;
+        let length = margin * 0.6;
+        let corners = [
+            (margin, margin, -1.0, -1.0),
+            (margin + content_width, margin, 1.0, -1.0),
+            (margin, margin + content_height, -1.0, 1.0),
+            (margin + content_width, margin + content_height, 1.0, 1.0),
+        ];
+
+        for (x, y, dx, dy) in corners {
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}" stroke="black" stroke-width="0.2"/>"#,
+                x + dx * gap,
+                x + dx * (gap + length)
+            );
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="black" stroke-width="0.2"/>"#,
+                y + dy * gap,
+                y + dy * (gap + length)
+            );
+        }
+    }
+
+    /// ページ全体における位置を示す小さな全体図キーを右下の余白に描画
+    fn write_overview_key(svg: &mut String, options: &PosterOptions, row: u32, column: u32) {
+        let (page_width, page_height) = options.page_size_mm();
+        let cell = OVERVIEW_CELL_MM.min(options.margin_mm * 0.6 / options.rows as f32);
+        let key_width = cell * options.columns as f32;
+        let key_height = cell * options.rows as f32;
+        let key_x = page_width - options.margin_mm - key_width;
+        let key_y = page_height - (options.margin_mm + key_height) / 2.0;
+
+        for r in 0..options.rows {
+            for c in 0..options.columns {
+                let fill = if r == row && c == column { "#606060" } else { "white" };
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{cell}" height="{cell}" fill="{fill}" stroke="black" stroke-width="0.15"/>"#,
+                    key_x + cell * c as f32,
+                    key_y + cell * r as f32
+                );
+            }
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="2.5" font-family="sans-serif" text-anchor="end" dominant-baseline="central">{}-{} / {}×{}</text>"#,
+            key_x - 1.5,
+            key_y + key_height / 2.0,
+            row + 1,
+            column + 1,
+            options.rows,
+            options.columns
+        );
+    }
+
+    /// ページごとのファイルパス（例: tree_r1c2.svg）を生成
+    fn page_file_path(base_path: &str, row: u32, column: u32) -> String {
+        let path = Path::new(base_path);
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "poster".to_string());
+        let file_name = format!("{stem}_r{}c{}.svg", row + 1, column + 1);
+        path.with_file_name(file_name).to_string_lossy().to_string()
+    }
+}
+
+impl ChartExporter for SvgChartExporter {
+    fn export_poster(
+        &self,
+        base_path: &str,
+        scene: &ChartScene,
+        options: &PosterOptions,
+    ) -> Result<Vec<String>, ChartExportError> {
+        let mut written_paths = Vec::new();
+        for row in 0..options.rows {
+            for column in 0..options.columns {
+                let svg = Self::render_poster_page(scene, options, row, column)?;
+                let file_path = Self::page_file_path(base_path, row, column);
+                fs::write(&file_path, svg).map_err(|error| ChartExportError::Write(error.to_string()))?;
+                written_paths.push(file_path);
+            }
+        }
+        Ok(written_paths)
+    }
+
+    fn export_image(
+        &self,
+        path: &str,
+        scene: &ChartScene,
+        options: &ImageExportOptions,
+    ) -> Result<(), ChartExportError> {
+        let svg = Self::render_image(scene, options)?;
+        fs::write(path, svg).map_err(|error| ChartExportError::Write(error.to_string()))
+    }
+}
+
+/// XMLの特殊文字をエスケープする
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use eframe::egui;
+    use uuid::Uuid;
+
+    use super::{SvgChartExporter, escape_xml};
+    use crate::application::{
+        ChartBox, ChartExportError, ChartExporter, ChartLayers, ChartLine, ChartLineStyle, ChartNode, ChartScene,
+        ChartTitleBlock, ImageExportOptions, PosterOptions,
+    };
+
+    fn sample_scene() -> ChartScene {
+        ChartScene {
+            nodes: vec![
+                ChartNode {
+                    rect: egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(100.0, 30.0)),
+                    label: "Tom & Jerry".to_string(),
+                    fill: (173, 216, 230),
+                    badges: "★⚓".to_string(),
+                },
+                ChartNode {
+                    rect: egui::Rect::from_min_size(egui::pos2(0.0, 200.0), egui::vec2(100.0, 30.0)),
+                    label: "<Child>".to_string(),
+                    fill: (255, 182, 193),
+                    badges: String::new(),
+                },
+            ],
+            lines: vec![ChartLine {
+                from: egui::pos2(50.0, 30.0),
+                to: egui::pos2(50.0, 200.0),
+                style: ChartLineStyle::Solid,
+            }],
+            family_boxes: vec![ChartBox {
+                rect: egui::Rect::from_min_max(egui::pos2(-20.0, -52.0), egui::pos2(120.0, 250.0)),
+                label: "Smith Family".to_string(),
+                color: (100, 100, 200),
+            }],
+            events: vec![ChartNode {
+                rect: egui::Rect::from_min_size(egui::pos2(200.0, 100.0), egui::vec2(120.0, 29.0)),
+                label: "Wedding".to_string(),
+                fill: (255, 255, 200),
+                badges: String::new(),
+            }],
+            event_lines: Vec::new(),
+            grid_size: 50.0,
+            title_block: None,
+            watermark: None,
+        }
+    }
+
+    #[test]
+    fn escape_xml_replaces_special_characters() {
+        assert_eq!(escape_xml(r#"<a & "b">"#), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+
+    #[test]
+    fn render_poster_page_contains_chart_crop_marks_and_key() {
+        let options = PosterOptions::default();
+        let svg = SvgChartExporter::render_poster_page(&sample_scene(), &options, 0, 1);
+        assert!(svg.is_ok(), "{svg:?}");
+        let svg = svg.expect("page should render");
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"width="297mm" height="210mm""#));
+        assert!(svg.contains("Tom &amp; Jerry"));
+        assert!(svg.contains("&lt;Child&gt;"));
+        assert!(svg.contains("1-2 / 2×2"));
+        // トンボ8本 + 親子線1本
+        assert_eq!(svg.matches("<line").count(), 9);
+        // 全体図キーのマス（現在のページのみ塗りつぶし）
+        assert_eq!(svg.matches(r##"fill="#606060""##).count(), 1);
+    }
+
+    #[test]
+    fn render_image_applies_dpi_and_layers() {
+        let options = ImageExportOptions {
+            scale: 1.0,
+            dpi: 254,
+            layers: ChartLayers {
+                grid: true,
+                family_boxes: false,
+                events: true,
+            },
+        };
+        let svg = SvgChartExporter::render_image(&sample_scene(), &options);
+        assert!(svg.is_ok(), "{svg:?}");
+        let svg = svg.expect("image should render");
+
+        // 幅: 20 + 320 + 20 = 360px → 254dpi で 36mm
+        assert!(svg.contains(r#"width="36mm""#), "{svg}");
+        assert!(svg.contains("Wedding"));
+        assert!(svg.contains(r#"text-anchor="end">★⚓</text>"#), "{svg}");
+        assert!(svg.contains("<path d=\"M"));
+        assert!(!svg.contains("Smith Family"));
+
+        let without_events = ImageExportOptions {
+            layers: ChartLayers {
+                grid: false,
+                family_boxes: true,
+                events: false,
+            },
+            ..options
+        };
+        let svg = SvgChartExporter::render_image(&sample_scene(), &without_events).unwrap_or_default();
+        assert!(!svg.contains("Wedding"));
+        assert!(!svg.contains("<path"));
+        assert!(svg.contains("Smith Family"));
+    }
+
+    #[test]
+    fn render_outputs_include_title_block_and_watermark() {
+        let scene = ChartScene {
+            title_block: Some(ChartTitleBlock {
+                title: "Smith & Sons".to_string(),
+                details: vec!["Generations: 2".to_string()],
+            }),
+            watermark: Some("DRAFT".to_string()),
+            ..sample_scene()
+        };
+
+        let image = SvgChartExporter::render_image(&scene, &ImageExportOptions::default()).unwrap_or_default();
+        assert!(image.contains("Smith &amp; Sons"));
+        assert!(image.contains("Generations: 2"));
+        assert_eq!(image.matches("DRAFT").count(), 1);
+
+        let options = PosterOptions::default();
+        for (row, column) in [(0, 0), (1, 1)] {
+            let page = SvgChartExporter::render_poster_page(&scene, &options, row, column).unwrap_or_default();
+            assert_eq!(page.matches("DRAFT").count(), 1);
+        }
+    }
+
+    #[test]
+    fn render_poster_page_rejects_empty_scene() {
+        let result = SvgChartExporter::render_poster_page(&ChartScene::default(), &PosterOptions::default(), 0, 0);
+        assert!(matches!(result, Err(ChartExportError::EmptyChart)));
+    }
+
+    #[test]
+    fn export_poster_writes_one_file_per_page() {
+        let directory = env::temp_dir().join(format!("family_tree_poster_{}", Uuid::new_v4()));
+        let create_result = fs::create_dir_all(&directory);
+        assert!(create_result.is_ok());
+        let base_path = directory.join("tree.svg").to_string_lossy().to_string();
+
+        let options = PosterOptions {
+            columns: 3,
+            rows: 2,
+            ..PosterOptions::default()
+        };
+        let result = SvgChartExporter.export_poster(&base_path, &sample_scene(), &options);
+        assert!(result.is_ok(), "{result:?}");
+        let paths = result.expect("poster should be written");
+        assert_eq!(paths.len(), 6);
+        assert!(paths[5].ends_with("tree_r2c3.svg"));
+        assert!(paths.iter().all(|path| std::path::Path::new(path).exists()));
+
+        let remove_result = fs::remove_dir_all(directory);
+        assert!(remove_result.is_ok());
+    }
+}
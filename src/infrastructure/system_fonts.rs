@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 日本語を含む CJK 文字を表示できる OS 標準フォントの候補（優先順）
+const CJK_FONT_CANDIDATES: &[&str] = &[
+    // Windows
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\meiryo.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+    // macOS
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+    // Linux
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/fonts-japanese-gothic.ttf",
+    "/usr/share/fonts/opentype/ipafont-gothic/ipagp.ttf",
+    "/usr/share/fonts/truetype/takao-gothic/TakaoPGothic.ttf",
+];
+
+/// OS に入っている CJK フォントを探して読み込む
+pub fn load_system_cjk_font() -> Option<(PathBuf, Vec<u8>)> {
+    let path = first_existing(CJK_FONT_CANDIDATES.iter().map(PathBuf::from), |path| path.is_file())?;
+    let bytes = fs::read(&path).ok()?;
+    Some((path, bytes))
+}
+
+/// 候補のうち最初に存在するパスを返す
+fn first_existing(candidates: impl IntoIterator<Item = PathBuf>, exists: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    candidates.into_iter().find(|path| exists(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_existing_keeps_candidate_order() {
+        let candidates = ["a.ttc", "b.ttf", "c.ttf"].map(PathBuf::from);
+        let found = first_existing(candidates.clone(), |path| path != Path::new("a.ttc"));
+        assert_eq!(found, Some(PathBuf::from("b.ttf")));
+        assert_eq!(first_existing(candidates, |_| false), None);
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::core::tree::{FamilyTree, Gender, Person, PersonId};
+
+/// 祖先表で選べる世代数
+pub const PEDIGREE_GENERATION_CHOICES: [usize; 3] = [4, 5, 6];
+
+/// 祖先表のテキストで1世代ごとに字下げする幅
+const PEDIGREE_INDENT: usize = 8;
+
+/// テキスト系図の出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextChartFormat {
+    PlainText,
+    Markdown,
+}
+
+impl TextChartFormat {
+    /// 拡張子から出力形式を決める（.md / .markdown なら Markdown）
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("md" | "markdown") => Self::Markdown,
+            _ => Self::PlainText,
+        }
+    }
+}
+
+/// 家系図を文書やメールに貼り付けられるテキストの系図として書き出す
+pub struct TextChartExporter;
+
+impl TextChartExporter {
+    /// 子孫の一覧をファイルに書き出し、載せた人数を返す
+    pub fn export_descendant_outline(
+        file_path: &str,
+        tree: &FamilyTree,
+        root: PersonId,
+        format: TextChartFormat,
+    ) -> std::io::Result<usize> {
+        let (text, count) = Self::descendant_outline(tree, root, format);
+        fs::write(file_path, text)?;
+        Ok(count)
+    }
+
+    /// 起点の人物から子孫を世代ごとに字下げして並べた一覧と、載せた人数を返す
+    ///
+    /// 各人物の行には世代番号と生没年を付け、配偶者は「+」を付けてその直後に並べる。
+    pub fn descendant_outline(tree: &FamilyTree, root: PersonId, format: TextChartFormat) -> (String, usize) {
+        let mut lines = Vec::new();
+        let mut visited = HashSet::new();
+        if let Some(person) = tree.persons.get(&root) {
+            if format == TextChartFormat::Markdown {
+                lines.push(format!("# {}", person.name));
+                lines.push(String::new());
+            }
+            Self::push_descendants(tree, root, 1, format, &mut visited, &mut lines);
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        (text, visited.len())
+    }
+
+    /// 祖先表をファイルに書き出し、載せた人数を返す
+    pub fn export_pedigree_chart(
+        file_path: &str,
+        tree: &FamilyTree,
+        root: PersonId,
+        generations: usize,
+        format: TextChartFormat,
+    ) -> std::io::Result<usize> {
+        let (text, count) = Self::pedigree_chart(tree, root, generations, format);
+        fs::write(file_path, text)?;
+        Ok(count)
+    }
+
+    /// 起点の人物（1番）から祖先を番号付きで並べた祖先表と、載せた人数を返す
+    ///
+    /// 番号は父が 2n、母が 2n+1 の通し番号（アーネンタフェル番号）。テキストでは父方を上、母方を下にして
+    /// 世代ごとに右へずらした横向きの系図にし、Markdown では親を子の下に入れ子にした一覧にする。
+    pub fn pedigree_chart(
+        tree: &FamilyTree,
+        root: PersonId,
+        generations: usize,
+        format: TextChartFormat,
+    ) -> (String, usize) {
+        let mut lines = Vec::new();
+        let mut count = 0;
+        if let Some(person) = tree.persons.get(&root) {
+            if format == TextChartFormat::Markdown {
+                lines.push(format!("# {}", person.name));
+                lines.push(String::new());
+            }
+            Self::push_ancestors(tree, root, 1, 1, generations.max(1), format, &mut count, &mut lines);
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        (text, count)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_ancestors(
+        tree: &FamilyTree,
+        id: PersonId,
+        number: u64,
+        generation: usize,
+        generations: usize,
+        format: TextChartFormat,
+        count: &mut usize,
+        lines: &mut Vec<String>,
+    ) {
+        let Some(person) = tree.persons.get(&id) else {
+            return;
+        };
+        *count += 1;
+        let (father, mother) = if generation < generations { father_and_mother(tree, id) } else { (None, None) };
+        let depth = generation - 1;
+        let line = match format {
+            TextChartFormat::PlainText => {
+                let label = outline_line(TextChartFormat::PlainText, 0, &number.to_string(), person);
+                format!("{}{label}", " ".repeat(depth * PEDIGREE_INDENT))
+            }
+            TextChartFormat::Markdown => outline_line(format, depth, &number.to_string(), person),
+        };
+
+        let index = lines.len();
+        if let Some(father) = father {
+            Self::push_ancestors(tree, father, number * 2, generation + 1, generations, format, count, lines);
+        }
+        // テキストでは父方の祖先の下、Markdown では親より先に本人を置く
+        match format {
+            TextChartFormat::PlainText => lines.push(line),
+            TextChartFormat::Markdown => lines.insert(index, line),
+        }
+        if let Some(mother) = mother {
+            Self::push_ancestors(tree, mother, number * 2 + 1, generation + 1, generations, format, count, lines);
+        }
+    }
+
+    fn push_descendants(
+        tree: &FamilyTree,
+        id: PersonId,
+        generation: usize,
+        format: TextChartFormat,
+        visited: &mut HashSet<PersonId>,
+        lines: &mut Vec<String>,
+    ) {
+        // 親子関係が循環していても無限に辿らない
+        let Some(person) = tree.persons.get(&id).filter(|_| visited.insert(id)) else {
+            return;
+        };
+        let depth = generation - 1;
+        lines.push(outline_line(format, depth, &generation.to_string(), person));
+        for spouse in sorted_persons(tree, tree.spouses_of(id)) {
+            lines.push(outline_line(format, depth + 1, "+", spouse));
+        }
+        for child in sorted_persons(tree, tree.children_of(id)) {
+            Self::push_descendants(tree, child.id, generation + 1, format, visited, lines);
+        }
+    }
+}
+
+/// 父と母を選ぶ（実親を優先する）
+///
+/// アーネンタフェル番号は父・母で決まるため、性別が不明な親は配偶者の性別から判断するか、
+/// もう一方の親が決まっているときだけ空いている方に入れる。判断できなければ空けておく。
+fn father_and_mother(tree: &FamilyTree, child: PersonId) -> (Option<PersonId>, Option<PersonId>) {
+    let mut edges: Vec<_> = tree.edges.iter().filter(|edge| edge.child == child && edge.parent != child).collect();
+    edges.sort_by_key(|edge| edge.kind != "biological");
+
+    let mut father = None;
+    let mut mother = None;
+    let mut unknown = Vec::new();
+    for edge in edges {
+        match tree.persons.get(&edge.parent).map(|parent| &parent.gender) {
+            Some(Gender::Male) if father.is_none() => father = Some(edge.parent),
+            Some(Gender::Female) if mother.is_none() => mother = Some(edge.parent),
+            Some(Gender::Male | Gender::Female) | None => {}
+            Some(_) => unknown.push(edge.parent),
+        }
+    }
+    for parent in unknown {
+        if father == Some(parent) || mother == Some(parent) {
+            continue;
+        }
+        let spouse_genders: Vec<&Gender> = tree
+            .spouses_of(parent)
+            .into_iter()
+            .filter_map(|spouse| tree.persons.get(&spouse).map(|spouse| &spouse.gender))
+            .collect();
+        let father_slot = if spouse_genders.contains(&&Gender::Female) {
+            true
+        } else if spouse_genders.contains(&&Gender::Male) {
+            false
+        } else if mother.is_some() != father.is_some() {
+            mother.is_some()
+        } else {
+            continue;
+        };
+        if father_slot && father.is_none() {
+            father = Some(parent);
+        } else if !father_slot && mother.is_none() {
+            mother = Some(parent);
+        }
+    }
+    (father, mother)
+}
+
+/// 一覧の1行（字下げ・印・名前・生没年）
+fn outline_line(format: TextChartFormat, depth: usize, marker: &str, person: &Person) -> String {
+    let label = match life_dates(person) {
+        Some(dates) => format!("{} ({dates})", person.name),
+        None => person.name.clone(),
+    };
+    match format {
+        TextChartFormat::PlainText => format!("{}{marker} {label}", "    ".repeat(depth)),
+        TextChartFormat::Markdown => format!("{}- **{marker}** {label}", "  ".repeat(depth)),
+    }
+}
+
+/// 生没年の表記（"1900-01-02 – 1970"、存命なら "1900 –"）。日付が何もなければ None
+fn life_dates(person: &Person) -> Option<String> {
+    let date = |value: &Option<String>| value.as_deref().map(str::trim).filter(|d| !d.is_empty()).map(str::to_string);
+    match (date(&person.birth), date(&person.death)) {
+        (None, None) if !person.deceased => None,
+        (None, None) => Some("? – ?".to_string()),
+        (Some(birth), None) if !person.deceased => Some(format!("{birth} –")),
+        (birth, death) => Some(format!(
+            "{} – {}",
+            birth.unwrap_or_else(|| "?".to_string()),
+            death.unwrap_or_else(|| "?".to_string())
+        )),
+    }
+}
+
+/// 重複を除き、生年月日・名前の順に並べる
+fn sorted_persons(tree: &FamilyTree, ids: Vec<PersonId>) -> Vec<&Person> {
+    let mut persons: Vec<&Person> = Vec::new();
+    for id in ids {
+        if let Some(person) = tree.persons.get(&id)
+            && !persons.iter().any(|p| p.id == id)
+        {
+            persons.push(person);
+        }
+    }
+    persons.sort_by(|a, b| {
+        let birth = |p: &Person| p.birth.clone().filter(|d| !d.trim().is_empty());
+        // 生年月日のない人物は後ろに回す
+        birth(a)
+            .is_none()
+            .cmp(&birth(b).is_none())
+            .then_with(|| birth(a).cmp(&birth(b)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    persons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tree::Gender;
+
+    fn add(tree: &mut FamilyTree, name: &str, birth: Option<&str>, death: Option<&str>) -> PersonId {
+        tree.add_person(
+            name.to_string(),
+            Gender::Unknown,
+            birth.map(str::to_string),
+            String::new(),
+            death.is_some(),
+            death.map(str::to_string),
+            (0.0, 0.0),
+        )
+    }
+
+    fn family() -> (FamilyTree, PersonId) {
+        let mut tree = FamilyTree::default();
+        let john = add(&mut tree, "John", Some("1900"), Some("1970-03-04"));
+        let mary = add(&mut tree, "Mary", None, None);
+        let bob = add(&mut tree, "Bob", Some("1930"), None);
+        let ann = add(&mut tree, "Ann", Some("1925"), None);
+        let carl = add(&mut tree, "Carl", None, None);
+        tree.add_spouse(john, mary, String::new());
+        tree.add_parent_child(john, bob, "biological".to_string());
+        tree.add_parent_child(john, ann, "biological".to_string());
+        tree.add_parent_child(ann, carl, "biological".to_string());
+        // 循環した親子関係があっても止まる
+        tree.add_parent_child(carl, john, "biological".to_string());
+        (tree, john)
+    }
+
+    #[test]
+    fn descendant_outline_plain_text() {
+        let (tree, john) = family();
+        let (text, count) = TextChartExporter::descendant_outline(&tree, john, TextChartFormat::PlainText);
+        assert_eq!(count, 4);
+        assert_eq!(
+            text,
+            "1 John (1900 – 1970-03-04)\n    + Mary\n    2 Ann (1925 –)\n        3 Carl\n    2 Bob (1930 –)\n"
+        );
+    }
+
+    #[test]
+    fn pedigree_chart_numbers_ancestors() {
+        let mut tree = FamilyTree::default();
+        let person = add(&mut tree, "Child", Some("1950"), None);
+        let father = tree.add_person("Father".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let step_father = tree.add_person("Step".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        let grandmother = tree.add_person("Grandma".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let great = add(&mut tree, "Great", None, None);
+        tree.add_parent_child(step_father, person, "adoptive".to_string());
+        tree.add_parent_child(mother, person, "biological".to_string());
+        tree.add_parent_child(father, person, "biological".to_string());
+        tree.add_parent_child(grandmother, mother, "biological".to_string());
+        tree.add_parent_child(great, grandmother, "biological".to_string());
+
+        let (text, count) = TextChartExporter::pedigree_chart(&tree, person, 3, TextChartFormat::PlainText);
+        assert_eq!(count, 4);
+        assert_eq!(
+            text,
+            "        2 Father\n1 Child (1950 –)\n        3 Mother\n                7 Grandma\n"
+        );
+
+        let (text, _) = TextChartExporter::pedigree_chart(&tree, person, 3, TextChartFormat::Markdown);
+        assert_eq!(
+            text,
+            "# Child\n\n- **1** Child (1950 –)\n  - **2** Father\n  - **3** Mother\n    - **7** Grandma\n"
+        );
+    }
+
+    #[test]
+    fn pedigree_chart_places_unknown_gender_parents_by_the_other_parent() {
+        let mut tree = FamilyTree::default();
+        let person = add(&mut tree, "Child", None, None);
+        let mother = tree.add_person("Mother".to_string(), Gender::Female, None, String::new(), false, None, (0.0, 0.0));
+        let parent = add(&mut tree, "Parent", None, None);
+        tree.add_parent_child(parent, person, "biological".to_string());
+        tree.add_parent_child(mother, person, "biological".to_string());
+        let (text, _) = TextChartExporter::pedigree_chart(&tree, person, 2, TextChartFormat::Markdown);
+        assert_eq!(text, "# Child\n\n- **1** Child\n  - **2** Parent\n  - **3** Mother\n");
+
+        // 配偶者の性別からも判断する
+        let mut tree = FamilyTree::default();
+        let person = add(&mut tree, "Child", None, None);
+        let parent = add(&mut tree, "Parent", None, None);
+        let husband = tree.add_person("Husband".to_string(), Gender::Male, None, String::new(), false, None, (0.0, 0.0));
+        tree.add_parent_child(parent, person, "biological".to_string());
+        tree.add_spouse(parent, husband, String::new());
+        let (text, _) = TextChartExporter::pedigree_chart(&tree, person, 2, TextChartFormat::Markdown);
+        assert_eq!(text, "# Child\n\n- **1** Child\n  - **3** Parent\n");
+
+        // 判断できなければ父の番号を割り当てずに空けておく
+        let mut tree = FamilyTree::default();
+        let person = add(&mut tree, "Child", None, None);
+        let parent = add(&mut tree, "Parent", None, None);
+        tree.add_parent_child(parent, person, "biological".to_string());
+        let (_, count) = TextChartExporter::pedigree_chart(&tree, person, 2, TextChartFormat::Markdown);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn descendant_outline_markdown() {
+        let (tree, john) = family();
+        let (text, _) = TextChartExporter::descendant_outline(&tree, john, TextChartFormat::Markdown);
+        assert!(text.starts_with("# John\n\n- **1** John (1900 – 1970-03-04)\n  - **+** Mary\n  - **2** Ann (1925 –)\n"));
+        assert_eq!(TextChartFormat::from_path(Path::new("out.MD")), TextChartFormat::Markdown);
+        assert_eq!(TextChartFormat::from_path(Path::new("out.txt")), TextChartFormat::PlainText);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use image::RgbaImage;
+
+/// サムネイルを保存するフォルダ名（設定フォルダの中に作る）
+const THUMBNAIL_DIR_NAME: &str = "thumbnails";
+
+/// サムネイルの長辺の最大ピクセル数
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+/// 縮小した写真をディスクに保存し、次回以降は元画像をデコードせずに使う
+///
+/// キーは元画像のパスと更新日時なので、写真を差し替えると自動的に作り直される。
+#[derive(Debug, Clone)]
+pub struct ThumbnailCache {
+    dir: PathBuf,
+}
+
+impl ThumbnailCache {
+    pub fn in_dir(dir: &Path) -> Self {
+        Self::new(dir.join(THUMBNAIL_DIR_NAME))
+    }
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// 保存済みのサムネイルを読み込み、なければ元画像から作って保存する
+    ///
+    /// 保存に失敗してもサムネイル自体は返す（次回また作り直すだけ）。
+    pub fn load_or_create(&self, photo_path: &str, modified_at: Option<SystemTime>) -> Option<RgbaImage> {
+        let cached_path = self.thumbnail_path(photo_path, modified_at);
+        if let Ok(image) = image::open(&cached_path) {
+            return Some(image.to_rgba8());
+        }
+
+        let thumbnail = image::open(photo_path)
+            .ok()?
+            .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .to_rgba8();
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = thumbnail.save(&cached_path);
+        }
+        Some(thumbnail)
+    }
+
+    fn thumbnail_path(&self, photo_path: &str, modified_at: Option<SystemTime>) -> PathBuf {
+        let modified_nanos = modified_at
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_nanos());
+        let key = fnv1a_64(format!("{photo_path}\0{modified_nanos}").as_bytes());
+        self.dir.join(format!("{key:016x}.png"))
+    }
+}
+
+/// 実行環境によらず同じ値になるハッシュ（ファイル名に使うため）
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn creates_thumbnail_once_and_reuses_it() {
+        let dir = std::env::temp_dir().join(format!("family_tree_thumbnail_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let photo_path = dir.join("large.png");
+        RgbaImage::from_pixel(1024, 512, Rgba([10, 20, 30, 255])).save(&photo_path).unwrap();
+        let photo_path = photo_path.to_string_lossy().to_string();
+        let modified_at = fs::metadata(&photo_path).unwrap().modified().ok();
+        let cache = ThumbnailCache::in_dir(&dir);
+
+        let thumbnail = cache.load_or_create(&photo_path, modified_at).expect("thumbnail should be created");
+        assert_eq!(thumbnail.dimensions(), (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2));
+        let cached_path = cache.thumbnail_path(&photo_path, modified_at);
+        assert!(cached_path.exists());
+
+        // 元画像がなくても保存済みのサムネイルを使う
+        fs::remove_file(&photo_path).unwrap();
+        assert!(cache.load_or_create(&photo_path, modified_at).is_some());
+        assert!(cache.load_or_create(&photo_path, None).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
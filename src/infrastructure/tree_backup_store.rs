@@ -0,0 +1,157 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::source_path_key::source_path_key;
+
+/// 保存した家系図ファイルのコピーを別のディレクトリ（同期フォルダなど）に残す
+///
+/// コピーは "{元のファイル名}_{パスのハッシュ値}_{YYYYMMDD-HHMMSS}.{拡張子}" の名前で書き込み、
+/// 同じファイルのコピーが上限を超えたら古いものから削除する。
+/// 別のディレクトリにある同名のファイルは、パスのハッシュ値で区別して互いのコピーを消さない。
+#[derive(Debug, Clone)]
+pub struct TreeBackupStore {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl TreeBackupStore {
+    pub fn new(dir: impl Into<PathBuf>, keep: usize) -> Self {
+        Self { dir: dir.into(), keep: keep.max(1) }
+    }
+
+    /// 保存済みのファイルを日時付きの名前でコピーし、コピー先のパスを返す
+    pub fn back_up(&self, source: &Path) -> io::Result<PathBuf> {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        self.back_up_with_timestamp(source, &timestamp)
+    }
+
+    fn back_up_with_timestamp(&self, source: &Path, timestamp: &str) -> io::Result<PathBuf> {
+        let (stem, extension) = Self::split_file_name(source)?;
+        let stem = format!("{stem}_{}", source_path_key(source));
+        fs::create_dir_all(&self.dir)?;
+
+        // 同じ秒に2回保存した場合は連番を付けて上書きしない
+        let mut target = self.dir.join(format!("{stem}_{timestamp}{extension}"));
+        let mut sequence = 1;
+        while target.exists() {
+            target = self.dir.join(format!("{stem}_{timestamp}-{sequence}{extension}"));
+            sequence += 1;
+        }
+        fs::copy(source, &target)?;
+        self.prune(&stem, &extension)?;
+        Ok(target)
+    }
+
+    /// 同じファイルのコピーのうち、新しいものから上限数だけ残して削除する
+    fn prune(&self, stem: &str, extension: &str) -> io::Result<()> {
+        let prefix = format!("{stem}_");
+        let mut backups: Vec<((String, u32), PathBuf)> = fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let order = Self::parse_timestamp(name.strip_prefix(&prefix)?.strip_suffix(extension)?)?;
+                Some((order, entry.path()))
+            })
+            .collect();
+        if backups.len() <= self.keep {
+            return Ok(());
+        }
+        // 日時・連番の順に並べると古い順になる
+        backups.sort();
+        for (_, path) in &backups[..backups.len() - self.keep] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// "YYYYMMDD-HHMMSS" または "YYYYMMDD-HHMMSS-連番" を日時と連番に分ける
+    fn parse_timestamp(value: &str) -> Option<(String, u32)> {
+        let base = value.get(..15)?;
+        let valid = base.char_indices().all(|(index, c)| if index == 8 { c == '-' } else { c.is_ascii_digit() });
+        if !valid {
+            return None;
+        }
+        let sequence = match &value[15..] {
+            "" => 0,
+            rest => rest.strip_prefix('-')?.parse().ok()?,
+        };
+        Some((base.to_string(), sequence))
+    }
+
+    /// ファイル名を拡張子の前後に分ける（拡張子は "." 付き、なければ空）
+    fn split_file_name(source: &Path) -> io::Result<(String, String)> {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "backup source has no file name"))?;
+        let extension = source
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        Ok((stem, extension))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_back_up_writes_timestamped_copies_and_prunes_old_ones() {
+        let root = env::temp_dir().join(format!("family_tree_backup_{}", Uuid::new_v4()));
+        let source = root.join("tree.json");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(&source, "{}").unwrap();
+        let key = source_path_key(&source);
+        let backup_dir = root.join("backups");
+        // 別のファイルのコピーは削除しない
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(backup_dir.join("other_20240101-000000.json"), "{}").unwrap();
+
+        let store = TreeBackupStore::new(&backup_dir, 2);
+        let first = store.back_up_with_timestamp(&source, "20240102-030405").unwrap();
+        assert_eq!(first.file_name().unwrap().to_string_lossy(), format!("tree_{key}_20240102-030405.json"));
+        assert_eq!(fs::read_to_string(&first).unwrap(), "{}");
+        let second = store.back_up_with_timestamp(&source, "20240102-030405").unwrap();
+        assert_eq!(second.file_name().unwrap().to_string_lossy(), format!("tree_{key}_20240102-030405-1.json"));
+        store.back_up_with_timestamp(&source, "20240103-000000").unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&backup_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "other_20240101-000000.json".to_string(),
+                format!("tree_{key}_20240102-030405-1.json"),
+                format!("tree_{key}_20240103-000000.json"),
+            ]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_same_file_name_in_other_directory_has_its_own_rotation() {
+        let root = env::temp_dir().join(format!("family_tree_backup_{}", Uuid::new_v4()));
+        let first = root.join("a").join("tree.json");
+        let second = root.join("b").join("tree.json");
+        for source in [&first, &second] {
+            fs::create_dir_all(source.parent().unwrap()).unwrap();
+            fs::write(source, "{}").unwrap();
+        }
+
+        let store = TreeBackupStore::new(root.join("backups"), 1);
+        let first_backup = store.back_up_with_timestamp(&first, "20240102-030405").unwrap();
+        let second_backup = store.back_up_with_timestamp(&second, "20240103-000000").unwrap();
+        assert!(first_backup.exists());
+        assert!(second_backup.exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// ロックファイルの拡張子（`tree.json` なら `tree.json.lock`）
+const LOCK_FILE_EXTENSION: &str = "lock";
+
+/// ファイルを開いているインスタンスの情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub user: String,
+    pub host: String,
+    pub pid: u32,
+    /// "YYYY-MM-DD HH:MM:SS"
+    pub acquired_at: String,
+}
+
+impl LockOwner {
+    /// このプロセスの情報
+    pub fn current() -> Self {
+        Self {
+            user: env::var("USER")
+                .or_else(|_| env::var("USERNAME"))
+                .unwrap_or_default(),
+            host: env::var("COMPUTERNAME")
+                .or_else(|_| env::var("HOSTNAME"))
+                .ok()
+                .or_else(|| fs::read_to_string("/etc/hostname").ok())
+                .map(|host| host.trim().to_string())
+                .unwrap_or_default(),
+            pid: std::process::id(),
+            acquired_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{} ({})", self.user, self.host, self.acquired_at)
+    }
+}
+
+#[derive(Debug)]
+pub enum TreeFileLockError {
+    /// 他のインスタンスがロックを持っている
+    HeldBy(LockOwner),
+    Io(io::Error),
+}
+
+impl fmt::Display for TreeFileLockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HeldBy(owner) => write!(f, "locked by {owner}"),
+            Self::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// 開いている家系図ファイルの横に置くロックファイル（勧告ロック）
+///
+/// 共有ドライブ上の同じファイルを複数人が同時に編集して、互いの保存を上書きしないようにする。
+/// 破棄されるとロックファイルを削除する。
+#[derive(Debug)]
+pub struct TreeFileLock {
+    tree_path: String,
+    lock_path: PathBuf,
+}
+
+impl TreeFileLock {
+    /// ロックを取得する。他のインスタンスのロックが残っていれば `HeldBy` を返す
+    pub fn acquire(tree_path: &str) -> Result<Self, TreeFileLockError> {
+        let lock_path = Self::lock_path_for(tree_path);
+        match OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+            Ok(mut file) => {
+                Self::write_owner(&mut file)?;
+                Ok(Self { tree_path: tree_path.to_string(), lock_path })
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                let owner = fs::read_to_string(&lock_path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str(&content).ok())
+                    .unwrap_or_else(|| LockOwner {
+                        user: String::new(),
+                        host: String::new(),
+                        pid: 0,
+                        acquired_at: String::new(),
+                    });
+                Err(TreeFileLockError::HeldBy(owner))
+            }
+            Err(error) => Err(TreeFileLockError::Io(error)),
+        }
+    }
+
+    /// 残っているロックを無視して取得する（異常終了で残ったロックの引き継ぎ用）
+    pub fn take_over(tree_path: &str) -> Result<Self, TreeFileLockError> {
+        let lock_path = Self::lock_path_for(tree_path);
+        let mut file = fs::File::create(&lock_path).map_err(TreeFileLockError::Io)?;
+        Self::write_owner(&mut file)?;
+        Ok(Self { tree_path: tree_path.to_string(), lock_path })
+    }
+
+    /// ロック対象の家系図ファイルのパス
+    pub fn tree_path(&self) -> &str {
+        &self.tree_path
+    }
+
+    /// ロックファイルがまだ自分のものか（他のインスタンスに引き継がれていないか）
+    pub fn is_held(&self) -> bool {
+        fs::read_to_string(&self.lock_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<LockOwner>(&content).ok())
+            .is_some_and(|owner| owner.pid == std::process::id() && owner.host == LockOwner::current().host)
+    }
+
+    fn lock_path_for(tree_path: &str) -> PathBuf {
+        let path = Path::new(tree_path);
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(LOCK_FILE_EXTENSION);
+        path.with_file_name(file_name)
+    }
+
+    fn write_owner(file: &mut fs::File) -> Result<(), TreeFileLockError> {
+        let content = serde_json::to_string(&LockOwner::current())
+            .map_err(|error| TreeFileLockError::Io(io::Error::other(error)))?;
+        file.write_all(content.as_bytes()).map_err(TreeFileLockError::Io)
+    }
+}
+
+impl Drop for TreeFileLock {
+    fn drop(&mut self) {
+        // 他のインスタンスに引き継がれたロックは消さない
+        if self.is_held() {
+            let _ = fs::remove_file(&self.lock_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_tree_path() -> String {
+        env::temp_dir()
+            .join(format!("family_tree_lock_test_{}.json", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn second_acquire_reports_owner_until_released() {
+        let path = temp_tree_path();
+        let lock = TreeFileLock::acquire(&path).expect("first lock should succeed");
+        assert!(lock.is_held());
+
+        match TreeFileLock::acquire(&path) {
+            Err(TreeFileLockError::HeldBy(owner)) => assert_eq!(owner.pid, std::process::id()),
+            other => panic!("expected HeldBy, got {other:?}"),
+        }
+
+        drop(lock);
+        assert!(!TreeFileLock::lock_path_for(&path).exists());
+        let relocked = TreeFileLock::acquire(&path).expect("lock should be free after drop");
+        drop(relocked);
+    }
+
+    #[test]
+    fn take_over_replaces_stale_lock() {
+        let path = temp_tree_path();
+        let lock_path = TreeFileLock::lock_path_for(&path);
+        fs::write(&lock_path, r#"{"user":"a","host":"other","pid":1,"acquired_at":""}"#).unwrap();
+
+        let lock = TreeFileLock::take_over(&path).expect("take over should succeed");
+        assert!(lock.is_held());
+        drop(lock);
+        assert!(!lock_path.exists());
+    }
+}
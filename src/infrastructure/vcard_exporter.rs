@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::tree::{FamilyTree, Gender, NameParts, Person, PersonId};
+
+/// vCard の1行の最大オクテット数（RFC 6350 3.2）
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 人物を vCard 4.0 (RFC 6350) 形式で書き出す
+///
+/// 電話帳アプリに取り込めるよう、名前・誕生日・写真・メモのみを出力する。
+pub struct VcardExporter;
+
+impl VcardExporter {
+    /// 指定した人物を1つの .vcf ファイルに書き出し、書き出した件数を返す
+    pub fn export(file_path: &str, tree: &FamilyTree, ids: &[PersonId]) -> std::io::Result<usize> {
+        let persons: Vec<&Person> = ids
+            .iter()
+            .filter_map(|id| tree.persons.get(id))
+            .collect();
+        let text: String = persons.iter().map(|person| Self::person_to_vcard(person)).collect();
+        fs::write(file_path, text)?;
+        Ok(persons.len())
+    }
+
+    /// 1人分の vCard を CRLF 区切りの文字列にする
+    pub fn person_to_vcard(person: &Person) -> String {
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            "VERSION:4.0".to_string(),
+            format!("UID:urn:uuid:{}", person.id),
+            format!("FN:{}", escape_text(&person.name)),
+            structured_name(&person.name),
+        ];
+        if let Some(gender) = gender_value(&person.gender) {
+            lines.push(format!("GENDER:{gender}"));
+        }
+        if let Some(birth) = person.birth.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+            lines.push(match vcard_date(birth) {
+                Some(date) => format!("BDAY:{date}"),
+                None => format!("BDAY;VALUE=text:{}", escape_text(birth)),
+            });
+        }
+        if person.has_own_photo()
+            && let Some(photo) = person.photo_path.as_deref().and_then(photo_data_uri)
+        {
+            lines.push(format!("PHOTO:{photo}"));
+        }
+        if !person.memo.trim().is_empty() {
+            lines.push(format!("NOTE:{}", escape_text(&person.memo)));
+        }
+        lines.push("END:VCARD".to_string());
+
+        lines.iter().map(|line| fold_line(line)).collect()
+    }
+}
+
+/// N（姓;名;ミドルネーム;敬称;接尾辞）。GEDCOM の書き出しと同じ規則で姓と名に分ける
+fn structured_name(name: &str) -> String {
+    let parts = NameParts::split(name);
+    format!("N:{};{};;;", escape_text(parts.surname), escape_text(&parts.given))
+}
+
+fn gender_value(gender: &Gender) -> Option<String> {
+    match gender {
+        Gender::Male => Some("M".to_string()),
+        Gender::Female => Some("F".to_string()),
+        Gender::Other(label) if !label.trim().is_empty() => Some(format!("O;{}", escape_text(label))),
+        Gender::Other(_) => Some("O".to_string()),
+        Gender::Unknown => None,
+    }
+}
+
+/// "YYYY-MM-DD" / "YYYY-MM" / "YYYY" を vCard の日付表記に変換する
+fn vcard_date(date: &str) -> Option<String> {
+    let parts: Vec<&str> = date.split(['-', '/', '.']).collect();
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    match parts.as_slice() {
+        [y] if digits(y, 4) => Some(y.to_string()),
+        [y, m] if digits(y, 4) && digits(m, 2) => Some(format!("{y}-{m}")),
+        [y, m, d] if digits(y, 4) && digits(m, 2) && digits(d, 2) => Some(format!("{y}{m}{d}")),
+        _ => None,
+    }
+}
+
+/// 写真ファイルを data URI として埋め込む（読めなければ出力しない）
+pub(crate) fn photo_data_uri(photo_path: &str) -> Option<String> {
+    let mime = match Path::new(photo_path)
+        .extension()?
+        .to_str()?
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        _ => return None,
+    };
+    let bytes = fs::read(photo_path).ok()?;
+    Some(format!("data:{mime};base64,{}", encode_base64(&bytes)))
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// テキスト値のエスケープ（RFC 6350 3.4、iCalendar も同じ規則）
+pub(crate) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 75オクテットを超える行を折り返す（マルチバイト文字の途中では切らない）
+pub(crate) fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(name: &str, birth: Option<&str>, memo: &str) -> Person {
+        let mut tree = FamilyTree::default();
+        let id = tree.add_person(
+            name.to_string(),
+            Gender::Female,
+            birth.map(str::to_string),
+            memo.to_string(),
+            false,
+            None,
+            (0.0, 0.0),
+        );
+        tree.persons.remove(&id).unwrap()
+    }
+
+    #[test]
+    fn person_to_vcard_writes_name_birthday_and_note() {
+        let vcard = VcardExporter::person_to_vcard(&person("山田 花子", Some("1950-04-01"), "趣味; 園芸,\n俳句"));
+
+        assert!(vcard.starts_with("BEGIN:VCARD\r\nVERSION:4.0\r\n"));
+        assert!(vcard.contains("FN:山田 花子\r\n"));
+        assert!(vcard.contains("N:山田;花子;;;\r\n"));
+        assert!(vcard.contains("GENDER:F\r\n"));
+        assert!(vcard.contains("BDAY:19500401\r\n"));
+        assert!(vcard.contains("NOTE:趣味\\; 園芸\\,\\n俳句\r\n"));
+        assert!(vcard.ends_with("END:VCARD\r\n"));
+    }
+
+    #[test]
+    fn structured_name_puts_the_surname_first() {
+        assert_eq!(structured_name("Mary Ann Smith"), "N:Smith;Mary Ann;;;");
+        assert_eq!(structured_name("Hanako"), "N:;Hanako;;;");
+    }
+
+    #[test]
+    fn unparsable_birthday_is_written_as_text() {
+        let vcard = VcardExporter::person_to_vcard(&person("A", Some("昭和25年頃"), ""));
+        assert!(vcard.contains("BDAY;VALUE=text:昭和25年頃\r\n"));
+        assert!(!vcard.contains("NOTE:"));
+        assert_eq!(vcard_date("1950-04"), Some("1950-04".to_string()));
+        assert_eq!(vcard_date("1950"), Some("1950".to_string()));
+    }
+
+    #[test]
+    fn long_lines_are_folded_within_75_octets() {
+        let vcard = VcardExporter::person_to_vcard(&person("A", None, &"あ".repeat(60)));
+        for line in vcard.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "{line}");
+        }
+        assert!(vcard.replace("\r\n ", "").contains(&format!("NOTE:{}", "あ".repeat(60))));
+    }
+
+    #[test]
+    fn encode_base64_pads_output() {
+        assert_eq!(encode_base64(b"M"), "TQ==");
+        assert_eq!(encode_base64(b"Ma"), "TWE=");
+        assert_eq!(encode_base64(b"Man"), "TWFu");
+    }
+}
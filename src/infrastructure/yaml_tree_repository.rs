@@ -0,0 +1,137 @@
+use std::fs;
+
+use serde_json::Value;
+
+use crate::application::{TreeRepository, TreeRepositoryError};
+use crate::core::tree::FamilyTree;
+
+use super::json_tree_repository::{saved_format_version, tree_from_json_value};
+
+/// 保存するYAMLの先頭行（形式の判別にも使う）
+pub const YAML_DOCUMENT_START: &str = "---";
+
+/// `FamilyTree`をYAMLファイルとして保存・読込するリポジトリ実装。
+///
+/// モデルはJSONと同じで、JSONの値を経由して変換する（移行処理も共通）。
+pub struct YamlTreeRepository;
+
+impl TreeRepository for YamlTreeRepository {
+    fn load(&self, file_path: &str) -> Result<FamilyTree, TreeRepositoryError> {
+        let content = fs::read_to_string(file_path)
+            .map_err(|error| TreeRepositoryError::Read(error.to_string()))?;
+
+        let value = parse_yaml(&content).map_err(TreeRepositoryError::Deserialize)?;
+        tree_from_json_value(value)
+    }
+
+    fn save(&self, file_path: &str, tree: &FamilyTree) -> Result<(), TreeRepositoryError> {
+        let mut tree = tree.clone();
+        tree.format_version = saved_format_version(&tree);
+        let value = serde_json::to_value(&tree)
+            .map_err(|error| TreeRepositoryError::Serialize(error.to_string()))?;
+        let yaml = to_yaml_string(&value).map_err(TreeRepositoryError::Serialize)?;
+
+        fs::write(file_path, yaml)
+            .map_err(|error| TreeRepositoryError::Write(error.to_string()))
+    }
+}
+
+/// JSONの値をYAML文書にする（形式を判別できるよう先頭に文書の開始行を付ける）
+pub fn to_yaml_string(value: &Value) -> Result<String, String> {
+    let body = serde_yaml_ng::to_string(value).map_err(|error| error.to_string())?;
+    Ok(format!("{YAML_DOCUMENT_START}\n{body}"))
+}
+
+/// YAML文書を読み込んでJSONの値にする
+pub fn parse_yaml(source: &str) -> Result<Value, String> {
+    serde_yaml_ng::from_str(source).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{parse_yaml, to_yaml_string, YamlTreeRepository};
+    use crate::application::TreeRepository;
+    use crate::core::tree::{FamilyTree, Gender};
+
+    #[test]
+    fn yaml_round_trip_preserves_values() {
+        let value = json!({
+            "name": "山田 太郎",
+            "memo": "line 1\nline 2: \"quoted\" # not a comment",
+            "empty": "",
+            "numeric_text": "1984",
+            "flag": true,
+            "missing": null,
+            "position": [10.5, -20.0],
+            "list": [{"a": 1, "b": [1, 2]}, "x", [], {}],
+            "nested": {"deep": {"key with space": "- dash"}},
+        });
+
+        let yaml = to_yaml_string(&value).unwrap();
+        assert!(yaml.starts_with("---\n"));
+        assert_eq!(parse_yaml(&yaml).unwrap(), value);
+    }
+
+    #[test]
+    fn parses_hand_written_yaml() {
+        let yaml = "\
+# 家系図
+format_version: 1
+persons: {}
+edges:
+- parent: a   # インラインのコメント
+  child: 'b''s'
+  kind: biological
+memo: |
+  first
+  second
+folded: >-
+  one
+  two
+empty_list: []
+base: &base {kind: adopted}
+copy: *base
+";
+        let value = parse_yaml(yaml).unwrap();
+        assert_eq!(value["format_version"], json!(1));
+        assert_eq!(value["edges"][0], json!({"parent": "a", "child": "b's", "kind": "biological"}));
+        assert_eq!(value["memo"], json!("first\nsecond\n"));
+        assert_eq!(value["folded"], json!("one two"));
+        assert_eq!(value["empty_list"], json!([]));
+        assert_eq!(value["copy"], json!({"kind": "adopted"}));
+    }
+
+    #[test]
+    fn reports_line_of_bad_indentation() {
+        let error = parse_yaml("a: 1\n    b: 2\n").unwrap_err();
+        assert!(error.contains("line 2"), "{error}");
+    }
+
+    #[test]
+    fn save_and_load_tree_round_trip() {
+        let repository = YamlTreeRepository;
+        let path = env::temp_dir().join(format!("family_tree_test_{}.yaml", Uuid::new_v4()));
+        let path = path.to_string_lossy().to_string();
+        let mut tree = FamilyTree::default();
+        let parent = tree.add_person("Taro".to_string(), Gender::Male, Some("1950-01-02".to_string()), "memo: #1\n2行目".to_string(), false, None, (10.0, 20.5));
+        let child = tree.add_person("Hanako".to_string(), Gender::Female, None, String::new(), true, None, (10.0, 140.0));
+        tree.add_parent_child(parent, child, "biological".to_string());
+
+        repository.save(&path, &tree).expect("yaml file should save");
+        let loaded = repository.load(&path).expect("yaml file should load");
+
+        assert_eq!(loaded.persons.len(), 2);
+        assert_eq!(loaded.persons[&parent].memo, "memo: #1\n2行目");
+        assert_eq!(loaded.persons[&parent].position, (10.0, 20.5));
+        assert!(loaded.persons[&child].deceased);
+        assert_eq!(loaded.edges.len(), 1);
+
+        let _ = fs::remove_file(path);
+    }
+}
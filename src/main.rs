@@ -6,7 +6,11 @@ mod app;
 
 use app::App;
 
+/// 起動時から閲覧モードにするコマンドライン引数
+const READONLY_FLAG: &str = "--readonly";
+
 fn main() -> eframe::Result<()> {
+    let read_only = std::env::args().skip(1).any(|arg| arg == READONLY_FLAG);
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
             .with_title("Family Tree")
@@ -17,37 +21,13 @@ fn main() -> eframe::Result<()> {
         "Family Tree",
         options,
         Box::new(|cc| {
-            // 日本語フォントが含まれるようにする
-            setup_fonts(&cc.egui_ctx);
-            Ok(Box::new(App::default()))
+            let mut app = App::default();
+            app.ui.read_only = read_only;
+            // 最初の描画から日本語が表示できるようにフォントを設定しておく
+            app.apply_ui_fonts(&cc.egui_ctx);
+            // Ctrl+0 などはキャンバスのズームに使うので、egui の画面全体の拡大縮小は無効にする
+            cc.egui_ctx.options_mut(|options| options.zoom_with_keyboard = false);
+            Ok(Box::new(app))
         }),
     )
 }
-
-fn setup_fonts(ctx: &eframe::egui::Context) {
-    let mut fonts = eframe::egui::FontDefinitions::default();
-    
-    // Noto Sans JPフォントを追加
-    fonts.font_data.insert(
-        "noto_sans_jp".to_owned(),
-        std::sync::Arc::new(eframe::egui::FontData::from_static(include_bytes!(
-            "../fonts/NotoSansJP-Regular.ttf"
-        ))),
-    );
-    
-    // Proportionalフォントファミリーの最優先に設定
-    fonts
-        .families
-        .entry(eframe::egui::FontFamily::Proportional)
-        .or_default()
-        .insert(0, "noto_sans_jp".to_owned());
-    
-    // Monospaceフォントファミリーにも追加
-    fonts
-        .families
-        .entry(eframe::egui::FontFamily::Monospace)
-        .or_default()
-        .push("noto_sans_jp".to_owned());
-    
-    ctx.set_fonts(fonts);
-}
@@ -0,0 +1,187 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::tree::{AnnotationId, PersonId};
+use crate::ui::{LogLevel, SideTab};
+
+pub trait AnnotationsTabRenderer {
+    fn render_annotations_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String);
+}
+
+impl AnnotationsTabRenderer for App {
+    fn render_annotations_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
+        ui.heading(t("manage_annotations"));
+        if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("add_new_annotation"))).clicked() {
+            self.annotation_editor.clear();
+        }
+        ui.separator();
+
+        self.render_annotation_list(ui, &t);
+        ui.separator();
+
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            ui.heading(t("annotation_editor"));
+            self.render_annotation_form_fields(ui, &t);
+            self.render_annotation_action_buttons(ui, &t);
+            ui.separator();
+
+            self.render_outstanding_notes(ui, &t);
+        });
+    }
+}
+
+impl App {
+    fn render_annotation_list(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        if self.tree.annotations.is_empty() {
+            ui.weak(t("no_annotations"));
+            return;
+        }
+
+        let entries: Vec<(AnnotationId, String)> = self
+            .tree
+            .annotations
+            .iter()
+            .map(|annotation| (annotation.id, annotation.text.lines().next().unwrap_or_default().to_string()))
+            .collect();
+        for (annotation_id, first_line) in entries {
+            let is_selected = self.annotation_editor.selected == Some(annotation_id);
+            if ui.selectable_label(is_selected, first_line).clicked() {
+                self.select_annotation(annotation_id);
+            }
+        }
+    }
+
+    /// 未対応の付箋の一覧。クリックで対象の人物を選択する
+    fn render_outstanding_notes(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        ui.heading(t("outstanding_notes"));
+
+        let notes: Vec<(PersonId, String)> = self
+            .tree
+            .outstanding_sticky_notes()
+            .into_iter()
+            .map(|note| (note.person, note.text.clone()))
+            .collect();
+        if notes.is_empty() {
+            ui.weak(t("no_outstanding_notes"));
+            return;
+        }
+
+        for (person_id, text) in notes {
+            let person_name = self.get_person_name(&person_id);
+            if ui.link(format!("{person_name}: {text}")).clicked() {
+                self.person_editor.selected = Some(person_id);
+                self.load_selected_person_into_form(person_id);
+                self.canvas.expanded_notes.insert(person_id);
+                self.ui.side_tab = SideTab::Persons;
+            }
+        }
+    }
+
+    fn render_annotation_form_fields(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        ui.label(t("annotation_text"));
+        ui.text_edit_multiline(&mut self.annotation_editor.text);
+
+        ui.horizontal(|ui| {
+            ui.label(t("font_size"));
+            ui.add(egui::DragValue::new(&mut self.annotation_editor.font_size).range(8.0..=96.0));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(t("color"));
+            ui.color_edit_button_rgb(&mut self.annotation_editor.color);
+        });
+    }
+
+    fn render_annotation_action_buttons(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        ui.horizontal(|ui| {
+            if self.annotation_editor.selected.is_none() {
+                if ui.button(t("add")).clicked() {
+                    self.add_annotation_from_editor(t);
+                }
+            } else {
+                if ui.button(t("update")).clicked() {
+                    self.update_selected_annotation(t);
+                }
+                if ui.button(t("delete")).clicked() {
+                    self.delete_selected_annotation(t);
+                }
+            }
+
+            if ui.button(t("cancel")).clicked() {
+                self.annotation_editor.clear();
+            }
+        });
+    }
+
+    /// 注釈を選択して編集欄に読み込む
+    pub(crate) fn select_annotation(&mut self, annotation_id: AnnotationId) {
+        let Some(annotation) = self.tree.annotation(annotation_id) else {
+            return;
+        };
+        let (r, g, b) = annotation.color;
+        self.annotation_editor.selected = Some(annotation_id);
+        self.annotation_editor.text = annotation.text.clone();
+        self.annotation_editor.font_size = annotation.font_size;
+        self.annotation_editor.color = [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0];
+    }
+
+    fn annotation_editor_color_rgb(&self) -> (u8, u8, u8) {
+        (
+            (self.annotation_editor.color[0] * 255.0).round() as u8,
+            (self.annotation_editor.color[1] * 255.0).round() as u8,
+            (self.annotation_editor.color[2] * 255.0).round() as u8,
+        )
+    }
+
+    fn add_annotation_from_editor(&mut self, t: &impl Fn(&str) -> String) {
+        if self.annotation_editor.text.trim().is_empty() {
+            self.toasts.warning(t("annotation_text_required"));
+            return;
+        }
+
+        let annotation_id = self.tree.add_annotation(
+            self.annotation_editor.text.clone(),
+            self.visible_canvas_left_top(),
+            self.annotation_editor.font_size,
+            self.annotation_editor_color_rgb(),
+        );
+        self.annotation_editor.selected = Some(annotation_id);
+        self.toasts.info(t("annotation_added"));
+        self.log.add(
+            format!("{}: {}", t("log_annotation_added"), self.annotation_editor.text),
+            LogLevel::Debug,
+        );
+    }
+
+    fn update_selected_annotation(&mut self, t: &impl Fn(&str) -> String) {
+        let Some(annotation_id) = self.annotation_editor.selected else {
+            return;
+        };
+
+        let color = self.annotation_editor_color_rgb();
+        if let Some(annotation) = self.tree.annotation_mut(annotation_id) {
+            annotation.text = self.annotation_editor.text.clone();
+            annotation.font_size = self.annotation_editor.font_size;
+            annotation.color = color;
+            self.toasts.info(t("annotation_updated"));
+            self.log.add(
+                format!("{}: {}", t("log_annotation_updated"), annotation.text),
+                LogLevel::Debug,
+            );
+        }
+    }
+
+    fn delete_selected_annotation(&mut self, t: &impl Fn(&str) -> String) {
+        let Some(annotation_id) = self.annotation_editor.selected else {
+            return;
+        };
+
+        self.tree.remove_annotation(annotation_id);
+        self.log.add(
+            format!("{}: {}", t("log_annotation_deleted"), self.annotation_editor.text),
+            LogLevel::Debug,
+        );
+        self.annotation_editor.clear();
+        self.toasts.info(t("annotation_deleted"));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app::App;
+use crate::core::tree::TextAnnotation;
+use crate::ui::{AnnotationRenderer, SideTab};
+
+impl AnnotationRenderer for App {
+    fn render_annotations(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        pointer_pos: Option<egui::Pos2>,
+    ) -> (bool, bool) {
+        let mut annotation_hovered = false;
+        let mut any_annotation_dragged = false;
+
+        let origin = self.canvas.canvas_origin;
+        let zoom = self.canvas.zoom;
+        let pan = self.canvas.pan;
+        let to_screen = |p: egui::Pos2| -> egui::Pos2 { origin + (p - origin) * zoom + pan };
+
+        let annotations: Vec<TextAnnotation> = self.tree.annotations.clone();
+        for annotation in annotations {
+            let annotation_id = annotation.id;
+            let annotation_position = annotation.position;
+            let (r, g, b) = annotation.color;
+            let screen_pos = to_screen(egui::pos2(annotation.position.0, annotation.position.1));
+            let text_rect = painter.text(
+                screen_pos,
+                egui::Align2::LEFT_TOP,
+                annotation.text,
+                egui::FontId::proportional(annotation.font_size * zoom),
+                egui::Color32::from_rgb(r, g, b),
+            );
+
+            let is_selected = self.annotation_editor.selected == Some(annotation_id) && !self.is_capturing_canvas_image();
+            if is_selected {
+                painter.rect_stroke(
+                    text_rect.expand(3.0),
+                    2.0,
+                    egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 100, 200)),
+                    egui::epaint::StrokeKind::Outside,
+                );
+            }
+
+            let interact_id = ui.id().with(("annotation_interact", annotation_id));
+            let response = ui.interact(text_rect, interact_id, self.canvas_item_sense());
+
+            if response.hovered() {
+                annotation_hovered = true;
+            }
+
+            if response.drag_started() {
+                self.canvas.dragging_annotation = Some(annotation_id);
+                self.canvas.annotation_drag_start = pointer_pos;
+            }
+
+            if response.dragged() && self.canvas.dragging_annotation == Some(annotation_id) {
+                any_annotation_dragged = true;
+                if let (Some(pos), Some(start)) = (pointer_pos, self.canvas.annotation_drag_start) {
+                    let delta = (pos - start) / zoom;
+                    let moved = (annotation_position.0 + delta.x, annotation_position.1 + delta.y);
+                    let placed = if self.canvas.grid.snap_while_dragging {
+                        self.snap_to_canvas_grid(moved, origin)
+                    } else {
+                        moved
+                    };
+                    if let Some(annotation) = self.tree.annotation_mut(annotation_id) {
+                        annotation.position = placed;
+                    }
+                    // スナップで合わせた分は次のフレームに持ち越す
+                    let carried = egui::vec2(moved.0 - placed.0, moved.1 - placed.1) * zoom;
+                    self.canvas.annotation_drag_start = Some(pos - carried);
+                }
+            }
+
+            if response.drag_stopped() && self.canvas.dragging_annotation == Some(annotation_id) {
+                if let Some(position) = self.tree.annotation(annotation_id).map(|annotation| annotation.position) {
+                    let snapped = self.snap_to_canvas_grid(position, origin);
+                    if let Some(annotation) = self.tree.annotation_mut(annotation_id) {
+                        annotation.position = snapped;
+                    }
+                }
+                self.canvas.dragging_annotation = None;
+                self.canvas.annotation_drag_start = None;
+            }
+
+            if response.clicked() {
+                self.select_annotation(annotation_id);
+                self.ui.side_tab = SideTab::Annotations;
+            }
+        }
+
+        (annotation_hovered, any_annotation_dragged)
+    }
+}
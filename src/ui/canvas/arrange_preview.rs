@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::layout::LayoutNode;
+use crate::core::tree::PersonId;
+use super::ArrangePreviewRenderer;
+
+impl ArrangePreviewRenderer for App {
+    fn render_arrange_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        nodes: &[LayoutNode],
+        screen_rects: &HashMap<PersonId, egui::Rect>,
+        origin: egui::Pos2,
+    ) {
+        // 閲覧モードに切り替えたらプレビューも取りやめる
+        if self.ui.read_only {
+            self.canvas.arrange_preview = None;
+        }
+        if self.canvas.arrange_preview.is_some() {
+            self.refresh_auto_arrange_preview();
+        }
+        let Some(moves) = self.canvas.arrange_preview.as_ref().map(|preview| &preview.moves) else {
+            return;
+        };
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let zoom = self.canvas.zoom;
+        let pan = self.canvas.pan;
+        let accent = ui.visuals().selection.stroke.color;
+
+        // 整列後の位置に半透明の枠を描き、今の位置から矢印で結ぶ
+        for node in nodes {
+            let (Some((x, y)), Some(current)) = (moves.get(&node.id), screen_rects.get(&node.id)) else {
+                continue;
+            };
+            let min = origin + (egui::pos2(*x, *y) - origin) * zoom + pan;
+            let ghost = egui::Rect::from_min_size(min, current.size());
+            painter.rect_filled(ghost, 6.0, accent.gamma_multiply(0.15));
+            painter.rect_stroke(ghost, 6.0, egui::Stroke::new(1.5, accent), egui::StrokeKind::Inside);
+            painter.arrow(
+                current.center(),
+                ghost.center() - current.center(),
+                egui::Stroke::new(1.0, accent.gamma_multiply(0.6)),
+            );
+        }
+
+        let count = moves.len();
+        let mut apply = false;
+        let mut cancel = ui.input(|i| i.key_pressed(egui::Key::Escape));
+        egui::Area::new(egui::Id::new("arrange_preview_bar"))
+            .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}: {count}", t("auto_arrange_preview")));
+                        if ui.button(t("apply")).clicked() {
+                            apply = true;
+                        }
+                        if ui.button(t("cancel")).clicked() {
+                            cancel = true;
+                        }
+                    });
+                });
+            });
+
+        if apply {
+            self.apply_auto_arrange_preview();
+        } else if cancel {
+            self.canvas.arrange_preview = None;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::app::App;
+use crate::ui::BackgroundRenderer;
+
+impl BackgroundRenderer for App {
+    fn render_canvas_background(&mut self, ctx: &egui::Context, painter: &egui::Painter, origin: egui::Pos2) {
+        let Some(background) = self.tree.background.as_ref() else {
+            return;
+        };
+        if background.path.is_empty() {
+            return;
+        }
+        let Some((texture, source_size)) = self.canvas.photo_texture_cache.get_or_load_with_source_size(ctx, &background.path) else {
+            return;
+        };
+
+        let to_screen = |p: egui::Pos2| -> egui::Pos2 {
+            let v = (p - origin) * self.canvas.zoom;
+            origin + v + self.canvas.pan
+        };
+
+        // 背景画像の座標は人物・イベントの位置と同じワールド座標
+        // テクスチャは縮小されていることがあるので、大きさは元画像のピクセル数から決める
+        let world_min = egui::pos2(background.position.0, background.position.1);
+        let world_max = world_min + source_size * background.scale;
+        let rect = egui::Rect::from_min_max(to_screen(world_min), to_screen(world_max));
+
+        painter.image(
+            texture.id(),
+            rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE.gamma_multiply(background.opacity.clamp(0.0, 1.0)),
+        );
+    }
+}
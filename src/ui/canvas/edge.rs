@@ -1,8 +1,38 @@
 use crate::app::{App, EDGE_STROKE_WIDTH, SPOUSE_LINE_OFFSET};
-use crate::core::tree::{PersonId, Gender};
-use crate::ui::EdgeRenderer;
+use crate::core::tree::{PersonId, SpouseLabelPosition, ADOPTIVE_KIND};
+use crate::core::i18n::Texts;
+use crate::ui::{EdgeRenderer, EdgeStyle};
 use std::collections::HashMap;
 
+/// 人物間の関係を示す点線の間隔と点の半径
+const PERSON_RELATION_DOT_SPACING: f32 = 6.0;
+const PERSON_RELATION_DOT_RADIUS: f32 = 1.0;
+
+/// 多胎のきょうだいをつなぐ括弧の高さと色
+const MULTIPLE_BIRTH_BRACKET_HEIGHT: f32 = 8.0;
+const MULTIPLE_BIRTH_BRACKET_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 120, 200);
+
+/// 配偶者の線とメモの間隔（ズーム1.0のとき）と、メモを描く最小の文字サイズ（画面上）
+const SPOUSE_LABEL_GAP: f32 = 6.0;
+const MIN_SPOUSE_LABEL_SCREEN_SIZE: f32 = 5.0;
+
+/// 養子縁組を示す円の半径
+const ADOPTION_GLYPH_RADIUS: f32 = 4.0;
+
+/// 親の接続点が複数ある子で、子の上端に入る線どうしの間隔（ズーム1.0のとき）
+const JUNCTION_ENTRY_SPACING: f32 = 12.0;
+/// 接続点の印の半径
+const JUNCTION_DOT_RADIUS: f32 = 3.0;
+
+/// 親子関係の種類ごとの線の色（実子は従来どおりの薄い灰色）
+fn parent_child_kind_color(kind: &str) -> egui::Color32 {
+    match kind {
+        "" | "biological" => egui::Color32::LIGHT_GRAY,
+        ADOPTIVE_KIND => egui::Color32::from_rgb(120, 160, 210),
+        _ => egui::Color32::from_rgb(210, 160, 100),
+    }
+}
+
 impl EdgeRenderer for App {
     fn render_canvas_edges(
         &mut self,
@@ -11,6 +41,7 @@ impl EdgeRenderer for App {
         screen_rects: &HashMap<PersonId, egui::Rect>,
     ) {
         // 配偶者の線
+        let mut clicked_spouse_pair = None;
         for s in &self.tree.spouses {
             if let (Some(r1), Some(r2)) = (screen_rects.get(&s.person1), screen_rects.get(&s.person2)) {
                 let a = r1.center();
@@ -27,107 +58,241 @@ impl EdgeRenderer for App {
                     [a - perpendicular, b - perpendicular],
                     egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY),
                 );
-                
-                // メモがある場合、ツールチップを表示
-                if !s.memo.is_empty() {
-                    let mid = egui::pos2((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
-                    let line_rect = egui::Rect::from_center_size(
-                        mid,
-                        egui::vec2((b.x - a.x).abs().max(20.0), (b.y - a.y).abs().max(20.0))
+
+                let mid = egui::pos2((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+
+                // 離婚している場合は中点に斜線を2本描画
+                if s.divorced {
+                    let slash = egui::vec2(dir.x + dir.y, dir.y - dir.x) * 5.0;
+                    let gap = dir * 3.0;
+                    for offset in [gap, -gap] {
+                        painter.line_segment(
+                            [mid + offset - slash, mid + offset + slash],
+                            egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::GRAY),
+                        );
+                    }
+                }
+
+                // メモ（結婚年など）を線の上か下に表示する
+                let font_size = s.label.font_size * self.canvas.zoom;
+                if s.label.visible && !s.memo.trim().is_empty() && font_size >= MIN_SPOUSE_LABEL_SCREEN_SIZE {
+                    let (anchor, offset) = match s.label.position {
+                        SpouseLabelPosition::Above => (egui::Align2::CENTER_BOTTOM, -SPOUSE_LABEL_GAP),
+                        SpouseLabelPosition::Below => (egui::Align2::CENTER_TOP, SPOUSE_LABEL_GAP),
+                    };
+                    let (r, g, b) = s.label.color;
+                    painter.text(
+                        mid + egui::vec2(0.0, offset * self.canvas.zoom),
+                        anchor,
+                        s.memo.trim(),
+                        egui::FontId::proportional(font_size),
+                        egui::Color32::from_rgb(r, g, b),
                     );
-                    let line_id = ui.id().with(("spouse_line", s.person1, s.person2));
-                    let line_response = ui.interact(line_rect, line_id, egui::Sense::hover());
-                    if line_response.hovered() {
+                }
+
+                // 中点をクリックすると配偶者関係の編集ダイアログを開く
+                let handle_rect = egui::Rect::from_center_size(mid, egui::vec2(16.0, 16.0));
+                let line_id = ui.id().with(("spouse_line", s.person1, s.person2));
+                let line_response = ui.interact(handle_rect, line_id, egui::Sense::click());
+                if line_response.clicked() && !self.ui.read_only {
+                    clicked_spouse_pair = Some((s.person1, s.person2));
+                }
+                if line_response.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    // メモがある場合、ツールチップを表示
+                    if !s.memo.is_empty() {
                         line_response.on_hover_text(&s.memo);
                     }
                 }
             }
         }
 
-        // 親子の線
-        let mut child_to_parents: HashMap<PersonId, Vec<PersonId>> = HashMap::new();
-        for e in &self.tree.edges {
-            child_to_parents.entry(e.child).or_default().push(e.parent);
+        if let Some((person1, person2)) = clicked_spouse_pair {
+            self.open_spouse_editor(person1, person2);
+        }
+
+        // 多胎（双子など）のきょうだいはノード上部を括弧でつなぐ
+        for members in self.tree.multiple_birth_groups().values() {
+            let mut rects: Vec<egui::Rect> = members
+                .iter()
+                .filter_map(|id| screen_rects.get(id).copied())
+                .collect();
+            if rects.len() < 2 {
+                continue;
+            }
+            rects.sort_by(|a, b| a.center().x.total_cmp(&b.center().x));
+
+            let bracket_y = rects.iter().map(|r| r.top()).fold(f32::INFINITY, f32::min) - MULTIPLE_BIRTH_BRACKET_HEIGHT;
+            let stroke = egui::Stroke::new(EDGE_STROKE_WIDTH, MULTIPLE_BIRTH_BRACKET_COLOR);
+            for rect in &rects {
+                let top = rect.center_top();
+                painter.line_segment([top, egui::pos2(top.x, bracket_y)], stroke);
+            }
+            if let (Some(first), Some(last)) = (rects.first(), rects.last()) {
+                painter.line_segment(
+                    [egui::pos2(first.center().x, bracket_y), egui::pos2(last.center().x, bracket_y)],
+                    stroke,
+                );
+            }
+        }
+
+        // 後見人・名付け親などの関係は細い点線で描画
+        let lang = self.ui.language;
+        for r in &self.tree.person_relations {
+            if let (Some(r1), Some(r2)) = (screen_rects.get(&r.from), screen_rects.get(&r.to)) {
+                let a = r1.center();
+                let b = r2.center();
+                painter.extend(egui::Shape::dotted_line(
+                    &[a, b],
+                    egui::Color32::GRAY,
+                    PERSON_RELATION_DOT_SPACING,
+                    PERSON_RELATION_DOT_RADIUS,
+                ));
+
+                let mid = egui::pos2((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+                let handle_rect = egui::Rect::from_center_size(mid, egui::vec2(12.0, 12.0));
+                let relation_id = ui.id().with(("person_relation", r.from, r.to, r.kind as u8));
+                let mut tooltip = format!(
+                    "{}: {}\n{}: {}",
+                    Texts::get(r.kind.role_key(true), lang),
+                    self.get_person_name(&r.from),
+                    Texts::get(r.kind.role_key(false), lang),
+                    self.get_person_name(&r.to),
+                );
+                if !r.memo.is_empty() {
+                    tooltip.push_str(&format!("\n{}", r.memo));
+                }
+                ui.interact(handle_rect, relation_id, egui::Sense::hover())
+                    .on_hover_text(tooltip);
+            }
         }
 
-        let mut processed_children = std::collections::HashSet::new();
+        // 親子の線（家族の単位ごとの接続点から子へ。接続点が複数ある子は種類のラベルを付ける）
+        let unions = self.tree.unions();
+        let mut union_children = self.tree.children_by_union();
+        let mut child_unions: HashMap<PersonId, Vec<usize>> = HashMap::new();
+        let mut partner_unions: HashMap<PersonId, usize> = HashMap::new();
+        for (index, union) in unions.iter().enumerate() {
+            for child in union_children.get(&union.id).into_iter().flatten() {
+                child_unions.entry(*child).or_default().push(index);
+            }
+            for partner in &union.partners {
+                *partner_unions.entry(*partner).or_default() += 1;
+            }
+        }
 
-        for e in &self.tree.edges {
-            let child_id = e.child;
-            
-            if processed_children.contains(&child_id) {
+        for (union_index, union) in unions.iter().enumerate() {
+            let parent_rects: Vec<egui::Rect> = union
+                .partners
+                .iter()
+                .filter_map(|id| screen_rects.get(id).copied())
+                .collect();
+            if parent_rects.len() != union.partners.len() {
                 continue;
             }
-            
-            if let Some(parents) = child_to_parents.get(&child_id) {
-                let mut father_id = None;
-                let mut mother_id = None;
-                let mut other_parents = Vec::new();
-                
-                for parent_id in parents {
-                    if let Some(parent) = self.tree.persons.get(parent_id) {
-                        match parent.gender {
-                            Gender::Male if father_id.is_none() => father_id = Some(*parent_id),
-                            Gender::Female if mother_id.is_none() => mother_id = Some(*parent_id),
-                            _ => other_parents.push(*parent_id),
-                        }
+
+            let color = parent_child_kind_color(&union.kind);
+            let from = match parent_rects.as_slice() {
+                [parent] => parent.center_bottom(),
+                [first, second] => {
+                    let (a, b) = (first.center(), second.center());
+                    if !self.tree.union_partners_are_spouses(union) {
+                        painter.line_segment([a, b], egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY));
                     }
+                    egui::pos2((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
                 }
-                
-                if let (Some(father), Some(mother)) = (father_id, mother_id) {
-                    let are_spouses = self.tree.spouses.iter().any(|s| {
-                        (s.person1 == father && s.person2 == mother) ||
-                        (s.person1 == mother && s.person2 == father)
-                    });
-                    
-                    if are_spouses {
-                        if let (Some(rf), Some(rm), Some(rc)) = (
-                            screen_rects.get(&father),
-                            screen_rects.get(&mother),
-                            screen_rects.get(&child_id)
-                        ) {
-                            let father_center = rf.center();
-                            let mother_center = rm.center();
-                            let mid = egui::pos2(
-                                (father_center.x + mother_center.x) / 2.0,
-                                (father_center.y + mother_center.y) / 2.0
-                            );
-                            let child_top = rc.center_top();
-                            
-                            painter.line_segment([mid, child_top], egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY));
-                        }
-                    } else {
-                        if let (Some(rf), Some(rm), Some(rc)) = (
-                            screen_rects.get(&father),
-                            screen_rects.get(&mother),
-                            screen_rects.get(&child_id)
-                        ) {
-                            let father_center = rf.center();
-                            let mother_center = rm.center();
-                            
-                            painter.line_segment(
-                                [father_center, mother_center],
-                                egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY)
-                            );
-                            
-                            let mid = egui::pos2(
-                                (father_center.x + mother_center.x) / 2.0,
-                                (father_center.y + mother_center.y) / 2.0
-                            );
-                            let child_top = rc.center_top();
-                            
-                            painter.line_segment([mid, child_top], egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY));
-                        }
-                    }
-                    processed_children.insert(child_id);
+                _ => continue,
+            };
+            // 再婚などで親が複数の単位に属する場合は、どの組の子かわかるよう接続点に印を付ける
+            if union.partners.iter().any(|partner| partner_unions.get(partner).is_some_and(|count| *count > 1)) {
+                painter.circle_filled(from, JUNCTION_DOT_RADIUS, color);
+            }
+
+            for child_id in &union_children.remove(&union.id).unwrap_or_default() {
+                let Some(rc) = screen_rects.get(child_id) else {
                     continue;
+                };
+                let entries = child_unions.get(child_id).map(Vec::as_slice).unwrap_or_default();
+                let entry = entries.iter().position(|index| *index == union_index).unwrap_or(0);
+                // 接続点が複数ある子は子の上端で少しずつずらして線が重ならないようにする
+                let spread = (entry as f32 - (entries.len().max(1) - 1) as f32 / 2.0) * JUNCTION_ENTRY_SPACING * self.canvas.zoom;
+                let to = rc.center_top() + egui::vec2(spread, 0.0);
+                self.draw_parent_child_connector(painter, from, to, color);
+
+                if entries.len() > 1 {
+                    painter.circle_filled(from, JUNCTION_DOT_RADIUS, color);
+                    let label_pos = from + (to - from) * 0.3;
+                    let galley = painter.layout_no_wrap(
+                        union.kind.clone(),
+                        egui::FontId::proportional(10.0 * self.canvas.zoom.clamp(0.7, 1.2)),
+                        egui::Color32::DARK_GRAY,
+                    );
+                    let label_rect = egui::Rect::from_center_size(label_pos, galley.size() + egui::vec2(6.0, 2.0));
+                    painter.rect(
+                        label_rect,
+                        3.0,
+                        egui::Color32::from_white_alpha(220),
+                        egui::Stroke::new(1.0, color),
+                        egui::StrokeKind::Inside,
+                    );
+                    painter.galley(label_rect.center() - galley.size() / 2.0, galley, egui::Color32::DARK_GRAY);
                 }
             }
-            
-            if let (Some(rp), Some(rc)) = (screen_rects.get(&e.parent), screen_rects.get(&e.child)) {
-                let a = rp.center_bottom();
-                let b = rc.center_top();
-                painter.line_segment([a, b], egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::LIGHT_GRAY));
+        }
+
+        // 養子縁組の親子関係は子側の端に白抜きの円を描画
+        let mut adoption_tooltips: HashMap<PersonId, Vec<String>> = HashMap::new();
+        for e in self.tree.edges.iter().filter(|e| e.is_adoptive()) {
+            let mut lines = vec![self.get_person_name(&e.parent)];
+            if let Some(date) = &e.adoption_date {
+                lines.push(date.clone());
+            }
+            if !e.adoption_type.is_empty() {
+                lines.push(e.adoption_type.clone());
+            }
+            if !e.adoption_note.is_empty() {
+                lines.push(e.adoption_note.clone());
+            }
+            adoption_tooltips.entry(e.child).or_default().push(lines.join(" / "));
+        }
+
+        for (child_id, tooltips) in &adoption_tooltips {
+            let Some(rc) = screen_rects.get(child_id) else {
+                continue;
+            };
+            let center = rc.center_top() - egui::vec2(0.0, ADOPTION_GLYPH_RADIUS);
+            painter.circle(
+                center,
+                ADOPTION_GLYPH_RADIUS,
+                egui::Color32::WHITE,
+                egui::Stroke::new(EDGE_STROKE_WIDTH, egui::Color32::GRAY),
+            );
+
+            let glyph_rect = egui::Rect::from_center_size(center, egui::vec2(ADOPTION_GLYPH_RADIUS * 2.0, ADOPTION_GLYPH_RADIUS * 2.0));
+            let glyph_id = ui.id().with(("adoption_glyph", child_id));
+            ui.interact(glyph_rect, glyph_id, egui::Sense::hover())
+                .on_hover_text(tooltips.join("\n"));
+        }
+    }
+}
+
+impl App {
+    /// 親（または両親の中点）から子への線を、設定に応じて直線か3次ベジェ曲線で描画
+    fn draw_parent_child_connector(&self, painter: &egui::Painter, from: egui::Pos2, to: egui::Pos2, color: egui::Color32) {
+        let stroke = egui::Stroke::new(EDGE_STROKE_WIDTH, color);
+        match self.ui.edge_style {
+            EdgeStyle::Straight => {
+                painter.line_segment([from, to], stroke);
+            }
+            EdgeStyle::Bezier => {
+                // 制御点を上下方向にずらし、親から垂直に出て子へ垂直に入る曲線にする
+                let offset = egui::vec2(0.0, self.ui.bezier_control_offset * self.canvas.zoom);
+                painter.add(egui::epaint::CubicBezierShape::from_points_stroke(
+                    [from, from + offset, to - offset, to],
+                    false,
+                    egui::Color32::TRANSPARENT,
+                    stroke,
+                ));
             }
         }
     }
@@ -28,7 +28,7 @@ impl EventNodeRenderer for App {
                 event.date.clone(),
                 event.description.clone(),
                 event.color,
-                self.event_editor.selected == Some(event_id),
+                self.event_editor.selected == Some(event_id) && !self.is_capturing_canvas_image(),
                 self.canvas.dragging_event == Some(event_id),
             );
             
@@ -93,7 +93,7 @@ impl EventNodeRenderer for App {
 
             // インタラクション処理
             let event_interact_id = ui.id().with(("event_interact", event_id));
-            let interact_response = ui.interact(rect, event_interact_id, egui::Sense::click_and_drag());
+            let interact_response = ui.interact(rect, event_interact_id, self.canvas_item_sense());
 
             if interact_response.hovered() {
                 event_hovered = true;
@@ -116,12 +116,20 @@ impl EventNodeRenderer for App {
                 if let (Some(pos), Some(start)) = (pointer_pos, self.canvas.event_drag_start) {
                     let delta = (pos - start) / self.canvas.zoom;
                     
-                    if let Some(event) = self.tree.events.get_mut(&event_id) {
-                        let current_pos = event.position;
-                        event.position.0 = current_pos.0 + delta.x;
-                        event.position.1 = current_pos.1 + delta.y;
+                    if let Some(current_pos) = self.tree.events.get(&event_id).map(|event| event.position) {
+                        let moved = (current_pos.0 + delta.x, current_pos.1 + delta.y);
+                        let placed = if self.canvas.grid.snap_while_dragging {
+                            self.snap_to_canvas_grid(moved, origin)
+                        } else {
+                            moved
+                        };
+                        if let Some(event) = self.tree.events.get_mut(&event_id) {
+                            event.position = placed;
+                        }
+                        // スナップで合わせた分は次のフレームに持ち越し、ポインタから離れていかないようにする
+                        let carried = egui::vec2(moved.0 - placed.0, moved.1 - placed.1) * self.canvas.zoom;
+                        self.canvas.event_drag_start = Some(pos - carried);
                     }
-                    self.canvas.event_drag_start = pointer_pos;
                 }
             }
 
@@ -134,12 +142,10 @@ impl EventNodeRenderer for App {
                 let t = |key: &str| Texts::get(key, lang);
                 self.log.add(format!("{}: {}", t("log_event_moved"), event_name), LogLevel::Debug);
                 
-                if self.canvas.show_grid {
+                if let Some(position) = self.tree.events.get(&event_id).map(|event| event.position) {
+                    let snapped = self.snap_to_canvas_grid(position, origin);
                     if let Some(event) = self.tree.events.get_mut(&event_id) {
-                        let (x, y) = event.position;
-                        let relative_pos = egui::pos2(x - origin.x, y - origin.y);
-                        let snapped_rel = LayoutEngine::snap_to_grid(relative_pos, self.canvas.grid_size);
-                        event.position = (origin.x + snapped_rel.x, origin.y + snapped_rel.y);
+                        event.position = snapped;
                     }
                 }
                 self.canvas.dragging_event = None;
@@ -153,6 +159,7 @@ impl EventNodeRenderer for App {
                 self.event_editor.new_event_description = description;
                 let (r, g, b) = color;
                 self.event_editor.new_event_color = [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0];
+                self.event_editor.new_event_place = self.tree.events.get(&event_id).and_then(|event| event.place);
                 self.ui.side_tab = SideTab::Events;
                 
                 let event_name = if name.is_empty() {
@@ -1,6 +1,7 @@
 use crate::app::App;
 use crate::core::tree::PersonId;
 use crate::core::i18n::Texts;
+use crate::core::slideshow::SlideshowSource;
 use crate::ui::{FamilyBoxRenderer, LogLevel, SideTab};
 use std::collections::HashMap;
 
@@ -103,7 +104,9 @@ impl FamilyBoxRenderer for App {
                     );
                 }
                 
-                let text_color = if resp.hovered() || resp.is_pointer_button_down_on() {
+                let text_color = if let Some((r, g, b)) = family.defaults.label_color {
+                    egui::Color32::from_rgb(r, g, b)
+                } else if resp.hovered() || resp.is_pointer_button_down_on() {
                     stroke_color
                 } else {
                     egui::Color32::from_rgb(
@@ -122,17 +125,20 @@ impl FamilyBoxRenderer for App {
                 );
                 
                 if resp.clicked() {
-                    self.family_editor.selected_family = Some(family.id);
-                    self.family_editor.new_family_name = family.name.clone();
-                    if let Some((r, g, b)) = family.color {
-                        self.family_editor.new_family_color = [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0];
-                    }
+                    self.family_editor.load_family(family);
                     self.ui.side_tab = SideTab::Families;
                     let lang = self.ui.language;
                     let t = |key: &str| Texts::get(key, lang);
-                    self.file.status = format!("{} {}", t("selected_family"), family.name);
+                    self.toasts.info(format!("{} {}", t("selected_family"), family.name));
                     self.log.add(format!("{}: {}", t("log_family_selected"), family.name), LogLevel::Debug);
                 }
+
+                resp.context_menu(|ui| {
+                    if ui.button(Texts::get("slideshow", self.ui.language)).clicked() {
+                        self.slideshow.open(SlideshowSource::Family(family.id));
+                        ui.close();
+                    }
+                });
             }
         }
     }
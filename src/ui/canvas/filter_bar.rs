@@ -0,0 +1,51 @@
+use crate::app::App;
+use crate::core::filter::{CanvasQuery, CanvasQueryError};
+use crate::core::i18n::Texts;
+
+use super::CanvasFilterBar;
+
+impl CanvasFilterBar for App {
+    fn render_canvas_filter_bar(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let query = CanvasQuery::parse(&self.canvas.filter_text);
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.canvas.filter_text)
+                    .hint_text(t("canvas_filter_hint"))
+                    .desired_width(260.0),
+            )
+            .on_hover_text(t("canvas_filter_help"));
+            if !self.canvas.filter_text.is_empty() && ui.small_button("✖").on_hover_text(t("clear_filter")).clicked() {
+                self.canvas.filter_text.clear();
+            }
+            match &query {
+                Err(CanvasQueryError::UnknownKey(key)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("{}: {key}", t("canvas_filter_unknown_key")));
+                }
+                Err(CanvasQueryError::InvalidValue { key, value }) => {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("{}: {key}:{value}", t("canvas_filter_invalid_value")),
+                    );
+                }
+                Ok(_) => {}
+            }
+        });
+
+        // 描画より先に一致しない人物を求めておく（入力が誤っている間は何も薄くしない）
+        let presumption = self.ui.deceased_presumption();
+        self.canvas.dimmed_persons = match query {
+            Ok(query) if query.is_active() => self
+                .tree
+                .persons
+                .values()
+                .filter(|person| !query.matches(&self.tree, person, presumption))
+                .map(|person| person.id)
+                .collect(),
+            _ => Default::default(),
+        };
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::layout::LayoutEngine;
+use crate::core::tree::PersonId;
+use crate::ui::{ImageCopyState, LogLevel, MIN_CANVAS_ZOOM};
+
+use super::CanvasImageCopy;
+
+/// 選択中のノードを切り抜くときに周りに残す余白
+const IMAGE_COPY_MARGIN: f32 = 16.0;
+/// スクリーンショットが届かないときに諦めるまでの時間（対応していない環境では届かない）
+const SCREENSHOT_TIMEOUT: Duration = Duration::from_secs(3);
+
+impl CanvasImageCopy for App {
+    fn prepare_canvas_capture(&mut self) {
+        if self.canvas.image_copy != Some(ImageCopyState::Requested) {
+            return;
+        }
+        // 移動中のカメラは行き先に戻す
+        let camera = match self.canvas.camera_animation.take() {
+            Some(animation) => (animation.to_zoom, animation.to_pan),
+            None => (self.canvas.zoom, self.canvas.pan),
+        };
+        self.canvas.image_copy = Some(ImageCopyState::Framing { camera });
+
+        let selected = self.image_copy_selection();
+        if selected.is_empty() {
+            return;
+        }
+        // 選択中の人物がすべて写るよう、必要なら縮小して中央に移す
+        let origin = self.canvas_layout_origin();
+        let photo_dimensions = self.collect_photo_dimensions();
+        let bounds = LayoutEngine::compute_layout(&self.tree, &photo_dimensions, self.ui.show_life_years)
+            .iter()
+            .filter(|node| selected.contains(&node.id))
+            .map(|node| node.rect)
+            .reduce(|bounds, rect| bounds.union(rect));
+        let Some(bounds) = bounds else {
+            return;
+        };
+        let rect = self.canvas.canvas_rect;
+        let available = (rect.size() - egui::Vec2::splat(IMAGE_COPY_MARGIN * 2.0)).max(egui::Vec2::splat(1.0));
+        let fit_zoom = (available.x / bounds.width().max(1.0)).min(available.y / bounds.height().max(1.0));
+        let zoom = fit_zoom.min(camera.0).max(MIN_CANVAS_ZOOM);
+        self.canvas.zoom = zoom;
+        self.canvas.pan = rect.center() - origin - (bounds.center() - origin) * zoom;
+    }
+
+    fn request_canvas_screenshot(&mut self, ctx: &egui::Context, rect: egui::Rect, screen_rects: &HashMap<PersonId, egui::Rect>) {
+        let Some(ImageCopyState::Framing { camera }) = self.canvas.image_copy else {
+            return;
+        };
+
+        let selected = self.image_copy_selection();
+        let region = if selected.is_empty() {
+            Some(rect)
+        } else {
+            // 最小倍率でも収まらない部分は写せないので、キャンバスの表示範囲で切る
+            selected
+                .iter()
+                .filter_map(|id| screen_rects.get(id))
+                .copied()
+                .reduce(|bounds, node| bounds.union(node))
+                .map(|bounds| bounds.expand(IMAGE_COPY_MARGIN).intersect(rect))
+                .filter(|region| region.is_positive())
+        };
+
+        let Some(region) = region else {
+            self.finish_canvas_capture(camera);
+            self.toasts.warning(Texts::get("copy_as_image_not_visible", self.ui.language));
+            return;
+        };
+        self.canvas.image_copy = Some(ImageCopyState::Capturing { region, camera, requested_at: Instant::now() });
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::default()));
+        // 入力がなくても結果を受け取れるよう、次のフレームを描く
+        ctx.request_repaint();
+    }
+
+    fn receive_canvas_screenshot(&mut self, ctx: &egui::Context) {
+        let Some(ImageCopyState::Capturing { region, camera, requested_at }) = self.canvas.image_copy else {
+            return;
+        };
+        let Some(screenshot) = ctx.input(|i| {
+            i.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        }) else {
+            // 届かないまま待ち続けると表示が撮影用のままになるので、時間切れで元に戻す
+            if requested_at.elapsed() >= SCREENSHOT_TIMEOUT {
+                self.finish_canvas_capture(camera);
+                let message = Texts::get("copy_as_image_timed_out", self.ui.language);
+                self.toasts.warning(message.clone());
+                self.log.add(message, LogLevel::Warning);
+            } else {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            return;
+        };
+        self.finish_canvas_capture(camera);
+
+        let t = |key: &str| Texts::get(key, self.ui.language);
+        // スクリーンショットは物理ピクセルなので、表示倍率を掛けて切り抜く
+        let pixels_per_point = ctx.pixels_per_point();
+        let image_rect = egui::Rect::from_min_size(egui::Pos2::ZERO, screenshot.size.map(|size| size as f32 / pixels_per_point).into());
+        let region = region.intersect(image_rect);
+        if !region.is_positive() {
+            self.toasts.warning(t("copy_as_image_not_visible"));
+            return;
+        }
+        let image = screenshot.region(&region, Some(pixels_per_point));
+        let message = format!("{} ({}×{})", t("copy_as_image_done"), image.width(), image.height());
+        ctx.copy_image(image);
+        self.toasts.info(message.clone());
+        self.log.add(message, LogLevel::Debug);
+    }
+}
+
+impl App {
+    /// 選択中のノード（選択がなければキャンバスの表示範囲）を画像としてクリップボードにコピーする
+    ///
+    /// 次のキャンバス描画で画面を撮って切り抜くため、コピーは1〜2フレーム遅れて完了する。
+    pub fn copy_canvas_as_image(&mut self) {
+        self.canvas.image_copy = Some(ImageCopyState::Requested);
+    }
+
+    /// 「画像としてコピー」で撮るフレームを描いているか（選択の強調表示や重なる表示を省く）
+    pub(crate) fn is_capturing_canvas_image(&self) -> bool {
+        matches!(self.canvas.image_copy, Some(ImageCopyState::Framing { .. } | ImageCopyState::Capturing { .. }))
+    }
+
+    /// 画像にする人物（複数選択がなければ選択中の1人）
+    pub(crate) fn image_copy_selection(&self) -> Vec<PersonId> {
+        if self.person_editor.selected_ids.is_empty() {
+            self.person_editor.selected.into_iter().collect()
+        } else {
+            self.person_editor.selected_ids.clone()
+        }
+    }
+
+    /// 撮り終えたらカメラを元に戻す
+    fn finish_canvas_capture(&mut self, (zoom, pan): (f32, egui::Vec2)) {
+        self.canvas.image_copy = None;
+        self.canvas.zoom = zoom;
+        self.canvas.pan = pan;
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 
 // モジュール宣言
 mod renderer;
+mod background;
 mod node;
 mod node_painter;
 mod node_interaction;
@@ -11,12 +12,37 @@ mod edge;
 mod family_box;
 mod event_node;
 mod event_relation;
+mod annotation;
+mod sticky_note;
+mod filter_bar;
+mod arrange_preview;
+mod ruler;
+mod image_copy;
+
+pub(crate) use node_painter::node_color_theme_from_preset;
 
 /// キャンバスのメイン描画トレイト
 pub trait CanvasRenderer {
     fn render_canvas(&mut self, ctx: &egui::Context);
 }
 
+/// 「画像としてコピー」トレイト
+pub trait CanvasImageCopy {
+    /// コピーが要求されていれば、撮るフレームに切り替えて選択中のノードが収まるようにカメラを動かす
+    fn prepare_canvas_capture(&mut self);
+
+    /// 撮るフレームなら、選択中のノード（なければ表示範囲）を撮るスクリーンショットを要求する
+    fn request_canvas_screenshot(&mut self, ctx: &egui::Context, rect: egui::Rect, screen_rects: &HashMap<PersonId, egui::Rect>);
+
+    /// 届いたスクリーンショットを切り抜いてクリップボードに入れる
+    fn receive_canvas_screenshot(&mut self, ctx: &egui::Context);
+}
+
+/// 背景画像描画トレイト
+pub trait BackgroundRenderer {
+    fn render_canvas_background(&mut self, ctx: &egui::Context, painter: &egui::Painter, origin: egui::Pos2);
+}
+
 /// ノード描画トレイト
 pub trait NodeRenderer {
     fn render_canvas_nodes(
@@ -52,6 +78,18 @@ pub trait PanZoomHandler {
         event_hovered: bool,
         any_event_dragged: bool,
     );
+
+    /// 画面上の anchor の位置にあるワールド座標を固定したままズームする
+    fn zoom_at(&mut self, anchor: egui::Pos2, origin: egui::Pos2, factor: f32);
+
+    /// タッチスクリーン・トラックパッドのピンチズームと2本指パン
+    fn handle_touch_gestures(&mut self, ui: &mut egui::Ui, rect: egui::Rect, origin: egui::Pos2);
+
+    /// 進行中のカメラ移動アニメーションを1フレーム進める
+    fn update_camera_animation(&mut self, ctx: &egui::Context);
+
+    /// ズーム倍率の一覧とスライダー（Ctrl+ホイールでのズームにも追従する）
+    fn render_zoom_toolbar(&mut self, ui: &mut egui::Ui);
 }
 
 /// エッジ描画トレイト
@@ -94,3 +132,54 @@ pub trait EventRelationRenderer {
         screen_rects: &HashMap<PersonId, egui::Rect>,
     );
 }
+
+/// 注釈（文字ラベル）描画トレイト
+pub trait AnnotationRenderer {
+    fn render_annotations(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        pointer_pos: Option<egui::Pos2>,
+    ) -> (bool, bool); // (annotation_hovered, any_annotation_dragged)
+}
+
+/// 付箋描画トレイト
+pub trait StickyNoteRenderer {
+    fn render_sticky_notes(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        screen_rects: &HashMap<PersonId, egui::Rect>,
+    );
+}
+
+/// 絞り込みバー（`tag:paternal alive:true` などに一致しない人物を薄く表示する）
+pub trait CanvasFilterBar {
+    fn render_canvas_filter_bar(&mut self, ui: &mut egui::Ui);
+}
+
+/// 自動整列のプレビュー（整列後の位置に半透明の枠を重ね、適用・取り消しを選ばせる）
+pub trait ArrangePreviewRenderer {
+    fn render_arrange_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        nodes: &[crate::core::layout::LayoutNode],
+        screen_rects: &HashMap<PersonId, egui::Rect>,
+        origin: egui::Pos2,
+    );
+}
+
+/// キャンバス端の定規と、定規から引き出すガイド線
+pub trait RulerRenderer {
+    /// ガイド線の追加・移動・削除（戻り値は (ホバー中, ドラッグ中)）
+    fn handle_guide_interactions(
+        &mut self,
+        ui: &mut egui::Ui,
+        rect: egui::Rect,
+        origin: egui::Pos2,
+        pointer_pos: Option<egui::Pos2>,
+    ) -> (bool, bool);
+
+    fn render_rulers_and_guides(&mut self, painter: &egui::Painter, rect: egui::Rect, origin: egui::Pos2);
+}
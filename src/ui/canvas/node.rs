@@ -13,9 +13,12 @@ impl App {
         screen_rects: &HashMap<PersonId, egui::Rect>,
     ) -> Option<NodeRenderInput> {
         let rect = screen_rects.get(&node.id).copied()?;
-        let is_selected = self.person_editor.selected == Some(node.id);
-        let is_multi_selected = self.person_editor.selected_ids.contains(&node.id);
+        // 「画像としてコピー」で撮るフレームでは選択を強調しない
+        let capturing = self.is_capturing_canvas_image();
+        let is_selected = !capturing && self.person_editor.selected == Some(node.id);
+        let is_multi_selected = !capturing && self.person_editor.selected_ids.contains(&node.id);
         let is_dragging = self.canvas.dragging_node == Some(node.id);
+        let is_dimmed = self.canvas.dimmed_persons.contains(&node.id);
 
         let person = self.tree.persons.get(&node.id);
 
@@ -26,7 +29,8 @@ impl App {
             is_multi_selected,
             is_dragging,
             person,
-        ))
+        )
+        .with_dimmed(is_dimmed))
     }
 }
 
@@ -44,6 +48,7 @@ impl NodeRenderer for App {
             .collect();
 
         let node_color_theme = node_color_theme_from_preset(self.ui.node_color_theme);
+        let capturing = self.is_capturing_canvas_image();
         let mut node_painter = NodePainter::new_with_theme(
             ui,
             painter,
@@ -52,11 +57,27 @@ impl NodeRenderer for App {
             self.ui.language,
             &mut self.canvas.photo_texture_cache,
             node_color_theme,
-        );
+        )
+        .with_step_relations(self.ui.show_step_relations)
+        .with_life_years(self.ui.show_life_years)
+        .with_presumed_deceased(self.ui.presumed_deceased_threshold())
+        .with_detail_level(self.ui.semantic_zoom.detail_level(self.canvas.zoom))
+        .with_other_gender_color(egui::Color32::from_rgb(
+            self.ui.other_gender_color[0],
+            self.ui.other_gender_color[1],
+            self.ui.other_gender_color[2],
+        ))
+        .with_node_style(self.tree.node_style)
+        .with_hover_card(!capturing);
 
         for input in &render_inputs {
             node_painter.draw_node(input);
         }
+
+        if let Some(person_id) = node_painter.clicked_card_link() {
+            self.select_single_person(person_id);
+            self.center_on_person(person_id);
+        }
     }
 }
 
@@ -2,10 +2,15 @@ use crate::app::App;
 use crate::core::tree::PersonId;
 use crate::core::layout::LayoutEngine;
 use crate::core::i18n::Texts;
+use crate::core::person_card::PersonCard;
+use crate::core::slideshow::SlideshowSource;
 use crate::ui::{LogLevel, SideTab};
 use super::NodeInteractionHandler;
 use std::collections::HashMap;
 
+/// ガイド線に吸着する距離（画面上のピクセル）
+const GUIDE_SNAP_DISTANCE: f32 = 8.0;
+
 impl NodeInteractionHandler for App {
     fn handle_node_interactions(
         &mut self,
@@ -24,21 +29,46 @@ impl NodeInteractionHandler for App {
         for n in nodes {
             if let Some(r) = screen_rects.get(&n.id) {
                 let node_id = ui.id().with(n.id);
-                let node_response = ui.interact(*r, node_id, egui::Sense::click_and_drag());
-                
+                let node_response = ui.interact(*r, node_id, self.canvas_item_sense());
+
+                // スクリーンリーダー向けに名前・日付・家族をまとめた説明を付ける
+                // （egui が説明を必要とするフレームでだけカードを組み立てる）
+                let lang = self.ui.language;
+                let is_selected = self.person_editor.selected == Some(n.id);
+                node_response.widget_info(|| {
+                    let label = PersonCard::build(&self.tree, n.id, lang, self.ui.show_step_relations, self.ui.deceased_presumption().current_year)
+                        .map(|card| card.accessible_label(lang))
+                        .unwrap_or_default();
+                    egui::WidgetInfo::selected(egui::WidgetType::Button, true, is_selected, label)
+                });
+                // Tab キーでフォーカスが移ったノードを画面内に表示する
+                if node_response.gained_focus() && ui.input(|i| i.key_pressed(egui::Key::Tab)) {
+                    self.center_on_person(n.id);
+                }
+                if node_response.has_focus() {
+                    let stroke = ui.visuals().selection.stroke;
+                    ui.painter().rect_stroke(r.expand(3.0), 4.0, stroke, egui::StrokeKind::Outside);
+                }
+
                 if node_response.hovered() {
                     node_hovered = true;
                 }
                 
-                if node_response.drag_started() {
+                // ロックされた人物は選択・編集はできるがドラッグでは動かさない
+                let locked = self.tree.persons.get(&n.id).is_some_and(|person| person.locked);
+                if node_response.drag_started() && locked {
+                    self.toasts.info(Texts::get("person_locked_hint", self.ui.language));
+                }
+
+                if node_response.drag_started() && !locked {
                     // 複数選択されたノードのドラッグ開始
                     if !self.person_editor.selected_ids.is_empty() && 
                        self.person_editor.selected_ids.contains(&n.id) {
-                        // 複数選択されたノードすべての初期位置を記録
+                        // 複数選択されたノードすべての初期位置を記録（ロックされた人物は動かさない）
                         self.canvas.multi_drag_starts.clear();
                         let mut names = Vec::new();
                         for id in &self.person_editor.selected_ids {
-                            if let Some(person) = self.tree.persons.get(id) {
+                            if let Some(person) = self.tree.persons.get(id).filter(|person| !person.locked) {
                                 self.canvas.multi_drag_starts.insert(*id, person.position);
                                 names.push(person.name.clone());
                             }
@@ -76,11 +106,21 @@ impl NodeInteractionHandler for App {
                         let delta = (pos - start) / self.canvas.zoom;
                         
                         // ドラッグ開始時の位置からの累積移動量を使用
-                        for (id, start_pos) in &self.canvas.multi_drag_starts {
-                            if let Some(person) = self.tree.persons.get_mut(id) {
-                                let new_x = start_pos.0 + delta.x;
-                                let new_y = start_pos.1 + delta.y;
-                                person.position = (new_x, new_y);
+                        let snap_while_dragging = self.canvas.grid.snap_while_dragging;
+                        let moved: Vec<(PersonId, (f32, f32))> = self
+                            .canvas
+                            .multi_drag_starts
+                            .iter()
+                            .map(|(id, start_pos)| {
+                                let position = (start_pos.0 + delta.x, start_pos.1 + delta.y);
+                                let position =
+                                    if snap_while_dragging { self.snap_to_canvas_grid(position, origin) } else { position };
+                                (*id, position)
+                            })
+                            .collect();
+                        for (id, position) in moved {
+                            if let Some(person) = self.tree.persons.get_mut(&id) {
+                                person.position = position;
                             }
                         }
                     }
@@ -123,31 +163,29 @@ impl NodeInteractionHandler for App {
                         }
                     }
                     
-                    if self.canvas.show_grid {
-                        // 複数選択されている場合は、すべてのノードをグリッドにスナップ
-                        if !self.canvas.multi_drag_starts.is_empty() {
-                            for id in self.canvas.multi_drag_starts.keys() {
-                                if let Some(person) = self.tree.persons.get_mut(id) {
-                                    let (x, y) = person.position;
-                                    let relative_pos = egui::pos2(x - origin.x, y - origin.y);
-                                    let snapped_rel = LayoutEngine::snap_to_grid(relative_pos, self.canvas.grid_size);
-                                    
-                                    let snapped_x = origin.x + snapped_rel.x;
-                                    let snapped_y = origin.y + snapped_rel.y;
-                                    
-                                    person.position = (snapped_x, snapped_y);
-                                }
+                    // 複数選択されている場合は、すべてのノードをグリッドにスナップ
+                    let dropped: Vec<PersonId> = if self.canvas.multi_drag_starts.is_empty() {
+                        vec![n.id]
+                    } else {
+                        self.canvas.multi_drag_starts.keys().copied().collect()
+                    };
+                    for id in &dropped {
+                        if let Some(position) = self.tree.persons.get(id).map(|person| person.position) {
+                            let snapped = self.snap_to_canvas_grid(position, origin);
+                            if let Some(person) = self.tree.persons.get_mut(id) {
+                                person.position = snapped;
                             }
-                        } else {
-                            if let Some(person) = self.tree.persons.get_mut(&n.id) {
+                        }
+                    }
+                    // 定規を表示中は、ガイド線の近くに置いた人物をガイド線にそろえる
+                    if self.canvas.show_rulers && !self.tree.guides.is_empty() {
+                        let threshold = GUIDE_SNAP_DISTANCE / self.canvas.zoom;
+                        for id in &dropped {
+                            if let Some(person) = self.tree.persons.get_mut(id) {
                                 let (x, y) = person.position;
                                 let relative_pos = egui::pos2(x - origin.x, y - origin.y);
-                                let snapped_rel = LayoutEngine::snap_to_grid(relative_pos, self.canvas.grid_size);
-                                
-                                let snapped_x = origin.x + snapped_rel.x;
-                                let snapped_y = origin.y + snapped_rel.y;
-                                
-                                person.position = (snapped_x, snapped_y);
+                                let snapped_rel = LayoutEngine::snap_to_guides(relative_pos, &self.tree.guides, threshold);
+                                person.position = (origin.x + snapped_rel.x, origin.y + snapped_rel.y);
                             }
                         }
                     }
@@ -169,17 +207,7 @@ impl NodeInteractionHandler for App {
                             // 最後の選択を更新
                             if let Some(last_id) = self.person_editor.selected_ids.last() {
                                 self.person_editor.selected = Some(*last_id);
-                                if let Some(person) = self.tree.persons.get(last_id) {
-                                    self.person_editor.new_name = person.name.clone();
-                                    self.person_editor.new_gender = person.gender;
-                                    self.person_editor.new_birth = person.birth.clone().unwrap_or_default();
-                                    self.person_editor.new_memo = person.memo.clone();
-                                    self.person_editor.new_deceased = person.deceased;
-                                    self.person_editor.new_death = person.death.clone().unwrap_or_default();
-                                    self.person_editor.new_photo_path = person.photo_path.clone().unwrap_or_default();
-                                    self.person_editor.new_display_mode = person.display_mode;
-                                    self.person_editor.new_photo_scale = person.photo_scale;
-                                }
+                                self.load_selected_person_into_form(*last_id);
                             } else {
                                 self.person_editor.selected = None;
                             }
@@ -192,17 +220,7 @@ impl NodeInteractionHandler for App {
                             let lang = self.ui.language;
                             let t = |key: &str| Texts::get(key, lang);
                             self.log.add(format!("{}: {} ({} {}{})", t("log_node_added_to_selection"), person_name, t("log_total"), self.person_editor.selected_ids.len(), t("count_suffix")), LogLevel::Debug);
-                            if let Some(person) = self.tree.persons.get(&n.id) {
-                                self.person_editor.new_name = person.name.clone();
-                                self.person_editor.new_gender = person.gender;
-                                self.person_editor.new_birth = person.birth.clone().unwrap_or_default();
-                                self.person_editor.new_memo = person.memo.clone();
-                                self.person_editor.new_deceased = person.deceased;
-                                self.person_editor.new_death = person.death.clone().unwrap_or_default();
-                                self.person_editor.new_photo_path = person.photo_path.clone().unwrap_or_default();
-                                self.person_editor.new_display_mode = person.display_mode;
-                                self.person_editor.new_photo_scale = person.photo_scale;
-                            }
+                            self.load_selected_person_into_form(n.id);
                         }
                     } else {
                         // Ctrlキーが押されていない場合は単一選択
@@ -214,19 +232,31 @@ impl NodeInteractionHandler for App {
                         let lang = self.ui.language;
                         let t = |key: &str| Texts::get(key, lang);
                         self.log.add(format!("{}: {}", t("log_node_selected"), person_name), LogLevel::Debug);
-                        if let Some(person) = self.tree.persons.get(&n.id) {
-                            self.person_editor.new_name = person.name.clone();
-                            self.person_editor.new_gender = person.gender;
-                            self.person_editor.new_birth = person.birth.clone().unwrap_or_default();
-                            self.person_editor.new_memo = person.memo.clone();
-                            self.person_editor.new_deceased = person.deceased;
-                            self.person_editor.new_death = person.death.clone().unwrap_or_default();
-                            self.person_editor.new_photo_path = person.photo_path.clone().unwrap_or_default();
-                            self.person_editor.new_display_mode = person.display_mode;
-                            self.person_editor.new_photo_scale = person.photo_scale;
-                        }
+                        self.load_selected_person_into_form(n.id);
                     }
                 }
+
+                node_response.context_menu(|ui| {
+                    if ui.button(Texts::get("slideshow", self.ui.language)).clicked() {
+                        self.slideshow.open(SlideshowSource::Person(n.id));
+                        ui.close();
+                    }
+                    let pinned = self.tree.persons.get(&n.id).is_some_and(|person| person.pinned);
+                    let pin_label = Texts::get(if pinned { "unpin_person" } else { "pin_person" }, self.ui.language);
+                    if ui.add_enabled(!self.ui.read_only, egui::Button::new(pin_label)).clicked() {
+                        if let Some(person) = self.tree.persons.get_mut(&n.id) {
+                            person.pinned = !pinned;
+                        }
+                        ui.close();
+                    }
+                    let lock_label = Texts::get(if locked { "unlock_person" } else { "lock_person" }, self.ui.language);
+                    if ui.add_enabled(!self.ui.read_only, egui::Button::new(lock_label)).clicked() {
+                        if let Some(person) = self.tree.persons.get_mut(&n.id) {
+                            person.locked = !locked;
+                        }
+                        ui.close();
+                    }
+                });
             }
         }
         
@@ -1,15 +1,28 @@
+use chrono::Datelike;
 use eframe::egui;
 
 use crate::app::NODE_CORNER_RADIUS;
-use crate::core::i18n::Language;
-use crate::core::layout::LayoutEngine;
-use crate::core::tree::{FamilyTree, Gender, Person, PersonDisplayMode, PersonId};
+use crate::core::i18n::{Language, Texts};
+use crate::core::layout::{LayoutEngine, NodeDetailLevel, LIFE_YEARS_LINE_HEIGHT, MAX_NAME_LINES, NAME_LINE_HEIGHT, NAME_PADDING_H};
+use crate::core::person_card::PersonCard;
+use crate::core::tree::{FamilyTree, Gender, NodeStyle, Person, PersonDisplayMode, PersonId};
 use crate::infrastructure::PhotoTextureCache;
 use crate::ui::NodeColorThemePreset;
 
 const NAME_AREA_HEIGHT: f32 = 30.0;
 
-const GENDER_VARIANT_COUNT: usize = 3;
+/// ホバーカードの写真の最大サイズ
+const HOVER_CARD_PHOTO_SIZE: egui::Vec2 = egui::vec2(96.0, 96.0);
+
+/// 円形アバターの輪の太さ（ズーム1.0のとき）
+const AVATAR_RING_WIDTH: f32 = 4.0;
+/// 円形アバターの円周の分割数
+const AVATAR_CIRCLE_SEGMENTS: u32 = 48;
+
+/// 絞り込みに一致しない人物に重ねる背景色の不透明度
+const DIMMED_NODE_OVERLAY_OPACITY: f32 = 0.75;
+
+const GENDER_VARIANT_COUNT: usize = 4;
 
 pub struct NodeColorTheme {
     base_fill: [egui::Color32; GENDER_VARIANT_COUNT],
@@ -23,21 +36,31 @@ pub struct NodeColorTheme {
     default_stroke_width: f32,
 }
 
+impl NodeColorTheme {
+    /// 選択状態でないノードの性別ごとの塗りつぶし色
+    pub fn base_fill_for(&self, gender: &Gender) -> egui::Color32 {
+        self.base_fill[NodePainter::gender_index(gender)]
+    }
+}
+
 pub const DEFAULT_NODE_COLOR_THEME: NodeColorTheme = NodeColorTheme {
     base_fill: [
         egui::Color32::from_rgb(173, 216, 230),
         egui::Color32::from_rgb(255, 182, 193),
         egui::Color32::from_rgb(245, 245, 245),
+        egui::Color32::from_rgb(220, 200, 240),
     ],
     selected_fill: [
         egui::Color32::from_rgb(200, 235, 255),
         egui::Color32::from_rgb(255, 220, 230),
         egui::Color32::from_rgb(200, 230, 255),
+        egui::Color32::from_rgb(235, 220, 250),
     ],
     multi_selected_fill: [
         egui::Color32::from_rgb(190, 225, 245),
         egui::Color32::from_rgb(255, 210, 220),
         egui::Color32::from_rgb(225, 240, 255),
+        egui::Color32::from_rgb(228, 212, 245),
     ],
     dragging_fill: egui::Color32::from_rgb(255, 220, 180),
     selected_stroke: egui::Color32::from_rgb(0, 100, 200),
@@ -52,16 +75,19 @@ pub const HIGH_CONTRAST_NODE_COLOR_THEME: NodeColorTheme = NodeColorTheme {
         egui::Color32::from_rgb(140, 200, 255),
         egui::Color32::from_rgb(255, 155, 200),
         egui::Color32::from_rgb(230, 230, 230),
+        egui::Color32::from_rgb(200, 160, 240),
     ],
     selected_fill: [
         egui::Color32::from_rgb(80, 170, 255),
         egui::Color32::from_rgb(255, 100, 170),
         egui::Color32::from_rgb(190, 220, 255),
+        egui::Color32::from_rgb(170, 110, 230),
     ],
     multi_selected_fill: [
         egui::Color32::from_rgb(120, 185, 255),
         egui::Color32::from_rgb(255, 130, 185),
         egui::Color32::from_rgb(210, 235, 255),
+        egui::Color32::from_rgb(185, 135, 235),
     ],
     dragging_fill: egui::Color32::from_rgb(255, 190, 120),
     selected_stroke: egui::Color32::from_rgb(0, 60, 160),
@@ -87,6 +113,8 @@ pub struct NodeRenderInput {
     pub gender: Gender,
     pub display_mode: Option<PersonDisplayMode>,
     pub photo_path: Option<String>,
+    /// 絞り込みに一致しないため薄く表示する
+    pub is_dimmed: bool,
 }
 
 impl NodeRenderInput {
@@ -98,7 +126,7 @@ impl NodeRenderInput {
         is_dragging: bool,
         person: Option<&Person>,
     ) -> Self {
-        let gender = person.map(|person| person.gender).unwrap_or(Gender::Unknown);
+        let gender = person.map(|person| person.gender.clone()).unwrap_or(Gender::Unknown);
         let display_mode = person.map(|person| person.display_mode);
         let photo_path = person.and_then(|person| person.photo_path.clone());
 
@@ -111,8 +139,14 @@ impl NodeRenderInput {
             gender,
             display_mode,
             photo_path,
+            is_dimmed: false,
         }
     }
+
+    pub fn with_dimmed(mut self, is_dimmed: bool) -> Self {
+        self.is_dimmed = is_dimmed;
+        self
+    }
 }
 
 struct NodeVisualStyle {
@@ -129,6 +163,16 @@ pub struct NodePainter<'a> {
     language: Language,
     photo_texture_cache: &'a mut PhotoTextureCache,
     color_theme: &'static NodeColorTheme,
+    show_step_relations: bool,
+    show_life_years: bool,
+    other_gender_color: Option<egui::Color32>,
+    presumed_deceased_threshold: Option<u32>,
+    current_year: i32,
+    detail_level: NodeDetailLevel,
+    node_style: NodeStyle,
+    show_hover_card: bool,
+    /// ホバーカードで家族のリンクがクリックされた人物
+    clicked_card_link: Option<PersonId>,
 }
 
 impl<'a> NodePainter<'a> {
@@ -149,33 +193,116 @@ impl<'a> NodePainter<'a> {
             language,
             photo_texture_cache,
             color_theme,
+            show_step_relations: false,
+            show_life_years: false,
+            other_gender_color: None,
+            presumed_deceased_threshold: None,
+            current_year: chrono::Local::now().year(),
+            detail_level: NodeDetailLevel::Full,
+            node_style: NodeStyle::Box,
+            show_hover_card: true,
+            clicked_card_link: None,
         }
     }
 
+    /// 写真付きノードの描き方（四角形・円形アバター）を設定
+    pub fn with_node_style(mut self, node_style: NodeStyle) -> Self {
+        self.node_style = node_style;
+        self
+    }
+
+    /// マウスを乗せた人物のカードを表示するかを設定（画像としてコピーするフレームでは出さない）
+    pub fn with_hover_card(mut self, show_hover_card: bool) -> Self {
+        self.show_hover_card = show_hover_card;
+        self
+    }
+
+    /// ズーム倍率に応じた描画内容の詳しさを設定
+    pub fn with_detail_level(mut self, detail_level: NodeDetailLevel) -> Self {
+        self.detail_level = detail_level;
+        self
+    }
+
+    /// ツールチップに導出した継親・継子を表示するかを設定
+    pub fn with_step_relations(mut self, show_step_relations: bool) -> Self {
+        self.show_step_relations = show_step_relations;
+        self
+    }
+
+    /// 名前の下に生没年を表示するかを設定（ノードの高さはレイアウト側で確保する）
+    pub fn with_life_years(mut self, show_life_years: bool) -> Self {
+        self.show_life_years = show_life_years;
+        self
+    }
+
+    /// 「その他」の性別のノード色をテーマの既定色から差し替える
+    pub fn with_other_gender_color(mut self, color: egui::Color32) -> Self {
+        self.other_gender_color = Some(color);
+        self
+    }
+
+    /// 出生から指定年数以上経過した人物を死亡と推定して表示する
+    pub fn with_presumed_deceased(mut self, threshold_years: Option<u32>) -> Self {
+        self.presumed_deceased_threshold = threshold_years;
+        self
+    }
+
+    fn is_presumed_deceased(&self, person_id: PersonId) -> bool {
+        let Some(threshold_years) = self.presumed_deceased_threshold else {
+            return false;
+        };
+        self.tree
+            .persons
+            .get(&person_id)
+            .is_some_and(|person| person.is_presumed_deceased(threshold_years, self.current_year))
+    }
+
     pub fn draw_node(&mut self, input: &NodeRenderInput) {
         let visual_style = self.resolve_node_visual_style(input);
 
-        self.draw_frame(input.rect, &visual_style);
-        self.draw_person_content(input);
-        self.draw_tooltip(input);
+        if let Some(photo_path) = self.circular_avatar_photo(input) {
+            self.draw_circular_avatar(input, photo_path, &visual_style);
+        } else {
+            self.draw_frame(input.rect, &visual_style);
+            self.draw_person_content(input);
+        }
+        self.draw_presumed_deceased_marker(input);
+        self.draw_pinned_marker(input);
+        self.draw_badges(input);
+        self.draw_dimmed_overlay(input);
+        if self.show_hover_card {
+            self.draw_tooltip(input);
+        }
     }
 
-    fn gender_index(gender: Gender) -> usize {
+    /// 絞り込みに一致しない人物の上に背景色を重ねて薄く見せる
+    fn draw_dimmed_overlay(&self, input: &NodeRenderInput) {
+        if !input.is_dimmed {
+            return;
+        }
+        let overlay = self.ui.visuals().panel_fill.gamma_multiply(DIMMED_NODE_OVERLAY_OPACITY);
+        self.painter.rect_filled(input.rect.expand(2.0), 4.0, overlay);
+    }
+
+    fn gender_index(gender: &Gender) -> usize {
         match gender {
             Gender::Male => 0,
             Gender::Female => 1,
             Gender::Unknown => 2,
+            Gender::Other(_) => 3,
         }
     }
 
     fn resolve_node_visual_style(&self, input: &NodeRenderInput) -> NodeVisualStyle {
-        let gender_index = Self::gender_index(input.gender);
+        let gender_index = Self::gender_index(&input.gender);
         let fill_color = if input.is_dragging {
             self.color_theme.dragging_fill
         } else if input.is_selected {
             self.color_theme.selected_fill[gender_index]
         } else if input.is_multi_selected {
             self.color_theme.multi_selected_fill[gender_index]
+        } else if let (Gender::Other(_), Some(color)) = (&input.gender, self.other_gender_color) {
+            color
         } else {
             self.color_theme.base_fill[gender_index]
         };
@@ -212,16 +339,69 @@ impl<'a> NodePainter<'a> {
     }
 
     fn draw_person_content(&mut self, input: &NodeRenderInput) {
-        if input.display_mode == Some(PersonDisplayMode::NameAndPhoto) {
-            if let Some(photo_path) = input.photo_path.as_deref() {
-                if !photo_path.is_empty() {
-                    self.draw_photo_and_name(input.rect, input.person_id, photo_path);
-                    return;
-                }
+        match self.detail_level {
+            NodeDetailLevel::Block => return,
+            NodeDetailLevel::NameOnly => {
+                self.draw_person_name(input.rect.center(), input.rect.width(), input.person_id, MAX_NAME_LINES);
+                return;
             }
+            NodeDetailLevel::NameAndDates | NodeDetailLevel::Full => {}
         }
 
-        self.draw_person_name(input.rect.center(), input.person_id);
+        if self.detail_level == NodeDetailLevel::Full
+            && let Some(display_mode) = input.display_mode
+            && display_mode.shows_photo()
+            && let Some(photo_path) = input.photo_path.as_deref()
+            && !photo_path.is_empty()
+        {
+            if display_mode == PersonDisplayMode::PhotoOnly {
+                self.draw_photo_only(input.rect, input.person_id, photo_path);
+            } else {
+                self.draw_photo_and_name(input.rect, input.person_id, photo_path);
+            }
+            return;
+        }
+
+        self.draw_person_name_and_dates(input.rect, input.person_id);
+    }
+
+    fn draw_person_name_and_dates(&self, rect: egui::Rect, person_id: PersonId) {
+        let center = rect.center();
+        let life_span = self
+            .tree
+            .persons
+            .get(&person_id)
+            .filter(|_| self.show_life_years)
+            .and_then(|person| self.life_years_label(person));
+        let Some(life_span) = life_span else {
+            self.draw_person_name(center, rect.width(), person_id, MAX_NAME_LINES);
+            return;
+        };
+
+        // 名前（折り返した行を含む）と生没年の行がノードの中央に来るように上下にずらす
+        // 行の間隔は文字と同じ倍率で伸縮させる
+        let scale = self.text_scale();
+        let offset = LIFE_YEARS_LINE_HEIGHT / 2.0 * scale;
+        let name_lines = self.draw_person_name(center - egui::vec2(0.0, offset), rect.width(), person_id, MAX_NAME_LINES);
+        let name_half_height = (name_lines - 1) as f32 * NAME_LINE_HEIGHT / 2.0;
+        self.painter.text(
+            center + egui::vec2(0.0, (14.0 + name_half_height) * scale - offset),
+            egui::Align2::CENTER_CENTER,
+            life_span,
+            egui::FontId::proportional(9.0 * scale),
+            egui::Color32::DARK_GRAY,
+        );
+    }
+
+    /// ノードに表示する生没年（存命で生年だけ分かる人は「b. 1995」の形）
+    fn life_years_label(&self, person: &Person) -> Option<String> {
+        match (person.birth_year(), person.death_year()) {
+            (Some(birth), None) if !person.deceased => {
+                let t = |key: &str| Texts::get(key, self.language);
+                Some(format!("{}{birth}{}", t("life_years_born_prefix"), t("life_years_born_suffix")))
+            }
+            _ => person.life_span_label(),
+        }
     }
 
     fn draw_photo_and_name(&mut self, rect: egui::Rect, person_id: PersonId, photo_path: &str) {
@@ -229,7 +409,7 @@ impl<'a> NodePainter<'a> {
         let photo_rect =
             egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), photo_height));
 
-        if let Some(texture) = self.photo_texture_cache.get_or_load(self.ui.ctx(), photo_path) {
+        if let Some(texture) = self.photo_texture_cache.get_or_load_thumbnail(self.ui.ctx(), photo_path) {
             self.painter.image(
                 texture.id(),
                 photo_rect,
@@ -242,27 +422,279 @@ impl<'a> NodePainter<'a> {
             rect.center().x,
             rect.min.y + photo_height + NAME_AREA_HEIGHT / 2.0,
         );
-        self.draw_person_name(text_center, person_id);
+        // 写真の幅に収まらない名前は省略する（全体はツールチップに出る）
+        self.draw_person_name(text_center, rect.width(), person_id, 1);
+    }
+
+    /// 写真だけを描き、マウスを乗せている間だけ写真の下端に名前を重ねる
+    fn draw_photo_only(&mut self, rect: egui::Rect, person_id: PersonId, photo_path: &str) {
+        if let Some(texture) = self.photo_texture_cache.get_or_load_thumbnail(self.ui.ctx(), photo_path) {
+            self.painter.image(
+                texture.id(),
+                rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        if self.ui.rect_contains_pointer(rect) {
+            self.draw_hover_name_strip(rect, person_id);
+        }
+    }
+
+    fn draw_hover_name_strip(&self, rect: egui::Rect, person_id: PersonId) {
+        let strip_height = (NAME_AREA_HEIGHT * self.zoom.clamp(0.7, 1.2)).min(rect.height());
+        let strip = egui::Rect::from_min_max(egui::pos2(rect.min.x, rect.max.y - strip_height), rect.max);
+        self.painter.rect_filled(strip, 0.0, egui::Color32::from_black_alpha(160));
+        let label = LayoutEngine::person_label(self.tree, person_id);
+        let font_id = egui::FontId::proportional(14.0 * self.zoom.clamp(0.7, 1.2));
+        let max_width = (rect.width() - NAME_PADDING_H * self.zoom).max(0.0);
+        let line = self
+            .painter
+            .fonts_mut(|fonts| LayoutEngine::wrap_name_measured(&label, max_width, 1, |c| fonts.glyph_width(&font_id, c)));
+        self.painter.text(strip.center(), egui::Align2::CENTER_CENTER, line.concat(), font_id, egui::Color32::WHITE);
     }
 
-    fn draw_person_name(&self, center: egui::Pos2, person_id: PersonId) {
+    /// 円形アバターで描く場合はその写真のパスを返す（写真付き表示かつ全情報を描く倍率のときのみ）
+    fn circular_avatar_photo<'i>(&self, input: &'i NodeRenderInput) -> Option<&'i str> {
+        if self.node_style != NodeStyle::CircularAvatar
+            || self.detail_level != NodeDetailLevel::Full
+            || !input.display_mode.is_some_and(PersonDisplayMode::shows_photo)
+        {
+            return None;
+        }
+        input.photo_path.as_deref().filter(|path| !path.is_empty())
+    }
+
+    /// 写真を円形に切り抜き、性別の色の輪で囲んで名前を下に描く
+    fn draw_circular_avatar(&mut self, input: &NodeRenderInput, photo_path: &str, style: &NodeVisualStyle) {
+        let rect = input.rect;
+        let photo_only = input.display_mode == Some(PersonDisplayMode::PhotoOnly);
+        let name_area_height = if photo_only { 0.0 } else { NAME_AREA_HEIGHT };
+        let photo_area = egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), rect.height() - name_area_height));
+        let ring_width = (AVATAR_RING_WIDTH * self.zoom).max(1.5);
+        let radius = (photo_area.width().min(photo_area.height()) / 2.0 - ring_width).max(1.0);
+        let center = photo_area.center();
+
+        match self.photo_texture_cache.get_or_load_thumbnail(self.ui.ctx(), photo_path) {
+            Some(texture) => {
+                self.painter.add(Self::circular_image_mesh(&texture, center, radius));
+            }
+            None => {
+                self.painter.circle_filled(center, radius, style.fill_color);
+            }
+        }
+        self.painter.circle_stroke(center, radius + ring_width / 2.0, egui::Stroke::new(ring_width, style.fill_color));
+        self.painter.circle_stroke(center, radius + ring_width, egui::Stroke::new(style.stroke_width, style.stroke_color));
+
+        if photo_only {
+            if self.ui.rect_contains_pointer(rect) {
+                self.draw_hover_name_strip(rect, input.person_id);
+            }
+            return;
+        }
+        let name_center = egui::pos2(rect.center().x, photo_area.max.y + NAME_AREA_HEIGHT / 2.0);
+        self.draw_person_name(name_center, rect.width(), input.person_id, 1);
+    }
+
+    /// テクスチャの中央の正方形を円に貼り付けたメッシュ
+    fn circular_image_mesh(texture: &egui::TextureHandle, center: egui::Pos2, radius: f32) -> egui::Mesh {
+        let [width, height] = texture.size().map(|size| size.max(1) as f32);
+        let (u_half, v_half) = if width > height {
+            (height / width / 2.0, 0.5)
+        } else {
+            (0.5, width / height / 2.0)
+        };
+
+        let mut mesh = egui::Mesh::with_texture(texture.id());
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos: center,
+            uv: egui::pos2(0.5, 0.5),
+            color: egui::Color32::WHITE,
+        });
+        for i in 0..=AVATAR_CIRCLE_SEGMENTS {
+            let angle = i as f32 / AVATAR_CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos: center + egui::vec2(cos, sin) * radius,
+                uv: egui::pos2(0.5 + cos * u_half, 0.5 + sin * v_half),
+                color: egui::Color32::WHITE,
+            });
+        }
+        for i in 1..=AVATAR_CIRCLE_SEGMENTS {
+            mesh.add_triangle(0, i, i + 1);
+        }
+        mesh
+    }
+
+    /// 名前をノードの幅（画面上の大きさ）に合わせて最大 max_lines 行で描き、描いた行数を返す
+    fn draw_person_name(&self, center: egui::Pos2, width: f32, person_id: PersonId, max_lines: usize) -> usize {
         let text = LayoutEngine::person_label(self.tree, person_id);
+        // 行数はレイアウトがノードの高さに割り当てた分までにし、折り返し位置は実際に描く大きさのフォントで測って決める
+        let max_lines = max_lines.min(LayoutEngine::name_line_count(&text));
+        let scale = self.text_scale();
+        let font_id = egui::FontId::proportional(14.0 * scale);
+        let max_width = (width - NAME_PADDING_H * self.zoom).max(0.0);
+        let lines = self.painter.fonts_mut(|fonts| {
+            LayoutEngine::wrap_name_measured(&text, max_width, max_lines, |c| fonts.glyph_width(&font_id, c))
+        });
+        let line_height = NAME_LINE_HEIGHT * scale;
+        let first_y = center.y - (lines.len().max(1) - 1) as f32 * line_height / 2.0;
+        for (index, line) in lines.iter().enumerate() {
+            self.painter.text(
+                egui::pos2(center.x, first_y + index as f32 * line_height),
+                egui::Align2::CENTER_CENTER,
+                line,
+                font_id.clone(),
+                egui::Color32::BLACK,
+            );
+        }
+        lines.len().max(1)
+    }
+
+    /// ノード内の文字の倍率（小さくしすぎず大きくしすぎないよう、ズームを一定の範囲に収める）
+    fn text_scale(&self) -> f32 {
+        self.zoom.clamp(0.7, 1.2)
+    }
+
+    /// 自動整列で動かさない人物は左上にピン（ロックされていれば錠前）を表示
+    fn draw_pinned_marker(&self, input: &NodeRenderInput) {
+        let Some(person) = self.tree.persons.get(&input.person_id) else {
+            return;
+        };
+        let marker = if person.locked {
+            "🔒"
+        } else if person.pinned {
+            "📌"
+        } else {
+            return;
+        };
+        if self.detail_level == NodeDetailLevel::Block {
+            return;
+        }
         self.painter.text(
-            center,
-            egui::Align2::CENTER_CENTER,
-            text,
-            egui::FontId::proportional(14.0 * self.zoom.clamp(0.7, 1.2)),
-            egui::Color32::BLACK,
+            input.rect.left_top() + egui::vec2(3.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            marker,
+            egui::FontId::proportional(10.0 * self.zoom.clamp(0.7, 1.2)),
+            egui::Color32::DARK_GRAY,
+        );
+    }
+
+    /// 人物の目印は右下にまとめて表示
+    fn draw_badges(&self, input: &NodeRenderInput) {
+        let Some(person) = self.tree.persons.get(&input.person_id) else {
+            return;
+        };
+        if self.detail_level == NodeDetailLevel::Block || person.badges.is_empty() {
+            return;
+        }
+        let glyphs: String = person.badges.iter().map(|badge| badge.glyph()).collect();
+        self.painter.text(
+            input.rect.right_bottom() + egui::vec2(-3.0, -2.0),
+            egui::Align2::RIGHT_BOTTOM,
+            glyphs,
+            egui::FontId::proportional(10.0 * self.zoom.clamp(0.7, 1.2)),
+            egui::Color32::DARK_GRAY,
         );
     }
 
+    fn draw_presumed_deceased_marker(&self, input: &NodeRenderInput) {
+        if self.detail_level == NodeDetailLevel::Block || !self.is_presumed_deceased(input.person_id) {
+            return;
+        }
+        self.painter.text(
+            input.rect.right_top() + egui::vec2(-3.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            Texts::get("presumed_deceased_marker", self.language),
+            egui::FontId::proportional(10.0 * self.zoom.clamp(0.7, 1.2)),
+            egui::Color32::DARK_GRAY,
+        );
+    }
+
+    /// ホバーカードで家族のリンクがクリックされた人物（描画後に呼び出し側で選択する）
+    pub fn clicked_card_link(&self) -> Option<PersonId> {
+        self.clicked_card_link
+    }
+
     fn draw_tooltip(&mut self, input: &NodeRenderInput) {
         let node_id = self.ui.id().with(input.person_id);
         let node_response = self.ui.interact(input.rect, node_id, egui::Sense::hover());
-        if node_response.hovered() {
-            let tooltip_text =
-                LayoutEngine::person_tooltip(self.tree, input.person_id, self.language);
-            node_response.on_hover_text(tooltip_text);
+        // カード内のリンクを押せるよう、カードにマウスを移している間も表示を続ける（egui が判定する）
+        node_response.on_hover_ui(|ui| {
+            let Some(mut card) = PersonCard::build(self.tree, input.person_id, self.language, self.show_step_relations, self.current_year)
+            else {
+                return;
+            };
+            if self.is_presumed_deceased(input.person_id) {
+                card.details.push(Texts::get("tooltip_presumed_deceased", self.language));
+            }
+            if let Some(person_id) = self.show_hover_card(ui, &card) {
+                self.clicked_card_link = Some(person_id);
+            }
+        });
+    }
+
+    /// 写真・日付・家族へのリンク・タグを並べたカードを描き、クリックされたリンク先を返す
+    fn show_hover_card(&mut self, ui: &mut egui::Ui, card: &PersonCard) -> Option<PersonId> {
+        let t = |key: &str| Texts::get(key, self.language);
+        let mut clicked = None;
+
+        ui.horizontal(|ui| {
+            if let Some(photo_path) = &card.photo_path
+                && let Some(texture) = self.photo_texture_cache.get_or_load_thumbnail(ui.ctx(), photo_path)
+            {
+                ui.add(egui::Image::new(&texture).max_size(HOVER_CARD_PHOTO_SIZE));
+            }
+            ui.vertical(|ui| {
+                ui.strong(&card.name);
+                for line in &card.details {
+                    ui.label(line);
+                }
+            });
+        });
+
+        // 選択しなくてもつながりを確かめられるよう、関係を1行にまとめ（例: "Parents: A, B · Spouses: C · Children: 3"）、
+        // 親・配偶者は名前から移動できるようにする。子は要約と同じく人数だけを示す
+        if let Some(summary) = card.relation_summary(self.language) {
+            ui.separator();
+            let row = ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
+                for (index, group) in card.family.iter().enumerate() {
+                    if index > 0 {
+                        ui.weak("·");
+                    }
+                    ui.weak(format!("{}:", t(group.label_key)));
+                    if group.is_counted() {
+                        ui.weak(group.persons.len().to_string());
+                        continue;
+                    }
+                    for (position, (person_id, name)) in group.persons.iter().enumerate() {
+                        let separator = if position + 1 < group.persons.len() { "," } else { "" };
+                        if ui.link(format!("{name}{separator}")).clicked() {
+                            clicked = Some(*person_id);
+                        }
+                    }
+                }
+            });
+            // 行全体はスクリーンリーダーに要約の1文として伝える
+            row.response
+                .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, summary.clone()));
         }
+
+        if !card.tags.is_empty() {
+            ui.separator();
+            ui.horizontal_wrapped(|ui| {
+                ui.weak(t("tooltip_tags"));
+                for tag in &card.tags {
+                    egui::Frame::new()
+                        .fill(ui.visuals().faint_bg_color)
+                        .corner_radius(4.0)
+                        .inner_margin(egui::Margin::symmetric(4, 1))
+                        .show(ui, |ui| ui.small(tag));
+                }
+            });
+        }
+
+        clicked
     }
-}
\ No newline at end of file
+}
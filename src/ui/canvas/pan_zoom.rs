@@ -1,5 +1,7 @@
 use crate::app::App;
-use crate::ui::PanZoomHandler;
+use crate::core::i18n::Texts;
+use crate::ui::shortcuts::{shortcut_button_label, SHORTCUT_ZOOM_RESET};
+use crate::ui::{PanZoomHandler, CAMERA_ANIMATION_DURATION, CANVAS_ZOOM_PRESETS, MAX_CANVAS_ZOOM, MIN_CANVAS_ZOOM};
 
 impl PanZoomHandler for App {
     fn handle_pan_zoom(
@@ -22,6 +24,7 @@ impl PanZoomHandler for App {
                 let primary_pressed = ui.input(|i| i.pointer.primary_pressed());
                 
                 if primary_pressed && rect.contains(pos) {
+                    self.canvas.camera_animation = None;
                     self.canvas.dragging_pan = true;
                     self.canvas.last_pointer_pos = Some(pos);
                 }
@@ -43,4 +46,106 @@ impl PanZoomHandler for App {
             self.canvas.last_pointer_pos = None;
         }
     }
+
+    fn zoom_at(&mut self, anchor: egui::Pos2, origin: egui::Pos2, factor: f32) {
+        self.canvas.camera_animation = None;
+
+        let old_zoom = self.canvas.zoom;
+        let new_zoom = (old_zoom * factor).clamp(MIN_CANVAS_ZOOM, MAX_CANVAS_ZOOM);
+        // screen = origin + (world - origin) * zoom + pan を anchor で不変に保つ
+        let world_offset = (anchor - origin - self.canvas.pan) / old_zoom;
+        self.canvas.zoom = new_zoom;
+        self.canvas.pan = anchor - origin - world_offset * new_zoom;
+    }
+
+    fn handle_touch_gestures(&mut self, ui: &mut egui::Ui, rect: egui::Rect, origin: egui::Pos2) {
+        let gesture = ui.input(|i| {
+            if let Some(touch) = i.multi_touch() {
+                return rect
+                    .contains(touch.center_pos)
+                    .then_some((touch.center_pos, touch.zoom_delta, touch.translation_delta, true));
+            }
+
+            // Ctrl+ホイールはカーソル中心ズームで処理済み
+            if i.modifiers.ctrl {
+                return None;
+            }
+            let anchor = i.pointer.hover_pos().filter(|pos| rect.contains(*pos))?;
+            let zoom = i.zoom_delta();
+            let translation = i.smooth_scroll_delta;
+            (zoom != 1.0 || translation != egui::Vec2::ZERO).then_some((anchor, zoom, translation, false))
+        });
+
+        let Some((anchor, zoom, translation, multi_touch)) = gesture else {
+            return;
+        };
+
+        // 2本指操作中は1本指ドラッグによるパンを止める
+        if multi_touch {
+            self.canvas.dragging_pan = false;
+            self.canvas.last_pointer_pos = None;
+        }
+
+        if zoom != 1.0 {
+            self.zoom_at(anchor, origin, zoom);
+        }
+        if translation != egui::Vec2::ZERO {
+            self.canvas.camera_animation = None;
+            self.canvas.pan += translation;
+        }
+    }
+
+    fn update_camera_animation(&mut self, ctx: &egui::Context) {
+        let Some(animation) = self.canvas.camera_animation.as_mut() else {
+            return;
+        };
+
+        let now = ctx.input(|i| i.time);
+        let started_at = *animation.started_at.get_or_insert(now);
+        let progress = ((now - started_at) / CAMERA_ANIMATION_DURATION) as f32;
+        let (zoom, pan) = animation.sample(progress);
+        self.canvas.zoom = zoom;
+        self.canvas.pan = pan;
+
+        if progress >= 1.0 {
+            self.canvas.camera_animation = None;
+        } else {
+            ctx.request_repaint();
+        }
+    }
+
+    fn render_zoom_toolbar(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        // 右から左に並べる
+        egui::ComboBox::from_id_salt("canvas_zoom_presets")
+            .selected_text(format!("{:.0}%", self.canvas.zoom * 100.0))
+            .width(80.0)
+            .show_ui(ui, |ui| {
+                for preset in CANVAS_ZOOM_PRESETS {
+                    let current = (self.canvas.zoom - preset).abs() < 0.005;
+                    if ui.selectable_label(current, format!("{:.0}%", preset * 100.0)).clicked() {
+                        self.set_canvas_zoom(preset);
+                    }
+                }
+                ui.separator();
+                if ui.selectable_label(false, t("zoom_fit_width")).clicked() {
+                    self.fit_canvas_to_width();
+                }
+                if ui.selectable_label(false, t("fit_to_view")).clicked() {
+                    self.fit_canvas_to_contents();
+                }
+            })
+            .response
+            .on_hover_text(shortcut_button_label(&t("zoom_reset"), &SHORTCUT_ZOOM_RESET));
+
+        let mut percent = self.canvas.zoom * 100.0;
+        let slider = egui::Slider::new(&mut percent, MIN_CANVAS_ZOOM * 100.0..=MAX_CANVAS_ZOOM * 100.0)
+            .logarithmic(true)
+            .show_value(false);
+        if ui.add(slider).on_hover_text(t("shortcut_zoom")).changed() {
+            self.set_canvas_zoom(percent / 100.0);
+        }
+    }
 }
@@ -1,30 +1,44 @@
 use std::collections::HashMap;
 
 use crate::app::App;
+use crate::core::i18n::Texts;
 use crate::core::layout::LayoutEngine;
 use crate::core::tree::PersonId;
 use crate::infrastructure::read_image_dimensions;
 
-use super::{CanvasRenderer, NodeRenderer, NodeInteractionHandler, PanZoomHandler, EdgeRenderer, FamilyBoxRenderer, EventNodeRenderer, EventRelationRenderer};
+use super::{ArrangePreviewRenderer, CanvasImageCopy, RulerRenderer, AnnotationRenderer, BackgroundRenderer, CanvasFilterBar, CanvasRenderer, NodeRenderer, NodeInteractionHandler, PanZoomHandler, StickyNoteRenderer, EdgeRenderer, FamilyBoxRenderer, EventNodeRenderer, EventRelationRenderer};
 
 impl CanvasRenderer for App {
     fn render_canvas(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                self.render_canvas_filter_bar(ui);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.render_zoom_toolbar(ui);
+                });
+            });
             let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click());
             let pointer_pos = ui.input(|i| i.pointer.interact_pos());
             
             // キャンバス情報を保存
             self.canvas.canvas_rect = rect;
 
-            // ズーム処理
-            ctx.input(|i| {
-                if i.modifiers.ctrl && i.raw_scroll_delta.y.abs() > 0.0 {
-                    let factor = (i.raw_scroll_delta.y / 400.0).exp();
-                    self.canvas.zoom = (self.canvas.zoom * factor).clamp(0.3, 3.0);
-                }
-            });
+            // カメラ移動アニメーション
+            self.update_camera_animation(ctx);
 
-            let painter = ui.painter_at(rect);
+            // 前のフレームで要求した「画像としてコピー」のスクリーンショット
+            self.receive_canvas_screenshot(ctx);
+            self.prepare_canvas_capture();
+
+            // 撮るフレームでは、重なっているウィンドウや通知に隠れないようキャンバスを手前のレイヤーに描く
+            let capturing = self.is_capturing_canvas_image();
+            let painter = if capturing {
+                let painter = ui.painter_at(rect).with_layer_id(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("canvas_image_copy")));
+                painter.rect_filled(rect, 0.0, ui.visuals().panel_fill);
+                painter
+            } else {
+                ui.painter_at(rect)
+            };
 
             let to_screen = |p: egui::Pos2, zoom: f32, pan: egui::Vec2, origin: egui::Pos2| -> egui::Pos2 {
                 let v = (p - origin) * zoom;
@@ -40,9 +54,33 @@ impl CanvasRenderer for App {
             
             // originを保存
             self.canvas.canvas_origin = origin;
-            
+
+            // ズーム処理（カーソル位置を中心に拡大縮小）
+            let scroll_zoom = ctx.input(|i| {
+                (i.modifiers.ctrl && i.raw_scroll_delta.y.abs() > 0.0)
+                    .then(|| (i.raw_scroll_delta.y / 400.0).exp())
+            });
+            if let Some(factor) = scroll_zoom {
+                let anchor = pointer_pos.filter(|pos| rect.contains(*pos)).unwrap_or(rect.center());
+                self.zoom_at(anchor, origin, factor);
+            }
+
+            // ピンチ操作・2本指スクロールによるズームとパン
+            self.handle_touch_gestures(ui, rect, origin);
+
+            // 背景画像は他のすべてのレイヤーより先に描画
+            self.render_canvas_background(ctx, &painter, origin);
+
             if self.canvas.show_grid {
-                LayoutEngine::draw_grid(&painter, rect, origin, self.canvas.zoom, self.canvas.pan, self.canvas.grid_size);
+                LayoutEngine::draw_grid(
+                    &painter,
+                    rect,
+                    origin,
+                    self.canvas.zoom,
+                    self.canvas.pan,
+                    self.canvas.grid_size,
+                    &self.canvas.grid,
+                );
             }
 
             let photo_dimensions: HashMap<PersonId, (u32, u32)> = self
@@ -50,7 +88,7 @@ impl CanvasRenderer for App {
                 .persons
                 .iter()
                 .filter_map(|(person_id, person)| {
-                    if person.display_mode != crate::core::tree::PersonDisplayMode::NameAndPhoto {
+                    if !person.display_mode.shows_photo() {
                         return None;
                     }
 
@@ -62,7 +100,25 @@ impl CanvasRenderer for App {
                 })
                 .collect();
 
-            let nodes = LayoutEngine::compute_layout(&self.tree, origin, &photo_dimensions);
+            let mut nodes = LayoutEngine::compute_layout(&self.tree, &photo_dimensions, self.ui.show_life_years);
+
+            // 人物一覧の絞り込み条件をキャンバスにも適用（一致しない人物とその関係線を隠す）
+            let filter = &self.person_editor.filter;
+            let presumption = self.ui.deceased_presumption();
+            if self.person_editor.apply_filter_to_canvas && filter.is_active() {
+                nodes.retain(|node| {
+                    self.tree
+                        .persons
+                        .get(&node.id)
+                        .is_some_and(|person| filter.matches(&self.tree, person, presumption))
+                });
+            }
+
+            // 選択中の人物を画像にするときは、それ以外の人物とその関係線を写さない
+            let capture_selection = if capturing { self.image_copy_selection() } else { Vec::new() };
+            if !capture_selection.is_empty() {
+                nodes.retain(|node| capture_selection.contains(&node.id));
+            }
 
             let mut screen_rects: HashMap<PersonId, egui::Rect> = HashMap::new();
             for n in &nodes {
@@ -71,16 +127,51 @@ impl CanvasRenderer for App {
                 screen_rects.insert(n.id, egui::Rect::from_min_max(min, max));
             }
 
+            // ガイド線の操作（ノードより優先する）
+            let (guide_hovered, guide_dragged) = self.handle_guide_interactions(ui, rect, origin, pointer_pos);
+
             // ノードのインタラクション処理
             let (node_hovered, any_node_dragged) = self.handle_node_interactions(ui, &nodes, &screen_rects, pointer_pos, origin);
             
             // イベントノード描画（ホバー/ドラッグ状態を先に取得）
-            let (event_hovered, any_event_dragged) = self.render_event_nodes(ui, &painter, &screen_rects, pointer_pos);
+            // 選択中の人物を画像にするときは、イベント・注釈・付箋も写さない
+            let (event_hovered, any_event_dragged) = if capture_selection.is_empty() {
+                self.render_event_nodes(ui, &painter, &screen_rects, pointer_pos)
+            } else {
+                (false, false)
+            };
+
+            // 注釈描画（イベントと同様にホバー/ドラッグ中はパンしない）
+            let (annotation_hovered, any_annotation_dragged) =
+                if capture_selection.is_empty() { self.render_annotations(ui, &painter, pointer_pos) } else { (false, false) };
+            let event_hovered = event_hovered || annotation_hovered || guide_hovered;
+            let any_event_dragged = any_event_dragged || any_annotation_dragged || guide_dragged;
 
             // ノードのない領域でのダブルクリックで全体表示
             if response.double_clicked() && !node_hovered && !event_hovered {
                 self.fit_canvas_to_contents();
             }
+
+            // 右クリックメニュー（クリック位置に家族のひな形を挿入）
+            if response.secondary_clicked()
+                && let Some(pos) = pointer_pos
+            {
+                let world = origin + (pos - origin - self.canvas.pan) / self.canvas.zoom;
+                self.canvas.context_menu_world_pos = Some((world.x, world.y));
+            }
+            // 閲覧モードでは家系図を変更するメニューを出さない
+            if !self.ui.read_only {
+                response.context_menu(|ui| {
+                    let lang = self.ui.language;
+                    let t = |key: &str| Texts::get(key, lang);
+                    if ui.button(t("insert_couple_with_child")).clicked() {
+                        if let Some(center) = self.canvas.context_menu_world_pos {
+                            self.insert_couple_with_child_template(center);
+                        }
+                        ui.close();
+                    }
+                });
+            }
             
             // パン・ズーム処理
             self.handle_pan_zoom(ui, rect, pointer_pos, node_hovered, any_node_dragged, event_hovered, any_event_dragged);
@@ -94,17 +185,24 @@ impl CanvasRenderer for App {
             // ノード描画
             self.render_canvas_nodes(ui, &painter, &nodes, &screen_rects);
 
-            // イベント関係線描画
-            self.render_event_relations(ui, &painter, &screen_rects);
-
-            // ズーム表示
-            painter.text(
-                rect.right_top() + egui::vec2(-10.0, 10.0),
-                egui::Align2::RIGHT_TOP,
-                format!("zoom: {:.2}", self.canvas.zoom),
-                egui::FontId::proportional(12.0),
-                egui::Color32::DARK_GRAY,
-            );
+            if capture_selection.is_empty() {
+                // 付箋描画（ノードの上に重ねる）
+                self.render_sticky_notes(ui, &painter, &screen_rects);
+
+                // イベント関係線描画
+                self.render_event_relations(ui, &painter, &screen_rects);
+            }
+
+            if !capturing {
+                // 定規とガイド線
+                self.render_rulers_and_guides(&painter, rect, origin);
+
+                // 自動整列のプレビュー（すべての要素の上に重ねる）
+                self.render_arrange_preview(ui, &painter, &nodes, &screen_rects, origin);
+            }
+
+            // 「画像としてコピー」（このフレームの描画結果を撮る）
+            self.request_canvas_screenshot(ctx, rect, &screen_rects);
         });
     }
 }
@@ -0,0 +1,212 @@
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::GuideAxis;
+use crate::ui::LogLevel;
+
+use super::RulerRenderer;
+
+/// 定規の幅（画面上のピクセル）
+const RULER_SIZE: f32 = 18.0;
+/// ガイド線をつかめる距離（画面上のピクセル）
+const GUIDE_GRAB_DISTANCE: f32 = 4.0;
+/// 目盛りの数字の間隔の最小値（画面上のピクセル）
+const MIN_LABEL_SPACING: f32 = 60.0;
+/// ガイド線の色
+const GUIDE_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 150, 220);
+
+impl RulerRenderer for App {
+    fn handle_guide_interactions(
+        &mut self,
+        ui: &mut egui::Ui,
+        rect: egui::Rect,
+        origin: egui::Pos2,
+        pointer_pos: Option<egui::Pos2>,
+    ) -> (bool, bool) {
+        if !self.canvas.show_rulers || self.ui.read_only {
+            self.canvas.dragging_guide = None;
+            return (false, false);
+        }
+        let (top_ruler, left_ruler) = ruler_rects(rect);
+        let to_ruler = |pos: egui::Pos2| (pos - origin - self.canvas.pan) / self.canvas.zoom;
+        let (primary_pressed, primary_down) = ui.input(|i| (i.pointer.primary_pressed(), i.pointer.primary_down()));
+
+        // ドラッグ中のガイド線を動かし、定規の上で離したら削除する
+        if let Some(guide_id) = self.canvas.dragging_guide {
+            let Some(axis) = self.tree.guides.iter().find(|g| g.id == guide_id).map(|g| g.axis) else {
+                self.canvas.dragging_guide = None;
+                return (false, false);
+            };
+            if let Some(pos) = pointer_pos {
+                let ruler_pos = to_ruler(pos);
+                let position = match axis {
+                    GuideAxis::Vertical => ruler_pos.x,
+                    GuideAxis::Horizontal => ruler_pos.y,
+                };
+                self.tree.move_guide(guide_id, position.round());
+            }
+            if !primary_down {
+                self.canvas.dragging_guide = None;
+                let lang = self.ui.language;
+                let t = |key: &str| Texts::get(key, lang);
+                let dropped_on_ruler = pointer_pos.is_some_and(|pos| top_ruler.contains(pos) || left_ruler.contains(pos));
+                if dropped_on_ruler {
+                    self.tree.remove_guide(guide_id);
+                }
+                // ドラッグ中の移動はまとめて、ドラッグ前からの変化をひとつの操作として記録する
+                let before = std::mem::take(&mut self.canvas.guides_before_drag);
+                let existed = before.iter().any(|g| g.id == guide_id);
+                let after = std::mem::replace(&mut self.tree.guides, before);
+                if after != self.tree.guides {
+                    let position = after.iter().find(|g| g.id == guide_id).map(|g| g.position);
+                    let label = match position {
+                        None => t("log_guide_removed"),
+                        Some(_) if existed => t("log_guide_moved"),
+                        Some(_) => t("log_guide_added"),
+                    };
+                    let message = match position {
+                        Some(position) => format!("{label}: {position:.0}"),
+                        None => label.clone(),
+                    };
+                    self.apply_undoable(label, |tree| tree.guides = after);
+                    self.log.add(message, LogLevel::Debug);
+                }
+            }
+            return (true, true);
+        }
+
+        let Some(pos) = pointer_pos.filter(|pos| rect.contains(*pos)) else {
+            return (false, false);
+        };
+
+        // 上の定規からは横の線、左の定規からは縦の線を引き出す
+        let ruler_pos = to_ruler(pos);
+        let from_ruler = if top_ruler.contains(pos) {
+            Some((GuideAxis::Horizontal, ruler_pos.y))
+        } else if left_ruler.contains(pos) {
+            Some((GuideAxis::Vertical, ruler_pos.x))
+        } else {
+            None
+        };
+        if let Some((axis, position)) = from_ruler {
+            ui.ctx().set_cursor_icon(cursor_for(axis));
+            if primary_pressed {
+                self.canvas.guides_before_drag = self.tree.guides.clone();
+                self.canvas.dragging_guide = Some(self.tree.add_guide(axis, position.round()));
+                return (true, true);
+            }
+            return (true, false);
+        }
+
+        let hovered_guide = self.tree.guides.iter().find(|guide| {
+            let screen = to_screen(guide.position, origin, self.canvas.zoom, self.canvas.pan);
+            match guide.axis {
+                GuideAxis::Vertical => (pos.x - screen.x).abs() <= GUIDE_GRAB_DISTANCE,
+                GuideAxis::Horizontal => (pos.y - screen.y).abs() <= GUIDE_GRAB_DISTANCE,
+            }
+        });
+        let Some(guide) = hovered_guide else {
+            return (false, false);
+        };
+        ui.ctx().set_cursor_icon(cursor_for(guide.axis));
+        if primary_pressed {
+            self.canvas.dragging_guide = Some(guide.id);
+            self.canvas.guides_before_drag = self.tree.guides.clone();
+            return (true, true);
+        }
+        (true, false)
+    }
+
+    fn render_rulers_and_guides(&mut self, painter: &egui::Painter, rect: egui::Rect, origin: egui::Pos2) {
+        if !self.canvas.show_rulers {
+            return;
+        }
+        let zoom = self.canvas.zoom;
+        let pan = self.canvas.pan;
+
+        for guide in &self.tree.guides {
+            let screen = to_screen(guide.position, origin, zoom, pan);
+            let width = if self.canvas.dragging_guide == Some(guide.id) { 2.0 } else { 1.0 };
+            let points = match guide.axis {
+                GuideAxis::Vertical => [egui::pos2(screen.x, rect.top()), egui::pos2(screen.x, rect.bottom())],
+                GuideAxis::Horizontal => [egui::pos2(rect.left(), screen.y), egui::pos2(rect.right(), screen.y)],
+            };
+            painter.line_segment(points, egui::Stroke::new(width, GUIDE_COLOR));
+        }
+
+        let (top_ruler, left_ruler) = ruler_rects(rect);
+        let background = egui::Color32::from_gray(245);
+        let tick_color = egui::Color32::from_gray(110);
+        painter.rect_filled(top_ruler, 0.0, background);
+        painter.rect_filled(left_ruler, 0.0, background);
+        painter.rect_filled(egui::Rect::from_min_size(rect.min, egui::vec2(RULER_SIZE, RULER_SIZE)), 0.0, background);
+        painter.line_segment([top_ruler.left_bottom(), top_ruler.right_bottom()], egui::Stroke::new(1.0, tick_color));
+        painter.line_segment([left_ruler.right_top(), left_ruler.right_bottom()], egui::Stroke::new(1.0, tick_color));
+
+        // 数字を付ける間隔（1・2・5 の倍数）と、その 1/5 の細かい目盛り
+        let step = label_step(MIN_LABEL_SPACING / zoom);
+        let minor = step / 5.0;
+        let font = egui::FontId::proportional(9.0);
+        let stroke = egui::Stroke::new(1.0, tick_color);
+
+        let first = ((top_ruler.left() - origin.x - pan.x) / zoom / minor).floor() as i64;
+        let last = ((top_ruler.right() - origin.x - pan.x) / zoom / minor).ceil() as i64;
+        for index in first..=last {
+            let value = index as f32 * minor;
+            let x = to_screen(value, origin, zoom, pan).x;
+            if x < top_ruler.left() + RULER_SIZE || x > top_ruler.right() {
+                continue;
+            }
+            let major = index % 5 == 0;
+            let length = if major { RULER_SIZE * 0.6 } else { RULER_SIZE * 0.25 };
+            painter.line_segment([egui::pos2(x, top_ruler.bottom() - length), egui::pos2(x, top_ruler.bottom())], stroke);
+            if major {
+                painter.text(egui::pos2(x + 2.0, top_ruler.top()), egui::Align2::LEFT_TOP, format!("{value:.0}"), font.clone(), tick_color);
+            }
+        }
+
+        let first = ((left_ruler.top() - origin.y - pan.y) / zoom / minor).floor() as i64;
+        let last = ((left_ruler.bottom() - origin.y - pan.y) / zoom / minor).ceil() as i64;
+        for index in first..=last {
+            let value = index as f32 * minor;
+            let y = to_screen(value, origin, zoom, pan).y;
+            if y < left_ruler.top() + RULER_SIZE || y > left_ruler.bottom() {
+                continue;
+            }
+            let major = index % 5 == 0;
+            let length = if major { RULER_SIZE * 0.6 } else { RULER_SIZE * 0.25 };
+            painter.line_segment([egui::pos2(left_ruler.right() - length, y), egui::pos2(left_ruler.right(), y)], stroke);
+            if major {
+                painter.text(egui::pos2(left_ruler.left() + 1.0, y + 1.0), egui::Align2::LEFT_TOP, format!("{value:.0}"), font.clone(), tick_color);
+            }
+        }
+    }
+}
+
+/// 上と左の定規の領域
+fn ruler_rects(rect: egui::Rect) -> (egui::Rect, egui::Rect) {
+    let top = egui::Rect::from_min_max(rect.left_top(), egui::pos2(rect.right(), rect.top() + RULER_SIZE));
+    let left = egui::Rect::from_min_max(rect.left_top(), egui::pos2(rect.left() + RULER_SIZE, rect.bottom()));
+    (top, left)
+}
+
+/// 定規の値（原点からの距離）を画面座標にする（x・y の両方に同じ値を使う）
+fn to_screen(value: f32, origin: egui::Pos2, zoom: f32, pan: egui::Vec2) -> egui::Pos2 {
+    origin + pan + egui::vec2(value, value) * zoom
+}
+
+/// 最小間隔以上になる 1・2・5 × 10^n の間隔
+fn label_step(min_step: f32) -> f32 {
+    let magnitude = 10f32.powf(min_step.max(1.0).log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= min_step)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn cursor_for(axis: GuideAxis) -> egui::CursorIcon {
+    match axis {
+        GuideAxis::Vertical => egui::CursorIcon::ResizeHorizontal,
+        GuideAxis::Horizontal => egui::CursorIcon::ResizeVertical,
+    }
+}
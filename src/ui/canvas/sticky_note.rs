@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::PersonId;
+use crate::ui::StickyNoteRenderer;
+
+/// 付箋の背景色
+const STICKY_NOTE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 240, 140);
+/// 開いた付箋の幅（ズーム倍率1のとき）
+const STICKY_NOTE_WIDTH: f32 = 180.0;
+
+impl StickyNoteRenderer for App {
+    fn render_sticky_notes(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        screen_rects: &HashMap<PersonId, egui::Rect>,
+    ) {
+        let zoom = self.canvas.zoom;
+        let scale = zoom.clamp(0.7, 1.2);
+
+        // 人物ごとに未対応の付箋をまとめる
+        let mut notes_by_person: HashMap<PersonId, Vec<String>> = HashMap::new();
+        for note in self.tree.outstanding_sticky_notes() {
+            notes_by_person.entry(note.person).or_default().push(note.text.clone());
+        }
+
+        for (person_id, texts) in notes_by_person {
+            let Some(node_rect) = screen_rects.get(&person_id) else {
+                continue;
+            };
+
+            // ノード左上に付箋の件数を示すピンを表示し、クリックで開閉
+            let pin_rect = egui::Rect::from_center_size(node_rect.left_top(), egui::vec2(18.0, 18.0) * scale);
+            painter.rect_filled(pin_rect, 2.0, STICKY_NOTE_COLOR);
+            painter.rect_stroke(
+                pin_rect,
+                2.0,
+                egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 150, 40)),
+                egui::epaint::StrokeKind::Inside,
+            );
+            painter.text(
+                pin_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                texts.len().to_string(),
+                egui::FontId::proportional(11.0 * scale),
+                egui::Color32::BLACK,
+            );
+
+            let pin_response = ui
+                .interact(pin_rect, ui.id().with(("sticky_note_pin", person_id)), egui::Sense::click())
+                .on_hover_text(Texts::get("toggle_sticky_notes", self.ui.language));
+            if pin_response.clicked() && !self.canvas.expanded_notes.remove(&person_id) {
+                self.canvas.expanded_notes.insert(person_id);
+            }
+
+            if !self.ui.show_all_notes && !self.canvas.expanded_notes.contains(&person_id) {
+                continue;
+            }
+
+            let text = texts
+                .iter()
+                .map(|text| format!("• {text}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let padding = 6.0 * scale;
+            let galley = painter.layout(
+                text,
+                egui::FontId::proportional(12.0 * scale),
+                egui::Color32::BLACK,
+                STICKY_NOTE_WIDTH * scale - padding * 2.0,
+            );
+            let note_rect = egui::Rect::from_min_size(
+                node_rect.right_top() + egui::vec2(8.0 * scale, 0.0),
+                galley.size() + egui::vec2(padding * 2.0, padding * 2.0),
+            );
+            painter.rect_filled(note_rect.translate(egui::vec2(2.0, 2.0)), 2.0, egui::Color32::from_black_alpha(40));
+            painter.rect_filled(note_rect, 2.0, STICKY_NOTE_COLOR);
+            painter.galley(note_rect.min + egui::vec2(padding, padding), galley, egui::Color32::BLACK);
+        }
+    }
+}
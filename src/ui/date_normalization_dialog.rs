@@ -0,0 +1,112 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::date_normalize::{apply_date_normalization, preview_date_normalization, DateField};
+use crate::core::i18n::Texts;
+use crate::ui::LogLevel;
+
+/// プレビューに表示する最大件数
+const PREVIEW_LIMIT: usize = 200;
+
+/// 日付の一括正規化ダイアログの描画トレイト
+pub trait DateNormalizationDialogRenderer {
+    fn render_date_normalization_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl DateNormalizationDialogRenderer for App {
+    fn render_date_normalization_dialog(&mut self, ctx: &egui::Context) {
+        if !self.date_normalization.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut apply = false;
+        let mut undo = false;
+
+        egui::Window::new(t("date_normalization"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.weak(t("date_normalization_formats"));
+                let previews = preview_date_normalization(&self.tree);
+                let unparseable = previews.iter().filter(|preview| preview.after.is_none()).count();
+                let rewritable = previews.len() - unparseable;
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}: {rewritable}", t("dates_to_normalize")));
+                    ui.separator();
+                    ui.label(format!("{}: {unparseable}", t("dates_unparseable")));
+                });
+                ui.checkbox(&mut self.date_normalization.show_unparseable_only, t("show_unparseable_only"));
+
+                ui.separator();
+                let visible: Vec<_> = previews
+                    .iter()
+                    .filter(|preview| !self.date_normalization.show_unparseable_only || preview.after.is_none())
+                    .collect();
+                egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    egui::Grid::new("date_normalization_preview").striped(true).num_columns(4).show(ui, |ui| {
+                        ui.strong(t("date_field"));
+                        ui.strong(t("date_owner"));
+                        ui.strong(t("date_before"));
+                        ui.strong(t("date_after"));
+                        ui.end_row();
+                        for preview in visible.iter().take(PREVIEW_LIMIT) {
+                            ui.weak(match preview.field {
+                                DateField::Birth => t("date_field_birth"),
+                                DateField::Death => t("date_field_death"),
+                                DateField::Marriage => t("date_field_marriage"),
+                                DateField::Divorce => t("date_field_divorce"),
+                                DateField::Adoption => t("date_field_adoption"),
+                                DateField::Event => t("date_field_event"),
+                            });
+                            ui.label(&preview.owner);
+                            ui.label(&preview.before);
+                            match &preview.after {
+                                Some(after) => ui.label(after),
+                                None => ui.colored_label(ui.visuals().warn_fg_color, t("date_unparseable")),
+                            };
+                            ui.end_row();
+                        }
+                    });
+                    if visible.len() > PREVIEW_LIMIT {
+                        ui.weak(format!("… +{}", visible.len() - PREVIEW_LIMIT));
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let can_apply = rewritable > 0 && !self.ui.read_only;
+                    if ui.add_enabled(can_apply, egui::Button::new(t("apply_date_normalization"))).clicked() {
+                        apply = true;
+                    }
+                    let can_undo = self.date_normalization.last_change.is_some_and(|id| self.undo_history.contains(id)) && !self.ui.read_only;
+                    if ui.add_enabled(can_undo, egui::Button::new(t("undo_date_normalization"))).clicked() {
+                        undo = true;
+                    }
+                });
+            });
+
+        if apply {
+            // 共通の履歴に記録し、書き換えた日付だけを元に戻せるようにする
+            let (count, change) = self.apply_undoable(t("date_normalization"), apply_date_normalization);
+            self.date_normalization.last_change = change;
+            self.toasts.info(format!("{}: {}", t("dates_normalized"), count));
+            self.log.add(format!("{}: {}", t("dates_normalized"), count), LogLevel::Debug);
+        }
+        if undo && let Some(id) = self.date_normalization.last_change.take() {
+            self.undo_tree_change(Some(id));
+        }
+        // 編集フォームに正規化前の日付が残らないよう読み込み直す
+        if apply && let Some(person_id) = self.person_editor.selected {
+            self.load_selected_person_into_form(person_id);
+        }
+        if !open {
+            // 閉じた後も正規化は編集メニューの「元に戻す」から戻せる
+            self.date_normalization.show = false;
+            self.date_normalization.last_change = None;
+        }
+    }
+}
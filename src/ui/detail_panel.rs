@@ -0,0 +1,375 @@
+use eframe::egui;
+use uuid::Uuid;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::i18n::Language;
+use crate::core::tree::{EventId, EventRelationType, Gender, PersonId, ADOPTIVE_KIND};
+use crate::ui::SideTab;
+
+/// 詳細パネルの写真の最大サイズ
+const DETAIL_PHOTO_SIZE: egui::Vec2 = egui::vec2(160.0, 160.0);
+
+/// 親子関係の種類の表示名（既定の種類は翻訳し、利用者が入力した種類はそのまま）
+fn relation_kind_label(kind: &str, lang: Language) -> String {
+    match kind.trim() {
+        "" | "biological" => Texts::get("relation_kind_biological", lang),
+        ADOPTIVE_KIND => Texts::get("relation_kind_adoptive", lang),
+        "foster" => Texts::get("relation_kind_foster", lang),
+        other => other.to_string(),
+    }
+}
+
+/// 詳細パネルに表示する対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DetailTarget {
+    Person(PersonId),
+    Family(Uuid),
+    Event(EventId),
+    Relation(PersonId, PersonId),
+}
+
+/// 右側の詳細パネルの描画トレイト（選択中の項目を読み取り専用で表示する）
+pub trait DetailPanelRenderer {
+    fn render_detail_panel(&mut self, ctx: &egui::Context);
+}
+
+impl DetailPanelRenderer for App {
+    fn render_detail_panel(&mut self, ctx: &egui::Context) {
+        if !self.ui.show_detail_panel {
+            return;
+        }
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        egui::SidePanel::right("detail_panel")
+            .resizable(true)
+            .default_width(260.0)
+            .show(ctx, |ui| {
+                ui.heading(t("detail_panel"));
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| match self.detail_target() {
+                    Some(DetailTarget::Person(person_id)) => self.render_person_details(ui, person_id),
+                    Some(DetailTarget::Family(family_id)) => self.render_family_details(ui, family_id),
+                    Some(DetailTarget::Event(event_id)) => self.render_event_details(ui, event_id),
+                    Some(DetailTarget::Relation(person1, person2)) => {
+                        self.render_relation_details(ui, person1, person2)
+                    }
+                    None => {
+                        ui.weak(t("detail_panel_empty"));
+                    }
+                });
+            });
+    }
+}
+
+impl App {
+    /// 表示する対象を決める（開いているタブの選択を優先し、なければ人物・家族・イベントの順）
+    fn detail_target(&mut self) -> Option<DetailTarget> {
+        // 配偶者関係は選択中の人物の関係のときだけ表示を続ける
+        if let Some((person1, person2)) = self.ui.detail_relation {
+            let selected = self.person_editor.selected;
+            if self.tree.spouse_relation(person1, person2).is_some()
+                && (selected == Some(person1) || selected == Some(person2))
+            {
+                return Some(DetailTarget::Relation(person1, person2));
+            }
+            self.ui.detail_relation = None;
+        }
+
+        let person = self
+            .person_editor
+            .selected
+            .filter(|id| self.tree.persons.contains_key(id))
+            .map(DetailTarget::Person);
+        let family = self
+            .family_editor
+            .selected_family
+            .filter(|id| self.tree.families.iter().any(|family| family.id == *id))
+            .map(DetailTarget::Family);
+        let event = self
+            .event_editor
+            .selected
+            .filter(|id| self.tree.events.contains_key(id))
+            .map(DetailTarget::Event);
+
+        match self.ui.side_tab {
+            SideTab::Families => family.or(person).or(event),
+            SideTab::Events => event.or(person).or(family),
+            _ => person.or(family).or(event),
+        }
+    }
+
+    /// 編集ボタン（閲覧モードでは押せない）
+    fn detail_edit_button(&self, ui: &mut egui::Ui) -> bool {
+        ui.add_enabled(!self.ui.read_only, egui::Button::new(Texts::get("open_in_editor", self.ui.language)))
+            .clicked()
+    }
+
+    /// 人物名のリンク。クリックするとその人物を選択する
+    fn detail_person_link(&mut self, ui: &mut egui::Ui, person_id: PersonId) {
+        if ui.link(self.get_person_name(&person_id)).clicked() {
+            self.ui.detail_relation = None;
+            self.select_single_person(person_id);
+            self.center_on_person(person_id);
+        }
+    }
+
+    fn render_person_details(&mut self, ui: &mut egui::Ui, person_id: PersonId) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(person) = self.tree.persons.get(&person_id).cloned() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.heading(&person.name);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.detail_edit_button(ui) {
+                    self.ui.side_tab = SideTab::Persons;
+                }
+            });
+        });
+
+        if person.has_own_photo()
+            && let Some(photo_path) = person.photo_path.as_deref()
+            && let Some(texture) = self.canvas.photo_texture_cache.get_or_load_thumbnail(ui.ctx(), photo_path)
+        {
+            ui.add(egui::Image::new(&texture).max_size(DETAIL_PHOTO_SIZE));
+        }
+
+        egui::Grid::new("detail_person_fields").num_columns(2).show(ui, |ui| {
+            ui.label(t("gender"));
+            ui.label(match &person.gender {
+                Gender::Male => t("male"),
+                Gender::Female => t("female"),
+                Gender::Unknown => t("unknown"),
+                Gender::Other(label) if !label.is_empty() => label.clone(),
+                Gender::Other(_) => t("other_gender"),
+            });
+            ui.end_row();
+            if let Some(birth) = person.birth.as_deref().filter(|date| !date.is_empty()) {
+                ui.label(t("birth"));
+                ui.label(birth);
+                ui.end_row();
+            }
+            if let Some(place) = person.birth_place.and_then(|id| self.tree.get_place(id)) {
+                ui.label(t("birth_place"));
+                ui.label(&place.name);
+                ui.end_row();
+            }
+            if person.deceased {
+                ui.label(t("death"));
+                ui.label(person.death.as_deref().filter(|date| !date.is_empty()).unwrap_or("?"));
+                ui.end_row();
+            }
+            if let Some(place) = person.death_place.and_then(|id| self.tree.get_place(id)) {
+                ui.label(t("death_place"));
+                ui.label(&place.name);
+                ui.end_row();
+            }
+        });
+        if !person.memo.is_empty() {
+            ui.label(t("memo"));
+            ui.label(&person.memo);
+        }
+
+        let parents = self.tree.parents_of(person_id);
+        if !parents.is_empty() {
+            ui.separator();
+            ui.strong(t("tooltip_parents"));
+            for parent_id in parents {
+                ui.horizontal(|ui| {
+                    self.detail_person_link(ui, parent_id);
+                    if let Some(relation) = self.tree.parent_child_relation(parent_id, person_id) {
+                        ui.weak(format!("({})", relation_kind_label(&relation.kind, lang)));
+                    }
+                });
+            }
+        }
+
+        let spouses = self.tree.spouses_of(person_id);
+        if !spouses.is_empty() {
+            ui.separator();
+            ui.strong(t("tooltip_spouses"));
+            for spouse_id in spouses {
+                ui.horizontal(|ui| {
+                    self.detail_person_link(ui, spouse_id);
+                    if ui.small_button(t("detail_relation")).clicked() {
+                        self.ui.detail_relation = Some((person_id, spouse_id));
+                    }
+                });
+            }
+        }
+
+        let children = self.tree.children_of(person_id);
+        if !children.is_empty() {
+            ui.separator();
+            ui.strong(t("tooltip_children"));
+            for child_id in children {
+                self.detail_person_link(ui, child_id);
+            }
+        }
+
+        let families: Vec<String> = self
+            .tree
+            .get_families_containing(person_id)
+            .iter()
+            .map(|family| family.name.clone())
+            .collect();
+        if !families.is_empty() {
+            ui.separator();
+            ui.strong(t("detail_families"));
+            for name in families {
+                ui.label(name);
+            }
+        }
+
+        let events: Vec<String> = self
+            .tree
+            .event_relations
+            .iter()
+            .filter(|relation| relation.person == person_id)
+            .filter_map(|relation| self.tree.events.get(&relation.event))
+            .map(|event| match event.date.as_deref().filter(|date| !date.is_empty()) {
+                Some(date) => format!("{} ({date})", event.name),
+                None => event.name.clone(),
+            })
+            .collect();
+        if !events.is_empty() {
+            ui.separator();
+            ui.strong(t("detail_events"));
+            for event in events {
+                ui.label(event);
+            }
+        }
+    }
+
+    fn render_family_details(&mut self, ui: &mut egui::Ui, family_id: Uuid) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(family) = self.tree.families.iter().find(|family| family.id == family_id).cloned() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            if let Some((r, g, b)) = family.color {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(14.0, 14.0), egui::Sense::hover());
+                ui.painter().rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+            }
+            ui.heading(&family.name);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.detail_edit_button(ui) {
+                    self.ui.side_tab = SideTab::Families;
+                }
+            });
+        });
+
+        ui.separator();
+        ui.strong(format!("{} ({})", t("members"), family.members.len()));
+        for member_id in family.members {
+            self.detail_person_link(ui, member_id);
+        }
+    }
+
+    fn render_event_details(&mut self, ui: &mut egui::Ui, event_id: EventId) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(event) = self.tree.events.get(&event_id).cloned() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.heading(if event.name.is_empty() { t("new_event") } else { event.name.clone() });
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.detail_edit_button(ui) {
+                    self.ui.side_tab = SideTab::Events;
+                }
+            });
+        });
+        if let Some(date) = event.date.as_deref().filter(|date| !date.is_empty()) {
+            ui.horizontal(|ui| {
+                ui.label(t("date"));
+                ui.label(date);
+            });
+        }
+        if let Some(place) = event.place.and_then(|id| self.tree.get_place(id)) {
+            ui.horizontal(|ui| {
+                ui.label(t("place"));
+                ui.label(&place.name);
+            });
+        }
+        if !event.description.is_empty() {
+            ui.label(t("description"));
+            ui.label(&event.description);
+        }
+
+        let relations: Vec<_> = self
+            .tree
+            .event_relations
+            .iter()
+            .filter(|relation| relation.event == event_id)
+            .cloned()
+            .collect();
+        if !relations.is_empty() {
+            ui.separator();
+            ui.strong(t("detail_related_persons"));
+            for relation in relations {
+                ui.horizontal(|ui| {
+                    self.detail_person_link(ui, relation.person);
+                    let relation_type = match relation.relation_type {
+                        EventRelationType::Line => t("line"),
+                        EventRelationType::ArrowToPerson => t("arrow_to_person"),
+                        EventRelationType::ArrowToEvent => t("arrow_to_event"),
+                    };
+                    ui.weak(format!("({relation_type})"));
+                });
+                if !relation.memo.is_empty() {
+                    ui.weak(format!("  {}", relation.memo));
+                }
+            }
+        }
+    }
+
+    fn render_relation_details(&mut self, ui: &mut egui::Ui, person1: PersonId, person2: PersonId) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(spouse) = self.tree.spouse_relation(person1, person2).cloned() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            if ui.small_button("◀").on_hover_text(t("detail_back")).clicked() {
+                self.ui.detail_relation = None;
+            }
+            ui.heading(t("spouse_editor"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.detail_edit_button(ui) {
+                    self.open_spouse_editor(person1, person2);
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            self.detail_person_link(ui, person1);
+            ui.label("–");
+            self.detail_person_link(ui, person2);
+        });
+
+        egui::Grid::new("detail_relation_fields").num_columns(2).show(ui, |ui| {
+            if let Some(date) = spouse.marriage_date.as_deref().filter(|date| !date.is_empty()) {
+                ui.label(t("marriage_date"));
+                ui.label(date);
+                ui.end_row();
+            }
+            if spouse.divorced {
+                ui.label(t("divorce_date"));
+                ui.label(spouse.divorce_date.as_deref().filter(|date| !date.is_empty()).unwrap_or("?"));
+                ui.end_row();
+            }
+        });
+        if !spouse.memo.is_empty() {
+            ui.label(t("memo"));
+            ui.label(&spouse.memo);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use eframe::egui;
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::FamilyTree;
+use crate::core::undo::{UndoId, UndoResult};
+use crate::ui::shortcuts::{shortcut_button_label, SHORTCUT_COPY_AS_IMAGE, SHORTCUT_FIND_REPLACE, SHORTCUT_REDO, SHORTCUT_UNDO};
+use crate::ui::LogLevel;
+
+pub trait EditMenuRenderer {
+    fn render_edit_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context);
+}
+
+impl EditMenuRenderer for App {
+    fn render_edit_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        ui.menu_button(t("edit_menu"), |ui| {
+            let can_edit = !self.ui.read_only;
+            let undo_label = match self.undo_history.undo_label() {
+                Some(label) => format!("{}: {label}", t("undo")),
+                None => t("undo"),
+            };
+            let can_undo = can_edit && self.undo_history.undo_label().is_some();
+            if ui.add_enabled(can_undo, egui::Button::new(shortcut_button_label(&undo_label, &SHORTCUT_UNDO))).clicked() {
+                self.undo_tree_change(None);
+                ui.close();
+            }
+            let redo_label = match self.undo_history.redo_label() {
+                Some(label) => format!("{}: {label}", t("redo")),
+                None => t("redo"),
+            };
+            let can_redo = can_edit && self.undo_history.redo_label().is_some();
+            if ui.add_enabled(can_redo, egui::Button::new(shortcut_button_label(&redo_label, &SHORTCUT_REDO))).clicked() {
+                self.redo_tree_change();
+                ui.close();
+            }
+
+            ui.separator();
+            if ui.button(shortcut_button_label(&t("find_replace"), &SHORTCUT_FIND_REPLACE)).clicked() {
+                self.find_replace.show = true;
+                ui.close();
+            }
+            if ui
+                .button(shortcut_button_label(&t("copy_as_image"), &SHORTCUT_COPY_AS_IMAGE))
+                .on_hover_text(t("copy_as_image_hint"))
+                .clicked()
+            {
+                self.copy_canvas_as_image();
+                ui.close();
+            }
+            if ui
+                .add_enabled(!self.ui.read_only, egui::Button::new(t("relation_cleanup")))
+                .on_hover_text(t("relation_cleanup_hint"))
+                .clicked()
+            {
+                self.open_relation_cleanup();
+                ui.close();
+            }
+            if ui
+                .add_enabled(!self.ui.read_only, egui::Button::new(t("photo_check")))
+                .on_hover_text(t("photo_check_hint"))
+                .clicked()
+            {
+                self.open_photo_check();
+                ui.close();
+            }
+            if ui
+                .add_enabled(!self.ui.read_only, egui::Button::new(t("date_normalization")))
+                .on_hover_text(t("date_normalization_hint"))
+                .clicked()
+            {
+                self.date_normalization.show = true;
+                self.date_normalization.last_change = None;
+                ui.close();
+            }
+            if ui
+                .add_enabled(!self.ui.read_only, egui::Button::new(t("edit_macros")))
+                .on_hover_text(t("edit_macros_hint"))
+                .clicked()
+            {
+                self.macro_recorder.show = true;
+                self.macro_recorder.last_change = None;
+                ui.close();
+            }
+
+            ui.separator();
+            if ui
+                .add_enabled(!self.ui.read_only, egui::Button::new(t("auto_arrange")))
+                .on_hover_text(t("auto_arrange_hint"))
+                .clicked()
+            {
+                self.preview_auto_arrange();
+                ui.close();
+            }
+        });
+
+        // キーボードショートカット（入力欄では文字の取り消しを優先する）
+        if !ctx.wants_keyboard_input() && !self.ui.read_only {
+            if SHORTCUT_UNDO.pressed(ctx) {
+                self.undo_tree_change(None);
+            }
+            if SHORTCUT_REDO.pressed(ctx) {
+                self.redo_tree_change();
+            }
+        }
+        if SHORTCUT_FIND_REPLACE.pressed(ctx) {
+            self.find_replace.show = true;
+        }
+        if SHORTCUT_COPY_AS_IMAGE.pressed(ctx) {
+            self.copy_canvas_as_image();
+        }
+    }
+}
+
+impl App {
+    /// 家系図への一括操作を行い、元に戻せるように共通の履歴に記録する
+    ///
+    /// 操作名にはメニュー項目の名前をそのまま渡せる（末尾の「…」は外す）。
+    pub(crate) fn apply_undoable<R>(&mut self, label: String, edit: impl FnOnce(&mut FamilyTree) -> R) -> (R, Option<UndoId>) {
+        let label = label.trim_end_matches('…').to_string();
+        self.undo_history.record(&mut self.tree, label, edit)
+    }
+
+    /// 記録した操作を元に戻す（id が None なら直前の操作）
+    ///
+    /// 操作の後に編集された項目は戻さずに残し、その数を知らせる。
+    pub(crate) fn undo_tree_change(&mut self, id: Option<UndoId>) -> Option<UndoResult> {
+        let result = match id {
+            Some(id) => self.undo_history.undo_entry(id, &mut self.tree),
+            None => self.undo_history.undo(&mut self.tree),
+        }?;
+        self.report_undo_result("undone", &result);
+        Some(result)
+    }
+
+    /// 元に戻した操作をやり直す
+    pub(crate) fn redo_tree_change(&mut self) {
+        if let Some(result) = self.undo_history.redo(&mut self.tree) {
+            self.report_undo_result("redone", &result);
+        }
+    }
+
+    fn report_undo_result(&mut self, key: &str, result: &UndoResult) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let message = format!("{}: {}", t(key), result.label);
+        self.toasts.info(message.clone());
+        self.log.add(message, LogLevel::Debug);
+        if result.skipped > 0 {
+            self.toasts.warning(format!("{}: {}", t("undo_kept_later_edits"), result.skipped));
+        }
+        // 編集フォームに操作前後の古い内容が残らないよう読み込み直す
+        if let Some(person_id) = self.person_editor.selected {
+            if self.tree.persons.contains_key(&person_id) {
+                self.load_selected_person_into_form(person_id);
+            } else {
+                self.person_editor.selected = None;
+            }
+        }
+    }
+}
@@ -10,13 +10,15 @@ pub trait EventsTabRenderer {
 impl EventsTabRenderer for App {
     fn render_events_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
         self.render_events_tab_header(ui, &t);
-        self.render_events_tab_editor_section(ui, &t);
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            self.render_events_tab_editor_section(ui, &t);
 
-        if let Some(event_id) = self.event_editor.selected {
-            self.render_events_tab_relations_section(ui, event_id, &t);
-        }
+            if let Some(event_id) = self.event_editor.selected {
+                self.render_events_tab_relations_section(ui, event_id, &t);
+            }
 
-        self.render_events_tab_actions_section(ui, &t);
+            self.render_events_tab_actions_section(ui, &t);
+        });
         self.render_events_tab_footer(ui, &t);
     }
 }
@@ -24,7 +26,7 @@ impl EventsTabRenderer for App {
 impl App {
     fn render_events_tab_header(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         ui.heading(t("manage_events"));
-        if ui.button(t("add_new_event")).clicked() {
+        if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("add_new_event"))).clicked() {
             self.clear_event_editor_selection();
         }
         ui.separator();
@@ -61,6 +63,11 @@ impl App {
         ui.label(t("description"));
         ui.text_edit_multiline(&mut self.event_editor.new_event_description);
 
+        ui.horizontal(|ui| {
+            ui.label(t("place"));
+            App::render_place_picker(ui, &self.tree.places, "event_place", &mut self.event_editor.new_event_place, t);
+        });
+
         ui.label(t("color"));
         ui.color_edit_button_rgb(&mut self.event_editor.new_event_color);
     }
@@ -100,8 +107,11 @@ impl App {
             visible_left_top,
             event_color,
         );
+        if let Some(event) = self.tree.events.get_mut(&event_id) {
+            event.place = self.event_editor.new_event_place;
+        }
         self.event_editor.selected = Some(event_id);
-        self.file.status = t("new_event_added");
+        self.toasts.info(t("new_event_added"));
         self.log.add(format!(
             "{}: {}",
             t("log_event_added"),
@@ -125,7 +135,8 @@ impl App {
             event.date = App::parse_optional_field(&self.event_editor.new_event_date);
             event.description = self.event_editor.new_event_description.clone();
             event.color = event_color;
-            self.file.status = t("event_updated");
+            event.place = self.event_editor.new_event_place;
+            self.toasts.info(t("event_updated"));
             self.log.add(format!(
                 "{}: {} {} {}",
                 t("log_event_updated"),
@@ -144,7 +155,7 @@ impl App {
         let event_name = self.event_name_or_unknown(event_id, t);
         self.tree.remove_event(event_id);
         self.clear_event_editor_selection();
-        self.file.status = t("event_deleted");
+        self.toasts.info(t("event_deleted"));
         self.log
             .add(
                 format!("{}: {}", t("log_event_deleted"), event_name),
@@ -283,7 +294,7 @@ impl App {
     ) {
         let event_name = self.event_name_or_unknown(event_id, t);
         self.tree.remove_event_relation(event_id, person_id);
-        self.file.status = t("relation_removed");
+        self.toasts.info(t("relation_removed"));
         self.log.add(format!(
             "{}: {} <-> {}",
             t("log_event_relation_removed"),
@@ -308,7 +319,7 @@ impl App {
         );
         self.event_editor.person_pick = None;
         self.event_editor.relation_memo.clear();
-        self.file.status = t("relation_added");
+        self.toasts.info(t("relation_added"));
         self.log.add(format!(
             "{}: {} <-> {}",
             t("log_event_relation_added"),
@@ -0,0 +1,486 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use eframe::egui;
+
+use crate::app::App;
+use crate::application::{
+    ChartBox, ChartExporter, ChartLayers, ChartLine, ChartLineStyle, ChartNode, ChartScene, ChartTitleBlock,
+    PaperSize,
+};
+use crate::core::i18n::Texts;
+use crate::core::layout::LayoutEngine;
+use crate::core::tree::{Gender, PersonId};
+use crate::infrastructure::SvgChartExporter;
+use crate::ui::{LogLevel, node_color_theme_from_preset};
+
+/// 家族の枠と人物ノードの間の余白
+const FAMILY_BOX_PADDING: f32 = 20.0;
+/// 家族の枠の上部に確保するラベル領域の高さ
+const FAMILY_BOX_LABEL_SPACE: f32 = 32.0;
+
+/// 書き出しダイアログの描画トレイト
+pub trait ExportDialogRenderer {
+    fn render_export_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl ExportDialogRenderer for App {
+    fn render_export_dialog(&mut self, ctx: &egui::Context) {
+        self.render_image_export_window(ctx);
+        self.render_poster_export_window(ctx);
+    }
+}
+
+/// 書き出すレイヤーの選択欄を描画
+fn render_layer_options(ui: &mut egui::Ui, layers: &mut ChartLayers, t: &dyn Fn(&str) -> String) {
+    ui.horizontal(|ui| {
+        ui.label(t("export_layers"));
+        ui.checkbox(&mut layers.grid, t("layer_grid"));
+        ui.checkbox(&mut layers.family_boxes, t("layer_family_boxes"));
+        ui.checkbox(&mut layers.events, t("layer_events"));
+    });
+}
+
+impl App {
+    /// 表題欄と透かしの入力欄を描画
+    fn render_annotation_options(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let default_title = self.default_export_title();
+        let export = &mut self.export;
+
+        ui.separator();
+        ui.checkbox(&mut export.include_title_block, t("include_title_block"));
+        ui.add_enabled_ui(export.include_title_block, |ui| {
+            egui::Grid::new("export_title_block_grid").num_columns(2).show(ui, |ui| {
+                ui.label(t("title_block_title"));
+                ui.add(egui::TextEdit::singleline(&mut export.title).hint_text(default_title));
+                ui.end_row();
+                ui.label(t("title_block_author"));
+                ui.text_edit_singleline(&mut export.author);
+                ui.end_row();
+                ui.label(t("title_block_date"));
+                ui.add(egui::TextEdit::singleline(&mut export.date).hint_text(today()));
+                ui.end_row();
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label(t("watermark"));
+            ui.add(egui::TextEdit::singleline(&mut export.watermark).hint_text(t("watermark_hint")));
+        });
+    }
+
+    /// 表題が空欄のときに使う、開いているファイル名
+    fn default_export_title(&self) -> String {
+        std::path::Path::new(&self.file.file_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or_else(|| Texts::get("default_export_file_name", self.ui.language))
+    }
+
+    fn build_title_block(&self, generations: usize) -> Option<ChartTitleBlock> {
+        if !self.export.include_title_block {
+            return None;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let title = match self.export.title.trim() {
+            "" => self.default_export_title(),
+            title => title.to_string(),
+        };
+        let date = match self.export.date.trim() {
+            "" => today(),
+            date => date.to_string(),
+        };
+
+        let mut details = Vec::new();
+        if !self.export.author.trim().is_empty() {
+            details.push(format!("{} {}", t("title_block_author"), self.export.author.trim()));
+        }
+        details.push(format!("{} {date}", t("title_block_date")));
+        details.push(format!("{} {generations}", t("title_block_generations")));
+
+        Some(ChartTitleBlock { title, details })
+    }
+
+    fn render_image_export_window(&mut self, ctx: &egui::Context) {
+        if !self.export.show_image_dialog {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let layers = self.export.image_options.layers;
+        let chart_size = self.cached_chart_scene().bounds(&layers).map(|bounds| bounds.size());
+
+        let mut is_open = true;
+        let mut export_clicked = false;
+        let mut cancel_clicked = false;
+
+        egui::Window::new(t("export_image_title"))
+            .id(egui::Id::new("export_image_window"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let options = &mut self.export.image_options;
+
+                ui.horizontal(|ui| {
+                    ui.label(t("export_scale"));
+                    ui.add(
+                        egui::DragValue::new(&mut options.scale)
+                            .range(0.25..=8.0)
+                            .speed(0.05)
+                            .suffix("×"),
+                    );
+                    ui.label(t("export_dpi"));
+                    ui.add(egui::DragValue::new(&mut options.dpi).range(36..=1200));
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(t("export_preset_screen")).clicked() {
+                        options.scale = 1.0;
+                        options.dpi = 96;
+                    }
+                    if ui.button(t("export_preset_print")).clicked() {
+                        options.scale = 3.125;
+                        options.dpi = 300;
+                    }
+                });
+                render_layer_options(ui, &mut options.layers, &t);
+
+                // SVG はピクセル数を持たないので、書き出す width/height と同じ mm で示す
+                if let Some(size) = chart_size {
+                    let (width_mm, height_mm) = options.print_size_mm(size);
+                    ui.weak(format!("{}: {width_mm:.0} × {height_mm:.0} mm", t("export_output_size")));
+                }
+
+                self.render_annotation_options(ui);
+
+                ui.horizontal(|ui| {
+                    export_clicked = ui.button(t("export")).clicked();
+                    cancel_clicked = ui.button(t("cancel")).clicked();
+                });
+            });
+
+        if export_clicked {
+            self.export_image();
+        }
+        if export_clicked || cancel_clicked || !is_open {
+            self.export.show_image_dialog = false;
+        }
+    }
+
+    fn render_poster_export_window(&mut self, ctx: &egui::Context) {
+        if !self.export.show_poster_dialog {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut is_open = true;
+        let mut export_clicked = false;
+        let mut cancel_clicked = false;
+
+        egui::Window::new(t("export_poster_title"))
+            .id(egui::Id::new("export_poster_window"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let options = &mut self.export.poster_options;
+
+                ui.horizontal(|ui| {
+                    ui.label(t("paper_size"));
+                    egui::ComboBox::from_id_salt("poster_paper_size")
+                        .selected_text(options.paper_size.label())
+                        .show_ui(ui, |ui| {
+                            for paper_size in PaperSize::ALL {
+                                ui.selectable_value(&mut options.paper_size, paper_size, paper_size.label());
+                            }
+                        });
+                    ui.checkbox(&mut options.landscape, t("landscape"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t("poster_columns"));
+                    ui.add(egui::DragValue::new(&mut options.columns).range(1..=10));
+                    ui.label(t("poster_rows"));
+                    ui.add(egui::DragValue::new(&mut options.rows).range(1..=10));
+                });
+                render_layer_options(ui, &mut options.layers, &t);
+
+                let (page_width, page_height) = options.page_size_mm();
+                ui.weak(format!(
+                    "{}: {:.0} × {:.0} mm",
+                    t("poster_total_size"),
+                    page_width * options.columns as f32,
+                    page_height * options.rows as f32
+                ));
+
+                self.render_annotation_options(ui);
+
+                ui.horizontal(|ui| {
+                    export_clicked = ui.button(t("export")).clicked();
+                    cancel_clicked = ui.button(t("cancel")).clicked();
+                });
+            });
+
+        if export_clicked {
+            self.export_poster();
+        }
+        if export_clicked || cancel_clicked || !is_open {
+            self.export.show_poster_dialog = false;
+        }
+    }
+
+    /// 現在の家系図を書き出し用の描画内容に変換する
+    /// 図の元になる内容のハッシュ値（家系図と表示・表題欄の設定）
+    fn chart_scene_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.file.current_fingerprint.hash(&mut hasher);
+        self.ui.show_life_years.hash(&mut hasher);
+        self.ui.language.hash(&mut hasher);
+        self.ui.node_color_theme.hash(&mut hasher);
+        self.ui.other_gender_color.hash(&mut hasher);
+        self.canvas.grid_size.to_bits().hash(&mut hasher);
+        self.export.include_title_block.hash(&mut hasher);
+        self.export.title.hash(&mut hasher);
+        self.export.author.hash(&mut hasher);
+        self.export.date.hash(&mut hasher);
+        self.export.watermark.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// 内容が変わったときだけ図を作り直す
+    fn cached_chart_scene(&mut self) -> &ChartScene {
+        let key = self.chart_scene_key();
+        match self.export.scene_cache.take() {
+            Some((cached, scene)) if cached == key => &self.export.scene_cache.insert((cached, scene)).1,
+            _ => {
+                let scene = self.build_chart_scene();
+                &self.export.scene_cache.insert((key, scene)).1
+            }
+        }
+    }
+
+    pub(crate) fn build_chart_scene(&self) -> ChartScene {
+        let photo_dimensions = self.collect_photo_dimensions();
+        let layout_nodes = LayoutEngine::compute_layout(&self.tree, &photo_dimensions, self.ui.show_life_years);
+        let generations = layout_nodes
+            .iter()
+            .map(|node| node.generation + 1)
+            .max()
+            .unwrap_or(0);
+        let rects: HashMap<PersonId, egui::Rect> =
+            layout_nodes.iter().map(|node| (node.id, node.rect)).collect();
+
+        let theme = node_color_theme_from_preset(self.ui.node_color_theme);
+        let nodes = layout_nodes
+            .iter()
+            .map(|node| {
+                let gender = self
+                    .tree
+                    .persons
+                    .get(&node.id)
+                    .map(|person| person.gender.clone())
+                    .unwrap_or(Gender::Unknown);
+                let fill = match gender {
+                    Gender::Other(_) => {
+                        let [r, g, b] = self.ui.other_gender_color;
+                        (r, g, b)
+                    }
+                    _ => {
+                        let color = theme.base_fill_for(&gender);
+                        (color.r(), color.g(), color.b())
+                    }
+                };
+                ChartNode {
+                    rect: node.rect,
+                    label: LayoutEngine::person_label(&self.tree, node.id),
+                    fill,
+                    badges: self
+                        .tree
+                        .persons
+                        .get(&node.id)
+                        .map(|person| person.badges.iter().map(|badge| badge.glyph()).collect())
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let mut lines = Vec::new();
+        let line = |from: egui::Pos2, to: egui::Pos2, style: ChartLineStyle| ChartLine { from, to, style };
+
+        // 配偶者の線
+        for spouse in &self.tree.spouses {
+            if let (Some(r1), Some(r2)) = (rects.get(&spouse.person1), rects.get(&spouse.person2)) {
+                lines.push(line(r1.center(), r2.center(), ChartLineStyle::Solid));
+            }
+        }
+
+        // 親子の線（両親が配偶者同士なら中点から子へ）
+        let mut processed_children = HashSet::new();
+        for edge in &self.tree.edges {
+            if !processed_children.insert(edge.child) {
+                continue;
+            }
+            let Some(child_rect) = rects.get(&edge.child) else {
+                continue;
+            };
+            let parents = self.tree.parents_of(edge.child);
+            if let [parent1, parent2] = parents[..]
+                && self.tree.spouse_relation(parent1, parent2).is_some()
+                && let (Some(r1), Some(r2)) = (rects.get(&parent1), rects.get(&parent2))
+            {
+                let mid = r1.center() + (r2.center() - r1.center()) / 2.0;
+                lines.push(line(mid, child_rect.center_top(), ChartLineStyle::Solid));
+                continue;
+            }
+            for parent in parents {
+                if let Some(parent_rect) = rects.get(&parent) {
+                    lines.push(line(parent_rect.center_bottom(), child_rect.center_top(), ChartLineStyle::Solid));
+                }
+            }
+        }
+
+        // 後見人・名付け親などの関係
+        for relation in &self.tree.person_relations {
+            if let (Some(r1), Some(r2)) = (rects.get(&relation.from), rects.get(&relation.to)) {
+                lines.push(line(r1.center(), r2.center(), ChartLineStyle::Dotted));
+            }
+        }
+
+        // 家族の枠（キャンバスと同じ余白とラベル領域を確保）
+        let family_boxes = self
+            .tree
+            .families
+            .iter()
+            .filter_map(|family| {
+                let members = family
+                    .members
+                    .iter()
+                    .filter_map(|member| rects.get(member).copied())
+                    .reduce(|a, b| a.union(b))?;
+                Some(ChartBox {
+                    rect: egui::Rect::from_min_max(
+                        members.min - egui::vec2(FAMILY_BOX_PADDING, FAMILY_BOX_PADDING + FAMILY_BOX_LABEL_SPACE),
+                        members.max + egui::vec2(FAMILY_BOX_PADDING, FAMILY_BOX_PADDING),
+                    ),
+                    label: family.name.clone(),
+                    color: family.color.unwrap_or((100, 100, 200)),
+                })
+            })
+            .collect();
+
+        // イベントとイベント関係の線
+        let event_rects = LayoutEngine::calculate_event_screen_rects(
+            &self.tree.events,
+            egui::Pos2::ZERO,
+            1.0,
+            egui::Vec2::ZERO,
+            self.ui.language,
+        );
+        let events = self
+            .tree
+            .events
+            .iter()
+            .filter_map(|(event_id, event)| {
+                Some(ChartNode {
+                    rect: *event_rects.get(event_id)?,
+                    label: if event.name.is_empty() {
+                        Texts::get("new_event", self.ui.language)
+                    } else {
+                        event.name.clone()
+                    },
+                    fill: event.color,
+                    badges: String::new(),
+                })
+            })
+            .collect();
+        let event_lines = self
+            .tree
+            .event_relations
+            .iter()
+            .filter_map(|relation| {
+                let event_rect = event_rects.get(&relation.event)?;
+                let person_rect = rects.get(&relation.person)?;
+                Some(line(event_rect.center(), person_rect.center(), ChartLineStyle::Solid))
+            })
+            .collect();
+
+        ChartScene {
+            nodes,
+            lines,
+            family_boxes,
+            events,
+            event_lines,
+            grid_size: self.canvas.grid_size,
+            title_block: self.build_title_block(generations),
+            watermark: Some(self.export.watermark.trim().to_string()).filter(|text| !text.is_empty()),
+        }
+    }
+
+    fn export_image(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t("file_filter_svg"), &["svg"])
+            .set_file_name(format!("{}.svg", t("default_export_file_name")))
+            .save_file()
+        else {
+            return;
+        };
+
+        let scene = self.build_chart_scene();
+        let options = self.export.image_options;
+        match SvgChartExporter.export_image(&path.display().to_string(), &scene, &options) {
+            Ok(()) => {
+                self.toasts.info(t("image_exported"));
+                self.log.add(
+                    format!("{}: {}", t("log_image_exported"), path.display()),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    fn export_poster(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t("file_filter_svg"), &["svg"])
+            .set_file_name(format!("{}.svg", t("default_export_file_name")))
+            .save_file()
+        else {
+            return;
+        };
+
+        let scene = self.build_chart_scene();
+        let options = self.export.poster_options;
+        match SvgChartExporter.export_poster(&path.display().to_string(), &scene, &options) {
+            Ok(paths) => {
+                self.toasts.info(format!("{} ({})", t("poster_exported"), paths.len()));
+                self.log.add(
+                    format!("{}: {}", t("log_poster_exported"), path.display()),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+}
+
+/// 今日の日付（YYYY-MM-DD）
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
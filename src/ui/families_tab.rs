@@ -1,4 +1,7 @@
 use crate::app::App;
+use crate::core::edit_macro::MacroStep;
+use crate::core::layout::{LayoutConfig, LayoutEngine, RelativePlacement};
+use crate::core::tree::FamilyDefaults;
 use crate::ui::LogLevel;
 
 use uuid::Uuid;
@@ -11,9 +14,11 @@ pub trait FamiliesTabRenderer {
 impl FamiliesTabRenderer for App {
     fn render_families_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
         self.render_families_tab_header(ui, &t);
-        self.render_families_tab_editor_section(ui, &t);
-        self.render_families_tab_relations_section(ui, &t);
-        self.render_families_tab_actions_section(ui, &t);
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            self.render_families_tab_editor_section(ui, &t);
+            self.render_families_tab_relations_section(ui, &t);
+            self.render_families_tab_actions_section(ui, &t);
+        });
         self.render_families_tab_footer(ui, &t);
     }
 }
@@ -21,7 +26,7 @@ impl FamiliesTabRenderer for App {
 impl App {
     fn render_families_tab_header(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         ui.heading(t("manage_families"));
-        if ui.button(t("add_new_family")).clicked() {
+        if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("add_new_family"))).clicked() {
             self.add_new_family(t);
         }
         ui.separator();
@@ -30,9 +35,10 @@ impl App {
     fn add_new_family(&mut self, t: &impl Fn(&str) -> String) {
         let color = self.family_editor_color_rgb();
         let family_id = self.tree.add_family(t("new_family"), Some(color));
-        self.family_editor.selected_family = Some(family_id);
-        self.family_editor.new_family_name = t("new_family");
-        self.file.status = t("new_family_added");
+        if let Some(family) = self.tree.families.iter().find(|family| family.id == family_id) {
+            self.family_editor.load_family(family);
+        }
+        self.toasts.info(t("new_family_added"));
         self.log
             .add(
                 format!("{}: {}", t("log_family_added"), t("new_family")),
@@ -67,6 +73,28 @@ impl App {
             ui.label(t("color"));
             ui.color_edit_button_rgb(&mut self.family_editor.new_family_color);
         });
+
+        if self.family_editor.selected_family.is_none() {
+            return;
+        }
+        ui.collapsing(t("family_defaults"), |ui| {
+            ui.weak(t("family_defaults_hint"));
+            egui::Grid::new("family_defaults_grid").num_columns(2).show(ui, |ui| {
+                ui.label(t("family_default_kind"));
+                ui.add(egui::TextEdit::singleline(&mut self.family_editor.new_default_kind).hint_text(t("family_default_kind_global")));
+                ui.end_row();
+
+                ui.label(t("family_default_surname"));
+                ui.text_edit_singleline(&mut self.family_editor.new_default_surname);
+                ui.end_row();
+
+                ui.checkbox(&mut self.family_editor.use_label_color, t("family_label_color"));
+                ui.add_enabled_ui(self.family_editor.use_label_color, |ui| {
+                    ui.color_edit_button_rgb(&mut self.family_editor.new_label_color);
+                });
+                ui.end_row();
+            });
+        });
     }
 
     fn render_families_tab_relations_section(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
@@ -75,6 +103,7 @@ impl App {
         self.render_family_members_list(ui, t);
         ui.separator();
         self.render_add_family_member_section(ui, t);
+        self.render_new_family_member_section(ui, t);
     }
 
     fn render_family_members_list(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
@@ -118,7 +147,7 @@ impl App {
     ) {
         let family_name = self.family_name_or_default(family_id);
         self.tree.remove_member_from_family(family_id, member_id);
-        self.file.status = t("member_removed");
+        self.toasts.info(t("member_removed"));
         self.log.add(format!(
             "{}: {} {} {}",
             t("log_family_member_removed"),
@@ -166,6 +195,80 @@ impl App {
         });
     }
 
+    /// 家族の既定値を使って新しい人物（親を選べばその子）を追加する欄
+    fn render_new_family_member_section(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let Some(family_id) = self.family_editor.selected_family else {
+            return;
+        };
+        let Some(members) = self.tree.families.iter().find(|f| f.id == family_id).map(|f| f.members.clone()) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(t("new_family_member"));
+            ui.add(egui::TextEdit::singleline(&mut self.family_editor.new_member_name).hint_text(t("new_person")).desired_width(120.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label(t("new_family_member_parent"));
+            let parent_name = self
+                .family_editor
+                .new_member_parent
+                .map(|id| self.get_person_name(&id))
+                .unwrap_or_else(|| t("new_family_member_no_parent"));
+            egui::ComboBox::from_id_salt("new_family_member_parent")
+                .selected_text(parent_name)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.family_editor.new_member_parent, None, t("new_family_member_no_parent"));
+                    for member in &members {
+                        if let Some(person) = self.tree.persons.get(member) {
+                            ui.selectable_value(&mut self.family_editor.new_member_parent, Some(*member), &person.name);
+                        }
+                    }
+                });
+            if ui.button(t("add")).clicked() {
+                self.add_new_family_member(family_id, t);
+            }
+        });
+    }
+
+    fn add_new_family_member(&mut self, family_id: Uuid, t: &impl Fn(&str) -> String) {
+        let defaults = self.ui.new_person_defaults.clone();
+        let name = match self.family_editor.new_member_name.trim() {
+            "" => defaults.next_name(&self.tree, &t("new_person")),
+            name => name.to_string(),
+        };
+        let parent = self.family_editor.new_member_parent.filter(|id| self.tree.persons.contains_key(id));
+        // 親がいれば親の下に、いなければ見えている範囲の左上に置く
+        let config = LayoutConfig::default();
+        let position = parent
+            .and_then(|parent| {
+                LayoutEngine::position_near(&self.tree, parent, Uuid::new_v4(), RelativePlacement::Child, config.x_spacing, config.y_spacing)
+            })
+            .map(|position| self.snap_to_canvas_grid(position, self.canvas.canvas_origin))
+            .unwrap_or_else(|| self.visible_canvas_left_top());
+        let fallback_kind = match defaults.relation_kind.trim() {
+            "" => "biological".to_string(),
+            kind => kind.to_string(),
+        };
+
+        let Some(person_id) =
+            self.tree.add_family_member_person(family_id, parent, &name, &fallback_kind, defaults.gender, position)
+        else {
+            return;
+        };
+        if let Some(person) = self.tree.persons.get_mut(&person_id) {
+            person.display_mode = defaults.display_mode;
+        }
+        self.family_editor.new_member_name.clear();
+        self.select_single_person(person_id);
+        let person_name = self.get_person_name(&person_id);
+        self.toasts.info(t("member_added"));
+        self.log.add(
+            format!("{}: {} {} {}", t("log_family_member_added"), person_name, t("log_to"), self.family_name_or_default(family_id)),
+            LogLevel::Debug,
+        );
+    }
+
     fn add_member_to_selected_family(
         &mut self,
         person_id: crate::core::tree::PersonId,
@@ -177,9 +280,9 @@ impl App {
 
         let family_name = self.family_name_or_default(family_id);
         let person_name = self.get_person_name(&person_id);
-        self.tree.add_member_to_family(family_id, person_id);
+        self.execute_relation_command(person_id, MacroStep::AddToFamily { family: family_id }, |_| {});
         self.family_editor.family_member_pick = None;
-        self.file.status = t("member_added");
+        self.toasts.info(t("member_added"));
         self.log.add(format!(
             "{}: {} {} {}",
             t("log_family_member_added"),
@@ -220,6 +323,7 @@ impl App {
 
         let new_name = self.family_editor.new_family_name.clone();
         let color = self.family_editor_color_rgb();
+        let label_color = to_rgb8(self.family_editor.new_label_color);
         if let Some(family) = self
             .tree
             .families
@@ -229,7 +333,12 @@ impl App {
             let old_name = family.name.clone();
             family.name = new_name;
             family.color = Some(color);
-            self.file.status = t("family_updated");
+            family.defaults = FamilyDefaults {
+                relation_kind: self.family_editor.new_default_kind.trim().to_string(),
+                surname: self.family_editor.new_default_surname.trim().to_string(),
+                label_color: self.family_editor.use_label_color.then_some(label_color),
+            };
+            self.toasts.info(t("family_updated"));
             self.log.add(format!(
                 "{}: {} {} {}",
                 t("log_family_updated"),
@@ -244,7 +353,7 @@ impl App {
         let family_name = self.family_name_or_default(family_id);
         self.tree.remove_family(family_id);
         self.clear_family_editor_selection();
-        self.file.status = t("family_deleted");
+        self.toasts.info(t("family_deleted"));
         self.log
             .add(
                 format!("{}: {}", t("log_family_deleted"), family_name),
@@ -253,14 +362,10 @@ impl App {
     }
 
     fn family_editor_color_rgb(&self) -> (u8, u8, u8) {
-        (
-            (self.family_editor.new_family_color[0] * 255.0) as u8,
-            (self.family_editor.new_family_color[1] * 255.0) as u8,
-            (self.family_editor.new_family_color[2] * 255.0) as u8,
-        )
+        to_rgb8(self.family_editor.new_family_color)
     }
 
-    fn family_name_or_default(&self, family_id: Uuid) -> String {
+    pub(crate) fn family_name_or_default(&self, family_id: Uuid) -> String {
         self.tree
             .families
             .iter()
@@ -275,3 +380,7 @@ impl App {
         self.family_editor.family_member_pick = None;
     }
 }
+
+fn to_rgb8(color: [f32; 3]) -> (u8, u8, u8) {
+    ((color[0] * 255.0).round() as u8, (color[1] * 255.0).round() as u8, (color[2] * 255.0).round() as u8)
+}
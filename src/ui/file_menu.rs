@@ -1,6 +1,13 @@
 use eframe::egui;
 use crate::app::App;
-use crate::core::tree::FamilyTree;
+use crate::core::i18n::Texts;
+use crate::core::tree::{ExternalPersonLink, FamilyTree, PersonId};
+use crate::infrastructure::{
+    CalendarExportOptions, HtmlViewerExporter, HtmlViewerOptions, IcalendarExporter, TextChartExporter, TextChartFormat, VcardExporter, PEDIGREE_GENERATION_CHOICES,
+};
+use crate::ui::LogLevel;
+use crate::ui::shortcuts::{shortcut_button_label, SHORTCUT_OPEN, SHORTCUT_SAVE};
+use crate::ui::PendingFileAction;
 
 pub trait FileMenuRenderer {
     fn render_file_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context);
@@ -9,111 +16,479 @@ pub trait FileMenuRenderer {
 impl FileMenuRenderer for App {
     fn render_file_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         let lang = self.ui.language;
-        let t = |key: &str| crate::core::i18n::Texts::get(key, lang);
-        let filter_family_tree = t("file_filter_family_tree");
-        let filter_json = t("file_filter_json");
-        let filter_sqlite = t("file_filter_sqlite");
-        let default_file_name = t("default_file_name");
+        let t = |key: &str| Texts::get(key, lang);
         
         ui.menu_button(t("file_menu"), |ui| {
             // 新規作成
             if ui.button(t("new")).clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                    .add_filter(&filter_json, &["json"])
-                    .add_filter(&filter_sqlite, &["sqlite", "db"])
-                    .set_file_name(&default_file_name)
-                    .save_file()
-                {
-                    self.tree = FamilyTree::default();
-                    self.person_editor.selected = None;
-                    self.family_editor.selected_family = None;
-                    self.event_editor.selected = None;
-                    self.file.file_path = path.display().to_string();
-                    self.file.status = t("new_tree_created");
-                    self.save();
-                }
+                self.request_file_action(PendingFileAction::New, ctx);
                 ui.close();
             }
             
+            // サンプルデータを生成
+            if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("generate_sample"))).clicked() {
+                self.sample_data.show = true;
+                ui.close();
+            }
+
             // 開く
-            if ui.button(format!("{} (Ctrl+O)", t("open"))).clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                    .add_filter(&filter_json, &["json"])
-                    .add_filter(&filter_sqlite, &["sqlite", "db"])
-                    .pick_file()
-                {
-                    self.file.file_path = path.display().to_string();
-                    self.load();
-                }
+            if ui.button(shortcut_button_label(&t("open"), &SHORTCUT_OPEN)).clicked() {
+                self.request_file_action(PendingFileAction::Open, ctx);
                 ui.close();
             }
             
             // 保存
-            if ui.button(format!("{} (Ctrl+S)", t("save"))).clicked() {
-                // ファイルパスが存在しない場合は名前を付けて保存
-                if self.file.file_path.is_empty() || !std::path::Path::new(&self.file.file_path).exists() {
-                    if let Some(path) = rfd::FileDialog::new()
-                        .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                        .add_filter(&filter_json, &["json"])
-                        .add_filter(&filter_sqlite, &["sqlite", "db"])
-                        .set_file_name(if self.file.file_path.is_empty() { &default_file_name } else { &self.file.file_path })
-                        .save_file()
-                    {
-                        self.file.file_path = path.display().to_string();
-                        self.save();
-                    }
-                } else {
-                    self.save();
-                }
+            let save_label = shortcut_button_label(&t("save"), &SHORTCUT_SAVE);
+            if ui.add_enabled(!self.ui.read_only, egui::Button::new(save_label)).clicked() {
+                self.save_with_dialog();
                 ui.close();
             }
             
             // 名前を付けて保存
-            if ui.button(t("save_as")).clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                    .add_filter(&filter_json, &["json"])
-                    .add_filter(&filter_sqlite, &["sqlite", "db"])
-                    .set_file_name(&self.file.file_path)
-                    .save_file()
-                {
-                    self.file.file_path = path.display().to_string();
-                    self.save();
-                }
+            if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("save_as"))).clicked() {
+                self.save_as_with_dialog();
+                ui.close();
+            }
+
+            ui.separator();
+
+            // 1枚の画像として書き出し
+            if ui.button(t("export_image")).clicked() {
+                self.export.show_image_dialog = true;
+                ui.close();
+            }
+
+            // 分割ポスターとして書き出し
+            if ui.button(t("export_poster")).clicked() {
+                self.export.show_poster_dialog = true;
+                ui.close();
+            }
+
+            // 選択中の人物を vCard として書き出し
+            let has_selection = !self.vcard_target_ids().is_empty();
+            if ui
+                .add_enabled(has_selection, egui::Button::new(t("export_vcard")))
+                .on_disabled_hover_text(t("export_vcard_no_selection"))
+                .clicked()
+            {
+                self.export_vcard();
+                ui.close();
+            }
+
+            // 選択中の人物（未選択ならホームの人物）から子孫の一覧をテキストとして書き出し
+            let text_chart_root = self.text_chart_root();
+            if ui
+                .add_enabled(text_chart_root.is_some(), egui::Button::new(t("export_descendant_outline")))
+                .on_hover_text(t("export_descendant_outline_hint"))
+                .on_disabled_hover_text(t("export_text_chart_no_selection"))
+                .clicked()
+            {
+                self.export_descendant_outline();
+                ui.close();
+            }
+
+            // 同じ人物の祖先表をテキストとして書き出し（世代数を選ぶ）
+            ui.add_enabled_ui(text_chart_root.is_some(), |ui| {
+                ui.menu_button(t("export_pedigree_chart"), |ui| {
+                    for generations in PEDIGREE_GENERATION_CHOICES {
+                        if ui.button(format!("{generations} {}", t("pedigree_generations"))).clicked() {
+                            self.export_pedigree_chart(generations);
+                            ui.close();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(t("export_pedigree_chart_hint"))
+                .on_disabled_hover_text(t("export_text_chart_no_selection"));
+            });
+
+            // 誕生日・結婚記念日を iCalendar として書き出し
+            if ui.button(t("export_icalendar")).clicked() {
+                self.export_icalendar();
                 ui.close();
             }
+
+            // ブラウザで見られる閲覧用の HTML として書き出し
+            ui.menu_button(t("export_html_viewer"), |ui| {
+                if ui.button(t("export_html_viewer_with_photos")).clicked() {
+                    self.export_html_viewer(true);
+                    ui.close();
+                }
+                if ui.button(t("export_html_viewer_without_photos")).clicked() {
+                    self.export_html_viewer(false);
+                    ui.close();
+                }
+            })
+            .response
+            .on_hover_text(t("export_html_viewer_hint"));
         });
         
         // キーボードショートカット
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::S)) {
-            // ファイルパスが存在しない場合は名前を付けて保存
-            if self.file.file_path.is_empty() || !std::path::Path::new(&self.file.file_path).exists() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                    .add_filter(&filter_json, &["json"])
-                    .add_filter(&filter_sqlite, &["sqlite", "db"])
-                    .set_file_name(if self.file.file_path.is_empty() { &default_file_name } else { &self.file.file_path })
-                    .save_file()
-                {
-                    self.file.file_path = path.display().to_string();
-                    self.save();
+        if SHORTCUT_SAVE.pressed(ctx) && !self.ui.read_only {
+            self.save_with_dialog();
+        }
+        if SHORTCUT_OPEN.pressed(ctx) {
+            self.request_file_action(PendingFileAction::Open, ctx);
+        }
+    }
+}
+
+impl App {
+    fn tree_file_dialog(&self) -> rfd::FileDialog {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        rfd::FileDialog::new()
+            .add_filter(t("file_filter_family_tree"), &["json", "yaml", "yml", "sqlite", "db", "gramps", "ged"])
+            .add_filter(t("file_filter_json"), &["json"])
+            .add_filter(t("file_filter_yaml"), &["yaml", "yml"])
+            .add_filter(t("file_filter_sqlite"), &["sqlite", "db"])
+            .add_filter(t("file_filter_gramps"), &["gramps"])
+            .add_filter(t("file_filter_gedcom"), &["ged"])
+    }
+
+    /// 未保存の変更があれば確認ダイアログを出し、なければそのまま実行する
+    pub(crate) fn request_file_action(&mut self, action: PendingFileAction, ctx: &egui::Context) {
+        if self.file.dirty {
+            self.file.pending_action = Some(action);
+        } else {
+            self.run_file_action(action, ctx);
+        }
+    }
+
+    pub(crate) fn run_file_action(&mut self, action: PendingFileAction, ctx: &egui::Context) {
+        match action {
+            PendingFileAction::New => self.new_tree_with_dialog(),
+            PendingFileAction::Open => self.open_with_dialog(),
+            PendingFileAction::OpenLinked => self.open_pending_linked_tree(),
+            PendingFileAction::LoadSample => self.load_sample_tree(),
+            PendingFileAction::Exit => {
+                self.file.close_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+    }
+
+    fn new_tree_with_dialog(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        if let Some(path) = self
+            .tree_file_dialog()
+            .set_file_name(t("default_file_name"))
+            .save_file()
+        {
+            let tree = FamilyTree::default();
+            self.session.switch_tree(&self.tree, &tree);
+            self.tree = tree;
+            self.undo_history.clear();
+            self.person_editor.selected = None;
+            self.family_editor.selected_family = None;
+            self.event_editor.selected = None;
+            self.file.file_path = path.display().to_string();
+            self.toasts.info(t("new_tree_created"));
+            self.save();
+        }
+    }
+
+    fn open_with_dialog(&mut self) {
+        if let Some(path) = self.tree_file_dialog().pick_file() {
+            self.file.file_path = path.display().to_string();
+            self.load();
+        }
+    }
+
+    /// 人物のリンク先の家系図を開く（URL はブラウザで開き、ファイルは未保存の変更を確認してから読み込む）
+    pub(crate) fn open_linked_tree(&mut self, link: ExternalPersonLink, ctx: &egui::Context) {
+        if link.is_url() {
+            ctx.open_url(egui::OpenUrl::new_tab(link.tree.trim()));
+            return;
+        }
+        // 相対パスは開いているファイルのフォルダーを基準にする
+        let path = std::path::Path::new(link.tree.trim());
+        let resolved = match std::path::Path::new(&self.file.file_path).parent() {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        if !resolved.exists() {
+            let lang = self.ui.language;
+            self.toasts.warning(format!("{}: {}", Texts::get("linked_tree_not_found", lang), resolved.display()));
+            return;
+        }
+        self.file.pending_linked_tree = Some(ExternalPersonLink { tree: resolved.display().to_string(), ..link });
+        self.request_file_action(PendingFileAction::OpenLinked, ctx);
+    }
+
+    fn open_pending_linked_tree(&mut self) {
+        let Some(link) = self.file.pending_linked_tree.take() else {
+            return;
+        };
+        self.file.file_path = link.tree;
+        self.load();
+        if link.person_id.is_empty() {
+            return;
+        }
+        match self.tree.find_person_by_id(&link.person_id) {
+            Some(person_id) => {
+                self.select_single_person(person_id);
+                self.center_on_person(person_id);
+            }
+            None => {
+                let lang = self.ui.language;
+                self.toasts.warning(format!("{}: {}", Texts::get("linked_person_not_found", lang), link.person_id));
+            }
+        }
+    }
+
+    /// 上書き保存（ファイルパスが存在しない場合は名前を付けて保存）し、保存できたかを返す
+    pub(crate) fn save_with_dialog(&mut self) -> bool {
+        if self.file.file_path.is_empty() || !std::path::Path::new(&self.file.file_path).exists() {
+            return self.save_as_with_dialog();
+        }
+        self.save()
+    }
+
+    fn save_as_with_dialog(&mut self) -> bool {
+        let lang = self.ui.language;
+        let file_name = if self.file.file_path.is_empty() {
+            Texts::get("default_file_name", lang)
+        } else {
+            self.file.file_path.clone()
+        };
+        match self.tree_file_dialog().set_file_name(file_name).save_file() {
+            Some(path) => {
+                let previous_path = std::mem::replace(&mut self.file.file_path, path.display().to_string());
+                let saved = self.save();
+                // 上書きをやめたら元のファイルパスに戻す
+                if let Some(confirm) = &mut self.file.overwrite_confirm {
+                    confirm.previous_path = previous_path;
                 }
-            } else {
-                self.save();
+                saved
             }
+            None => false,
         }
-        if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::O)) {
-            if let Some(path) = rfd::FileDialog::new()
-                .add_filter(&filter_family_tree, &["json", "sqlite", "db"])
-                .add_filter(&filter_json, &["json"])
-                .add_filter(&filter_sqlite, &["sqlite", "db"])
-                .pick_file()
-            {
-                self.file.file_path = path.display().to_string();
-                self.load();
+    }
+
+    /// vCard の書き出し対象（複数選択があればそれを、なければ選択中の人物）
+    fn vcard_target_ids(&self) -> Vec<PersonId> {
+        if !self.person_editor.selected_ids.is_empty() {
+            self.person_editor.selected_ids.clone()
+        } else {
+            self.person_editor.selected.into_iter().collect()
+        }
+    }
+
+    fn export_vcard(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t("file_filter_vcard"), &["vcf"])
+            .set_file_name(format!("{}.vcf", t("default_vcard_file_name")))
+            .save_file()
+        else {
+            return;
+        };
+
+        let ids = self.vcard_target_ids();
+        match VcardExporter::export(&path.display().to_string(), &self.tree, &ids) {
+            Ok(count) => {
+                self.toasts.info(format!("{} ({})", t("vcard_exported"), count));
+                self.log.add(
+                    format!("{}: {} ({})", t("log_vcard_exported"), path.display(), count),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    /// テキスト系図の保存先を選ぶ（拡張子が .md なら Markdown で書き出す）
+    fn text_chart_file_dialog(&self, default_name: &str) -> Option<std::path::PathBuf> {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        rfd::FileDialog::new()
+            .add_filter(t("file_filter_text"), &["txt"])
+            .add_filter(t("file_filter_markdown"), &["md"])
+            .set_file_name(format!("{default_name}.txt"))
+            .save_file()
+    }
+
+    /// 子孫一覧・祖先表の起点（選択中の人物、未選択ならホームの人物）
+    fn text_chart_root(&self) -> Option<PersonId> {
+        self.person_editor.selected.or(self.tree.home_person)
+    }
+
+    fn export_descendant_outline(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(root) = self.text_chart_root() else {
+            return;
+        };
+        let default_name = format!("{} - {}", self.get_person_name(&root), t("default_descendant_outline_file_name"));
+        let Some(path) = self.text_chart_file_dialog(&default_name) else {
+            return;
+        };
+
+        let format = TextChartFormat::from_path(&path);
+        match TextChartExporter::export_descendant_outline(&path.display().to_string(), &self.tree, root, format) {
+            Ok(count) => {
+                self.toasts.info(format!("{} ({})", t("descendant_outline_exported"), count));
+                self.log.add(
+                    format!("{}: {} ({})", t("log_descendant_outline_exported"), path.display(), count),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    fn export_pedigree_chart(&mut self, generations: usize) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let Some(root) = self.text_chart_root() else {
+            return;
+        };
+        let default_name = format!("{} - {}", self.get_person_name(&root), t("default_pedigree_chart_file_name"));
+        let Some(path) = self.text_chart_file_dialog(&default_name) else {
+            return;
+        };
+
+        let format = TextChartFormat::from_path(&path);
+        match TextChartExporter::export_pedigree_chart(&path.display().to_string(), &self.tree, root, generations, format) {
+            Ok(count) => {
+                self.toasts.info(format!("{} ({})", t("pedigree_chart_exported"), count));
+                self.log.add(
+                    format!("{}: {} ({})", t("log_pedigree_chart_exported"), path.display(), count),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    fn export_icalendar(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t("file_filter_icalendar"), &["ics"])
+            .set_file_name(format!("{}.ics", t("default_calendar_file_name")))
+            .save_file()
+        else {
+            return;
+        };
+
+        let options = CalendarExportOptions {
+            birthday_label: t("calendar_birthday"),
+            anniversary_label: t("calendar_anniversary"),
+            presumption: self.ui.deceased_presumption(),
+            dtstamp: chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+        };
+        match IcalendarExporter::export(&path.display().to_string(), &self.tree, &options) {
+            Ok(count) => {
+                self.toasts.info(format!("{} ({})", t("calendar_exported"), count));
+                self.log.add(
+                    format!("{}: {} ({})", t("log_calendar_exported"), path.display(), count),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    fn export_html_viewer(&mut self, inline_photos: bool) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let title = self.file.display_name(&t("untitled"));
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(t("file_filter_html"), &["html", "htm"])
+            .set_file_name(format!("{title}.html"))
+            .save_file()
+        else {
+            return;
+        };
+
+        let labels = ["birth", "death", "parents", "children", "spouses", "hint", "search"];
+        let options = HtmlViewerOptions {
+            title,
+            inline_photos,
+            labels: labels.into_iter().map(|key| (key, t(&format!("html_viewer_{key}")))).collect(),
+            presumption: self.ui.deceased_presumption(),
+        };
+        match HtmlViewerExporter::export(&path.display().to_string(), &self.tree, &options) {
+            Ok(count) => {
+                self.toasts.info(format!("{} ({})", t("html_viewer_exported"), count));
+                self.log.add(
+                    format!("{}: {} ({})", t("log_html_viewer_exported"), path.display(), count),
+                    LogLevel::Debug,
+                );
+            }
+            Err(error) => {
+                self.set_error_status_and_log(&t("export_failed"), &error.to_string());
+            }
+        }
+    }
+
+    /// 「変更を保存しますか？」ダイアログ
+    pub(crate) fn render_unsaved_changes_dialog(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.file.pending_action else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut choice = None;
+        let response = egui::Modal::new(egui::Id::new("unsaved_changes_dialog")).show(ctx, |ui| {
+            ui.heading(t("unsaved_changes_title"));
+            ui.add_space(6.0);
+            ui.label(t("unsaved_changes_message"));
+            // 終了時はこのセッションで行った編集の件数も示す
+            if action == PendingFileAction::Exit {
+                let counts = self.session.counts(&self.tree);
+                if !counts.is_empty() {
+                    ui.add_space(4.0);
+                    ui.weak(format!("{}: {}", t("session_edits"), self.session_summary(&counts)));
+                }
+            }
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("save")).clicked() {
+                    choice = Some(true);
+                }
+                if ui.button(t("discard_changes")).clicked() {
+                    choice = Some(false);
+                }
+                if ui.button(t("cancel")).clicked() {
+                    ui.close();
+                }
+            });
+        });
+
+        match choice {
+            // 保存に失敗・キャンセルした場合は元の操作を行わない
+            Some(true) => {
+                self.file.pending_action = None;
+                if self.save_with_dialog() {
+                    self.run_file_action(action, ctx);
+                } else if let Some(confirm) = &mut self.file.overwrite_confirm {
+                    // 上書きの確認を待つ場合は、確認して保存した後に続ける
+                    confirm.pending_action = Some(action);
+                }
+            }
+            Some(false) => {
+                self.file.pending_action = None;
+                self.run_file_action(action, ctx);
             }
+            None if response.should_close() => self.file.pending_action = None,
+            None => {}
         }
     }
 }
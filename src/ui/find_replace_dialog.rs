@@ -0,0 +1,121 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::find_replace::ReplaceField;
+use crate::core::i18n::Texts;
+use crate::ui::LogLevel;
+
+/// プレビューに表示する最大件数
+const PREVIEW_LIMIT: usize = 200;
+
+/// 検索・置換ダイアログの描画トレイト
+pub trait FindReplaceDialogRenderer {
+    fn render_find_replace_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl FindReplaceDialogRenderer for App {
+    fn render_find_replace_dialog(&mut self, ctx: &egui::Context) {
+        if !self.find_replace.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut replace_all = false;
+        let mut undo = false;
+
+        egui::Window::new(t("find_replace"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let query = &mut self.find_replace.query;
+                egui::Grid::new("find_replace_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(t("find_text"));
+                    ui.text_edit_singleline(&mut query.find);
+                    ui.end_row();
+                    ui.label(t("replace_text"));
+                    ui.text_edit_singleline(&mut query.replace);
+                    ui.end_row();
+                });
+                ui.checkbox(&mut query.case_sensitive, t("case_sensitive"));
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(t("replace_scope"));
+                    ui.checkbox(&mut query.scope.person_names, t("replace_person_names"));
+                    ui.checkbox(&mut query.scope.person_memos, t("replace_person_memos"));
+                    ui.checkbox(&mut query.scope.relation_kinds, t("replace_relation_kinds"));
+                    ui.checkbox(&mut query.scope.event_descriptions, t("replace_event_descriptions"));
+                });
+
+                ui.separator();
+                let previews = self.find_replace.query.preview(&self.tree);
+                ui.label(format!("{}: {}", t("affected_entries"), previews.len()));
+                egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    egui::Grid::new("find_replace_preview").striped(true).num_columns(3).show(ui, |ui| {
+                        for preview in previews.iter().take(PREVIEW_LIMIT) {
+                            let field = match preview.field {
+                                ReplaceField::PersonName => t("replace_person_names"),
+                                ReplaceField::PersonMemo => t("replace_person_memos"),
+                                ReplaceField::RelationKind => t("replace_relation_kinds"),
+                                ReplaceField::EventDescription => t("replace_event_descriptions"),
+                            };
+                            ui.weak(field);
+                            ui.label(&preview.owner);
+                            ui.label(format!("{} → {}", preview.before, preview.after));
+                            ui.end_row();
+                        }
+                    });
+                    if previews.len() > PREVIEW_LIMIT {
+                        ui.weak(format!("… +{}", previews.len() - PREVIEW_LIMIT));
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let can_replace = !previews.is_empty() && !self.ui.read_only;
+                    if ui.add_enabled(can_replace, egui::Button::new(t("replace_all"))).clicked() {
+                        replace_all = true;
+                    }
+                    let can_undo = self
+                        .find_replace
+                        .last_change
+                        .is_some_and(|id| self.undo_history.contains(id))
+                        && !self.ui.read_only;
+                    if ui.add_enabled(can_undo, egui::Button::new(t("undo_replace"))).clicked() {
+                        undo = true;
+                    }
+                });
+            });
+
+        if replace_all {
+            // 置換した項目だけを共通の履歴に記録して、後の編集を消さずに元に戻せるようにする
+            let query = self.find_replace.query.clone();
+            let (count, change) = self.apply_undoable(t("find_replace"), |tree| query.apply(tree));
+            self.find_replace.last_change = change;
+            self.toasts.info(format!("{}: {}", t("replace_done"), count));
+            self.log.add(
+                format!(
+                    "{}: \"{}\" → \"{}\" ({})",
+                    t("replace_done"),
+                    self.find_replace.query.find,
+                    self.find_replace.query.replace,
+                    count
+                ),
+                LogLevel::Debug,
+            );
+        }
+        if undo && let Some(id) = self.find_replace.last_change.take() {
+            self.undo_tree_change(Some(id));
+        }
+        // 編集フォームに置換前の内容が残らないよう読み込み直す
+        if replace_all && let Some(person_id) = self.person_editor.selected {
+            self.load_selected_person_into_form(person_id);
+        }
+        if !open {
+            // 閉じた後も置換は編集メニューの「元に戻す」から戻せる
+            self.find_replace.show = false;
+            self.find_replace.last_change = None;
+        }
+    }
+}
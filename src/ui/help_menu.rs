@@ -1,6 +1,7 @@
 use eframe::egui;
 use crate::app::App;
 use crate::core::i18n::Texts;
+use crate::ui::shortcuts::{shortcut_button_label, ShortcutInput, SHORTCUTS, SHORTCUT_SHOW_SHORTCUTS};
 
 pub trait HelpMenuRenderer {
     fn render_help_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context);
@@ -12,6 +13,10 @@ impl HelpMenuRenderer for App {
         let t = |key: &str| Texts::get(key, lang);
         
         ui.menu_button(t("help_menu"), |ui| {
+            if ui.button(shortcut_button_label(&t("shortcuts"), &SHORTCUT_SHOW_SHORTCUTS)).clicked() {
+                self.ui.show_shortcuts_dialog = true;
+                ui.close();
+            }
             if ui.button(t("about")).clicked() {
                 self.ui.show_about_dialog = true;
                 ui.close();
@@ -22,6 +27,46 @@ impl HelpMenuRenderer for App {
             }
         });
         
+        if SHORTCUT_SHOW_SHORTCUTS.pressed(ctx) {
+            self.ui.show_shortcuts_dialog = !self.ui.show_shortcuts_dialog;
+        }
+
+        // ショートカット一覧（ショートカット定義から分類ごとに生成）
+        if self.ui.show_shortcuts_dialog {
+            let mut open = true;
+            egui::Window::new(t("shortcuts"))
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    let mut categories: Vec<&str> = Vec::new();
+                    for shortcut in SHORTCUTS {
+                        if !categories.contains(&shortcut.category) {
+                            categories.push(shortcut.category);
+                        }
+                    }
+
+                    for category in categories {
+                        ui.strong(t(category));
+                        egui::Grid::new(category).num_columns(2).striped(true).show(ui, |ui| {
+                            for shortcut in SHORTCUTS.iter().filter(|s| s.category == category) {
+                                let keys = match shortcut.input {
+                                    ShortcutInput::Key(_) => shortcut.key_label().unwrap_or_default(),
+                                    ShortcutInput::Gesture(gesture) => t(gesture),
+                                };
+                                ui.monospace(keys);
+                                ui.label(t(shortcut.action));
+                                ui.end_row();
+                            }
+                        });
+                        ui.add_space(6.0);
+                    }
+                });
+            if !open {
+                self.ui.show_shortcuts_dialog = false;
+            }
+        }
+
         // バージョン情報ダイアログ
         if self.ui.show_about_dialog {
             egui::Window::new(t("about"))
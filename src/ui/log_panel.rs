@@ -0,0 +1,84 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::ui::LogLevel;
+
+/// ログパネル（下部）の描画トレイト
+pub trait LogPanelRenderer {
+    fn render_log_panel(&mut self, ctx: &egui::Context);
+}
+
+impl LogPanelRenderer for App {
+    fn render_log_panel(&mut self, ctx: &egui::Context) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        egui::TopBottomPanel::bottom("log_panel")
+            .resizable(true)
+            .default_height(120.0)
+            .min_height(60.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(t("log_panel_title"));
+                    ui.separator();
+
+                    // レベルごとの表示切り替え
+                    for level in LogLevel::ALL {
+                        let mut visible = !self.log.hidden_levels.contains(&level);
+                        let label = egui::RichText::new(level.as_str()).color(level.color()).monospace();
+                        if ui.toggle_value(&mut visible, label).changed() {
+                            if visible {
+                                self.log.hidden_levels.remove(&level);
+                            } else {
+                                self.log.hidden_levels.insert(level);
+                            }
+                        }
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.log.filter_text)
+                            .hint_text(t("log_filter_hint"))
+                            .desired_width(160.0),
+                    );
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(t("clear")).clicked() {
+                            self.log.clear();
+                        }
+                        if ui.button(t("copy")).on_hover_text(t("log_copy_hint")).clicked() {
+                            ui.ctx().copy_text(self.log.visible_messages_text());
+                        }
+                    });
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for msg in self.log.visible_messages() {
+                            ui.horizontal(|ui| {
+                                // 前回までのセッションの記録は日付も表示
+                                let timestamp = if msg.date == today {
+                                    msg.timestamp.clone()
+                                } else {
+                                    format!("{} {}", msg.date, msg.timestamp)
+                                };
+                                ui.label(
+                                    egui::RichText::new(timestamp)
+                                        .color(egui::Color32::GRAY)
+                                        .monospace()
+                                );
+                                ui.label(
+                                    egui::RichText::new(format!("[{}]", msg.level.as_str()))
+                                        .color(msg.level.color())
+                                        .monospace()
+                                );
+                                ui.label(&msg.message);
+                            });
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,205 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::edit_macro::{EditMacro, MacroStep};
+use crate::core::i18n::Texts;
+use crate::core::tree::{FamilyTree, PersonId};
+use crate::ui::LogLevel;
+
+/// 操作の記録（マクロ）ダイアログの描画トレイト
+pub trait MacroDialogRenderer {
+    fn render_macro_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl MacroDialogRenderer for App {
+    fn render_macro_dialog(&mut self, ctx: &egui::Context) {
+        if !self.macro_recorder.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut start_recording = false;
+        let mut stop_recording = false;
+        let mut save = false;
+        let mut run: Option<usize> = None;
+        let mut delete: Option<usize> = None;
+        let mut undo = false;
+
+        let targets = self.macro_targets();
+        let recorded: Vec<String> = self
+            .macro_recorder
+            .recording
+            .iter()
+            .flatten()
+            .map(|step| self.describe_macro_step(step, &t))
+            .collect();
+        let saved: Vec<(String, Vec<String>)> = self
+            .tree
+            .macros
+            .iter()
+            .map(|edit_macro| {
+                let steps = edit_macro.steps.iter().map(|step| self.describe_macro_step(step, &t)).collect();
+                (edit_macro.name.clone(), steps)
+            })
+            .collect();
+
+        egui::Window::new(t("edit_macros"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let state = &mut self.macro_recorder;
+                let can_edit = !self.ui.read_only;
+                ui.horizontal(|ui| {
+                    if state.recording.is_none() {
+                        if ui.add_enabled(can_edit, egui::Button::new(t("macro_start_recording"))).clicked() {
+                            start_recording = true;
+                        }
+                    } else {
+                        ui.colored_label(egui::Color32::from_rgb(220, 60, 60), "●");
+                        ui.label(t("macro_recording"));
+                        if ui.button(t("macro_stop_recording")).clicked() {
+                            stop_recording = true;
+                        }
+                    }
+                });
+                if state.recording.is_some() {
+                    ui.weak(t("macro_recording_hint"));
+                    for (index, step) in recorded.iter().enumerate() {
+                        ui.label(format!("{}. {step}", index + 1));
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(t("macro_name"));
+                        ui.text_edit_singleline(&mut state.new_name);
+                        let can_save = !recorded.is_empty() && !state.new_name.trim().is_empty();
+                        if ui.add_enabled(can_save, egui::Button::new(t("macro_save"))).clicked() {
+                            save = true;
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.label(format!("{}: {}", t("macro_targets"), targets.len()));
+                if saved.is_empty() {
+                    ui.weak(t("macro_none_saved"));
+                }
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    for (index, (name, steps)) in saved.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            let can_run = can_edit && !targets.is_empty() && state.recording.is_none();
+                            if ui.add_enabled(can_run, egui::Button::new(t("macro_run"))).clicked() {
+                                run = Some(index);
+                            }
+                            if ui.add_enabled(can_edit, egui::Button::new("🗑")).on_hover_text(t("delete")).clicked() {
+                                delete = Some(index);
+                            }
+                            ui.strong(name);
+                        });
+                        for step in steps {
+                            ui.weak(format!("    {step}"));
+                        }
+                    }
+                });
+
+                ui.separator();
+                let can_undo = state.last_change.is_some_and(|id| self.undo_history.contains(id)) && can_edit;
+                if ui.add_enabled(can_undo, egui::Button::new(t("macro_undo"))).clicked() {
+                    undo = true;
+                }
+            });
+
+        if start_recording {
+            self.macro_recorder.recording = Some(Vec::new());
+            self.macro_recorder.last_change = None;
+        }
+        if stop_recording {
+            self.macro_recorder.recording = None;
+        }
+        if save && let Some(steps) = self.macro_recorder.recording.take() {
+            let name = self.macro_recorder.new_name.trim().to_string();
+            self.macro_recorder.new_name.clear();
+            self.log.add(format!("{}: {name}", t("log_macro_saved")), LogLevel::Debug);
+            self.tree.macros.push(EditMacro::new(name, steps));
+            self.toasts.info(t("macro_saved"));
+        }
+        if let Some(index) = run {
+            // 再生全体を共通の履歴に1件として記録し、まとめて元に戻せるようにする
+            let edit_macro = self.tree.macros[index].clone();
+            let (count, change) = self.apply_undoable(edit_macro.name.clone(), |tree| edit_macro.replay(tree, &targets));
+            self.macro_recorder.last_change = change;
+            self.toasts.info(format!("{}: {count}", t("macro_applied")));
+            self.log.add(
+                format!("{}: {} ({} × {count})", t("log_macro_applied"), edit_macro.name, targets.len()),
+                LogLevel::Debug,
+            );
+        }
+        if let Some(index) = delete {
+            let removed = self.tree.macros.remove(index);
+            self.log.add(format!("{}: {}", t("log_macro_deleted"), removed.name), LogLevel::Debug);
+        }
+        if undo && let Some(id) = self.macro_recorder.last_change.take() {
+            self.undo_tree_change(Some(id));
+        }
+        if run.is_some() && let Some(person_id) = self.person_editor.selected {
+            self.load_selected_person_into_form(person_id);
+        }
+        if !open {
+            // 閉じたら記録は終える（再生は編集メニューの「元に戻す」から戻せる）
+            self.macro_recorder.show = false;
+            self.macro_recorder.recording = None;
+            self.macro_recorder.last_change = None;
+        }
+    }
+}
+
+impl App {
+    /// 関係の編集をコマンドとして対象の人物に実行し、適用できたかを返す
+    ///
+    /// 実行した操作は共通の元に戻す履歴に1件として記録し、マクロを記録中ならマクロにも加える。
+    /// `then` は同じ履歴の項目に含める付随の編集（人物の移動や養子縁組の詳細など。マクロには残さない）。
+    pub(crate) fn execute_relation_command(
+        &mut self,
+        target: PersonId,
+        step: MacroStep,
+        then: impl FnOnce(&mut FamilyTree),
+    ) -> bool {
+        let label = self.describe_macro_step(&step, &|key: &str| Texts::get(key, self.ui.language));
+        let (applied, _) = self.apply_undoable(label, |tree| {
+            let applied = step.apply(tree, target);
+            if applied {
+                then(tree);
+            }
+            applied
+        });
+        if applied && let Some(steps) = self.macro_recorder.recording.as_mut() {
+            steps.push(step);
+        }
+        applied
+    }
+
+    /// マクロを適用する人物（複数選択があればそれらの人物、なければ選択中の人物）
+    fn macro_targets(&self) -> Vec<PersonId> {
+        if self.person_editor.selected_ids.is_empty() {
+            self.person_editor.selected.into_iter().collect()
+        } else {
+            self.person_editor.selected_ids.to_vec()
+        }
+    }
+
+    fn describe_macro_step(&self, step: &MacroStep, t: &impl Fn(&str) -> String) -> String {
+        let name = |id: &PersonId| self.get_person_name(id);
+        match step {
+            MacroStep::AddParent { parent, kind } => format!("{}: {} ({kind})", t("macro_step_add_parent"), name(parent)),
+            MacroStep::AddChild { child, kind } => format!("{}: {} ({kind})", t("macro_step_add_child"), name(child)),
+            MacroStep::AddSpouse { spouse, .. } => format!("{}: {}", t("macro_step_add_spouse"), name(spouse)),
+            MacroStep::SetParentKind { parent, kind } => {
+                format!("{}: {} → {kind}", t("macro_step_set_parent_kind"), name(parent))
+            }
+            MacroStep::AddToFamily { family } => {
+                format!("{}: {}", t("macro_step_add_to_family"), self.family_name_or_default(*family))
+            }
+        }
+    }
+}
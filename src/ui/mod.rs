@@ -1,19 +1,61 @@
 pub mod state;
 pub mod file_menu;
+pub mod edit_menu;
 pub mod view_menu;
 pub mod help_menu;
 pub mod persons_tab;
+pub mod persons_table;
 pub mod families_tab;
 pub mod events_tab;
+pub mod places_tab;
+pub mod annotations_tab;
 pub mod settings_tab;
+pub mod stats_tab;
+pub mod spouse_editor;
+pub mod export_dialog;
+pub mod shortcuts;
+pub mod theme;
+pub mod log_panel;
+pub mod toasts;
+pub mod status_bar;
+pub mod find_replace_dialog;
+pub mod relation_cleanup_dialog;
+pub mod photo_check_dialog;
+pub mod date_normalization_dialog;
+pub mod macro_dialog;
+pub mod sample_data_dialog;
+pub mod photo_edit_dialog;
+pub mod slideshow_window;
+pub mod place_map_window;
+pub mod detail_panel;
 pub mod canvas;
 
 pub use state::*;
 pub use file_menu::FileMenuRenderer;
+pub use edit_menu::EditMenuRenderer;
 pub use view_menu::ViewMenuRenderer;
 pub use help_menu::HelpMenuRenderer;
 pub use persons_tab::PersonsTabRenderer;
+pub use persons_table::PersonsTableRenderer;
 pub use families_tab::FamiliesTabRenderer;
 pub use events_tab::EventsTabRenderer;
+pub use places_tab::PlacesTabRenderer;
+pub use annotations_tab::AnnotationsTabRenderer;
 pub use settings_tab::SettingsTabRenderer;
+pub use stats_tab::StatsTabRenderer;
+pub use spouse_editor::SpouseEditorRenderer;
+pub use export_dialog::ExportDialogRenderer;
+pub use find_replace_dialog::FindReplaceDialogRenderer;
+pub use relation_cleanup_dialog::RelationCleanupDialogRenderer;
+pub use photo_check_dialog::PhotoCheckDialogRenderer;
+pub use date_normalization_dialog::DateNormalizationDialogRenderer;
+pub use macro_dialog::MacroDialogRenderer;
+pub use sample_data_dialog::SampleDataDialogRenderer;
+pub use photo_edit_dialog::PhotoEditDialogRenderer;
+pub use slideshow_window::SlideshowRenderer;
+pub use place_map_window::PlaceMapRenderer;
+pub use detail_panel::DetailPanelRenderer;
+pub use log_panel::LogPanelRenderer;
+pub use toasts::ToastRenderer;
+pub use status_bar::StatusBarRenderer;
 pub use canvas::*;
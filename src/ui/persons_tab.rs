@@ -1,9 +1,17 @@
 use std::collections::HashMap;
 
+use chrono::Datelike;
 use eframe::egui;
 use crate::app::App;
-use crate::core::tree::{Gender, Person, PersonDisplayMode, PersonId};
-use crate::ui::LogLevel;
+use crate::core::tree::{self, ExternalPersonLink, FamilyTree, Gender, Person, PersonBadge, PersonDeletionPlan, PersonDisplayMode, PersonId, PersonRelationKind, ADOPTIVE_KIND, DEFAULT_PHOTO_PATH};
+use crate::core::bulk_edit::{BulkEdit, BulkEditChange};
+use crate::core::edit_macro::MacroStep;
+use crate::core::filter::{PersonFilter, PersonFilterPreset};
+use crate::core::layout::RelativePlacement;
+use crate::core::event_template::{EventTemplateError, EVENT_TEMPLATES};
+use crate::core::validation::{self, ValidationIssue};
+use crate::ui::shortcuts::{shortcut_button_label, SHORTCUT_SELECTION_BACK, SHORTCUT_SELECTION_FORWARD};
+use crate::ui::{LogLevel, PersonListView, PersonsTableRenderer};
 
 const DEFAULT_RELATION_KIND: &str = "biological";
 
@@ -14,14 +22,18 @@ pub trait PersonsTabRenderer {
 impl PersonsTabRenderer for App {
     fn render_persons_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
         self.render_persons_tab_header(ui, &t);
-        self.render_persons_tab_editor_section(ui, &t);
 
-        // 関係管理（編集モードの場合のみ表示）
-        if let Some(sel) = self.person_editor.selected {
-            self.render_persons_tab_relations_section(ui, sel, &t);
-        }
+        // 閲覧モードでは一覧・検索だけを操作できるようにする
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            self.render_persons_tab_editor_section(ui, &t);
+
+            // 関係管理（編集モードの場合のみ表示）
+            if let Some(sel) = self.person_editor.selected {
+                self.render_persons_tab_relations_section(ui, sel, &t);
+            }
 
-        self.render_persons_tab_actions_section(ui, &t);
+            self.render_persons_tab_actions_section(ui, &t);
+        });
         self.render_persons_tab_footer(ui, &t);
     }
 }
@@ -29,37 +41,209 @@ impl PersonsTabRenderer for App {
 impl App {
     fn render_persons_tab_header(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         ui.heading(t("manage_persons"));
-        if ui.button(t("add_new_person")).clicked() {
-            self.add_new_person(t);
-        }
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("add_new_person"))).clicked() {
+                self.add_new_person(t);
+            }
+            ui.separator();
+            let can_go_back = self.person_editor.history.can_go_back();
+            if ui
+                .add_enabled(can_go_back, egui::Button::new("◀"))
+                .on_hover_text(shortcut_button_label(&t("selection_back"), &SHORTCUT_SELECTION_BACK))
+                .clicked()
+            {
+                self.navigate_selection_back();
+            }
+            let can_go_forward = self.person_editor.history.can_go_forward();
+            if ui
+                .add_enabled(can_go_forward, egui::Button::new("▶"))
+                .on_hover_text(shortcut_button_label(&t("selection_forward"), &SHORTCUT_SELECTION_FORWARD))
+                .clicked()
+            {
+                self.navigate_selection_forward();
+            }
+        });
+
+        self.render_persons_list(ui, t);
         ui.separator();
     }
 
+    /// 人物一覧（キャンバスでの選択と相互に同期する）
+    fn render_persons_list(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let selected = self.person_editor.selected;
+        // キャンバスなど一覧以外で選択が変わった場合だけスクロールさせる
+        let scroll_to_selected = selected != self.person_editor.list_synced_selection;
+        let mut clicked = None;
+
+        egui::CollapsingHeader::new(format!("{} ({})", t("persons_list"), self.tree.persons.len()))
+            .id_salt("persons_list")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.person_editor.list_view, PersonListView::List, t("list_view"));
+                    ui.selectable_value(&mut self.person_editor.list_view, PersonListView::Table, t("table_view"));
+                });
+                self.render_person_filter_controls(ui, t);
+                clicked = match self.person_editor.list_view {
+                    PersonListView::List => self.render_persons_simple_list(ui, scroll_to_selected),
+                    PersonListView::Table => self.render_persons_table(ui, scroll_to_selected, t),
+                };
+            });
+        self.person_editor.list_synced_selection = selected;
+
+        let Some((person_id, toggle)) = clicked else {
+            return;
+        };
+        if toggle {
+            // Ctrl+クリックで複数選択に追加・解除
+            let selected_ids = &mut self.person_editor.selected_ids;
+            if let Some(index) = selected_ids.iter().position(|id| *id == person_id) {
+                selected_ids.remove(index);
+            } else {
+                selected_ids.push(person_id);
+            }
+            self.person_editor.selected = selected_ids.last().copied();
+            self.person_editor.list_synced_selection = self.person_editor.selected;
+            if let Some(last_id) = self.person_editor.selected {
+                self.load_selected_person_into_form(last_id);
+            }
+            return;
+        }
+
+        self.select_person_from_list(person_id);
+    }
+
+    /// 一覧で選んだ人物を選択し、キャンバスをその人物に合わせる
+    pub(crate) fn select_person_from_list(&mut self, person_id: PersonId) {
+        self.person_editor.selected = Some(person_id);
+        self.person_editor.selected_ids = vec![person_id];
+        self.person_editor.list_synced_selection = Some(person_id);
+        self.load_selected_person_into_form(person_id);
+        self.center_on_person(person_id);
+    }
+
+    fn render_person_filter_controls(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let mut submitted_id = None;
+        ui.horizontal(|ui| {
+            ui.label(t("search"));
+            let response = ui
+                .text_edit_singleline(&mut self.person_editor.filter.query)
+                .on_hover_text(t("person_search_hint"));
+            // 短縮IDを貼り付けて Enter を押したら、その人物を選択する（IDでなければ名前の絞り込みのまま）
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submitted_id = self.tree.find_person_by_id(&self.person_editor.filter.query);
+            }
+        });
+        if let Some(person_id) = submitted_id {
+            self.select_person_from_list(person_id);
+            self.toasts.info(t("person_found"));
+        }
+        let filter = &mut self.person_editor.filter;
+        ui.horizontal(|ui| {
+            ui.label(t("filter"));
+            let preset_label = |preset: PersonFilterPreset| match preset {
+                PersonFilterPreset::All => t("filter_all"),
+                PersonFilterPreset::AliveOnly => t("filter_alive_only"),
+                PersonFilterPreset::DeceasedOnly => t("filter_deceased_only"),
+                PersonFilterPreset::NoBirthDate => t("filter_no_birth_date"),
+                PersonFilterPreset::HasPhoto => t("filter_has_photo"),
+                PersonFilterPreset::InFamily(family_id) => {
+                    let family_name = self
+                        .tree
+                        .get_family(family_id)
+                        .map(|family| family.name.clone())
+                        .unwrap_or_else(|| t("unknown"));
+                    format!("{}: {}", t("filter_in_family"), family_name)
+                }
+            };
+            egui::ComboBox::from_id_salt("person_filter_preset")
+                .selected_text(preset_label(filter.preset))
+                .show_ui(ui, |ui| {
+                    let presets = [
+                        PersonFilterPreset::All,
+                        PersonFilterPreset::AliveOnly,
+                        PersonFilterPreset::DeceasedOnly,
+                        PersonFilterPreset::NoBirthDate,
+                        PersonFilterPreset::HasPhoto,
+                    ]
+                    .into_iter()
+                    .chain(self.tree.families.iter().map(|family| PersonFilterPreset::InFamily(family.id)));
+                    for preset in presets {
+                        ui.selectable_value(&mut filter.preset, preset, preset_label(preset));
+                    }
+                });
+            if filter.is_active() && ui.small_button("✖").on_hover_text(t("clear_filter")).clicked() {
+                *filter = PersonFilter::default();
+            }
+        });
+        ui.checkbox(&mut self.person_editor.apply_filter_to_canvas, t("apply_filter_to_canvas"));
+    }
+
+    fn render_persons_simple_list(&self, ui: &mut egui::Ui, scroll_to_selected: bool) -> Option<(PersonId, bool)> {
+        let filter = &self.person_editor.filter;
+        let presumption = self.ui.deceased_presumption();
+        let mut entries: Vec<(PersonId, &str, i32)> = self
+            .tree
+            .persons
+            .values()
+            .filter_map(|person| {
+                filter
+                    .score(&self.tree, person, presumption)
+                    .map(|score| (person.id, person.name.as_str(), score))
+            })
+            .collect();
+        // 検索中は一致度の高い順、それ以外は名前順
+        entries.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(b.1)));
+
+        let mut clicked = None;
+        egui::ScrollArea::vertical()
+            .id_salt("persons_list_scroll")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for (person_id, name, _) in entries {
+                    let is_selected = self.person_editor.selected == Some(person_id);
+                    let response = ui.selectable_label(is_selected, name);
+                    if is_selected && scroll_to_selected {
+                        response.scroll_to_me(Some(egui::Align::Center));
+                    }
+                    if response.clicked() {
+                        clicked = Some((person_id, false));
+                    }
+                }
+            });
+        clicked
+    }
+
     fn add_new_person(&mut self, t: &impl Fn(&str) -> String) {
         let visible_left_top = self.visible_canvas_left_top();
+        let defaults = self.ui.new_person_defaults.clone();
+        let name = defaults.next_name(&self.tree, &t("new_person"));
         let person_id = self.tree.add_person(
-            t("new_person"),
-            Gender::Unknown,
+            name.clone(),
+            defaults.gender,
             None,
             String::new(),
             false,
             None,
             visible_left_top,
         );
+        if let Some(person) = self.tree.persons.get_mut(&person_id) {
+            person.display_mode = defaults.display_mode;
+        }
         self.person_editor.selected = Some(person_id);
         self.load_selected_person_into_form(person_id);
-        self.file.status = t("new_person_added");
+        self.toasts.info(t("new_person_added"));
         self.log
             .add(
-                format!("{}: {}", t("log_person_added"), t("new_person")),
+                format!("{}: {}", t("log_person_added"), name),
                 LogLevel::Debug,
             );
     }
 
-    fn load_selected_person_into_form(&mut self, person_id: PersonId) {
+    pub(crate) fn load_selected_person_into_form(&mut self, person_id: PersonId) {
         if let Some(person) = self.tree.persons.get(&person_id) {
             self.person_editor.new_name = person.name.clone();
-            self.person_editor.new_gender = person.gender;
+            self.person_editor.new_gender = person.gender.clone();
             self.person_editor.new_birth = person.birth.clone().unwrap_or_default();
             self.person_editor.new_memo = person.memo.clone();
             self.person_editor.new_deceased = person.deceased;
@@ -67,6 +251,11 @@ impl App {
             self.person_editor.new_photo_path = person.photo_path.clone().unwrap_or_default();
             self.person_editor.new_display_mode = person.display_mode;
             self.person_editor.new_photo_scale = person.photo_scale;
+            self.person_editor.new_birth_place = person.birth_place;
+            self.person_editor.new_death_place = person.death_place;
+            let link = person.external_link.clone();
+            self.person_editor.new_external_tree = link.as_ref().map(|link| link.tree.clone()).unwrap_or_default();
+            self.person_editor.new_external_person = link.map(|link| link.person_id).unwrap_or_default();
         }
     }
 
@@ -75,10 +264,135 @@ impl App {
         self.render_person_basic_fields(ui, t);
         self.render_person_photo_fields(ui, t);
         self.render_person_display_fields(ui, t);
+        self.render_person_external_link_fields(ui, t);
     }
 
     fn render_persons_tab_actions_section(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         self.render_person_action_buttons(ui, t);
+        self.render_bulk_edit(ui, t);
+    }
+
+    /// 複数選択した人物の一括編集
+    fn render_bulk_edit(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let selected_ids = self.person_editor.selected_ids.clone();
+        if selected_ids.len() < 2 {
+            return;
+        }
+
+        let mut apply = false;
+        ui.horizontal(|ui| {
+            ui.menu_button(format!("{} ({})", t("bulk_edit"), selected_ids.len()), |ui| {
+                let edit = &mut self.person_editor.bulk_edit;
+                egui::Grid::new("bulk_edit_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(t("bulk_edit_family"));
+                    let family_name = edit
+                        .family
+                        .and_then(|family_id| self.tree.get_family(family_id))
+                        .map(|family| family.name.clone())
+                        .unwrap_or_else(|| t("bulk_edit_keep"));
+                    egui::ComboBox::from_id_salt("bulk_edit_family")
+                        .selected_text(family_name)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut edit.family, None, t("bulk_edit_keep"));
+                            for family in &self.tree.families {
+                                ui.selectable_value(&mut edit.family, Some(family.id), &family.name);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(t("deceased"));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut edit.deceased, None, t("bulk_edit_keep"));
+                        ui.selectable_value(&mut edit.deceased, Some(true), t("yes"));
+                        ui.selectable_value(&mut edit.deceased, Some(false), t("no"));
+                    });
+                    ui.end_row();
+
+                    ui.label(t("display_mode"));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut edit.display_mode, None, t("bulk_edit_keep"));
+                        ui.selectable_value(&mut edit.display_mode, Some(PersonDisplayMode::NameOnly), t("name_only"));
+                        ui.selectable_value(
+                            &mut edit.display_mode,
+                            Some(PersonDisplayMode::NameAndPhoto),
+                            t("name_and_photo"),
+                        );
+                        ui.selectable_value(&mut edit.display_mode, Some(PersonDisplayMode::PhotoOnly), t("photo_only"));
+                    });
+                    ui.end_row();
+                });
+
+                // 適用前に、人物ごとに変わる項目を変更前 → 変更後で示す
+                let changes = edit.preview(&self.tree, &selected_ids);
+                if !edit.is_empty() {
+                    ui.separator();
+                    if changes.is_empty() {
+                        ui.weak(t("bulk_edit_no_changes"));
+                    } else {
+                        ui.label(format!("{}: {}", t("bulk_edit_preview"), changes.len()));
+                        egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                            for change in &changes {
+                                ui.label(Self::describe_bulk_edit_change(&self.tree, change, t));
+                            }
+                        });
+                    }
+                }
+
+                if ui.add_enabled(!changes.is_empty(), egui::Button::new(t("apply"))).clicked() {
+                    apply = true;
+                    ui.close();
+                }
+            });
+
+            // 同じ人物を選択している間だけ元に戻せる（戻すのは一括編集で変えた項目だけ）
+            let undo_id = self
+                .person_editor
+                .bulk_edit_undo
+                .as_ref()
+                .filter(|(ids, id)| *ids == selected_ids && self.undo_history.contains(*id))
+                .map(|(_, id)| *id);
+            if let Some(id) = undo_id
+                && ui.button(t("undo_bulk_edit")).clicked()
+            {
+                self.person_editor.bulk_edit_undo = None;
+                self.undo_tree_change(Some(id));
+            }
+        });
+
+        if apply {
+            let edit = self.person_editor.bulk_edit;
+            let (changed, undo_id) = self.apply_undoable(t("bulk_edit"), |tree| edit.apply(tree, &selected_ids));
+            self.person_editor.bulk_edit_undo = undo_id.map(|id| (selected_ids, id));
+            self.person_editor.bulk_edit = BulkEdit::default();
+            self.toasts.info(format!("{}: {}", t("bulk_edit_done"), changed));
+            self.log.add(format!("{}: {}", t("bulk_edit_done"), changed), LogLevel::Debug);
+            if let Some(person_id) = self.person_editor.selected {
+                self.load_selected_person_into_form(person_id);
+            }
+        }
+    }
+
+    /// 一括編集のプレビューの1行（例: "Taro – Deceased: No → Yes"）
+    fn describe_bulk_edit_change(tree: &FamilyTree, change: &BulkEditChange, t: &impl Fn(&str) -> String) -> String {
+        let yes_no = |value: bool| if value { t("yes") } else { t("no") };
+        let display_mode = |mode: PersonDisplayMode| match mode {
+            PersonDisplayMode::NameOnly => t("name_only"),
+            PersonDisplayMode::NameAndPhoto => t("name_and_photo"),
+            PersonDisplayMode::PhotoOnly => t("photo_only"),
+        };
+        let name = tree.persons.get(&change.person()).map(|person| person.name.as_str()).unwrap_or_default();
+        let detail = match *change {
+            BulkEditChange::AddToFamily { family, .. } => format!(
+                "{} {}",
+                t("bulk_edit_family"),
+                tree.get_family(family).map(|family| family.name.as_str()).unwrap_or_default()
+            ),
+            BulkEditChange::Deceased { before, after, .. } => format!("{}: {} → {}", t("deceased"), yes_no(before), yes_no(after)),
+            BulkEditChange::DisplayMode { before, after, .. } => {
+                format!("{}: {} → {}", t("display_mode"), display_mode(before), display_mode(after))
+            }
+        };
+        format!("{name} – {detail}")
     }
 
     fn render_person_editor_heading(&self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
@@ -88,6 +402,16 @@ impl App {
             .and_then(|id| self.tree.persons.get(&id))
         {
             ui.heading(format!("{} {}", t("edit"), person.name));
+
+            // 外部資料から参照するための短縮ID
+            let short_id = tree::short_id(&person.id);
+            ui.horizontal(|ui| {
+                ui.label(t("person_id"));
+                ui.monospace(&short_id).on_hover_text(person.id.to_string());
+                if ui.small_button("📋").on_hover_text(t("copy_id")).clicked() {
+                    ui.ctx().copy_text(short_id.clone());
+                }
+            });
             return;
         }
         ui.heading(t("person_editor"));
@@ -111,22 +435,58 @@ impl App {
                 Gender::Unknown,
                 t("unknown"),
             );
+            let is_other = matches!(self.person_editor.new_gender, Gender::Other(_));
+            if ui.radio(is_other, t("other_gender")).clicked() && !is_other {
+                self.person_editor.new_gender = Gender::Other(String::new());
+            }
         });
+        // その他の性別は自由記述で表記を入力
+        if let Gender::Other(label) = &mut self.person_editor.new_gender {
+            ui.horizontal(|ui| {
+                ui.label(t("gender_label"));
+                ui.text_edit_singleline(label);
+            });
+        }
         ui.horizontal(|ui| {
             ui.label(t("birth"));
             ui.text_edit_singleline(&mut self.person_editor.new_birth);
         });
-        ui.checkbox(&mut self.person_editor.new_deceased, t("deceased"));
+        ui.horizontal(|ui| {
+            ui.label(t("birth_place"));
+            App::render_place_picker(ui, &self.tree.places, "person_birth_place", &mut self.person_editor.new_birth_place, t);
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.person_editor.new_deceased, t("deceased"));
+            if self.is_selected_person_presumed_deceased() {
+                ui.weak(t("presumed_deceased_marker"))
+                    .on_hover_text(t("tooltip_presumed_deceased"));
+            }
+        });
         if self.person_editor.new_deceased {
             ui.horizontal(|ui| {
                 ui.label(t("death"));
                 ui.text_edit_singleline(&mut self.person_editor.new_death);
             });
+            ui.horizontal(|ui| {
+                ui.label(t("death_place"));
+                App::render_place_picker(ui, &self.tree.places, "person_death_place", &mut self.person_editor.new_death_place, t);
+            });
         }
         ui.label(t("memo"));
         ui.text_edit_multiline(&mut self.person_editor.new_memo);
     }
 
+    fn is_selected_person_presumed_deceased(&self) -> bool {
+        let Some(threshold_years) = self.ui.presumed_deceased_threshold() else {
+            return false;
+        };
+        let current_year = chrono::Local::now().year();
+        self.person_editor
+            .selected
+            .and_then(|id| self.tree.persons.get(&id))
+            .is_some_and(|person| person.is_presumed_deceased(threshold_years, current_year))
+    }
+
     fn render_person_photo_fields(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         ui.horizontal(|ui| {
             ui.label(t("photo_path"));
@@ -136,15 +496,49 @@ impl App {
                     .add_filter(t("file_filter_images"), &["png", "jpg", "jpeg", "bmp", "gif"])
                     .pick_file()
                 {
-                    self.person_editor.new_photo_path = path.display().to_string();
+                    // 取り込む前に切り抜き・回転できるようにする
+                    self.photo_edit.open(path.display().to_string());
                 }
             }
+            let current_path = self.person_editor.new_photo_path.trim().to_string();
+            if !current_path.is_empty() && current_path != DEFAULT_PHOTO_PATH && ui.button(t("edit_photo")).clicked() {
+                self.photo_edit.open(current_path);
+            }
             if !self.person_editor.new_photo_path.is_empty() && ui.button(t("clear_photo")).clicked() {
                 self.person_editor.new_photo_path.clear();
             }
         });
     }
 
+    /// 別の家系図ファイルにいる同じ人物へのリンク
+    fn render_person_external_link_fields(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        ui.horizontal(|ui| {
+            ui.label(t("linked_tree"));
+            ui.add(egui::TextEdit::singleline(&mut self.person_editor.new_external_tree).hint_text(t("linked_tree_hint")));
+            if ui.button(t("choose_linked_tree")).clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter(t("file_filter_family_tree"), &["json", "yaml", "yml", "sqlite", "db", "gramps", "ged"])
+                    .pick_file()
+            {
+                self.person_editor.new_external_tree = path.display().to_string();
+            }
+        });
+        if self.person_editor.new_external_tree.trim().is_empty() {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label(t("linked_person_id"));
+            ui.text_edit_singleline(&mut self.person_editor.new_external_person);
+            if ui.button(t("open_linked_tree")).clicked() {
+                let link = ExternalPersonLink {
+                    tree: self.person_editor.new_external_tree.trim().to_string(),
+                    person_id: self.person_editor.new_external_person.trim().to_string(),
+                };
+                self.open_linked_tree(link, ui.ctx());
+            }
+        });
+    }
+
     fn render_person_display_fields(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
         ui.horizontal(|ui| {
             ui.label(t("display_mode"));
@@ -158,9 +552,14 @@ impl App {
                 PersonDisplayMode::NameAndPhoto,
                 t("name_and_photo"),
             );
+            ui.radio_value(
+                &mut self.person_editor.new_display_mode,
+                PersonDisplayMode::PhotoOnly,
+                t("photo_only"),
+            );
         });
 
-        if self.person_editor.new_display_mode == PersonDisplayMode::NameAndPhoto {
+        if self.person_editor.new_display_mode.shows_photo() {
             ui.horizontal(|ui| {
                 ui.label(t("photo_scale"));
                 ui.add(egui::Slider::new(&mut self.person_editor.new_photo_scale, 0.1..=3.0).text("×"));
@@ -180,14 +579,73 @@ impl App {
                 self.cancel_person_edit();
             }
             if ui.button(t("delete")).clicked() {
-                self.delete_selected_person(t);
+                self.person_editor.pending_delete = self.person_editor.selected;
+            }
+            if ui.button(t("show_on_canvas")).clicked()
+                && let Some(person_id) = self.person_editor.selected
+            {
+                self.center_on_person(person_id);
             }
         });
+        self.render_person_delete_confirmation(ui, t);
+    }
+
+    /// 削除の影響（関係・家族グループ・イベント）を示し、確定したら削除する
+    fn render_person_delete_confirmation(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let Some(person_id) = self.person_editor.pending_delete else {
+            return;
+        };
+        if self.person_editor.selected != Some(person_id) {
+            self.person_editor.pending_delete = None;
+            return;
+        }
+        let Some(plan) = self.tree.plan_person_deletion(person_id, self.person_editor.delete_orphans) else {
+            self.person_editor.pending_delete = None;
+            return;
+        };
+
+        let mut confirm = false;
+        let mut cancel = false;
+        ui.group(|ui| {
+            ui.label(format!("{}: {}", t("delete_confirm"), self.get_person_name(&person_id)));
+            let counts = [
+                ("delete_parent_child_links", plan.parent_child_links),
+                ("delete_spouse_links", plan.spouse_links),
+                ("delete_person_relation_links", plan.person_relation_links),
+                ("delete_event_links", plan.event_links),
+            ];
+            for (key, count) in counts.into_iter().filter(|(_, count)| *count > 0) {
+                ui.weak(format!("{}: {}", t(key), count));
+            }
+            if !plan.families.is_empty() {
+                let names: Vec<String> = plan.families.iter().map(|id| self.family_name_or_default(*id)).collect();
+                ui.weak(format!("{}: {}", t("delete_affected_families"), names.join(", ")));
+            }
+
+            if !plan.orphan_candidates.is_empty() {
+                let names: Vec<String> = plan.orphan_candidates.iter().map(|id| self.get_person_name(id)).collect();
+                ui.checkbox(&mut self.person_editor.delete_orphans, t("delete_placeholder_relatives"))
+                    .on_hover_text(t("delete_placeholder_relatives_hint"));
+                ui.weak(names.join(", "));
+            }
+
+            ui.horizontal(|ui| {
+                confirm = ui.add_enabled(!self.ui.read_only, egui::Button::new(t("delete"))).clicked();
+                cancel = ui.button(t("cancel")).clicked();
+            });
+        });
+
+        if confirm {
+            self.delete_selected_person(&plan, t);
+        }
+        if confirm || cancel {
+            self.person_editor.pending_delete = None;
+        }
     }
 
     fn update_selected_person(&mut self, t: &impl Fn(&str) -> String) {
         if self.person_editor.new_name.trim().is_empty() {
-            self.file.status = t("name_required");
+            self.toasts.warning(t("name_required"));
             return;
         }
 
@@ -197,7 +655,7 @@ impl App {
 
         if let Some(person) = self.tree.persons.get_mut(&person_id) {
             person.name = self.person_editor.new_name.trim().to_string();
-            person.gender = self.person_editor.new_gender;
+            person.gender = self.person_editor.new_gender.clone();
             person.birth = App::parse_optional_field(&self.person_editor.new_birth);
             person.memo = self.person_editor.new_memo.clone();
             person.deceased = self.person_editor.new_deceased;
@@ -213,7 +671,16 @@ impl App {
             };
             person.display_mode = self.person_editor.new_display_mode;
             person.photo_scale = self.person_editor.new_photo_scale.clamp(0.1, 3.0);
-            self.file.status = t("person_updated");
+            person.birth_place = self.person_editor.new_birth_place;
+            person.death_place = self.person_editor.new_deceased.then_some(self.person_editor.new_death_place).flatten();
+            let external_tree = self.person_editor.new_external_tree.trim();
+            person.external_link = (!external_tree.is_empty()).then(|| ExternalPersonLink {
+                tree: external_tree.to_string(),
+                person_id: self.person_editor.new_external_person.trim().to_string(),
+            });
+            // 性別で父母の組が決まるので、家族の単位を組み直す
+            self.tree.sync_unions();
+            self.toasts.info(t("person_updated"));
         }
     }
 
@@ -222,22 +689,21 @@ impl App {
         self.clear_person_form();
     }
 
-    fn delete_selected_person(&mut self, t: &impl Fn(&str) -> String) {
-        let Some(person_id) = self.person_editor.selected else {
-            return;
-        };
-
-        let person_name = self.get_person_name(&person_id);
-        self.tree.remove_person(person_id);
+    fn delete_selected_person(&mut self, plan: &PersonDeletionPlan, t: &impl Fn(&str) -> String) {
+        let person_name = self.get_person_name(&plan.person);
+        self.tree.apply_deletion_plan(plan);
         self.person_editor.selected = None;
         self.person_editor.selected_ids.clear();
         self.clear_person_form();
-        self.file.status = t("deleted");
+        self.toasts.info(t("deleted"));
         self.log
             .add(
                 format!("{}: {}", t("log_person_deleted"), person_name),
                 LogLevel::Debug,
             );
+        if !plan.orphans.is_empty() {
+            self.log.add(format!("{}: {}", t("log_placeholder_relatives_deleted"), plan.orphans.len()), LogLevel::Debug);
+        }
     }
 
     fn render_persons_tab_footer(&self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
@@ -282,41 +748,85 @@ impl App {
 
     fn relation_kind_or_default(&self) -> String {
         let kind = self.relation_editor.relation_kind.trim();
-        if kind.is_empty() {
-            DEFAULT_RELATION_KIND.to_string()
-        } else {
+        let default_kind = self.ui.new_person_defaults.relation_kind.trim();
+        if !kind.is_empty() {
             kind.to_string()
+        } else if !default_kind.is_empty() {
+            default_kind.to_string()
+        } else {
+            DEFAULT_RELATION_KIND.to_string()
         }
     }
 
-    fn start_parent_kind_edit(&mut self, parent_id: PersonId, child_id: PersonId, current_kind: &str) {
+    fn start_parent_kind_edit(&mut self, parent_id: PersonId, child_id: PersonId) {
+        let Some(edge) = self.tree.parent_child_relation(parent_id, child_id) else {
+            return;
+        };
         self.relation_editor.editing_parent_kind = Some((parent_id, child_id));
-        self.relation_editor.temp_kind = current_kind.to_string();
+        self.relation_editor.temp_kind = edge.kind.clone();
+        self.relation_editor.temp_adoption_date = edge.adoption_date.clone().unwrap_or_default();
+        self.relation_editor.temp_adoption_type = edge.adoption_type.clone();
+        self.relation_editor.temp_adoption_note = edge.adoption_note.clone();
     }
 
     fn clear_parent_kind_edit(&mut self) {
         self.relation_editor.editing_parent_kind = None;
         self.relation_editor.temp_kind.clear();
+        self.relation_editor.temp_adoption_date.clear();
+        self.relation_editor.temp_adoption_type.clear();
+        self.relation_editor.temp_adoption_note.clear();
     }
 
     fn remove_parent_relation(&mut self, parent_id: PersonId, child_id: PersonId, t: &impl Fn(&str) -> String) {
         self.tree.remove_parent_child(parent_id, child_id);
-        self.file.status = t("relation_removed");
+        self.toasts.info(t("relation_removed"));
     }
 
     fn save_parent_relation_kind(&mut self, parent_id: PersonId, child_id: PersonId, t: &impl Fn(&str) -> String) {
-        if let Some(edge) = self
-            .tree
-            .edges
-            .iter_mut()
-            .find(|edge| edge.parent == parent_id && edge.child == child_id)
-        {
-            edge.kind = if self.relation_editor.temp_kind.trim().is_empty() {
-                "biological".to_string()
+        let kind = if self.relation_editor.temp_kind.trim().is_empty() {
+            "biological".to_string()
+        } else {
+            self.relation_editor.temp_kind.trim().to_string()
+        };
+        let adoption_date = App::parse_optional_field(&self.relation_editor.temp_adoption_date);
+        let adoption_type = self.relation_editor.temp_adoption_type.trim().to_string();
+        let adoption_note = self.relation_editor.temp_adoption_note.clone();
+
+        // 養子縁組の詳細は養子関係の場合のみ保持する（マクロには種類だけを記録する）
+        let step = MacroStep::SetParentKind { parent: parent_id, kind };
+        let applied = self.execute_relation_command(child_id, step, |tree| {
+            let Some(edge) = tree.parent_child_relation_mut(parent_id, child_id) else {
+                return;
+            };
+            if edge.is_adoptive() {
+                edge.adoption_date = adoption_date;
+                edge.adoption_type = adoption_type;
+                edge.adoption_note = adoption_note;
             } else {
-                self.relation_editor.temp_kind.trim().to_string()
+                edge.adoption_date = None;
+                edge.adoption_type.clear();
+                edge.adoption_note.clear();
+            }
+        });
+        if applied {
+            self.toasts.info(t("relation_kind_updated"));
+
+            let issue = ValidationIssue::AdoptionBeforeBirth {
+                parent: parent_id,
+                child: child_id,
             };
-            self.file.status = t("relation_kind_updated");
+            if validation::validate_tree(&self.tree).contains(&issue) {
+                self.toasts.warning(t("adoption_before_birth"));
+                self.log.add(
+                    format!(
+                        "{}: {} - {}",
+                        t("log_adoption_before_birth"),
+                        self.get_person_name(&parent_id),
+                        self.get_person_name(&child_id)
+                    ),
+                    LogLevel::Warning,
+                );
+            }
         }
         self.clear_parent_kind_edit();
     }
@@ -333,7 +843,7 @@ impl App {
 
     fn remove_spouse_relation(&mut self, person1: PersonId, person2: PersonId, t: &impl Fn(&str) -> String) {
         self.tree.remove_spouse(person1, person2);
-        self.file.status = t("relation_removed");
+        self.toasts.info(t("relation_removed"));
     }
 
     fn save_spouse_relation_memo(&mut self, person1: PersonId, person2: PersonId, t: &impl Fn(&str) -> String) {
@@ -347,7 +857,7 @@ impl App {
             })
         {
             spouse_relation.memo = self.relation_editor.temp_spouse_memo.clone();
-            self.file.status = t("spouse_memo_updated");
+            self.toasts.info(t("spouse_memo_updated"));
         }
         self.clear_spouse_memo_edit();
     }
@@ -374,7 +884,7 @@ impl App {
                 match parent.gender {
                     Gender::Male => fathers.push((*parent_id, parent.name.clone())),
                     Gender::Female => mothers.push((*parent_id, parent.name.clone())),
-                    Gender::Unknown => other_parents.push((*parent_id, parent.name.clone())),
+                    Gender::Unknown | Gender::Other(_) => other_parents.push((*parent_id, parent.name.clone())),
                 }
             }
         }
@@ -391,8 +901,330 @@ impl App {
         // 配偶者の表示
         self.render_spouse_relations(ui, sel, t);
 
+        // 子の表示（もう一方の親ごと）
+        self.render_child_relations(ui, sel, t);
+
+        // 導出した継親・継子の表示
+        if self.ui.show_step_relations {
+            self.render_step_relations(ui, sel, t);
+        }
+
+        // 後見人・名付け親などの関係の表示
+        self.render_person_relations(ui, sel, t);
+
+        // 目印（ノードの隅に表示する記号）
+        self.render_badges_section(ui, sel, t);
+
+        // ホームの人物の設定
+        self.render_home_person_toggle(ui, sel, t);
+
+        // 多胎（双子など）の表示と登録
+        self.render_multiple_birth_section(ui, sel, t);
+
+        // 確認事項の付箋
+        self.render_sticky_notes_section(ui, sel, t);
+
+        // テンプレートから法要などのイベントを作る
+        self.render_event_template_section(ui, sel, t);
+
         // 新しい関係を追加
         self.render_add_relations(ui, sel, &all_ids, t);
+        self.render_add_person_relation(ui, sel, &all_ids, t);
+    }
+
+    fn render_person_relations(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        let relations: Vec<_> = self
+            .tree
+            .person_relations_of(sel)
+            .into_iter()
+            .cloned()
+            .collect();
+        if relations.is_empty() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(t("person_relations"));
+        });
+
+        for relation in relations {
+            // 相手側の人物とその役割
+            let other_is_holder = relation.to == sel;
+            let other_id = if other_is_holder { relation.from } else { relation.to };
+            let other_name = self.get_person_name(&other_id);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", t(relation.kind.role_key(other_is_holder))));
+                if ui.small_button(&other_name).clicked() {
+                    self.person_editor.selected = Some(other_id);
+                }
+                if !relation.memo.is_empty() {
+                    ui.label(format!("({})", relation.memo));
+                }
+                if ui.small_button("❌").on_hover_text(t("remove_relation")).clicked() {
+                    self.tree
+                        .remove_person_relation(relation.from, relation.to, relation.kind);
+                    self.toasts.info(t("relation_removed"));
+                }
+            });
+        }
+    }
+
+    fn render_sticky_notes_section(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        ui.label(t("sticky_notes"));
+
+        let notes: Vec<_> = self.tree.sticky_notes_of(sel).into_iter().cloned().collect();
+        for note in notes {
+            ui.horizontal(|ui| {
+                let mut resolved = note.resolved;
+                if ui
+                    .checkbox(&mut resolved, "")
+                    .on_hover_text(t("sticky_note_resolved"))
+                    .changed()
+                    && let Some(note) = self.tree.sticky_note_mut(note.id)
+                {
+                    note.resolved = resolved;
+                }
+                if note.resolved {
+                    ui.label(egui::RichText::new(&note.text).strikethrough().weak());
+                } else {
+                    ui.label(&note.text);
+                }
+                if ui.small_button("❌").on_hover_text(t("delete")).clicked() {
+                    self.tree.remove_sticky_note(note.id);
+                    self.toasts.info(t("sticky_note_deleted"));
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.person_editor.new_sticky_note);
+            if ui.button(t("add_sticky_note")).clicked() && !self.person_editor.new_sticky_note.trim().is_empty() {
+                let text = std::mem::take(&mut self.person_editor.new_sticky_note);
+                self.tree.add_sticky_note(sel, text.trim().to_string());
+                self.toasts.info(t("sticky_note_added"));
+                self.log.add(
+                    format!("{}: {} - {}", t("log_sticky_note_added"), self.get_person_name(&sel), text.trim()),
+                    LogLevel::Debug,
+                );
+            }
+        });
+    }
+
+    fn render_home_person_toggle(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        let is_home = self.tree.home_person == Some(sel);
+        let mut checked = is_home;
+        let response = ui.add_enabled(!self.ui.read_only, egui::Checkbox::new(&mut checked, t("home_person")));
+        if !response.on_hover_text(t("home_person_hint")).changed() {
+            return;
+        }
+        self.tree.home_person = checked.then_some(sel);
+        let key = if checked { "log_home_person_set" } else { "log_home_person_cleared" };
+        self.log.add(format!("{}: {}", t(key), self.get_person_name(&sel)), LogLevel::Debug);
+    }
+
+    fn render_event_template_section(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        ui.horizontal(|ui| {
+            ui.label(t("event_templates"));
+            let selected = &mut self.person_editor.event_template;
+            *selected = (*selected).min(EVENT_TEMPLATES.len() - 1);
+            egui::ComboBox::from_id_salt("event_template_pick")
+                .selected_text(t(EVENT_TEMPLATES[*selected].key))
+                .show_ui(ui, |ui| {
+                    for (index, template) in EVENT_TEMPLATES.iter().enumerate() {
+                        ui.selectable_value(selected, index, t(template.key));
+                    }
+                });
+            let response = ui.add_enabled(!self.ui.read_only, egui::Button::new(t("apply_event_template")));
+            if !response.on_hover_text(t("event_template_hint")).clicked() {
+                return;
+            }
+            let template = EVENT_TEMPLATES[self.person_editor.event_template];
+            match template.apply(&mut self.tree, sel, self.ui.language) {
+                Ok(created) if created.is_empty() => self.toasts.info(t("event_template_nothing_new")),
+                Ok(created) => {
+                    let message = format!("{}: {} ({})", t("event_template_applied"), t(template.key), created.len());
+                    self.toasts.info(message.clone());
+                    self.log.add(format!("{} – {}", message, self.get_person_name(&sel)), LogLevel::Debug);
+                }
+                Err(EventTemplateError::MissingBaseDate) => self.toasts.warning(t("event_template_missing_date")),
+                Err(EventTemplateError::UnknownPerson) => {}
+            }
+        });
+    }
+
+    fn render_badges_section(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        let badges = self.tree.persons.get(&sel).map(|p| p.badges.clone()).unwrap_or_default();
+        let mut toggled = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(t("person_badges"));
+            ui.add_enabled_ui(!self.ui.read_only, |ui| {
+                for badge in PersonBadge::ALL {
+                    let label = t(&format!("badge_{}", badge.key()));
+                    if ui
+                        .selectable_label(badges.contains(&badge), badge.glyph())
+                        .on_hover_text(label)
+                        .clicked()
+                    {
+                        toggled = Some(badge);
+                    }
+                }
+            });
+        });
+        if let Some(badge) = toggled {
+            let glyph = badge.glyph();
+            self.tree.toggle_person_badge(sel, badge);
+            self.log.add(
+                format!(
+                    "{}: {} {}",
+                    t("log_badge_toggled"),
+                    self.get_person_name(&sel),
+                    glyph
+                ),
+                LogLevel::Debug,
+            );
+        }
+    }
+
+    fn render_multiple_birth_section(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        let multiple_birth_siblings = self.tree.multiple_birth_siblings_of(sel);
+        let date_mismatch = self
+            .tree
+            .persons
+            .get(&sel)
+            .and_then(|p| p.multiple_birth_group)
+            .is_some_and(|group| {
+                validation::validate_tree(&self.tree)
+                    .contains(&ValidationIssue::MultipleBirthDateMismatch { group })
+            });
+
+        // きょうだいの中から同じ出産の相手を選んで登録できる
+        let siblings: Vec<PersonId> = self
+            .tree
+            .siblings_of(sel)
+            .into_iter()
+            .filter(|id| !multiple_birth_siblings.contains(id))
+            .collect();
+        let selected_ids = self.person_editor.selected_ids.clone();
+        if multiple_birth_siblings.is_empty() && siblings.is_empty() && selected_ids.len() < 2 {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(t("multiple_birth"));
+            if date_mismatch {
+                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "⚠")
+                    .on_hover_text(t("multiple_birth_date_mismatch"));
+            }
+        });
+
+        for sibling_id in &multiple_birth_siblings {
+            let sibling_name = self.get_person_name(sibling_id);
+            if ui.small_button(&sibling_name).clicked() {
+                self.person_editor.selected = Some(*sibling_id);
+            }
+        }
+        if !multiple_birth_siblings.is_empty() && ui.small_button(t("remove_multiple_birth")).clicked() {
+            self.tree.clear_multiple_birth(sel);
+            self.toasts.info(t("multiple_birth_updated"));
+        }
+
+        if !siblings.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label(t("add_multiple_birth"));
+                Self::render_relation_target_picker(
+                    ui,
+                    &self.tree.persons,
+                    "add_multiple_birth",
+                    &mut self.relation_editor.multiple_birth_pick,
+                    sel,
+                    &siblings,
+                    t,
+                );
+                if ui.button(t("add")).clicked()
+                    && let Some(sibling) = self.relation_editor.multiple_birth_pick.take()
+                {
+                    self.tree.mark_multiple_birth(&[sel, sibling]);
+                    self.toasts.info(t("multiple_birth_updated"));
+                    self.report_multiple_birth_mismatch(sel, t);
+                }
+            });
+        }
+
+        // キャンバスで複数選択した人物をまとめて登録
+        if selected_ids.len() >= 2 && ui.button(t("mark_selected_multiple_birth")).clicked() {
+            self.tree.mark_multiple_birth(&selected_ids);
+            self.toasts.info(t("multiple_birth_updated"));
+            self.report_multiple_birth_mismatch(sel, t);
+        }
+    }
+
+    fn report_multiple_birth_mismatch(&mut self, person_id: PersonId, t: &impl Fn(&str) -> String) {
+        let Some(group) = self.tree.persons.get(&person_id).and_then(|p| p.multiple_birth_group) else {
+            return;
+        };
+        if validation::validate_tree(&self.tree).contains(&ValidationIssue::MultipleBirthDateMismatch { group }) {
+            self.toasts.warning(t("multiple_birth_date_mismatch"));
+            self.log.add(
+                format!("{}: {}", t("log_multiple_birth_date_mismatch"), self.get_person_name(&person_id)),
+                LogLevel::Warning,
+            );
+        }
+    }
+
+    fn render_add_person_relation(
+        &mut self,
+        ui: &mut egui::Ui,
+        sel: PersonId,
+        all_ids: &[PersonId],
+        t: &impl Fn(&str) -> String,
+    ) {
+        ui.add_space(4.0);
+
+        // 選択中の人物の後見人・名付け親・師を追加
+        ui.horizontal(|ui| {
+            ui.label(t("add_person_relation"));
+            Self::render_relation_target_picker(
+                ui,
+                &self.tree.persons,
+                "add_person_relation",
+                &mut self.relation_editor.person_relation_pick,
+                sel,
+                all_ids,
+                t,
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label(t("kind"));
+            egui::ComboBox::from_id_salt("person_relation_kind")
+                .selected_text(t(self.relation_editor.person_relation_kind.role_key(true)))
+                .show_ui(ui, |ui| {
+                    for kind in PersonRelationKind::ALL {
+                        ui.selectable_value(
+                            &mut self.relation_editor.person_relation_kind,
+                            kind,
+                            t(kind.role_key(true)),
+                        );
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(t("memo"));
+            ui.text_edit_singleline(&mut self.relation_editor.person_relation_memo);
+            if ui.button(t("add")).clicked()
+                && let Some(holder) = self.relation_editor.person_relation_pick
+            {
+                self.tree.add_person_relation(
+                    holder,
+                    sel,
+                    self.relation_editor.person_relation_kind,
+                    self.relation_editor.person_relation_memo.trim().to_string(),
+                );
+                self.relation_editor.person_relation_pick = None;
+                self.relation_editor.person_relation_memo.clear();
+                self.toasts.info(t("person_relation_added"));
+            }
+        });
     }
 
     fn render_parent_relations(
@@ -411,12 +1243,26 @@ impl App {
             ui.label(label);
         });
         
+        let issues = validation::validate_tree(&self.tree);
+
         for (parent_id, parent_name) in parents {
-            // 関係の種類を取得
-            let kind = self.tree.edges.iter()
-                .find(|e| e.parent == *parent_id && e.child == sel)
-                .map(|e| e.kind.clone())
-                .unwrap_or_default();
+            // 関係の種類と養子縁組の詳細を取得
+            let Some(edge) = self.tree.parent_child_relation(*parent_id, sel) else {
+                continue;
+            };
+            let kind = edge.kind.clone();
+            let adoption_summary = edge.is_adoptive().then(|| {
+                [edge.adoption_date.clone().unwrap_or_default(), edge.adoption_type.clone()]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+            let adoption_note = edge.adoption_note.clone();
+            let adoption_before_birth = issues.contains(&ValidationIssue::AdoptionBeforeBirth {
+                parent: *parent_id,
+                child: sel,
+            });
             
             ui.horizontal(|ui| {
                 if ui.small_button(parent_name).clicked() {
@@ -427,10 +1273,22 @@ impl App {
                 if !kind.is_empty() && kind != "biological" {
                     ui.label(format!("({})", kind));
                 }
+
+                // 養子縁組の詳細表示
+                if let Some(summary) = adoption_summary.filter(|summary| !summary.is_empty()) {
+                    let response = ui.label(summary);
+                    if !adoption_note.is_empty() {
+                        response.on_hover_text(&adoption_note);
+                    }
+                }
+                if adoption_before_birth {
+                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "⚠")
+                        .on_hover_text(t("adoption_before_birth"));
+                }
                 
                 // 編集ボタン
                 if ui.small_button("✏️").on_hover_text(&t("edit_kind")).clicked() {
-                    self.start_parent_kind_edit(*parent_id, sel, &kind);
+                    self.start_parent_kind_edit(*parent_id, sel);
                 }
                 
                 // 削除ボタン
@@ -451,6 +1309,87 @@ impl App {
                         self.clear_parent_kind_edit();
                     }
                 });
+
+                // 養子縁組の詳細編集
+                if self.relation_editor.temp_kind.trim() == ADOPTIVE_KIND {
+                    ui.horizontal(|ui| {
+                        ui.label(t("adoption_date"));
+                        ui.text_edit_singleline(&mut self.relation_editor.temp_adoption_date);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(t("adoption_type"));
+                        ui.text_edit_singleline(&mut self.relation_editor.temp_adoption_type);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(t("adoption_note"));
+                        ui.text_edit_singleline(&mut self.relation_editor.temp_adoption_note);
+                    });
+                }
+            }
+        }
+    }
+
+    fn render_child_relations(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        // 選択中の人物が親になっている家族の単位を、もう一方の親ごとにまとめる
+        let mut groups: Vec<(Option<PersonId>, Vec<PersonId>)> = Vec::new();
+        let mut union_children = self.tree.children_by_union();
+        for union in self.tree.unions().iter().filter(|union| union.partners.contains(&sel)) {
+            let other = union.partners.iter().copied().find(|id| *id != sel);
+            let union_children = union_children.remove(&union.id).unwrap_or_default();
+            match groups.iter_mut().find(|(parent, _)| *parent == other) {
+                Some((_, children)) => children.extend(union_children),
+                None => groups.push((other, union_children)),
+            }
+        }
+        if groups.is_empty() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(t("children"));
+        });
+
+        for (other_parent, children) in groups {
+            let heading = match other_parent {
+                Some(other) => format!("{} {}", t("children_with"), self.get_person_name(&other)),
+                None => t("children_other_parent_unknown"),
+            };
+            ui.horizontal(|ui| {
+                ui.add_space(8.0);
+                match other_parent {
+                    Some(other) if ui.small_button(&heading).clicked() => {
+                        self.person_editor.selected = Some(other);
+                    }
+                    Some(_) => {}
+                    None => {
+                        ui.weak(&heading);
+                    }
+                }
+            });
+
+            for child_id in children {
+                let kind = self
+                    .tree
+                    .parent_child_relation(sel, child_id)
+                    .map(|edge| edge.kind.clone())
+                    .unwrap_or_default();
+                let child_name = self.get_person_name(&child_id);
+                ui.horizontal(|ui| {
+                    ui.add_space(16.0);
+                    if ui.small_button(&child_name).clicked() {
+                        self.person_editor.selected = Some(child_id);
+                    }
+
+                    // 種類の表示
+                    if !kind.is_empty() && kind != "biological" {
+                        ui.label(format!("({})", kind));
+                    }
+
+                    // 削除ボタン
+                    if ui.small_button("❌").on_hover_text(t("remove_relation")).clicked() {
+                        self.remove_parent_relation(sel, child_id, t);
+                    }
+                });
             }
         }
     }
@@ -471,20 +1410,27 @@ impl App {
                 .map(|p| p.name.clone())
                 .unwrap_or_default();
             
-            // 配偶者関係のメモを取得
-            let spouse_memo = self.tree.spouses.iter()
-                .find(|s| {
-                    (s.person1 == sel && s.person2 == *spouse_id) ||
-                    (s.person1 == *spouse_id && s.person2 == sel)
-                })
+            // 配偶者関係のメモ・結婚情報を取得
+            let spouse_relation = self.tree.spouse_relation(sel, *spouse_id);
+            let spouse_memo = spouse_relation
                 .map(|s| s.memo.clone())
                 .unwrap_or_default();
+            let marriage_date = spouse_relation.and_then(|s| s.marriage_date.clone());
+            let divorced = spouse_relation.is_some_and(|s| s.divorced);
             
             ui.horizontal(|ui| {
                 if ui.small_button(&spouse_name).clicked() {
                     self.person_editor.selected = Some(*spouse_id);
                 }
                 
+                // 結婚年月日・離婚の表示
+                if let Some(date) = &marriage_date {
+                    ui.label(format!("💍 {}", date));
+                }
+                if divorced {
+                    ui.label(t("divorced"));
+                }
+
                 // メモの表示と編集
                 if !spouse_memo.is_empty() {
                     ui.label(format!("({})", spouse_memo));
@@ -517,6 +1463,32 @@ impl App {
         }
     }
 
+    fn render_step_relations(&mut self, ui: &mut egui::Ui, sel: PersonId, t: &impl Fn(&str) -> String) {
+        let groups = [
+            (t("step_parents"), self.tree.step_parents_of(sel)),
+            (t("step_children"), self.tree.step_children_of(sel)),
+        ];
+
+        for (label, person_ids) in groups {
+            if person_ids.is_empty() {
+                continue;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(label);
+            });
+            for person_id in person_ids {
+                let name = self.get_person_name(&person_id);
+                ui.horizontal(|ui| {
+                    if ui.small_button(&name).clicked() {
+                        self.person_editor.selected = Some(person_id);
+                    }
+                    ui.weak(t("derived_relation"));
+                });
+            }
+        }
+    }
+
     fn render_add_relations(
         &mut self,
         ui: &mut egui::Ui,
@@ -546,9 +1518,12 @@ impl App {
             if ui.button(t("add")).clicked() {
                 if let Some(parent) = self.relation_editor.parent_pick {
                     let relation_kind = self.relation_kind_or_default();
-                    self.tree.add_parent_child(parent, sel, relation_kind);
+                    let position = self.linked_person_position(sel, parent, RelativePlacement::Parent);
+                    let step = MacroStep::AddParent { parent, kind: relation_kind };
+                    if self.execute_relation_command(sel, step, |tree| move_linked_person(tree, parent, position)) {
+                        self.toasts.info(t("parent_added"));
+                    }
                     self.relation_editor.parent_pick = None;
-                    self.file.status = t("parent_added");
                 }
             }
         });
@@ -574,9 +1549,12 @@ impl App {
             if ui.button(t("add")).clicked() {
                 if let Some(child) = self.relation_editor.child_pick {
                     let relation_kind = self.relation_kind_or_default();
-                    self.tree.add_parent_child(sel, child, relation_kind);
+                    let position = self.linked_person_position(sel, child, RelativePlacement::Child);
+                    let step = MacroStep::AddChild { child, kind: relation_kind };
+                    if self.execute_relation_command(sel, step, |tree| move_linked_person(tree, child, position)) {
+                        self.toasts.info(t("child_added"));
+                    }
                     self.relation_editor.child_pick = None;
-                    self.file.status = t("child_added");
                 }
             }
         });
@@ -601,12 +1579,25 @@ impl App {
             ui.text_edit_singleline(&mut self.relation_editor.spouse_memo);
             if ui.button(t("add")).clicked() {
                 if let Some(spouse) = self.relation_editor.spouse_pick {
-                    self.tree.add_spouse(sel, spouse, self.relation_editor.spouse_memo.clone());
+                    let memo = self.relation_editor.spouse_memo.clone();
+                    let position = self.linked_person_position(sel, spouse, RelativePlacement::Spouse);
+                    let step = MacroStep::AddSpouse { spouse, memo };
+                    if self.execute_relation_command(sel, step, |tree| move_linked_person(tree, spouse, position)) {
+                        self.toasts.info(t("spouse_added"));
+                    }
                     self.relation_editor.spouse_pick = None;
                     self.relation_editor.spouse_memo.clear();
-                    self.file.status = t("spouse_added");
                 }
             }
         });
     }
 }
+
+/// 関係の追加と同じ操作として、まだつながっていなかった人物を相手の近くに動かす
+fn move_linked_person(tree: &mut FamilyTree, person: PersonId, position: Option<(f32, f32)>) {
+    if let Some(position) = position
+        && let Some(person) = tree.persons.get_mut(&person)
+    {
+        person.position = position;
+    }
+}
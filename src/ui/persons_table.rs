@@ -0,0 +1,167 @@
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+use crate::app::App;
+use crate::core::tree::{Gender, PersonId};
+use crate::ui::PersonTableColumn;
+
+/// 人物一覧（表形式）描画トレイト
+pub trait PersonsTableRenderer {
+    /// 並べ替え可能な人物の表を描画し、クリックされた人物と Ctrl 押下の有無を返す
+    fn render_persons_table(
+        &mut self,
+        ui: &mut egui::Ui,
+        scroll_to_selected: bool,
+        t: &impl Fn(&str) -> String,
+    ) -> Option<(PersonId, bool)>;
+}
+
+/// 表の1行分の表示内容
+struct PersonRow {
+    id: PersonId,
+    name: String,
+    birth: String,
+    death: String,
+    gender: String,
+    children: usize,
+    family: String,
+}
+
+impl PersonsTableRenderer for App {
+    fn render_persons_table(
+        &mut self,
+        ui: &mut egui::Ui,
+        scroll_to_selected: bool,
+        t: &impl Fn(&str) -> String,
+    ) -> Option<(PersonId, bool)> {
+        let mut rows = self.collect_person_rows(t);
+        let (sort_column, ascending) = self.person_editor.table_sort;
+        rows.sort_by(|a, b| {
+            let ordering = match sort_column {
+                PersonTableColumn::Name => a.name.cmp(&b.name),
+                PersonTableColumn::Birth => a.birth.cmp(&b.birth),
+                PersonTableColumn::Death => a.death.cmp(&b.death),
+                PersonTableColumn::Gender => a.gender.cmp(&b.gender),
+                PersonTableColumn::Children => a.children.cmp(&b.children),
+                PersonTableColumn::Family => a.family.cmp(&b.family),
+            };
+            // 同じ値の行は名前順で安定させる
+            let ordering = ordering.then_with(|| a.name.cmp(&b.name));
+            if ascending { ordering } else { ordering.reverse() }
+        });
+
+        let columns = [
+            (PersonTableColumn::Name, t("column_name")),
+            (PersonTableColumn::Birth, t("column_birth")),
+            (PersonTableColumn::Death, t("column_death")),
+            (PersonTableColumn::Gender, t("column_gender")),
+            (PersonTableColumn::Children, t("column_children")),
+            (PersonTableColumn::Family, t("column_family")),
+        ];
+        let scroll_row = scroll_to_selected
+            .then(|| rows.iter().position(|row| Some(row.id) == self.person_editor.selected))
+            .flatten();
+
+        let mut clicked = None;
+        let mut table = TableBuilder::new(ui)
+            .id_salt("persons_table")
+            .striped(true)
+            .resizable(true)
+            .max_scroll_height(240.0)
+            .sense(egui::Sense::click())
+            .column(Column::auto().at_least(80.0))
+            .columns(Column::auto(), columns.len() - 1);
+        if let Some(row_index) = scroll_row {
+            table = table.scroll_to_row(row_index, Some(egui::Align::Center));
+        }
+
+        table
+            .header(20.0, |mut header| {
+                for (column, label) in &columns {
+                    header.col(|ui| {
+                        let marker = match self.person_editor.table_sort {
+                            (sorted, true) if sorted == *column => " ▲",
+                            (sorted, false) if sorted == *column => " ▼",
+                            _ => "",
+                        };
+                        if ui.button(format!("{label}{marker}")).clicked() {
+                            let (sorted, ascending) = self.person_editor.table_sort;
+                            self.person_editor.table_sort = (*column, sorted != *column || !ascending);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(18.0, rows.len(), |mut table_row| {
+                    let row = &rows[table_row.index()];
+                    let is_selected = self.person_editor.selected_ids.contains(&row.id)
+                        || self.person_editor.selected == Some(row.id);
+                    table_row.set_selected(is_selected);
+
+                    table_row.col(|ui| {
+                        ui.label(&row.name);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(&row.birth);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(&row.death);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(&row.gender);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(row.children.to_string());
+                    });
+                    table_row.col(|ui| {
+                        ui.label(&row.family);
+                    });
+
+                    if table_row.response().clicked() {
+                        let toggle = table_row.response().ctx.input(|i| i.modifiers.command);
+                        clicked = Some((row.id, toggle));
+                    }
+                });
+            });
+
+        clicked
+    }
+}
+
+impl App {
+    fn collect_person_rows(&self, t: &impl Fn(&str) -> String) -> Vec<PersonRow> {
+        let filter = &self.person_editor.filter;
+        let presumption = self.ui.deceased_presumption();
+        self.tree
+            .persons
+            .values()
+            .filter(|person| filter.matches(&self.tree, person, presumption))
+            .map(|person| {
+                let gender = match &person.gender {
+                    Gender::Male => t("male"),
+                    Gender::Female => t("female"),
+                    Gender::Unknown => t("unknown"),
+                    Gender::Other(label) if !label.is_empty() => label.clone(),
+                    Gender::Other(_) => t("other_gender"),
+                };
+                let family = self
+                    .tree
+                    .get_families_containing(person.id)
+                    .iter()
+                    .map(|family| family.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                PersonRow {
+                    id: person.id,
+                    name: person.name.clone(),
+                    birth: person.birth.clone().unwrap_or_default(),
+                    death: person.death.clone().unwrap_or_default(),
+                    gender,
+                    children: self.tree.children_of(person.id).len(),
+                    family,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,146 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::PersonId;
+use crate::infrastructure::{clear_photos, find_broken_photos, relocate_photos, BrokenPhoto, PhotoProblem};
+use crate::ui::LogLevel;
+
+/// 写真の確認ダイアログの描画トレイト
+pub trait PhotoCheckDialogRenderer {
+    fn render_photo_check_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl PhotoCheckDialogRenderer for App {
+    fn render_photo_check_dialog(&mut self, ctx: &egui::Context) {
+        if !self.photo_check.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut relocate = false;
+        let mut clear = false;
+        let mut undo = false;
+        let mut rescan = false;
+
+        let descriptions: Vec<String> = self
+            .photo_check
+            .broken
+            .iter()
+            .map(|photo| {
+                let problem = match photo.problem {
+                    PhotoProblem::Missing => t("photo_check_missing"),
+                    PhotoProblem::Undecodable => t("photo_check_undecodable"),
+                };
+                format!("{} ({})", self.get_person_name(&photo.person), problem)
+            })
+            .collect();
+
+        egui::Window::new(t("photo_check"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                let state = &mut self.photo_check;
+                if state.broken.is_empty() {
+                    ui.weak(t("photo_check_no_issues"));
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}: {}", t("photo_check_found"), state.broken.len()));
+                        if ui.small_button(t("cleanup_select_all")).clicked() {
+                            state.checked.iter_mut().for_each(|checked| *checked = true);
+                        }
+                        if ui.small_button(t("cleanup_select_none")).clicked() {
+                            state.checked.iter_mut().for_each(|checked| *checked = false);
+                        }
+                    });
+                    egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                        for ((checked, photo), description) in state.checked.iter_mut().zip(&state.broken).zip(&descriptions) {
+                            ui.checkbox(checked, description);
+                            ui.weak(format!("    {}", photo.path));
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let can_fix = state.checked.iter().any(|checked| *checked) && !self.ui.read_only;
+                    if ui
+                        .add_enabled(can_fix, egui::Button::new(t("photo_check_relocate")))
+                        .on_hover_text(t("photo_check_relocate_hint"))
+                        .clicked()
+                    {
+                        relocate = true;
+                    }
+                    if ui.add_enabled(can_fix, egui::Button::new(t("photo_check_clear"))).clicked() {
+                        clear = true;
+                    }
+                    if ui.button(t("cleanup_rescan")).clicked() {
+                        rescan = true;
+                    }
+                    let can_undo = state.last_change.is_some_and(|id| self.undo_history.contains(id)) && !self.ui.read_only;
+                    if ui.add_enabled(can_undo, egui::Button::new(t("cleanup_undo"))).clicked() {
+                        undo = true;
+                    }
+                });
+            });
+
+        let selected: Vec<BrokenPhoto> = self
+            .photo_check
+            .broken
+            .iter()
+            .zip(&self.photo_check.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(photo, _)| photo.clone())
+            .collect();
+        // 共通の履歴に記録し、直した写真だけをまとめて元に戻せるようにする
+        if relocate && let Some(folder) = rfd::FileDialog::new().pick_folder() {
+            let (relocated, change) =
+                self.apply_undoable(t("photo_check_relocate"), |tree| relocate_photos(tree, &selected, &folder));
+            if change.is_some() {
+                self.photo_check.last_change = change;
+            }
+            let message = format!("{}: {}/{}", t("photo_check_relocated"), relocated.len(), selected.len());
+            self.toasts.info(message.clone());
+            self.log.add(format!("{message} ({})", folder.display()), LogLevel::Debug);
+            rescan = true;
+        }
+        if clear {
+            let persons: Vec<PersonId> = selected.iter().map(|photo| photo.person).collect();
+            let ((), change) = self.apply_undoable(t("photo_check_clear"), |tree| clear_photos(tree, &persons));
+            self.photo_check.last_change = change;
+            self.toasts.info(format!("{}: {}", t("photo_check_cleared"), persons.len()));
+            self.log.add(format!("{}: {}", t("photo_check_cleared"), persons.len()), LogLevel::Debug);
+            rescan = true;
+        }
+        if undo && let Some(id) = self.photo_check.last_change.take() {
+            self.undo_tree_change(Some(id));
+            rescan = true;
+        }
+        if rescan {
+            self.scan_photos();
+        }
+        if !open {
+            // 閉じた後も修正は編集メニューの「元に戻す」から戻せる
+            self.photo_check.show = false;
+            self.photo_check.last_change = None;
+        }
+    }
+}
+
+impl App {
+    /// 写真の確認ダイアログを開く（開くたびに調べ直す）
+    pub fn open_photo_check(&mut self) {
+        self.photo_check.show = true;
+        self.photo_check.last_change = None;
+        self.scan_photos();
+    }
+
+    fn scan_photos(&mut self) {
+        let broken = find_broken_photos(&self.tree);
+        self.photo_check.checked = vec![true; broken.len()];
+        self.photo_check.broken = broken;
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::MANAGED_PHOTO_DIR;
+use crate::infrastructure::photo_editor::{rotate_quarter_turns, save_edited_photo};
+use crate::ui::LogLevel;
+
+/// プレビュー用に縮小する最大サイズ（ピクセル）
+const PREVIEW_SOURCE_MAX_SIZE: u32 = 1024;
+/// ダイアログ内でのプレビューの表示サイズ
+const PREVIEW_DISPLAY_SIZE: egui::Vec2 = egui::vec2(360.0, 360.0);
+
+/// 写真の切り抜き・回転ダイアログの描画トレイト
+pub trait PhotoEditDialogRenderer {
+    fn render_photo_edit_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl PhotoEditDialogRenderer for App {
+    fn render_photo_edit_dialog(&mut self, ctx: &egui::Context) {
+        let Some(source_path) = self.photo_edit.source_path.clone() else {
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        // 元画像は開いたときに一度だけ読み込み、プレビュー用に縮小しておく
+        if self.photo_edit.preview_image.is_none() {
+            match image::open(&source_path) {
+                Ok(image) => {
+                    self.photo_edit.preview_image =
+                        Some(image.thumbnail(PREVIEW_SOURCE_MAX_SIZE, PREVIEW_SOURCE_MAX_SIZE));
+                }
+                Err(error) => {
+                    self.photo_edit.close();
+                    self.set_error_status_and_log(&t("photo_edit_load_failed"), &error.to_string());
+                    return;
+                }
+            }
+        }
+        self.update_photo_edit_preview(ctx);
+
+        let mut apply = false;
+        let mut use_original = false;
+        let mut cancel = false;
+        egui::Modal::new(egui::Id::new("photo_edit_dialog")).show(ctx, |ui| {
+            ui.heading(t("edit_photo"));
+            ui.weak(&source_path);
+            ui.add_space(6.0);
+
+            ui.horizontal(|ui| {
+                if ui.button(format!("⟲ {}", t("rotate_left"))).clicked() {
+                    self.photo_edit.edit.rotate_counterclockwise();
+                }
+                if ui.button(format!("⟳ {}", t("rotate_right"))).clicked() {
+                    self.photo_edit.edit.rotate_clockwise();
+                }
+                if ui.button(t("reset_crop")).clicked() {
+                    self.photo_edit.edit.reset_crop();
+                }
+            });
+            ui.weak(t("photo_crop_hint"));
+            self.render_photo_crop_area(ui);
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("apply")).clicked() {
+                    apply = true;
+                }
+                if ui.button(t("use_original_photo")).clicked() {
+                    use_original = true;
+                }
+                if ui.button(t("cancel")).clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+        if apply && !self.photo_edit.edit.is_identity() {
+            match save_edited_photo(&source_path, &self.photo_edit.edit, Path::new(MANAGED_PHOTO_DIR)) {
+                Ok(saved_path) => {
+                    self.person_editor.new_photo_path = saved_path.display().to_string();
+                    self.toasts.info(t("photo_edited"));
+                    self.log.add(
+                        format!("{}: {}", t("log_photo_edited"), saved_path.display()),
+                        LogLevel::Debug,
+                    );
+                }
+                Err(error) => {
+                    self.set_error_status_and_log(&t("photo_edit_save_failed"), &error);
+                    return;
+                }
+            }
+            self.photo_edit.close();
+        } else if apply || use_original {
+            self.person_editor.new_photo_path = source_path;
+            self.photo_edit.close();
+        } else if cancel {
+            self.photo_edit.close();
+        }
+    }
+}
+
+impl App {
+    /// 回転が変わったときだけプレビューのテクスチャを作り直す
+    fn update_photo_edit_preview(&mut self, ctx: &egui::Context) {
+        let turns = self.photo_edit.edit.quarter_turns;
+        if self.photo_edit.preview_texture.as_ref().is_some_and(|(_, t)| *t == turns) {
+            return;
+        }
+        let Some(image) = &self.photo_edit.preview_image else {
+            return;
+        };
+        let rgba = rotate_quarter_turns(image, turns).to_rgba8();
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice());
+        let texture = ctx.load_texture("photo_edit_preview", color_image, Default::default());
+        self.photo_edit.preview_texture = Some((texture, turns));
+    }
+
+    /// プレビュー上をドラッグして切り抜き範囲を選び、範囲外を暗く表示する
+    fn render_photo_crop_area(&mut self, ui: &mut egui::Ui) {
+        let Some((texture, _)) = self.photo_edit.preview_texture.clone() else {
+            return;
+        };
+        let image_size = texture.size_vec2();
+        let scale = (PREVIEW_DISPLAY_SIZE.x / image_size.x).min(PREVIEW_DISPLAY_SIZE.y / image_size.y);
+        let (rect, response) = ui.allocate_exact_size(image_size * scale, egui::Sense::drag());
+        let painter = ui.painter_at(rect);
+        painter.image(
+            texture.id(),
+            rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+
+        let to_fraction = |pos: egui::Pos2| {
+            let fraction = (pos - rect.min) / rect.size();
+            egui::pos2(fraction.x.clamp(0.0, 1.0), fraction.y.clamp(0.0, 1.0))
+        };
+        if response.drag_started()
+            && let Some(pos) = response.interact_pointer_pos()
+        {
+            self.photo_edit.drag_start = Some(to_fraction(pos));
+        }
+        if response.dragged()
+            && let (Some(start), Some(pos)) = (self.photo_edit.drag_start, response.interact_pointer_pos())
+        {
+            let end = to_fraction(pos);
+            self.photo_edit.edit.crop = [start.x.min(end.x), start.y.min(end.y), start.x.max(end.x), start.y.max(end.y)];
+        }
+        if response.drag_stopped() {
+            self.photo_edit.drag_start = None;
+            // クリックだけで範囲が潰れた場合は切り抜きを解除する
+            let [left, top, right, bottom] = self.photo_edit.edit.crop;
+            if right - left < 0.01 || bottom - top < 0.01 {
+                self.photo_edit.edit.reset_crop();
+            }
+        }
+
+        let [left, top, right, bottom] = self.photo_edit.edit.crop;
+        let crop_rect = egui::Rect::from_min_max(
+            rect.min + egui::vec2(left, top) * rect.size(),
+            rect.min + egui::vec2(right, bottom) * rect.size(),
+        );
+        let shade = egui::Color32::from_black_alpha(140);
+        for outside in [
+            egui::Rect::from_min_max(rect.min, egui::pos2(rect.max.x, crop_rect.min.y)),
+            egui::Rect::from_min_max(egui::pos2(rect.min.x, crop_rect.max.y), rect.max),
+            egui::Rect::from_min_max(egui::pos2(rect.min.x, crop_rect.min.y), egui::pos2(crop_rect.min.x, crop_rect.max.y)),
+            egui::Rect::from_min_max(egui::pos2(crop_rect.max.x, crop_rect.min.y), egui::pos2(rect.max.x, crop_rect.max.y)),
+        ] {
+            painter.rect_filled(outside, 0.0, shade);
+        }
+        painter.rect_stroke(crop_rect, 0.0, egui::Stroke::new(1.5, egui::Color32::WHITE), egui::StrokeKind::Inside);
+    }
+}
@@ -0,0 +1,256 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::place_map::{self, MapPin, MapPinKind};
+use crate::core::tree::PersonId;
+
+/// 重なった印を1つにまとめる画面上の距離（ピクセル）
+const CLUSTER_RADIUS: f32 = 12.0;
+/// 1つだけの印の半径
+const PIN_RADIUS: f32 = 5.0;
+/// 経線・緯線を引く間隔（度）
+const GRATICULE_STEP: i32 = 30;
+/// 1度あたりのピクセル数の範囲
+const MIN_MAP_SCALE: f32 = 0.5;
+const MAX_MAP_SCALE: f32 = 400.0;
+/// 全体表示で印の周りに残す余白（度）
+const FIT_MARGIN_DEGREES: f32 = 2.0;
+
+const BIRTH_PIN_COLOR: egui::Color32 = egui::Color32::from_rgb(70, 130, 220);
+const DEATH_PIN_COLOR: egui::Color32 = egui::Color32::from_rgb(130, 130, 130);
+const MIXED_PIN_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 150, 40);
+const MIGRATION_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(60, 150, 90, 160);
+
+/// 出生地・死亡地の地図ウィンドウの描画トレイト
+pub trait PlaceMapRenderer {
+    fn render_place_map(&mut self, ctx: &egui::Context);
+}
+
+impl PlaceMapRenderer for App {
+    fn render_place_map(&mut self, ctx: &egui::Context) {
+        if !self.place_map.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let pins = place_map::collect_pins(&self.tree, self.place_map.show_deaths);
+        let migrations = if self.place_map.show_migrations {
+            place_map::collect_migrations(&self.tree)
+        } else {
+            Vec::new()
+        };
+        let mut open = true;
+        let mut select = None;
+
+        egui::Window::new(t("place_map"))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([640.0, 420.0])
+            .show(ctx, |ui| {
+                let state = &mut self.place_map;
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut state.show_deaths, t("place_map_show_deaths"));
+                    ui.checkbox(&mut state.show_migrations, t("place_map_show_migrations"))
+                        .on_hover_text(t("place_map_show_migrations_hint"));
+                    if ui.button(t("fit_to_view")).clicked() {
+                        state.fit_pending = true;
+                    }
+                });
+                if pins.is_empty() {
+                    ui.weak(t("place_map_no_pins"));
+                }
+                // 同じ地点にいる人物は地図を拡大しても分けられないので、一覧から選ぶ
+                if !state.picked.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        for person_id in &state.picked {
+                            let name = self.tree.persons.get(person_id).map(|person| person.name.as_str()).unwrap_or_default();
+                            if ui.link(name).clicked() {
+                                select = Some(*person_id);
+                            }
+                        }
+                    });
+                }
+
+                let size = ui.available_size().max(egui::vec2(200.0, 160.0));
+                let (response, painter) = ui.allocate_painter(size, egui::Sense::click_and_drag());
+                let rect = response.rect;
+                if std::mem::take(&mut state.fit_pending) {
+                    state.fit_to_pins(&pins, rect);
+                }
+
+                // ドラッグで移動、ホイールでマウス位置を中心に拡大・縮小する
+                if response.dragged() {
+                    let delta = response.drag_delta() / state.scale;
+                    state.center.0 -= delta.x;
+                    state.center.1 -= delta.y;
+                }
+                if let Some(pointer) = response.hover_pos() {
+                    let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                    if scroll != 0.0 {
+                        let before = state.to_map(rect, pointer);
+                        state.scale = (state.scale * (scroll * 0.002).exp()).clamp(MIN_MAP_SCALE, MAX_MAP_SCALE);
+                        let after = state.to_map(rect, pointer);
+                        state.center.0 += before.0 - after.0;
+                        state.center.1 += before.1 - after.1;
+                    }
+                }
+
+                let visuals = ui.visuals();
+                painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+                let grid_stroke = egui::Stroke::new(1.0, visuals.weak_text_color().gamma_multiply(0.4));
+                let axis_stroke = egui::Stroke::new(1.0, visuals.weak_text_color());
+                for longitude in (-180i32..=180).step_by(GRATICULE_STEP as usize) {
+                    let top = state.to_screen(rect, place_map::project((90.0, f64::from(longitude))));
+                    let bottom = state.to_screen(rect, place_map::project((-90.0, f64::from(longitude))));
+                    let stroke = if longitude == 0 || longitude.abs() == 180 { axis_stroke } else { grid_stroke };
+                    painter.line_segment([top, bottom], stroke);
+                }
+                for latitude in (-90i32..=90).step_by(GRATICULE_STEP as usize) {
+                    let left = state.to_screen(rect, place_map::project((f64::from(latitude), -180.0)));
+                    let right = state.to_screen(rect, place_map::project((f64::from(latitude), 180.0)));
+                    let stroke = if latitude == 0 || latitude.abs() == 90 { axis_stroke } else { grid_stroke };
+                    painter.line_segment([left, right], stroke);
+                }
+
+                // 国境線（塗りつぶすと凹んだ形を分割する必要があるので線だけ描く）
+                let land_stroke = egui::Stroke::new(1.0, visuals.text_color().gamma_multiply(0.5));
+                for ring in place_map::world_outline() {
+                    let points: Vec<egui::Pos2> = ring.iter().map(|&point| state.to_screen(rect, point)).collect();
+                    painter.add(egui::Shape::closed_line(points, land_stroke));
+                }
+
+                for migration in &migrations {
+                    let from = state.to_screen(rect, place_map::project(migration.from));
+                    let to = state.to_screen(rect, place_map::project(migration.to));
+                    painter.arrow(from, to - from, egui::Stroke::new(1.5, MIGRATION_COLOR));
+                }
+
+                let points: Vec<egui::Pos2> = pins
+                    .iter()
+                    .map(|pin| state.to_screen(rect, place_map::project(pin.coordinates)))
+                    .collect();
+                let clusters = place_map::cluster_points(&points.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(), CLUSTER_RADIUS);
+                let mut hovered = None;
+                for members in &clusters {
+                    let center = points[members[0]];
+                    let radius = if members.len() == 1 { PIN_RADIUS } else { PIN_RADIUS + 3.0 + (members.len() as f32).sqrt() * 2.0 };
+                    let color = cluster_color(members.iter().map(|&index| &pins[index]));
+                    painter.circle(center, radius, color, egui::Stroke::new(1.0, visuals.strong_text_color()));
+                    if members.len() > 1 {
+                        painter.text(
+                            center,
+                            egui::Align2::CENTER_CENTER,
+                            members.len().to_string(),
+                            egui::FontId::proportional(11.0),
+                            egui::Color32::WHITE,
+                        );
+                    }
+                    if response.hover_pos().is_some_and(|pointer| pointer.distance(center) <= radius.max(CLUSTER_RADIUS / 2.0)) {
+                        hovered = Some(members);
+                    }
+                }
+
+                let Some(members) = hovered else {
+                    return;
+                };
+                let cluster: Vec<&MapPin> = members.iter().map(|&index| &pins[index]).collect();
+                let tooltip_lines: Vec<String> = cluster
+                    .iter()
+                    .map(|pin| {
+                        let name = self.tree.persons.get(&pin.person).map(|person| person.name.as_str()).unwrap_or_default();
+                        let place = self.tree.get_place(pin.place).map(|place| place.name.as_str()).unwrap_or_default();
+                        let kind = match pin.kind {
+                            MapPinKind::Birth => t("birth_place"),
+                            MapPinKind::Death => t("death_place"),
+                        };
+                        format!("{name} – {kind}: {place}")
+                    })
+                    .collect();
+                let clicked = response.clicked();
+                response.on_hover_ui_at_pointer(|ui| {
+                    for line in &tooltip_lines {
+                        ui.label(line);
+                    }
+                });
+                if clicked {
+                    let mut persons: Vec<PersonId> = Vec::new();
+                    for pin in &cluster {
+                        if !persons.contains(&pin.person) {
+                            persons.push(pin.person);
+                        }
+                    }
+                    let same_spot = cluster.iter().all(|pin| pin.coordinates == cluster[0].coordinates);
+                    if let [person_id] = persons.as_slice() {
+                        select = Some(*person_id);
+                    } else if same_spot {
+                        state.picked = persons;
+                    } else {
+                        // 離れた地点がまとまっているときは、拡大して分ける
+                        let (x, y) = place_map::project(cluster[0].coordinates);
+                        state.center = (x, y);
+                        state.scale = (state.scale * 2.0).min(MAX_MAP_SCALE);
+                    }
+                }
+            });
+
+        if let Some(person_id) = select {
+            self.select_person_from_list(person_id);
+        }
+        if !open {
+            self.place_map.show = false;
+            self.place_map.picked.clear();
+        }
+    }
+}
+
+/// まとまった印の色（出生地だけ・死亡地だけならその色、混ざっていれば別の色）
+fn cluster_color<'a>(mut pins: impl Iterator<Item = &'a MapPin>) -> egui::Color32 {
+    let Some(first) = pins.next() else {
+        return MIXED_PIN_COLOR;
+    };
+    if pins.any(|pin| pin.kind != first.kind) {
+        return MIXED_PIN_COLOR;
+    }
+    match first.kind {
+        MapPinKind::Birth => BIRTH_PIN_COLOR,
+        MapPinKind::Death => DEATH_PIN_COLOR,
+    }
+}
+
+impl crate::ui::PlaceMapState {
+    fn to_screen(&self, rect: egui::Rect, (x, y): (f32, f32)) -> egui::Pos2 {
+        rect.center() + egui::vec2((x - self.center.0) * self.scale, (y - self.center.1) * self.scale)
+    }
+
+    fn to_map(&self, rect: egui::Rect, pos: egui::Pos2) -> (f32, f32) {
+        let offset = (pos - rect.center()) / self.scale;
+        (self.center.0 + offset.x, self.center.1 + offset.y)
+    }
+
+    /// すべての印が収まるように表示範囲を合わせる（印がなければ世界全体）
+    fn fit_to_pins(&mut self, pins: &[MapPin], rect: egui::Rect) {
+        let projected: Vec<(f32, f32)> = pins.iter().map(|pin| place_map::project(pin.coordinates)).collect();
+        let (min, max) = if projected.is_empty() {
+            ((0.0, 0.0), (360.0, 180.0))
+        } else {
+            projected.iter().fold(((f32::MAX, f32::MAX), (f32::MIN, f32::MIN)), |(min, max), &(x, y)| {
+                ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+            })
+        };
+        let width = (max.0 - min.0) + FIT_MARGIN_DEGREES * 2.0;
+        let height = (max.1 - min.1) + FIT_MARGIN_DEGREES * 2.0;
+        self.center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+        self.scale = (rect.width() / width).min(rect.height() / height).clamp(MIN_MAP_SCALE, MAX_MAP_SCALE);
+    }
+}
+
+impl App {
+    /// 出生地・死亡地の地図を開き、印が収まるように表示する
+    pub fn open_place_map(&mut self) {
+        self.place_map.show = true;
+        self.place_map.fit_pending = true;
+        self.place_map.picked.clear();
+    }
+}
@@ -0,0 +1,240 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::tree::{Place, PlaceId, PlaceReference};
+use crate::ui::LogLevel;
+
+pub trait PlacesTabRenderer {
+    fn render_places_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String);
+}
+
+impl PlacesTabRenderer for App {
+    fn render_places_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
+        ui.heading(t("manage_places"));
+        if ui.add_enabled(!self.ui.read_only, egui::Button::new(t("add_new_place"))).clicked() {
+            self.add_new_place(&t);
+        }
+        ui.separator();
+
+        self.render_place_list(ui, &t);
+        ui.separator();
+
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            if self.place_editor.selected.is_some() {
+                ui.heading(t("place_editor"));
+                self.render_place_form_fields(ui, &t);
+                self.render_place_action_buttons(ui, &t);
+                ui.separator();
+            }
+            self.render_duplicate_places(ui, &t);
+        });
+    }
+}
+
+impl App {
+    /// 場所の選択欄（地名辞典から選ぶ。未設定にもできる）
+    pub(crate) fn render_place_picker(
+        ui: &mut egui::Ui,
+        places: &[Place],
+        combo_id: &str,
+        selected: &mut Option<PlaceId>,
+        t: &impl Fn(&str) -> String,
+    ) {
+        let selected_text = selected
+            .and_then(|id| places.iter().find(|place| place.id == id))
+            .map(|place| place.name.clone())
+            .unwrap_or_else(|| t("no_place"));
+        egui::ComboBox::from_id_salt(combo_id)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, t("no_place"));
+                for place in places {
+                    ui.selectable_value(selected, Some(place.id), &place.name);
+                }
+            });
+    }
+
+    fn add_new_place(&mut self, t: &impl Fn(&str) -> String) {
+        let place_id = self.tree.add_place(t("new_place"));
+        self.select_place(place_id);
+        self.toasts.info(t("new_place_added"));
+        self.log.add(format!("{}: {}", t("log_place_added"), t("new_place")), LogLevel::Debug);
+    }
+
+    fn select_place(&mut self, place_id: PlaceId) {
+        let Some(place) = self.tree.get_place(place_id) else {
+            return;
+        };
+        self.place_editor.selected = Some(place_id);
+        self.place_editor.new_name = place.name.clone();
+        self.place_editor.new_latitude = place.latitude.map(|value| value.to_string()).unwrap_or_default();
+        self.place_editor.new_longitude = place.longitude.map(|value| value.to_string()).unwrap_or_default();
+        self.place_editor.new_note = place.note.clone();
+        self.place_editor.merge_target = None;
+    }
+
+    fn render_place_list(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        if self.tree.places.is_empty() {
+            ui.weak(t("no_places"));
+            return;
+        }
+
+        let mut entries: Vec<(PlaceId, String, usize)> = self
+            .tree
+            .places
+            .iter()
+            .map(|place| (place.id, place.name.clone(), self.tree.place_usage_count(place.id)))
+            .collect();
+        entries.sort_by(|a, b| a.1.cmp(&b.1));
+
+        egui::ScrollArea::vertical()
+            .id_salt("place_list")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for (place_id, name, usage_count) in entries {
+                    let is_selected = self.place_editor.selected == Some(place_id);
+                    if ui.selectable_label(is_selected, format!("{name} ({usage_count})")).clicked() {
+                        self.select_place(place_id);
+                    }
+                }
+            });
+    }
+
+    fn render_place_form_fields(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        ui.label(t("name"));
+        ui.text_edit_singleline(&mut self.place_editor.new_name);
+        ui.horizontal(|ui| {
+            ui.label(t("latitude"));
+            ui.add(egui::TextEdit::singleline(&mut self.place_editor.new_latitude).desired_width(80.0));
+            ui.label(t("longitude"));
+            ui.add(egui::TextEdit::singleline(&mut self.place_editor.new_longitude).desired_width(80.0));
+        });
+        ui.label(t("memo"));
+        ui.text_edit_multiline(&mut self.place_editor.new_note);
+    }
+
+    fn render_place_action_buttons(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let Some(place_id) = self.place_editor.selected else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            if ui.button(t("update")).clicked() {
+                self.update_selected_place(place_id, t);
+            }
+            if ui.button(t("delete")).clicked() {
+                self.delete_selected_place(place_id, t);
+            }
+            if ui.button(t("cancel")).clicked() {
+                self.place_editor.clear();
+            }
+        });
+
+        // 表記ゆれの場所を選び、選択中の場所をそちらにまとめる
+        ui.horizontal(|ui| {
+            let places: Vec<Place> = self.tree.places.iter().filter(|place| place.id != place_id).cloned().collect();
+            ui.label(t("merge_place_into"));
+            Self::render_place_picker(ui, &places, "place_merge_target", &mut self.place_editor.merge_target, t);
+            if let Some(target_id) = self.place_editor.merge_target
+                && ui.button(t("merge_places")).on_hover_text(t("merge_places_hint")).clicked()
+            {
+                self.merge_places(target_id, &[place_id], t);
+            }
+        });
+        // 統合する前に、付け替わる参照を一覧で見せておく
+        if let Some(target) = self.place_editor.merge_target.and_then(|id| self.tree.get_place(id)) {
+            const MAX_PREVIEW_REFERENCES: usize = 10;
+            let references = self.tree.place_references(place_id);
+            ui.weak(format!("{} ({}) → {}", t("merge_places_preview"), references.len(), target.name));
+            for reference in references.iter().take(MAX_PREVIEW_REFERENCES) {
+                let (name, kind) = match *reference {
+                    PlaceReference::BirthPlace(id) => (self.tree.persons.get(&id).map(|p| p.name.clone()), "birth_place"),
+                    PlaceReference::DeathPlace(id) => (self.tree.persons.get(&id).map(|p| p.name.clone()), "death_place"),
+                    PlaceReference::Event(id) => (self.tree.events.get(&id).map(|e| e.name.clone()), "event_place"),
+                };
+                ui.weak(format!("  {} – {}", name.unwrap_or_default(), t(kind)));
+            }
+            if references.len() > MAX_PREVIEW_REFERENCES {
+                ui.weak(format!("  … +{}", references.len() - MAX_PREVIEW_REFERENCES));
+            }
+        }
+    }
+
+    fn update_selected_place(&mut self, place_id: PlaceId, t: &impl Fn(&str) -> String) {
+        let name = self.place_editor.new_name.trim().to_string();
+        if name.is_empty() {
+            self.toasts.warning(t("name_required"));
+            return;
+        }
+        let parse_coordinate = |text: &str, limit: f64| -> Result<Option<f64>, ()> {
+            let text = text.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            match text.parse::<f64>() {
+                Ok(value) if value.abs() <= limit => Ok(Some(value)),
+                _ => Err(()),
+            }
+        };
+        let (Ok(latitude), Ok(longitude)) = (
+            parse_coordinate(&self.place_editor.new_latitude, 90.0),
+            parse_coordinate(&self.place_editor.new_longitude, 180.0),
+        ) else {
+            self.toasts.warning(t("invalid_coordinates"));
+            return;
+        };
+
+        if let Some(place) = self.tree.place_mut(place_id) {
+            place.name = name.clone();
+            place.latitude = latitude;
+            place.longitude = longitude;
+            place.note = self.place_editor.new_note.clone();
+            self.toasts.info(t("place_updated"));
+            self.log.add(format!("{}: {name}", t("log_place_updated")), LogLevel::Debug);
+        }
+    }
+
+    fn delete_selected_place(&mut self, place_id: PlaceId, t: &impl Fn(&str) -> String) {
+        let name = self.tree.get_place(place_id).map(|place| place.name.clone()).unwrap_or_default();
+        self.tree.remove_place(place_id);
+        self.place_editor.clear();
+        self.toasts.info(t("place_deleted"));
+        self.log.add(format!("{}: {name}", t("log_place_deleted")), LogLevel::Debug);
+    }
+
+    /// 重複した場所を keep にまとめる（まとめて元に戻せるよう、ひとつの操作として記録する）
+    fn merge_places(&mut self, keep: PlaceId, duplicates: &[PlaceId], t: &impl Fn(&str) -> String) {
+        let place_name = |id: PlaceId| self.tree.get_place(id).map(|place| place.name.clone()).unwrap_or_default();
+        let duplicate_names: Vec<String> = duplicates.iter().map(|id| place_name(*id)).collect();
+        let message = format!("{}: {} → {}", t("log_places_merged"), duplicate_names.join(", "), place_name(keep));
+        self.apply_undoable(t("log_places_merged"), |tree| {
+            for duplicate in duplicates {
+                tree.merge_places(keep, *duplicate);
+            }
+        });
+        self.select_place(keep);
+        self.toasts.info(t("places_merged"));
+        self.log.add(message, LogLevel::Debug);
+    }
+
+    /// 大文字小文字・空白・記号だけが違う場所の一覧と、まとめて統合するボタン
+    fn render_duplicate_places(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let groups = self.tree.duplicate_place_groups();
+        if groups.is_empty() {
+            return;
+        }
+        ui.heading(t("duplicate_places"));
+        for group in groups {
+            let names: Vec<String> = group
+                .iter()
+                .filter_map(|id| self.tree.get_place(*id))
+                .map(|place| place.name.clone())
+                .collect();
+            ui.horizontal_wrapped(|ui| {
+                ui.label(names.join(" / "));
+                if ui.small_button(t("merge_places")).on_hover_text(t("merge_duplicate_places_hint")).clicked() {
+                    self.merge_places(group[0], &group[1..], t);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::relation_cleanup::{apply_relation_fixes, find_relation_issues, RelationIssue};
+use crate::ui::LogLevel;
+
+/// 関係の整理ダイアログの描画トレイト
+pub trait RelationCleanupDialogRenderer {
+    fn render_relation_cleanup_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl RelationCleanupDialogRenderer for App {
+    fn render_relation_cleanup_dialog(&mut self, ctx: &egui::Context) {
+        if !self.relation_cleanup.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut apply = false;
+        let mut undo = false;
+        let mut rescan = false;
+
+        let descriptions: Vec<(String, String)> = self
+            .relation_cleanup
+            .issues
+            .iter()
+            .map(|issue| self.describe_relation_issue(issue, &t))
+            .collect();
+
+        egui::Window::new(t("relation_cleanup"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                let state = &mut self.relation_cleanup;
+                if state.issues.is_empty() {
+                    ui.weak(t("cleanup_no_issues"));
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}: {}", t("cleanup_found"), state.issues.len()));
+                        if ui.small_button(t("cleanup_select_all")).clicked() {
+                            state.checked.iter_mut().for_each(|checked| *checked = true);
+                        }
+                        if ui.small_button(t("cleanup_select_none")).clicked() {
+                            state.checked.iter_mut().for_each(|checked| *checked = false);
+                        }
+                    });
+                    egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                        for (checked, (issue, fix)) in state.checked.iter_mut().zip(&descriptions) {
+                            ui.checkbox(checked, issue);
+                            ui.weak(format!("    → {fix}"));
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let can_apply = state.checked.iter().any(|checked| *checked) && !self.ui.read_only;
+                    if ui.add_enabled(can_apply, egui::Button::new(t("cleanup_apply"))).clicked() {
+                        apply = true;
+                    }
+                    if ui.button(t("cleanup_rescan")).clicked() {
+                        rescan = true;
+                    }
+                    let can_undo = state.last_change.is_some_and(|id| self.undo_history.contains(id)) && !self.ui.read_only;
+                    if ui.add_enabled(can_undo, egui::Button::new(t("cleanup_undo"))).clicked() {
+                        undo = true;
+                    }
+                });
+            });
+
+        if apply {
+            let fixes: Vec<RelationIssue> = self
+                .relation_cleanup
+                .issues
+                .iter()
+                .zip(&self.relation_cleanup.checked)
+                .filter(|(_, checked)| **checked)
+                .map(|(issue, _)| issue.clone())
+                .collect();
+            // 共通の履歴に記録し、修正した関係だけをまとめて元に戻せるようにする
+            let (count, change) = self.apply_undoable(t("relation_cleanup"), |tree| apply_relation_fixes(tree, &fixes));
+            self.relation_cleanup.last_change = change;
+            self.toasts.info(format!("{}: {}", t("cleanup_applied"), count));
+            self.log.add(format!("{}: {}", t("cleanup_applied"), count), LogLevel::Debug);
+        }
+        if undo && let Some(id) = self.relation_cleanup.last_change.take() {
+            self.undo_tree_change(Some(id));
+        }
+        if apply || undo || rescan {
+            self.scan_relation_issues();
+        }
+        if !open {
+            // 閉じた後も修正は編集メニューの「元に戻す」から戻せる
+            self.relation_cleanup.show = false;
+            self.relation_cleanup.last_change = None;
+        }
+    }
+}
+
+impl App {
+    /// 関係の整理ダイアログを開く（開くたびに調べ直す）
+    pub fn open_relation_cleanup(&mut self) {
+        self.relation_cleanup.show = true;
+        self.relation_cleanup.last_change = None;
+        self.scan_relation_issues();
+    }
+
+    fn scan_relation_issues(&mut self) {
+        let issues = find_relation_issues(&self.tree);
+        self.relation_cleanup.checked = vec![true; issues.len()];
+        self.relation_cleanup.issues = issues;
+    }
+
+    /// 問題の説明と修正内容の説明
+    fn describe_relation_issue(&self, issue: &RelationIssue, t: &impl Fn(&str) -> String) -> (String, String) {
+        let name = |id| self.get_person_name(id);
+        match issue {
+            RelationIssue::DuplicateSpouse { person1, person2, count } => (
+                format!("{}: {} – {} (×{count})", t("cleanup_duplicate_spouse"), name(person1), name(person2)),
+                t("cleanup_fix_keep_first"),
+            ),
+            RelationIssue::RedundantParentEdge { parent, child, kinds } => (
+                format!(
+                    "{}: {} → {} ({})",
+                    t("cleanup_redundant_parent"),
+                    name(parent),
+                    name(child),
+                    kinds.join(", ")
+                ),
+                format!("{}: {}", t("cleanup_fix_keep_kind"), kinds[0]),
+            ),
+            RelationIssue::MutualParent { parent, child } => (
+                format!("{}: {} ⇄ {}", t("cleanup_mutual_parent"), name(parent), name(child)),
+                format!("{}: {} → {}", t("cleanup_fix_remove_parent"), name(child), name(parent)),
+            ),
+            RelationIssue::ParentAndSpouse { parent, child } => (
+                format!("{}: {} – {}", t("cleanup_parent_and_spouse"), name(parent), name(child)),
+                t("cleanup_fix_remove_spouse"),
+            ),
+            RelationIssue::SelfRelation { person } => (
+                format!("{}: {}", t("cleanup_self_relation"), name(person)),
+                t("cleanup_fix_remove_self"),
+            ),
+        }
+    }
+}
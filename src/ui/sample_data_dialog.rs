@@ -0,0 +1,95 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::sample_data::{self, SampleNameLocale, MAX_SAMPLE_CHILDREN, MAX_SAMPLE_GENERATIONS};
+use crate::ui::{LogLevel, PendingFileAction};
+
+/// サンプルデータ生成ダイアログの描画トレイト
+pub trait SampleDataDialogRenderer {
+    fn render_sample_data_dialog(&mut self, ctx: &egui::Context);
+}
+
+impl SampleDataDialogRenderer for App {
+    fn render_sample_data_dialog(&mut self, ctx: &egui::Context) {
+        if !self.sample_data.show {
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let mut open = true;
+        let mut generate = false;
+
+        egui::Window::new(t("generate_sample"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let options = &mut self.sample_data.options;
+                egui::Grid::new("sample_data_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(t("sample_generations"));
+                    ui.add(egui::Slider::new(&mut options.generations, 1..=MAX_SAMPLE_GENERATIONS));
+                    ui.end_row();
+
+                    ui.label(t("sample_children_per_couple"));
+                    ui.add(egui::Slider::new(&mut options.children_per_couple, 1..=MAX_SAMPLE_CHILDREN));
+                    ui.end_row();
+
+                    ui.label(t("sample_name_locale"));
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut options.locale, SampleNameLocale::Japanese, t("japanese"));
+                        ui.radio_value(&mut options.locale, SampleNameLocale::English, t("english"));
+                    });
+                    ui.end_row();
+
+                    ui.label(t("sample_seed"));
+                    ui.add(egui::DragValue::new(&mut options.seed));
+                    ui.end_row();
+                });
+                ui.checkbox(&mut options.include_photos, t("sample_include_photos"));
+                ui.checkbox(&mut options.include_events, t("sample_include_events"));
+
+                ui.separator();
+                ui.weak(t("sample_replaces_tree"));
+                if ui.button(t("generate")).clicked() {
+                    generate = true;
+                }
+            });
+
+        if generate {
+            self.sample_data.show = false;
+            self.request_file_action(PendingFileAction::LoadSample, ctx);
+        }
+        if !open {
+            self.sample_data.show = false;
+        }
+    }
+}
+
+impl App {
+    /// サンプルデータで家系図を置き換える（新規作成と同じく、保存先とファイルのロックは手放す）
+    pub(crate) fn load_sample_tree(&mut self) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let tree = sample_data::generate_sample_tree(&self.sample_data.options);
+        self.session.switch_tree(&self.tree, &tree);
+        self.tree = tree;
+        self.undo_history.clear();
+        self.person_editor.selected = None;
+        self.person_editor.selected_ids.clear();
+        self.family_editor.selected_family = None;
+        self.event_editor.selected = None;
+        // 既存のファイルを上書きしないよう保存先は未設定にする
+        self.file.file_path.clear();
+        self.file.lock = None;
+        self.file.detected_format = None;
+        self.file.last_saved_at = None;
+        self.toasts.info(format!("{}: {}", t("sample_generated"), self.tree.persons.len()));
+        self.log.add(
+            format!("{}: {}", t("sample_generated"), self.tree.persons.len()),
+            LogLevel::Debug,
+        );
+        self.fit_canvas_to_contents();
+    }
+}
@@ -1,6 +1,8 @@
 use crate::app::App;
-use crate::core::i18n::Language;
-use crate::ui::NodeColorThemePreset;
+use crate::core::i18n::{self, Language};
+use crate::core::layout::{GridStyle, LayoutDirection};
+use crate::core::tree::{Gender, NodeStyle, PersonDisplayMode};
+use crate::ui::{EdgeStyle, NodeColorThemePreset, UiFontFamily, DEFAULT_UI_FONT_SIZE, MAX_BACKUP_KEEP, MAX_UI_FONT_SIZE, MIN_UI_FONT_SIZE};
 
 /// 設定タブのUI描画トレイト
 pub trait SettingsTabRenderer {
@@ -37,6 +39,35 @@ impl SettingsTabRenderer for App {
                 )
                 .changed();
         });
+        let grid = &mut self.canvas.grid;
+        ui.horizontal(|ui| {
+            has_changed |= ui.radio_value(&mut grid.style, GridStyle::Lines, t("grid_style_lines")).changed();
+            has_changed |= ui.radio_value(&mut grid.style, GridStyle::Dots, t("grid_style_dots")).changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label(t("grid_major_every"));
+            has_changed |= ui.add(egui::DragValue::new(&mut grid.major_every).speed(1.0).range(0..=20)).changed();
+        })
+        .response
+        .on_hover_text(t("grid_major_every_hint"));
+        has_changed |= ui.checkbox(&mut grid.snap_while_dragging, t("snap_while_dragging")).changed();
+        ui.horizontal(|ui| {
+            ui.label(t("snap_strength"));
+            has_changed |= ui.add(egui::Slider::new(&mut grid.snap_strength, 0.0..=1.0)).changed();
+        })
+        .response
+        .on_hover_text(t("snap_strength_hint"));
+        has_changed |= ui
+            .checkbox(&mut self.canvas.show_rulers, t("show_rulers"))
+            .on_hover_text(t("show_rulers_hint"))
+            .changed();
+        ui.horizontal(|ui| {
+            ui.label(format!("{}: {}", t("canvas_guides"), self.tree.guides.len()));
+            let can_clear = !self.tree.guides.is_empty() && !self.ui.read_only;
+            if ui.add_enabled(can_clear, egui::Button::new(t("clear_guides"))).clicked() {
+                self.apply_undoable(t("clear_guides"), |tree| tree.guides.clear());
+            }
+        });
 
         ui.separator();
         ui.label(t("node_color_theme"));
@@ -56,9 +87,240 @@ impl SettingsTabRenderer for App {
                 )
                 .changed();
         });
+        has_changed |= ui
+            .checkbox(&mut self.ui.high_contrast_ui, t("high_contrast_ui"))
+            .on_hover_text(t("high_contrast_ui_hint"))
+            .changed();
+
+        ui.separator();
+        ui.label(t("ui_font"));
+        ui.horizontal(|ui| {
+            has_changed |= ui
+                .radio_value(&mut self.ui.ui_font_family, UiFontFamily::Bundled, t("ui_font_bundled"))
+                .changed();
+            has_changed |= ui
+                .radio_value(&mut self.ui.ui_font_family, UiFontFamily::System, t("ui_font_system"))
+                .changed();
+        });
+        // 反映済みの設定についてだけ、見つかったフォントを表示する
+        if self.ui.applied_ui_font.map(|(family, _)| family) == Some(UiFontFamily::System) {
+            match &self.ui.system_font_path {
+                Some(path) => ui.weak(path),
+                None => ui.colored_label(ui.visuals().warn_fg_color, t("ui_font_system_not_found")),
+            };
+        }
+        ui.horizontal(|ui| {
+            ui.label(t("ui_font_size"));
+            has_changed |= ui
+                .add(egui::Slider::new(&mut self.ui.ui_font_size, MIN_UI_FONT_SIZE..=MAX_UI_FONT_SIZE).step_by(1.0))
+                .changed();
+            if ui.small_button(t("ui_font_size_reset")).clicked() {
+                self.ui.ui_font_size = DEFAULT_UI_FONT_SIZE;
+                has_changed = true;
+            }
+        });
+
+        // ノードの形は家系図ファイルに保存する（アプリの設定ではない）
+        ui.separator();
+        ui.label(t("node_style"));
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.tree.node_style, NodeStyle::Box, t("node_style_box"));
+                ui.radio_value(&mut self.tree.node_style, NodeStyle::CircularAvatar, t("node_style_circular_avatar"));
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(t("other_gender_color"));
+            has_changed |= ui
+                .color_edit_button_srgb(&mut self.ui.other_gender_color)
+                .changed();
+        });
+
+        ui.separator();
+        ui.label(t("layout_direction"));
+        ui.horizontal(|ui| {
+            has_changed |= ui
+                .radio_value(
+                    &mut self.ui.layout_direction,
+                    LayoutDirection::LeftToRight,
+                    t("layout_direction_ltr"),
+                )
+                .changed();
+            has_changed |= ui
+                .radio_value(
+                    &mut self.ui.layout_direction,
+                    LayoutDirection::RightToLeft,
+                    t("layout_direction_rtl"),
+                )
+                .changed();
+        });
+
+        ui.separator();
+        ui.label(t("presumed_deceased"));
+        has_changed |= ui
+            .checkbox(&mut self.ui.presume_deceased, t("presume_deceased"))
+            .changed();
+        ui.add_enabled_ui(self.ui.presume_deceased, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t("presumed_deceased_years"));
+                has_changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.ui.presumed_deceased_years)
+                            .speed(1.0)
+                            .range(50..=200),
+                    )
+                    .changed();
+            });
+        });
+
+        ui.separator();
+        ui.label(t("edge_style"));
+        ui.horizontal(|ui| {
+            has_changed |= ui
+                .radio_value(&mut self.ui.edge_style, EdgeStyle::Straight, t("edge_style_straight"))
+                .changed();
+            has_changed |= ui
+                .radio_value(&mut self.ui.edge_style, EdgeStyle::Bezier, t("edge_style_bezier"))
+                .changed();
+        });
+        ui.add_enabled_ui(self.ui.edge_style == EdgeStyle::Bezier, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t("bezier_control_offset"));
+                has_changed |= ui
+                    .add(egui::Slider::new(&mut self.ui.bezier_control_offset, 0.0..=200.0))
+                    .changed();
+            });
+        });
+
+        ui.separator();
+        ui.label(t("semantic_zoom"));
+        ui.weak(t("semantic_zoom_hint"));
+        let thresholds = &mut self.ui.semantic_zoom;
+        egui::Grid::new("semantic_zoom_grid").num_columns(2).show(ui, |ui| {
+            for (label_key, value) in [
+                ("semantic_zoom_photo", &mut thresholds.photo),
+                ("semantic_zoom_dates", &mut thresholds.dates),
+                ("semantic_zoom_name", &mut thresholds.name),
+            ] {
+                ui.label(t(label_key));
+                has_changed |= ui
+                    .add(egui::Slider::new(value, 0.0..=1.5).suffix("×"))
+                    .changed();
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+        ui.label(t("relation_display"));
+        has_changed |= ui
+            .checkbox(&mut self.ui.show_step_relations, t("show_step_relations"))
+            .changed();
+
+        ui.separator();
+        has_changed |= self.render_new_person_defaults(ui, &t);
+
+        ui.separator();
+        ui.label(t("secondary_backup"));
+        ui.weak(t("secondary_backup_hint"));
+        ui.horizontal(|ui| {
+            has_changed |= ui
+                .add(egui::TextEdit::singleline(&mut self.ui.backup_dir).hint_text(t("secondary_backup_disabled")))
+                .lost_focus();
+            if ui.button(t("choose_backup_dir")).clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.ui.backup_dir = dir.display().to_string();
+                has_changed = true;
+            }
+        });
+        ui.add_enabled_ui(!self.ui.backup_dir.trim().is_empty(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t("secondary_backup_keep"));
+                has_changed |= ui
+                    .add(egui::DragValue::new(&mut self.ui.backup_keep).speed(1.0).range(1..=MAX_BACKUP_KEEP))
+                    .changed();
+            });
+        });
 
         if has_changed {
             self.save_settings();
         }
+
+        Self::render_missing_translations(ui, &t);
+    }
+}
+
+impl App {
+    /// 新しい人物の初期値の設定（変更があれば true）
+    fn render_new_person_defaults(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) -> bool {
+        let mut has_changed = false;
+        let defaults = &mut self.ui.new_person_defaults;
+        ui.label(t("new_person_defaults"));
+        egui::Grid::new("new_person_defaults_grid").num_columns(2).show(ui, |ui| {
+            ui.label(t("new_person_name_template"));
+            has_changed |= ui
+                .add(egui::TextEdit::singleline(&mut defaults.name_template).hint_text(t("new_person")))
+                .on_hover_text(t("new_person_name_template_hint"))
+                .lost_focus();
+            ui.end_row();
+
+            ui.label(t("gender"));
+            ui.horizontal(|ui| {
+                for (gender, key) in [(Gender::Male, "male"), (Gender::Female, "female"), (Gender::Unknown, "unknown")] {
+                    has_changed |= ui.radio_value(&mut defaults.gender, gender, t(key)).changed();
+                }
+            });
+            ui.end_row();
+
+            ui.label(t("display_mode"));
+            ui.horizontal(|ui| {
+                for (mode, key) in [
+                    (PersonDisplayMode::NameOnly, "name_only"),
+                    (PersonDisplayMode::NameAndPhoto, "name_and_photo"),
+                    (PersonDisplayMode::PhotoOnly, "photo_only"),
+                ] {
+                    has_changed |= ui.radio_value(&mut defaults.display_mode, mode, t(key)).changed();
+                }
+            });
+            ui.end_row();
+
+            ui.label(t("new_person_relation_kind"));
+            let response = ui.text_edit_singleline(&mut defaults.relation_kind);
+            if response.lost_focus() {
+                // 関係の追加フォームにもすぐ反映する
+                self.relation_editor.relation_kind = defaults.relation_kind.clone();
+                has_changed = true;
+            }
+            ui.end_row();
+        });
+        has_changed
+    }
+
+    /// 翻訳のないキーの一覧（開発用。警告はデバッグビルドでのみ記録される）
+    fn render_missing_translations(ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let missing = i18n::missing_keys();
+        if missing.is_empty() {
+            return;
+        }
+        ui.separator();
+        egui::CollapsingHeader::new(format!("⚠ {} ({})", t("missing_translations"), missing.len()))
+            .id_salt("missing_translations")
+            .show(ui, |ui| {
+                for lang in [Language::Japanese, Language::English] {
+                    let keys: Vec<&str> = missing
+                        .iter()
+                        .filter(|(missing_lang, _)| *missing_lang == lang)
+                        .map(|(_, key)| key.as_str())
+                        .collect();
+                    if keys.is_empty() {
+                        continue;
+                    }
+                    ui.strong(format!("{} ({})", lang.code(), keys.len()));
+                    for key in keys {
+                        ui.monospace(key);
+                    }
+                }
+            });
     }
 }
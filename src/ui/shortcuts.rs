@@ -0,0 +1,140 @@
+use eframe::egui;
+
+/// ショートカットの入力方法
+#[derive(Debug, Clone, Copy)]
+pub enum ShortcutInput {
+    /// キーボードのキー（修飾キー付き）
+    Key(egui::KeyboardShortcut),
+    /// マウス・タッチ操作（表記の翻訳キー）
+    Gesture(&'static str),
+}
+
+/// ショートカット一覧の1項目（分類と操作名は翻訳キー）
+#[derive(Debug, Clone, Copy)]
+pub struct Shortcut {
+    pub category: &'static str,
+    pub action: &'static str,
+    pub input: ShortcutInput,
+}
+
+impl Shortcut {
+    const fn key(category: &'static str, action: &'static str, modifiers: egui::Modifiers, key: egui::Key) -> Self {
+        Self {
+            category,
+            action,
+            input: ShortcutInput::Key(egui::KeyboardShortcut::new(modifiers, key)),
+        }
+    }
+
+    const fn gesture(category: &'static str, action: &'static str, gesture: &'static str) -> Self {
+        Self {
+            category,
+            action,
+            input: ShortcutInput::Gesture(gesture),
+        }
+    }
+
+    /// このフレームでショートカットが押されたか（押されていればキー入力を消費する）
+    ///
+    /// 修飾キーは egui の `consume_shortcut` で照合するため、余分な Shift/Alt は無視される。
+    /// Ctrl+Shift+I と Ctrl+I のように重なるものは、修飾キーの多い方から先に調べること
+    pub fn pressed(&self, ctx: &egui::Context) -> bool {
+        let ShortcutInput::Key(shortcut) = self.input else {
+            return false;
+        };
+        ctx.input_mut(|i| i.consume_shortcut(&shortcut))
+    }
+
+    /// キーの表記（例: "Ctrl+S"）。マウス操作の場合は None
+    pub fn key_label(&self) -> Option<String> {
+        let ShortcutInput::Key(shortcut) = self.input else {
+            return None;
+        };
+        let mut parts = Vec::new();
+        if shortcut.modifiers.command {
+            parts.push(if cfg!(target_os = "macos") { "Cmd" } else { "Ctrl" });
+        } else if shortcut.modifiers.ctrl {
+            parts.push("Ctrl");
+        }
+        if shortcut.modifiers.alt {
+            parts.push("Alt");
+        }
+        if shortcut.modifiers.shift {
+            parts.push("Shift");
+        }
+        parts.push(shortcut.logical_key.symbol_or_name());
+        Some(parts.join("+"))
+    }
+}
+
+/// メニュー項目などに表示する「操作名 (キー)」の表記
+pub fn shortcut_button_label(label: &str, shortcut: &Shortcut) -> String {
+    match shortcut.key_label() {
+        Some(keys) => format!("{label} ({keys})"),
+        None => label.to_string(),
+    }
+}
+
+pub const SHORTCUT_SAVE: Shortcut = Shortcut::key("shortcut_category_file", "save", egui::Modifiers::COMMAND, egui::Key::S);
+pub const SHORTCUT_OPEN: Shortcut = Shortcut::key("shortcut_category_file", "open", egui::Modifiers::COMMAND, egui::Key::O);
+pub const SHORTCUT_UNDO: Shortcut = Shortcut::key("shortcut_category_edit", "undo", egui::Modifiers::COMMAND, egui::Key::Z);
+pub const SHORTCUT_REDO: Shortcut = Shortcut::key("shortcut_category_edit", "redo", egui::Modifiers::COMMAND, egui::Key::Y);
+pub const SHORTCUT_FIND_REPLACE: Shortcut =
+    Shortcut::key("shortcut_category_edit", "find_replace", egui::Modifiers::COMMAND, egui::Key::H);
+pub const SHORTCUT_SELECTION_BACK: Shortcut =
+    Shortcut::key("shortcut_category_navigation", "selection_back", egui::Modifiers::ALT, egui::Key::ArrowLeft);
+pub const SHORTCUT_SELECTION_FORWARD: Shortcut =
+    Shortcut::key("shortcut_category_navigation", "selection_forward", egui::Modifiers::ALT, egui::Key::ArrowRight);
+pub const SHORTCUT_GO_HOME: Shortcut =
+    Shortcut::key("shortcut_category_navigation", "go_to_home_person", egui::Modifiers::ALT, egui::Key::Home);
+pub const SHORTCUT_NEXT_SIDE_TAB: Shortcut =
+    Shortcut::key("shortcut_category_navigation", "next_side_tab", egui::Modifiers::COMMAND, egui::Key::PageDown);
+pub const SHORTCUT_PREVIOUS_SIDE_TAB: Shortcut =
+    Shortcut::key("shortcut_category_navigation", "previous_side_tab", egui::Modifiers::COMMAND, egui::Key::PageUp);
+pub const SHORTCUT_ZOOM_RESET: Shortcut =
+    Shortcut::key("shortcut_category_canvas", "zoom_reset", egui::Modifiers::COMMAND, egui::Key::Num0);
+pub const SHORTCUT_COPY_AS_IMAGE: Shortcut =
+    Shortcut::key("shortcut_category_canvas", "copy_as_image", egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::I);
+pub const SHORTCUT_SHOW_SHORTCUTS: Shortcut =
+    Shortcut::key("shortcut_category_help", "shortcuts", egui::Modifiers::NONE, egui::Key::F1);
+
+const fn camera_bookmark_shortcut(key: egui::Key) -> Shortcut {
+    Shortcut::key("shortcut_category_navigation", "camera_bookmarks", egui::Modifiers::COMMAND, key)
+}
+
+/// 保存した表示位置（カメラのブックマーク）の1件目〜9件目に移動する Ctrl+1〜9
+pub const CAMERA_BOOKMARK_SHORTCUTS: [Shortcut; 9] = [
+    camera_bookmark_shortcut(egui::Key::Num1),
+    camera_bookmark_shortcut(egui::Key::Num2),
+    camera_bookmark_shortcut(egui::Key::Num3),
+    camera_bookmark_shortcut(egui::Key::Num4),
+    camera_bookmark_shortcut(egui::Key::Num5),
+    camera_bookmark_shortcut(egui::Key::Num6),
+    camera_bookmark_shortcut(egui::Key::Num7),
+    camera_bookmark_shortcut(egui::Key::Num8),
+    camera_bookmark_shortcut(egui::Key::Num9),
+];
+
+/// アプリ全体のショートカット一覧（ヘルプの一覧表示はここから生成する）
+pub const SHORTCUTS: &[Shortcut] = &[
+    SHORTCUT_SAVE,
+    SHORTCUT_OPEN,
+    SHORTCUT_UNDO,
+    SHORTCUT_REDO,
+    SHORTCUT_FIND_REPLACE,
+    SHORTCUT_SELECTION_BACK,
+    SHORTCUT_SELECTION_FORWARD,
+    SHORTCUT_GO_HOME,
+    SHORTCUT_NEXT_SIDE_TAB,
+    SHORTCUT_PREVIOUS_SIDE_TAB,
+    Shortcut::gesture("shortcut_category_navigation", "shortcut_focus_persons", "gesture_tab"),
+    Shortcut::gesture("shortcut_category_navigation", "camera_bookmarks", "gesture_ctrl_number"),
+    Shortcut::gesture("shortcut_category_canvas", "shortcut_zoom", "gesture_ctrl_wheel"),
+    SHORTCUT_ZOOM_RESET,
+    SHORTCUT_COPY_AS_IMAGE,
+    Shortcut::gesture("shortcut_category_canvas", "shortcut_pan", "gesture_drag_background"),
+    Shortcut::gesture("shortcut_category_canvas", "fit_to_view", "gesture_double_click_background"),
+    Shortcut::gesture("shortcut_category_canvas", "shortcut_multi_select", "gesture_ctrl_click"),
+    Shortcut::gesture("shortcut_category_canvas", "insert_couple_with_child", "gesture_right_click_background"),
+    SHORTCUT_SHOW_SHORTCUTS,
+];
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::slideshow::{self, SlideshowSource};
+
+/// スライドショーで写真を表示する最大サイズ
+const SLIDE_MAX_SIZE: egui::Vec2 = egui::vec2(480.0, 480.0);
+
+/// 写真スライドショーの描画トレイト
+pub trait SlideshowRenderer {
+    fn render_slideshow(&mut self, ctx: &egui::Context);
+}
+
+impl SlideshowRenderer for App {
+    fn render_slideshow(&mut self, ctx: &egui::Context) {
+        let Some(source) = self.slideshow.source else {
+            return;
+        };
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let slides = slideshow::collect_slides(&self.tree, source);
+        let target_name = match source {
+            SlideshowSource::Person(person_id) => self.get_person_name(&person_id),
+            SlideshowSource::Family(family_id) => self
+                .tree
+                .families
+                .iter()
+                .find(|family| family.id == family_id)
+                .map(|family| family.name.clone())
+                .unwrap_or_else(|| t("unknown")),
+        };
+
+        // 一定間隔で次の写真へ進める
+        if self.slideshow.playing && slides.len() > 1 {
+            let now = ctx.input(|i| i.time);
+            let last = *self.slideshow.last_advanced_at.get_or_insert(now);
+            let interval = f64::from(self.slideshow.interval_seconds);
+            if now - last >= interval {
+                self.slideshow.index = (self.slideshow.index + 1) % slides.len();
+                self.slideshow.last_advanced_at = Some(now);
+            }
+            let remaining = (interval - (now - self.slideshow.last_advanced_at.unwrap_or(now))).max(0.0);
+            ctx.request_repaint_after(Duration::from_secs_f64(remaining));
+        }
+        if self.slideshow.index >= slides.len() {
+            self.slideshow.index = 0;
+        }
+
+        let mut open = true;
+        egui::Window::new(format!("{}: {}", t("slideshow"), target_name))
+            .id(egui::Id::new("slideshow_window"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let Some(slide) = slides.get(self.slideshow.index) else {
+                    ui.label(t("slideshow_no_photos"));
+                    return;
+                };
+
+                ui.vertical_centered(|ui| {
+                    if let Some(photo_path) = &slide.photo_path {
+                        match self.canvas.photo_texture_cache.get_or_load(ctx, photo_path) {
+                            Some(texture) => {
+                                ui.add(egui::Image::new(&texture).max_size(SLIDE_MAX_SIZE));
+                            }
+                            None => {
+                                ui.weak(format!("{}: {}", t("slideshow_photo_missing"), photo_path));
+                            }
+                        }
+                        ui.add_space(6.0);
+                    }
+                    ui.heading(&slide.caption);
+                    if let Some(dates) = &slide.dates {
+                        ui.label(dates);
+                    }
+                    if !slide.description.is_empty() {
+                        ui.label(&slide.description);
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let has_many = slides.len() > 1;
+                    if ui.add_enabled(has_many, egui::Button::new("◀")).clicked() {
+                        self.slideshow.index = (self.slideshow.index + slides.len() - 1) % slides.len();
+                        self.slideshow.last_advanced_at = None;
+                    }
+                    let play_label = if self.slideshow.playing { t("slideshow_pause") } else { t("slideshow_play") };
+                    if ui.add_enabled(has_many, egui::Button::new(play_label)).clicked() {
+                        self.slideshow.playing = !self.slideshow.playing;
+                        self.slideshow.last_advanced_at = None;
+                    }
+                    if ui.add_enabled(has_many, egui::Button::new("▶")).clicked() {
+                        self.slideshow.index = (self.slideshow.index + 1) % slides.len();
+                        self.slideshow.last_advanced_at = None;
+                    }
+                    ui.label(format!("{} / {}", self.slideshow.index + 1, slides.len()));
+                    ui.separator();
+                    ui.label(t("slideshow_interval"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.slideshow.interval_seconds)
+                            .range(1.0..=60.0)
+                            .speed(0.1)
+                            .suffix(t("seconds_suffix")),
+                    );
+                });
+            });
+
+        if !open {
+            self.slideshow.source = None;
+        }
+    }
+}
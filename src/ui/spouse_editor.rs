@@ -0,0 +1,146 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::tree::{PersonId, SpouseLabelPosition, SpouseLabelStyle};
+use crate::ui::LogLevel;
+
+/// キャンバスから開く配偶者関係編集ダイアログの描画トレイト
+pub trait SpouseEditorRenderer {
+    fn render_spouse_editor(&mut self, ctx: &egui::Context);
+}
+
+impl SpouseEditorRenderer for App {
+    fn render_spouse_editor(&mut self, ctx: &egui::Context) {
+        let Some((person1, person2)) = self.relation_editor.canvas_spouse_pair else {
+            return;
+        };
+
+        // 関係が削除された場合はダイアログを閉じる
+        if self.tree.spouse_relation(person1, person2).is_none() {
+            self.close_spouse_editor();
+            return;
+        }
+
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+        let title = format!(
+            "{}: {} - {}",
+            t("spouse_editor"),
+            self.get_person_name(&person1),
+            self.get_person_name(&person2)
+        );
+
+        let mut is_open = true;
+        let mut save_clicked = false;
+        let mut cancel_clicked = false;
+
+        egui::Window::new(title)
+            .id(egui::Id::new("spouse_editor_window"))
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t("marriage_date"));
+                    ui.text_edit_singleline(&mut self.relation_editor.canvas_marriage_date);
+                });
+                ui.checkbox(&mut self.relation_editor.canvas_divorced, t("divorced"));
+                if self.relation_editor.canvas_divorced {
+                    ui.horizontal(|ui| {
+                        ui.label(t("divorce_date"));
+                        ui.text_edit_singleline(&mut self.relation_editor.canvas_divorce_date);
+                    });
+                }
+                ui.label(t("memo"));
+                ui.text_edit_multiline(&mut self.relation_editor.canvas_spouse_memo);
+
+                // キャンバス上のメモの表示方法
+                let label = &mut self.relation_editor.canvas_spouse_label;
+                ui.checkbox(&mut label.visible, t("spouse_label_visible"));
+                ui.add_enabled_ui(label.visible, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut label.position, SpouseLabelPosition::Above, t("spouse_label_above"));
+                        ui.radio_value(&mut label.position, SpouseLabelPosition::Below, t("spouse_label_below"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(t("font_size"));
+                        ui.add(egui::Slider::new(&mut label.font_size, 6.0..=32.0));
+                        let mut color = [label.color.0, label.color.1, label.color.2];
+                        if ui.color_edit_button_srgb(&mut color).changed() {
+                            label.color = (color[0], color[1], color[2]);
+                        }
+                    });
+                });
+
+                ui.horizontal(|ui| {
+                    save_clicked = ui.button(t("save")).clicked();
+                    cancel_clicked = ui.button(t("cancel")).clicked();
+                });
+            });
+
+        if save_clicked {
+            self.save_canvas_spouse_relation(person1, person2);
+        }
+        if save_clicked || cancel_clicked || !is_open {
+            self.close_spouse_editor();
+        }
+    }
+}
+
+impl App {
+    /// 配偶者関係の編集ダイアログを開き、現在の値をフォームに読み込む
+    pub(crate) fn open_spouse_editor(&mut self, person1: PersonId, person2: PersonId) {
+        let Some(spouse) = self.tree.spouse_relation(person1, person2) else {
+            return;
+        };
+
+        self.relation_editor.canvas_spouse_pair = Some((person1, person2));
+        self.relation_editor.canvas_marriage_date = spouse.marriage_date.clone().unwrap_or_default();
+        self.relation_editor.canvas_divorced = spouse.divorced;
+        self.relation_editor.canvas_divorce_date = spouse.divorce_date.clone().unwrap_or_default();
+        self.relation_editor.canvas_spouse_memo = spouse.memo.clone();
+        self.relation_editor.canvas_spouse_label = spouse.label;
+    }
+
+    fn close_spouse_editor(&mut self) {
+        self.relation_editor.canvas_spouse_pair = None;
+        self.relation_editor.canvas_marriage_date.clear();
+        self.relation_editor.canvas_divorced = false;
+        self.relation_editor.canvas_divorce_date.clear();
+        self.relation_editor.canvas_spouse_memo.clear();
+        self.relation_editor.canvas_spouse_label = SpouseLabelStyle::default();
+    }
+
+    fn save_canvas_spouse_relation(&mut self, person1: PersonId, person2: PersonId) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let marriage_date = App::parse_optional_field(&self.relation_editor.canvas_marriage_date);
+        let divorced = self.relation_editor.canvas_divorced;
+        let divorce_date = divorced
+            .then(|| App::parse_optional_field(&self.relation_editor.canvas_divorce_date))
+            .flatten();
+        let memo = self.relation_editor.canvas_spouse_memo.clone();
+        let label = self.relation_editor.canvas_spouse_label;
+
+        if let Some(spouse) = self.tree.spouse_relation_mut(person1, person2) {
+            spouse.marriage_date = marriage_date;
+            spouse.divorced = divorced;
+            spouse.divorce_date = divorce_date;
+            spouse.memo = memo;
+            spouse.label = label;
+
+            self.toasts.info(t("spouse_relation_updated"));
+            let names = format!(
+                "{} - {}",
+                self.get_person_name(&person1),
+                self.get_person_name(&person2)
+            );
+            self.log.add(
+                format!("{}: {}", t("log_spouse_relation_updated"), names),
+                LogLevel::Debug,
+            );
+        }
+    }
+}
@@ -1,15 +1,28 @@
 use eframe::egui;
 use serde::{Deserialize, Serialize};
-use crate::core::tree::{Gender, PersonId, EventId, EventRelationType, PersonDisplayMode};
+use chrono::Datelike;
+use crate::core::tree::{AnnotationId, CanvasGuide, DeceasedPresumption, FamilyTree, Gender, PersonId, EventId, EventRelationType, ExternalPersonLink, Family, PersonDisplayMode, PersonRelationKind, PlaceId, SpouseLabelStyle, DEFAULT_ANNOTATION_FONT_SIZE, DEFAULT_PRESUMED_DECEASED_YEARS};
+use crate::application::{ChartScene, ImageExportOptions, PosterOptions};
 use crate::core::i18n::Language;
-use crate::infrastructure::PhotoTextureCache;
+use crate::core::bulk_edit::BulkEdit;
+use crate::core::filter::PersonFilter;
+use crate::core::sample_data::SampleOptions;
+use crate::core::slideshow::SlideshowSource;
+use crate::core::statistics::EditCounts;
+use crate::core::find_replace::FindReplaceQuery;
+use crate::core::relation_cleanup::RelationIssue;
+use crate::core::edit_macro::MacroStep;
+use crate::core::undo::UndoId;
+use crate::core::layout::{GridSettings, LayoutDirection, SemanticZoomThresholds};
+use crate::infrastructure::{ActivityLogRecord, BrokenPhoto, ActivityLogStore, ErrorLogFile, PhotoTextureCache, StorageFormat, OverwriteWarning, RecoverySnapshot, RecoverySnapshotStore, LockOwner, PhotoEdit, TreeFileLock};
 use uuid::Uuid;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::Instant;
 
 /// ログレベル
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum LogLevel {
     Critical,
     Error,
@@ -19,6 +32,19 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Critical,
+        LogLevel::Error,
+        LogLevel::Warning,
+        LogLevel::Information,
+        LogLevel::Debug,
+    ];
+
+    /// as_str の表記からログレベルを復元
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.as_str() == name)
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             LogLevel::Critical => "CRITICAL",
@@ -46,11 +72,20 @@ impl Default for LogLevel {
     }
 }
 
+/// エラーダイアログの内容
+#[derive(Clone, Debug)]
+pub struct ErrorDialog {
+    pub title: String,
+    pub message: String,
+}
+
 /// ログメッセージ
 #[derive(Clone)]
 pub struct LogMessage {
     pub message: String,
     pub timestamp: String,
+    /// 記録した日付（前回までのセッションの履歴と区別する）
+    pub date: String,
     pub level: LogLevel,
 }
 
@@ -59,6 +94,14 @@ pub struct LogState {
     pub messages: Vec<LogMessage>,
     pub max_messages: usize,
     pub log_file_path: Option<PathBuf>,
+    /// エラー・警告だけを残す設定ディレクトリ内のログファイル
+    pub error_log: Option<ErrorLogFile>,
+    /// セッションをまたいで操作履歴を保持する保存先
+    pub activity_store: Option<ActivityLogStore>,
+    /// ログパネルで非表示にするレベル
+    pub hidden_levels: std::collections::HashSet<LogLevel>,
+    /// ログパネルの文字列による絞り込み
+    pub filter_text: String,
 }
 
 impl Default for LogState {
@@ -67,6 +110,10 @@ impl Default for LogState {
             messages: Vec::new(),
             max_messages: 100,
             log_file_path: None,
+            error_log: None,
+            activity_store: None,
+            hidden_levels: std::collections::HashSet::new(),
+            filter_text: String::new(),
         }
     }
 }
@@ -79,15 +126,25 @@ impl LogState {
 
         let now = chrono::Local::now();
         let timestamp = now.format("%H:%M:%S").to_string();
+        let date = now.format("%Y-%m-%d").to_string();
         
         self.messages.push(LogMessage {
             message: message.clone(),
             timestamp: timestamp.clone(),
+            date: date.clone(),
             level,
         });
         
         // ファイルに出力
         self.write_to_file(&timestamp, level, &message);
+        self.write_to_error_log(level, &message);
+        if let Some(store) = &mut self.activity_store {
+            let _ = store.append(&ActivityLogRecord {
+                timestamp: format!("{date} {timestamp}"),
+                level: level.as_str().to_string(),
+                message,
+            });
+        }
         
         // 最大数を超えた場合は古いものから削除
         if self.messages.len() > self.max_messages {
@@ -113,6 +170,62 @@ impl LogState {
         Ok(())
     }
     
+    /// 操作履歴の保存先を設定し、前回までのセッションの履歴を読み込む
+    pub fn set_activity_store(&mut self, mut store: ActivityLogStore) -> std::io::Result<()> {
+        let records = store.load_recent(self.max_messages)?;
+        let mut restored: Vec<LogMessage> = records
+            .into_iter()
+            .filter_map(|record| {
+                let (date, timestamp) = record.timestamp.split_once(' ')?;
+                Some(LogMessage {
+                    message: record.message,
+                    timestamp: timestamp.to_string(),
+                    date: date.to_string(),
+                    level: LogLevel::from_name(&record.level)?,
+                })
+            })
+            .collect();
+        restored.append(&mut self.messages);
+        let overflow = restored.len().saturating_sub(self.max_messages);
+        restored.drain(..overflow);
+        self.messages = restored;
+        self.activity_store = Some(store);
+        Ok(())
+    }
+
+    /// ログパネルの絞り込み条件に一致するメッセージ
+    pub fn visible_messages(&self) -> impl Iterator<Item = &LogMessage> {
+        let filter = self.filter_text.trim().to_lowercase();
+        self.messages.iter().filter(move |msg| {
+            !self.hidden_levels.contains(&msg.level)
+                && (filter.is_empty() || msg.message.to_lowercase().contains(&filter))
+        })
+    }
+
+    /// 表示中のメッセージをクリップボード用のテキストにする
+    pub fn visible_messages_text(&self) -> String {
+        self.visible_messages()
+            .map(|msg| format!("{} {} [{}] {}", msg.date, msg.timestamp, msg.level.as_str(), msg.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// エラー・警告の追記先を設定
+    pub fn set_error_log(&mut self, error_log: ErrorLogFile) {
+        self.error_log = Some(error_log);
+    }
+
+    /// エラー・警告を日付付きでエラーログに追記
+    fn write_to_error_log(&self, level: LogLevel, message: &str) {
+        if matches!(level, LogLevel::Debug | LogLevel::Information) {
+            return;
+        }
+        if let Some(error_log) = &self.error_log {
+            let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+            let _ = error_log.append(&format!("[{}] [{}] {}", timestamp, level.as_str(), message));
+        }
+    }
+
     /// ログをファイルに書き込み
     fn write_to_file(&self, timestamp: &str, level: LogLevel, message: &str) {
         if let Some(path) = &self.log_file_path {
@@ -142,6 +255,116 @@ pub struct PersonEditorState {
     pub new_photo_path: String,
     pub new_display_mode: PersonDisplayMode,
     pub new_photo_scale: f32,
+    pub new_birth_place: Option<PlaceId>,
+    pub new_death_place: Option<PlaceId>,
+    /// 別の家系図ファイル（または URL）と、その中での人物ID
+    pub new_external_tree: String,
+    pub new_external_person: String,
+    /// 選択中の人物に追加する付箋の内容
+    pub new_sticky_note: String,
+    /// 人物選択の履歴（戻る・進む）
+    pub history: SelectionHistory,
+    /// 人物一覧を最後にスクロールさせた選択（キャンバスでの選択変更を検出する）
+    pub list_synced_selection: Option<PersonId>,
+    /// 人物一覧の表示形式
+    pub list_view: PersonListView,
+    /// 表形式の並べ替え列と昇順かどうか
+    pub table_sort: (PersonTableColumn, bool),
+    /// 人物一覧の絞り込み条件
+    pub filter: PersonFilter,
+    /// 絞り込み条件をキャンバスの表示にも適用するか
+    pub apply_filter_to_canvas: bool,
+    /// 複数選択した人物への一括編集の内容
+    pub bulk_edit: BulkEdit,
+    /// 直前の一括編集の対象人物と、共有の元に戻す履歴での操作
+    pub bulk_edit_undo: Option<(Vec<PersonId>, UndoId)>,
+    /// 削除の確認中の人物
+    pub pending_delete: Option<PersonId>,
+    /// 削除するとき、孤立する仮の親族も一緒に削除するか
+    pub delete_orphans: bool,
+    /// 選択中のイベントのテンプレート（EVENT_TEMPLATES の添字）
+    pub event_template: usize,
+}
+
+/// 人物一覧の表示形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersonListView {
+    #[default]
+    List,
+    Table,
+}
+
+/// 人物一覧（表形式）の列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PersonTableColumn {
+    #[default]
+    Name,
+    Birth,
+    Death,
+    Gender,
+    Children,
+    Family,
+}
+
+/// 保持する選択履歴の最大件数
+const SELECTION_HISTORY_LIMIT: usize = 100;
+
+/// ブラウザのように戻る・進むができる人物選択の履歴
+#[derive(Default)]
+pub struct SelectionHistory {
+    back: Vec<PersonId>,
+    forward: Vec<PersonId>,
+    current: Option<PersonId>,
+}
+
+impl SelectionHistory {
+    /// 現在の選択を記録する（選択解除は履歴に残さない）
+    pub fn observe(&mut self, selected: Option<PersonId>) {
+        let Some(selected) = selected else {
+            return;
+        };
+        if self.current == Some(selected) {
+            return;
+        }
+
+        if let Some(current) = self.current.replace(selected) {
+            self.back.push(current);
+            if self.back.len() > SELECTION_HISTORY_LIMIT {
+                self.back.remove(0);
+            }
+        }
+        self.forward.clear();
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    /// ひとつ前に選択していた人物へ戻る（`exists` が false を返す削除済みの人物は履歴から除いて飛ばす）
+    pub fn go_back(&mut self, exists: impl Fn(PersonId) -> bool) -> Option<PersonId> {
+        let position = self.back.iter().rposition(|id| exists(*id))?;
+        self.back.truncate(position + 1);
+        let previous = self.back.pop()?;
+        if let Some(current) = self.current.replace(previous) {
+            self.forward.push(current);
+        }
+        Some(previous)
+    }
+
+    /// 戻る前に選択していた人物へ進む（削除済みの人物は履歴から除いて飛ばす）
+    pub fn go_forward(&mut self, exists: impl Fn(PersonId) -> bool) -> Option<PersonId> {
+        let position = self.forward.iter().rposition(|id| exists(*id))?;
+        self.forward.truncate(position + 1);
+        let next = self.forward.pop()?;
+        if let Some(current) = self.current.replace(next) {
+            self.back.push(current);
+        }
+        Some(next)
+    }
 }
 
 impl PersonEditorState {
@@ -155,6 +378,10 @@ impl PersonEditorState {
         self.new_photo_path.clear();
         self.new_display_mode = PersonDisplayMode::NameOnly;
         self.new_photo_scale = 1.0;
+        self.new_birth_place = None;
+        self.new_death_place = None;
+        self.new_external_tree.clear();
+        self.new_external_person.clear();
     }
 }
 
@@ -177,6 +404,25 @@ pub struct RelationEditorState {
     // 親子関係の種類編集
     pub editing_parent_kind: Option<(PersonId, PersonId)>,
     pub temp_kind: String,
+    pub temp_adoption_date: String,
+    pub temp_adoption_type: String,
+    pub temp_adoption_note: String,
+
+    // 後見人・名付け親などの関係追加
+    pub person_relation_pick: Option<PersonId>,
+    pub person_relation_kind: PersonRelationKind,
+    pub person_relation_memo: String,
+
+    // 多胎（双子など）の登録
+    pub multiple_birth_pick: Option<PersonId>,
+
+    // キャンバスからの配偶者関係編集
+    pub canvas_spouse_pair: Option<(PersonId, PersonId)>,
+    pub canvas_marriage_date: String,
+    pub canvas_divorced: bool,
+    pub canvas_divorce_date: String,
+    pub canvas_spouse_memo: String,
+    pub canvas_spouse_label: SpouseLabelStyle,
 }
 
 impl RelationEditorState {
@@ -195,15 +441,41 @@ pub struct FamilyEditorState {
     pub new_family_name: String,
     pub new_family_color: [f32; 3],
     pub family_member_pick: Option<PersonId>,
+    // 家族から人物を追加するときの既定値
+    pub new_default_kind: String,
+    pub new_default_surname: String,
+    pub use_label_color: bool,
+    pub new_label_color: [f32; 3],
+    // 家族に新しい人物を追加
+    pub new_member_name: String,
+    pub new_member_parent: Option<PersonId>,
 }
 
 impl FamilyEditorState {
     pub fn new() -> Self {
         Self {
             new_family_color: [0.8, 0.8, 1.0],
+            new_label_color: [0.2, 0.2, 0.2],
             ..Default::default()
         }
     }
+
+    /// 家族を選択して、編集欄にその家族の内容を読み込む
+    pub fn load_family(&mut self, family: &Family) {
+        let to_rgb = |(r, g, b): (u8, u8, u8)| [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0];
+        self.selected_family = Some(family.id);
+        self.new_family_name = family.name.clone();
+        if let Some(color) = family.color {
+            self.new_family_color = to_rgb(color);
+        }
+        self.new_default_kind = family.defaults.relation_kind.clone();
+        self.new_default_surname = family.defaults.surname.clone();
+        self.use_label_color = family.defaults.label_color.is_some();
+        if let Some(color) = family.defaults.label_color {
+            self.new_label_color = to_rgb(color);
+        }
+        self.new_member_parent = None;
+    }
 }
 
 /// イベント管理の状態
@@ -214,6 +486,7 @@ pub struct EventEditorState {
     pub new_event_date: String,
     pub new_event_description: String,
     pub new_event_color: [f32; 3],
+    pub new_event_place: Option<PlaceId>,
     
     // イベントと人物の関係追加
     pub person_pick: Option<PersonId>,
@@ -227,6 +500,26 @@ impl EventEditorState {
         self.new_event_date.clear();
         self.new_event_description.clear();
         self.new_event_color = [1.0, 1.0, 0.8]; // デフォルトの淡い黄色
+        self.new_event_place = None;
+    }
+}
+
+/// 地名辞典（場所の管理）の状態
+#[derive(Default)]
+pub struct PlaceEditorState {
+    pub selected: Option<PlaceId>,
+    pub new_name: String,
+    /// 緯度・経度の入力（空欄なら座標なし）
+    pub new_latitude: String,
+    pub new_longitude: String,
+    pub new_note: String,
+    /// 選択中の場所をまとめる先
+    pub merge_target: Option<PlaceId>,
+}
+
+impl PlaceEditorState {
+    pub fn clear(&mut self) {
+        *self = Self::default();
     }
 }
 
@@ -247,19 +540,103 @@ pub struct CanvasState {
     // イベントノードドラッグ
     pub dragging_event: Option<EventId>,
     pub event_drag_start: Option<egui::Pos2>,
+
+    // 注釈ドラッグ
+    pub dragging_annotation: Option<AnnotationId>,
+    pub annotation_drag_start: Option<egui::Pos2>,
+
+    // 付箋を開いている人物
+    pub expanded_notes: std::collections::HashSet<PersonId>,
     
     // グリッド
     pub show_grid: bool,
     pub grid_size: f32,
+    pub grid: GridSettings,
+
+    // 定規とガイド線（ドラッグ中のガイド線）
+    pub show_rulers: bool,
+    pub dragging_guide: Option<Uuid>,
+    /// ドラッグを始める前のガイド線（離したときにひとつの操作として元に戻せるよう記録する）
+    pub guides_before_drag: Vec<CanvasGuide>,
     
     // キャンバス情報
     pub canvas_rect: egui::Rect,
     pub canvas_origin: egui::Pos2,
 
+    // 全体表示・人物へのジャンプ時のカメラ移動
+    pub camera_animation: Option<CameraAnimation>,
+
+    // 右クリックメニューを開いた位置（ワールド座標）
+    pub context_menu_world_pos: Option<(f32, f32)>,
+
+    // 自動整列のプレビュー（適用するまで家系図は変更しない）
+    pub arrange_preview: Option<ArrangePreview>,
+
+    // 絞り込みバー（一致しない人物を薄く表示する）
+    pub filter_text: String,
+    pub dimmed_persons: std::collections::HashSet<PersonId>,
+
+    // 「画像としてコピー」の進行状況
+    pub image_copy: Option<ImageCopyState>,
+
     // 写真テクスチャキャッシュ
     pub photo_texture_cache: PhotoTextureCache,
 }
 
+/// カメラ移動アニメーションの所要時間（秒）
+pub const CAMERA_ANIMATION_DURATION: f64 = 0.2;
+
+/// キャンバスのズーム倍率の範囲
+pub const MIN_CANVAS_ZOOM: f32 = 0.25;
+pub const MAX_CANVAS_ZOOM: f32 = 3.0;
+
+/// ズームの一覧から選べる倍率（25% / 50% / 100% / 200%）
+pub const CANVAS_ZOOM_PRESETS: [f32; 4] = [0.25, 0.5, 1.0, 2.0];
+
+/// 自動整列のプレビュー
+#[derive(Debug, Clone)]
+pub struct ArrangePreview {
+    /// 動かす人物と整列後の位置
+    pub moves: std::collections::HashMap<PersonId, (f32, f32)>,
+    /// 計算したときの家系図のフィンガープリント（編集されたら計算し直す）
+    pub fingerprint: u64,
+}
+
+/// ズーム・パンを目標値まで滑らかに移動させるアニメーション
+#[derive(Debug, Clone, Copy)]
+pub struct CameraAnimation {
+    pub from_zoom: f32,
+    pub from_pan: egui::Vec2,
+    pub to_zoom: f32,
+    pub to_pan: egui::Vec2,
+    /// 最初のフレームで記録する開始時刻
+    pub started_at: Option<f64>,
+}
+
+impl CameraAnimation {
+    /// 進捗（0.0〜1.0）に対応するズームとパンを返す（ease-out cubic）
+    pub fn sample(&self, progress: f32) -> (f32, egui::Vec2) {
+        let t = progress.clamp(0.0, 1.0);
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let zoom = self.from_zoom + (self.to_zoom - self.from_zoom) * eased;
+        let pan = self.from_pan + (self.to_pan - self.from_pan) * eased;
+        (zoom, pan)
+    }
+}
+
+/// 「画像としてコピー」は選択範囲が収まるようにカメラを動かし、そのフレームの描画を撮って切り抜く
+///
+/// 撮るフレームでは選択の強調表示・ツールチップ・通知を描かず、キャンバスを他のウィンドウより手前に描く。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageCopyState {
+    /// メニューなどから要求された（次のキャンバス描画で撮る）
+    Requested,
+    /// 撮るフレームを描画中（撮った後に戻すズームとパン）
+    Framing { camera: (f32, egui::Vec2) },
+    /// 範囲（スクリーン座標）を決めてスクリーンショットを待っている（要求した時刻から待ち時間を測る）
+    Capturing { region: egui::Rect, camera: (f32, egui::Vec2), requested_at: Instant },
+}
+
 impl Default for CanvasState {
     fn default() -> Self {
         Self {
@@ -272,29 +649,502 @@ impl Default for CanvasState {
             multi_drag_starts: std::collections::HashMap::new(),
             dragging_event: None,
             event_drag_start: None,
+            dragging_annotation: None,
+            annotation_drag_start: None,
+            expanded_notes: std::collections::HashSet::new(),
             show_grid: true,
             grid_size: 50.0,
+            grid: GridSettings::default(),
+            show_rulers: false,
+            dragging_guide: None,
+            guides_before_drag: Vec::new(),
             canvas_rect: egui::Rect::NOTHING,
             canvas_origin: egui::Pos2::ZERO,
+            camera_animation: None,
+            context_menu_world_pos: None,
+            arrange_preview: None,
+            filter_text: String::new(),
+            dimmed_persons: std::collections::HashSet::new(),
+            image_copy: None,
             photo_texture_cache: PhotoTextureCache::default(),
         }
     }
 }
 
+/// 注釈（キャンバス上の文字ラベル）編集の状態
+pub struct AnnotationEditorState {
+    pub selected: Option<AnnotationId>,
+    pub text: String,
+    pub font_size: f32,
+    pub color: [f32; 3],
+}
+
+impl Default for AnnotationEditorState {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            text: String::new(),
+            font_size: DEFAULT_ANNOTATION_FONT_SIZE,
+            color: [60.0 / 255.0, 60.0 / 255.0, 60.0 / 255.0],
+        }
+    }
+}
+
+impl AnnotationEditorState {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
 /// ファイル操作の状態
 #[derive(Default)]
 pub struct FileState {
     pub file_path: String,
-    pub status: String,
+    /// 最後に保存・読み込みした時点の家系図のハッシュ値
+    pub saved_fingerprint: u64,
+    /// 最後に編集を確認したフレームでの家系図のハッシュ値
+    pub current_fingerprint: u64,
+    /// 未保存の変更があるか
+    pub dirty: bool,
+    /// このセッションで最後に保存した時刻
+    pub last_saved_at: Option<Instant>,
+    /// 「変更を保存しますか？」の確認待ちの操作
+    pub pending_action: Option<PendingFileAction>,
+    /// 開く予定のリンク先の家系図と人物
+    pub pending_linked_tree: Option<ExternalPersonLink>,
+    /// 確認済みのためウィンドウを閉じてよいか
+    pub close_confirmed: bool,
+    /// 最後にウィンドウへ設定したタイトル
+    pub window_title: String,
+    /// 読み込んだファイルの内容から判別した形式
+    pub detected_format: Option<StorageFormat>,
+    /// 開いているファイルのロック（他のインスタンスとの同時編集を防ぐ）
+    pub lock: Option<TreeFileLock>,
+    /// 他のインスタンスがファイルをロックしていたときの確認
+    pub lock_conflict: Option<LockConflict>,
+    /// 上書きの確認が済んだ（または自分で保存した）ファイルパス
+    pub overwrite_checked_path: Option<String>,
+    /// 保存先の既存ファイルを上書きしてよいかの確認
+    pub overwrite_confirm: Option<OverwriteConfirm>,
 }
 
 impl FileState {
     pub fn new() -> Self {
         Self {
             file_path: String::new(),
-            status: String::new(),
+            saved_fingerprint: 0,
+            current_fingerprint: 0,
+            dirty: false,
+            last_saved_at: None,
+            pending_action: None,
+            pending_linked_tree: None,
+            close_confirmed: false,
+            window_title: String::new(),
+            detected_format: None,
+            lock: None,
+            lock_conflict: None,
+            overwrite_checked_path: None,
+            overwrite_confirm: None,
         }
     }
+
+    /// ファイル名と未保存マーク（例: "tree.json *"）
+    pub fn display_name(&self, untitled: &str) -> String {
+        let name = std::path::Path::new(&self.file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| untitled.to_string());
+        if self.dirty {
+            format!("{} *", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// 他のインスタンスがロックしているファイルを開いた・保存しようとしたときの状態
+#[derive(Debug, Clone)]
+pub struct LockConflict {
+    pub owner: LockOwner,
+    /// ロックを引き継いだあとに保存をやり直すか
+    pub retry_save: bool,
+}
+
+/// 形式の違うファイルや別の家系図のファイルに保存しようとしたときの状態
+#[derive(Debug, Clone)]
+pub struct OverwriteConfirm {
+    pub warning: OverwriteWarning,
+    /// 上書きをやめたときに戻すファイルパス
+    pub previous_path: String,
+    /// 保存してから続ける操作（「変更を保存しますか？」で保存を選んだ終了・開くなど）
+    pub pending_action: Option<PendingFileAction>,
+}
+
+/// 未保存の変更を破棄する可能性のある操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingFileAction {
+    New,
+    Open,
+    /// 人物のリンク先の家系図を開く（開くファイルは FileState::pending_linked_tree）
+    OpenLinked,
+    /// サンプルデータ生成ダイアログの設定で家系図を置き換える
+    LoadSample,
+    Exit,
+}
+
+/// 通知の表示時間（秒）。エラーは閉じるまで表示し続ける
+const TOAST_INFO_SECONDS: f64 = 3.0;
+const TOAST_WARNING_SECONDS: f64 = 6.0;
+/// 同時に積み重ねて表示する通知の最大数
+const MAX_TOASTS: usize = 5;
+
+/// 通知の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            ToastKind::Info => egui::Color32::from_rgb(100, 150, 255),
+            ToastKind::Warning => LogLevel::Warning.color(),
+            ToastKind::Error => LogLevel::Error.color(),
+        }
+    }
+
+    fn lifetime(&self) -> Option<f64> {
+        match self {
+            ToastKind::Info => Some(TOAST_INFO_SECONDS),
+            ToastKind::Warning => Some(TOAST_WARNING_SECONDS),
+            ToastKind::Error => None,
+        }
+    }
+}
+
+/// キャンバス上に積み重ねて表示する通知
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub message: String,
+    pub kind: ToastKind,
+    /// 最初に描画した時刻（表示時間の起点）
+    pub shown_at: Option<f64>,
+}
+
+/// 通知（トースト）の状態
+#[derive(Default)]
+pub struct ToastState {
+    pub toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+impl ToastState {
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(message.into(), ToastKind::Info);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(message.into(), ToastKind::Warning);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(message.into(), ToastKind::Error);
+    }
+
+    fn push(&mut self, message: String, kind: ToastKind) {
+        // 同じ内容が表示中なら表示時間だけやり直す
+        if let Some(existing) = self.toasts.iter_mut().find(|toast| toast.message == message && toast.kind == kind) {
+            existing.shown_at = None;
+            return;
+        }
+
+        self.toasts.push(Toast {
+            id: self.next_id,
+            message,
+            kind,
+            shown_at: None,
+        });
+        self.next_id += 1;
+
+        // 上限を超えたら古い通知から消す（エラーは残す）
+        while self.toasts.len() > MAX_TOASTS {
+            match self.toasts.iter().position(|toast| toast.kind != ToastKind::Error) {
+                Some(index) => self.toasts.remove(index),
+                None => self.toasts.remove(0),
+            };
+        }
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|toast| toast.id != id);
+    }
+
+    /// 表示時間を過ぎた通知を取り除く
+    pub fn expire(&mut self, now: f64) {
+        for toast in &mut self.toasts {
+            toast.shown_at.get_or_insert(now);
+        }
+        self.toasts.retain(|toast| match (toast.kind.lifetime(), toast.shown_at) {
+            (Some(lifetime), Some(shown_at)) => now - shown_at < lifetime,
+            _ => true,
+        });
+    }
+}
+
+/// 検索・置換ダイアログの状態
+#[derive(Default)]
+pub struct FindReplaceState {
+    pub show: bool,
+    pub query: FindReplaceQuery,
+    /// 直前の置換（共通の履歴に記録した操作）
+    pub last_change: Option<UndoId>,
+}
+
+/// 関係の整理ダイアログの状態
+#[derive(Default)]
+pub struct RelationCleanupState {
+    pub show: bool,
+    /// 見つかった重複・矛盾
+    pub issues: Vec<RelationIssue>,
+    /// 修正を適用するかどうか（issues と同じ順）
+    pub checked: Vec<bool>,
+    /// 直前の修正（共通の履歴に記録した操作）
+    pub last_change: Option<UndoId>,
+}
+
+/// 写真の確認ダイアログの状態
+#[derive(Default)]
+pub struct PhotoCheckState {
+    pub show: bool,
+    /// 見つからない・読めない写真
+    pub broken: Vec<BrokenPhoto>,
+    /// 付け直し・参照の解除の対象にするかどうか（broken と同じ順）
+    pub checked: Vec<bool>,
+    /// 直前の修正（共通の履歴に記録した操作）
+    pub last_change: Option<UndoId>,
+}
+
+/// 日付の一括正規化ダイアログの状態
+#[derive(Default)]
+pub struct DateNormalizationState {
+    pub show: bool,
+    /// 解析できない日付だけを表示する
+    pub show_unparseable_only: bool,
+    /// 直前の正規化（共通の履歴に記録した操作）
+    pub last_change: Option<UndoId>,
+}
+
+/// 操作の記録（マクロ）ダイアログの状態
+#[derive(Default)]
+pub struct MacroRecorderState {
+    pub show: bool,
+    /// 記録中の操作（記録していなければ None）
+    pub recording: Option<Vec<MacroStep>>,
+    /// 保存するマクロの名前
+    pub new_name: String,
+    /// 直前の再生（共通の履歴に記録した操作）
+    pub last_change: Option<UndoId>,
+}
+
+/// サンプルデータ生成ダイアログの状態
+#[derive(Default)]
+pub struct SampleDataState {
+    pub show: bool,
+    pub options: SampleOptions,
+}
+
+/// 写真の切り抜き・回転ダイアログの状態
+#[derive(Default)]
+pub struct PhotoEditDialogState {
+    /// 編集中の元画像のパス（None なら閉じている）
+    pub source_path: Option<String>,
+    pub edit: PhotoEdit,
+    /// プレビュー用に縮小した元画像（回転前）
+    pub preview_image: Option<image::DynamicImage>,
+    /// 現在の回転を反映したプレビューのテクスチャと、その回転数
+    pub preview_texture: Option<(egui::TextureHandle, u8)>,
+    /// 切り抜き範囲のドラッグ開始位置（プレビューに対する比率）
+    pub drag_start: Option<egui::Pos2>,
+}
+
+impl PhotoEditDialogState {
+    pub fn open(&mut self, source_path: String) {
+        *self = Self {
+            source_path: Some(source_path),
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 出生地・死亡地の地図ウィンドウの状態
+pub struct PlaceMapState {
+    pub show: bool,
+    /// 出生地に加えて死亡地も表示する
+    pub show_deaths: bool,
+    /// 親の出生地から子の出生地への移動を矢印で表示する
+    pub show_migrations: bool,
+    /// 表示の中心（正距円筒図法の平面座標）
+    pub center: (f32, f32),
+    /// 1度あたりの画面上のピクセル数
+    pub scale: f32,
+    /// 同じ地点の印をクリックしたときに一覧から選ぶ人物
+    pub picked: Vec<PersonId>,
+    /// 次の描画で印が収まるように表示範囲を合わせる
+    pub fit_pending: bool,
+}
+
+impl Default for PlaceMapState {
+    fn default() -> Self {
+        Self {
+            show: false,
+            show_deaths: true,
+            show_migrations: true,
+            center: (180.0, 90.0),
+            scale: 2.0,
+            picked: Vec::new(),
+            fit_pending: false,
+        }
+    }
+}
+
+/// スライドショーの既定の切り替え間隔（秒）
+pub const DEFAULT_SLIDESHOW_INTERVAL_SECONDS: f32 = 4.0;
+
+/// 写真スライドショーの状態
+pub struct SlideshowState {
+    /// 表示中の対象（None なら閉じている）
+    pub source: Option<SlideshowSource>,
+    pub index: usize,
+    pub playing: bool,
+    pub interval_seconds: f32,
+    /// 最後にスライドを切り替えた時刻
+    pub last_advanced_at: Option<f64>,
+}
+
+impl SlideshowState {
+    pub fn open(&mut self, source: SlideshowSource) {
+        self.source = Some(source);
+        self.index = 0;
+        self.playing = true;
+        self.last_advanced_at = None;
+    }
+}
+
+impl Default for SlideshowState {
+    fn default() -> Self {
+        Self {
+            source: None,
+            index: 0,
+            playing: false,
+            interval_seconds: DEFAULT_SLIDESHOW_INTERVAL_SECONDS,
+            last_advanced_at: None,
+        }
+    }
+}
+
+/// 何回変更するごとに復旧用スナップショットを書き込むか
+pub const RECOVERY_SNAPSHOT_INTERVAL: u32 = 5;
+
+/// 異常終了からの復旧用スナップショットの状態
+#[derive(Default)]
+pub struct RecoveryState {
+    pub store: Option<RecoverySnapshotStore>,
+    /// 直前のフレームで観測した家系図のハッシュ値
+    pub last_fingerprint: u64,
+    /// 前回のスナップショット以降の変更回数
+    pub mutations_since_snapshot: u32,
+    /// 起動時に見つかった、復元するか確認中のスナップショットとその保存先
+    pub pending_restore: Option<(RecoverySnapshotStore, RecoverySnapshot)>,
+}
+
+impl RecoveryState {
+    /// 家系図のハッシュ値を観測し、スナップショットを書き込むべきかを返す
+    pub fn observe(&mut self, fingerprint: u64) -> bool {
+        if fingerprint == self.last_fingerprint {
+            return false;
+        }
+        self.last_fingerprint = fingerprint;
+        self.mutations_since_snapshot += 1;
+        if self.mutations_since_snapshot >= RECOVERY_SNAPSHOT_INTERVAL {
+            self.mutations_since_snapshot = 0;
+            return true;
+        }
+        false
+    }
+}
+
+/// このセッション（起動してから）の編集の集計
+pub struct SessionState {
+    pub started_at: Instant,
+    /// 今開いている家系図の、開いた時点の内容
+    pub baseline: FamilyTree,
+    /// 前に開いていた家系図での編集の件数
+    pub carried: EditCounts,
+    /// 集計した時点の家系図のハッシュ値と件数（毎フレーム数え直さないため）
+    cached_counts: Option<(u64, EditCounts)>,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            baseline: FamilyTree::default(),
+            carried: EditCounts::default(),
+            cached_counts: None,
+        }
+    }
+}
+
+impl SessionState {
+    /// セッション全体の編集の件数
+    pub fn counts(&self, current: &FamilyTree) -> EditCounts {
+        self.carried + EditCounts::between(&self.baseline, current)
+    }
+
+    /// 家系図のハッシュ値が前回と同じなら、前回数えた件数を返す
+    pub fn cached_counts(&mut self, fingerprint: u64, current: &FamilyTree) -> EditCounts {
+        match self.cached_counts {
+            Some((cached, counts)) if cached == fingerprint => counts,
+            _ => {
+                let counts = self.counts(current);
+                self.cached_counts = Some((fingerprint, counts));
+                counts
+            }
+        }
+    }
+
+    /// 別の家系図に切り替える前に、今の家系図での編集を集計に加えて起点を移す
+    pub fn switch_tree(&mut self, current: &FamilyTree, next: &FamilyTree) {
+        self.carried = self.counts(current);
+        self.baseline = next.clone();
+        self.cached_counts = None;
+    }
+}
+
+/// 書き出しダイアログの状態
+#[derive(Default)]
+pub struct ExportState {
+    pub show_poster_dialog: bool,
+    pub poster_options: PosterOptions,
+    pub show_image_dialog: bool,
+    pub image_options: ImageExportOptions,
+    pub include_title_block: bool,
+    /// 空欄の場合はファイル名を表題に使う
+    pub title: String,
+    pub author: String,
+    /// 空欄の場合は書き出した日付を使う
+    pub date: String,
+    /// 空欄の場合は透かしを描かない
+    pub watermark: String,
+    /// ダイアログに寸法を出すために作った図と、その元になった内容のハッシュ値（写真を毎フレーム読み直さないため）
+    pub scene_cache: Option<(u64, ChartScene)>,
 }
 
 /// UI全般の状態
@@ -303,21 +1153,195 @@ pub enum SideTab {
     Persons,
     Families,
     Events,
+    Places,
+    Annotations,
+    Statistics,
     Settings,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl SideTab {
+    /// タブの並び順
+    pub const ALL: [SideTab; 7] = [
+        SideTab::Persons,
+        SideTab::Families,
+        SideTab::Events,
+        SideTab::Places,
+        SideTab::Annotations,
+        SideTab::Statistics,
+        SideTab::Settings,
+    ];
+
+    /// offset だけ隣のタブ（端では反対側に回り込む）
+    pub fn cycled(self, offset: isize) -> SideTab {
+        let index = Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0) as isize;
+        Self::ALL[(index + offset).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// 統計タブのグラフの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatisticsChart {
+    Lifespan,
+    BirthsPerDecade,
+    ChildrenPerCouple,
+}
+
+/// 「その他」の性別のノード色（既定値）
+pub const DEFAULT_OTHER_GENDER_COLOR: [u8; 3] = [220, 200, 240];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NodeColorThemePreset {
     Default,
     HighContrast,
 }
 
+/// 画面の文字に使うフォント
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UiFontFamily {
+    /// 同梱の Noto Sans JP
+    #[default]
+    Bundled,
+    /// OS に入っている日本語フォント（見つからなければ同梱フォント）
+    System,
+}
+
+/// 画面の文字サイズ（本文）の既定値
+pub const DEFAULT_UI_FONT_SIZE: f32 = 13.0;
+/// 画面の文字サイズの下限
+pub const MIN_UI_FONT_SIZE: f32 = 9.0;
+/// 画面の文字サイズの上限
+pub const MAX_UI_FONT_SIZE: f32 = 24.0;
+
+/// 予備のバックアップとして残すコピーの数の既定値
+pub const DEFAULT_BACKUP_KEEP: usize = 10;
+/// 予備のバックアップとして残すコピーの数の上限
+pub const MAX_BACKUP_KEEP: usize = 100;
+
+/// 親子の線の描き方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EdgeStyle {
+    #[default]
+    Straight,
+    Bezier,
+}
+
+/// ベジェ曲線の制御点のずらし幅（既定値）
+pub const DEFAULT_BEZIER_CONTROL_OFFSET: f32 = 40.0;
+
+/// 名前の書式で連番に置き換える部分
+pub const NAME_TEMPLATE_NUMBER: &str = "{n}";
+
+/// 新しい人物を追加するときの初期値
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewPersonDefaults {
+    pub gender: Gender,
+    pub display_mode: PersonDisplayMode,
+    /// 親子関係を追加するときの種類
+    pub relation_kind: String,
+    /// 名前の書式（"{n}" は連番になる。空なら画面の言語の既定の名前を使う）
+    pub name_template: String,
+}
+
+impl Default for NewPersonDefaults {
+    fn default() -> Self {
+        Self {
+            gender: Gender::Unknown,
+            display_mode: PersonDisplayMode::NameOnly,
+            relation_kind: "biological".to_string(),
+            name_template: String::new(),
+        }
+    }
+}
+
+impl NewPersonDefaults {
+    /// 次に追加する人物の名前（"{n}" は同じ書式の名前に付いた最大の番号 + 1 になる）
+    pub fn next_name(&self, tree: &FamilyTree, fallback_template: &str) -> String {
+        let template = match self.name_template.trim() {
+            "" => fallback_template,
+            template => template,
+        };
+        let Some((prefix, suffix)) = template.split_once(NAME_TEMPLATE_NUMBER) else {
+            return template.to_string();
+        };
+        let last = tree
+            .persons
+            .values()
+            .filter_map(|person| person.name.strip_prefix(prefix)?.strip_suffix(suffix)?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("{prefix}{}{suffix}", last + 1)
+    }
+}
+
 pub struct UiState {
     pub side_tab: SideTab,
     pub language: Language,
     pub node_color_theme: NodeColorThemePreset,
+    pub layout_direction: LayoutDirection,
+    pub edge_style: EdgeStyle,
+    pub bezier_control_offset: f32,
+    pub semantic_zoom: SemanticZoomThresholds,
+    pub show_step_relations: bool,
+    pub other_gender_color: [u8; 3],
+    pub presume_deceased: bool,
+    pub presumed_deceased_years: u32,
+    /// すべての人物の付箋を常に開いて表示する
+    pub show_all_notes: bool,
+    /// ノードの名前の下に生没年を表示する
+    pub show_life_years: bool,
     pub show_about_dialog: bool,
+    pub show_shortcuts_dialog: bool,
+    /// 保存・読み込み失敗など、確認が必要なエラーのダイアログ
+    pub error_dialog: Option<ErrorDialog>,
     pub show_license_dialog: bool,
+    /// 閲覧モード（家系図を変更する操作をすべて無効にする）
+    pub read_only: bool,
+    /// 右側に選択中の項目の詳細パネルを表示する
+    pub show_detail_panel: bool,
+    /// 画面全体を高コントラストの配色にする
+    pub high_contrast_ui: bool,
+    /// 最後に画面に反映した高コントラスト設定（未反映なら None）
+    pub applied_high_contrast_ui: Option<bool>,
+    /// 画面の文字に使うフォント
+    pub ui_font_family: UiFontFamily,
+    /// 画面の文字サイズ（本文）
+    pub ui_font_size: f32,
+    /// 最後に画面に反映したフォント設定（未反映なら None）
+    pub applied_ui_font: Option<(UiFontFamily, f32)>,
+    /// 読み込んだ OS のフォントのパス（見つからなかった場合は None）
+    pub system_font_path: Option<String>,
+    /// 保存のたびにコピーを書き込む予備のバックアップ先（空なら書き込まない）
+    pub backup_dir: String,
+    /// 予備のバックアップ先に残すコピーの数
+    pub backup_keep: usize,
+    /// 詳細パネルで表示中の配偶者関係
+    pub detail_relation: Option<(PersonId, PersonId)>,
+    /// 配置を保存するときの名前の入力欄
+    pub layout_snapshot_name: String,
+    /// 削除の確認中の保存した配置
+    pub layout_snapshot_pending_delete: Option<Uuid>,
+    /// 表示位置を保存するときの名前の入力欄
+    pub camera_bookmark_name: String,
+    /// 統計タブでクリックした棒（グラフとその区間）
+    pub stats_selection: Option<(StatisticsChart, i32)>,
+    /// 新しい人物・関係を追加するときの初期値
+    pub new_person_defaults: NewPersonDefaults,
+}
+
+impl UiState {
+    /// 死亡推定が有効な場合、その基準年数を返す
+    pub fn presumed_deceased_threshold(&self) -> Option<u32> {
+        self.presume_deceased.then_some(self.presumed_deceased_years)
+    }
+
+    /// 絞り込みや書き出しで使う、今年を基準にした死亡推定の設定
+    pub fn deceased_presumption(&self) -> DeceasedPresumption {
+        DeceasedPresumption {
+            threshold_years: self.presumed_deceased_threshold(),
+            current_year: chrono::Local::now().year(),
+        }
+    }
 }
 
 impl Default for UiState {
@@ -326,8 +1350,134 @@ impl Default for UiState {
             side_tab: SideTab::Persons,
             language: Language::Japanese,
             node_color_theme: NodeColorThemePreset::Default,
+            layout_direction: LayoutDirection::LeftToRight,
+            edge_style: EdgeStyle::Straight,
+            bezier_control_offset: DEFAULT_BEZIER_CONTROL_OFFSET,
+            semantic_zoom: SemanticZoomThresholds::default(),
+            show_step_relations: true,
+            other_gender_color: DEFAULT_OTHER_GENDER_COLOR,
+            presume_deceased: true,
+            presumed_deceased_years: DEFAULT_PRESUMED_DECEASED_YEARS,
+            show_all_notes: false,
+            show_life_years: true,
             show_about_dialog: false,
+            show_shortcuts_dialog: false,
+            error_dialog: None,
             show_license_dialog: false,
+            read_only: false,
+            show_detail_panel: false,
+            high_contrast_ui: false,
+            applied_high_contrast_ui: None,
+            ui_font_family: UiFontFamily::default(),
+            ui_font_size: DEFAULT_UI_FONT_SIZE,
+            applied_ui_font: None,
+            system_font_path: None,
+            backup_dir: String::new(),
+            backup_keep: DEFAULT_BACKUP_KEEP,
+            detail_relation: None,
+            layout_snapshot_name: String::new(),
+            layout_snapshot_pending_delete: None,
+            camera_bookmark_name: String::new(),
+            stats_selection: None,
+            new_person_defaults: NewPersonDefaults::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(count: usize) -> Vec<PersonId> {
+        (0..count).map(|_| Uuid::new_v4()).collect()
+    }
+
+    #[test]
+    fn test_selection_history_back_and_forward() {
+        let [a, b, c] = ids(3)[..] else { unreachable!() };
+        let mut history = SelectionHistory::default();
+        for id in [a, b, c] {
+            history.observe(Some(id));
         }
+        // 選択解除と同じ人物の再選択は記録しない
+        history.observe(None);
+        history.observe(Some(c));
+
+        assert_eq!(history.go_back(|_| true), Some(b));
+        assert_eq!(history.go_back(|_| true), Some(a));
+        assert_eq!(history.go_back(|_| true), None);
+        assert_eq!(history.go_forward(|_| true), Some(b));
+        assert_eq!(history.go_forward(|_| true), Some(c));
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn test_selection_history_new_selection_after_back_drops_forward() {
+        let [a, b, c, d] = ids(4)[..] else { unreachable!() };
+        let mut history = SelectionHistory::default();
+        for id in [a, b, c] {
+            history.observe(Some(id));
+        }
+        history.go_back(|_| true);
+        history.go_back(|_| true);
+        assert!(history.can_go_forward());
+
+        history.observe(Some(d));
+        assert!(!history.can_go_forward());
+        assert_eq!(history.go_forward(|_| true), None);
+        assert_eq!(history.go_back(|_| true), Some(a));
+    }
+
+    #[test]
+    fn test_selection_history_is_capped() {
+        let persons = ids(SELECTION_HISTORY_LIMIT + 10);
+        let mut history = SelectionHistory::default();
+        for id in &persons {
+            history.observe(Some(*id));
+        }
+
+        let mut visited = Vec::new();
+        while let Some(id) = history.go_back(|_| true) {
+            visited.push(id);
+        }
+        assert_eq!(visited.len(), SELECTION_HISTORY_LIMIT);
+        // 古いものから捨てられる
+        assert_eq!(visited.last(), Some(&persons[9]));
+    }
+
+    #[test]
+    fn test_selection_history_skips_deleted_persons() {
+        let [a, b, c, d] = ids(4)[..] else { unreachable!() };
+        let mut history = SelectionHistory::default();
+        for id in [a, b, c, d] {
+            history.observe(Some(id));
+        }
+        let exists = |id: PersonId| id != b && id != c;
+
+        assert_eq!(history.go_back(exists), Some(a));
+        assert_eq!(history.go_forward(exists), Some(d));
+        assert_eq!(history.go_forward(exists), None);
+
+        // 残っている人物がいなければ履歴の位置は動かない
+        assert_eq!(history.go_back(|id| id == d), None);
+        assert_eq!(history.go_back(exists), Some(a));
+    }
+
+    #[test]
+    fn test_new_person_defaults_next_name() {
+        let mut tree = FamilyTree::default();
+        let mut defaults = NewPersonDefaults::default();
+        assert_eq!(defaults.next_name(&tree, "New Person {n}"), "New Person 1");
+
+        for name in ["New Person 1", "New Person 4", "New Person x", "Taro"] {
+            tree.add_person(name.to_string(), Gender::Unknown, None, String::new(), false, None, (0.0, 0.0));
+        }
+        assert_eq!(defaults.next_name(&tree, "New Person {n}"), "New Person 5");
+
+        defaults.name_template = "佐藤 ({n})".to_string();
+        assert_eq!(defaults.next_name(&tree, "New Person {n}"), "佐藤 (1)");
+        // 連番のない書式はそのまま使う
+        defaults.name_template = "Unnamed".to_string();
+        assert_eq!(defaults.next_name(&tree, "New Person {n}"), "Unnamed");
     }
 }
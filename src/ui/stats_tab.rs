@@ -0,0 +1,174 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Plot};
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::core::statistics::{
+    births_per_decade, children_per_couple, lifespan_histogram, EditCounts, StatisticsBucket, STATISTICS_BUCKET_YEARS,
+};
+use crate::ui::StatisticsChart;
+
+/// グラフ1つ分の高さ
+const CHART_HEIGHT: f32 = 140.0;
+/// 棒のツールチップに並べる人物名の上限
+const TOOLTIP_MAX_NAMES: usize = 8;
+
+pub trait StatsTabRenderer {
+    fn render_stats_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String);
+}
+
+impl StatsTabRenderer for App {
+    fn render_stats_tab(&mut self, ui: &mut egui::Ui, t: impl Fn(&str) -> String) {
+        ui.heading(t("statistics"));
+        self.render_session_summary(ui, &t);
+        ui.separator();
+        ui.weak(t("statistics_hint"));
+
+        for chart in [
+            StatisticsChart::Lifespan,
+            StatisticsChart::BirthsPerDecade,
+            StatisticsChart::ChildrenPerCouple,
+        ] {
+            ui.separator();
+            self.render_statistics_chart(ui, &t, chart);
+        }
+    }
+}
+
+/// 編集の件数の項目（表示名のキーと件数）
+fn session_rows(counts: &EditCounts) -> [(&'static str, usize); 7] {
+    [
+        ("session_persons_added", counts.persons_added),
+        ("session_persons_edited", counts.persons_edited),
+        ("session_persons_removed", counts.persons_removed),
+        ("session_relations_added", counts.relations_added),
+        ("session_relations_removed", counts.relations_removed),
+        ("session_events_added", counts.events_added),
+        ("session_events_removed", counts.events_removed),
+    ]
+}
+
+impl App {
+    /// このセッションの経過時間と編集の件数
+    fn render_session_summary(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String) {
+        let counts = self.session.cached_counts(self.file.current_fingerprint, &self.tree);
+        let minutes = self.session.started_at.elapsed().as_secs() / 60;
+        ui.strong(format!("{} ({}{})", t("session_edits"), minutes, t("session_minutes")));
+        egui::Grid::new("session_edit_counts").num_columns(2).show(ui, |ui| {
+            for (key, count) in session_rows(&counts) {
+                ui.label(t(key));
+                ui.label(count.to_string());
+                ui.end_row();
+            }
+        });
+        if ui.small_button(t("session_copy_summary")).clicked() {
+            ui.ctx().copy_text(self.session_summary(&counts));
+        }
+    }
+
+    /// 編集の件数を1行にまとめる（件数が0の項目は省く）
+    pub(crate) fn session_summary(&self, counts: &EditCounts) -> String {
+        let t = |key: &str| Texts::get(key, self.ui.language);
+        let parts: Vec<String> = session_rows(counts)
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(key, count)| format!("{} {}", t(key), count))
+            .collect();
+        if parts.is_empty() {
+            t("session_no_edits")
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    fn render_statistics_chart(&mut self, ui: &mut egui::Ui, t: &impl Fn(&str) -> String, chart: StatisticsChart) {
+        let (title, buckets, bar_width) = match chart {
+            StatisticsChart::Lifespan => (t("chart_lifespan"), lifespan_histogram(&self.tree), STATISTICS_BUCKET_YEARS),
+            StatisticsChart::BirthsPerDecade => {
+                (t("chart_births_per_decade"), births_per_decade(&self.tree), STATISTICS_BUCKET_YEARS)
+            }
+            StatisticsChart::ChildrenPerCouple => (t("chart_children_per_couple"), children_per_couple(&self.tree), 1),
+        };
+        // 夫婦の分布は夫婦の数で数える（人物には夫婦の両方が入っている）
+        let count_of = |bucket: &StatisticsBucket| match chart {
+            StatisticsChart::ChildrenPerCouple => bucket.count() / 2,
+            _ => bucket.count(),
+        };
+        let bucket_label = |key: i32| match chart {
+            StatisticsChart::Lifespan => format!("{key}–{}", key + STATISTICS_BUCKET_YEARS - 1),
+            StatisticsChart::BirthsPerDecade => format!("{key}{}", t("chart_decade_suffix")),
+            StatisticsChart::ChildrenPerCouple => key.to_string(),
+        };
+
+        ui.strong(&title);
+        if buckets.is_empty() {
+            ui.weak(t("chart_no_data"));
+            return;
+        }
+
+        let bars: Vec<Bar> = buckets
+            .iter()
+            .map(|bucket| {
+                let mut names: Vec<String> = bucket
+                    .persons
+                    .iter()
+                    .take(TOOLTIP_MAX_NAMES)
+                    .map(|id| self.get_person_name(id))
+                    .collect();
+                if bucket.persons.len() > TOOLTIP_MAX_NAMES {
+                    names.push(format!("… +{}", bucket.persons.len() - TOOLTIP_MAX_NAMES));
+                }
+                Bar::new(f64::from(bucket.key) + f64::from(bar_width) / 2.0, count_of(bucket) as f64)
+                    .width(f64::from(bar_width) * 0.9)
+                    .name(format!("{}: {}\n{}", bucket_label(bucket.key), count_of(bucket), names.join("\n")))
+            })
+            .collect();
+        let bar_chart = BarChart::new(title.clone(), bars).element_formatter(Box::new(|bar, _| bar.name.clone()));
+
+        let response = Plot::new(("statistics_chart", chart))
+            .height(CHART_HEIGHT)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .allow_drag(false)
+            .allow_boxed_zoom(false)
+            .include_y(0.0)
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(bar_chart);
+                plot_ui.pointer_coordinate()
+            });
+
+        // 棒をクリックすると、その区間の人物を下に一覧表示する
+        if response.response.clicked()
+            && let Some(pointer) = response.inner
+            && let Some(bucket) = buckets.iter().find(|bucket| {
+                let start = f64::from(bucket.key);
+                (start..start + f64::from(bar_width)).contains(&pointer.x)
+            })
+        {
+            self.ui.stats_selection = Some((chart, bucket.key));
+        }
+
+        let Some(bucket) = self
+            .ui
+            .stats_selection
+            .filter(|(selected_chart, _)| *selected_chart == chart)
+            .and_then(|(_, key)| buckets.iter().find(|bucket| bucket.key == key))
+        else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(format!("{} ({})", bucket_label(bucket.key), count_of(bucket)));
+            if ui.small_button("✖").clicked() {
+                self.ui.stats_selection = None;
+            }
+        });
+        ui.horizontal_wrapped(|ui| {
+            for person_id in &bucket.persons {
+                if ui.link(self.get_person_name(person_id)).clicked() {
+                    self.select_single_person(*person_id);
+                    self.center_on_person(*person_id);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::app::App;
+use crate::core::i18n::Texts;
+use crate::ui::{LogLevel, SideTab};
+
+/// 最終保存からの経過時間を更新する間隔
+const ELAPSED_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// ステータスバー（下部）の描画トレイト
+pub trait StatusBarRenderer {
+    fn render_status_bar(&mut self, ctx: &egui::Context);
+}
+
+impl StatusBarRenderer for App {
+    fn render_status_bar(&mut self, ctx: &egui::Context) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                // 家系図の件数
+                let relations = self.tree.edges.len() + self.tree.spouses.len();
+                ui.label(format!("{}: {}", t("status_persons"), self.tree.persons.len()));
+                ui.separator();
+                ui.label(format!("{}: {}", t("status_relations"), relations));
+                ui.separator();
+                ui.label(format!("{}: {}", t("status_events"), self.tree.events.len()));
+                ui.separator();
+                ui.label(format!("{}: {:.0}%", t("status_zoom"), self.canvas.zoom * 100.0));
+                if let Some(selected) = self.selected_entity_name() {
+                    ui.separator();
+                    ui.label(format!("{}: {}", t("status_selected"), selected));
+                }
+
+                // 現在のファイル・保存状態
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let mut name = self.file.display_name(&t("untitled"));
+                    if let Some(format) = self.file.detected_format {
+                        name = format!("{} [{}]", name, format.label());
+                    }
+                    let label = ui.label(format!("{}: {}", t("current_file"), name));
+                    if !self.file.file_path.is_empty() {
+                        label.on_hover_text(&self.file.file_path);
+                    }
+                    ui.separator();
+                    ui.weak(self.last_saved_label(&t));
+                    ui.separator();
+                    if self.file.dirty {
+                        ui.label(egui::RichText::new(t("status_unsaved")).color(LogLevel::Warning.color()));
+                    } else {
+                        ui.weak(t("status_no_changes"));
+                    }
+                    if self.ui.read_only {
+                        ui.separator();
+                        ui.label(egui::RichText::new(t("read_only_mode")).color(LogLevel::Warning.color()));
+                    }
+                });
+            });
+        });
+
+        // 入力がなくても経過時間の表示が古くならないようにする
+        if self.file.last_saved_at.is_some() {
+            ctx.request_repaint_after(ELAPSED_REFRESH_INTERVAL);
+        }
+    }
+}
+
+impl App {
+    /// 開いているタブで選択中の項目の名前（タブに選択がなければ選択中の人物）
+    fn selected_entity_name(&self) -> Option<String> {
+        let tab_selection = match self.ui.side_tab {
+            SideTab::Families => self.family_editor.selected_family.map(|id| self.family_name_or_default(id)),
+            SideTab::Events => self
+                .event_editor
+                .selected
+                .and_then(|id| self.tree.events.get(&id))
+                .map(|event| event.name.clone()),
+            SideTab::Places => self
+                .place_editor
+                .selected
+                .and_then(|id| self.tree.get_place(id))
+                .map(|place| place.name.clone()),
+            _ => None,
+        };
+        tab_selection.or_else(|| self.person_editor.selected.map(|id| self.get_person_name(&id)))
+    }
+
+    /// 最後に保存してからの経過時間（例: "Saved 5 min ago"）
+    fn last_saved_label(&self, t: &impl Fn(&str) -> String) -> String {
+        let Some(saved_at) = self.file.last_saved_at else {
+            return t("status_not_saved_yet");
+        };
+        let minutes = saved_at.elapsed().as_secs() / 60;
+        let elapsed = match minutes {
+            0 => t("status_just_now"),
+            1..60 => format!("{}{}", minutes, t("status_minutes_ago")),
+            _ => format!("{}{}", minutes / 60, t("status_hours_ago")),
+        };
+        format!("{} {}", t("status_last_saved"), elapsed)
+    }
+}
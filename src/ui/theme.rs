@@ -0,0 +1,74 @@
+use eframe::egui;
+
+/// 弱視のユーザー向けの高コントラストな配色（黒背景・白文字・黄色の強調）
+pub fn high_contrast_visuals() -> egui::Visuals {
+    let mut visuals = egui::Visuals::dark();
+    let black = egui::Color32::BLACK;
+    let white = egui::Color32::WHITE;
+    let yellow = egui::Color32::from_rgb(255, 230, 0);
+
+    visuals.override_text_color = Some(white);
+    visuals.panel_fill = black;
+    visuals.window_fill = black;
+    visuals.extreme_bg_color = black;
+    visuals.faint_bg_color = egui::Color32::from_gray(24);
+    visuals.window_stroke = egui::Stroke::new(1.5, white);
+    visuals.hyperlink_color = yellow;
+    visuals.warn_fg_color = yellow;
+    visuals.selection.bg_fill = egui::Color32::from_rgb(0, 40, 140);
+    visuals.selection.stroke = egui::Stroke::new(2.0, yellow);
+
+    let widgets = &mut visuals.widgets;
+    for state in [&mut widgets.noninteractive, &mut widgets.inactive] {
+        state.bg_fill = black;
+        state.weak_bg_fill = black;
+        state.bg_stroke = egui::Stroke::new(1.5, white);
+        state.fg_stroke = egui::Stroke::new(1.5, white);
+    }
+    for state in [&mut widgets.hovered, &mut widgets.active, &mut widgets.open] {
+        state.bg_fill = egui::Color32::from_gray(32);
+        state.weak_bg_fill = egui::Color32::from_gray(32);
+        state.bg_stroke = egui::Stroke::new(2.0, yellow);
+        state.fg_stroke = egui::Stroke::new(2.0, yellow);
+    }
+    visuals
+}
+
+/// 同梱の日本語フォント
+const BUNDLED_FONT_NAME: &str = "noto_sans_jp";
+/// OS から読み込んだ日本語フォント
+const SYSTEM_FONT_NAME: &str = "system_cjk";
+
+/// 日本語が表示できるフォント定義（OS のフォントがあれば優先し、同梱フォントを予備にする）
+pub fn font_definitions(system_font: Option<Vec<u8>>) -> egui::FontDefinitions {
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert(
+        BUNDLED_FONT_NAME.to_owned(),
+        std::sync::Arc::new(egui::FontData::from_static(include_bytes!("../../fonts/NotoSansJP-Regular.ttf"))),
+    );
+    let mut preferred = vec![BUNDLED_FONT_NAME.to_owned()];
+    if let Some(bytes) = system_font {
+        fonts
+            .font_data
+            .insert(SYSTEM_FONT_NAME.to_owned(), std::sync::Arc::new(egui::FontData::from_owned(bytes)));
+        preferred.insert(0, SYSTEM_FONT_NAME.to_owned());
+    }
+
+    // Proportional は日本語フォントを最優先にし、Monospace では予備として使う
+    let proportional = fonts.families.entry(egui::FontFamily::Proportional).or_default();
+    for (index, name) in preferred.iter().enumerate() {
+        proportional.insert(index, name.clone());
+    }
+    fonts.families.entry(egui::FontFamily::Monospace).or_default().extend(preferred);
+    fonts
+}
+
+/// 本文の文字サイズに合わせて各文字スタイルを拡大・縮小する
+pub fn scaled_text_styles(body_size: f32) -> std::collections::BTreeMap<egui::TextStyle, egui::FontId> {
+    let mut styles = egui::style::default_text_styles();
+    let scale = body_size / styles[&egui::TextStyle::Body].size;
+    for font_id in styles.values_mut() {
+        font_id.size = (font_id.size * scale).round();
+    }
+    styles
+}
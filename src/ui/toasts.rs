@@ -0,0 +1,59 @@
+use eframe::egui;
+
+use crate::app::App;
+use crate::ui::ToastKind;
+
+/// 通知（トースト）の描画トレイト
+pub trait ToastRenderer {
+    fn render_toasts(&mut self, ctx: &egui::Context);
+}
+
+impl ToastRenderer for App {
+    fn render_toasts(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.toasts.expire(now);
+        // 「画像としてコピー」で撮るフレームには写さない
+        if self.toasts.toasts.is_empty() || self.is_capturing_canvas_image() {
+            return;
+        }
+
+        // キャンバスの右下に新しいものが下になるよう積み重ねる
+        let anchor = if self.canvas.canvas_rect == egui::Rect::NOTHING {
+            ctx.content_rect().right_bottom()
+        } else {
+            self.canvas.canvas_rect.right_bottom()
+        };
+        let mut dismissed = None;
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(anchor - egui::vec2(12.0, 12.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                for toast in &self.toasts.toasts {
+                    egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.5, toast.kind.color()))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("●").color(toast.kind.color()));
+                                ui.label(&toast.message);
+                                if toast.kind == ToastKind::Error && ui.small_button("✖").clicked() {
+                                    dismissed = Some(toast.id);
+                                }
+                            });
+                        });
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(id) = dismissed {
+            self.toasts.dismiss(id);
+        }
+        // 自動で消える通知のために再描画を予約
+        if self.toasts.toasts.iter().any(|toast| toast.kind != ToastKind::Error) {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
+        }
+    }
+}
@@ -2,9 +2,13 @@ use eframe::egui;
 
 use crate::app::App;
 use crate::core::i18n::Texts;
+use crate::core::tree::CanvasBackground;
+use crate::ui::shortcuts::{shortcut_button_label, CAMERA_BOOKMARK_SHORTCUTS, SHORTCUT_ZOOM_RESET};
+use crate::ui::LogLevel;
 
 pub trait ViewMenuRenderer {
     fn render_view_menu(&mut self, ui: &mut egui::Ui);
+    fn render_layout_snapshot_delete_dialog(&mut self, ctx: &egui::Context);
 }
 
 impl ViewMenuRenderer for App {
@@ -17,6 +21,256 @@ impl ViewMenuRenderer for App {
                 self.fit_canvas_to_contents();
                 ui.close();
             }
+            if ui.button(t("zoom_fit_width")).clicked() {
+                self.fit_canvas_to_width();
+                ui.close();
+            }
+            if ui.button(shortcut_button_label(&t("zoom_reset"), &SHORTCUT_ZOOM_RESET)).clicked() {
+                self.set_canvas_zoom(1.0);
+                ui.close();
+            }
+
+            if ui.checkbox(&mut self.ui.show_all_notes, t("show_all_notes")).changed() {
+                self.save_settings();
+            }
+
+            if ui.checkbox(&mut self.ui.show_life_years, t("show_life_years")).changed() {
+                self.save_settings();
+            }
+
+            if ui.checkbox(&mut self.ui.show_detail_panel, t("show_detail_panel")).changed() {
+                self.save_settings();
+            }
+
+            ui.menu_button(t("camera_bookmarks"), |ui| {
+                self.render_camera_bookmarks_menu(ui);
+            });
+
+            if ui.button(t("place_map")).clicked() {
+                self.open_place_map();
+                ui.close();
+            }
+
+            ui.checkbox(&mut self.ui.read_only, t("read_only_mode"))
+                .on_hover_text(t("read_only_mode_hint"));
+
+            ui.separator();
+            ui.add_enabled_ui(!self.ui.read_only, |ui| {
+                ui.menu_button(t("background_image"), |ui| {
+                    self.render_background_menu(ui);
+                });
+                ui.menu_button(t("layout_snapshots"), |ui| {
+                    self.render_layout_snapshots_menu(ui);
+                });
+            });
+        });
+    }
+
+    /// 保存した配置を削除する前の確認
+    fn render_layout_snapshot_delete_dialog(&mut self, ctx: &egui::Context) {
+        let Some(id) = self.ui.layout_snapshot_pending_delete else {
+            return;
+        };
+        let Some(name) = self.tree.layout_snapshots.iter().find(|s| s.id == id).map(|s| s.name.clone()) else {
+            self.ui.layout_snapshot_pending_delete = None;
+            return;
+        };
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        let mut confirm = false;
+        let response = egui::Modal::new(egui::Id::new("layout_snapshot_delete_dialog")).show(ctx, |ui| {
+            ui.heading(t("delete_layout_snapshot_title"));
+            ui.add_space(6.0);
+            ui.label(&name);
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button(t("delete")).clicked() {
+                    confirm = true;
+                }
+                if ui.button(t("cancel")).clicked() {
+                    ui.close();
+                }
+            });
+        });
+
+        if confirm {
+            let label = format!("{}: {name}", t("log_layout_snapshot_deleted"));
+            self.apply_undoable(label.clone(), |tree| tree.remove_layout_snapshot(id));
+            self.log.add(label, LogLevel::Debug);
+        }
+        if confirm || response.should_close() {
+            self.ui.layout_snapshot_pending_delete = None;
+        }
+    }
+}
+impl App {
+    /// 表示位置（パンとズーム）の保存と移動。移動は閲覧モードでもできる
+    fn render_camera_bookmarks_menu(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        ui.add_enabled_ui(!self.ui.read_only, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.ui.camera_bookmark_name)
+                        .hint_text(t("camera_bookmark_name"))
+                        .desired_width(140.0),
+                );
+                let name = self.ui.camera_bookmark_name.trim().to_string();
+                if ui
+                    .add_enabled(!name.is_empty(), egui::Button::new(t("save_camera_bookmark")))
+                    .on_hover_text(t("save_camera_bookmark_hint"))
+                    .clicked()
+                {
+                    self.tree.save_camera_bookmark(name.clone(), self.canvas.zoom, (self.canvas.pan.x, self.canvas.pan.y));
+                    self.ui.camera_bookmark_name.clear();
+                    self.toasts.info(format!("{}: {name}", t("camera_bookmark_saved")));
+                    self.log.add(format!("{}: {name}", t("log_camera_bookmark_saved")), LogLevel::Debug);
+                }
+            });
         });
+
+        if self.tree.camera_bookmarks.is_empty() {
+            ui.weak(t("no_camera_bookmarks"));
+            return;
+        }
+        ui.separator();
+
+        let mut go_to = None;
+        let mut remove = None;
+        for (index, bookmark) in self.tree.camera_bookmarks.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let label = match CAMERA_BOOKMARK_SHORTCUTS.get(index) {
+                    Some(shortcut) => shortcut_button_label(&bookmark.name, shortcut),
+                    None => bookmark.name.clone(),
+                };
+                if ui.button(label).clicked() {
+                    go_to = Some(index);
+                }
+                if ui
+                    .add_enabled(!self.ui.read_only, egui::Button::new("🗑").small())
+                    .on_hover_text(t("delete"))
+                    .clicked()
+                {
+                    remove = Some((bookmark.id, bookmark.name.clone()));
+                }
+            });
+        }
+
+        if let Some(index) = go_to {
+            self.go_to_camera_bookmark(index);
+            ui.close();
+        }
+        if let Some((id, name)) = remove {
+            self.tree.remove_camera_bookmark(id);
+            self.log.add(format!("{}: {name}", t("log_camera_bookmark_deleted")), LogLevel::Debug);
+        }
     }
-}
\ No newline at end of file
+
+    /// 名前付きの配置の保存・切り替え
+    fn render_layout_snapshots_menu(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.ui.layout_snapshot_name)
+                    .hint_text(t("layout_snapshot_name"))
+                    .desired_width(140.0),
+            );
+            let name = self.ui.layout_snapshot_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(t("save_layout_snapshot")))
+                .on_hover_text(t("save_layout_snapshot_hint"))
+                .clicked()
+            {
+                self.tree.save_layout_snapshot(name.clone());
+                self.ui.layout_snapshot_name.clear();
+                self.toasts.info(format!("{}: {name}", t("layout_snapshot_saved")));
+                self.log.add(format!("{}: {name}", t("log_layout_snapshot_saved")), LogLevel::Debug);
+            }
+        });
+
+        if self.tree.layout_snapshots.is_empty() {
+            ui.weak(t("no_layout_snapshots"));
+            return;
+        }
+        ui.separator();
+
+        let mut apply = None;
+        let mut remove = None;
+        for snapshot in &self.tree.layout_snapshots {
+            ui.horizontal(|ui| {
+                if ui.button(&snapshot.name).on_hover_text(t("apply_layout_snapshot")).clicked() {
+                    apply = Some((snapshot.id, snapshot.name.clone()));
+                }
+                if ui
+                    .add_enabled(!self.ui.read_only, egui::Button::new("🗑").small())
+                    .on_hover_text(t("delete"))
+                    .clicked()
+                {
+                    remove = Some(snapshot.id);
+                }
+            });
+        }
+
+        // 切り替える前の位置は元に戻すで取り戻せる
+        if let Some((id, name)) = apply {
+            let label = format!("{}: {name}", t("log_layout_snapshot_applied"));
+            if self.apply_undoable(label, |tree| tree.apply_layout_snapshot(id)).0 {
+                self.toasts.info(format!("{}: {name}", t("layout_snapshot_applied")));
+                self.log.add(format!("{}: {name}", t("log_layout_snapshot_applied")), LogLevel::Debug);
+            }
+            ui.close();
+        }
+        if let Some(id) = remove {
+            self.ui.layout_snapshot_pending_delete = Some(id);
+            ui.close();
+        }
+    }
+
+    fn render_background_menu(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let t = |key: &str| Texts::get(key, lang);
+
+        if ui.button(t("choose_background_image")).clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter(t("file_filter_images"), &["png", "jpg", "jpeg", "bmp", "gif"])
+                .pick_file()
+            {
+                let path = path.display().to_string();
+                match self.tree.background.as_mut() {
+                    Some(background) => background.path = path.clone(),
+                    None => self.tree.background = Some(CanvasBackground::new(path.clone())),
+                }
+                self.toasts.info(t("background_image_set"));
+                self.log.add(format!("{}: {path}", t("log_background_image_set")), LogLevel::Debug);
+            }
+            ui.close();
+        }
+
+        let Some(background) = self.tree.background.as_mut() else {
+            return;
+        };
+
+        ui.label(&background.path);
+        ui.add(egui::Slider::new(&mut background.opacity, 0.0..=1.0).text(t("background_opacity")));
+        ui.add(
+            egui::Slider::new(&mut background.scale, 0.1..=10.0)
+                .logarithmic(true)
+                .text(t("background_scale")),
+        );
+        ui.horizontal(|ui| {
+            ui.label(t("background_position"));
+            ui.add(egui::DragValue::new(&mut background.position.0).prefix("x: "));
+            ui.add(egui::DragValue::new(&mut background.position.1).prefix("y: "));
+        });
+
+        if ui.button(t("remove_background_image")).clicked() {
+            self.tree.background = None;
+            self.toasts.info(t("background_image_removed"));
+            ui.close();
+        }
+    }
+}